@@ -0,0 +1,215 @@
+//! `continuum-bench`: floods a Continuum deployment with order traffic and
+//! reports sequencing throughput, confirmation latency and failure rates.
+//!
+//! A set of throwaway traders is funded from the payer, then each submits
+//! its share of the orders concurrently. With `--execute` the payer also
+//! executes the orders as a relayer; otherwise the bench waits for an
+//! external relayer to fill them. Meant for localnet and devnet.
+
+mod report;
+mod setup;
+mod traffic;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair};
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinSet;
+
+use continuum_cp_swap_client::cp_swap::PoolStateInfo;
+use continuum_cp_swap_client::retry::RetryPolicy;
+use continuum_cp_swap_client::CP_SWAP_PROGRAM_ID;
+
+use crate::report::Report;
+use crate::setup::Side;
+use crate::traffic::{Executor, OrderParams, Outcome};
+
+#[derive(Parser)]
+#[command(name = "continuum-bench", version, about)]
+struct Cli {
+    #[arg(long, short, default_value = "http://127.0.0.1:8899")]
+    url: String,
+
+    /// Payer funding the traders, and executor with --execute
+    #[arg(long, short)]
+    keypair: PathBuf,
+
+    /// CP-Swap pool state of a registered pool
+    #[arg(long)]
+    pool: Pubkey,
+
+    /// Mint the orders sell; the payer must hold enough of it
+    #[arg(long)]
+    input_mint: Pubkey,
+
+    #[arg(long, default_value_t = CP_SWAP_PROGRAM_ID)]
+    cp_swap_program: Pubkey,
+
+    /// Concurrent traders
+    #[arg(long, default_value_t = 8)]
+    traders: usize,
+
+    /// Orders submitted in total
+    #[arg(long, default_value_t = 100)]
+    orders: usize,
+
+    /// Exact input of each order
+    #[arg(long, default_value_t = 1_000)]
+    amount: u64,
+
+    #[arg(long, default_value_t = 0)]
+    min_amount_out: u64,
+
+    /// Submissions per second across all traders [default: as fast as possible]
+    #[arg(long)]
+    rate: Option<f64>,
+
+    /// Lamports given to each trader for fees and rent
+    #[arg(long, default_value_t = 100_000_000)]
+    trader_lamports: u64,
+
+    /// Execute the orders with the payer instead of waiting for a relayer
+    #[arg(long)]
+    execute: bool,
+
+    /// How long to wait for an external relayer to fill the orders; 0 skips
+    /// fill measurement
+    #[arg(long, default_value_t = 60)]
+    fill_timeout_secs: u64,
+}
+
+fn read_keypair(path: &PathBuf) -> Result<Keypair> {
+    read_keypair_file(path).map_err(|err| anyhow!("failed to read keypair {}: {}", path.display(), err))
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let traders = cli.traders.clamp(1, cli.orders.max(1));
+    let rpc = Arc::new(RpcClient::new_with_commitment(cli.url.clone(), CommitmentConfig::confirmed()));
+    let payer = read_keypair(&cli.keypair)?;
+    let policy = RetryPolicy::default();
+
+    let data = rpc
+        .get_account_data(&cli.pool)
+        .await
+        .with_context(|| format!("failed to fetch pool {}", cli.pool))?;
+    let pool = PoolStateInfo::parse(&data).ok_or_else(|| anyhow!("invalid pool {}", cli.pool))?;
+    let side = Side::new(&pool, cli.input_mint)?;
+
+    println!("Funding {} traders...", traders);
+    let per_trader = cli.orders.div_ceil(traders);
+    let mut funded = Vec::with_capacity(traders);
+    for _ in 0..traders {
+        let tokens = cli.amount.saturating_mul(per_trader as u64);
+        funded.push(setup::fund_trader(&rpc, &payer, cli.pool, side, cli.trader_lamports, tokens, &policy).await?);
+    }
+    let funded = Arc::new(funded);
+    if cli.execute {
+        setup::ensure_fee_balance(&rpc, &payer, cli.pool, &policy).await?;
+    }
+
+    let report = Arc::new(Mutex::new(Report {
+        requested: cli.orders,
+        ..Report::default()
+    }));
+    let (executions, executor) = if cli.execute {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let executor = Executor {
+            rpc: rpc.clone(),
+            payer: payer.insecure_clone(),
+            traders: funded.clone(),
+            pool_id: cli.pool,
+            pool,
+            cp_swap_program: cli.cp_swap_program,
+            side,
+            policy: policy.clone(),
+        };
+        (Some(sender), Some(tokio::spawn(executor.run(receiver, report.clone()))))
+    } else {
+        (None, None)
+    };
+
+    println!("Submitting {} orders...", cli.orders);
+    let params = Arc::new(OrderParams {
+        pool_id: cli.pool,
+        input_mint: side.input_mint,
+        amount_in: cli.amount,
+        min_amount_out: cli.min_amount_out,
+        policy: RetryPolicy {
+            max_attempts: 2,
+            ..policy
+        },
+        pacing: cli
+            .rate
+            .filter(|rate| *rate > 0.0)
+            .map(|rate| Mutex::new(tokio::time::interval(Duration::from_secs_f64(1.0 / rate)))),
+    });
+    let (outcomes, mut received) = mpsc::unbounded_channel();
+    let mut workers = JoinSet::new();
+    let mut remaining = cli.orders;
+    for index in 0..traders {
+        let count = per_trader.min(remaining);
+        remaining -= count;
+        workers.spawn(traffic::submit_orders(
+            rpc.clone(),
+            funded.clone(),
+            index,
+            count,
+            params.clone(),
+            outcomes.clone(),
+        ));
+    }
+    drop(outcomes);
+
+    let started = Instant::now();
+    let mut submitted = Vec::new();
+    while let Some(outcome) = received.recv().await {
+        let mut report = report.lock().await;
+        match outcome {
+            Outcome::Submitted {
+                submitted: order,
+                latency,
+                resubmits,
+            } => {
+                report.submitted += 1;
+                report.resubmitted += resubmits;
+                report.submit_latency.record(latency);
+                report.submit_window = started.elapsed();
+                report.first_sequence = Some(report.first_sequence.map_or(order.sequence, |s| s.min(order.sequence)));
+                report.last_sequence = Some(report.last_sequence.map_or(order.sequence, |s| s.max(order.sequence)));
+                match &executions {
+                    Some(executions) => {
+                        let _ = executions.send(order);
+                    }
+                    None => submitted.push(order),
+                }
+            }
+            Outcome::Failed { resubmits } => {
+                report.submit_failed += 1;
+                report.resubmitted += resubmits;
+            }
+        }
+    }
+    while workers.join_next().await.is_some() {}
+
+    drop(executions);
+    if let Some(executor) = executor {
+        println!("Waiting for executions...");
+        executor.await?;
+    } else if cli.fill_timeout_secs > 0 {
+        println!("Waiting for fills...");
+        let mut report = report.lock().await;
+        traffic::await_fills(&rpc, submitted, Duration::from_secs(cli.fill_timeout_secs), &mut report).await;
+    }
+
+    report.lock().await.print();
+    Ok(())
+}
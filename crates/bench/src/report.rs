@@ -0,0 +1,119 @@
+//! Collected measurements and the final report.
+
+use std::time::Duration;
+
+/// Latency samples of one kind of operation
+#[derive(Default)]
+pub struct Latencies {
+    samples: Vec<Duration>,
+}
+
+impl Latencies {
+    pub fn record(&mut self, latency: Duration) {
+        self.samples.push(latency);
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    fn percentile(sorted: &[Duration], percentile: usize) -> Duration {
+        let index = (sorted.len() * percentile).div_ceil(100).saturating_sub(1);
+        sorted[index.min(sorted.len() - 1)]
+    }
+
+    fn print(&self, name: &str) {
+        if self.samples.is_empty() {
+            println!("  {:<10} no samples", name);
+            return;
+        }
+        let mut sorted = self.samples.clone();
+        sorted.sort();
+        let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        println!(
+            "  {:<10} p50 {:>8.0} ms  p90 {:>8.0} ms  p99 {:>8.0} ms  max {:>8.0} ms",
+            name,
+            ms(Self::percentile(&sorted, 50)),
+            ms(Self::percentile(&sorted, 90)),
+            ms(Self::percentile(&sorted, 99)),
+            ms(*sorted.last().unwrap()),
+        );
+    }
+}
+
+#[derive(Default)]
+pub struct Report {
+    pub requested: usize,
+    pub submitted: usize,
+    pub submit_failed: usize,
+    /// Submissions rebuilt because another order took their sequence first
+    pub resubmitted: usize,
+    pub executed: usize,
+    pub execute_failed: usize,
+    /// Orders still pending when the fill timeout ran out
+    pub unfilled: usize,
+    /// Time from the first submission to the last confirmed one
+    pub submit_window: Duration,
+    pub first_sequence: Option<u64>,
+    pub last_sequence: Option<u64>,
+    /// Send to confirmation of each submission
+    pub submit_latency: Latencies,
+    /// Send to confirmation of each execution
+    pub execute_latency: Latencies,
+    /// Submission confirmed to order executed
+    pub fill_latency: Latencies,
+}
+
+impl Report {
+    fn rate(count: usize, total: usize) -> f64 {
+        if total == 0 {
+            0.0
+        } else {
+            count as f64 * 100.0 / total as f64
+        }
+    }
+
+    pub fn print(&self) {
+        let window = self.submit_window.as_secs_f64();
+        let throughput = if window > 0.0 {
+            self.submitted as f64 / window
+        } else {
+            0.0
+        };
+
+        println!();
+        println!("Orders requested:  {}", self.requested);
+        println!(
+            "Submitted:         {} ({:.1}% failed, {} resubmitted)",
+            self.submitted,
+            Self::rate(self.submit_failed, self.requested),
+            self.resubmitted
+        );
+        if let (Some(first), Some(last)) = (self.first_sequence, self.last_sequence) {
+            println!("Sequences:         {}..={}", first, last);
+        }
+        println!("Throughput:        {:.2} orders/s over {:.1} s", throughput, window);
+        if self.executed + self.execute_failed > 0 {
+            println!(
+                "Executed:          {} ({:.1}% failed)",
+                self.executed,
+                Self::rate(self.execute_failed, self.executed + self.execute_failed)
+            );
+        }
+        if self.fill_latency.len() + self.unfilled > 0 {
+            println!("Filled:            {} ({} unfilled)", self.fill_latency.len(), self.unfilled);
+        }
+        println!("Latency:");
+        self.submit_latency.print("submit");
+        if !self.execute_latency.is_empty() {
+            self.execute_latency.print("execute");
+        }
+        if !self.fill_latency.is_empty() {
+            self.fill_latency.print("fill");
+        }
+    }
+}
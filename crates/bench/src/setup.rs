@@ -0,0 +1,105 @@
+//! Creating and funding the traders that generate the load.
+
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use anchor_spl::token::{self, spl_token};
+use anyhow::{anyhow, bail, Context, Result};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_system_interface::instruction as system_instruction;
+
+use continuum_cp_swap_client::cp_swap::PoolStateInfo;
+use continuum_cp_swap_client::retry::{send_with_retry, RetryPolicy};
+use continuum_cp_swap_client::transaction::TransactionBuilder;
+use continuum_cp_swap_client::{instructions, pda};
+
+/// Which side of the pool the load sells
+#[derive(Debug, Clone, Copy)]
+pub struct Side {
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub output_program: Pubkey,
+}
+
+impl Side {
+    pub fn new(pool: &PoolStateInfo, input_mint: Pubkey) -> Result<Self> {
+        let (input_program, output_mint, output_program) = if input_mint == pool.token_0_mint {
+            (pool.token_0_program, pool.token_1_mint, pool.token_1_program)
+        } else if input_mint == pool.token_1_mint {
+            (pool.token_1_program, pool.token_0_mint, pool.token_0_program)
+        } else {
+            bail!("{} is not a mint of the pool", input_mint);
+        };
+        if input_program != token::ID {
+            bail!("only SPL Token input mints are supported");
+        }
+        Ok(Self {
+            input_mint,
+            output_mint,
+            output_program,
+        })
+    }
+}
+
+/// A generated keypair with its token accounts on both sides of the pool
+pub struct Trader {
+    pub signer: Keypair,
+    pub source: Pubkey,
+    pub destination: Pubkey,
+}
+
+/// Create a trader funded with `lamports` of SOL and `tokens` of the input
+/// mint from the payer's associated account, approving the pool authority
+/// for all of it
+pub async fn fund_trader(
+    rpc: &RpcClient,
+    payer: &Keypair,
+    pool_id: Pubkey,
+    side: Side,
+    lamports: u64,
+    tokens: u64,
+    policy: &RetryPolicy,
+) -> Result<Trader> {
+    let funder = payer.pubkey();
+    let funder_source = get_associated_token_address_with_program_id(&funder, &side.input_mint, &token::ID);
+    let pool_authority = pda::find_pool_authority(&pool_id).0;
+
+    let signer = Keypair::new();
+    let user = signer.pubkey();
+    let source = get_associated_token_address_with_program_id(&user, &side.input_mint, &token::ID);
+    let destination = get_associated_token_address_with_program_id(&user, &side.output_mint, &side.output_program);
+
+    let builder = TransactionBuilder::new(funder).instructions([
+        system_instruction::transfer(&funder, &user, lamports),
+        create_associated_token_account_idempotent(&funder, &user, &side.input_mint, &token::ID),
+        create_associated_token_account_idempotent(&funder, &user, &side.output_mint, &side.output_program),
+        spl_token::instruction::transfer(&token::ID, &funder_source, &source, &funder, &[], tokens)?,
+        spl_token::instruction::approve(&token::ID, &source, &pool_authority, &user, &[], tokens)?,
+    ]);
+    send_with_retry(rpc, builder, &[payer, &signer], policy)
+        .await
+        .map_err(|err| anyhow!(err))
+        .with_context(|| format!("failed to fund trader {}", user))?;
+
+    Ok(Trader {
+        signer,
+        source,
+        destination,
+    })
+}
+
+/// Open the payer's relayer fee balance on the pool if it has none, so it
+/// can execute
+pub async fn ensure_fee_balance(rpc: &RpcClient, payer: &Keypair, pool_id: Pubkey, policy: &RetryPolicy) -> Result<()> {
+    let relayer = payer.pubkey();
+    if rpc.get_account(&pda::find_relayer_fee_balance(&pool_id, &relayer).0).await.is_ok() {
+        return Ok(());
+    }
+    let builder = TransactionBuilder::new(relayer).instruction(instructions::open_relayer_fee_balance(relayer, pool_id));
+    send_with_retry(rpc, builder, &[payer], policy)
+        .await
+        .map_err(|err| anyhow!(err))
+        .context("failed to open the relayer fee balance")?;
+    Ok(())
+}
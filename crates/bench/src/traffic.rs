@@ -0,0 +1,262 @@
+//! The load itself: traders submitting orders concurrently, and optionally
+//! the payer executing them in sequence order as they land.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::Interval;
+
+use continuum_cp_swap_client::accounts::{decode, OrderState, OrderStatus, PoolFifoState};
+use continuum_cp_swap_client::cp_swap::PoolStateInfo;
+use continuum_cp_swap_client::instructions::{self, ExecuteOrderParams, OrderOptions};
+use continuum_cp_swap_client::retry::{send_with_retry, RetryPolicy};
+use continuum_cp_swap_client::transaction::TransactionBuilder;
+use continuum_cp_swap_client::pda;
+
+use crate::report::Report;
+use crate::setup::{Side, Trader};
+
+/// Builds per order. The order account is derived from the FIFO sequence,
+/// so a submission fails if another order takes the sequence first.
+const SUBMIT_ATTEMPTS: usize = 3;
+
+/// How often pending orders are polled while waiting for fills
+const FILL_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// What each order sends
+pub struct OrderParams {
+    pub pool_id: Pubkey,
+    pub input_mint: Pubkey,
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+    pub policy: RetryPolicy,
+    /// Shared pacing of submissions across traders
+    pub pacing: Option<Mutex<Interval>>,
+}
+
+/// A confirmed submission
+#[derive(Clone, Copy)]
+pub struct Submitted {
+    pub sequence: u64,
+    pub order: Pubkey,
+    pub trader: usize,
+    pub confirmed_at: Instant,
+}
+
+pub enum Outcome {
+    Submitted {
+        submitted: Submitted,
+        latency: Duration,
+        resubmits: usize,
+    },
+    Failed {
+        resubmits: usize,
+    },
+}
+
+async fn submit_one(rpc: &RpcClient, trader: &Trader, params: &OrderParams) -> Result<(u64, Pubkey)> {
+    let user = trader.signer.pubkey();
+    let data = rpc.get_account_data(&pda::find_pool_fifo(&params.pool_id).0).await?;
+    let fifo: PoolFifoState = decode(&data)?;
+    let builder = TransactionBuilder::new(user).instruction(instructions::submit_order(
+        user,
+        params.pool_id,
+        params.input_mint,
+        fifo.current_sequence,
+        params.amount_in,
+        params.min_amount_out,
+        true,
+        OrderOptions {
+            from_wallet: true,
+            ..OrderOptions::default()
+        },
+    ));
+    send_with_retry(rpc, builder, &[&trader.signer], &params.policy)
+        .await
+        .map_err(|err| anyhow!(err))?;
+    let sequence = fifo.current_sequence + 1;
+    Ok((sequence, pda::find_order(&params.pool_id, sequence).0))
+}
+
+/// Submit `count` orders from one trader, one after the other
+pub async fn submit_orders(
+    rpc: Arc<RpcClient>,
+    traders: Arc<Vec<Trader>>,
+    index: usize,
+    count: usize,
+    params: Arc<OrderParams>,
+    outcomes: mpsc::UnboundedSender<Outcome>,
+) {
+    let trader = &traders[index];
+    for _ in 0..count {
+        if let Some(pacing) = &params.pacing {
+            pacing.lock().await.tick().await;
+        }
+
+        let mut resubmits = 0;
+        let outcome = loop {
+            let started = Instant::now();
+            match submit_one(&rpc, trader, &params).await {
+                Ok((sequence, order)) => {
+                    break Outcome::Submitted {
+                        submitted: Submitted {
+                            sequence,
+                            order,
+                            trader: index,
+                            confirmed_at: Instant::now(),
+                        },
+                        latency: started.elapsed(),
+                        resubmits,
+                    }
+                }
+                Err(err) if resubmits + 1 < SUBMIT_ATTEMPTS => {
+                    eprintln!("submission failed, rebuilding: {:#}", err);
+                    resubmits += 1;
+                }
+                Err(err) => {
+                    eprintln!("submission failed: {:#}", err);
+                    break Outcome::Failed { resubmits };
+                }
+            }
+        };
+        if outcomes.send(outcome).is_err() {
+            return;
+        }
+    }
+}
+
+/// Executes submitted orders as the payer, lowest sequence first among
+/// those received so far
+pub struct Executor {
+    pub rpc: Arc<RpcClient>,
+    pub payer: Keypair,
+    pub traders: Arc<Vec<Trader>>,
+    pub pool_id: Pubkey,
+    pub pool: PoolStateInfo,
+    pub cp_swap_program: Pubkey,
+    pub side: Side,
+    pub policy: RetryPolicy,
+}
+
+impl Executor {
+    async fn execute(&self, submitted: &Submitted) -> Result<()> {
+        let trader = &self.traders[submitted.trader];
+        let user = trader.signer.pubkey();
+        let executor = self.payer.pubkey();
+        let swap = self.pool.swap_accounts(
+            &self.cp_swap_program,
+            self.pool_id,
+            user,
+            self.side.input_mint,
+            trader.source,
+            trader.destination,
+        );
+        let ix = instructions::execute_order(ExecuteOrderParams {
+            executor,
+            order: submitted.order,
+            user,
+            sequence: submitted.sequence,
+            pool_id: self.pool_id,
+            user_source: trader.source,
+            user_destination: trader.destination,
+            source_mint: self.side.input_mint,
+            cp_swap_program: self.cp_swap_program,
+            with_receipt: false,
+            with_relayer_info: false,
+            with_memo: false,
+            callback_program: None,
+            post_fill: None,
+            parent_order: None,
+            oracle: None,
+            shared_escrow: None,
+            delegated_input: false,
+            permit: None,
+            escrow_refund: None,
+            max_fill_amount: None,
+            remaining_accounts: swap.execute_remaining_accounts(),
+        });
+        send_with_retry(&self.rpc, TransactionBuilder::execution(executor, ix), &[&self.payer], &self.policy)
+            .await
+            .map_err(|err| anyhow!(err))?;
+        Ok(())
+    }
+
+    /// Execute everything received until the sender is dropped
+    pub async fn run(self, mut submissions: mpsc::UnboundedReceiver<Submitted>, report: Arc<Mutex<Report>>) {
+        let mut pending = BTreeMap::new();
+        let mut open = true;
+        while open || !pending.is_empty() {
+            // Take everything queued before picking the lowest sequence
+            if pending.is_empty() {
+                match submissions.recv().await {
+                    Some(submitted) => {
+                        pending.insert(submitted.sequence, submitted);
+                    }
+                    None => open = false,
+                }
+            }
+            while let Ok(submitted) = submissions.try_recv() {
+                pending.insert(submitted.sequence, submitted);
+            }
+            let Some((_, submitted)) = pending.pop_first() else {
+                continue;
+            };
+
+            let started = Instant::now();
+            let result = self.execute(&submitted).await;
+            let mut report = report.lock().await;
+            match result {
+                Ok(()) => {
+                    report.executed += 1;
+                    report.execute_latency.record(started.elapsed());
+                    report.fill_latency.record(submitted.confirmed_at.elapsed());
+                }
+                Err(err) => {
+                    eprintln!("execution of {} failed: {:#}", submitted.sequence, err);
+                    report.execute_failed += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Poll submitted orders until each is no longer pending or `timeout`
+/// passes, recording when each was seen filled. Latencies are accurate to
+/// the poll interval.
+pub async fn await_fills(rpc: &RpcClient, mut waiting: Vec<Submitted>, timeout: Duration, report: &mut Report) {
+    let deadline = Instant::now() + timeout;
+    while !waiting.is_empty() && Instant::now() < deadline {
+        let mut still_waiting = Vec::new();
+        for chunk in waiting.chunks(100) {
+            let addresses: Vec<Pubkey> = chunk.iter().map(|submitted| submitted.order).collect();
+            let accounts = match rpc.get_multiple_accounts(&addresses).await {
+                Ok(accounts) => accounts,
+                Err(err) => {
+                    eprintln!("failed to poll orders: {}", err);
+                    vec![None; addresses.len()]
+                }
+            };
+            for (submitted, account) in chunk.iter().zip(accounts) {
+                let status = account
+                    .and_then(|account| decode::<OrderState>(&account.data).ok())
+                    .map(|order| order.status);
+                match status {
+                    Some(OrderStatus::Executed) => report.fill_latency.record(submitted.confirmed_at.elapsed()),
+                    Some(OrderStatus::Pending) | None => still_waiting.push(*submitted),
+                    Some(_) => report.execute_failed += 1,
+                }
+            }
+        }
+        waiting = still_waiting;
+        if !waiting.is_empty() {
+            tokio::time::sleep(FILL_POLL_INTERVAL).await;
+        }
+    }
+    report.unfilled = waiting.len();
+}
@@ -0,0 +1,478 @@
+//! Administrator commands. All of them must be signed by `FifoState::admin`.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use anchor_spl::associated_token::get_associated_token_address;
+use clap::Subcommand;
+use solana_sdk::hash::hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use continuum_cp_swap_client::accounts::{decode, CpSwapPoolRegistry, ExecutionReceipt, PoolFeeLedger};
+use continuum_cp_swap_client::cp_swap::{find_amm_config, PoolAddresses, PoolStateInfo};
+use continuum_cp_swap_client::transaction::decode_lookup_table;
+use continuum_cp_swap_client::{instructions, lookup_table, pda};
+
+use crate::{read_keypair, Session};
+
+#[derive(Subcommand)]
+pub enum AdminCommand {
+    /// Create the global FIFO state with the signer as admin
+    Initialize,
+
+    /// Create a CP-Swap pool owned by Continuum, register it and set up its fee ledger
+    RegisterPool {
+        #[arg(long)]
+        mint_a: Pubkey,
+        #[arg(long)]
+        mint_b: Pubkey,
+        /// AMM config address, overrides --amm-config-index
+        #[arg(long)]
+        amm_config: Option<Pubkey>,
+        #[arg(long, default_value_t = 0)]
+        amm_config_index: u16,
+        /// Initial deposit of the lower-sorted mint
+        #[arg(long)]
+        init_amount_0: u64,
+        /// Initial deposit of the higher-sorted mint
+        #[arg(long)]
+        init_amount_1: u64,
+        #[arg(long, default_value_t = 0)]
+        open_time: u64,
+        /// Token account receiving the CP-Swap pool creation fee
+        #[arg(long)]
+        create_pool_fee: Pubkey,
+        /// Keypair of the risk authority, required once one is set
+        #[arg(long)]
+        risk_keypair: Option<PathBuf>,
+    },
+
+    /// Create the FIFO queue of a pool registered before pools had their own
+    PoolFifo {
+        #[arg(long)]
+        pool: Pubkey,
+    },
+
+    /// Grow the FIFO state of the first deployment to the current layout
+    MigrateFifoState,
+
+    /// Grow a pool registry of the first deployment to the current layout,
+    /// once the FIFO state has been
+    MigratePool {
+        #[arg(long)]
+        pool: Pubkey,
+    },
+
+    /// Create a pool's address lookup table, or add the execution accounts
+    /// its table is missing
+    PoolLookupTable {
+        #[arg(long)]
+        pool: Pubkey,
+    },
+
+    /// Set the key that must co-sign pool registrations
+    SetRiskAuthority {
+        risk_authority: Pubkey,
+        /// Keypair of the risk authority being replaced, if one is set
+        #[arg(long)]
+        current_risk_keypair: Option<PathBuf>,
+    },
+
+    /// Set the CP-Swap program executions and pool registrations CPI into
+    SetCpSwapProgram {
+        cp_swap_program: Pubkey,
+        /// Keypair of the risk authority, required once one is set
+        #[arg(long)]
+        risk_keypair: Option<PathBuf>,
+    },
+
+    /// Register an off-chain relayer
+    AddRelayer { relayer: Pubkey },
+
+    /// Deregister a relayer, returning its account rent to the admin
+    RemoveRelayer { relayer: Pubkey },
+
+    /// Stop order submission and immediate swaps
+    Pause {
+        /// Pause only this pool [default: every pool]
+        #[arg(long)]
+        pool: Option<Pubkey>,
+        /// Why, recorded in the pause event
+        #[arg(long)]
+        reason: Option<String>,
+    },
+
+    /// Resume order submission and immediate swaps
+    Unpause {
+        /// Resume only this pool [default: the global pause]
+        #[arg(long)]
+        pool: Option<Pubkey>,
+    },
+
+    /// Set the fees charged on executed orders
+    SetFees {
+        #[arg(long)]
+        protocol_bps: u16,
+        #[arg(long)]
+        relayer_bps: u16,
+    },
+
+    /// Cap how many immediate swaps each registered relayer may make per window
+    SetSwapRateLimit {
+        /// 0 lifts the cap
+        #[arg(long)]
+        max_swaps: u32,
+        #[arg(long, default_value_t = 60)]
+        window_secs: u32,
+    },
+
+    /// Only let registered relayers execute queued orders
+    RestrictExecution,
+
+    /// Let any signer execute queued orders again
+    OpenExecution,
+
+    /// Put a relayer on an execution quota, or change its quota
+    RelayerQuota {
+        relayer: Pubkey,
+        /// Executions per period [default: no limit]
+        #[arg(long, default_value_t = 0)]
+        max_executions: u32,
+        /// Volume per period, in each pool's token 0 [default: no limit]
+        #[arg(long, default_value_t = 0)]
+        max_volume: u64,
+        /// Period length in seconds
+        #[arg(long, default_value_t = 24 * 60 * 60)]
+        period: i64,
+    },
+
+    /// Reset a relayer's quota counters once its period has ended
+    ResetRelayerQuota { relayer: Pubkey },
+
+    /// Set the share of protocol fees set aside for the insurance fund
+    SetInsuranceShare { bps: u16 },
+
+    /// Show a pool's insurance fund
+    InsuranceStatus {
+        #[arg(long)]
+        pool: Pubkey,
+    },
+
+    /// Pay a harmed user out of a pool's insurance fund
+    PayoutInsurance {
+        #[arg(long)]
+        pool: Pubkey,
+        #[arg(long)]
+        mint: Pubkey,
+        /// Owner of the token account paid
+        #[arg(long)]
+        user: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        /// Keypair of the risk authority, if one is set
+        #[arg(long)]
+        risk_keypair: Option<PathBuf>,
+    },
+
+    /// Compensate the owner of a mishandled order out of its pool's insurance fund
+    CompensateOrder {
+        #[arg(long)]
+        pool: Pubkey,
+        /// Order sequence, whose execution receipt identifies it
+        #[arg(long)]
+        sequence: u64,
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        /// Finding behind the compensation; its SHA-256 hash is recorded
+        #[arg(long)]
+        reason: String,
+        /// Keypair of the risk authority, if one is set
+        #[arg(long)]
+        risk_keypair: Option<PathBuf>,
+    },
+
+    /// Withdraw a pool's protocol fees to the treasury
+    WithdrawFees {
+        #[arg(long)]
+        pool: Pubkey,
+        /// Treasury account for token 0 [default: admin's associated token account]
+        #[arg(long)]
+        treasury_0: Option<Pubkey>,
+        /// Treasury account for token 1 [default: admin's associated token account]
+        #[arg(long)]
+        treasury_1: Option<Pubkey>,
+    },
+}
+
+pub fn run(session: &Session, command: AdminCommand) -> Result<()> {
+    let admin = session.signer.pubkey();
+
+    match command {
+        AdminCommand::Initialize => {
+            session.send(vec![instructions::initialize(admin)])?;
+            println!("FIFO state: {}", pda::find_fifo_state().0);
+        }
+
+        AdminCommand::RegisterPool {
+            mint_a,
+            mint_b,
+            amm_config,
+            amm_config_index,
+            init_amount_0,
+            init_amount_1,
+            open_time,
+            create_pool_fee,
+            risk_keypair,
+        } => {
+            let risk_signer = risk_keypair.as_deref().map(read_keypair).transpose()?;
+            let cp_swap_program = session.settings.cp_swap_program;
+            let amm_config =
+                amm_config.unwrap_or_else(|| find_amm_config(&cp_swap_program, amm_config_index));
+            let pool = PoolAddresses::derive(&cp_swap_program, &amm_config, mint_a, mint_b);
+
+            let co_signers: Vec<&Keypair> = risk_signer.iter().collect();
+            session.send_with(vec![instructions::initialize_cp_swap_pool(
+                admin,
+                risk_signer.as_ref().map(|signer| signer.pubkey()),
+                pool.pool_state,
+                cp_swap_program,
+                init_amount_0,
+                init_amount_1,
+                open_time,
+                pool.initialize_accounts(admin, amm_config, create_pool_fee),
+            )], &co_signers)?;
+            session.send(vec![
+                instructions::initialize_fee_ledger(admin, pool.pool_state, pool.token_0_mint, pool.token_1_mint),
+                instructions::initialize_pool_fifo(admin, pool.pool_state),
+            ])?;
+            println!("Pool: {}", pool.pool_state);
+            println!("Registry: {}", pda::find_pool_registry(&pool.pool_state).0);
+        }
+
+        AdminCommand::PoolFifo { pool } => {
+            session.send(vec![instructions::initialize_pool_fifo(admin, pool)])?;
+            println!("Pool FIFO: {}", pda::find_pool_fifo(&pool).0);
+        }
+
+        AdminCommand::MigrateFifoState => {
+            session.send(vec![instructions::migrate_fifo_state(admin)])?;
+        }
+
+        AdminCommand::MigratePool { pool } => {
+            session.send(vec![instructions::migrate_pool_registry(admin, pool)])?;
+            println!("Registry: {}", pda::find_pool_registry(&pool).0);
+        }
+
+        AdminCommand::PoolLookupTable { pool } => {
+            let data = session.rpc.get_account_data(&pool).context("failed to fetch pool")?;
+            let state = PoolStateInfo::parse(&data).context("invalid pool")?;
+            let data = session
+                .rpc
+                .get_account_data(&pda::find_pool_registry(&pool).0)
+                .context("failed to fetch pool registry")?;
+            let registry: CpSwapPoolRegistry = decode(&data).context("invalid pool registry")?;
+
+            let table = match registry.lookup_table() {
+                Some(table) => table,
+                None => {
+                    let slot = session.rpc.get_slot().context("failed to fetch slot")?;
+                    let (create, table) = lookup_table::create_pool_lookup_table(admin, admin, slot);
+                    session.send(vec![create, instructions::set_pool_lookup_table(admin, pool, table)])?;
+                    table
+                }
+            };
+
+            let data = session.rpc.get_account_data(&table).context("failed to fetch lookup table")?;
+            let existing = decode_lookup_table(table, &data)?.addresses;
+            let addresses = lookup_table::pool_addresses(pool, session.settings.cp_swap_program, &state);
+            let extensions = lookup_table::extend_pool_lookup_table(table, admin, admin, &existing, &addresses);
+            let added = addresses.iter().filter(|address| !existing.contains(address)).count();
+            for ix in extensions {
+                session.send(vec![ix])?;
+            }
+            println!("Lookup table: {} ({} addresses added)", table, added);
+        }
+
+        AdminCommand::SetRiskAuthority {
+            risk_authority,
+            current_risk_keypair,
+        } => {
+            let current = current_risk_keypair.as_deref().map(read_keypair).transpose()?;
+            let co_signers: Vec<&Keypair> = current.iter().collect();
+            session.send_with(
+                vec![instructions::set_risk_authority(
+                    admin,
+                    current.as_ref().map(|signer| signer.pubkey()),
+                    risk_authority,
+                )],
+                &co_signers,
+            )?;
+        }
+
+        AdminCommand::SetCpSwapProgram {
+            cp_swap_program,
+            risk_keypair,
+        } => {
+            let risk = risk_keypair.as_deref().map(read_keypair).transpose()?;
+            let co_signers: Vec<&Keypair> = risk.iter().collect();
+            session.send_with(
+                vec![instructions::set_cp_swap_program(
+                    admin,
+                    risk.as_ref().map(|signer| signer.pubkey()),
+                    cp_swap_program,
+                )],
+                &co_signers,
+            )?;
+        }
+
+        AdminCommand::AddRelayer { relayer } => {
+            session.send(vec![instructions::add_relayer(admin, relayer)])?;
+        }
+
+        AdminCommand::RemoveRelayer { relayer } => {
+            session.send(vec![instructions::remove_relayer(admin, relayer)])?;
+        }
+
+        AdminCommand::Pause { pool, reason } => {
+            session.send(vec![instructions::set_pause(admin, pool, true, reason)])?;
+        }
+
+        AdminCommand::Unpause { pool } => {
+            session.send(vec![instructions::set_pause(admin, pool, false, None)])?;
+        }
+
+        AdminCommand::SetFees {
+            protocol_bps,
+            relayer_bps,
+        } => {
+            session.send(vec![instructions::set_fee_config(admin, protocol_bps, relayer_bps)])?;
+        }
+
+        AdminCommand::SetSwapRateLimit { max_swaps, window_secs } => {
+            session.send(vec![instructions::set_swap_rate_limit(admin, max_swaps, window_secs)])?;
+        }
+
+        AdminCommand::RestrictExecution => {
+            session.send(vec![instructions::set_permissionless_execution(admin, false)])?;
+        }
+
+        AdminCommand::OpenExecution => {
+            session.send(vec![instructions::set_permissionless_execution(admin, true)])?;
+        }
+
+        AdminCommand::RelayerQuota {
+            relayer,
+            max_executions,
+            max_volume,
+            period,
+        } => {
+            let relayer_quota = pda::find_relayer_quota(&relayer).0;
+            let exists = session.rpc.get_account(&relayer_quota).is_ok();
+            let ix = if exists {
+                instructions::set_relayer_quota(admin, relayer, max_executions, max_volume, period)
+            } else {
+                instructions::initialize_relayer_quota(admin, relayer, max_executions, max_volume, period)
+            };
+            session.send(vec![ix])?;
+            println!("Relayer quota: {}", relayer_quota);
+        }
+
+        AdminCommand::ResetRelayerQuota { relayer } => {
+            session.send(vec![instructions::reset_relayer_quota(relayer)])?;
+        }
+
+        AdminCommand::SetInsuranceShare { bps } => {
+            session.send(vec![instructions::set_insurance_share(admin, bps)])?;
+        }
+
+        AdminCommand::InsuranceStatus { pool } => {
+            let fee_ledger = fetch_fee_ledger(session, &pool)?;
+            println!("Token 0 {}: {} available, {} paid out", fee_ledger.token_0, fee_ledger.insurance_0.outstanding(), fee_ledger.insurance_0.paid_out);
+            println!("Token 1 {}: {} available, {} paid out", fee_ledger.token_1, fee_ledger.insurance_1.outstanding(), fee_ledger.insurance_1.paid_out);
+        }
+
+        AdminCommand::PayoutInsurance {
+            pool,
+            mint,
+            user,
+            amount,
+            risk_keypair,
+        } => {
+            let risk = risk_keypair.as_deref().map(read_keypair).transpose()?;
+            let co_signers: Vec<&Keypair> = risk.iter().collect();
+            session.send_with(
+                vec![instructions::payout_insurance(
+                    admin,
+                    risk.as_ref().map(|signer| signer.pubkey()),
+                    pool,
+                    mint,
+                    get_associated_token_address(&user, &mint),
+                    amount,
+                )],
+                &co_signers,
+            )?;
+        }
+
+        AdminCommand::CompensateOrder {
+            pool,
+            sequence,
+            mint,
+            amount,
+            reason,
+            risk_keypair,
+        } => {
+            let receipt_address = pda::find_receipt(&pool, sequence).0;
+            let data = session
+                .rpc
+                .get_account_data(&receipt_address)
+                .context("failed to fetch execution receipt")?;
+            let receipt: ExecutionReceipt = decode(&data).context("invalid execution receipt")?;
+            let risk = risk_keypair.as_deref().map(read_keypair).transpose()?;
+            let co_signers: Vec<&Keypair> = risk.iter().collect();
+            session.send_with(
+                vec![instructions::compensate_order(
+                    admin,
+                    risk.as_ref().map(|signer| signer.pubkey()),
+                    pool,
+                    receipt_address,
+                    mint,
+                    get_associated_token_address(&receipt.user, &mint),
+                    amount,
+                    hash(reason.as_bytes()).to_bytes(),
+                )],
+                &co_signers,
+            )?;
+            println!("Compensation: {}", pda::find_compensation(&receipt_address).0);
+        }
+
+        AdminCommand::WithdrawFees {
+            pool,
+            treasury_0,
+            treasury_1,
+        } => {
+            let fee_ledger = fetch_fee_ledger(session, &pool)?;
+
+            session.send(vec![instructions::withdraw_protocol_fees(
+                admin,
+                pool,
+                fee_ledger.token_0,
+                fee_ledger.token_1,
+                treasury_0.unwrap_or_else(|| get_associated_token_address(&admin, &fee_ledger.token_0)),
+                treasury_1.unwrap_or_else(|| get_associated_token_address(&admin, &fee_ledger.token_1)),
+            )])?;
+        }
+    }
+
+    Ok(())
+}
+
+fn fetch_fee_ledger(session: &Session, pool: &Pubkey) -> Result<PoolFeeLedger> {
+    let data = session
+        .rpc
+        .get_account_data(&pda::find_fee_ledger(pool).0)
+        .context("failed to fetch fee ledger")?;
+    decode(&data).context("invalid fee ledger")
+}
@@ -0,0 +1,132 @@
+//! Profile file: named sets of cluster and keypair settings.
+//!
+//! ```toml
+//! default_profile = "devnet"
+//!
+//! [profiles.devnet]
+//! rpc_url = "https://api.devnet.solana.com"
+//! keypair = "~/.config/solana/id.json"
+//! commitment = "confirmed"
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair};
+
+use continuum_cp_swap_client::{cluster, CP_SWAP_PROGRAM_ID};
+
+/// Location of the profile file when `--config` is not given
+pub fn default_config_path() -> PathBuf {
+    expand_home("~/.config/continuum/cli.toml")
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ConfigFile {
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Profile {
+    pub rpc_url: String,
+    pub keypair: String,
+    #[serde(default)]
+    pub commitment: Option<String>,
+    /// CP-Swap program the deployment's pools live on
+    #[serde(default)]
+    pub cp_swap_program: Option<String>,
+}
+
+impl ConfigFile {
+    /// Read the profile file. A missing file at the default location is
+    /// treated as empty so that `--url`/`--keypair` alone suffice.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let (path, explicit) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => (default_config_path(), false),
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => toml::from_str(&contents)
+                .with_context(|| format!("invalid config file {}", path.display())),
+            Err(err) if !explicit && err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err).with_context(|| format!("failed to read {}", path.display())),
+        }
+    }
+
+    pub fn profile(&self, name: Option<&str>) -> Result<Option<Profile>> {
+        match name.or(self.default_profile.as_deref()) {
+            Some(name) => self
+                .profiles
+                .get(name)
+                .cloned()
+                .map(Some)
+                .ok_or_else(|| anyhow!("profile `{}` not found", name)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Settings resolved from the profile and command line overrides
+pub struct Settings {
+    pub rpc_url: String,
+    pub keypair_path: PathBuf,
+    pub commitment: CommitmentConfig,
+    pub cp_swap_program: Pubkey,
+}
+
+impl Settings {
+    pub fn resolve(
+        profile: Option<Profile>,
+        url: Option<String>,
+        keypair: Option<String>,
+    ) -> Result<Self> {
+        let rpc_url = url
+            .or_else(|| profile.as_ref().map(|p| p.rpc_url.clone()))
+            .unwrap_or_else(|| cluster::DEFAULT_RPC_URL.to_string());
+        let keypair = keypair
+            .or_else(|| profile.as_ref().map(|p| p.keypair.clone()))
+            .unwrap_or_else(|| "~/.config/solana/id.json".to_string());
+        let commitment = match profile.as_ref().and_then(|p| p.commitment.as_deref()) {
+            Some(commitment) => commitment
+                .parse()
+                .map_err(|_| anyhow!("invalid commitment `{}`", commitment))?,
+            None => CommitmentConfig::confirmed(),
+        };
+        let cp_swap_program = match profile.as_ref().and_then(|p| p.cp_swap_program.as_deref()) {
+            Some(program) => program
+                .parse()
+                .map_err(|_| anyhow!("invalid cp_swap_program `{}`", program))?,
+            None => CP_SWAP_PROGRAM_ID,
+        };
+
+        Ok(Self {
+            rpc_url,
+            keypair_path: expand_home(&keypair),
+            commitment,
+            cp_swap_program,
+        })
+    }
+
+    pub fn rpc_client(&self) -> RpcClient {
+        RpcClient::new_with_commitment(self.rpc_url.clone(), self.commitment)
+    }
+
+    pub fn keypair(&self) -> Result<Keypair> {
+        read_keypair_file(&self.keypair_path)
+            .map_err(|err| anyhow!("failed to read keypair {}: {}", self.keypair_path.display(), err))
+    }
+}
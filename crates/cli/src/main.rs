@@ -0,0 +1,105 @@
+//! `continuum-cli`: administration of a Continuum deployment and trading
+//! against its FIFO queue.
+
+mod admin;
+mod config;
+mod trader;
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use clap::{Parser, Subcommand};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signature, Signer};
+
+use continuum_cp_swap_client::transaction::TransactionBuilder;
+
+use config::{ConfigFile, Settings};
+
+#[derive(Parser)]
+#[command(name = "continuum-cli", version, about)]
+struct Cli {
+    /// Profile file [default: ~/.config/continuum/cli.toml]
+    #[arg(long, global = true, env = "CONTINUUM_CLI_CONFIG")]
+    config: Option<PathBuf>,
+
+    /// Profile to use instead of the file's default_profile
+    #[arg(long, short, global = true, env = "CONTINUUM_PROFILE")]
+    profile: Option<String>,
+
+    /// RPC URL, overriding the profile [default: the public endpoint of the
+    /// cluster the CLI was built for]
+    #[arg(long, short, global = true)]
+    url: Option<String>,
+
+    /// Signer keypair file, overriding the profile
+    #[arg(long, short, global = true)]
+    keypair: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+// Parsed once per run, like `TraderCommand`
+#[allow(clippy::large_enum_variant)]
+#[derive(Subcommand)]
+enum Command {
+    #[command(flatten)]
+    Admin(admin::AdminCommand),
+
+    #[command(flatten)]
+    Trader(trader::TraderCommand),
+}
+
+/// Connection and signer shared by all commands
+pub struct Session {
+    pub settings: Settings,
+    pub rpc: RpcClient,
+    pub signer: Keypair,
+}
+
+impl Session {
+    /// Sign with the profile keypair, send and wait for confirmation
+    pub fn send(&self, ixs: Vec<Instruction>) -> Result<Signature> {
+        self.send_with(ixs, &[])
+    }
+
+    /// As [`Self::send`], with `co_signers` signing alongside the profile keypair
+    pub fn send_with(&self, ixs: Vec<Instruction>, co_signers: &[&Keypair]) -> Result<Signature> {
+        let blockhash = self.rpc.get_latest_blockhash().context("failed to fetch blockhash")?;
+        let mut signers = vec![&self.signer];
+        signers.extend_from_slice(co_signers);
+        let tx = TransactionBuilder::new(self.signer.pubkey())
+            .instructions(ixs)
+            .sign(blockhash, &signers)?;
+        let signature = self
+            .rpc
+            .send_and_confirm_transaction(&tx)
+            .context("transaction failed")?;
+        println!("Signature: {}", signature);
+        Ok(signature)
+    }
+}
+
+/// Read a keypair signing alongside the profile's, e.g. a co-signer's
+pub fn read_keypair(path: &Path) -> Result<Keypair> {
+    read_keypair_file(path).map_err(|err| anyhow!("failed to read keypair {}: {}", path.display(), err))
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let config = ConfigFile::load(cli.config.as_deref())?;
+    let settings = Settings::resolve(config.profile(cli.profile.as_deref())?, cli.url, cli.keypair)?;
+    let session = Session {
+        rpc: settings.rpc_client(),
+        signer: settings.keypair()?,
+        settings,
+    };
+
+    match cli.command {
+        Command::Admin(command) => admin::run(&session, command),
+        Command::Trader(command) => trader::run(&session, command),
+    }
+}
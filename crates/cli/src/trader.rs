@@ -0,0 +1,1422 @@
+//! Trader commands: submitting, cancelling and inspecting orders, and
+//! managing order templates, icebergs, shared escrows, delegations, permits
+//! and organizations. Relayers also claim their fees here.
+
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Context, Result};
+use anchor_lang::AccountDeserialize;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::TokenAccount;
+use clap::{Subcommand, ValueEnum};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::RpcFilterType;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use continuum_cp_swap_client::accounts::{
+    decode, ClientOrderId, LegacyOrderStateV0, CoSignScope, CoSigner, CpSwapPoolRegistry, DcaSchedule, ExecutionPolicy, Iceberg, LimitPrice, OrderState, Organization, OrderStatus, OrderTemplate, PoolFifoState,
+    RelayerFeeBalance, SharedEscrow, TrailDirection, TriggerDirection,
+};
+use continuum_cp_swap_client::cp_swap::{parse_trade_fee_rate, PoolStateInfo};
+use continuum_cp_swap_client::filters::OrderQuery;
+use continuum_cp_swap_client::quote::{quote_order, Reserves};
+use continuum_cp_swap_client::instructions::{self, DcaScheduleParams, Escrow, OrderOptions, OrderTemplateParams, OrderVault, TrailingStopParams};
+use continuum_cp_swap_client::trace::{self, TraceId};
+use continuum_cp_swap_client::{pda, PROGRAM_ID};
+
+use crate::{read_keypair, Session};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum StatusArg {
+    Pending,
+    Executed,
+    Cancelled,
+    Failed,
+    Expired,
+}
+
+impl From<StatusArg> for OrderStatus {
+    fn from(status: StatusArg) -> Self {
+        match status {
+            StatusArg::Pending => OrderStatus::Pending,
+            StatusArg::Executed => OrderStatus::Executed,
+            StatusArg::Cancelled => OrderStatus::Cancelled,
+            StatusArg::Failed => OrderStatus::Failed,
+            StatusArg::Expired => OrderStatus::Expired,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum CoSignArg {
+    Submission,
+    Cancellation,
+    Both,
+}
+
+impl From<CoSignArg> for CoSignScope {
+    fn from(scope: CoSignArg) -> Self {
+        match scope {
+            CoSignArg::Submission => CoSignScope::Submission,
+            CoSignArg::Cancellation => CoSignScope::Cancellation,
+            CoSignArg::Both => CoSignScope::Both,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum PolicyArg {
+    /// Good till cancelled
+    Gtc,
+    /// Immediate or cancel
+    Ioc,
+    /// Fill or kill
+    Fok,
+}
+
+impl From<PolicyArg> for ExecutionPolicy {
+    fn from(policy: PolicyArg) -> Self {
+        match policy {
+            PolicyArg::Gtc => ExecutionPolicy::GoodTillCancelled,
+            PolicyArg::Ioc => ExecutionPolicy::ImmediateOrCancel,
+            PolicyArg::Fok => ExecutionPolicy::FillOrKill,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum TriggerArg {
+    /// Fire once the price is at or above the trigger
+    Above,
+    /// Fire once the price is at or below the trigger
+    Below,
+}
+
+impl From<TriggerArg> for TriggerDirection {
+    fn from(direction: TriggerArg) -> Self {
+        match direction {
+            TriggerArg::Above => TriggerDirection::Above,
+            TriggerArg::Below => TriggerDirection::Below,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum TrailArg {
+    Sell,
+    Buy,
+}
+
+impl From<TrailArg> for TrailDirection {
+    fn from(direction: TrailArg) -> Self {
+        match direction {
+            TrailArg::Sell => TrailDirection::Sell,
+            TrailArg::Buy => TrailDirection::Buy,
+        }
+    }
+}
+
+fn parse_trace_id(hex: &str) -> std::result::Result<TraceId, String> {
+    trace::parse(hex).ok_or_else(|| "expected 32 hex digits".to_string())
+}
+
+fn parse_client_order_id(hex: &str) -> std::result::Result<ClientOrderId, String> {
+    let mut client_order_id = [0; 32];
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err("expected 64 hex digits".to_string());
+    }
+    for (byte, digits) in client_order_id.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        let digits = std::str::from_utf8(digits).map_err(|err| err.to_string())?;
+        *byte = u8::from_str_radix(digits, 16).map_err(|_| "expected 64 hex digits".to_string())?;
+    }
+    Ok(client_order_id)
+}
+
+fn parse_limit_price(price: &str) -> std::result::Result<LimitPrice, String> {
+    let (numerator, denominator) = price.split_once('/').ok_or("expected NUMERATOR/DENOMINATOR")?;
+    let limit = LimitPrice {
+        numerator: numerator.parse().map_err(|err| format!("invalid numerator: {}", err))?,
+        denominator: denominator.parse().map_err(|err| format!("invalid denominator: {}", err))?,
+    };
+    if !limit.is_valid() {
+        return Err("numerator and denominator must be positive".to_string());
+    }
+    Ok(limit)
+}
+
+// Parsed once per run, so the size of `Submit` doesn't matter
+#[allow(clippy::large_enum_variant)]
+#[derive(Subcommand)]
+pub enum TraderCommand {
+    /// Submit an order to the FIFO queue
+    Submit {
+        #[arg(long)]
+        pool: Pubkey,
+        /// Exact input, or exact output with --base-output
+        #[arg(long)]
+        amount: u64,
+        /// Minimum output, or maximum input with --base-output
+        #[arg(long)]
+        limit: u64,
+        #[arg(long)]
+        base_output: bool,
+        /// Owner of the account receiving the output [default: signer]
+        #[arg(long)]
+        recipient: Option<Pubkey>,
+        /// Program to notify once the order fills
+        #[arg(long)]
+        callback_program: Option<Pubkey>,
+        /// Exact token account to pay the output into, e.g. an invoice's
+        #[arg(long, conflicts_with = "recipient")]
+        destination_account: Option<Pubkey>,
+        /// Unix timestamp before which the order can't be executed
+        #[arg(long)]
+        valid_from: Option<i64>,
+        /// Seconds after submission past which the order expires unexecuted
+        #[arg(long)]
+        ttl: Option<i64>,
+        /// Hold the order until the pool offers at least this much output
+        /// per unit of input, as NUMERATOR/DENOMINATOR in base units
+        #[arg(
+            long,
+            value_parser = parse_limit_price,
+            conflicts_with_all = ["callback_program", "destination_account", "valid_from", "parent_order", "trail_bps", "delegate_from", "co_signer", "co_signer_keypair"],
+        )]
+        limit_price: Option<LimitPrice>,
+        /// Sell the amount in this many slices, --interval seconds apart,
+        /// escrowed with --escrow
+        #[arg(
+            long,
+            requires_all = ["interval", "escrow"],
+            conflicts_with_all = ["base_output", "limit_price", "callback_program", "destination_account", "valid_from", "parent_order", "trail_bps", "co_signer", "co_signer_keypair"],
+        )]
+        slices: Option<u16>,
+        /// Least seconds between the slices of --slices
+        #[arg(long, requires = "slices")]
+        interval: Option<i64>,
+        /// Order account that must execute before this one can
+        #[arg(long)]
+        parent_order: Option<Pubkey>,
+        /// Hold the order as a trailing stop this many bps behind the price
+        #[arg(long, requires = "oracle")]
+        trail_bps: Option<u16>,
+        #[arg(long, value_enum, default_value = "sell")]
+        trail_direction: TrailArg,
+        /// Hold the order until --oracle's price crosses this, for a
+        /// stop-loss or take-profit, in the feed's own units
+        #[arg(
+            long,
+            requires = "oracle",
+            conflicts_with_all = ["trail_bps", "limit_price", "slices", "callback_program", "destination_account", "valid_from", "parent_order", "delegate_from", "co_signer", "co_signer_keypair"],
+        )]
+        trigger_price: Option<i64>,
+        /// Which way the price must cross --trigger-price
+        #[arg(long, value_enum, default_value = "below")]
+        trigger_direction: TriggerArg,
+        /// Pyth price update account the trailing stop or trigger follows
+        #[arg(long)]
+        oracle: Option<Pubkey>,
+        /// Spend from the signer's shared escrow of this mint
+        #[arg(long, conflicts_with = "delegate_from")]
+        shared_escrow: Option<Pubkey>,
+        /// Keep the input in the signer's associated token account of this
+        /// mint, approving the order's escrow for --allowance
+        #[arg(long, requires = "allowance")]
+        delegate_from: Option<Pubkey>,
+        /// Amount approved with --delegate-from, covering the input and fees;
+        /// what is unspent is returned at execution
+        #[arg(long, requires = "delegate_from")]
+        allowance: Option<u64>,
+        /// Spend through the permit over the signer's associated token
+        /// account of this mint
+        #[arg(long, conflicts_with_all = ["shared_escrow", "delegate_from"])]
+        permit: Option<Pubkey>,
+        /// Escrow the input and fees in the order's vault at submission,
+        /// from the signer's associated token account of this mint
+        #[arg(long, conflicts_with_all = ["shared_escrow", "delegate_from", "permit"])]
+        escrow: Option<Pubkey>,
+        /// Leave the input in the signer's associated token account of this
+        /// mint, spent by the pool authority through an approval given before
+        /// execution, instead of escrowing it
+        #[arg(long, conflicts_with_all = ["shared_escrow", "delegate_from", "permit", "escrow"])]
+        from_wallet: Option<Pubkey>,
+        /// Key that must co-sign the order, when it doesn't sign this
+        /// submission
+        #[arg(long, conflicts_with = "co_signer_keypair")]
+        co_signer: Option<Pubkey>,
+        /// Co-signer signing this submission
+        #[arg(long)]
+        co_signer_keypair: Option<PathBuf>,
+        /// What the co-signer must sign
+        #[arg(long, value_enum, default_value = "both")]
+        co_sign: CoSignArg,
+        /// How long the order rests in the queue [default: gtc]; IOC and FOK
+        /// orders can't wait on a condition
+        #[arg(long, value_enum, conflicts_with_all = ["limit_price", "slices", "valid_from", "parent_order", "trail_bps", "trigger_price"])]
+        policy: Option<PolicyArg>,
+        /// Trace ID, 32 hex digits, to follow the order by in events and
+        /// off-chain systems [default: derived from its sequence]
+        #[arg(long, value_parser = parse_trace_id)]
+        trace_id: Option<TraceId>,
+        /// Your own ID for the order, 64 hex digits, carried by it and its
+        /// OrderSubmitted event
+        #[arg(long, value_parser = parse_client_order_id, conflicts_with_all = ["limit_price", "slices", "trigger_price"])]
+        client_order_id: Option<ClientOrderId>,
+        /// Short note carried likewise
+        #[arg(long, conflicts_with_all = ["limit_price", "slices", "trigger_price"])]
+        memo: Option<String>,
+    },
+
+    /// Ratchet a pending order's trailing stop to its oracle's price
+    UpdateTrail { order: Pubkey },
+
+    /// Cancel one of the signer's pending orders, or as a delegate one of
+    /// the user's who delegated to the signer
+    Cancel {
+        order: Pubkey,
+        #[arg(long)]
+        as_delegate: bool,
+        /// The order's co-signer, when it must sign cancellations
+        #[arg(long)]
+        co_signer_keypair: Option<PathBuf>,
+    },
+
+    /// Change the amounts of one of the signer's pending orders. Only
+    /// tightening its limit keeps its place in line; any other change
+    /// re-queues it at the tail.
+    Modify {
+        order: Pubkey,
+        /// New exact input, or exact output of an exact output order
+        #[arg(long)]
+        amount: u64,
+        /// New minimum output, or maximum input of an exact output order
+        #[arg(long)]
+        limit: u64,
+        /// The order's co-signer, when it must sign cancellations
+        #[arg(long)]
+        co_signer_keypair: Option<PathBuf>,
+    },
+
+    /// Move the pool's queue past a settled or conditional order next in
+    /// line, or one stuck there for the grace period, unblocking the orders
+    /// behind it
+    Skip { order: Pubkey },
+
+    /// Settle a pending order past its time to live, refunding its escrow
+    Expire { order: Pubkey },
+
+    /// Move one of the signer's pending order accounts of the first
+    /// deployment, from before orders were sequenced by their pool, to the
+    /// tail of its pool's queue
+    Migrate {
+        order: Pubkey,
+        /// Mint the order sells, which the first deployment didn't record
+        #[arg(long)]
+        input_mint: Pubkey,
+    },
+
+    /// Let a delegate submit and cancel the signer's orders
+    Delegate {
+        delegate: Pubkey,
+        /// Largest maximum input of one delegated order
+        #[arg(long)]
+        max_amount: u64,
+        /// Unix timestamp after which the delegation can't be used
+        #[arg(long)]
+        expires_at: Option<i64>,
+    },
+
+    /// Withdraw the signer's delegation to a delegate
+    Undelegate { delegate: Pubkey },
+
+    /// Submit an order for a user who delegated to the signer
+    SubmitFor {
+        user: Pubkey,
+        #[arg(long)]
+        pool: Pubkey,
+        /// Mint the order sells
+        #[arg(long)]
+        input_mint: Pubkey,
+        /// Exact input, or exact output with --base-output
+        #[arg(long)]
+        amount: u64,
+        /// Minimum output, or maximum input with --base-output
+        #[arg(long)]
+        limit: u64,
+        #[arg(long)]
+        base_output: bool,
+    },
+
+    /// Show an order account
+    Status { order: Pubkey },
+
+    /// List orders, optionally by user, pool and status
+    ListOrders {
+        #[arg(long)]
+        user: Option<Pubkey>,
+        #[arg(long)]
+        pool: Option<Pubkey>,
+        #[arg(long, value_enum)]
+        status: Option<StatusArg>,
+    },
+
+    /// Create a repeating order, funded from an escrow the deposit is moved into
+    CreateTemplate {
+        #[arg(long)]
+        pool: Pubkey,
+        /// Any number not used by another of the signer's templates
+        #[arg(long)]
+        id: u64,
+        /// Mint being sold, deposited from the signer's associated token account
+        #[arg(long)]
+        input_mint: Pubkey,
+        /// Exact input of each occurrence, or exact output with --base-output
+        #[arg(long)]
+        amount: u64,
+        /// Minimum output of each occurrence, or maximum input with --base-output
+        #[arg(long)]
+        limit: u64,
+        #[arg(long)]
+        base_output: bool,
+        /// Seconds between occurrences
+        #[arg(long)]
+        interval: i64,
+        #[arg(long)]
+        occurrences: u32,
+        /// Amount moved into the escrow [default: enough for every occurrence]
+        #[arg(long)]
+        deposit: Option<u64>,
+    },
+
+    /// Enqueue a template's next occurrence if it is due
+    MaterializeTemplate { template: Pubkey },
+
+    /// Stop one of the signer's templates and refund its escrow
+    CloseTemplate { id: u64 },
+
+    /// Create a recurring swap of a fixed amount, funded from an escrow the
+    /// budget is moved into
+    CreateDca {
+        #[arg(long)]
+        pool: Pubkey,
+        /// Any number not used by another of the signer's DCA schedules
+        #[arg(long)]
+        id: u64,
+        /// Mint being sold, taken from the signer's associated token account;
+        /// the output goes to their associated account of the pool's other mint
+        #[arg(long)]
+        input_mint: Pubkey,
+        /// Exact input of each tick
+        #[arg(long)]
+        amount: u64,
+        /// Minimum output of each tick
+        #[arg(long)]
+        limit: u64,
+        /// Seconds between ticks
+        #[arg(long)]
+        interval: i64,
+        /// Total input sold over every tick
+        #[arg(long)]
+        budget: u64,
+    },
+
+    /// Execute a DCA schedule's tick if it is due, as a relayer the pool allows
+    ExecuteDcaTick { schedule: Pubkey },
+
+    /// Stop one of the signer's DCA schedules and refund its budget
+    CloseDca { id: u64 },
+
+    /// Create an order enqueued one tranche at a time, funded from an escrow
+    CreateIceberg {
+        #[arg(long)]
+        pool: Pubkey,
+        /// Any number not used by another of the signer's icebergs
+        #[arg(long)]
+        id: u64,
+        /// Mint being sold, deposited from the signer's associated token account
+        #[arg(long)]
+        input_mint: Pubkey,
+        /// Exact input of each tranche
+        #[arg(long)]
+        tranche: u64,
+        /// Minimum output of each tranche
+        #[arg(long)]
+        limit: u64,
+        /// Total amount moved into the escrow
+        #[arg(long)]
+        deposit: u64,
+    },
+
+    /// Enqueue an iceberg's next tranche if the last one has executed
+    RearmIceberg { iceberg: Pubkey },
+
+    /// Stop one of the signer's icebergs and refund its escrow
+    CloseIceberg { id: u64 },
+
+    /// Open a shared escrow of a mint, which orders on any pool can spend from
+    OpenEscrow { mint: Pubkey },
+
+    /// Move tokens from the signer's associated token account into their shared escrow
+    DepositEscrow {
+        mint: Pubkey,
+        #[arg(long)]
+        amount: u64,
+    },
+
+    /// Move unreserved tokens out of a shared escrow
+    WithdrawEscrow {
+        mint: Pubkey,
+        #[arg(long)]
+        amount: u64,
+    },
+
+    /// Show a shared escrow's balance and reservations
+    EscrowStatus {
+        mint: Pubkey,
+        #[arg(long)]
+        user: Option<Pubkey>,
+    },
+
+    /// Close a shared escrow no pending order reserves from
+    CloseEscrow { mint: Pubkey },
+
+    /// Permit orders to spend from the signer's associated token account of
+    /// a mint, or change an existing permit's terms
+    Permit {
+        mint: Pubkey,
+        /// What orders may spend through the permit, fees included
+        #[arg(long)]
+        allowance: u64,
+        /// Unix timestamp after which the permit can't be spent through
+        #[arg(long)]
+        expires_at: i64,
+    },
+
+    /// Close a permit and revoke its approval
+    ClosePermit { mint: Pubkey },
+
+    /// Create an organization owned by the signer
+    CreateOrg {
+        /// Any number not used by another of the signer's organizations
+        id: u64,
+    },
+
+    /// Let a member submit orders for one of the signer's organizations, or
+    /// change their limits
+    SetOrgMember {
+        id: u64,
+        member: Pubkey,
+        /// Largest maximum input of one order
+        #[arg(long)]
+        max_amount: u64,
+        /// Pool the member may trade, repeatable [default: any]
+        #[arg(long = "pool")]
+        pools: Vec<Pubkey>,
+    },
+
+    /// Remove a member from one of the signer's organizations
+    RemoveOrgMember { id: u64, member: Pubkey },
+
+    /// Open an escrow of a mint for one of the signer's organizations
+    OpenOrgEscrow { id: u64, mint: Pubkey },
+
+    /// Move tokens from the signer's associated token account into an
+    /// organization's escrow
+    FundOrgEscrow {
+        org: Pubkey,
+        mint: Pubkey,
+        #[arg(long)]
+        amount: u64,
+    },
+
+    /// Move unreserved tokens out of one of the signer's organization escrows
+    WithdrawOrgEscrow {
+        id: u64,
+        mint: Pubkey,
+        #[arg(long)]
+        amount: u64,
+    },
+
+    /// Show an organization's members; `escrow-status --user <ORG>` shows
+    /// its escrows
+    OrgStatus { org: Pubkey },
+
+    /// Submit an order for an organization the signer is a member of
+    SubmitOrg {
+        org: Pubkey,
+        #[arg(long)]
+        pool: Pubkey,
+        /// Mint being sold, spent from the organization's escrow
+        #[arg(long)]
+        input_mint: Pubkey,
+        /// Exact input, or exact output with --base-output
+        #[arg(long)]
+        amount: u64,
+        /// Minimum output, or maximum input with --base-output
+        #[arg(long)]
+        limit: u64,
+        #[arg(long)]
+        base_output: bool,
+    },
+
+    /// Cancel a pending organization order as its owner or a member
+    CancelOrg { order: Pubkey },
+
+    /// Show the relayer fees the signer has earned on a pool
+    RelayerFees {
+        #[arg(long)]
+        pool: Pubkey,
+    },
+
+    /// Claim the relayer fees the signer has earned on a pool
+    ClaimRelayerFees {
+        #[arg(long)]
+        pool: Pubkey,
+        /// Account receiving token 0 [default: signer's associated token account]
+        #[arg(long)]
+        destination_0: Option<Pubkey>,
+        /// Account receiving token 1 [default: signer's associated token account]
+        #[arg(long)]
+        destination_1: Option<Pubkey>,
+    },
+
+    /// Quote a swap from the pool's current reserves
+    Quote {
+        #[arg(long)]
+        pool: Pubkey,
+        /// Mint being sold
+        #[arg(long)]
+        input_mint: Pubkey,
+        /// Exact input, or exact output with --base-output
+        #[arg(long)]
+        amount: u64,
+        #[arg(long)]
+        base_output: bool,
+    },
+}
+
+fn fetch_data(session: &Session, address: &Pubkey, what: &str) -> Result<Vec<u8>> {
+    session
+        .rpc
+        .get_account_data(address)
+        .with_context(|| format!("failed to fetch {} {}", what, address))
+}
+
+fn fetch<T: AccountDeserialize>(session: &Session, address: &Pubkey, what: &str) -> Result<T> {
+    let data = fetch_data(session, address, what)?;
+    decode(&data).with_context(|| format!("invalid {} {}", what, address))
+}
+
+fn print_order(address: &Pubkey, order: &OrderState) {
+    println!("Order:          {}", address);
+    println!("  sequence:     {}", order.sequence);
+    println!("  user:         {}", order.user);
+    println!("  pool:         {}", order.pool_id);
+    println!("  amount:       {}", order.amount_in);
+    println!("  limit:        {}", order.min_amount_out);
+    println!("  base input:   {}", order.is_base_input);
+    println!("  status:       {:?}", order.status);
+    println!("  submitted at: {}", order.submitted_at);
+    println!("  trace ID:     {}", trace::to_hex(&order.trace_id));
+    if let Some(client_order_id) = order.client_order_id {
+        let hex: String = client_order_id.iter().map(|byte| format!("{:02x}", byte)).collect();
+        println!("  client ID:    {}", hex);
+    }
+    if let Some(memo) = &order.memo {
+        println!("  memo:         {}", memo);
+    }
+    println!(
+        "  fees at most: {} bps protocol, {} bps relayer",
+        order.fee_quote.protocol_fee_bps, order.fee_quote.relayer_fee_bps
+    );
+    if let Some(executed_at) = order.executed_at {
+        println!("  executed at:  {}", executed_at);
+    }
+    if let Some(recipient) = order.recipient {
+        println!("  recipient:    {}", recipient);
+    }
+    if let Some(callback_program) = order.callback_program {
+        println!("  callback:     {}", callback_program);
+    }
+    if let Some(destination_account) = order.destination_account {
+        println!("  destination:  {}", destination_account);
+    }
+    if let Some(valid_from) = order.valid_from {
+        println!("  valid from:   {}", valid_from);
+    }
+    if let Some(expires_at) = order.expires_at {
+        println!("  expires at:   {}", expires_at);
+    }
+    if order.execution_policy != ExecutionPolicy::GoodTillCancelled {
+        println!("  policy:       {:?}", order.execution_policy);
+    }
+    if let Some(limit) = order.limit_price {
+        println!("  limit price:  {}/{}", limit.numerator, limit.denominator);
+    }
+    if let Some(twap) = order.twap {
+        println!(
+            "  twap:         {}/{} slices, {}s apart",
+            twap.slices_executed, twap.num_slices, twap.interval_seconds
+        );
+    }
+    if order.amount_out_filled > 0 {
+        println!(
+            "  filled:       {} in for {} out, {} left",
+            order.amount_in - order.amount_in_remaining,
+            order.amount_out_filled,
+            order.amount_in_remaining
+        );
+    }
+    if let Some(source) = order.source_account {
+        println!("  source:       {}", source);
+    }
+    if let Some(refund) = order.escrow_refund {
+        println!("  escrowed:     refunded to {}", refund);
+    }
+    if let Some(parent) = order.parent_order {
+        println!("  parent:       {}", parent);
+    }
+    if let Some(stop) = order.trailing_stop {
+        println!("  trailing:     {:?} {} bps of {}", stop.direction, stop.trail_bps, stop.oracle);
+        println!("  stop price:   {} (best {})", stop.stop_price, stop.reference_price);
+    }
+    if let Some(trigger) = order.trigger {
+        println!("  trigger:      {:?} {} of {}", trigger.direction, trigger.trigger_price, trigger.oracle);
+    }
+    if let Some(escrow) = order.shared_escrow {
+        println!("  escrow:       {}", escrow);
+    }
+    if order.delegated_input {
+        println!("  delegate:     {}", pda::find_order_escrow(address).0);
+    }
+    if let Some(permit) = order.permit {
+        println!("  permit:       {}", permit);
+    }
+    if let Some(co_signer) = order.co_signer {
+        println!("  co-signer:    {} ({:?})", co_signer.key, co_signer.scope);
+    }
+}
+
+pub fn run(session: &Session, command: TraderCommand) -> Result<()> {
+    let user = session.signer.pubkey();
+
+    match command {
+        TraderCommand::Submit {
+            pool,
+            amount,
+            limit,
+            base_output,
+            recipient,
+            callback_program,
+            destination_account,
+            valid_from,
+            ttl,
+            limit_price,
+            slices,
+            interval,
+            parent_order,
+            trail_bps,
+            trail_direction,
+            trigger_price,
+            trigger_direction,
+            oracle,
+            shared_escrow,
+            delegate_from,
+            allowance,
+            permit,
+            escrow,
+            from_wallet,
+            co_signer,
+            co_signer_keypair,
+            co_sign,
+            policy,
+            trace_id,
+            client_order_id,
+            memo,
+        } => {
+            if oracle.is_some() && trail_bps.is_none() && trigger_price.is_none() {
+                bail!("--oracle is only used with --trail-bps or --trigger-price");
+            }
+            // Each way of funding the order names the mint it sells
+            let Some(input_mint) = escrow.or(shared_escrow).or(delegate_from).or(permit).or(from_wallet) else {
+                bail!("fund the order with --escrow, --shared-escrow, --delegate-from, --permit or --from-wallet <MINT>");
+            };
+            let co_signer_keypair = co_signer_keypair.as_deref().map(read_keypair).transpose()?;
+            let co_signer = co_signer_keypair
+                .as_ref()
+                .map(|keypair| keypair.pubkey())
+                .or(co_signer)
+                .map(|key| CoSigner { key, scope: co_sign.into() });
+            if co_signer.is_some_and(|co_signer| co_signer.scope.on_submission()) && co_signer_keypair.is_none() {
+                bail!("the co-signer must sign the submission: pass --co-signer-keypair");
+            }
+
+            let pool_fifo: PoolFifoState = fetch(session, &pda::find_pool_fifo(&pool).0, "FIFO queue")?;
+            let registry: CpSwapPoolRegistry =
+                fetch(session, &pda::find_pool_registry(&pool).0, "pool registry")?;
+            let current_sequence = pool_fifo.current_sequence;
+            let order = pda::find_order(&pool, current_sequence + 1).0;
+
+            let mut ixs = Vec::new();
+            if let (Some(mint), Some(allowance)) = (delegate_from, allowance) {
+                let source = get_associated_token_address(&user, &mint);
+                ixs.push(instructions::approve_order_escrow(user, source, order, allowance));
+            }
+            let options = OrderOptions {
+                recipient,
+                callback_program,
+                compliance_program: registry.compliance_program(),
+                destination_account,
+                valid_from,
+                ttl,
+                parent_order,
+                trailing_stop: trail_bps.map(|trail_bps| TrailingStopParams {
+                    direction: trail_direction.into(),
+                    trail_bps,
+                }),
+                oracle,
+                shared_escrow: shared_escrow.map(|mint| pda::find_shared_escrow(&user, &mint).0),
+                delegated_input: delegate_from.is_some(),
+                permit: permit.map(|mint| pda::find_permit(&get_associated_token_address(&user, &mint)).0),
+                co_signer,
+                trace_id,
+                escrow: escrow.map(|mint| Escrow {
+                    source: get_associated_token_address(&user, &mint),
+                }),
+                execution_policy: policy.map(Into::into),
+                client_order_id,
+                memo,
+                from_wallet: from_wallet.is_some(),
+                ..OrderOptions::default()
+            };
+            ixs.push(if let Some((slices, interval)) = slices.zip(interval) {
+                instructions::submit_twap_order(user, pool, input_mint, current_sequence, amount, limit, slices, interval, options)
+            } else if let (Some(trigger_price), Some(oracle)) = (trigger_price, oracle) {
+                instructions::submit_trigger_order(
+                    user,
+                    pool,
+                    input_mint,
+                    current_sequence,
+                    amount,
+                    limit,
+                    !base_output,
+                    oracle,
+                    trigger_price,
+                    trigger_direction.into(),
+                    options,
+                )
+            } else if let Some(limit_price) = limit_price {
+                instructions::submit_limit_order(
+                    user,
+                    pool,
+                    input_mint,
+                    current_sequence,
+                    amount,
+                    limit,
+                    !base_output,
+                    limit_price,
+                    options,
+                )
+            } else {
+                instructions::submit_order(user, pool, input_mint, current_sequence, amount, limit, !base_output, options)
+            });
+            let co_signers: Vec<&Keypair> = co_signer_keypair.iter().collect();
+            session.send_with(ixs, &co_signers)?;
+            let trace_id = trace_id.unwrap_or_else(|| trace::default_trace_id(&pool, current_sequence + 1));
+            println!("Order: {}", order);
+            println!("Sequence: {}", current_sequence + 1);
+            println!("Trace ID: {}", trace::to_hex(&trace_id));
+        }
+
+        TraderCommand::Cancel {
+            order,
+            as_delegate,
+            co_signer_keypair,
+        } => {
+            let state: OrderState = fetch(session, &order, "order")?;
+            let co_signer_keypair = co_signer_keypair.as_deref().map(read_keypair).transpose()?;
+            let co_signer = co_signer_keypair.as_ref().map(|keypair| keypair.pubkey());
+            if !state.cancel_co_signed(co_signer.as_ref()) {
+                bail!("order {} needs its co-signer's keypair: pass --co-signer-keypair", order);
+            }
+            let co_signers: Vec<&Keypair> = co_signer_keypair.iter().collect();
+            if as_delegate {
+                session.send_with(
+                    vec![instructions::cancel_order_as_delegate(
+                        user,
+                        state.user,
+                        state.pool_id,
+                        order,
+                        state.shared_escrow,
+                        co_signer,
+                        OrderVault::of(&state),
+                    )],
+                    &co_signers,
+                )?;
+            } else {
+                if state.user != user {
+                    bail!("order {} belongs to {}", order, state.user);
+                }
+                session.send_with(
+                    vec![instructions::cancel_order(
+                        user,
+                        state.pool_id,
+                        order,
+                        state.shared_escrow,
+                        co_signer,
+                        OrderVault::of(&state),
+                    )],
+                    &co_signers,
+                )?;
+            }
+        }
+
+        TraderCommand::Modify {
+            order,
+            amount,
+            limit,
+            co_signer_keypair,
+        } => {
+            let state: OrderState = fetch(session, &order, "order")?;
+            if state.user != user {
+                bail!("order {} belongs to {}", order, state.user);
+            }
+            let co_signer_keypair = co_signer_keypair.as_deref().map(read_keypair).transpose()?;
+            let co_signer = co_signer_keypair.as_ref().map(|keypair| keypair.pubkey());
+            if !state.cancel_co_signed(co_signer.as_ref()) {
+                bail!("order {} needs its co-signer's keypair: pass --co-signer-keypair", order);
+            }
+            let requeue_sequence = if state.is_tightened_by(amount, limit) {
+                None
+            } else {
+                let pool_fifo: PoolFifoState = fetch(session, &pda::find_pool_fifo(&state.pool_id).0, "FIFO queue")?;
+                Some(pool_fifo.current_sequence)
+            };
+            let co_signers: Vec<&Keypair> = co_signer_keypair.iter().collect();
+            session.send_with(
+                vec![instructions::modify_order(
+                    user,
+                    state.pool_id,
+                    order,
+                    amount,
+                    limit,
+                    requeue_sequence,
+                    co_signer,
+                    OrderVault::of(&state),
+                )],
+                &co_signers,
+            )?;
+            match requeue_sequence {
+                Some(current_sequence) => {
+                    println!("Order: {}", pda::find_order(&state.pool_id, current_sequence + 1).0);
+                    println!("Sequence: {}", current_sequence + 1);
+                }
+                None => println!("Order {} kept its place in line", order),
+            }
+        }
+
+        TraderCommand::Skip { order } => {
+            let state: OrderState = fetch(session, &order, "order")?;
+            session.send(vec![instructions::skip_order(state.pool_id, order)])?;
+        }
+
+        TraderCommand::Expire { order } => {
+            let state: OrderState = fetch(session, &order, "order")?;
+            session.send(vec![instructions::expire_order(
+                state.user,
+                state.pool_id,
+                order,
+                state.shared_escrow,
+                OrderVault::of(&state),
+            )])?;
+        }
+
+        TraderCommand::Migrate { order, input_mint } => {
+            let state = LegacyOrderStateV0::decode(&fetch_data(session, &order, "order")?)
+                .with_context(|| format!("invalid legacy order {}", order))?;
+            let pool_fifo: PoolFifoState = fetch(session, &pda::find_pool_fifo(&state.pool_id).0, "FIFO queue")?;
+            let current_sequence = pool_fifo.current_sequence;
+            if state.user != user {
+                bail!("order {} belongs to {}, who must migrate it", order, state.user);
+            }
+            session.send(vec![instructions::migrate_order(
+                user,
+                state.sequence,
+                state.pool_id,
+                input_mint,
+                current_sequence,
+            )])?;
+            println!("Order: {}", pda::find_order(&state.pool_id, current_sequence + 1).0);
+            println!("Sequence: {}", current_sequence + 1);
+        }
+
+        TraderCommand::Delegate {
+            delegate,
+            max_amount,
+            expires_at,
+        } => {
+            session.send(vec![instructions::approve_user_delegation(
+                user,
+                delegate,
+                user,
+                max_amount,
+                expires_at,
+            )])?;
+            println!("Delegation: {}", pda::find_user_delegation(&user, &delegate).0);
+        }
+
+        TraderCommand::Undelegate { delegate } => {
+            session.send(vec![instructions::revoke_user_delegation(user, delegate, user)])?;
+        }
+
+        TraderCommand::SubmitFor {
+            user: owner,
+            pool,
+            input_mint,
+            amount,
+            limit,
+            base_output,
+        } => {
+            let pool_fifo: PoolFifoState = fetch(session, &pda::find_pool_fifo(&pool).0, "FIFO queue")?;
+            let registry: CpSwapPoolRegistry =
+                fetch(session, &pda::find_pool_registry(&pool).0, "pool registry")?;
+            let current_sequence = pool_fifo.current_sequence;
+            session.send(vec![instructions::submit_order_as_delegate(
+                user,
+                owner,
+                pool,
+                input_mint,
+                current_sequence,
+                amount,
+                limit,
+                !base_output,
+                registry.compliance_program(),
+            )])?;
+            println!("Order: {}", pda::find_order(&pool, current_sequence + 1).0);
+            println!("Sequence: {}", current_sequence + 1);
+        }
+
+        TraderCommand::UpdateTrail { order } => {
+            let state: OrderState = fetch(session, &order, "order")?;
+            let stop = state
+                .trailing_stop
+                .ok_or_else(|| anyhow!("order {} has no trailing stop", order))?;
+            session.send(vec![instructions::update_trail(state.pool_id, order, stop.oracle)])?;
+        }
+
+        TraderCommand::Status { order } => {
+            let state: OrderState = fetch(session, &order, "order")?;
+            print_order(&order, &state);
+        }
+
+        TraderCommand::ListOrders { user, pool, status } => {
+            let query = OrderQuery {
+                user,
+                pool_id: pool,
+                status: status.map(Into::into),
+            };
+            let accounts = session
+                .rpc
+                .get_program_accounts_with_config(
+                    &PROGRAM_ID,
+                    RpcProgramAccountsConfig {
+                        filters: Some(
+                            query.filters().into_iter().map(RpcFilterType::from).collect(),
+                        ),
+                        account_config: RpcAccountInfoConfig {
+                            encoding: Some(UiAccountEncoding::Base64),
+                            ..RpcAccountInfoConfig::default()
+                        },
+                        ..RpcProgramAccountsConfig::default()
+                    },
+                )
+                .context("failed to fetch orders")?;
+
+            let orders = query.decode_orders(
+                accounts
+                    .into_iter()
+                    .map(|(address, account)| (address, account.data)),
+            );
+            for (address, order) in &orders {
+                print_order(address, order);
+            }
+            println!("{} order(s)", orders.len());
+        }
+
+        TraderCommand::CreateTemplate {
+            pool,
+            id,
+            input_mint,
+            amount,
+            limit,
+            base_output,
+            interval,
+            occurrences,
+            deposit,
+        } => {
+            let params = OrderTemplateParams {
+                amount_in: amount,
+                min_amount_out: limit,
+                is_base_input: !base_output,
+                interval,
+                total_occurrences: occurrences,
+            };
+            let per_occurrence = if base_output { limit } else { amount };
+            let deposit = deposit.unwrap_or(per_occurrence.saturating_mul(occurrences as u64));
+            session.send(vec![instructions::create_order_template(
+                user,
+                pool,
+                id,
+                input_mint,
+                get_associated_token_address(&user, &input_mint),
+                params,
+                deposit,
+            )])?;
+            println!("Template: {}", pda::find_order_template(&user, id).0);
+        }
+
+        TraderCommand::MaterializeTemplate { template } => {
+            let state: OrderTemplate = fetch(session, &template, "order template")?;
+            let pool_fifo: PoolFifoState = fetch(session, &pda::find_pool_fifo(&state.pool_id).0, "FIFO queue")?;
+            let current_sequence = pool_fifo.current_sequence;
+            session.send(vec![instructions::materialize_order_template(user, &state, current_sequence)])?;
+            println!("Order: {}", pda::find_order(&state.pool_id, current_sequence + 1).0);
+            println!("Sequence: {}", current_sequence + 1);
+        }
+
+        TraderCommand::CloseTemplate { id } => {
+            let template = pda::find_order_template(&user, id).0;
+            let state: OrderTemplate = fetch(session, &template, "order template")?;
+            let escrow: TokenAccount = fetch(session, &pda::find_template_escrow(&template).0, "template escrow")?;
+            session.send(vec![instructions::close_order_template(
+                user,
+                state.pool_id,
+                id,
+                get_associated_token_address(&user, &escrow.mint),
+            )])?;
+        }
+
+        TraderCommand::CreateDca {
+            pool,
+            id,
+            input_mint,
+            amount,
+            limit,
+            interval,
+            budget,
+        } => {
+            let registry: CpSwapPoolRegistry = fetch(session, &pda::find_pool_registry(&pool).0, "pool registry")?;
+            let output_mint = if input_mint == registry.token_0 { registry.token_1 } else { registry.token_0 };
+            let params = DcaScheduleParams {
+                amount_per_tick: amount,
+                min_amount_out_per_tick: limit,
+                interval,
+            };
+            session.send(vec![instructions::create_dca_schedule(
+                user,
+                pool,
+                id,
+                input_mint,
+                get_associated_token_address(&user, &input_mint),
+                get_associated_token_address(&user, &output_mint),
+                params,
+                budget,
+            )])?;
+            println!("DCA schedule: {}", pda::find_dca_schedule(&user, id).0);
+        }
+
+        TraderCommand::ExecuteDcaTick { schedule } => {
+            let state: DcaSchedule = fetch(session, &schedule, "DCA schedule")?;
+            let pool_state = PoolStateInfo::parse(&fetch_data(session, &state.pool_id, "pool")?)
+                .ok_or_else(|| anyhow!("invalid pool {}", state.pool_id))?;
+            let cp_swap_program = session.settings.cp_swap_program;
+            let swap = pool_state.swap_accounts(
+                &cp_swap_program,
+                state.pool_id,
+                pda::find_pool_authority(&state.pool_id).0,
+                state.input_mint,
+                pda::find_dca_escrow(&schedule).0,
+                state.destination,
+            );
+            let with_relayer_info = session.rpc.get_account(&pda::find_relayer_info(&user).0).is_ok();
+            session.send(vec![instructions::execute_dca_tick(user, cp_swap_program, &state, &swap, with_relayer_info)])?;
+            println!("Tick: {}", state.ticks_executed + 1);
+        }
+
+        TraderCommand::CloseDca { id } => {
+            let schedule = pda::find_dca_schedule(&user, id).0;
+            let state: DcaSchedule = fetch(session, &schedule, "DCA schedule")?;
+            session.send(vec![instructions::close_dca_schedule(
+                user,
+                state.pool_id,
+                id,
+                get_associated_token_address(&user, &state.input_mint),
+            )])?;
+        }
+
+        TraderCommand::CreateIceberg {
+            pool,
+            id,
+            input_mint,
+            tranche,
+            limit,
+            deposit,
+        } => {
+            session.send(vec![instructions::create_iceberg(
+                user,
+                pool,
+                id,
+                input_mint,
+                get_associated_token_address(&user, &input_mint),
+                tranche,
+                limit,
+                deposit,
+            )])?;
+            println!("Iceberg: {}", pda::find_iceberg(&user, id).0);
+        }
+
+        TraderCommand::RearmIceberg { iceberg } => {
+            let state: Iceberg = fetch(session, &iceberg, "iceberg")?;
+            let pool_fifo: PoolFifoState = fetch(session, &pda::find_pool_fifo(&state.pool_id).0, "FIFO queue")?;
+            let current_sequence = pool_fifo.current_sequence;
+            session.send(vec![instructions::rearm_iceberg(user, &state, current_sequence)])?;
+            println!("Order: {}", pda::find_order(&state.pool_id, current_sequence + 1).0);
+            println!("Sequence: {}", current_sequence + 1);
+        }
+
+        TraderCommand::CloseIceberg { id } => {
+            let iceberg = pda::find_iceberg(&user, id).0;
+            let state: Iceberg = fetch(session, &iceberg, "iceberg")?;
+            let escrow: TokenAccount = fetch(session, &pda::find_iceberg_escrow(&iceberg).0, "iceberg escrow")?;
+            session.send(vec![instructions::close_iceberg(
+                user,
+                state.pool_id,
+                id,
+                get_associated_token_address(&user, &escrow.mint),
+            )])?;
+        }
+
+        TraderCommand::OpenEscrow { mint } => {
+            session.send(vec![instructions::open_shared_escrow(user, mint)])?;
+            println!("Shared escrow: {}", pda::find_shared_escrow(&user, &mint).0);
+        }
+
+        TraderCommand::DepositEscrow { mint, amount } => {
+            session.send(vec![instructions::deposit_shared_escrow(
+                user,
+                mint,
+                get_associated_token_address(&user, &mint),
+                amount,
+            )])?;
+        }
+
+        TraderCommand::WithdrawEscrow { mint, amount } => {
+            session.send(vec![instructions::withdraw_shared_escrow(
+                user,
+                mint,
+                get_associated_token_address(&user, &mint),
+                amount,
+            )])?;
+        }
+
+        TraderCommand::EscrowStatus { mint, user: owner } => {
+            let escrow = pda::find_shared_escrow(&owner.unwrap_or(user), &mint).0;
+            let state: SharedEscrow = fetch(session, &escrow, "shared escrow")?;
+            let vault: TokenAccount = fetch(session, &pda::find_shared_escrow_vault(&escrow).0, "shared escrow vault")?;
+            println!("Shared escrow {}", escrow);
+            println!("  balance:      {}", vault.amount);
+            println!("  reserved:     {} by {} order(s)", state.reserved, state.open_orders);
+            println!("  available:    {}", state.available(vault.amount));
+        }
+
+        TraderCommand::CloseEscrow { mint } => {
+            session.send(vec![instructions::close_shared_escrow(
+                user,
+                mint,
+                get_associated_token_address(&user, &mint),
+            )])?;
+        }
+
+        TraderCommand::Permit { mint, allowance, expires_at } => {
+            let token_account = get_associated_token_address(&user, &mint);
+            let permit = pda::find_permit(&token_account).0;
+            let exists = session.rpc.get_account(&permit).is_ok();
+            let ix = if exists {
+                instructions::update_permit(user, token_account, allowance, expires_at)
+            } else {
+                instructions::create_permit(user, token_account, allowance, expires_at)
+            };
+            session.send(vec![ix])?;
+            println!("Permit: {}", permit);
+        }
+
+        TraderCommand::ClosePermit { mint } => {
+            session.send(vec![instructions::close_permit(user, get_associated_token_address(&user, &mint))])?;
+        }
+
+        TraderCommand::CreateOrg { id } => {
+            session.send(vec![instructions::create_organization(user, id)])?;
+            println!("Organization: {}", pda::find_organization(&user, id).0);
+        }
+
+        TraderCommand::SetOrgMember {
+            id,
+            member,
+            max_amount,
+            pools,
+        } => {
+            session.send(vec![instructions::set_org_member(user, id, member, max_amount, pools)])?;
+        }
+
+        TraderCommand::RemoveOrgMember { id, member } => {
+            session.send(vec![instructions::remove_org_member(user, id, member)])?;
+        }
+
+        TraderCommand::OpenOrgEscrow { id, mint } => {
+            session.send(vec![instructions::open_org_escrow(user, id, mint)])?;
+            let organization = pda::find_organization(&user, id).0;
+            println!("Escrow: {}", pda::find_shared_escrow(&organization, &mint).0);
+        }
+
+        TraderCommand::FundOrgEscrow { org, mint, amount } => {
+            session.send(vec![instructions::fund_org_escrow(
+                user,
+                get_associated_token_address(&user, &mint),
+                org,
+                mint,
+                amount,
+            )])?;
+        }
+
+        TraderCommand::WithdrawOrgEscrow { id, mint, amount } => {
+            session.send(vec![instructions::withdraw_org_escrow(
+                user,
+                id,
+                mint,
+                get_associated_token_address(&user, &mint),
+                amount,
+            )])?;
+        }
+
+        TraderCommand::OrgStatus { org } => {
+            let state: Organization = fetch(session, &org, "organization")?;
+            println!("Organization {} (id {})", org, state.org_id);
+            println!("  owner:        {}", state.owner);
+            for member in &state.members {
+                let pools = if member.pools.is_empty() {
+                    "any pool".to_string()
+                } else {
+                    member.pools.iter().map(|pool| pool.to_string()).collect::<Vec<_>>().join(", ")
+                };
+                println!("  member:       {} up to {} on {}", member.member, member.max_amount_in, pools);
+            }
+        }
+
+        TraderCommand::SubmitOrg {
+            org,
+            pool,
+            input_mint,
+            amount,
+            limit,
+            base_output,
+        } => {
+            let pool_fifo: PoolFifoState = fetch(session, &pda::find_pool_fifo(&pool).0, "FIFO queue")?;
+            let registry: CpSwapPoolRegistry =
+                fetch(session, &pda::find_pool_registry(&pool).0, "pool registry")?;
+            let output_mint = if input_mint == registry.token_0 {
+                registry.token_1
+            } else if input_mint == registry.token_1 {
+                registry.token_0
+            } else {
+                bail!("pool {} doesn't trade {}", pool, input_mint);
+            };
+            let current_sequence = pool_fifo.current_sequence;
+            session.send(vec![instructions::submit_org_order(
+                user,
+                org,
+                pool,
+                current_sequence,
+                input_mint,
+                output_mint,
+                amount,
+                limit,
+                !base_output,
+                registry.compliance_program(),
+            )])?;
+            println!("Order: {}", pda::find_order(&pool, current_sequence + 1).0);
+            println!("Sequence: {}", current_sequence + 1);
+        }
+
+        TraderCommand::CancelOrg { order } => {
+            let state: OrderState = fetch(session, &order, "order")?;
+            let shared_escrow = state
+                .shared_escrow
+                .ok_or_else(|| anyhow!("order {} isn't an organization order", order))?;
+            session.send(vec![instructions::cancel_org_order(user, state.user, state.pool_id, order, shared_escrow)])?;
+        }
+
+        TraderCommand::RelayerFees { pool } => {
+            let address = pda::find_relayer_fee_balance(&pool, &user).0;
+            let balance: RelayerFeeBalance = fetch(session, &address, "relayer fee balance")?;
+            println!("Fee balance: {}", address);
+            println!("Claimable:   {} / {}", balance.claimable_0, balance.claimable_1);
+            println!("Claimed:     {} / {}", balance.claimed_0, balance.claimed_1);
+        }
+
+        TraderCommand::ClaimRelayerFees {
+            pool,
+            destination_0,
+            destination_1,
+        } => {
+            let registry: CpSwapPoolRegistry = fetch(session, &pda::find_pool_registry(&pool).0, "pool registry")?;
+            session.send(vec![instructions::claim_relayer_fees(
+                user,
+                pool,
+                registry.token_0,
+                registry.token_1,
+                destination_0.unwrap_or_else(|| get_associated_token_address(&user, &registry.token_0)),
+                destination_1.unwrap_or_else(|| get_associated_token_address(&user, &registry.token_1)),
+            )])?;
+        }
+
+        TraderCommand::Quote {
+            pool,
+            input_mint,
+            amount,
+            base_output,
+        } => {
+            let pool_state = PoolStateInfo::parse(&fetch_data(session, &pool, "pool")?)
+                .ok_or_else(|| anyhow!("invalid pool {}", pool))?;
+            let p = &pool_state;
+            let (input_vault, output_vault, input_fees, output_fees) = if input_mint == p.token_0_mint {
+                (p.token_0_vault, p.token_1_vault, p.unclaimed_fees_0, p.unclaimed_fees_1)
+            } else if input_mint == p.token_1_mint {
+                (p.token_1_vault, p.token_0_vault, p.unclaimed_fees_1, p.unclaimed_fees_0)
+            } else {
+                bail!("{} is not a mint of pool {}", input_mint, pool);
+            };
+
+            let input: TokenAccount = fetch(session, &input_vault, "vault")?;
+            let output: TokenAccount = fetch(session, &output_vault, "vault")?;
+            let amm_config = fetch_data(session, &pool_state.amm_config, "AMM config")?;
+            let trade_fee_rate =
+                parse_trade_fee_rate(&amm_config).ok_or_else(|| anyhow!("invalid AMM config"))?;
+
+            let reserves = Reserves {
+                input: input.amount.saturating_sub(input_fees),
+                output: output.amount.saturating_sub(output_fees),
+            };
+            let quote = quote_order(reserves, amount, !base_output, trade_fee_rate)
+                .ok_or_else(|| anyhow!("pool cannot fill this trade"))?;
+
+            println!("Amount in:    {}", quote.amount_in);
+            println!("Amount out:   {}", quote.amount_out);
+            println!("Trade fee:    {}", quote.trade_fee);
+            println!("Price impact: {:.2}%", quote.price_impact_bps as f64 / 100.0);
+        }
+    }
+
+    Ok(())
+}
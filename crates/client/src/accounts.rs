@@ -0,0 +1,16 @@
+//! Decoding of Continuum program accounts.
+
+use anchor_lang::{AccountDeserialize, Result};
+
+pub use continuum_cp_swap::state::{
+    ClientOrderId, CoSignScope, CoSigner, Compensation, CpSwapPoolRegistry, DcaSchedule, ExecutionPolicy, ExecutionReceipt, FeeSchedule, FeeTier, FifoState, Iceberg, LimitPrice, OrderState, OrderStatus,
+    OrderTemplate, OrgMember, Organization, Permit, PoolFeeLedger, PriceAccumulator, PoolFifoState, PoolRelayers, RelayerFeeBalance, RelayerInfo, RelayerQuota, SharedEscrow, TrailDirection,
+    TrailingStop, TriggerCondition, TriggerDirection, TwapSchedule, UserDelegation, UserOrdering, UserVolume,
+};
+pub use continuum_cp_swap::legacy::{LegacyFifoStateV0, LegacyOrderStateV0, LegacyOrderStatusV0, LegacyPoolRegistryV0};
+
+/// Decode raw account data (including the 8-byte discriminator) into a
+/// program account type, checking the discriminator.
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
@@ -0,0 +1,244 @@
+//! Addresses and account lists of the Raydium CP-Swap program.
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token;
+use solana_sdk::hash::hash;
+
+use crate::instructions::CpSwapSwapAccounts;
+use crate::pda;
+
+pub const AMM_CONFIG_SEED: &[u8] = b"amm_config";
+pub const POOL_SEED: &[u8] = b"pool";
+pub const AUTHORITY_SEED: &[u8] = b"vault_and_lp_mint_auth_seed";
+pub const LP_MINT_SEED: &[u8] = b"pool_lp_mint";
+pub const POOL_VAULT_SEED: &[u8] = b"pool_vault";
+pub const OBSERVATION_SEED: &[u8] = b"observation";
+
+/// Order two mints the way CP-Swap stores them in a pool
+pub fn sort_mints(a: Pubkey, b: Pubkey) -> (Pubkey, Pubkey) {
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// AMM config indices are seeded big-endian
+pub fn find_amm_config(program_id: &Pubkey, index: u16) -> Pubkey {
+    Pubkey::find_program_address(&[AMM_CONFIG_SEED, &index.to_be_bytes()], program_id).0
+}
+
+pub fn find_authority(program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[AUTHORITY_SEED], program_id).0
+}
+
+/// PDAs of a pool, derived from its config and sorted mints
+#[derive(Debug, Clone, Copy)]
+pub struct PoolAddresses {
+    pub pool_state: Pubkey,
+    pub authority: Pubkey,
+    pub lp_mint: Pubkey,
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+    pub token_0_vault: Pubkey,
+    pub token_1_vault: Pubkey,
+    pub observation_state: Pubkey,
+}
+
+impl PoolAddresses {
+    pub fn derive(program_id: &Pubkey, amm_config: &Pubkey, mint_a: Pubkey, mint_b: Pubkey) -> Self {
+        let (token_0_mint, token_1_mint) = sort_mints(mint_a, mint_b);
+        let find = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, program_id).0;
+
+        let pool_state = find(&[
+            POOL_SEED,
+            amm_config.as_ref(),
+            token_0_mint.as_ref(),
+            token_1_mint.as_ref(),
+        ]);
+
+        Self {
+            pool_state,
+            authority: find_authority(program_id),
+            lp_mint: find(&[LP_MINT_SEED, pool_state.as_ref()]),
+            token_0_mint,
+            token_1_mint,
+            token_0_vault: find(&[POOL_VAULT_SEED, pool_state.as_ref(), token_0_mint.as_ref()]),
+            token_1_vault: find(&[POOL_VAULT_SEED, pool_state.as_ref(), token_1_mint.as_ref()]),
+            observation_state: find(&[OBSERVATION_SEED, pool_state.as_ref()]),
+        }
+    }
+
+    /// Accounts of CP-Swap `initialize`, in order, for a pool created with
+    /// the Continuum pool authority as its custom authority. The creator
+    /// funds the pool from its associated token accounts.
+    pub fn initialize_accounts(
+        &self,
+        creator: Pubkey,
+        amm_config: Pubkey,
+        create_pool_fee: Pubkey,
+    ) -> Vec<AccountMeta> {
+        let ata = |mint: &Pubkey| get_associated_token_address(&creator, mint);
+
+        vec![
+            AccountMeta::new(creator, true),
+            AccountMeta::new_readonly(amm_config, false),
+            AccountMeta::new_readonly(pda::find_pool_authority(&self.pool_state).0, false),
+            AccountMeta::new(self.pool_state, false),
+            AccountMeta::new_readonly(self.token_0_mint, false),
+            AccountMeta::new_readonly(self.token_1_mint, false),
+            AccountMeta::new(self.lp_mint, false),
+            AccountMeta::new(ata(&self.token_0_mint), false),
+            AccountMeta::new(ata(&self.token_1_mint), false),
+            AccountMeta::new(ata(&self.lp_mint), false),
+            AccountMeta::new(self.token_0_vault, false),
+            AccountMeta::new(self.token_1_vault, false),
+            AccountMeta::new(create_pool_fee, false),
+            AccountMeta::new(self.observation_state, false),
+            AccountMeta::new_readonly(token::ID, false),
+            AccountMeta::new_readonly(token::ID, false),
+            AccountMeta::new_readonly(token::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+        ]
+    }
+
+    /// Swap accounts for a trader swapping out of `input_mint` using their
+    /// associated token accounts
+    pub fn swap_accounts(&self, user: Pubkey, amm_config: Pubkey, input_mint: Pubkey) -> CpSwapSwapAccounts {
+        let (input_mint, output_mint, input_vault, output_vault) = if input_mint == self.token_0_mint {
+            (self.token_0_mint, self.token_1_mint, self.token_0_vault, self.token_1_vault)
+        } else {
+            (self.token_1_mint, self.token_0_mint, self.token_1_vault, self.token_0_vault)
+        };
+
+        CpSwapSwapAccounts {
+            user,
+            authority: self.authority,
+            amm_config,
+            pool_state: self.pool_state,
+            input_token_account: get_associated_token_address(&user, &input_mint),
+            output_token_account: get_associated_token_address(&user, &output_mint),
+            input_vault,
+            output_vault,
+            input_token_program: token::ID,
+            output_token_program: token::ID,
+            input_mint,
+            output_mint,
+            observation_state: self.observation_state,
+        }
+    }
+}
+
+fn pubkey_at(data: &[u8], offset: usize) -> Option<Pubkey> {
+    data.get(offset..offset + 32).and_then(|bytes| Pubkey::try_from(bytes).ok())
+}
+
+fn u64_at(data: &[u8], offset: usize) -> Option<u64> {
+    data.get(offset..offset + 8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+/// Fields of a CP-Swap `PoolState` account needed to quote and route swaps.
+/// The account is zero-copy and packed:
+/// amm_config (8) | pool_creator (40) | token_0_vault (72) | token_1_vault (104)
+/// | lp_mint (136) | token_0_mint (168) | token_1_mint (200) | token_0_program (232)
+/// | token_1_program (264) | observation_key (296) | 5 x u8 (328) | lp_supply (333)
+/// | protocol_fees_token_0 (341) | protocol_fees_token_1 (349)
+/// | fund_fees_token_0 (357) | fund_fees_token_1 (365)
+#[derive(Debug, Clone, Copy)]
+pub struct PoolStateInfo {
+    pub amm_config: Pubkey,
+    pub token_0_vault: Pubkey,
+    pub token_1_vault: Pubkey,
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+    pub token_0_program: Pubkey,
+    pub token_1_program: Pubkey,
+    pub observation_key: Pubkey,
+    /// Fees held in the vaults that are not part of the reserves
+    pub unclaimed_fees_0: u64,
+    pub unclaimed_fees_1: u64,
+}
+
+impl PoolStateInfo {
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        Some(Self {
+            amm_config: pubkey_at(data, 8)?,
+            token_0_vault: pubkey_at(data, 72)?,
+            token_1_vault: pubkey_at(data, 104)?,
+            token_0_mint: pubkey_at(data, 168)?,
+            token_1_mint: pubkey_at(data, 200)?,
+            token_0_program: pubkey_at(data, 232)?,
+            token_1_program: pubkey_at(data, 264)?,
+            observation_key: pubkey_at(data, 296)?,
+            unclaimed_fees_0: u64_at(data, 341)?.checked_add(u64_at(data, 357)?)?,
+            unclaimed_fees_1: u64_at(data, 349)?.checked_add(u64_at(data, 365)?)?,
+        })
+    }
+
+    /// Swap accounts for a trade selling `input_mint` between the given
+    /// token accounts
+    pub fn swap_accounts(
+        &self,
+        program_id: &Pubkey,
+        pool_state: Pubkey,
+        user: Pubkey,
+        input_mint: Pubkey,
+        input_token_account: Pubkey,
+        output_token_account: Pubkey,
+    ) -> CpSwapSwapAccounts {
+        let (output_mint, input_vault, output_vault, input_program, output_program) =
+            if input_mint == self.token_0_mint {
+                (self.token_1_mint, self.token_0_vault, self.token_1_vault, self.token_0_program, self.token_1_program)
+            } else {
+                (self.token_0_mint, self.token_1_vault, self.token_0_vault, self.token_1_program, self.token_0_program)
+            };
+
+        CpSwapSwapAccounts {
+            user,
+            authority: find_authority(program_id),
+            amm_config: self.amm_config,
+            pool_state,
+            input_token_account,
+            output_token_account,
+            input_vault,
+            output_vault,
+            input_token_program: input_program,
+            output_token_program: output_program,
+            input_mint,
+            output_mint,
+            observation_state: self.observation_key,
+        }
+    }
+}
+
+/// Size of a CP-Swap `AmmConfig` account: discriminator | bump (u8)
+/// | disable_create_pool (bool) | index (u16) | trade_fee_rate (u64)
+/// | protocol_fee_rate (u64) | fund_fee_rate (u64) | create_pool_fee (u64)
+/// | protocol_owner | fund_owner | padding ([u64; 16])
+pub const AMM_CONFIG_LEN: usize = 8 + 1 + 1 + 2 + 8 * 4 + 32 * 2 + 8 * 16;
+
+/// Data of an `AmmConfig` account with the given trade fee and no protocol,
+/// fund or creation fees. Only the CP-Swap admin can create configs, so
+/// local deployments inject this at genesis instead.
+pub fn amm_config_data(program_id: &Pubkey, index: u16, trade_fee_rate: u64) -> Vec<u8> {
+    let (_, bump) = Pubkey::find_program_address(&[AMM_CONFIG_SEED, &index.to_be_bytes()], program_id);
+    let mut data = Vec::with_capacity(AMM_CONFIG_LEN);
+    data.extend_from_slice(&hash(b"account:AmmConfig").to_bytes()[..8]);
+    data.push(bump);
+    data.push(0);
+    data.extend_from_slice(&index.to_le_bytes());
+    data.extend_from_slice(&trade_fee_rate.to_le_bytes());
+    data.resize(AMM_CONFIG_LEN, 0);
+    data
+}
+
+/// Trade fee rate of a CP-Swap `AmmConfig` account:
+/// bump (u8) | disable_create_pool (bool) | index (u16) | trade_fee_rate (u64) | ...
+pub fn parse_trade_fee_rate(amm_config_data: &[u8]) -> Option<u64> {
+    u64_at(amm_config_data, 12)
+}
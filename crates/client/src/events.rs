@@ -0,0 +1,628 @@
+//! Decoding of Continuum events from transaction logs and event-CPI data.
+//!
+//! Events are matched on their Anchor discriminator. Events about a pool are
+//! numbered among the pool's events by `event_sequence`, so an indexer can
+//! detect gaps per pool; `PoolRegistered` and the admin events outside any
+//! pool carry no number. Layouts that predate the event sequence are still
+//! accepted and reported as [`EventVersion::V0`], with `event_sequence` set
+//! to 0. `OrderExecuted` events from before fills
+//! carried their price and fee breakdown are reported as [`EventVersion::V1`],
+//! with those fields set to 0. Order events from before they carried a trace
+//! ID are reported as [`EventVersion::V2`], and all legacy order events get
+//! the trace ID derived from their sequence, see [`legacy_trace_id`]. Legacy
+//! order events other than `OrderSubmitted` didn't name their pool and are
+//! reported with `pool_id` set to the default key. `OrderSubmitted` events from before they carried a client order ID
+//! and memo are reported as [`EventVersion::V3`], without either.
+
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::prelude::{AnchorDeserialize, Pubkey};
+use anchor_lang::Discriminator;
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+pub use continuum_cp_swap::instructions::SwapExecuted;
+pub use continuum_cp_swap::state::{
+    CheckpointRecorded, CrossChainOrderReceived, DcaTickExecuted, ExecutionRejected, FeesAccrued, FillAttested,
+    InsurancePaidOut, OrderArchived, OrderCompensated, OrderCancelled, OrderExecuted, OrderExpired, OrderModified, OrderPartiallyFilled, OrderSubmitted, PauseSet, PoolRegistered,
+    ProtocolFeesWithdrawn, RejectionReason, RelayerAdded, RelayerFeesClaimed, RelayerRemoved,
+    TrailUpdated, TwapSliceExecuted,
+};
+use continuum_cp_swap::state::TraceId;
+
+use crate::trace::legacy_trace_id;
+use crate::PROGRAM_ID;
+
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// Layout version an event was decoded from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventVersion {
+    /// Original layout, without `event_sequence`
+    V0,
+    /// With `event_sequence`, before `OrderExecuted` carried its fill breakdown
+    V1,
+    /// With the fill breakdown, before order events carried a trace ID
+    V2,
+    /// With trace IDs, before `OrderSubmitted` carried a client order ID
+    /// and memo
+    V3,
+    /// Current layout
+    V4,
+}
+
+/// A decoded Continuum event
+pub enum ContinuumEvent {
+    OrderSubmitted(OrderSubmitted),
+    OrderExecuted(OrderExecuted),
+    OrderCancelled(OrderCancelled),
+    SwapExecuted(SwapExecuted),
+    PoolRegistered(PoolRegistered),
+    OrderArchived(OrderArchived),
+    CheckpointRecorded(CheckpointRecorded),
+    FeesAccrued(FeesAccrued),
+    RelayerFeesClaimed(RelayerFeesClaimed),
+    ProtocolFeesWithdrawn(ProtocolFeesWithdrawn),
+    InsurancePaidOut(InsurancePaidOut),
+    OrderCompensated(OrderCompensated),
+    RelayerAdded(RelayerAdded),
+    RelayerRemoved(RelayerRemoved),
+    CrossChainOrderReceived(CrossChainOrderReceived),
+    FillAttested(FillAttested),
+    TrailUpdated(TrailUpdated),
+    PauseSet(PauseSet),
+    OrderExpired(OrderExpired),
+    TwapSliceExecuted(TwapSliceExecuted),
+    OrderPartiallyFilled(OrderPartiallyFilled),
+    DcaTickExecuted(DcaTickExecuted),
+    OrderModified(OrderModified),
+    /// Only found in the logs of failed transactions
+    ExecutionRejected(ExecutionRejected),
+}
+
+impl ContinuumEvent {
+    /// Number of the event among its pool's events; 0 for V0 events,
+    /// rejections and events outside any pool
+    pub fn event_sequence(&self) -> u64 {
+        match self {
+            Self::OrderSubmitted(e) => e.event_sequence,
+            Self::OrderExecuted(e) => e.event_sequence,
+            Self::OrderCancelled(e) => e.event_sequence,
+            Self::SwapExecuted(e) => e.event_sequence,
+            Self::PoolRegistered(_) => 0,
+            Self::OrderArchived(e) => e.event_sequence,
+            Self::CheckpointRecorded(e) => e.event_sequence,
+            Self::FeesAccrued(e) => e.event_sequence,
+            Self::RelayerFeesClaimed(e) => e.event_sequence,
+            Self::ProtocolFeesWithdrawn(e) => e.event_sequence,
+            Self::InsurancePaidOut(e) => e.event_sequence,
+            Self::OrderCompensated(e) => e.event_sequence,
+            Self::RelayerAdded(_) => 0,
+            Self::RelayerRemoved(_) => 0,
+            Self::CrossChainOrderReceived(e) => e.event_sequence,
+            Self::FillAttested(e) => e.event_sequence,
+            Self::TrailUpdated(e) => e.event_sequence,
+            Self::PauseSet(_) => 0,
+            Self::OrderExpired(e) => e.event_sequence,
+            Self::TwapSliceExecuted(e) => e.event_sequence,
+            Self::OrderPartiallyFilled(e) => e.event_sequence,
+            Self::DcaTickExecuted(e) => e.event_sequence,
+            Self::OrderModified(e) => e.event_sequence,
+            Self::ExecutionRejected(_) => 0,
+        }
+    }
+}
+
+/// An event together with the layout it was decoded from
+pub struct ParsedEvent {
+    pub version: EventVersion,
+    pub event: ContinuumEvent,
+}
+
+/// Decode one event from its serialized form (discriminator followed by the
+/// borsh-encoded fields). Returns `None` for unknown discriminators or
+/// malformed data.
+pub fn decode_event(data: &[u8]) -> Option<ParsedEvent> {
+    if data.len() < 8 {
+        return None;
+    }
+    let (discriminator, body) = data.split_at(8);
+
+    macro_rules! current {
+        ($($ty:ident),* $(,)?) => {
+            $(
+                if discriminator == $ty::DISCRIMINATOR {
+                    return match $ty::try_from_slice(body) {
+                        Ok(event) => Some(ParsedEvent {
+                            version: EventVersion::V4,
+                            event: ContinuumEvent::$ty(event),
+                        }),
+                        Err(_) => legacy::decode(discriminator, body),
+                    };
+                }
+            )*
+        };
+    }
+
+    current!(
+        OrderSubmitted,
+        OrderExecuted,
+        OrderCancelled,
+        SwapExecuted,
+        PoolRegistered,
+        OrderArchived,
+        CheckpointRecorded,
+        FeesAccrued,
+        RelayerFeesClaimed,
+        ProtocolFeesWithdrawn,
+        InsurancePaidOut,
+        OrderCompensated,
+        RelayerAdded,
+        RelayerRemoved,
+        CrossChainOrderReceived,
+        FillAttested,
+        TrailUpdated,
+        PauseSet,
+        OrderExpired,
+        TwapSliceExecuted,
+        OrderPartiallyFilled,
+        DcaTickExecuted,
+        OrderModified,
+        ExecutionRejected,
+    );
+
+    None
+}
+
+/// Decode an event emitted through event-CPI, given the inner instruction data
+pub fn decode_event_cpi(ix_data: &[u8]) -> Option<ParsedEvent> {
+    ix_data
+        .strip_prefix(EVENT_IX_TAG_LE)
+        .and_then(decode_event)
+}
+
+/// The first rejection in a failed transaction's log messages, telling why
+/// `execute_order` refused it
+pub fn find_rejection<S: AsRef<str>>(logs: &[S]) -> Option<ExecutionRejected> {
+    parse_logs(logs).into_iter().find_map(|parsed| match parsed.event {
+        ContinuumEvent::ExecutionRejected(e) => Some(e),
+        _ => None,
+    })
+}
+
+/// Decode all Continuum events in a transaction's log messages. Only
+/// `Program data:` lines emitted while the Continuum program is the innermost
+/// executing program are considered.
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Vec<ParsedEvent> {
+    let program_id = PROGRAM_ID.to_string();
+    let mut stack: Vec<String> = Vec::new();
+    let mut events = Vec::new();
+
+    for line in logs.iter().map(AsRef::as_ref) {
+        if let Some(rest) = line.strip_prefix("Program ") {
+            let mut parts = rest.split_whitespace();
+            let (Some(id), Some(action)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            match action {
+                "invoke" => {
+                    stack.push(id.to_string());
+                    continue;
+                }
+                "success" | "failed:" => {
+                    stack.pop();
+                    continue;
+                }
+                _ => {}
+            }
+        }
+
+        if stack.last() != Some(&program_id) {
+            continue;
+        }
+        if let Some(encoded) = line.strip_prefix(PROGRAM_DATA_PREFIX) {
+            if let Some(event) = STANDARD.decode(encoded.trim()).ok().and_then(|d| decode_event(&d)) {
+                events.push(event);
+            }
+        }
+    }
+
+    events
+}
+
+/// Layouts emitted before the current one: V3 for `OrderSubmitted` without
+/// a client order ID or memo, V2 for order events without a
+/// trace ID, V1 for `OrderExecuted` without its fill breakdown, V0 for events
+/// without a sequence number
+mod legacy {
+    use super::*;
+    use anchor_lang::prelude::borsh;
+
+    #[derive(AnchorDeserialize)]
+    struct OrderSubmittedV0 {
+        sequence: u64,
+        user: Pubkey,
+        pool_id: Pubkey,
+        amount_in: u64,
+        is_base_input: bool,
+    }
+
+    #[derive(AnchorDeserialize)]
+    struct OrderSubmittedV3 {
+        event_sequence: u64,
+        sequence: u64,
+        user: Pubkey,
+        pool_id: Pubkey,
+        amount_in: u64,
+        is_base_input: bool,
+        trace_id: TraceId,
+    }
+
+    #[derive(AnchorDeserialize)]
+    struct OrderSubmittedV2 {
+        event_sequence: u64,
+        sequence: u64,
+        user: Pubkey,
+        pool_id: Pubkey,
+        amount_in: u64,
+        is_base_input: bool,
+    }
+
+    #[derive(AnchorDeserialize)]
+    struct OrderExecutedV2 {
+        event_sequence: u64,
+        sequence: u64,
+        user: Pubkey,
+        amount_out: u64,
+        executor: Pubkey,
+        amount_in: u64,
+        price: u64,
+        protocol_fee: u64,
+        relayer_fee: u64,
+        rebate: u64,
+    }
+
+    #[derive(AnchorDeserialize)]
+    struct OrderCancelledV2 {
+        event_sequence: u64,
+        sequence: u64,
+        user: Pubkey,
+    }
+
+    #[derive(AnchorDeserialize)]
+    struct ExecutionRejectedV2 {
+        sequence: u64,
+        executor: Pubkey,
+        reason: RejectionReason,
+    }
+
+    #[derive(AnchorDeserialize)]
+    struct OrderExecutedV1 {
+        event_sequence: u64,
+        sequence: u64,
+        user: Pubkey,
+        amount_out: u64,
+        executor: Pubkey,
+    }
+
+    #[derive(AnchorDeserialize)]
+    struct OrderExecutedV0 {
+        sequence: u64,
+        user: Pubkey,
+        amount_out: u64,
+        executor: Pubkey,
+    }
+
+    #[derive(AnchorDeserialize)]
+    struct OrderCancelledV0 {
+        sequence: u64,
+        user: Pubkey,
+    }
+
+    #[derive(AnchorDeserialize)]
+    struct SwapExecutedV0 {
+        sequence: u64,
+        pool_id: Pubkey,
+        amount_in: u64,
+        is_base_input: bool,
+    }
+
+    pub(super) fn decode(discriminator: &[u8], body: &[u8]) -> Option<ParsedEvent> {
+        if discriminator == OrderSubmitted::DISCRIMINATOR {
+            if let Ok(e) = OrderSubmittedV3::try_from_slice(body) {
+                return Some(ParsedEvent {
+                    version: EventVersion::V3,
+                    event: ContinuumEvent::OrderSubmitted(OrderSubmitted {
+                        event_sequence: e.event_sequence,
+                        sequence: e.sequence,
+                        user: e.user,
+                        pool_id: e.pool_id,
+                        amount_in: e.amount_in,
+                        is_base_input: e.is_base_input,
+                        trace_id: e.trace_id,
+                        client_order_id: None,
+                        memo: None,
+                    }),
+                });
+            }
+        }
+
+        if let Some(event) = decode_v2(discriminator, body) {
+            return Some(ParsedEvent {
+                version: EventVersion::V2,
+                event,
+            });
+        }
+
+        if discriminator == OrderExecuted::DISCRIMINATOR {
+            if let Ok(e) = OrderExecutedV1::try_from_slice(body) {
+                return Some(ParsedEvent {
+                    version: EventVersion::V1,
+                    event: ContinuumEvent::OrderExecuted(OrderExecuted {
+                        event_sequence: e.event_sequence,
+                        sequence: e.sequence,
+                        pool_id: Pubkey::default(),
+                        user: e.user,
+                        amount_out: e.amount_out,
+                        executor: e.executor,
+                        amount_in: 0,
+                        price: 0,
+                        protocol_fee: 0,
+                        relayer_fee: 0,
+                        rebate: 0,
+                        trace_id: legacy_trace_id(e.sequence),
+                    }),
+                });
+            }
+        }
+
+        let event = if discriminator == OrderSubmitted::DISCRIMINATOR {
+            let e = OrderSubmittedV0::try_from_slice(body).ok()?;
+            ContinuumEvent::OrderSubmitted(OrderSubmitted {
+                event_sequence: 0,
+                sequence: e.sequence,
+                user: e.user,
+                pool_id: e.pool_id,
+                amount_in: e.amount_in,
+                is_base_input: e.is_base_input,
+                trace_id: legacy_trace_id(e.sequence),
+                client_order_id: None,
+                memo: None,
+            })
+        } else if discriminator == OrderExecuted::DISCRIMINATOR {
+            let e = OrderExecutedV0::try_from_slice(body).ok()?;
+            ContinuumEvent::OrderExecuted(OrderExecuted {
+                event_sequence: 0,
+                sequence: e.sequence,
+                pool_id: Pubkey::default(),
+                user: e.user,
+                amount_out: e.amount_out,
+                executor: e.executor,
+                amount_in: 0,
+                price: 0,
+                protocol_fee: 0,
+                relayer_fee: 0,
+                rebate: 0,
+                trace_id: legacy_trace_id(e.sequence),
+            })
+        } else if discriminator == OrderCancelled::DISCRIMINATOR {
+            let e = OrderCancelledV0::try_from_slice(body).ok()?;
+            ContinuumEvent::OrderCancelled(OrderCancelled {
+                event_sequence: 0,
+                sequence: e.sequence,
+                pool_id: Pubkey::default(),
+                user: e.user,
+                trace_id: legacy_trace_id(e.sequence),
+            })
+        } else if discriminator == SwapExecuted::DISCRIMINATOR {
+            let e = SwapExecutedV0::try_from_slice(body).ok()?;
+            ContinuumEvent::SwapExecuted(SwapExecuted {
+                event_sequence: 0,
+                sequence: e.sequence,
+                user: Pubkey::default(),
+                pool_id: e.pool_id,
+                amount_in: e.amount_in,
+                is_base_input: e.is_base_input,
+            })
+        } else {
+            return None;
+        };
+
+        Some(ParsedEvent {
+            version: EventVersion::V0,
+            event,
+        })
+    }
+    fn decode_v2(discriminator: &[u8], body: &[u8]) -> Option<ContinuumEvent> {
+        let event = if discriminator == OrderSubmitted::DISCRIMINATOR {
+            let e = OrderSubmittedV2::try_from_slice(body).ok()?;
+            ContinuumEvent::OrderSubmitted(OrderSubmitted {
+                event_sequence: e.event_sequence,
+                sequence: e.sequence,
+                user: e.user,
+                pool_id: e.pool_id,
+                amount_in: e.amount_in,
+                is_base_input: e.is_base_input,
+                trace_id: legacy_trace_id(e.sequence),
+                client_order_id: None,
+                memo: None,
+            })
+        } else if discriminator == OrderExecuted::DISCRIMINATOR {
+            let e = OrderExecutedV2::try_from_slice(body).ok()?;
+            ContinuumEvent::OrderExecuted(OrderExecuted {
+                event_sequence: e.event_sequence,
+                sequence: e.sequence,
+                pool_id: Pubkey::default(),
+                user: e.user,
+                amount_out: e.amount_out,
+                executor: e.executor,
+                amount_in: e.amount_in,
+                price: e.price,
+                protocol_fee: e.protocol_fee,
+                relayer_fee: e.relayer_fee,
+                rebate: e.rebate,
+                trace_id: legacy_trace_id(e.sequence),
+            })
+        } else if discriminator == OrderCancelled::DISCRIMINATOR {
+            let e = OrderCancelledV2::try_from_slice(body).ok()?;
+            ContinuumEvent::OrderCancelled(OrderCancelled {
+                event_sequence: e.event_sequence,
+                sequence: e.sequence,
+                pool_id: Pubkey::default(),
+                user: e.user,
+                trace_id: legacy_trace_id(e.sequence),
+            })
+        } else if discriminator == ExecutionRejected::DISCRIMINATOR {
+            let e = ExecutionRejectedV2::try_from_slice(body).ok()?;
+            ContinuumEvent::ExecutionRejected(ExecutionRejected {
+                sequence: e.sequence,
+                pool_id: Pubkey::default(),
+                executor: e.executor,
+                reason: e.reason,
+                trace_id: legacy_trace_id(e.sequence),
+            })
+        } else {
+            return None;
+        };
+        Some(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{AnchorSerialize, Event};
+
+    fn submitted(event_sequence: u64, sequence: u64) -> OrderSubmitted {
+        OrderSubmitted {
+            event_sequence,
+            sequence,
+            user: Pubkey::new_unique(),
+            pool_id: Pubkey::new_unique(),
+            amount_in: 1_000,
+            is_base_input: true,
+            trace_id: [7; 16],
+            client_order_id: Some([9; 32]),
+            memo: Some("hello".to_string()),
+        }
+    }
+
+    fn data_line(data: &[u8]) -> String {
+        format!("{PROGRAM_DATA_PREFIX}{}", STANDARD.encode(data))
+    }
+
+    fn wrapped(inner: Vec<String>) -> Vec<String> {
+        let mut logs = vec![format!("Program {PROGRAM_ID} invoke [1]")];
+        logs.extend(inner);
+        logs.push(format!("Program {PROGRAM_ID} success"));
+        logs
+    }
+
+    #[test]
+    fn parse_logs_round_trips_events_with_their_event_sequence() {
+        let event = submitted(41, 3);
+        let cancelled = OrderCancelled {
+            event_sequence: 42,
+            sequence: 3,
+            pool_id: event.pool_id,
+            user: event.user,
+            trace_id: event.trace_id,
+        };
+        let logs = wrapped(vec![
+            "Program log: Instruction: SubmitOrder".to_string(),
+            data_line(&event.data()),
+            data_line(&cancelled.data()),
+        ]);
+
+        let parsed = parse_logs(&logs);
+        assert_eq!(parsed.len(), 2);
+        assert!(parsed.iter().all(|p| p.version == EventVersion::V4));
+        assert_eq!(parsed[0].event.event_sequence(), 41);
+        assert_eq!(parsed[1].event.event_sequence(), 42);
+        let ContinuumEvent::OrderSubmitted(ref decoded) = parsed[0].event else {
+            panic!("expected OrderSubmitted");
+        };
+        assert_eq!(decoded.sequence, 3);
+        assert_eq!(decoded.user, event.user);
+        assert_eq!(decoded.pool_id, event.pool_id);
+        assert_eq!(decoded.trace_id, event.trace_id);
+        assert_eq!(decoded.client_order_id, event.client_order_id);
+        assert_eq!(decoded.memo, event.memo);
+    }
+
+    #[test]
+    fn parse_logs_ignores_data_of_other_programs() {
+        let other = Pubkey::new_unique();
+        let logs = wrapped(vec![
+            format!("Program {other} invoke [2]"),
+            data_line(&submitted(1, 1).data()),
+            format!("Program {other} success"),
+            data_line(&submitted(2, 2).data()),
+        ]);
+
+        let parsed = parse_logs(&logs);
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].event.event_sequence(), 2);
+
+        let outside = vec![data_line(&submitted(3, 3).data())];
+        assert!(parse_logs(&outside).is_empty());
+    }
+
+    #[test]
+    fn legacy_layout_decodes_as_v0_without_event_sequence() {
+        let event = submitted(0, 5);
+        let mut data = OrderSubmitted::DISCRIMINATOR.to_vec();
+        // The original layout: sequence, user, pool_id, amount_in, is_base_input
+        (event.sequence, event.user, event.pool_id, event.amount_in, event.is_base_input)
+            .serialize(&mut data)
+            .unwrap();
+
+        let parsed = parse_logs(&wrapped(vec![data_line(&data)]));
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].version, EventVersion::V0);
+        assert_eq!(parsed[0].event.event_sequence(), 0);
+        let ContinuumEvent::OrderSubmitted(ref decoded) = parsed[0].event else {
+            panic!("expected OrderSubmitted");
+        };
+        assert_eq!(decoded.sequence, 5);
+        assert_eq!(decoded.trace_id, legacy_trace_id(5));
+    }
+
+    #[test]
+    fn decode_event_cpi_strips_the_event_tag() {
+        let event = submitted(8, 8);
+        let mut ix_data = EVENT_IX_TAG_LE.to_vec();
+        ix_data.extend(event.data());
+
+        let parsed = decode_event_cpi(&ix_data).unwrap();
+        assert_eq!(parsed.version, EventVersion::V4);
+        assert_eq!(parsed.event.event_sequence(), 8);
+        assert!(decode_event_cpi(&event.data()).is_none());
+    }
+
+    #[test]
+    fn unknown_or_short_data_is_skipped() {
+        assert!(decode_event(&[1, 2, 3]).is_none());
+        assert!(decode_event(&[0; 64]).is_none());
+        let logs = wrapped(vec![data_line(&[0; 64]), "Program data: not base64!".to_string()]);
+        assert!(parse_logs(&logs).is_empty());
+    }
+
+    #[test]
+    fn find_rejection_returns_the_rejection_of_a_failed_execution() {
+        let rejected = ExecutionRejected {
+            sequence: 4,
+            pool_id: Pubkey::new_unique(),
+            executor: Pubkey::new_unique(),
+            reason: RejectionReason::SlippageExceeded,
+            trace_id: [1; 16],
+        };
+        let logs = vec![
+            format!("Program {PROGRAM_ID} invoke [1]"),
+            data_line(&rejected.data()),
+            format!("Program {PROGRAM_ID} failed: custom program error: 0x1771"),
+        ];
+
+        let found = find_rejection(&logs).unwrap();
+        assert_eq!(found.sequence, 4);
+        assert_eq!(found.reason, RejectionReason::SlippageExceeded);
+    }
+}
@@ -0,0 +1,49 @@
+//! Priority fee estimation from recently landed transactions.
+
+use anchor_lang::prelude::Pubkey;
+use solana_client::client_error::ClientError;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::instruction::Instruction;
+
+/// The `percentile` (0-100) of the given prioritization fees, in
+/// micro-lamports per compute unit. Zero when there are no samples.
+pub fn fee_percentile(fees: &[u64], percentile: u8) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+    let mut sorted = fees.to_vec();
+    sorted.sort_unstable();
+    let rank = (sorted.len() - 1) * usize::from(percentile.min(100)) / 100;
+    sorted[rank]
+}
+
+/// Accounts whose write locks a transaction competes for
+pub fn writable_accounts<'a>(instructions: impl IntoIterator<Item = &'a Instruction>) -> Vec<Pubkey> {
+    let mut accounts: Vec<Pubkey> = instructions
+        .into_iter()
+        .flat_map(|ix| ix.accounts.iter())
+        .filter(|meta| meta.is_writable && !meta.is_signer)
+        .map(|meta| meta.pubkey)
+        .collect();
+    accounts.sort_unstable();
+    accounts.dedup();
+    accounts
+}
+
+/// Compute-unit price that would have placed a transaction at `percentile`
+/// among recent transactions writing to any of `accounts`, capped at
+/// `max_price`
+pub async fn estimate_compute_unit_price(
+    rpc: &RpcClient,
+    accounts: &[Pubkey],
+    percentile: u8,
+    max_price: u64,
+) -> Result<u64, ClientError> {
+    let fees: Vec<u64> = rpc
+        .get_recent_prioritization_fees(accounts)
+        .await?
+        .into_iter()
+        .map(|sample| sample.prioritization_fee)
+        .collect();
+    Ok(fee_percentile(&fees, percentile).min(max_price))
+}
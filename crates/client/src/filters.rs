@@ -0,0 +1,254 @@
+//! `getProgramAccounts` filters for enumerating orders.
+//!
+//! Memcmp offsets are into the raw `OrderState` account data, discriminator
+//! included. Only fields ahead of the first variable-length field have a
+//! fixed offset and can be filtered on:
+//!
+//! | offset | size | field            |
+//! |--------|------|------------------|
+//! | 0      | 8    | discriminator    |
+//! | 8      | 8    | sequence         |
+//! | 16     | 32   | user             |
+//! | 48     | 32   | pool_id          |
+//! | 80     | 8    | amount_in        |
+//! | 88     | 8    | min_amount_out   |
+//! | 96     | 1    | is_base_input    |
+//! | 97     | 1    | status           |
+//!
+//! Keep these in sync with `continuum_cp_swap::state::OrderState`.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Discriminator;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+
+use crate::accounts::{decode, OrderState, OrderStatus};
+
+pub const ORDER_DISCRIMINATOR_OFFSET: usize = 0;
+pub const ORDER_SEQUENCE_OFFSET: usize = 8;
+pub const ORDER_USER_OFFSET: usize = 16;
+pub const ORDER_POOL_ID_OFFSET: usize = 48;
+pub const ORDER_IS_BASE_INPUT_OFFSET: usize = 96;
+pub const ORDER_STATUS_OFFSET: usize = 97;
+
+/// A `getProgramAccounts` filter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RpcFilter {
+    DataSize(u64),
+    Memcmp { offset: usize, bytes: Vec<u8> },
+}
+
+impl RpcFilter {
+    pub fn memcmp(offset: usize, bytes: impl Into<Vec<u8>>) -> Self {
+        Self::Memcmp { offset, bytes: bytes.into() }
+    }
+
+    /// Memcmp bytes base64-encoded, for use with `"encoding": "base64"`
+    pub fn encoded_bytes(&self) -> Option<String> {
+        match self {
+            Self::Memcmp { bytes, .. } => Some(STANDARD.encode(bytes)),
+            Self::DataSize(_) => None,
+        }
+    }
+}
+
+impl From<RpcFilter> for RpcFilterType {
+    fn from(filter: RpcFilter) -> Self {
+        match filter {
+            RpcFilter::DataSize(size) => RpcFilterType::DataSize(size),
+            RpcFilter::Memcmp { offset, bytes } => {
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(offset, bytes))
+            }
+        }
+    }
+}
+
+/// Borsh tag of an order status, as stored at [`ORDER_STATUS_OFFSET`]
+pub fn status_tag(status: OrderStatus) -> u8 {
+    match status {
+        OrderStatus::Pending => 0,
+        OrderStatus::Executed => 1,
+        OrderStatus::Cancelled => 2,
+        OrderStatus::Failed => 3,
+        OrderStatus::Expired => 4,
+    }
+}
+
+/// Selects orders by user, pool and status. Unset fields match anything.
+#[derive(Debug, Clone, Default)]
+pub struct OrderQuery {
+    pub user: Option<Pubkey>,
+    pub pool_id: Option<Pubkey>,
+    pub status: Option<OrderStatus>,
+}
+
+impl OrderQuery {
+    /// Pending orders, optionally narrowed further with the setters
+    pub fn pending() -> Self {
+        Self::default().status(OrderStatus::Pending)
+    }
+
+    pub fn user(mut self, user: Pubkey) -> Self {
+        self.user = Some(user);
+        self
+    }
+
+    pub fn pool(mut self, pool_id: Pubkey) -> Self {
+        self.pool_id = Some(pool_id);
+        self
+    }
+
+    pub fn status(mut self, status: OrderStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Filters to pass to `getProgramAccounts` for the Continuum program.
+    /// Always includes the `OrderState` discriminator.
+    pub fn filters(&self) -> Vec<RpcFilter> {
+        let mut filters = vec![RpcFilter::memcmp(
+            ORDER_DISCRIMINATOR_OFFSET,
+            OrderState::DISCRIMINATOR,
+        )];
+        if let Some(user) = self.user {
+            filters.push(RpcFilter::memcmp(ORDER_USER_OFFSET, user.to_bytes()));
+        }
+        if let Some(pool_id) = self.pool_id {
+            filters.push(RpcFilter::memcmp(ORDER_POOL_ID_OFFSET, pool_id.to_bytes()));
+        }
+        if let Some(status) = self.status {
+            filters.push(RpcFilter::memcmp(ORDER_STATUS_OFFSET, [status_tag(status)]));
+        }
+        filters
+    }
+
+    /// Whether a decoded order satisfies the query
+    pub fn matches(&self, order: &OrderState) -> bool {
+        self.user.is_none_or(|user| order.user == user)
+            && self.pool_id.is_none_or(|pool_id| order.pool_id == pool_id)
+            && self.status.is_none_or(|status| order.status == status)
+    }
+
+    /// Decode the accounts returned by `getProgramAccounts`, dropping any
+    /// that fail to decode or don't match the query, sorted by sequence.
+    pub fn decode_orders<I, D>(&self, accounts: I) -> Vec<(Pubkey, OrderState)>
+    where
+        I: IntoIterator<Item = (Pubkey, D)>,
+        D: AsRef<[u8]>,
+    {
+        let mut orders: Vec<_> = accounts
+            .into_iter()
+            .filter_map(|(address, data)| {
+                let order = decode::<OrderState>(data.as_ref()).ok()?;
+                self.matches(&order).then_some((address, order))
+            })
+            .collect();
+        orders.sort_by_key(|(_, order)| order.sequence);
+        orders
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::AccountSerialize;
+    use continuum_cp_swap::state::{ExecutionPolicy, FeeQuote};
+
+    use super::*;
+
+    fn order(sequence: u64, user: Pubkey, pool_id: Pubkey, status: OrderStatus) -> OrderState {
+        OrderState {
+            sequence,
+            user,
+            pool_id,
+            amount_in: 1_000,
+            min_amount_out: 900,
+            is_base_input: true,
+            status,
+            submitted_at: 1_700_000_000,
+            executed_at: None,
+            recipient: None,
+            callback_program: None,
+            destination_account: None,
+            post_fill: None,
+            valid_from: None,
+            source_account: None,
+            parent_order: None,
+            trailing_stop: None,
+            shared_escrow: None,
+            delegated_input: false,
+            permit: None,
+            co_signer: None,
+            fee_quote: FeeQuote { protocol_fee_bps: 5, relayer_fee_bps: 10 },
+            trace_id: [7; 16],
+            escrow_refund: None,
+            expires_at: None,
+            limit_price: None,
+            twap: None,
+            amount_in_remaining: 1_000,
+            amount_out_filled: 0,
+            execution_policy: ExecutionPolicy::GoodTillCancelled,
+            trigger: None,
+            client_order_id: None,
+            input_mint: Pubkey::new_unique(),
+            memo: None,
+        }
+    }
+
+    fn serialize(order: &OrderState) -> Vec<u8> {
+        let mut data = Vec::new();
+        order.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    /// Whether `data` passes every memcmp of `filters`, as the RPC node checks
+    fn passes(filters: &[RpcFilter], data: &[u8]) -> bool {
+        filters.iter().all(|filter| match filter {
+            RpcFilter::Memcmp { offset, bytes } => data.get(*offset..*offset + bytes.len()) == Some(bytes.as_slice()),
+            RpcFilter::DataSize(size) => data.len() as u64 == *size,
+        })
+    }
+
+    #[test]
+    fn offsets_match_borsh_layout() {
+        let (user, pool_id) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let data = serialize(&order(0x0102_0304_0506_0708, user, pool_id, OrderStatus::Cancelled));
+
+        assert_eq!(&data[ORDER_DISCRIMINATOR_OFFSET..8], OrderState::DISCRIMINATOR);
+        assert_eq!(data[ORDER_SEQUENCE_OFFSET..ORDER_SEQUENCE_OFFSET + 8], 0x0102_0304_0506_0708u64.to_le_bytes());
+        assert_eq!(data[ORDER_USER_OFFSET..ORDER_USER_OFFSET + 32], user.to_bytes());
+        assert_eq!(data[ORDER_POOL_ID_OFFSET..ORDER_POOL_ID_OFFSET + 32], pool_id.to_bytes());
+        assert_eq!(data[80..88], 1_000u64.to_le_bytes());
+        assert_eq!(data[88..96], 900u64.to_le_bytes());
+        assert_eq!(data[ORDER_IS_BASE_INPUT_OFFSET], 1);
+        assert_eq!(data[ORDER_STATUS_OFFSET], status_tag(OrderStatus::Cancelled));
+    }
+
+    #[test]
+    fn status_tags_match_borsh() {
+        for status in [OrderStatus::Pending, OrderStatus::Executed, OrderStatus::Cancelled, OrderStatus::Failed, OrderStatus::Expired] {
+            let data = serialize(&order(1, Pubkey::new_unique(), Pubkey::new_unique(), status));
+            assert_eq!(data[ORDER_STATUS_OFFSET], status_tag(status));
+        }
+    }
+
+    #[test]
+    fn filters_select_what_matches_selects() {
+        let (user, pool_id) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let query = OrderQuery::pending().user(user).pool(pool_id);
+        let orders = [
+            order(2, user, pool_id, OrderStatus::Pending),
+            order(1, user, pool_id, OrderStatus::Pending),
+            order(3, user, pool_id, OrderStatus::Executed),
+            order(4, Pubkey::new_unique(), pool_id, OrderStatus::Pending),
+            order(5, user, Pubkey::new_unique(), OrderStatus::Pending),
+        ];
+        let filters = query.filters();
+        for order in &orders {
+            assert_eq!(passes(&filters, &serialize(order)), query.matches(order));
+        }
+
+        let accounts = orders.iter().map(|order| (Pubkey::new_unique(), serialize(order)));
+        let sequences: Vec<_> = query.decode_orders(accounts).into_iter().map(|(_, order)| order.sequence).collect();
+        assert_eq!(sequences, [1, 2]);
+    }
+}
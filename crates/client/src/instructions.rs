@@ -0,0 +1,2311 @@
+//! Typed builders for the Continuum instructions used by traders and relayers.
+
+use std::collections::HashMap;
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use anchor_spl::{memo, token};
+pub use continuum_cp_swap::instructions::{BatchOrder, DcaScheduleParams, OrderTemplateParams, SubmitOrderParams, TrailingStopParams};
+use continuum_cp_swap::intent::OrderIntent;
+use continuum_cp_swap::state::{
+    ClientOrderId, CoSigner, DcaSchedule, ExecutionPolicy, FeeTier, Iceberg, LimitPrice, OrderState, OrderTemplate, PostFillAction, TraceId, TriggerDirection,
+};
+use continuum_cp_swap::{accounts, instruction};
+use solana_ed25519_program::new_ed25519_instruction_with_signature;
+
+use crate::{pda, PROGRAM_ID};
+
+/// Optional behaviour of a submitted order; the default is a plain order
+/// delivering to the user
+#[derive(Debug, Clone, Default)]
+pub struct OrderOptions {
+    /// Owner of the account receiving the output, if not the user
+    pub recipient: Option<Pubkey>,
+    /// Invoked after the order fills, see [`continuum_cp_swap::callback`]
+    pub callback_program: Option<Pubkey>,
+    /// The pool's `CpSwapPoolRegistry::compliance_program`, if it has one
+    pub compliance_program: Option<Pubkey>,
+    /// Exact token account the output is paid into, for paying a third party
+    pub destination_account: Option<Pubkey>,
+    /// Burn or stake the output, see [`continuum_cp_swap::post_fill`]
+    pub post_fill: Option<PostFillAction>,
+    /// Unix timestamp before which the order can't be executed
+    pub valid_from: Option<i64>,
+    /// Order account that must execute before this one can
+    pub parent_order: Option<Pubkey>,
+    /// Hold the order until the price of `oracle` trails back by the stop
+    pub trailing_stop: Option<TrailingStopParams>,
+    /// Price update account a trailing stop follows
+    pub oracle: Option<Pubkey>,
+    /// The user's shared escrow to spend from, which reserves the order's input
+    pub shared_escrow: Option<Pubkey>,
+    /// Spend the input by delegation to the order's escrow PDA, see
+    /// [`approve_order_escrow`], rather than to the pool authority
+    pub delegated_input: bool,
+    /// The user's permit to spend through, see [`create_permit`]; the order
+    /// then spends from the permit's token account
+    pub permit: Option<Pubkey>,
+    /// Key that must sign the submission and/or cancellation with the user;
+    /// it signs this submission when its scope covers it
+    pub co_signer: Option<CoSigner>,
+    /// Trace ID the order's events carry, see [`crate::trace`]; by default
+    /// one derived from its sequence
+    pub trace_id: Option<TraceId>,
+    /// Deposit the input and the most fees it can be charged into the
+    /// order's vault at submission, see [`continuum_cp_swap::order_vault`].
+    /// Required unless the order spends from a shared escrow, a permit, a
+    /// delegated approval or `from_wallet`
+    pub escrow: Option<Escrow>,
+    /// Leave the input in the user's wallet, for the pool authority to spend
+    /// by the user's approval, instead of escrowing it
+    pub from_wallet: bool,
+    /// Seconds after submission the order expires, see [`expire_order`]
+    pub ttl: Option<i64>,
+    /// Good-till-cancelled by default; an immediate-or-cancel order's
+    /// unfilled rest is cancelled, see [`cancel_ioc_order`]
+    pub execution_policy: Option<ExecutionPolicy>,
+    /// The submitter's own identifier for the order, carried by it and its
+    /// `OrderSubmitted` event
+    pub client_order_id: Option<ClientOrderId>,
+    /// Free text carried likewise, at most
+    /// [`continuum_cp_swap::consts::MAX_ORDER_MEMO_LEN`] bytes
+    pub memo: Option<String>,
+}
+
+/// Where an escrowed order's deposit comes from
+#[derive(Debug, Clone, Copy)]
+pub struct Escrow {
+    /// The user's token account of the order's input mint, which is also
+    /// refunded
+    pub source: Pubkey,
+}
+
+/// The vault of an escrowed order, passed to cancel, expire or re-queue it
+#[derive(Debug, Clone, Copy)]
+pub struct OrderVault {
+    pub pool_id: Pubkey,
+    /// `OrderState::escrow_refund`
+    pub refund: Pubkey,
+    /// `OrderState::input_mint`, which a re-queued order's new vault holds
+    pub input_mint: Pubkey,
+}
+
+impl OrderVault {
+    /// The vault of `order`, if it is escrowed
+    pub fn of(order: &OrderState) -> Option<Self> {
+        order.escrow_refund.map(|refund| Self {
+            pool_id: order.pool_id,
+            refund,
+            input_mint: order.input_mint,
+        })
+    }
+}
+
+/// Submit an order selling `input_mint`, one of the pool's.
+/// `current_sequence` is `PoolFifoState::current_sequence` of `pool_id` read
+/// before submission; the order account is derived from the sequence after
+/// it, which the order gets.
+#[allow(clippy::too_many_arguments)]
+pub fn submit_order(
+    user: Pubkey,
+    pool_id: Pubkey,
+    input_mint: Pubkey,
+    current_sequence: u64,
+    amount_in: u64,
+    min_amount_out: u64,
+    is_base_input: bool,
+    options: OrderOptions,
+) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: submit_order_accounts(user, pool_id, input_mint, current_sequence, &options).to_account_metas(None),
+        data: instruction::SubmitOrder {
+            amount_in,
+            min_amount_out,
+            is_base_input,
+            params: SubmitOrderParams {
+                recipient: options.recipient,
+                callback_program: options.callback_program,
+                destination_account: options.destination_account,
+                post_fill: options.post_fill,
+                valid_from: options.valid_from,
+                parent_order: options.parent_order,
+                trailing_stop: options.trailing_stop,
+                delegated_input: options.delegated_input,
+                co_signer: options.co_signer,
+                trace_id: options.trace_id,
+                ttl: options.ttl,
+                execution_policy: options.execution_policy,
+                client_order_id: options.client_order_id,
+                memo: options.memo,
+                from_wallet: options.from_wallet,
+            },
+        }
+        .data(),
+    }
+}
+
+/// Submit a limit order, held until the pool's spot price reaches
+/// `limit_price`. `current_sequence` is as for [`submit_order`]. Of
+/// `options`, only the recipient, compliance program, trace ID, TTL and the
+/// shared escrow, permit or escrow funding the order apply.
+#[allow(clippy::too_many_arguments)]
+pub fn submit_limit_order(
+    user: Pubkey,
+    pool_id: Pubkey,
+    input_mint: Pubkey,
+    current_sequence: u64,
+    amount_in: u64,
+    min_amount_out: u64,
+    is_base_input: bool,
+    limit_price: LimitPrice,
+    options: OrderOptions,
+) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: submit_order_accounts(user, pool_id, input_mint, current_sequence, &options).to_account_metas(None),
+        data: instruction::SubmitLimitOrder {
+            amount_in,
+            min_amount_out,
+            is_base_input,
+            limit_price,
+            recipient: options.recipient,
+            trace_id: options.trace_id,
+            ttl: options.ttl,
+        }
+        .data(),
+    }
+}
+
+/// Submit an exact input order sold in `num_slices` slices at least
+/// `interval_seconds` apart. `current_sequence` is as for [`submit_order`].
+/// `options` must carry the escrow the order is deposited from; of the rest,
+/// only the recipient, compliance program, trace ID and TTL apply.
+#[allow(clippy::too_many_arguments)]
+pub fn submit_twap_order(
+    user: Pubkey,
+    pool_id: Pubkey,
+    input_mint: Pubkey,
+    current_sequence: u64,
+    amount_in: u64,
+    min_amount_out: u64,
+    num_slices: u16,
+    interval_seconds: i64,
+    options: OrderOptions,
+) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: submit_order_accounts(user, pool_id, input_mint, current_sequence, &options).to_account_metas(None),
+        data: instruction::SubmitTwapOrder {
+            amount_in,
+            min_amount_out,
+            num_slices,
+            interval_seconds,
+            recipient: options.recipient,
+            trace_id: options.trace_id,
+            ttl: options.ttl,
+        }
+        .data(),
+    }
+}
+
+/// Submit a stop-loss or take-profit order, held until the price of
+/// `oracle`, a Pyth price update account, crosses `trigger_price` in
+/// `direction`. `current_sequence` is as for [`submit_order`]. Of `options`,
+/// only the recipient, compliance program, trace ID, TTL and the shared
+/// escrow, permit or escrow funding the order apply.
+#[allow(clippy::too_many_arguments)]
+pub fn submit_trigger_order(
+    user: Pubkey,
+    pool_id: Pubkey,
+    input_mint: Pubkey,
+    current_sequence: u64,
+    amount_in: u64,
+    min_amount_out: u64,
+    is_base_input: bool,
+    oracle: Pubkey,
+    trigger_price: i64,
+    direction: TriggerDirection,
+    options: OrderOptions,
+) -> Instruction {
+    let options = OrderOptions {
+        oracle: Some(oracle),
+        ..options
+    };
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: submit_order_accounts(user, pool_id, input_mint, current_sequence, &options).to_account_metas(None),
+        data: instruction::SubmitTriggerOrder {
+            amount_in,
+            min_amount_out,
+            is_base_input,
+            trigger_price,
+            direction,
+            recipient: options.recipient,
+            trace_id: options.trace_id,
+            ttl: options.ttl,
+        }
+        .data(),
+    }
+}
+
+fn submit_order_accounts(
+    user: Pubkey,
+    pool_id: Pubkey,
+    input_mint: Pubkey,
+    current_sequence: u64,
+    options: &OrderOptions,
+) -> accounts::SubmitOrder {
+    let (compliance_program, compliance_record) = compliance_accounts(options.compliance_program, &user);
+    let order = pda::find_order(&pool_id, current_sequence + 1).0;
+    let escrow = options.escrow;
+    accounts::SubmitOrder {
+        fifo_state: pda::find_fifo_state().0,
+        pool_registry: pda::find_pool_registry(&pool_id).0,
+        pool_fifo: pda::find_pool_fifo(&pool_id).0,
+        order_state: order,
+        user_ordering: pda::find_user_ordering(&user).0,
+        user,
+        pool_id,
+        system_program: system_program::ID,
+        clock: sysvar::clock::ID,
+        compliance_program,
+        compliance_record,
+        oracle: options.oracle,
+        shared_escrow: options.shared_escrow,
+        shared_escrow_vault: options
+            .shared_escrow
+            .map(|shared_escrow| pda::find_shared_escrow_vault(&shared_escrow).0),
+        permit: options.permit,
+        co_signer: options
+            .co_signer
+            .filter(|co_signer| co_signer.scope.on_submission())
+            .map(|co_signer| co_signer.key),
+        order_vault: escrow.map(|_| pda::find_order_vault(&order).0),
+        input_mint,
+        escrow_source: escrow.map(|escrow| escrow.source),
+        pool_authority: escrow.map(|_| pda::find_pool_authority(&pool_id).0),
+        token_program: escrow.map(|_| token::ID),
+    }
+}
+
+/// Approve the escrow PDA of the order at `order` to spend up to `allowance`
+/// from `user_source`, for an order with `OrderOptions::delegated_input`.
+/// The allowance must cover fees as well as the input; execution returns
+/// what is unspent and clears the approval.
+pub fn approve_order_escrow(user: Pubkey, user_source: Pubkey, order: Pubkey, allowance: u64) -> Instruction {
+    token::spl_token::instruction::approve(
+        &token::ID,
+        &user_source,
+        &pda::find_order_escrow(&order).0,
+        &user,
+        &[],
+        allowance,
+    )
+    // Only fails for a token program other than SPL Token
+    .unwrap()
+}
+
+/// Submit up to `MAX_BATCH_ORDERS` orders, each at the next sequence of its
+/// pool. `current_sequences` holds `PoolFifoState::current_sequence` of each
+/// of their pools read before submission; each order account is derived
+/// from the sequence it gets.
+pub fn submit_orders(user: Pubkey, current_sequences: &HashMap<Pubkey, u64>, orders: Vec<BatchOrder>) -> Instruction {
+    let accounts = accounts::SubmitOrders {
+        fifo_state: pda::find_fifo_state().0,
+        user_ordering: pda::find_user_ordering(&user).0,
+        user,
+        system_program: system_program::ID,
+        clock: sysvar::clock::ID,
+    };
+
+    let mut metas = accounts.to_account_metas(None);
+    let mut sequences = current_sequences.clone();
+    for order in &orders {
+        let sequence = sequences.entry(order.pool_id).or_default();
+        *sequence += 1;
+        metas.push(AccountMeta::new_readonly(pda::find_pool_registry(&order.pool_id).0, false));
+        metas.push(AccountMeta::new(pda::find_pool_fifo(&order.pool_id).0, false));
+        metas.push(AccountMeta::new(pda::find_order(&order.pool_id, *sequence).0, false));
+    }
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: metas,
+        data: instruction::SubmitOrders { orders }.data(),
+    }
+}
+
+/// Create a repeating order on `pool_id` selling `mint`, moving `deposit`
+/// from `user_source` into the template's escrow
+pub fn create_order_template(
+    user: Pubkey,
+    pool_id: Pubkey,
+    template_id: u64,
+    mint: Pubkey,
+    user_source: Pubkey,
+    params: OrderTemplateParams,
+    deposit: u64,
+) -> Instruction {
+    let template = pda::find_order_template(&user, template_id).0;
+    let accounts = accounts::CreateOrderTemplate {
+        fifo_state: pda::find_fifo_state().0,
+        pool_registry: pda::find_pool_registry(&pool_id).0,
+        template,
+        mint,
+        escrow: pda::find_template_escrow(&template).0,
+        pool_authority: pda::find_pool_authority(&pool_id).0,
+        user_source,
+        user,
+        token_program: token::ID,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::CreateOrderTemplate { template_id, params, deposit }.data(),
+    }
+}
+
+/// Enqueue the next occurrence of `template` at the order PDA for the
+/// sequence after `current_sequence`, its pool's, as any payer
+pub fn materialize_order_template(payer: Pubkey, template: &OrderTemplate, current_sequence: u64) -> Instruction {
+    let address = pda::find_order_template(&template.user, template.template_id).0;
+    let accounts = accounts::MaterializeOrderTemplate {
+        fifo_state: pda::find_fifo_state().0,
+        template: address,
+        pool_registry: pda::find_pool_registry(&template.pool_id).0,
+        pool_fifo: pda::find_pool_fifo(&template.pool_id).0,
+        escrow: pda::find_template_escrow(&address).0,
+        order_state: pda::find_order(&template.pool_id, current_sequence + 1).0,
+        user_ordering: pda::find_user_ordering(&template.user).0,
+        payer,
+        system_program: system_program::ID,
+        clock: sysvar::clock::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::MaterializeOrderTemplate {}.data(),
+    }
+}
+
+/// Stop a template, refunding its escrow into `user_destination`
+pub fn close_order_template(user: Pubkey, pool_id: Pubkey, template_id: u64, user_destination: Pubkey) -> Instruction {
+    let template = pda::find_order_template(&user, template_id).0;
+    let accounts = accounts::CloseOrderTemplate {
+        template,
+        escrow: pda::find_template_escrow(&template).0,
+        pool_authority: pda::find_pool_authority(&pool_id).0,
+        user_destination,
+        user,
+        token_program: token::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::CloseOrderTemplate {}.data(),
+    }
+}
+
+/// Create a DCA schedule on `pool_id` selling `mint` for the pool's other
+/// token into `destination`, moving `budget` from `user_source` into the
+/// schedule's escrow
+#[allow(clippy::too_many_arguments)]
+pub fn create_dca_schedule(
+    user: Pubkey,
+    pool_id: Pubkey,
+    schedule_id: u64,
+    mint: Pubkey,
+    user_source: Pubkey,
+    destination: Pubkey,
+    params: DcaScheduleParams,
+    budget: u64,
+) -> Instruction {
+    let schedule = pda::find_dca_schedule(&user, schedule_id).0;
+    let accounts = accounts::CreateDcaSchedule {
+        fifo_state: pda::find_fifo_state().0,
+        pool_registry: pda::find_pool_registry(&pool_id).0,
+        schedule,
+        mint,
+        escrow: pda::find_dca_escrow(&schedule).0,
+        pool_authority: pda::find_pool_authority(&pool_id).0,
+        user_source,
+        destination,
+        user,
+        token_program: token::ID,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::CreateDcaSchedule { schedule_id, params, budget }.data(),
+    }
+}
+
+/// Execute the due tick of `schedule` as `executor`. `swap` are the pool's
+/// swap accounts out of the schedule's input mint; its user token accounts
+/// are replaced by the schedule's escrow and destination.
+/// `with_relayer_info` is as for [`ExecuteOrderParams`].
+pub fn execute_dca_tick(
+    executor: Pubkey,
+    cp_swap_program: Pubkey,
+    schedule: &DcaSchedule,
+    swap: &CpSwapSwapAccounts,
+    with_relayer_info: bool,
+) -> Instruction {
+    let address = pda::find_dca_schedule(&schedule.user, schedule.schedule_id).0;
+    let accounts = accounts::ExecuteDcaTick {
+        fifo_state: pda::find_fifo_state().0,
+        schedule: address,
+        pool_registry: pda::find_pool_registry(&schedule.pool_id).0,
+        pool_fifo: pda::find_pool_fifo(&schedule.pool_id).0,
+        pool_authority: pda::find_pool_authority(&schedule.pool_id).0,
+        escrow: pda::find_dca_escrow(&address).0,
+        destination: schedule.destination,
+        pool_relayers: pda::find_pool_relayers(&schedule.pool_id).0,
+        relayer_info: with_relayer_info.then(|| pda::find_relayer_info(&executor).0),
+        relayer_quota: pda::find_relayer_quota(&executor).0,
+        fee_ledger: pda::find_fee_ledger(&schedule.pool_id).0,
+        fee_vault: pda::find_fee_vault(&schedule.pool_id, &schedule.input_mint).0,
+        relayer_fee_balance: pda::find_relayer_fee_balance(&schedule.pool_id, &executor).0,
+        executor,
+        cp_swap_program,
+        token_program: token::ID,
+        clock: sysvar::clock::ID,
+    };
+
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(swap.execute_remaining_accounts());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: metas,
+        data: instruction::ExecuteDcaTick {}.data(),
+    }
+}
+
+/// Stop a DCA schedule, refunding its escrow into `user_destination`
+pub fn close_dca_schedule(user: Pubkey, pool_id: Pubkey, schedule_id: u64, user_destination: Pubkey) -> Instruction {
+    let schedule = pda::find_dca_schedule(&user, schedule_id).0;
+    let accounts = accounts::CloseDcaSchedule {
+        schedule,
+        escrow: pda::find_dca_escrow(&schedule).0,
+        pool_authority: pda::find_pool_authority(&pool_id).0,
+        user_destination,
+        user,
+        token_program: token::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::CloseDcaSchedule {}.data(),
+    }
+}
+
+/// Create an iceberg on `pool_id` selling `mint` in tranches of
+/// `tranche_in`, moving `deposit` from `user_source` into its escrow
+#[allow(clippy::too_many_arguments)]
+pub fn create_iceberg(
+    user: Pubkey,
+    pool_id: Pubkey,
+    iceberg_id: u64,
+    mint: Pubkey,
+    user_source: Pubkey,
+    tranche_in: u64,
+    tranche_min_out: u64,
+    deposit: u64,
+) -> Instruction {
+    let iceberg = pda::find_iceberg(&user, iceberg_id).0;
+    let accounts = accounts::CreateIceberg {
+        fifo_state: pda::find_fifo_state().0,
+        pool_registry: pda::find_pool_registry(&pool_id).0,
+        iceberg,
+        mint,
+        escrow: pda::find_iceberg_escrow(&iceberg).0,
+        pool_authority: pda::find_pool_authority(&pool_id).0,
+        user_source,
+        user,
+        token_program: token::ID,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::CreateIceberg {
+            iceberg_id,
+            tranche_in,
+            tranche_min_out,
+            deposit,
+        }
+        .data(),
+    }
+}
+
+/// Enqueue the next tranche of `iceberg` at the order PDA for the sequence
+/// after `current_sequence`, its pool's, as any payer
+pub fn rearm_iceberg(payer: Pubkey, iceberg: &Iceberg, current_sequence: u64) -> Instruction {
+    let address = pda::find_iceberg(&iceberg.user, iceberg.iceberg_id).0;
+    let accounts = accounts::RearmIceberg {
+        fifo_state: pda::find_fifo_state().0,
+        iceberg: address,
+        pool_registry: pda::find_pool_registry(&iceberg.pool_id).0,
+        pool_fifo: pda::find_pool_fifo(&iceberg.pool_id).0,
+        escrow: pda::find_iceberg_escrow(&address).0,
+        active_order: iceberg.active_order,
+        order_state: pda::find_order(&iceberg.pool_id, current_sequence + 1).0,
+        user_ordering: pda::find_user_ordering(&iceberg.user).0,
+        payer,
+        system_program: system_program::ID,
+        clock: sysvar::clock::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::RearmIceberg {}.data(),
+    }
+}
+
+/// Stop an iceberg, refunding its escrow into `user_destination`
+pub fn close_iceberg(user: Pubkey, pool_id: Pubkey, iceberg_id: u64, user_destination: Pubkey) -> Instruction {
+    let iceberg = pda::find_iceberg(&user, iceberg_id).0;
+    let accounts = accounts::CloseIceberg {
+        iceberg,
+        escrow: pda::find_iceberg_escrow(&iceberg).0,
+        pool_authority: pda::find_pool_authority(&pool_id).0,
+        user_destination,
+        user,
+        token_program: token::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::CloseIceberg {}.data(),
+    }
+}
+
+/// Open `user`'s shared escrow for `mint`
+pub fn open_shared_escrow(user: Pubkey, mint: Pubkey) -> Instruction {
+    let shared_escrow = pda::find_shared_escrow(&user, &mint).0;
+    let accounts = accounts::OpenSharedEscrow {
+        shared_escrow,
+        vault: pda::find_shared_escrow_vault(&shared_escrow).0,
+        mint,
+        user,
+        token_program: token::ID,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::OpenSharedEscrow {}.data(),
+    }
+}
+
+/// Move `amount` from `user_source` into `user`'s shared escrow for `mint`
+pub fn deposit_shared_escrow(user: Pubkey, mint: Pubkey, user_source: Pubkey, amount: u64) -> Instruction {
+    let shared_escrow = pda::find_shared_escrow(&user, &mint).0;
+    let accounts = accounts::DepositSharedEscrow {
+        shared_escrow,
+        vault: pda::find_shared_escrow_vault(&shared_escrow).0,
+        user_source,
+        user,
+        token_program: token::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::DepositSharedEscrow { amount }.data(),
+    }
+}
+
+/// Move `amount` of the unreserved balance of `user`'s shared escrow for
+/// `mint` to `user_destination`
+pub fn withdraw_shared_escrow(user: Pubkey, mint: Pubkey, user_destination: Pubkey, amount: u64) -> Instruction {
+    let shared_escrow = pda::find_shared_escrow(&user, &mint).0;
+    let accounts = accounts::WithdrawSharedEscrow {
+        shared_escrow,
+        vault: pda::find_shared_escrow_vault(&shared_escrow).0,
+        user_destination,
+        user,
+        token_program: token::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::WithdrawSharedEscrow { amount }.data(),
+    }
+}
+
+/// Close `user`'s shared escrow for `mint`, refunding it to `user_destination`
+pub fn close_shared_escrow(user: Pubkey, mint: Pubkey, user_destination: Pubkey) -> Instruction {
+    let shared_escrow = pda::find_shared_escrow(&user, &mint).0;
+    let accounts = accounts::CloseSharedEscrow {
+        shared_escrow,
+        vault: pda::find_shared_escrow_vault(&shared_escrow).0,
+        user_destination,
+        user,
+        token_program: token::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::CloseSharedEscrow {}.data(),
+    }
+}
+
+/// Ratchet the trailing stop of the order at `order` on `pool_id` with
+/// `oracle`'s price
+pub fn update_trail(pool_id: Pubkey, order: Pubkey, oracle: Pubkey) -> Instruction {
+    let accounts = accounts::UpdateTrail {
+        order_state: order,
+        pool_fifo: pda::find_pool_fifo(&pool_id).0,
+        oracle,
+        clock: sysvar::clock::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::UpdateTrail {}.data(),
+    }
+}
+
+/// The optional compliance accounts of a submission on a pool vetted by
+/// `compliance_program`
+fn compliance_accounts(compliance_program: Option<Pubkey>, user: &Pubkey) -> (Option<Pubkey>, Option<Pubkey>) {
+    let record = compliance_program.map(|program| pda::find_compliance_record(&program, user).0);
+    (compliance_program, record)
+}
+
+/// Accounts and options of an order execution
+pub struct ExecuteOrderParams {
+    pub executor: Pubkey,
+    /// Address of the order account
+    pub order: Pubkey,
+    /// Owner of the order
+    pub user: Pubkey,
+    /// Sequence stored in the order account
+    pub sequence: u64,
+    pub pool_id: Pubkey,
+    pub user_source: Pubkey,
+    pub user_destination: Pubkey,
+    /// Mint of `user_source`, selecting the fee vault
+    pub source_mint: Pubkey,
+    pub cp_swap_program: Pubkey,
+    /// Create an execution receipt for this fill
+    pub with_receipt: bool,
+    /// The executor is a registered relayer, so its posted fee applies on
+    /// pools with a relayer fee cap; required unless
+    /// `FifoState::permissionless_execution`
+    pub with_relayer_info: bool,
+    /// Tag the fill with an SPL Memo
+    pub with_memo: bool,
+    /// `OrderState::callback_program`, which must be passed when set
+    pub callback_program: Option<Pubkey>,
+    /// Required when the order has a post-fill action; `user_destination`
+    /// is then the post-fill vault of `output_mint`
+    pub post_fill: Option<PostFillParams>,
+    /// `OrderState::parent_order`, which must be passed when set
+    pub parent_order: Option<Pubkey>,
+    /// `TrailingStop::oracle` of the order's trailing stop, if it has one
+    pub oracle: Option<Pubkey>,
+    /// `OrderState::shared_escrow`, which must be passed when set
+    pub shared_escrow: Option<Pubkey>,
+    /// `OrderState::delegated_input`, for which the order escrow is passed
+    pub delegated_input: bool,
+    /// `OrderState::permit`, which must be passed when set
+    pub permit: Option<Pubkey>,
+    /// `OrderState::escrow_refund`, which must be passed when set;
+    /// `user_source` is then the order's vault
+    pub escrow_refund: Option<Pubkey>,
+    /// Fill at most this much of an exact input order's remaining input,
+    /// leaving the rest pending
+    pub max_fill_amount: Option<u64>,
+    /// The pool's CP-Swap accounts, see
+    /// [`CpSwapSwapAccounts::execute_remaining_accounts`]
+    pub remaining_accounts: Vec<AccountMeta>,
+}
+
+/// An order's post-fill action and the mint it runs on
+pub struct PostFillParams {
+    pub action: PostFillAction,
+    pub output_mint: Pubkey,
+}
+
+/// Execute a pending order as a relayer
+pub fn execute_order(params: ExecuteOrderParams) -> Instruction {
+    let post_fill = params.post_fill.as_ref();
+    let stake_program = post_fill.and_then(|post_fill| match post_fill.action {
+        PostFillAction::Stake { program } => Some((program, post_fill.output_mint)),
+        PostFillAction::Burn => None,
+    });
+    let accounts = accounts::ExecuteOrder {
+        fifo_state: pda::find_fifo_state().0,
+        order_state: params.order,
+        pool_registry: pda::find_pool_registry(&params.pool_id).0,
+        pool_fifo: pda::find_pool_fifo(&params.pool_id).0,
+        pool_authority: pda::find_pool_authority(&params.pool_id).0,
+        user_ordering: pda::find_user_ordering(&params.user).0,
+        pool_relayers: pda::find_pool_relayers(&params.pool_id).0,
+        executor: params.executor,
+        relayer_info: params
+            .with_relayer_info
+            .then(|| pda::find_relayer_info(&params.executor).0),
+        relayer_quota: pda::find_relayer_quota(&params.executor).0,
+        user_source: params.user_source,
+        user_destination: params.user_destination,
+        receipt: params
+            .with_receipt
+            .then(|| pda::find_receipt(&params.pool_id, params.sequence).0),
+        fee_ledger: pda::find_fee_ledger(&params.pool_id).0,
+        fee_vault: pda::find_fee_vault(&params.pool_id, &params.source_mint).0,
+        fee_schedule: pda::find_fee_schedule().0,
+        user_volume: pda::find_user_volume(&params.pool_id, &params.user).0,
+        relayer_fee_balance: pda::find_relayer_fee_balance(&params.pool_id, &params.executor).0,
+        cp_swap_program: params.cp_swap_program,
+        memo_program: params.with_memo.then_some(memo::ID),
+        callback_program: params.callback_program,
+        post_fill_authority: post_fill.map(|_| pda::find_post_fill_authority().0),
+        output_mint: post_fill.map(|post_fill| post_fill.output_mint),
+        post_fill_program: stake_program.map(|(program, _)| program),
+        post_fill_target: stake_program.map(|(program, mint)| pda::find_stake_deposit(&program, &mint).0),
+        approved_stake_program: stake_program.map(|(program, _)| pda::find_approved_stake_program(&program).0),
+        parent_order: params.parent_order,
+        oracle: params.oracle,
+        shared_escrow: params.shared_escrow,
+        input_mint: params.delegated_input.then_some(params.source_mint),
+        order_escrow: params
+            .delegated_input
+            .then(|| pda::find_order_escrow(&params.order).0),
+        permit: params.permit,
+        escrow_refund: params.escrow_refund,
+        user: params.escrow_refund.map(|_| params.user),
+        token_program: token::ID,
+        system_program: system_program::ID,
+        clock: sysvar::clock::ID,
+    };
+
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(params.remaining_accounts);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: metas,
+        data: instruction::ExecuteOrder {
+            expected_sequence: params.sequence,
+            max_fill_amount: params.max_fill_amount,
+        }
+        .data(),
+    }
+}
+
+/// One order of an [`execute_orders_batch`]
+pub struct BatchExecution {
+    /// Address of the order account
+    pub order: Pubkey,
+    /// Owner of the order
+    pub user: Pubkey,
+    pub user_source: Pubkey,
+    pub user_destination: Pubkey,
+    /// The order's [`OrderVault`] when it is escrowed, which is refunded
+    /// once it executes
+    pub vault: Option<OrderVault>,
+    /// The pool's CP-Swap accounts, see
+    /// [`CpSwapSwapAccounts::execute_remaining_accounts`]
+    pub remaining_accounts: Vec<AccountMeta>,
+}
+
+/// Execute the next orders in `pool_id`'s queue, in queue order, as a
+/// relayer. Only orders with none of the optional accounts of
+/// [`execute_order`] but those of a vault can be batched, see
+/// `OrderState::is_batchable`.
+/// `with_relayer_info` is as for [`ExecuteOrderParams`].
+pub fn execute_orders_batch(
+    executor: Pubkey,
+    pool_id: Pubkey,
+    token_0_mint: Pubkey,
+    token_1_mint: Pubkey,
+    cp_swap_program: Pubkey,
+    with_relayer_info: bool,
+    orders: Vec<BatchExecution>,
+) -> Instruction {
+    let accounts = accounts::ExecuteOrdersBatch {
+        fifo_state: pda::find_fifo_state().0,
+        pool_registry: pda::find_pool_registry(&pool_id).0,
+        pool_fifo: pda::find_pool_fifo(&pool_id).0,
+        pool_authority: pda::find_pool_authority(&pool_id).0,
+        executor,
+        pool_relayers: pda::find_pool_relayers(&pool_id).0,
+        relayer_info: with_relayer_info.then(|| pda::find_relayer_info(&executor).0),
+        relayer_quota: pda::find_relayer_quota(&executor).0,
+        fee_ledger: pda::find_fee_ledger(&pool_id).0,
+        fee_vault_0: pda::find_fee_vault(&pool_id, &token_0_mint).0,
+        fee_vault_1: pda::find_fee_vault(&pool_id, &token_1_mint).0,
+        fee_schedule: pda::find_fee_schedule().0,
+        relayer_fee_balance: pda::find_relayer_fee_balance(&pool_id, &executor).0,
+        cp_swap_program,
+        token_program: token::ID,
+        clock: sysvar::clock::ID,
+    };
+
+    let count = orders.len() as u8;
+    let mut metas = accounts.to_account_metas(None);
+    for order in orders {
+        metas.extend([
+            AccountMeta::new(order.order, false),
+            AccountMeta::new(pda::find_user_ordering(&order.user).0, false),
+            AccountMeta::new(order.user_source, false),
+            AccountMeta::new(order.user_destination, false),
+            AccountMeta::new(pda::find_user_volume(&pool_id, &order.user).0, false),
+        ]);
+        // The program's ID stands in for the vault accounts of an order without one
+        match order.vault {
+            Some(vault) => metas.extend([AccountMeta::new(vault.refund, false), AccountMeta::new(order.user, false)]),
+            None => metas.extend([AccountMeta::new_readonly(PROGRAM_ID, false), AccountMeta::new_readonly(PROGRAM_ID, false)]),
+        }
+        metas.extend(order.remaining_accounts);
+    }
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: metas,
+        data: instruction::ExecuteOrdersBatch { count }.data(),
+    }
+}
+
+/// Submit an order intent signed by its owner, paid for by `payer`. Returns
+/// the ed25519 verification and the submission, which must be sent in this
+/// order in one transaction. `compliance_program` is as for [`submit_order`].
+pub fn submit_order_intent(
+    payer: Pubkey,
+    intent: OrderIntent,
+    current_sequence: u64,
+    signature: &[u8; 64],
+    compliance_program: Option<Pubkey>,
+) -> Vec<Instruction> {
+    let (compliance_program, compliance_record) = compliance_accounts(compliance_program, &intent.user);
+    let verify = new_ed25519_instruction_with_signature(
+        &intent.message(),
+        signature,
+        &intent.user.to_bytes(),
+    );
+    let accounts = accounts::SubmitOrderIntent {
+        fifo_state: pda::find_fifo_state().0,
+        pool_registry: pda::find_pool_registry(&intent.pool_id).0,
+        pool_fifo: pda::find_pool_fifo(&intent.pool_id).0,
+        used_intent: pda::find_used_intent(&intent.user, intent.nonce).0,
+        order_state: pda::find_order(&intent.pool_id, current_sequence + 1).0,
+        user_ordering: pda::find_user_ordering(&intent.user).0,
+        payer,
+        instructions: sysvar::instructions::ID,
+        system_program: system_program::ID,
+        compliance_program,
+        compliance_record,
+    };
+
+    vec![
+        verify,
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: accounts.to_account_metas(None),
+            data: instruction::SubmitOrderIntent { intent }.data(),
+        },
+    ]
+}
+
+/// Approve a permit over `user`'s `token_account`, letting orders naming it
+/// spend up to `allowance` from the account, fees included, until `expires_at`
+pub fn create_permit(user: Pubkey, token_account: Pubkey, allowance: u64, expires_at: i64) -> Instruction {
+    let accounts = accounts::CreatePermit {
+        permit: pda::find_permit(&token_account).0,
+        token_account,
+        user,
+        token_program: token::ID,
+        system_program: system_program::ID,
+        clock: sysvar::clock::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::CreatePermit { allowance, expires_at }.data(),
+    }
+}
+
+/// Set what the permit over `token_account` still allows, and until when
+pub fn update_permit(user: Pubkey, token_account: Pubkey, allowance: u64, expires_at: i64) -> Instruction {
+    let accounts = accounts::UpdatePermit {
+        permit: pda::find_permit(&token_account).0,
+        user,
+        clock: sysvar::clock::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::UpdatePermit { allowance, expires_at }.data(),
+    }
+}
+
+/// Close the permit over `token_account` and revoke its approval
+pub fn close_permit(user: Pubkey, token_account: Pubkey) -> Instruction {
+    let accounts = accounts::ClosePermit {
+        permit: pda::find_permit(&token_account).0,
+        token_account,
+        user,
+        token_program: token::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::ClosePermit {}.data(),
+    }
+}
+
+pub fn create_organization(owner: Pubkey, org_id: u64) -> Instruction {
+    let accounts = accounts::CreateOrganization {
+        organization: pda::find_organization(&owner, org_id).0,
+        owner,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::CreateOrganization { org_id }.data(),
+    }
+}
+
+/// Let `member` submit orders of up to `max_amount_in` on `pools`, or any
+/// pool if empty, for the organization
+pub fn set_org_member(owner: Pubkey, org_id: u64, member: Pubkey, max_amount_in: u64, pools: Vec<Pubkey>) -> Instruction {
+    let accounts = accounts::SetOrgMember {
+        organization: pda::find_organization(&owner, org_id).0,
+        owner,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::SetOrgMember { member, max_amount_in, pools }.data(),
+    }
+}
+
+pub fn remove_org_member(owner: Pubkey, org_id: u64, member: Pubkey) -> Instruction {
+    let accounts = accounts::RemoveOrgMember {
+        organization: pda::find_organization(&owner, org_id).0,
+        owner,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::RemoveOrgMember { member }.data(),
+    }
+}
+
+pub fn open_org_escrow(owner: Pubkey, org_id: u64, mint: Pubkey) -> Instruction {
+    let organization = pda::find_organization(&owner, org_id).0;
+    let shared_escrow = pda::find_shared_escrow(&organization, &mint).0;
+    let accounts = accounts::OpenOrgEscrow {
+        organization,
+        shared_escrow,
+        vault: pda::find_shared_escrow_vault(&shared_escrow).0,
+        mint,
+        owner,
+        token_program: token::ID,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::OpenOrgEscrow {}.data(),
+    }
+}
+
+/// Move `amount` from `source`, owned by `funder`, into `organization`'s
+/// escrow of `mint`; anyone may fund an organization
+pub fn fund_org_escrow(funder: Pubkey, source: Pubkey, organization: Pubkey, mint: Pubkey, amount: u64) -> Instruction {
+    let vault = pda::find_shared_escrow_vault(&pda::find_shared_escrow(&organization, &mint).0).0;
+    token::spl_token::instruction::transfer(&token::ID, &source, &vault, &funder, &[], amount)
+        // Only fails for a token program other than SPL Token
+        .unwrap()
+}
+
+pub fn withdraw_org_escrow(owner: Pubkey, org_id: u64, mint: Pubkey, destination: Pubkey, amount: u64) -> Instruction {
+    let organization = pda::find_organization(&owner, org_id).0;
+    let shared_escrow = pda::find_shared_escrow(&organization, &mint).0;
+    let accounts = accounts::WithdrawOrgEscrow {
+        organization,
+        shared_escrow,
+        vault: pda::find_shared_escrow_vault(&shared_escrow).0,
+        destination,
+        owner,
+        token_program: token::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::WithdrawOrgEscrow { amount }.data(),
+    }
+}
+
+/// Submit an order for `organization` as one of its members, spending from
+/// its escrow of `input_mint` into its escrow of `output_mint`.
+/// `current_sequence` is `PoolFifoState::current_sequence` of `pool_id` read
+/// before submission.
+#[allow(clippy::too_many_arguments)]
+pub fn submit_org_order(
+    member: Pubkey,
+    organization: Pubkey,
+    pool_id: Pubkey,
+    current_sequence: u64,
+    input_mint: Pubkey,
+    output_mint: Pubkey,
+    amount_in: u64,
+    min_amount_out: u64,
+    is_base_input: bool,
+    compliance_program: Option<Pubkey>,
+) -> Instruction {
+    let (compliance_program, compliance_record) = compliance_accounts(compliance_program, &organization);
+    let input_escrow = pda::find_shared_escrow(&organization, &input_mint).0;
+    let output_escrow = pda::find_shared_escrow(&organization, &output_mint).0;
+    let accounts = accounts::SubmitOrgOrder {
+        fifo_state: pda::find_fifo_state().0,
+        pool_registry: pda::find_pool_registry(&pool_id).0,
+        pool_fifo: pda::find_pool_fifo(&pool_id).0,
+        organization,
+        order_state: pda::find_order(&pool_id, current_sequence + 1).0,
+        user_ordering: pda::find_user_ordering(&organization).0,
+        input_escrow,
+        input_vault: pda::find_shared_escrow_vault(&input_escrow).0,
+        output_escrow,
+        output_vault: pda::find_shared_escrow_vault(&output_escrow).0,
+        member,
+        pool_id,
+        system_program: system_program::ID,
+        clock: sysvar::clock::ID,
+        compliance_program,
+        compliance_record,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::SubmitOrgOrder {
+            amount_in,
+            min_amount_out,
+            is_base_input,
+        }
+        .data(),
+    }
+}
+
+/// Cancel `organization`'s pending order at `order` on `pool_id` as its
+/// owner or a member. `shared_escrow` is the order's
+/// `OrderState::shared_escrow`.
+pub fn cancel_org_order(
+    signer: Pubkey,
+    organization: Pubkey,
+    pool_id: Pubkey,
+    order: Pubkey,
+    shared_escrow: Pubkey,
+) -> Instruction {
+    let accounts = accounts::CancelOrgOrder {
+        organization,
+        order_state: order,
+        pool_fifo: pda::find_pool_fifo(&pool_id).0,
+        user_ordering: pda::find_user_ordering(&organization).0,
+        shared_escrow,
+        signer,
+        clock: sysvar::clock::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::CancelOrgOrder {}.data(),
+    }
+}
+
+/// Let `delegate` submit orders of up to `max_amount_in` for `user` and
+/// cancel their orders; `payer` pays for the delegation account
+pub fn approve_user_delegation(
+    user: Pubkey,
+    delegate: Pubkey,
+    payer: Pubkey,
+    max_amount_in: u64,
+    expires_at: Option<i64>,
+) -> Instruction {
+    let accounts = accounts::ApproveUserDelegation {
+        user_delegation: pda::find_user_delegation(&user, &delegate).0,
+        user,
+        delegate,
+        payer,
+        system_program: system_program::ID,
+        clock: sysvar::clock::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::ApproveUserDelegation { max_amount_in, expires_at }.data(),
+    }
+}
+
+/// Withdraw `user`'s delegation to `delegate`, returning its rent to `receiver`
+pub fn revoke_user_delegation(user: Pubkey, delegate: Pubkey, receiver: Pubkey) -> Instruction {
+    let accounts = accounts::RevokeUserDelegation {
+        user_delegation: pda::find_user_delegation(&user, &delegate).0,
+        user,
+        receiver,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::RevokeUserDelegation {}.data(),
+    }
+}
+
+/// Submit an order selling `input_mint` for `user` as their delegate.
+/// `current_sequence` is `PoolFifoState::current_sequence` of `pool_id` read
+/// before submission.
+#[allow(clippy::too_many_arguments)]
+pub fn submit_order_as_delegate(
+    delegate: Pubkey,
+    user: Pubkey,
+    pool_id: Pubkey,
+    input_mint: Pubkey,
+    current_sequence: u64,
+    amount_in: u64,
+    min_amount_out: u64,
+    is_base_input: bool,
+    compliance_program: Option<Pubkey>,
+) -> Instruction {
+    let (compliance_program, compliance_record) = compliance_accounts(compliance_program, &user);
+    let accounts = accounts::SubmitOrderAsDelegate {
+        fifo_state: pda::find_fifo_state().0,
+        pool_registry: pda::find_pool_registry(&pool_id).0,
+        pool_fifo: pda::find_pool_fifo(&pool_id).0,
+        user_delegation: pda::find_user_delegation(&user, &delegate).0,
+        order_state: pda::find_order(&pool_id, current_sequence + 1).0,
+        user_ordering: pda::find_user_ordering(&user).0,
+        delegate,
+        pool_id,
+        input_mint,
+        system_program: system_program::ID,
+        clock: sysvar::clock::ID,
+        compliance_program,
+        compliance_record,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::SubmitOrderAsDelegate {
+            amount_in,
+            min_amount_out,
+            is_base_input,
+        }
+        .data(),
+    }
+}
+
+/// Cancel `user`'s pending order at `order` on `pool_id` as their delegate.
+/// `shared_escrow` is the order's `OrderState::shared_escrow`,
+/// `co_signer` signs too when the order's co-signer must, and `vault` is
+/// the order's [`OrderVault`] when it is escrowed.
+pub fn cancel_order_as_delegate(
+    delegate: Pubkey,
+    user: Pubkey,
+    pool_id: Pubkey,
+    order: Pubkey,
+    shared_escrow: Option<Pubkey>,
+    co_signer: Option<Pubkey>,
+    vault: Option<OrderVault>,
+) -> Instruction {
+    let accounts = accounts::CancelOrderAsDelegate {
+        user_delegation: pda::find_user_delegation(&user, &delegate).0,
+        order_state: order,
+        pool_fifo: pda::find_pool_fifo(&pool_id).0,
+        user_ordering: pda::find_user_ordering(&user).0,
+        delegate,
+        clock: sysvar::clock::ID,
+        shared_escrow,
+        co_signer,
+        order_vault: vault.map(|_| pda::find_order_vault(&order).0),
+        escrow_refund: vault.map(|vault| vault.refund),
+        pool_authority: vault.map(|vault| pda::find_pool_authority(&vault.pool_id).0),
+        token_program: vault.map(|_| token::ID),
+        user: vault.map(|_| user),
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::CancelOrderAsDelegate {}.data(),
+    }
+}
+
+/// Cancel a pending order on `pool_id` as its submitter. `shared_escrow` is
+/// the order's `OrderState::shared_escrow`, whose reservation is released,
+/// `co_signer` signs too when the order's co-signer must, and `vault` is
+/// the order's [`OrderVault`] when it is escrowed, which is refunded.
+pub fn cancel_order(
+    user: Pubkey,
+    pool_id: Pubkey,
+    order: Pubkey,
+    shared_escrow: Option<Pubkey>,
+    co_signer: Option<Pubkey>,
+    vault: Option<OrderVault>,
+) -> Instruction {
+    let accounts = accounts::CancelOrder {
+        order_state: order,
+        pool_fifo: pda::find_pool_fifo(&pool_id).0,
+        user_ordering: pda::find_user_ordering(&user).0,
+        user,
+        clock: sysvar::clock::ID,
+        shared_escrow,
+        co_signer,
+        order_vault: vault.map(|_| pda::find_order_vault(&order).0),
+        escrow_refund: vault.map(|vault| vault.refund),
+        pool_authority: vault.map(|vault| pda::find_pool_authority(&vault.pool_id).0),
+        token_program: vault.map(|_| token::ID),
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::CancelOrder {}.data(),
+    }
+}
+
+/// Change the amounts of the user's pending order at `order` on `pool_id`.
+/// Tightening only its limit keeps it in place, see
+/// `OrderState::is_tightened_by`; any other change re-queues it, and
+/// `requeue_sequence` is then `PoolFifoState::current_sequence` of `pool_id`
+/// read before; the replacement's account is derived from the sequence after
+/// it.
+/// `co_signer` signs too when the order's co-signer must, and `vault` is the
+/// order's [`OrderVault`] when it is escrowed, whose deposit a re-queue moves
+/// to the replacement's vault.
+#[allow(clippy::too_many_arguments)]
+pub fn modify_order(
+    user: Pubkey,
+    pool_id: Pubkey,
+    order: Pubkey,
+    amount_in: u64,
+    min_amount_out: u64,
+    requeue_sequence: Option<u64>,
+    co_signer: Option<Pubkey>,
+    vault: Option<OrderVault>,
+) -> Instruction {
+    let replacement = requeue_sequence.map(|sequence| pda::find_order(&pool_id, sequence + 1).0);
+    let vault = vault.zip(replacement);
+    let accounts = accounts::ModifyOrder {
+        fifo_state: pda::find_fifo_state().0,
+        order_state: order,
+        pool_registry: pda::find_pool_registry(&pool_id).0,
+        pool_fifo: pda::find_pool_fifo(&pool_id).0,
+        replacement,
+        user_ordering: pda::find_user_ordering(&user).0,
+        user,
+        co_signer,
+        system_program: system_program::ID,
+        clock: sysvar::clock::ID,
+        order_vault: vault.map(|_| pda::find_order_vault(&order).0),
+        replacement_vault: vault.map(|(_, replacement)| pda::find_order_vault(&replacement).0),
+        input_mint: vault.map(|(vault, _)| vault.input_mint),
+        escrow_refund: vault.map(|(vault, _)| vault.refund),
+        pool_authority: vault.map(|(vault, _)| pda::find_pool_authority(&vault.pool_id).0),
+        token_program: vault.map(|_| token::ID),
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::ModifyOrder { amount_in, min_amount_out }.data(),
+    }
+}
+
+/// Settle `user`'s pending order at `order` on `pool_id` once it is past its
+/// `OrderState::expires_at`. `shared_escrow` is the order's
+/// `OrderState::shared_escrow` and `vault` its [`OrderVault`] when it is
+/// escrowed. Anyone can sign.
+pub fn expire_order(
+    user: Pubkey,
+    pool_id: Pubkey,
+    order: Pubkey,
+    shared_escrow: Option<Pubkey>,
+    vault: Option<OrderVault>,
+) -> Instruction {
+    let accounts = accounts::ExpireOrder {
+        fifo_state: pda::find_fifo_state().0,
+        order_state: order,
+        pool_fifo: pda::find_pool_fifo(&pool_id).0,
+        user_ordering: pda::find_user_ordering(&user).0,
+        clock: sysvar::clock::ID,
+        shared_escrow,
+        order_vault: vault.map(|_| pda::find_order_vault(&order).0),
+        escrow_refund: vault.map(|vault| vault.refund),
+        pool_authority: vault.map(|_| pda::find_pool_authority(&pool_id).0),
+        token_program: vault.map(|_| token::ID),
+        user: vault.map(|_| user),
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::ExpireOrder {}.data(),
+    }
+}
+
+/// Move `user`'s pending order at global `sequence` on `pool_id`, selling
+/// `input_mint`, from its [`pda::find_legacy_order`] address to the tail of
+/// the pool's queue. `current_sequence` is `PoolFifoState::current_sequence`
+/// of `pool_id` read before. The user signs and pays.
+pub fn migrate_order(user: Pubkey, sequence: u64, pool_id: Pubkey, input_mint: Pubkey, current_sequence: u64) -> Instruction {
+    let accounts = accounts::MigrateOrder {
+        fifo_state: pda::find_fifo_state().0,
+        pool_registry: pda::find_pool_registry(&pool_id).0,
+        legacy_order: pda::find_legacy_order(&user, sequence).0,
+        input_mint,
+        pool_fifo: pda::find_pool_fifo(&pool_id).0,
+        order_state: pda::find_order(&pool_id, current_sequence + 1).0,
+        payer: user,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::MigrateOrder { pool_id }.data(),
+    }
+}
+
+/// Grow the FIFO state of the first deployment to the current layout
+pub fn migrate_fifo_state(admin: Pubkey) -> Instruction {
+    let accounts = accounts::MigrateFifoState {
+        fifo_state: pda::find_fifo_state().0,
+        admin,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::MigrateFifoState {}.data(),
+    }
+}
+
+/// Grow the registry of `pool_id` from the first deployment to the current
+/// layout, once the FIFO state has been
+pub fn migrate_pool_registry(admin: Pubkey, pool_id: Pubkey) -> Instruction {
+    let accounts = accounts::MigratePoolRegistry {
+        fifo_state: pda::find_fifo_state().0,
+        pool_registry: pda::find_pool_registry(&pool_id).0,
+        pool_state: pool_id,
+        admin,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::MigratePoolRegistry {}.data(),
+    }
+}
+
+/// Cancel `user`'s immediate-or-cancel order at `order` on `pool_id`, next in
+/// its pool's line, that `executor` couldn't fill. `shared_escrow` and
+/// `vault` are as for [`expire_order`], `with_relayer_info` as for
+/// [`ExecuteOrderParams`].
+pub fn cancel_ioc_order(
+    executor: Pubkey,
+    user: Pubkey,
+    pool_id: Pubkey,
+    order: Pubkey,
+    shared_escrow: Option<Pubkey>,
+    vault: Option<OrderVault>,
+    with_relayer_info: bool,
+) -> Instruction {
+    let accounts = accounts::CancelIocOrder {
+        fifo_state: pda::find_fifo_state().0,
+        order_state: order,
+        pool_fifo: pda::find_pool_fifo(&pool_id).0,
+        pool_relayers: pda::find_pool_relayers(&pool_id).0,
+        executor,
+        relayer_info: with_relayer_info.then(|| pda::find_relayer_info(&executor).0),
+        user_ordering: pda::find_user_ordering(&user).0,
+        clock: sysvar::clock::ID,
+        shared_escrow,
+        order_vault: vault.map(|_| pda::find_order_vault(&order).0),
+        escrow_refund: vault.map(|vault| vault.refund),
+        pool_authority: vault.map(|_| pda::find_pool_authority(&pool_id).0),
+        token_program: vault.map(|_| token::ID),
+        user: vault.map(|_| user),
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::CancelIocOrder {}.data(),
+    }
+}
+
+/// Move the queue of the order's pool past `order`, which must be next in
+/// line and either settled, conditional or next in line for
+/// `HEAD_GRACE_PERIOD`. Anyone can sign.
+pub fn skip_order(pool_id: Pubkey, order: Pubkey) -> Instruction {
+    let accounts = accounts::SkipOrder {
+        pool_fifo: pda::find_pool_fifo(&pool_id).0,
+        order_state: order,
+        clock: sysvar::clock::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::SkipOrder {}.data(),
+    }
+}
+
+/// CP-Swap accounts of a swap, in the order `swap_immediate` passes them through
+pub struct CpSwapSwapAccounts {
+    pub user: Pubkey,
+    /// CP-Swap vault authority
+    pub authority: Pubkey,
+    pub amm_config: Pubkey,
+    pub pool_state: Pubkey,
+    pub input_token_account: Pubkey,
+    pub output_token_account: Pubkey,
+    pub input_vault: Pubkey,
+    pub output_vault: Pubkey,
+    pub input_token_program: Pubkey,
+    pub output_token_program: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub observation_state: Pubkey,
+}
+
+impl CpSwapSwapAccounts {
+    pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(self.user, true),
+            AccountMeta::new_readonly(self.authority, false),
+            AccountMeta::new_readonly(self.amm_config, false),
+            AccountMeta::new(self.pool_state, false),
+            AccountMeta::new(self.input_token_account, false),
+            AccountMeta::new(self.output_token_account, false),
+            AccountMeta::new(self.input_vault, false),
+            AccountMeta::new(self.output_vault, false),
+            AccountMeta::new_readonly(self.input_token_program, false),
+            AccountMeta::new_readonly(self.output_token_program, false),
+            AccountMeta::new_readonly(self.input_mint, false),
+            AccountMeta::new_readonly(self.output_mint, false),
+            AccountMeta::new(self.observation_state, false),
+        ]
+    }
+
+    /// The pool's accounts, which `execute_order` takes as remaining
+    /// accounts; it supplies the signer and the user's token accounts itself
+    pub fn execute_remaining_accounts(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(self.authority, false),
+            AccountMeta::new_readonly(self.amm_config, false),
+            AccountMeta::new(self.pool_state, false),
+            AccountMeta::new(self.input_vault, false),
+            AccountMeta::new(self.output_vault, false),
+            AccountMeta::new_readonly(self.input_token_program, false),
+            AccountMeta::new_readonly(self.output_token_program, false),
+            AccountMeta::new_readonly(self.input_mint, false),
+            AccountMeta::new_readonly(self.output_mint, false),
+            AccountMeta::new(self.observation_state, false),
+        ]
+    }
+}
+
+/// Submit and execute a swap in one instruction, failing if it lands after
+/// `deadline` (unix seconds). The swap's user must be a registered relayer.
+/// `compliance_program` is as for [`submit_order`].
+#[allow(clippy::too_many_arguments)]
+pub fn swap_immediate(
+    cp_swap_program: Pubkey,
+    swap: &CpSwapSwapAccounts,
+    amount_in: u64,
+    min_amount_out: u64,
+    is_base_input: bool,
+    deadline: Option<i64>,
+    compliance_program: Option<Pubkey>,
+) -> Instruction {
+    let pool_id = swap.pool_state;
+    let (_, pool_authority_bump) = pda::find_pool_authority(&pool_id);
+    let (compliance_program, compliance_record) = compliance_accounts(compliance_program, &swap.user);
+
+    let accounts = accounts::SwapImmediate {
+        fifo_state: pda::find_fifo_state().0,
+        pool_registry: pda::find_pool_registry(&pool_id).0,
+        pool_fifo: pda::find_pool_fifo(&pool_id).0,
+        cp_swap_program,
+        compliance_program,
+        compliance_record,
+        relayer_info: pda::find_relayer_info(&swap.user).0,
+    };
+
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(swap.to_account_metas());
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: metas,
+        data: instruction::SwapImmediate {
+            amount_in,
+            min_amount_out,
+            is_base_input,
+            pool_id,
+            pool_authority_bump,
+            deadline,
+        }
+        .data(),
+    }
+}
+
+/// Create the global FIFO state with `admin` as its administrator
+pub fn initialize(admin: Pubkey) -> Instruction {
+    let accounts = accounts::Initialize {
+        fifo_state: pda::find_fifo_state().0,
+        admin,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::Initialize {}.data(),
+    }
+}
+
+/// Create a CP-Swap pool under the Continuum pool authority and register it.
+/// `cp_swap_accounts` are the accounts of CP-Swap `initialize`, see
+/// [`crate::cp_swap::PoolAddresses::initialize_accounts`]. Once the FIFO
+/// state has a risk authority, it must co-sign as `risk_authority`.
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_cp_swap_pool(
+    admin: Pubkey,
+    risk_authority: Option<Pubkey>,
+    pool_state: Pubkey,
+    cp_swap_program: Pubkey,
+    init_amount_0: u64,
+    init_amount_1: u64,
+    open_time: u64,
+    cp_swap_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let accounts = accounts::InitializeCpSwapPool {
+        fifo_state: pda::find_fifo_state().0,
+        pool_registry: pda::find_pool_registry(&pool_state).0,
+        pool_authority: pda::find_pool_authority(&pool_state).0,
+        admin,
+        risk_authority,
+        pool_state,
+        cp_swap_program,
+        system_program: system_program::ID,
+    };
+
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(cp_swap_accounts);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: metas,
+        data: instruction::InitializeCpSwapPool {
+            init_amount_0,
+            init_amount_1,
+            open_time,
+        }
+        .data(),
+    }
+}
+
+/// Create the fee ledger and fee vaults of a registered pool
+pub fn initialize_fee_ledger(
+    admin: Pubkey,
+    pool_id: Pubkey,
+    token_0_mint: Pubkey,
+    token_1_mint: Pubkey,
+) -> Instruction {
+    let accounts = accounts::InitializeFeeLedger {
+        fifo_state: pda::find_fifo_state().0,
+        pool_registry: pda::find_pool_registry(&pool_id).0,
+        fee_ledger: pda::find_fee_ledger(&pool_id).0,
+        pool_authority: pda::find_pool_authority(&pool_id).0,
+        token_0_mint,
+        token_1_mint,
+        fee_vault_0: pda::find_fee_vault(&pool_id, &token_0_mint).0,
+        fee_vault_1: pda::find_fee_vault(&pool_id, &token_1_mint).0,
+        admin,
+        token_program: token::ID,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::InitializeFeeLedger {}.data(),
+    }
+}
+
+/// Create the FIFO queue of a registered pool
+pub fn initialize_pool_fifo(admin: Pubkey, pool_id: Pubkey) -> Instruction {
+    let accounts = accounts::InitializePoolFifo {
+        fifo_state: pda::find_fifo_state().0,
+        pool_registry: pda::find_pool_registry(&pool_id).0,
+        pool_fifo: pda::find_pool_fifo(&pool_id).0,
+        admin,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::InitializePoolFifo {}.data(),
+    }
+}
+
+pub fn add_relayer(admin: Pubkey, relayer: Pubkey) -> Instruction {
+    let accounts = accounts::AddRelayer {
+        fifo_state: pda::find_fifo_state().0,
+        relayer_info: pda::find_relayer_info(&relayer).0,
+        admin,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::AddRelayer { relayer }.data(),
+    }
+}
+
+pub fn remove_relayer(admin: Pubkey, relayer: Pubkey) -> Instruction {
+    let accounts = accounts::RemoveRelayer {
+        fifo_state: pda::find_fifo_state().0,
+        relayer_info: pda::find_relayer_info(&relayer).0,
+        admin,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::RemoveRelayer {}.data(),
+    }
+}
+
+/// Post the fee `relayer` charges, applied up to each pool's cap
+pub fn set_relayer_fee(relayer: Pubkey, fee_bps: u16) -> Instruction {
+    let accounts = accounts::SetRelayerFee {
+        relayer_info: pda::find_relayer_info(&relayer).0,
+        relayer,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::SetRelayerFee { fee_bps }.data(),
+    }
+}
+
+/// Cap the relayer fee on a pool so relayers can post their own
+pub fn set_relayer_fee_cap(admin: Pubkey, pool_id: Pubkey, max_relayer_fee_bps: u16) -> Instruction {
+    let accounts = accounts::SetRelayerFeeCap {
+        fifo_state: pda::find_fifo_state().0,
+        pool_registry: pda::find_pool_registry(&pool_id).0,
+        admin,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::SetRelayerFeeCap { max_relayer_fee_bps }.data(),
+    }
+}
+
+/// Create the volume-tiered protocol fee schedule
+pub fn initialize_fee_schedule(admin: Pubkey, tiers: Vec<FeeTier>) -> Instruction {
+    let accounts = accounts::InitializeFeeSchedule {
+        fifo_state: pda::find_fifo_state().0,
+        fee_schedule: pda::find_fee_schedule().0,
+        admin,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::InitializeFeeSchedule { tiers }.data(),
+    }
+}
+
+/// Replace the volume-tiered protocol fee schedule
+pub fn set_fee_schedule(admin: Pubkey, tiers: Vec<FeeTier>) -> Instruction {
+    let accounts = accounts::SetFeeSchedule {
+        fifo_state: pda::find_fifo_state().0,
+        fee_schedule: pda::find_fee_schedule().0,
+        admin,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::SetFeeSchedule { tiers }.data(),
+    }
+}
+
+/// Start tracking `user`'s volume on a pool for fee tiers, paid by `payer`
+pub fn open_user_volume(payer: Pubkey, pool_id: Pubkey, user: Pubkey) -> Instruction {
+    let accounts = accounts::OpenUserVolume {
+        pool_registry: pda::find_pool_registry(&pool_id).0,
+        user_volume: pda::find_user_volume(&pool_id, &user).0,
+        payer,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::OpenUserVolume { user }.data(),
+    }
+}
+
+/// Restrict a pool to `relayers`
+pub fn initialize_pool_relayers(admin: Pubkey, pool_id: Pubkey, relayers: Vec<Pubkey>) -> Instruction {
+    let accounts = accounts::InitializePoolRelayers {
+        fifo_state: pda::find_fifo_state().0,
+        pool_registry: pda::find_pool_registry(&pool_id).0,
+        pool_relayers: pda::find_pool_relayers(&pool_id).0,
+        admin,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::InitializePoolRelayers { relayers }.data(),
+    }
+}
+
+/// Replace the relayers assigned to a pool; an empty list opens it to all
+pub fn set_pool_relayers(admin: Pubkey, pool_id: Pubkey, relayers: Vec<Pubkey>) -> Instruction {
+    let accounts = accounts::SetPoolRelayers {
+        fifo_state: pda::find_fifo_state().0,
+        pool_relayers: pda::find_pool_relayers(&pool_id).0,
+        admin,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::SetPoolRelayers { relayers }.data(),
+    }
+}
+
+/// Set the pool registration co-signer; `current_risk_authority` must sign
+/// when one is already set
+pub fn set_risk_authority(
+    admin: Pubkey,
+    current_risk_authority: Option<Pubkey>,
+    risk_authority: Pubkey,
+) -> Instruction {
+    let accounts = accounts::SetRiskAuthority {
+        fifo_state: pda::find_fifo_state().0,
+        admin,
+        current_risk_authority,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::SetRiskAuthority { risk_authority }.data(),
+    }
+}
+
+/// Set the CP-Swap program executions and pool registrations CPI into.
+/// `risk_authority` must co-sign once the FIFO state has one.
+pub fn set_cp_swap_program(admin: Pubkey, risk_authority: Option<Pubkey>, cp_swap_program: Pubkey) -> Instruction {
+    let accounts = accounts::SetCpSwapProgram {
+        fifo_state: pda::find_fifo_state().0,
+        admin,
+        risk_authority,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::SetCpSwapProgram { cp_swap_program }.data(),
+    }
+}
+
+/// Set the program vetting submissions on a pool, or remove it with the
+/// default pubkey
+pub fn set_compliance_program(admin: Pubkey, pool_id: Pubkey, compliance_program: Pubkey) -> Instruction {
+    let accounts = accounts::SetComplianceProgram {
+        fifo_state: pda::find_fifo_state().0,
+        pool_registry: pda::find_pool_registry(&pool_id).0,
+        admin,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::SetComplianceProgram { compliance_program }.data(),
+    }
+}
+
+/// Record a pool's address lookup table, see [`crate::lookup_table`], or
+/// remove it with the default pubkey
+pub fn set_pool_lookup_table(admin: Pubkey, pool_id: Pubkey, lookup_table: Pubkey) -> Instruction {
+    let accounts = accounts::SetPoolLookupTable {
+        fifo_state: pda::find_fifo_state().0,
+        pool_registry: pda::find_pool_registry(&pool_id).0,
+        admin,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::SetPoolLookupTable { lookup_table }.data(),
+    }
+}
+
+/// Create the post-fill vault of `mint`
+pub fn initialize_post_fill_vault(payer: Pubkey, mint: Pubkey) -> Instruction {
+    let accounts = accounts::InitializePostFillVault {
+        mint,
+        post_fill_vault: pda::find_post_fill_vault(&mint).0,
+        post_fill_authority: pda::find_post_fill_authority().0,
+        payer,
+        token_program: token::ID,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::InitializePostFillVault {}.data(),
+    }
+}
+
+pub fn approve_stake_program(admin: Pubkey, program: Pubkey) -> Instruction {
+    let accounts = accounts::ApproveStakeProgram {
+        fifo_state: pda::find_fifo_state().0,
+        approved_stake_program: pda::find_approved_stake_program(&program).0,
+        admin,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::ApproveStakeProgram { program }.data(),
+    }
+}
+
+pub fn revoke_stake_program(admin: Pubkey, program: Pubkey) -> Instruction {
+    let accounts = accounts::RevokeStakeProgram {
+        fifo_state: pda::find_fifo_state().0,
+        approved_stake_program: pda::find_approved_stake_program(&program).0,
+        admin,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::RevokeStakeProgram {}.data(),
+    }
+}
+
+/// Pause or resume every pool, or only `pool_id`, giving `reason` in the
+/// `PauseSet` event
+pub fn set_pause(admin: Pubkey, pool_id: Option<Pubkey>, paused: bool, reason: Option<String>) -> Instruction {
+    let accounts = accounts::SetPause {
+        fifo_state: pda::find_fifo_state().0,
+        pool_registry: pool_id.map(|pool_id| pda::find_pool_registry(&pool_id).0),
+        admin,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::SetPause { paused, reason }.data(),
+    }
+}
+
+/// Create the mock clock of a `test-clock` build
+#[cfg(feature = "test-clock")]
+pub fn initialize_mock_clock(admin: Pubkey, unix_timestamp: Option<i64>) -> Instruction {
+    let accounts = accounts::InitializeMockClock {
+        fifo_state: pda::find_fifo_state().0,
+        mock_clock: pda::find_mock_clock().0,
+        admin,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::InitializeMockClock { unix_timestamp }.data(),
+    }
+}
+
+/// Set the mock clock of a `test-clock` build; `None` restores the cluster
+/// clock
+#[cfg(feature = "test-clock")]
+pub fn set_mock_clock(admin: Pubkey, unix_timestamp: Option<i64>) -> Instruction {
+    let accounts = accounts::SetMockClock {
+        fifo_state: pda::find_fifo_state().0,
+        mock_clock: pda::find_mock_clock().0,
+        admin,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::SetMockClock { unix_timestamp }.data(),
+    }
+}
+
+/// Append the mock clock to an order lifecycle instruction so a `test-clock`
+/// build reads its timestamp instead of the cluster clock's
+#[cfg(feature = "test-clock")]
+pub fn with_mock_clock(mut ix: Instruction) -> Instruction {
+    ix.accounts.push(AccountMeta::new_readonly(pda::find_mock_clock().0, false));
+    ix
+}
+
+/// Put `relayer` on a quota of `max_executions` and `max_volume` (in each
+/// pool's token_0) per `period` seconds; 0 leaves a limit off
+pub fn initialize_relayer_quota(
+    admin: Pubkey,
+    relayer: Pubkey,
+    max_executions: u32,
+    max_volume: u64,
+    period: i64,
+) -> Instruction {
+    let accounts = accounts::InitializeRelayerQuota {
+        fifo_state: pda::find_fifo_state().0,
+        relayer_quota: pda::find_relayer_quota(&relayer).0,
+        admin,
+        system_program: system_program::ID,
+        clock: sysvar::clock::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::InitializeRelayerQuota {
+            relayer,
+            max_executions,
+            max_volume,
+            period,
+        }
+        .data(),
+    }
+}
+
+/// Change the quota of `relayer`
+pub fn set_relayer_quota(
+    admin: Pubkey,
+    relayer: Pubkey,
+    max_executions: u32,
+    max_volume: u64,
+    period: i64,
+) -> Instruction {
+    let accounts = accounts::SetRelayerQuota {
+        fifo_state: pda::find_fifo_state().0,
+        relayer_quota: pda::find_relayer_quota(&relayer).0,
+        admin,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::SetRelayerQuota {
+            max_executions,
+            max_volume,
+            period,
+        }
+        .data(),
+    }
+}
+
+/// Reset the quota counters of `relayer` once its period has ended
+pub fn reset_relayer_quota(relayer: Pubkey) -> Instruction {
+    let accounts = accounts::ResetRelayerQuota {
+        relayer_quota: pda::find_relayer_quota(&relayer).0,
+        clock: sysvar::clock::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::ResetRelayerQuota {}.data(),
+    }
+}
+
+/// Cap immediate swaps per registered relayer to `max_swaps` per
+/// `window_secs`; 0 lifts the cap
+pub fn set_swap_rate_limit(admin: Pubkey, max_swaps: u32, window_secs: u32) -> Instruction {
+    let accounts = accounts::SetSwapRateLimit {
+        fifo_state: pda::find_fifo_state().0,
+        admin,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::SetSwapRateLimit { max_swaps, window_secs }.data(),
+    }
+}
+
+/// Let any signer execute queued orders, or only registered relayers, who
+/// then pass their registration, see `ExecuteOrderParams::with_relayer_info`
+pub fn set_permissionless_execution(admin: Pubkey, permissionless: bool) -> Instruction {
+    let accounts = accounts::SetPermissionlessExecution {
+        fifo_state: pda::find_fifo_state().0,
+        admin,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::SetPermissionlessExecution { permissionless }.data(),
+    }
+}
+
+pub fn set_fee_config(admin: Pubkey, protocol_fee_bps: u16, relayer_fee_bps: u16) -> Instruction {
+    let accounts = accounts::SetFeeConfig {
+        fifo_state: pda::find_fifo_state().0,
+        admin,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::SetFeeConfig {
+            protocol_fee_bps,
+            relayer_fee_bps,
+        }
+        .data(),
+    }
+}
+
+/// Withdraw a pool's outstanding protocol fees to the treasury token accounts
+pub fn withdraw_protocol_fees(
+    admin: Pubkey,
+    pool_id: Pubkey,
+    token_0_mint: Pubkey,
+    token_1_mint: Pubkey,
+    treasury_token_0: Pubkey,
+    treasury_token_1: Pubkey,
+) -> Instruction {
+    let accounts = accounts::WithdrawProtocolFees {
+        fifo_state: pda::find_fifo_state().0,
+        fee_ledger: pda::find_fee_ledger(&pool_id).0,
+        pool_fifo: pda::find_pool_fifo(&pool_id).0,
+        fee_vault_0: pda::find_fee_vault(&pool_id, &token_0_mint).0,
+        fee_vault_1: pda::find_fee_vault(&pool_id, &token_1_mint).0,
+        pool_authority: pda::find_pool_authority(&pool_id).0,
+        treasury_token_0,
+        treasury_token_1,
+        admin,
+        token_program: token::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::WithdrawProtocolFees {}.data(),
+    }
+}
+
+/// Have the user's future orders settle in submission order
+pub fn enable_user_ordering(user: Pubkey) -> Instruction {
+    let accounts = accounts::EnableUserOrdering {
+        user_ordering: pda::find_user_ordering(&user).0,
+        user,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::EnableUserOrdering {}.data(),
+    }
+}
+
+/// Leave per-user ordering once none of the user's ordered orders is pending
+pub fn disable_user_ordering(user: Pubkey) -> Instruction {
+    let accounts = accounts::DisableUserOrdering {
+        user_ordering: pda::find_user_ordering(&user).0,
+        user,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::DisableUserOrdering {}.data(),
+    }
+}
+
+/// Set the share of protocol fees set aside for the insurance fund
+pub fn set_insurance_share(admin: Pubkey, insurance_share_bps: u16) -> Instruction {
+    let accounts = accounts::SetInsuranceShare {
+        fifo_state: pda::find_fifo_state().0,
+        admin,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::SetInsuranceShare { insurance_share_bps }.data(),
+    }
+}
+
+/// Pay `amount` of `mint` out of a pool's insurance fund to `recipient`, a
+/// token account of `mint`. `risk_authority` must co-sign once the FIFO
+/// state has one.
+pub fn payout_insurance(
+    admin: Pubkey,
+    risk_authority: Option<Pubkey>,
+    pool_id: Pubkey,
+    mint: Pubkey,
+    recipient: Pubkey,
+    amount: u64,
+) -> Instruction {
+    let accounts = accounts::PayoutInsurance {
+        fifo_state: pda::find_fifo_state().0,
+        fee_ledger: pda::find_fee_ledger(&pool_id).0,
+        pool_fifo: pda::find_pool_fifo(&pool_id).0,
+        fee_vault: pda::find_fee_vault(&pool_id, &mint).0,
+        pool_authority: pda::find_pool_authority(&pool_id).0,
+        recipient,
+        admin,
+        risk_authority,
+        token_program: token::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::PayoutInsurance { amount }.data(),
+    }
+}
+
+/// Compensate the owner of the order with execution receipt `receipt` on
+/// `pool_id` with `amount` of `mint`, paid into `recipient`, a token account
+/// of theirs. `reason` is a hash of the finding behind it, and
+/// `risk_authority` is as for [`payout_insurance`].
+#[allow(clippy::too_many_arguments)]
+pub fn compensate_order(
+    admin: Pubkey,
+    risk_authority: Option<Pubkey>,
+    pool_id: Pubkey,
+    receipt: Pubkey,
+    mint: Pubkey,
+    recipient: Pubkey,
+    amount: u64,
+    reason: [u8; 32],
+) -> Instruction {
+    let accounts = accounts::CompensateOrder {
+        fifo_state: pda::find_fifo_state().0,
+        receipt,
+        pool_fifo: pda::find_pool_fifo(&pool_id).0,
+        compensation: pda::find_compensation(&receipt).0,
+        fee_ledger: pda::find_fee_ledger(&pool_id).0,
+        fee_vault: pda::find_fee_vault(&pool_id, &mint).0,
+        pool_authority: pda::find_pool_authority(&pool_id).0,
+        recipient,
+        admin,
+        risk_authority,
+        token_program: token::ID,
+        system_program: system_program::ID,
+        clock: sysvar::clock::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::CompensateOrder { amount, reason }.data(),
+    }
+}
+
+/// Open the account accruing a relayer's share of a pool's fees. Required
+/// before the relayer can execute orders on the pool.
+pub fn open_relayer_fee_balance(relayer: Pubkey, pool_id: Pubkey) -> Instruction {
+    let accounts = accounts::OpenRelayerFeeBalance {
+        fee_ledger: pda::find_fee_ledger(&pool_id).0,
+        relayer_fee_balance: pda::find_relayer_fee_balance(&pool_id, &relayer).0,
+        relayer,
+        system_program: system_program::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::OpenRelayerFeeBalance {}.data(),
+    }
+}
+
+/// Pay out the relayer fees accrued on a pool to the relayer's token accounts
+pub fn claim_relayer_fees(
+    relayer: Pubkey,
+    pool_id: Pubkey,
+    token_0_mint: Pubkey,
+    token_1_mint: Pubkey,
+    relayer_token_0: Pubkey,
+    relayer_token_1: Pubkey,
+) -> Instruction {
+    let accounts = accounts::ClaimRelayerFees {
+        fee_ledger: pda::find_fee_ledger(&pool_id).0,
+        pool_fifo: pda::find_pool_fifo(&pool_id).0,
+        relayer_fee_balance: pda::find_relayer_fee_balance(&pool_id, &relayer).0,
+        fee_vault_0: pda::find_fee_vault(&pool_id, &token_0_mint).0,
+        fee_vault_1: pda::find_fee_vault(&pool_id, &token_1_mint).0,
+        pool_authority: pda::find_pool_authority(&pool_id).0,
+        relayer_token_0,
+        relayer_token_1,
+        relayer,
+        token_program: token::ID,
+    };
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: instruction::ClaimRelayerFees {}.data(),
+    }
+}
@@ -0,0 +1,27 @@
+//! Rust client for the Continuum CP-Swap program: PDA derivation, typed
+//! instruction builders and account decoding, so relayers and bots don't have
+//! to hand-roll byte layouts.
+
+pub mod accounts;
+pub mod cp_swap;
+pub mod events;
+pub mod fees;
+pub mod filters;
+pub mod instructions;
+pub mod lookup_table;
+pub mod pda;
+pub mod quote;
+pub mod retry;
+pub mod trace;
+pub mod tracker;
+pub mod transaction;
+
+pub use continuum_cp_swap::cluster;
+pub use continuum_cp_swap::intent;
+pub use continuum_cp_swap::oracle;
+pub use continuum_cp_swap::state;
+pub use continuum_cp_swap::ID as PROGRAM_ID;
+
+/// Raydium CP-Swap program Continuum pools are deployed on, for the cluster
+/// the program was built for
+pub use continuum_cp_swap::cluster::CP_SWAP_PROGRAM_ID;
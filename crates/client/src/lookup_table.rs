@@ -0,0 +1,84 @@
+//! Address lookup tables of pools. A pool's table holds the accounts every
+//! execution on it references, so relayers can compile batches of
+//! executions into one v0 transaction; its address is recorded in the pool
+//! registry with [`crate::instructions::set_pool_lookup_table`].
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::system_program;
+use anchor_spl::{memo, token};
+use solana_address_lookup_table_interface::instruction::{create_lookup_table, extend_lookup_table};
+
+use crate::cp_swap::{find_authority, PoolStateInfo};
+use crate::pda;
+
+/// Most addresses added per extend instruction, keeping each extension
+/// within one transaction
+pub const MAX_ADDRESSES_PER_EXTEND: usize = 20;
+
+/// The accounts every execution on `pool_id` references regardless of the
+/// order or relayer, in a stable order and without duplicates
+pub fn pool_addresses(pool_id: Pubkey, cp_swap_program: Pubkey, state: &PoolStateInfo) -> Vec<Pubkey> {
+    let candidates = [
+        pda::find_fifo_state().0,
+        pda::find_pool_registry(&pool_id).0,
+        pda::find_pool_authority(&pool_id).0,
+        pda::find_pool_relayers(&pool_id).0,
+        pda::find_fee_ledger(&pool_id).0,
+        pda::find_fee_vault(&pool_id, &state.token_0_mint).0,
+        pda::find_fee_vault(&pool_id, &state.token_1_mint).0,
+        pda::find_fee_schedule().0,
+        cp_swap_program,
+        find_authority(&cp_swap_program),
+        state.amm_config,
+        pool_id,
+        state.token_0_vault,
+        state.token_1_vault,
+        state.token_0_mint,
+        state.token_1_mint,
+        state.token_0_program,
+        state.token_1_program,
+        state.observation_key,
+        token::ID,
+        memo::ID,
+        system_program::ID,
+        sysvar::clock::ID,
+    ];
+
+    let mut addresses = Vec::with_capacity(candidates.len());
+    for address in candidates {
+        if !addresses.contains(&address) {
+            addresses.push(address);
+        }
+    }
+    addresses
+}
+
+/// Create a lookup table owned by `authority`. Returns the instruction and
+/// the table's address, which is derived from `recent_slot`.
+pub fn create_pool_lookup_table(authority: Pubkey, payer: Pubkey, recent_slot: u64) -> (Instruction, Pubkey) {
+    create_lookup_table(authority, payer, recent_slot)
+}
+
+/// Add the `addresses` a table doesn't hold yet, given the ones it does, in
+/// as many instructions as they take. Each instruction is meant for its own
+/// transaction.
+pub fn extend_pool_lookup_table(
+    lookup_table: Pubkey,
+    authority: Pubkey,
+    payer: Pubkey,
+    existing: &[Pubkey],
+    addresses: &[Pubkey],
+) -> Vec<Instruction> {
+    let missing: Vec<Pubkey> = addresses
+        .iter()
+        .filter(|address| !existing.contains(address))
+        .copied()
+        .collect();
+
+    missing
+        .chunks(MAX_ADDRESSES_PER_EXTEND)
+        .map(|chunk| extend_lookup_table(lookup_table, authority, Some(payer), chunk.to_vec()))
+        .collect()
+}
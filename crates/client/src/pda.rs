@@ -0,0 +1,193 @@
+//! Program derived addresses of the Continuum program.
+
+use anchor_lang::prelude::Pubkey;
+
+use crate::PROGRAM_ID;
+
+pub use continuum_cp_swap::consts::{
+    COMPENSATION_SEED, COMPLIANCE_RECORD_SEED, DCA_ESCROW_SEED, DCA_SCHEDULE_SEED, ESCROW_SEED, FEE_LEDGER_SEED, FEE_SCHEDULE_SEED, FEE_VAULT_SEED, FIFO_STATE_SEED, ICEBERG_ESCROW_SEED, ICEBERG_SEED, INTENT_SEED,
+    MOCK_CLOCK_SEED, ORDER_SEED, ORDER_TEMPLATE_SEED, ORDER_VAULT_SEED, ORGANIZATION_SEED, PERMIT_SEED, POOL_AUTHORITY_SEED, POOL_FIFO_SEED, POOL_REGISTRY_SEED, POOL_RELAYERS_SEED,
+    POST_FILL_AUTHORITY_SEED, POST_FILL_VAULT_SEED, RECEIPT_SEED,
+    RELAYER_FEES_SEED, RELAYER_QUOTA_SEED, RELAYER_SEED, SHARED_ESCROW_SEED, SHARED_ESCROW_VAULT_SEED, STAKE_DEPOSIT_SEED,
+    STAKE_PROGRAM_SEED, TEMPLATE_ESCROW_SEED, USER_DELEGATION_SEED, USER_ORDERING_SEED, USER_VOLUME_SEED,
+};
+
+pub fn find_fifo_state() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FIFO_STATE_SEED], &PROGRAM_ID)
+}
+
+pub fn find_pool_registry(pool_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_REGISTRY_SEED, pool_id.as_ref()], &PROGRAM_ID)
+}
+
+pub fn find_pool_authority(pool_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_AUTHORITY_SEED, pool_id.as_ref()], &PROGRAM_ID)
+}
+
+/// Order accounts are seeded with their pool and their sequence in its queue
+pub fn find_order(pool_id: &Pubkey, sequence: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ORDER_SEED, pool_id.as_ref(), &sequence.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+/// Address orders had before they were sequenced by their pool, seeded with
+/// their user and the global sequence before their own
+pub fn find_legacy_order(user: &Pubkey, sequence: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ORDER_SEED, user.as_ref(), &(sequence - 1).to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+pub fn find_receipt(pool_id: &Pubkey, sequence: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[RECEIPT_SEED, pool_id.as_ref(), &sequence.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+pub fn find_relayer_info(relayer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RELAYER_SEED, relayer.as_ref()], &PROGRAM_ID)
+}
+
+pub fn find_compensation(receipt: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[COMPENSATION_SEED, receipt.as_ref()], &PROGRAM_ID)
+}
+
+pub fn find_relayer_quota(relayer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RELAYER_QUOTA_SEED, relayer.as_ref()], &PROGRAM_ID)
+}
+
+pub fn find_fee_ledger(pool_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEE_LEDGER_SEED, pool_id.as_ref()], &PROGRAM_ID)
+}
+
+pub fn find_fee_vault(pool_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[FEE_VAULT_SEED, pool_id.as_ref(), mint.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+pub fn find_relayer_fee_balance(pool_id: &Pubkey, relayer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[RELAYER_FEES_SEED, pool_id.as_ref(), relayer.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+pub fn find_used_intent(user: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[INTENT_SEED, user.as_ref(), &nonce.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+pub fn find_user_ordering(user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[USER_ORDERING_SEED, user.as_ref()], &PROGRAM_ID)
+}
+
+pub fn find_fee_schedule() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEE_SCHEDULE_SEED], &PROGRAM_ID)
+}
+
+pub fn find_user_volume(pool_id: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[USER_VOLUME_SEED, pool_id.as_ref(), user.as_ref()], &PROGRAM_ID)
+}
+
+pub fn find_pool_relayers(pool_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_RELAYERS_SEED, pool_id.as_ref()], &PROGRAM_ID)
+}
+
+pub fn find_pool_fifo(pool_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_FIFO_SEED, pool_id.as_ref()], &PROGRAM_ID)
+}
+
+/// A user's record under a pool's compliance program, which owns it
+pub fn find_compliance_record(compliance_program: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[COMPLIANCE_RECORD_SEED, user.as_ref()], compliance_program)
+}
+
+pub fn find_order_template(user: &Pubkey, template_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ORDER_TEMPLATE_SEED, user.as_ref(), &template_id.to_le_bytes()], &PROGRAM_ID)
+}
+
+/// Escrow funding a template's occurrences, owned by the pool authority
+pub fn find_template_escrow(template: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TEMPLATE_ESCROW_SEED, template.as_ref()], &PROGRAM_ID)
+}
+
+pub fn find_dca_schedule(user: &Pubkey, schedule_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DCA_SCHEDULE_SEED, user.as_ref(), &schedule_id.to_le_bytes()], &PROGRAM_ID)
+}
+
+/// Escrow holding a DCA schedule's budget, owned by the pool authority
+pub fn find_dca_escrow(schedule: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DCA_ESCROW_SEED, schedule.as_ref()], &PROGRAM_ID)
+}
+
+pub fn find_iceberg(user: &Pubkey, iceberg_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ICEBERG_SEED, user.as_ref(), &iceberg_id.to_le_bytes()], &PROGRAM_ID)
+}
+
+/// Escrow funding an iceberg's tranches, owned by the pool authority
+pub fn find_iceberg_escrow(iceberg: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ICEBERG_ESCROW_SEED, iceberg.as_ref()], &PROGRAM_ID)
+}
+
+/// Escrow PDA of an order: the token account holding a cross-chain order's
+/// input, or the delegate a user approves for an order with `delegated_input`
+pub fn find_order_escrow(order: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ESCROW_SEED, order.as_ref()], &PROGRAM_ID)
+}
+
+/// Vault of an order escrowed at submission, see `continuum_cp_swap::order_vault`
+pub fn find_order_vault(order: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ORDER_VAULT_SEED, order.as_ref()], &PROGRAM_ID)
+}
+
+pub fn find_shared_escrow(user: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SHARED_ESCROW_SEED, user.as_ref(), mint.as_ref()], &PROGRAM_ID)
+}
+
+/// Vault holding a shared escrow's tokens, owned by the escrow account
+pub fn find_shared_escrow_vault(shared_escrow: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SHARED_ESCROW_VAULT_SEED, shared_escrow.as_ref()], &PROGRAM_ID)
+}
+
+/// Delegation letting `delegate` submit and cancel orders for `user`
+pub fn find_user_delegation(user: &Pubkey, delegate: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[USER_DELEGATION_SEED, user.as_ref(), delegate.as_ref()], &PROGRAM_ID)
+}
+
+pub fn find_organization(owner: &Pubkey, org_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ORGANIZATION_SEED, owner.as_ref(), &org_id.to_le_bytes()], &PROGRAM_ID)
+}
+
+/// Permit over `token_account`, which is also its SPL delegate
+pub fn find_permit(token_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PERMIT_SEED, token_account.as_ref()], &PROGRAM_ID)
+}
+
+pub fn find_post_fill_vault(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POST_FILL_VAULT_SEED, mint.as_ref()], &PROGRAM_ID)
+}
+
+pub fn find_post_fill_authority() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POST_FILL_AUTHORITY_SEED], &PROGRAM_ID)
+}
+
+pub fn find_approved_stake_program(program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STAKE_PROGRAM_SEED, program.as_ref()], &PROGRAM_ID)
+}
+
+/// A staking program's deposit account for `mint`, which that program owns
+pub fn find_stake_deposit(program: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STAKE_DEPOSIT_SEED, mint.as_ref()], program)
+}
+
+pub fn find_mock_clock() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MOCK_CLOCK_SEED], &PROGRAM_ID)
+}
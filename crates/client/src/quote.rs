@@ -0,0 +1,185 @@
+//! Local quotes replicating Raydium CP-Swap's constant-product math, computed
+//! from vault balances without simulating a transaction.
+//!
+//! The trade fee is taken from the input before the curve is applied, rounded
+//! up, as CP-Swap does. Reserves should be the vault balances net of the
+//! protocol and fund fees the pool has not yet collected.
+
+/// Denominator of CP-Swap fee rates (`AmmConfig::trade_fee_rate`)
+pub const FEE_RATE_DENOMINATOR: u64 = 1_000_000;
+
+/// Pool reserves oriented by swap direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reserves {
+    pub input: u64,
+    pub output: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quote {
+    /// Input taken from the trader, including the trade fee
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub trade_fee: u64,
+    /// Shortfall of the execution price against the spot price, in basis points
+    pub price_impact_bps: u64,
+}
+
+fn ceil_div(numerator: u128, denominator: u128) -> u128 {
+    numerator.div_ceil(denominator)
+}
+
+fn trade_fee(amount: u64, trade_fee_rate: u64) -> u64 {
+    ceil_div(amount as u128 * trade_fee_rate as u128, FEE_RATE_DENOMINATOR as u128) as u64
+}
+
+/// Price impact of receiving `amount_out` for `amount_in`, against the
+/// pre-trade spot price of `reserves`
+pub fn price_impact_bps(reserves: Reserves, amount_in: u64, amount_out: u64) -> u64 {
+    if amount_in == 0 || reserves.output == 0 {
+        return 0;
+    }
+    // spot output = amount_in * output / input
+    let spot_out = amount_in as u128 * reserves.output as u128;
+    let actual_out = amount_out as u128 * reserves.input as u128;
+    if actual_out >= spot_out {
+        return 0;
+    }
+    let shortfall = spot_out - actual_out;
+    // Only the largest reserves overflow, where scaling the divisor instead
+    // loses no basis point
+    match shortfall.checked_mul(10_000) {
+        Some(scaled) => (scaled / spot_out) as u64,
+        None => (shortfall / (spot_out / 10_000)) as u64,
+    }
+}
+
+/// Quote a `swap_base_input` of exactly `amount_in`. Returns `None` if the
+/// pool is empty or the trade cannot be filled.
+pub fn quote_base_input(reserves: Reserves, amount_in: u64, trade_fee_rate: u64) -> Option<Quote> {
+    if reserves.input == 0 || reserves.output == 0 || trade_fee_rate >= FEE_RATE_DENOMINATOR {
+        return None;
+    }
+    let fee = trade_fee(amount_in, trade_fee_rate);
+    let amount_in_less_fees = (amount_in - fee) as u128;
+    let amount_out = amount_in_less_fees * reserves.output as u128
+        / (reserves.input as u128 + amount_in_less_fees);
+    let amount_out = u64::try_from(amount_out).ok()?;
+
+    Some(Quote {
+        amount_in,
+        amount_out,
+        trade_fee: fee,
+        price_impact_bps: price_impact_bps(reserves, amount_in, amount_out),
+    })
+}
+
+/// Quote a `swap_base_output` receiving exactly `amount_out`. Returns `None`
+/// if the pool cannot provide that much output.
+pub fn quote_base_output(reserves: Reserves, amount_out: u64, trade_fee_rate: u64) -> Option<Quote> {
+    if reserves.input == 0 || amount_out >= reserves.output || trade_fee_rate >= FEE_RATE_DENOMINATOR {
+        return None;
+    }
+    let amount_in_less_fees = ceil_div(
+        reserves.input as u128 * amount_out as u128,
+        (reserves.output - amount_out) as u128,
+    );
+    // Gross up so that the fee taken from the result leaves amount_in_less_fees
+    let amount_in = ceil_div(
+        amount_in_less_fees * FEE_RATE_DENOMINATOR as u128,
+        (FEE_RATE_DENOMINATOR - trade_fee_rate) as u128,
+    );
+    let amount_in = u64::try_from(amount_in).ok()?;
+
+    Some(Quote {
+        amount_in,
+        amount_out,
+        trade_fee: trade_fee(amount_in, trade_fee_rate),
+        price_impact_bps: price_impact_bps(reserves, amount_in, amount_out),
+    })
+}
+
+/// Quote an order as the program submits it: for base-input orders
+/// `amount` is the exact input, otherwise the exact output.
+pub fn quote_order(reserves: Reserves, amount: u64, is_base_input: bool, trade_fee_rate: u64) -> Option<Quote> {
+    if is_base_input {
+        quote_base_input(reserves, amount, trade_fee_rate)
+    } else {
+        quote_base_output(reserves, amount, trade_fee_rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Expected values follow Raydium CP-Swap's `CurveCalculator` with a 0.25%
+    // trade fee: `Fees::trading_fee`, the constant-product curve and
+    // `Fees::calculate_pre_fee_amount`
+    const RATE: u64 = 2_500;
+    const RESERVES: Reserves = Reserves {
+        input: 1_000_000_000,
+        output: 2_000_000_000,
+    };
+
+    #[test]
+    fn base_input_matches_cp_swap() {
+        let quote = quote_base_input(RESERVES, 1_000_000, RATE).unwrap();
+        assert_eq!(quote, Quote {
+            amount_in: 1_000_000,
+            amount_out: 1_993_011,
+            trade_fee: 2_500,
+            price_impact_bps: 34,
+        });
+    }
+
+    #[test]
+    fn base_output_matches_cp_swap() {
+        let quote = quote_base_output(RESERVES, 1_000_000, RATE).unwrap();
+        assert_eq!(quote, Quote {
+            amount_in: 501_505,
+            amount_out: 1_000_000,
+            trade_fee: 1_254,
+            price_impact_bps: 30,
+        });
+        // Spending the quoted input delivers at least the requested output
+        assert!(quote_base_input(RESERVES, quote.amount_in, RATE).unwrap().amount_out >= 1_000_000);
+    }
+
+    #[test]
+    fn fee_rounds_up_and_output_down() {
+        // A fee of 0.0025 is rounded up to the whole input
+        let quote = quote_base_input(RESERVES, 1, RATE).unwrap();
+        assert_eq!((quote.trade_fee, quote.amount_out), (1, 0));
+        // 399 * 2 / 1000.000399 floors to 797 without a fee
+        let quote = quote_base_input(RESERVES, 399, 0).unwrap();
+        assert_eq!((quote.trade_fee, quote.amount_out), (0, 797));
+        // Without a fee the smallest output costs half an input unit, rounded up
+        assert_eq!(quote_base_output(RESERVES, 1, 0).unwrap().amount_in, 1);
+    }
+
+    #[test]
+    fn zero_liquidity_has_no_quote() {
+        let empty = Reserves { input: 0, output: 0 };
+        assert_eq!(quote_base_input(empty, 1_000, RATE), None);
+        assert_eq!(quote_base_output(empty, 1_000, RATE), None);
+        assert_eq!(quote_base_input(Reserves { input: 1_000, output: 0 }, 1_000, RATE), None);
+        // The whole output reserve can't be bought
+        assert_eq!(quote_base_output(RESERVES, RESERVES.output, RATE), None);
+    }
+
+    #[test]
+    fn overflowing_input_has_no_quote() {
+        let reserves = Reserves { input: u64::MAX, output: 2 };
+        assert_eq!(quote_base_output(reserves, 1, RATE), None);
+        // The largest inputs are computed in u128 without overflowing
+        let quote = quote_base_input(Reserves { input: u64::MAX, output: u64::MAX }, u64::MAX, 0).unwrap();
+        assert_eq!((quote.amount_out, quote.price_impact_bps), (u64::MAX / 2, 5_000));
+    }
+
+    #[test]
+    fn quote_order_follows_direction() {
+        assert_eq!(quote_order(RESERVES, 1_000_000, true, RATE), quote_base_input(RESERVES, 1_000_000, RATE));
+        assert_eq!(quote_order(RESERVES, 1_000_000, false, RATE), quote_base_output(RESERVES, 1_000_000, RATE));
+    }
+}
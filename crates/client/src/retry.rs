@@ -0,0 +1,253 @@
+//! Sending transactions that survive congestion: blockhash transactions are
+//! re-signed with a fresh blockhash once theirs expires, durable nonce
+//! transactions are rebroadcast, and each attempt raises the priority fee.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use anchor_lang::prelude::Pubkey;
+use solana_client::client_error::ClientError;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::nonce_utils::{self, nonblocking as nonce_rpc};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::hash::Hash;
+use solana_sdk::signature::Signature;
+use solana_sdk::signer::signers::Signers;
+use solana_sdk::transaction::TransactionError;
+
+use crate::accounts::{decode, CpSwapPoolRegistry, PoolFifoState};
+use crate::instructions::OrderOptions;
+use crate::transaction::{TransactionBuildError, TransactionBuilder};
+use crate::{instructions, pda};
+
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    /// Micro-lamports per compute unit added to the priority fee per retry
+    pub priority_fee_step: u64,
+    pub max_priority_fee: u64,
+    /// How long a durable nonce transaction may go unconfirmed before it is
+    /// rebroadcast with a higher fee
+    pub nonce_rebroadcast_after: Duration,
+    pub poll_interval: Duration,
+    pub commitment: CommitmentConfig,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            priority_fee_step: 10_000,
+            max_priority_fee: 1_000_000,
+            nonce_rebroadcast_after: Duration::from_secs(30),
+            poll_interval: Duration::from_millis(500),
+            commitment: CommitmentConfig::confirmed(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SendError {
+    Build(TransactionBuildError),
+    Rpc(ClientError),
+    Nonce(nonce_utils::Error),
+    /// A program account could not be decoded
+    InvalidAccount(Pubkey),
+    /// The transaction landed but failed
+    Failed {
+        signature: Signature,
+        err: TransactionError,
+    },
+    /// No attempt confirmed. Earlier signatures may still land if they were
+    /// signed with a durable nonce that has not advanced.
+    Exhausted { signatures: Vec<Signature> },
+}
+
+impl fmt::Display for SendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Build(err) => write!(f, "{}", err),
+            Self::Rpc(err) => write!(f, "rpc error: {}", err),
+            Self::Nonce(err) => write!(f, "nonce error: {}", err),
+            Self::InvalidAccount(address) => write!(f, "invalid account {}", address),
+            Self::Failed { signature, err } => write!(f, "transaction {} failed: {}", signature, err),
+            Self::Exhausted { signatures } => {
+                write!(f, "transaction not confirmed after {} attempts", signatures.len())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SendError {}
+
+impl From<TransactionBuildError> for SendError {
+    fn from(err: TransactionBuildError) -> Self {
+        Self::Build(err)
+    }
+}
+
+impl From<ClientError> for SendError {
+    fn from(err: ClientError) -> Self {
+        Self::Rpc(err)
+    }
+}
+
+impl From<nonce_utils::Error> for SendError {
+    fn from(err: nonce_utils::Error) -> Self {
+        Self::Nonce(err)
+    }
+}
+
+/// Blockhash currently stored in a durable nonce account
+pub async fn fetch_nonce_blockhash(
+    rpc: &RpcClient,
+    nonce_account: &Pubkey,
+    commitment: CommitmentConfig,
+) -> Result<Hash, SendError> {
+    let account = nonce_rpc::get_account_with_commitment(rpc, nonce_account, commitment).await?;
+    Ok(nonce_rpc::data_from_account(&account)?.blockhash())
+}
+
+/// Build a submission of an order selling `input_mint`, signed with a
+/// durable nonce, so it can be held off-chain and sent later. The order
+/// account is derived from the pool's current FIFO sequence: if other orders
+/// are submitted on the pool before this one lands it fails and must be
+/// rebuilt. The order spends from the user's wallet, so the user approves the
+/// pool authority before it executes.
+#[allow(clippy::too_many_arguments)]
+pub async fn build_nonce_submit_order(
+    rpc: &RpcClient,
+    user: Pubkey,
+    pool_id: Pubkey,
+    input_mint: Pubkey,
+    amount_in: u64,
+    min_amount_out: u64,
+    is_base_input: bool,
+    recipient: Option<Pubkey>,
+    nonce_account: Pubkey,
+    nonce_authority: Pubkey,
+) -> Result<TransactionBuilder, SendError> {
+    let pool_fifo_address = pda::find_pool_fifo(&pool_id).0;
+    let data = rpc.get_account_data(&pool_fifo_address).await?;
+    let pool_fifo: PoolFifoState =
+        decode(&data).map_err(|_| SendError::InvalidAccount(pool_fifo_address))?;
+    let registry_address = pda::find_pool_registry(&pool_id).0;
+    let data = rpc.get_account_data(&registry_address).await?;
+    let registry: CpSwapPoolRegistry =
+        decode(&data).map_err(|_| SendError::InvalidAccount(registry_address))?;
+
+    let ix = instructions::submit_order(
+        user,
+        pool_id,
+        input_mint,
+        pool_fifo.current_sequence,
+        amount_in,
+        min_amount_out,
+        is_base_input,
+        OrderOptions {
+            recipient,
+            compliance_program: registry.compliance_program(),
+            from_wallet: true,
+            ..OrderOptions::default()
+        },
+    );
+    Ok(TransactionBuilder::new(user)
+        .durable_nonce(nonce_account, nonce_authority)
+        .instruction(ix))
+}
+
+pub enum Outcome {
+    Confirmed(Signature),
+    /// The blockhash expired, or a nonce transaction stayed unconfirmed for
+    /// `nonce_rebroadcast_after`
+    Expired,
+}
+
+/// Poll until one of `signatures` confirms, fails or can no longer land.
+/// Without a `last_valid_block_height` the signatures are taken to be
+/// nonce transactions.
+pub async fn confirm(
+    rpc: &RpcClient,
+    signatures: &[Signature],
+    last_valid_block_height: Option<u64>,
+    policy: &RetryPolicy,
+) -> Result<Outcome, SendError> {
+    let started = Instant::now();
+    loop {
+        tokio::time::sleep(policy.poll_interval).await;
+
+        let statuses = rpc.get_signature_statuses(signatures).await?.value;
+        for (signature, status) in signatures.iter().zip(statuses) {
+            let Some(status) = status else { continue };
+            if let Some(err) = status.err {
+                return Err(SendError::Failed { signature: *signature, err });
+            }
+            if status.satisfies_commitment(policy.commitment) {
+                return Ok(Outcome::Confirmed(*signature));
+            }
+        }
+
+        let expired = match last_valid_block_height {
+            Some(last_valid) => rpc.get_block_height().await? > last_valid,
+            None => started.elapsed() >= policy.nonce_rebroadcast_after,
+        };
+        if expired {
+            return Ok(Outcome::Expired);
+        }
+    }
+}
+
+/// Sign and send the transaction, retrying with a higher priority fee until
+/// it confirms or the policy gives up. Returns the signature that landed.
+pub async fn send_with_retry<T: Signers + ?Sized>(
+    rpc: &RpcClient,
+    builder: TransactionBuilder,
+    signers: &T,
+    policy: &RetryPolicy,
+) -> Result<Signature, SendError> {
+    let mut builder = builder;
+    let mut signatures = Vec::new();
+
+    for attempt in 0..policy.max_attempts {
+        if attempt > 0 {
+            let price = builder
+                .current_compute_unit_price()
+                .unwrap_or(0)
+                .saturating_add(policy.priority_fee_step)
+                .min(policy.max_priority_fee);
+            builder = builder.compute_unit_price(price);
+        }
+
+        let (blockhash, last_valid_block_height) = match builder.nonce() {
+            Some(nonce) => (
+                fetch_nonce_blockhash(rpc, &nonce.account, policy.commitment).await?,
+                None,
+            ),
+            None => {
+                let (hash, last_valid) = rpc
+                    .get_latest_blockhash_with_commitment(policy.commitment)
+                    .await?;
+                (hash, Some(last_valid))
+            }
+        };
+
+        let tx = builder.sign(blockhash, signers)?;
+        signatures.push(tx.signatures[0]);
+        rpc.send_transaction(&tx).await?;
+
+        // A nonce only advances when one of the attempts lands, so every
+        // earlier signature is still a candidate
+        let candidates = if last_valid_block_height.is_some() {
+            &signatures[signatures.len() - 1..]
+        } else {
+            &signatures[..]
+        };
+        if let Outcome::Confirmed(signature) =
+            confirm(rpc, candidates, last_valid_block_height, policy).await?
+        {
+            return Ok(signature);
+        }
+    }
+
+    Err(SendError::Exhausted { signatures })
+}
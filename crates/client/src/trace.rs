@@ -0,0 +1,34 @@
+//! Trace IDs, the identifiers that follow an order from its submission
+//! through every event about it and into off-chain systems. They are written
+//! as 32 lowercase hex digits.
+
+use anchor_lang::solana_program::keccak;
+
+pub use continuum_cp_swap::state::{default_trace_id, TraceId};
+
+/// Trace ID orders got when their submitter didn't choose one before orders
+/// were sequenced by their pool: the first 16 bytes of keccak("trace",
+/// sequence), with their global sequence
+pub fn legacy_trace_id(sequence: u64) -> TraceId {
+    let hash = keccak::hashv(&[b"trace", &sequence.to_le_bytes()]).to_bytes();
+    let mut trace_id = [0; 16];
+    trace_id.copy_from_slice(&hash[..16]);
+    trace_id
+}
+
+/// Hex form of a trace ID
+pub fn to_hex(trace_id: &TraceId) -> String {
+    trace_id.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Parse the hex form of a trace ID, `None` unless it is 32 hex digits
+pub fn parse(hex: &str) -> Option<TraceId> {
+    if hex.len() != 32 || !hex.is_ascii() {
+        return None;
+    }
+    let mut trace_id = [0; 16];
+    for (byte, digits) in trace_id.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(digits).ok()?, 16).ok()?;
+    }
+    Some(trace_id)
+}
@@ -0,0 +1,236 @@
+//! Tracking of a submitted order until it reaches a terminal state.
+//!
+//! [`OrderLifecycle`] is the state machine, fed with order account updates
+//! and decoded events. [`OrderTracker`] drives it from websocket
+//! subscriptions to the order account and to logs mentioning it.
+
+use std::time::Duration;
+
+use anchor_lang::prelude::Pubkey;
+use futures_util::StreamExt;
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::nonblocking::pubsub_client::{PubsubClient, PubsubClientError};
+use solana_client::rpc_config::{
+    RpcAccountInfoConfig, RpcTransactionLogsConfig, RpcTransactionLogsFilter,
+};
+use solana_sdk::commitment_config::CommitmentConfig;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::accounts::{decode, OrderState, OrderStatus};
+use crate::events::{parse_logs, ContinuumEvent};
+
+/// Details of an executed order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fill {
+    pub amount_out: u64,
+    pub executor: Pubkey,
+    pub executed_at: Option<i64>,
+    /// Transaction signature, known when the fill was seen in the logs
+    pub signature: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderPhase {
+    Pending,
+    Executed(Fill),
+    Cancelled { signature: Option<String> },
+    /// The order expired on-chain, or the tracking deadline passed while it
+    /// was still pending
+    Expired,
+}
+
+impl OrderPhase {
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, Self::Pending)
+    }
+}
+
+/// Order state machine: Pending → Executed / Cancelled / Expired.
+/// Terminal phases are never left; later updates only fill in details.
+#[derive(Debug, Clone)]
+pub struct OrderLifecycle {
+    pub user: Pubkey,
+    pub sequence: u64,
+    phase: OrderPhase,
+}
+
+impl OrderLifecycle {
+    pub fn new(user: Pubkey, sequence: u64) -> Self {
+        Self {
+            user,
+            sequence,
+            phase: OrderPhase::Pending,
+        }
+    }
+
+    pub fn phase(&self) -> &OrderPhase {
+        &self.phase
+    }
+
+    /// Apply an order account update. Returns whether the phase changed.
+    pub fn apply_account(&mut self, order: &OrderState) -> bool {
+        if order.sequence != self.sequence || order.user != self.user {
+            return false;
+        }
+        match (&mut self.phase, order.status) {
+            (OrderPhase::Pending, OrderStatus::Cancelled) => {
+                self.phase = OrderPhase::Cancelled { signature: None };
+                true
+            }
+            (OrderPhase::Pending, OrderStatus::Expired) => {
+                self.phase = OrderPhase::Expired;
+                true
+            }
+            (OrderPhase::Executed(fill), OrderStatus::Executed) => {
+                fill.executed_at = fill.executed_at.or(order.executed_at);
+                false
+            }
+            // The account alone doesn't record the fill amount; wait for the event
+            _ => false,
+        }
+    }
+
+    /// Apply an event from a transaction with `signature`. Returns whether
+    /// the phase changed.
+    pub fn apply_event(&mut self, event: &ContinuumEvent, signature: Option<&str>) -> bool {
+        match event {
+            ContinuumEvent::OrderExecuted(e) if e.sequence == self.sequence && e.user == self.user => {
+                if self.phase.is_terminal() {
+                    return false;
+                }
+                self.phase = OrderPhase::Executed(Fill {
+                    amount_out: e.amount_out,
+                    executor: e.executor,
+                    executed_at: None,
+                    signature: signature.map(str::to_string),
+                });
+                true
+            }
+            ContinuumEvent::OrderCancelled(e) if e.sequence == self.sequence && e.user == self.user => {
+                match &mut self.phase {
+                    OrderPhase::Pending => {
+                        self.phase = OrderPhase::Cancelled {
+                            signature: signature.map(str::to_string),
+                        };
+                        true
+                    }
+                    OrderPhase::Cancelled { signature: known } => {
+                        if known.is_none() {
+                            *known = signature.map(str::to_string);
+                        }
+                        false
+                    }
+                    _ => false,
+                }
+            }
+            ContinuumEvent::OrderExpired(e) if e.sequence == self.sequence && e.user == self.user => self.expire(),
+            _ => false,
+        }
+    }
+
+    /// Mark a still-pending order as expired
+    pub fn expire(&mut self) -> bool {
+        if self.phase.is_terminal() {
+            return false;
+        }
+        self.phase = OrderPhase::Expired;
+        true
+    }
+}
+
+/// Watches one order over websocket subscriptions
+pub struct OrderTracker {
+    phase: watch::Receiver<OrderPhase>,
+    task: JoinHandle<Result<(), PubsubClientError>>,
+}
+
+impl OrderTracker {
+    /// Start tracking the order at `order`. If `timeout` elapses while the
+    /// order is still pending it is reported as expired.
+    pub async fn start(
+        ws_url: &str,
+        order: Pubkey,
+        user: Pubkey,
+        sequence: u64,
+        timeout: Option<Duration>,
+    ) -> Result<Self, PubsubClientError> {
+        let client = PubsubClient::new(ws_url).await?;
+        let (sender, phase) = watch::channel(OrderPhase::Pending);
+
+        let task = tokio::spawn(async move {
+            let commitment = Some(CommitmentConfig::confirmed());
+            let (mut accounts, _unsubscribe_account) = client
+                .account_subscribe(
+                    &order,
+                    Some(RpcAccountInfoConfig {
+                        encoding: Some(UiAccountEncoding::Base64),
+                        commitment,
+                        ..RpcAccountInfoConfig::default()
+                    }),
+                )
+                .await?;
+            let (mut logs, _unsubscribe_logs) = client
+                .logs_subscribe(
+                    RpcTransactionLogsFilter::Mentions(vec![order.to_string()]),
+                    RpcTransactionLogsConfig { commitment },
+                )
+                .await?;
+
+            let mut lifecycle = OrderLifecycle::new(user, sequence);
+            let deadline = tokio::time::sleep(timeout.unwrap_or(Duration::MAX));
+            tokio::pin!(deadline);
+
+            while !lifecycle.phase().is_terminal() {
+                let changed = tokio::select! {
+                    Some(update) = accounts.next() => update
+                        .value
+                        .data
+                        .decode()
+                        .and_then(|data| decode::<OrderState>(&data).ok())
+                        .is_some_and(|state| lifecycle.apply_account(&state)),
+                    Some(update) = logs.next() => {
+                        let mut changed = false;
+                        if update.value.err.is_none() {
+                            for parsed in parse_logs(&update.value.logs) {
+                                changed |= lifecycle.apply_event(&parsed.event, Some(&update.value.signature));
+                            }
+                        }
+                        changed
+                    }
+                    _ = &mut deadline, if timeout.is_some() => lifecycle.expire(),
+                    else => break,
+                };
+                if changed && sender.send(lifecycle.phase().clone()).is_err() {
+                    break;
+                }
+            }
+            Ok(())
+        });
+
+        Ok(Self { phase, task })
+    }
+
+    /// Latest observed phase
+    pub fn phase(&self) -> OrderPhase {
+        self.phase.borrow().clone()
+    }
+
+    /// Subscribe to phase changes
+    pub fn watch(&self) -> watch::Receiver<OrderPhase> {
+        self.phase.clone()
+    }
+
+    /// Wait until the order reaches a terminal phase. Returns the last
+    /// observed phase if the subscriptions end first.
+    pub async fn wait(mut self) -> OrderPhase {
+        let terminal = self
+            .phase
+            .wait_for(OrderPhase::is_terminal)
+            .await
+            .map(|phase| phase.clone());
+        let phase = terminal.unwrap_or_else(|_| self.phase.borrow().clone());
+        self.task.abort();
+        phase
+    }
+}
@@ -0,0 +1,180 @@
+//! Assembly of versioned transactions. Executions reference more accounts than
+//! fit in a legacy transaction, so they are compiled against the pool's
+//! address lookup tables.
+
+use std::fmt;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use solana_address_lookup_table_interface::state::AddressLookupTable;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::hash::Hash;
+use solana_sdk::message::{v0, AddressLookupTableAccount, CompileError, VersionedMessage};
+use solana_sdk::signer::{signers::Signers, SignerError};
+use solana_sdk::transaction::VersionedTransaction;
+use solana_system_interface::instruction::advance_nonce_account;
+
+/// Compute unit limit requested for an `execute_order` transaction
+pub const DEFAULT_EXECUTION_COMPUTE_UNITS: u32 = 400_000;
+
+#[derive(Debug)]
+pub enum TransactionBuildError {
+    /// The lookup table account data could not be decoded
+    InvalidLookupTable(Pubkey),
+    Compile(CompileError),
+    Sign(SignerError),
+}
+
+impl fmt::Display for TransactionBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLookupTable(address) => write!(f, "invalid lookup table {}", address),
+            Self::Compile(err) => write!(f, "failed to compile message: {}", err),
+            Self::Sign(err) => write!(f, "failed to sign transaction: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for TransactionBuildError {}
+
+impl From<CompileError> for TransactionBuildError {
+    fn from(err: CompileError) -> Self {
+        Self::Compile(err)
+    }
+}
+
+impl From<SignerError> for TransactionBuildError {
+    fn from(err: SignerError) -> Self {
+        Self::Sign(err)
+    }
+}
+
+/// Decode a fetched address lookup table account
+pub fn decode_lookup_table(
+    address: Pubkey,
+    data: &[u8],
+) -> Result<AddressLookupTableAccount, TransactionBuildError> {
+    let table = AddressLookupTable::deserialize(data)
+        .map_err(|_| TransactionBuildError::InvalidLookupTable(address))?;
+    Ok(AddressLookupTableAccount {
+        key: address,
+        addresses: table.addresses.to_vec(),
+    })
+}
+
+/// Durable nonce account used in place of a recent blockhash
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DurableNonce {
+    pub account: Pubkey,
+    pub authority: Pubkey,
+}
+
+/// Builds a v0 transaction with compute budget instructions prepended
+#[derive(Clone)]
+pub struct TransactionBuilder {
+    payer: Pubkey,
+    instructions: Vec<Instruction>,
+    lookup_tables: Vec<AddressLookupTableAccount>,
+    compute_unit_limit: Option<u32>,
+    compute_unit_price: Option<u64>,
+    nonce: Option<DurableNonce>,
+}
+
+impl TransactionBuilder {
+    pub fn new(payer: Pubkey) -> Self {
+        Self {
+            payer,
+            instructions: Vec::new(),
+            lookup_tables: Vec::new(),
+            compute_unit_limit: None,
+            compute_unit_price: None,
+            nonce: None,
+        }
+    }
+
+    /// A builder preset for order executions
+    pub fn execution(executor: Pubkey, execute_ix: Instruction) -> Self {
+        Self::new(executor)
+            .compute_unit_limit(DEFAULT_EXECUTION_COMPUTE_UNITS)
+            .instruction(execute_ix)
+    }
+
+    pub fn instruction(mut self, ix: Instruction) -> Self {
+        self.instructions.push(ix);
+        self
+    }
+
+    pub fn instructions(mut self, ixs: impl IntoIterator<Item = Instruction>) -> Self {
+        self.instructions.extend(ixs);
+        self
+    }
+
+    pub fn lookup_table(mut self, table: AddressLookupTableAccount) -> Self {
+        self.lookup_tables.push(table);
+        self
+    }
+
+    pub fn compute_unit_limit(mut self, units: u32) -> Self {
+        self.compute_unit_limit = Some(units);
+        self
+    }
+
+    /// Priority fee, in micro-lamports per compute unit
+    pub fn compute_unit_price(mut self, micro_lamports: u64) -> Self {
+        self.compute_unit_price = Some(micro_lamports);
+        self
+    }
+
+    /// Sign with a durable nonce instead of a recent blockhash. The nonce
+    /// authority must be among the signers, and the blockhash passed to
+    /// [`Self::sign`] must be the nonce's stored blockhash.
+    pub fn durable_nonce(mut self, account: Pubkey, authority: Pubkey) -> Self {
+        self.nonce = Some(DurableNonce { account, authority });
+        self
+    }
+
+    pub fn nonce(&self) -> Option<DurableNonce> {
+        self.nonce
+    }
+
+    pub fn current_compute_unit_price(&self) -> Option<u64> {
+        self.compute_unit_price
+    }
+
+    /// All instructions in transaction order: nonce advance, compute budget,
+    /// then the caller's instructions
+    pub fn all_instructions(&self) -> Vec<Instruction> {
+        let mut ixs = Vec::with_capacity(self.instructions.len() + 3);
+        if let Some(nonce) = self.nonce {
+            ixs.push(advance_nonce_account(&nonce.account, &nonce.authority));
+        }
+        if let Some(units) = self.compute_unit_limit {
+            ixs.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+        }
+        if let Some(micro_lamports) = self.compute_unit_price {
+            ixs.push(ComputeBudgetInstruction::set_compute_unit_price(micro_lamports));
+        }
+        ixs.extend(self.instructions.iter().cloned());
+        ixs
+    }
+
+    pub fn message(&self, recent_blockhash: Hash) -> Result<VersionedMessage, TransactionBuildError> {
+        let message = v0::Message::try_compile(
+            &self.payer,
+            &self.all_instructions(),
+            &self.lookup_tables,
+            recent_blockhash,
+        )?;
+        Ok(VersionedMessage::V0(message))
+    }
+
+    /// Compile and sign. `signers` must include the payer.
+    pub fn sign<T: Signers + ?Sized>(
+        &self,
+        recent_blockhash: Hash,
+        signers: &T,
+    ) -> Result<VersionedTransaction, TransactionBuildError> {
+        let message = self.message(recent_blockhash)?;
+        Ok(VersionedTransaction::try_new(message, signers)?)
+    }
+}
@@ -0,0 +1,138 @@
+-- Schema of the Continuum indexer. Created on startup if missing.
+--
+-- Pubkeys and signatures are base58 text. Amounts are raw token units and
+-- u64 values are stored as BIGINT; `slot` columns hold the slot a row was
+-- last written at, and older updates never overwrite newer ones.
+
+-- The singleton FIFO state account
+CREATE TABLE IF NOT EXISTS fifo_state (
+    address TEXT PRIMARY KEY,
+    -- Sequence of simple submissions, which take no place in a pool's queue
+    current_sequence BIGINT NOT NULL,
+    admin TEXT NOT NULL,
+    emergency_pause BOOLEAN NOT NULL,
+    archived_orders BIGINT NOT NULL,
+    protocol_fee_bps INTEGER NOT NULL,
+    relayer_fee_bps INTEGER NOT NULL,
+    slot BIGINT NOT NULL
+);
+-- Checkpoints and event numbering moved to each pool's queue
+ALTER TABLE fifo_state DROP COLUMN IF EXISTS checkpoint_sequence;
+ALTER TABLE fifo_state DROP COLUMN IF EXISTS event_sequence;
+
+-- Each pool's FIFO queue, which sequences its orders
+CREATE TABLE IF NOT EXISTS pool_queues (
+    pool_id TEXT PRIMARY KEY,
+    address TEXT NOT NULL,
+    current_sequence BIGINT NOT NULL,
+    -- Highest sequence executed, skipped or settled in line
+    head BIGINT NOT NULL,
+    checkpoint_sequence BIGINT NOT NULL,
+    immediate_swaps BIGINT NOT NULL,
+    -- Number of events emitted about the pool
+    event_sequence BIGINT NOT NULL,
+    slot BIGINT NOT NULL
+);
+
+-- Registered CP-Swap pools, keyed by pool state address
+CREATE TABLE IF NOT EXISTS pools (
+    pool_id TEXT PRIMARY KEY,
+    registry TEXT NOT NULL,
+    token_0 TEXT NOT NULL,
+    token_1 TEXT NOT NULL,
+    continuum_authority TEXT NOT NULL,
+    is_active BOOLEAN NOT NULL,
+    created_at BIGINT NOT NULL,
+    -- Lifetime traded volume per side
+    volume_0 NUMERIC NOT NULL,
+    volume_1 NUMERIC NOT NULL,
+    slot BIGINT NOT NULL
+);
+
+-- Every order seen, from its account while it exists and from its events
+-- after it is closed, keyed by its pool and its sequence in the pool's
+-- queue. Indexes from before orders were sequenced by their pool keep their
+-- `orders` table, keyed by the global sequence, as it was.
+CREATE TABLE IF NOT EXISTS pool_orders (
+    pool TEXT NOT NULL,
+    sequence BIGINT NOT NULL,
+    address TEXT,
+    owner TEXT NOT NULL,
+    amount_in BIGINT,
+    min_amount_out BIGINT,
+    is_base_input BOOLEAN,
+    recipient TEXT,
+    -- pending, executed, cancelled or failed
+    status TEXT NOT NULL,
+    submitted_at BIGINT,
+    executed_at BIGINT,
+    amount_out BIGINT,
+    executor TEXT,
+    -- Set once the order is moved into the archive tree
+    archived BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Fill breakdown from the OrderExecuted event
+    amount_spent BIGINT,
+    -- Output per unit of input, scaled by 10^9
+    price BIGINT,
+    protocol_fee BIGINT,
+    relayer_fee BIGINT,
+    -- Fees the flat rates would have charged, less those charged
+    rebate BIGINT,
+    -- Trace ID in hex, also carried by the order's events
+    trace_id TEXT,
+    -- The submitter's client order ID in hex and memo, NULL when not given
+    client_order_id TEXT,
+    memo TEXT,
+    slot BIGINT NOT NULL,
+    PRIMARY KEY (pool, sequence)
+);
+CREATE INDEX IF NOT EXISTS pool_orders_trace_id ON pool_orders (trace_id);
+CREATE INDEX IF NOT EXISTS pool_orders_owner_client_order_id ON pool_orders (owner, client_order_id);
+CREATE INDEX IF NOT EXISTS pool_orders_owner ON pool_orders (owner);
+CREATE INDEX IF NOT EXISTS pool_orders_pool_status ON pool_orders (pool, status);
+
+CREATE TABLE IF NOT EXISTS relayers (
+    relayer TEXT PRIMARY KEY,
+    address TEXT NOT NULL,
+    registered_at BIGINT NOT NULL,
+    last_active BIGINT NOT NULL,
+    last_active_slot BIGINT NOT NULL,
+    slot BIGINT NOT NULL
+);
+
+-- Every program event from successful transactions. Columns that don't
+-- apply to an event's kind are NULL.
+CREATE TABLE IF NOT EXISTS events (
+    signature TEXT NOT NULL,
+    -- Position of the event within its transaction
+    event_index INTEGER NOT NULL,
+    slot BIGINT NOT NULL,
+    -- Number of the event among its pool's events, 0 for events outside any
+    -- pool and for events from before it was introduced
+    event_sequence BIGINT NOT NULL,
+    -- Event name, e.g. OrderSubmitted
+    kind TEXT NOT NULL,
+    -- Order sequence number in its pool's queue, or the immediate swap's
+    -- number among the pool's
+    sequence BIGINT,
+    owner TEXT,
+    pool TEXT,
+    amount_in BIGINT,
+    amount_out BIGINT,
+    -- Executing, fee-earning, added or removed relayer
+    relayer TEXT,
+    PRIMARY KEY (signature, event_index)
+);
+-- Trace ID in hex of the order an event is about, added after the table
+ALTER TABLE events ADD COLUMN IF NOT EXISTS trace_id TEXT;
+CREATE INDEX IF NOT EXISTS events_sequence ON events (sequence);
+CREATE INDEX IF NOT EXISTS events_pool_sequence ON events (pool, sequence);
+CREATE INDEX IF NOT EXISTS events_pool_event_sequence ON events (pool, event_sequence);
+CREATE INDEX IF NOT EXISTS events_trace_id ON events (trace_id);
+CREATE INDEX IF NOT EXISTS events_kind_slot ON events (kind, slot);
+
+-- Progress of the stream, used to resume after a restart
+CREATE TABLE IF NOT EXISTS indexer_state (
+    id INTEGER PRIMARY KEY,
+    last_slot BIGINT NOT NULL
+);
@@ -0,0 +1,51 @@
+//! Indexer configuration, read from a TOML file.
+//!
+//! ```toml
+//! grpc_url = "https://yellowstone.example.com"
+//! x_token = "<access token>"
+//! # Used to load the program's accounts when the stream connects
+//! rpc_url = "https://api.mainnet-beta.solana.com"
+//! database_url = "postgres://indexer@localhost/continuum"
+//! # processed, confirmed or finalized
+//! commitment = "confirmed"
+//! # Resume from the last indexed slot; the endpoint must still hold it
+//! replay = false
+//! ```
+
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use yellowstone_grpc_proto::prelude::CommitmentLevel;
+
+fn default_commitment() -> String {
+    "confirmed".to_string()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IndexerConfig {
+    /// Yellowstone gRPC endpoint
+    pub grpc_url: String,
+    #[serde(default)]
+    pub x_token: Option<String>,
+    pub rpc_url: String,
+    /// Postgres URL the index is written to
+    pub database_url: String,
+    #[serde(default = "default_commitment")]
+    pub commitment: String,
+    #[serde(default)]
+    pub replay: bool,
+}
+
+impl IndexerConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read config {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("invalid config {}", path.display()))
+    }
+
+    pub fn commitment(&self) -> Result<CommitmentLevel> {
+        CommitmentLevel::from_str_name(&self.commitment.to_uppercase())
+            .ok_or_else(|| anyhow!("invalid commitment `{}`", self.commitment))
+    }
+}
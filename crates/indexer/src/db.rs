@@ -0,0 +1,545 @@
+//! Writes to the Postgres index. See `schema.sql` for the tables.
+
+use anyhow::{Context, Result};
+use solana_sdk::pubkey::Pubkey;
+use sqlx::postgres::PgPoolOptions;
+use sqlx::{PgPool, Postgres, Transaction};
+
+use continuum_cp_swap_client::accounts::{
+    ClientOrderId, CpSwapPoolRegistry, FifoState, OrderState, OrderStatus, PoolFifoState, RelayerInfo,
+};
+use continuum_cp_swap_client::events::{ContinuumEvent, EventVersion, ParsedEvent};
+use continuum_cp_swap_client::trace::{self, TraceId};
+
+const SCHEMA: &str = include_str!("../schema.sql");
+
+fn client_order_id_hex(client_order_id: &ClientOrderId) -> String {
+    client_order_id.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn order_status(status: &OrderStatus) -> &'static str {
+    match status {
+        OrderStatus::Pending => "pending",
+        OrderStatus::Executed => "executed",
+        OrderStatus::Cancelled => "cancelled",
+        OrderStatus::Failed => "failed",
+        OrderStatus::Expired => "expired",
+    }
+}
+
+/// The pool an order event names; legacy order events other than
+/// `OrderSubmitted` are decoded with the default key, as they named none
+fn named_pool(pool_id: Pubkey) -> Option<Pubkey> {
+    (pool_id != Pubkey::default()).then_some(pool_id)
+}
+
+/// The indexed columns of one event
+#[derive(Default)]
+struct EventRow {
+    kind: &'static str,
+    sequence: Option<u64>,
+    owner: Option<Pubkey>,
+    pool: Option<Pubkey>,
+    amount_in: Option<u64>,
+    amount_out: Option<u64>,
+    relayer: Option<Pubkey>,
+    trace_id: Option<TraceId>,
+}
+
+impl From<&ContinuumEvent> for EventRow {
+    fn from(event: &ContinuumEvent) -> Self {
+        match event {
+            ContinuumEvent::OrderSubmitted(e) => Self {
+                kind: "OrderSubmitted",
+                sequence: Some(e.sequence),
+                owner: Some(e.user),
+                pool: Some(e.pool_id),
+                amount_in: Some(e.amount_in),
+                trace_id: Some(e.trace_id),
+                ..Self::default()
+            },
+            ContinuumEvent::OrderExecuted(e) => Self {
+                kind: "OrderExecuted",
+                sequence: Some(e.sequence),
+                pool: named_pool(e.pool_id),
+                owner: Some(e.user),
+                amount_in: Some(e.amount_in),
+                amount_out: Some(e.amount_out),
+                relayer: Some(e.executor),
+                trace_id: Some(e.trace_id),
+            },
+            ContinuumEvent::OrderCancelled(e) => Self {
+                kind: "OrderCancelled",
+                sequence: Some(e.sequence),
+                pool: named_pool(e.pool_id),
+                owner: Some(e.user),
+                trace_id: Some(e.trace_id),
+                ..Self::default()
+            },
+            ContinuumEvent::SwapExecuted(e) => Self {
+                kind: "SwapExecuted",
+                sequence: Some(e.sequence),
+                owner: Some(e.user),
+                pool: Some(e.pool_id),
+                amount_in: Some(e.amount_in),
+                ..Self::default()
+            },
+            ContinuumEvent::PoolRegistered(e) => Self {
+                kind: "PoolRegistered",
+                pool: Some(e.pool_id),
+                ..Self::default()
+            },
+            ContinuumEvent::OrderArchived(e) => Self {
+                kind: "OrderArchived",
+                sequence: Some(e.sequence),
+                owner: Some(e.user),
+                pool: Some(e.pool_id),
+                ..Self::default()
+            },
+            ContinuumEvent::CheckpointRecorded(e) => Self {
+                kind: "CheckpointRecorded",
+                sequence: Some(e.sequence),
+                pool: named_pool(e.pool_id),
+                ..Self::default()
+            },
+            ContinuumEvent::FeesAccrued(e) => Self {
+                kind: "FeesAccrued",
+                sequence: Some(e.sequence),
+                pool: Some(e.pool_id),
+                relayer: Some(e.relayer),
+                ..Self::default()
+            },
+            ContinuumEvent::RelayerFeesClaimed(e) => Self {
+                kind: "RelayerFeesClaimed",
+                pool: Some(e.pool_id),
+                relayer: Some(e.relayer),
+                ..Self::default()
+            },
+            ContinuumEvent::ProtocolFeesWithdrawn(e) => Self {
+                kind: "ProtocolFeesWithdrawn",
+                pool: Some(e.pool_id),
+                ..Self::default()
+            },
+            ContinuumEvent::InsurancePaidOut(e) => Self {
+                kind: "InsurancePaidOut",
+                pool: Some(e.pool_id),
+                owner: Some(e.recipient),
+                amount_out: Some(e.amount),
+                ..Self::default()
+            },
+            ContinuumEvent::OrderCompensated(e) => Self {
+                kind: "OrderCompensated",
+                sequence: Some(e.sequence),
+                owner: Some(e.user),
+                pool: Some(e.pool_id),
+                amount_out: Some(e.amount),
+                ..Self::default()
+            },
+            ContinuumEvent::RelayerAdded(e) => Self {
+                kind: "RelayerAdded",
+                relayer: Some(e.relayer),
+                ..Self::default()
+            },
+            ContinuumEvent::RelayerRemoved(e) => Self {
+                kind: "RelayerRemoved",
+                relayer: Some(e.relayer),
+                ..Self::default()
+            },
+            ContinuumEvent::CrossChainOrderReceived(e) => Self {
+                kind: "CrossChainOrderReceived",
+                sequence: Some(e.sequence),
+                pool: named_pool(e.pool_id),
+                owner: Some(e.user),
+                ..Self::default()
+            },
+            ContinuumEvent::FillAttested(e) => Self {
+                kind: "FillAttested",
+                sequence: Some(e.sequence),
+                pool: Some(e.pool_id),
+                ..Self::default()
+            },
+            ContinuumEvent::TrailUpdated(e) => Self {
+                kind: "TrailUpdated",
+                sequence: Some(e.sequence),
+                pool: named_pool(e.pool_id),
+                ..Self::default()
+            },
+            ContinuumEvent::OrderExpired(e) => Self {
+                kind: "OrderExpired",
+                sequence: Some(e.sequence),
+                pool: named_pool(e.pool_id),
+                owner: Some(e.user),
+                trace_id: Some(e.trace_id),
+                ..Self::default()
+            },
+            ContinuumEvent::TwapSliceExecuted(e) => Self {
+                kind: "TwapSliceExecuted",
+                sequence: Some(e.sequence),
+                pool: named_pool(e.pool_id),
+                owner: Some(e.user),
+                relayer: Some(e.executor),
+                amount_in: Some(e.amount_in),
+                trace_id: Some(e.trace_id),
+                ..Self::default()
+            },
+            ContinuumEvent::OrderPartiallyFilled(e) => Self {
+                kind: "OrderPartiallyFilled",
+                sequence: Some(e.sequence),
+                pool: named_pool(e.pool_id),
+                owner: Some(e.user),
+                relayer: Some(e.executor),
+                amount_in: Some(e.amount_in),
+                trace_id: Some(e.trace_id),
+                ..Self::default()
+            },
+            ContinuumEvent::DcaTickExecuted(e) => Self {
+                kind: "DcaTickExecuted",
+                owner: Some(e.user),
+                pool: Some(e.pool_id),
+                relayer: Some(e.executor),
+                amount_in: Some(e.amount_in),
+                amount_out: Some(e.amount_out),
+                ..Self::default()
+            },
+            ContinuumEvent::OrderModified(e) => Self {
+                kind: "OrderModified",
+                sequence: Some(e.sequence),
+                pool: named_pool(e.pool_id),
+                owner: Some(e.user),
+                amount_in: Some(e.amount_in),
+                trace_id: Some(e.trace_id),
+                ..Self::default()
+            },
+            ContinuumEvent::PauseSet(e) => Self {
+                kind: "PauseSet",
+                pool: e.pool_id,
+                ..Self::default()
+            },
+            ContinuumEvent::ExecutionRejected(e) => Self {
+                kind: "ExecutionRejected",
+                sequence: Some(e.sequence),
+                pool: named_pool(e.pool_id),
+                relayer: Some(e.executor),
+                trace_id: Some(e.trace_id),
+                ..Self::default()
+            },
+        }
+    }
+}
+
+pub struct Database {
+    pool: PgPool,
+}
+
+impl Database {
+    /// Connect and create the tables if needed
+    pub async fn connect(url: &str) -> Result<Self> {
+        let pool = PgPoolOptions::new()
+            .max_connections(4)
+            .connect(url)
+            .await
+            .context("failed to connect to the index database")?;
+        sqlx::raw_sql(SCHEMA).execute(&pool).await?;
+        Ok(Self { pool })
+    }
+
+    /// Last slot fully written, if any
+    pub async fn last_slot(&self) -> Result<Option<u64>> {
+        let row: Option<(i64,)> = sqlx::query_as("SELECT last_slot FROM indexer_state WHERE id = 1")
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.map(|(slot,)| slot as u64))
+    }
+
+    pub async fn set_last_slot(&self, slot: u64) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO indexer_state (id, last_slot) VALUES (1, $1)
+             ON CONFLICT (id) DO UPDATE SET last_slot = GREATEST(indexer_state.last_slot, EXCLUDED.last_slot)",
+        )
+        .bind(slot as i64)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn upsert_fifo(&self, address: &Pubkey, fifo: &FifoState, slot: u64) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO fifo_state (address, current_sequence, admin, emergency_pause,
+                archived_orders, protocol_fee_bps, relayer_fee_bps, slot)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             ON CONFLICT (address) DO UPDATE SET
+                current_sequence = EXCLUDED.current_sequence,
+                admin = EXCLUDED.admin,
+                emergency_pause = EXCLUDED.emergency_pause,
+                archived_orders = EXCLUDED.archived_orders,
+                protocol_fee_bps = EXCLUDED.protocol_fee_bps,
+                relayer_fee_bps = EXCLUDED.relayer_fee_bps,
+                slot = EXCLUDED.slot
+             WHERE fifo_state.slot <= EXCLUDED.slot",
+        )
+        .bind(address.to_string())
+        .bind(fifo.current_sequence as i64)
+        .bind(fifo.admin.to_string())
+        .bind(fifo.emergency_pause)
+        .bind(fifo.archived_orders as i64)
+        .bind(fifo.protocol_fee_bps as i32)
+        .bind(fifo.relayer_fee_bps as i32)
+        .bind(slot as i64)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn upsert_pool_fifo(&self, address: &Pubkey, fifo: &PoolFifoState, slot: u64) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO pool_queues (pool_id, address, current_sequence, head, checkpoint_sequence,
+                immediate_swaps, event_sequence, slot)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             ON CONFLICT (pool_id) DO UPDATE SET
+                current_sequence = EXCLUDED.current_sequence,
+                head = EXCLUDED.head,
+                checkpoint_sequence = EXCLUDED.checkpoint_sequence,
+                immediate_swaps = EXCLUDED.immediate_swaps,
+                event_sequence = EXCLUDED.event_sequence,
+                slot = EXCLUDED.slot
+             WHERE pool_queues.slot <= EXCLUDED.slot",
+        )
+        .bind(fifo.pool_id.to_string())
+        .bind(address.to_string())
+        .bind(fifo.current_sequence as i64)
+        .bind(fifo.head as i64)
+        .bind(fifo.checkpoint_sequence as i64)
+        .bind(fifo.immediate_swaps as i64)
+        .bind(fifo.event_sequence as i64)
+        .bind(slot as i64)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn upsert_pool(&self, registry: &Pubkey, pool: &CpSwapPoolRegistry, slot: u64) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO pools (pool_id, registry, token_0, token_1, continuum_authority,
+                is_active, created_at, volume_0, volume_1, slot)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8::NUMERIC, $9::NUMERIC, $10)
+             ON CONFLICT (pool_id) DO UPDATE SET
+                is_active = EXCLUDED.is_active,
+                volume_0 = EXCLUDED.volume_0,
+                volume_1 = EXCLUDED.volume_1,
+                slot = EXCLUDED.slot
+             WHERE pools.slot <= EXCLUDED.slot",
+        )
+        .bind(pool.pool_id.to_string())
+        .bind(registry.to_string())
+        .bind(pool.token_0.to_string())
+        .bind(pool.token_1.to_string())
+        .bind(pool.continuum_authority.to_string())
+        .bind(pool.is_active)
+        .bind(pool.created_at)
+        .bind(pool.volume_0.lifetime.to_string())
+        .bind(pool.volume_1.lifetime.to_string())
+        .bind(slot as i64)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn upsert_order(&self, address: &Pubkey, order: &OrderState, slot: u64) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO pool_orders (pool, sequence, address, owner, amount_in, min_amount_out,
+                is_base_input, recipient, status, submitted_at, executed_at, trace_id,
+                client_order_id, memo, slot)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+             ON CONFLICT (pool, sequence) DO UPDATE SET
+                address = EXCLUDED.address,
+                amount_in = EXCLUDED.amount_in,
+                min_amount_out = EXCLUDED.min_amount_out,
+                is_base_input = EXCLUDED.is_base_input,
+                recipient = EXCLUDED.recipient,
+                status = EXCLUDED.status,
+                submitted_at = EXCLUDED.submitted_at,
+                executed_at = EXCLUDED.executed_at,
+                trace_id = EXCLUDED.trace_id,
+                client_order_id = EXCLUDED.client_order_id,
+                memo = EXCLUDED.memo,
+                slot = EXCLUDED.slot
+             WHERE pool_orders.slot <= EXCLUDED.slot",
+        )
+        .bind(order.pool_id.to_string())
+        .bind(order.sequence as i64)
+        .bind(address.to_string())
+        .bind(order.user.to_string())
+        .bind(order.amount_in as i64)
+        .bind(order.min_amount_out as i64)
+        .bind(order.is_base_input)
+        .bind(order.recipient.map(|recipient| recipient.to_string()))
+        .bind(order_status(&order.status))
+        .bind(order.submitted_at)
+        .bind(order.executed_at)
+        .bind(trace::to_hex(&order.trace_id))
+        .bind(order.client_order_id.as_ref().map(client_order_id_hex))
+        .bind(order.memo.clone())
+        .bind(slot as i64)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn upsert_relayer(&self, address: &Pubkey, relayer: &RelayerInfo, slot: u64) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO relayers (relayer, address, registered_at, last_active, last_active_slot, slot)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             ON CONFLICT (relayer) DO UPDATE SET
+                last_active = EXCLUDED.last_active,
+                last_active_slot = EXCLUDED.last_active_slot,
+                slot = EXCLUDED.slot
+             WHERE relayers.slot <= EXCLUDED.slot",
+        )
+        .bind(relayer.relayer.to_string())
+        .bind(address.to_string())
+        .bind(relayer.registered_at)
+        .bind(relayer.last_active)
+        .bind(relayer.last_active_slot as i64)
+        .bind(slot as i64)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record the events of one transaction and apply them to the order
+    /// rows, so closed orders keep their final status
+    pub async fn record_events(&self, signature: &str, slot: u64, events: &[ParsedEvent]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for (index, parsed) in events.iter().enumerate() {
+            let row = EventRow::from(&parsed.event);
+            let inserted = sqlx::query(
+                "INSERT INTO events (signature, event_index, slot, event_sequence, kind, sequence,
+                    owner, pool, amount_in, amount_out, relayer, trace_id)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                 ON CONFLICT (signature, event_index) DO NOTHING",
+            )
+            .bind(signature)
+            .bind(index as i32)
+            .bind(slot as i64)
+            .bind(parsed.event.event_sequence() as i64)
+            .bind(row.kind)
+            .bind(row.sequence.map(|sequence| sequence as i64))
+            .bind(row.owner.map(|owner| owner.to_string()))
+            .bind(row.pool.map(|pool| pool.to_string()))
+            .bind(row.amount_in.map(|amount| amount as i64))
+            .bind(row.amount_out.map(|amount| amount as i64))
+            .bind(row.relayer.map(|relayer| relayer.to_string()))
+            .bind(row.trace_id.as_ref().map(trace::to_hex))
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+            // Replayed transactions were applied the first time
+            if inserted == 1 {
+                apply_event(&mut tx, parsed, slot).await?;
+            }
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+async fn apply_event(tx: &mut Transaction<'_, Postgres>, parsed: &ParsedEvent, slot: u64) -> Result<()> {
+    match &parsed.event {
+        ContinuumEvent::OrderSubmitted(e) => {
+            sqlx::query(
+                "INSERT INTO pool_orders (pool, sequence, owner, amount_in, is_base_input, status, trace_id,
+                    client_order_id, memo, slot)
+                 VALUES ($1, $2, $3, $4, $5, 'pending', $6, $7, $8, $9)
+                 ON CONFLICT (pool, sequence) DO NOTHING",
+            )
+            .bind(e.pool_id.to_string())
+            .bind(e.sequence as i64)
+            .bind(e.user.to_string())
+            .bind(e.amount_in as i64)
+            .bind(e.is_base_input)
+            .bind(trace::to_hex(&e.trace_id))
+            .bind(e.client_order_id.as_ref().map(client_order_id_hex))
+            .bind(e.memo.clone())
+            .bind(slot as i64)
+            .execute(&mut **tx)
+            .await?;
+        }
+        ContinuumEvent::OrderExecuted(e) if named_pool(e.pool_id).is_some() => {
+            // Older layouts carry no breakdown, which is left NULL
+            let breakdown = matches!(parsed.version, EventVersion::V2 | EventVersion::V3 | EventVersion::V4).then_some(e);
+            sqlx::query(
+                "INSERT INTO pool_orders (pool, sequence, owner, status, amount_out, executor, amount_spent,
+                    price, protocol_fee, relayer_fee, rebate, slot)
+                 VALUES ($1, $2, $3, 'executed', $4, $5, $6, $7, $8, $9, $10, $11)
+                 ON CONFLICT (pool, sequence) DO UPDATE SET
+                    status = 'executed',
+                    amount_out = EXCLUDED.amount_out,
+                    executor = EXCLUDED.executor,
+                    amount_spent = EXCLUDED.amount_spent,
+                    price = EXCLUDED.price,
+                    protocol_fee = EXCLUDED.protocol_fee,
+                    relayer_fee = EXCLUDED.relayer_fee,
+                    rebate = EXCLUDED.rebate,
+                    slot = GREATEST(pool_orders.slot, EXCLUDED.slot)",
+            )
+            .bind(e.pool_id.to_string())
+            .bind(e.sequence as i64)
+            .bind(e.user.to_string())
+            .bind(e.amount_out as i64)
+            .bind(e.executor.to_string())
+            .bind(breakdown.map(|e| e.amount_in as i64))
+            .bind(breakdown.map(|e| e.price as i64))
+            .bind(breakdown.map(|e| e.protocol_fee as i64))
+            .bind(breakdown.map(|e| e.relayer_fee as i64))
+            .bind(breakdown.map(|e| e.rebate as i64))
+            .bind(slot as i64)
+            .execute(&mut **tx)
+            .await?;
+        }
+        ContinuumEvent::OrderCancelled(e) if named_pool(e.pool_id).is_some() => {
+            sqlx::query(
+                "INSERT INTO pool_orders (pool, sequence, owner, status, slot)
+                 VALUES ($1, $2, $3, 'cancelled', $4)
+                 ON CONFLICT (pool, sequence) DO UPDATE SET
+                    status = 'cancelled',
+                    slot = GREATEST(pool_orders.slot, EXCLUDED.slot)",
+            )
+            .bind(e.pool_id.to_string())
+            .bind(e.sequence as i64)
+            .bind(e.user.to_string())
+            .bind(slot as i64)
+            .execute(&mut **tx)
+            .await?;
+        }
+        ContinuumEvent::OrderExpired(e) => {
+            sqlx::query(
+                "INSERT INTO pool_orders (pool, sequence, owner, status, slot)
+                 VALUES ($1, $2, $3, 'expired', $4)
+                 ON CONFLICT (pool, sequence) DO UPDATE SET
+                    status = 'expired',
+                    slot = GREATEST(pool_orders.slot, EXCLUDED.slot)",
+            )
+            .bind(e.pool_id.to_string())
+            .bind(e.sequence as i64)
+            .bind(e.user.to_string())
+            .bind(slot as i64)
+            .execute(&mut **tx)
+            .await?;
+        }
+        ContinuumEvent::OrderArchived(e) => {
+            sqlx::query("UPDATE pool_orders SET archived = TRUE WHERE pool = $1 AND sequence = $2")
+                .bind(e.pool_id.to_string())
+                .bind(e.sequence as i64)
+                .execute(&mut **tx)
+                .await?;
+        }
+        ContinuumEvent::RelayerRemoved(e) => {
+            sqlx::query("DELETE FROM relayers WHERE relayer = $1")
+                .bind(e.relayer.to_string())
+                .execute(&mut **tx)
+                .await?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
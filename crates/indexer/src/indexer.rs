@@ -0,0 +1,160 @@
+//! The Yellowstone subscription. Program-owned accounts are upserted as they
+//! change and every successful transaction touching the program has its
+//! events recorded. Yellowstone only reports changes, so the accounts are
+//! also loaded with `getProgramAccounts` each time the stream (re)connects.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use futures_util::{SinkExt, StreamExt};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use yellowstone_grpc_client::{ClientTlsConfig, GeyserGrpcClient};
+use yellowstone_grpc_proto::prelude::subscribe_update::UpdateOneof;
+use yellowstone_grpc_proto::prelude::{
+    CommitmentLevel, SubscribeRequest, SubscribeRequestFilterAccounts,
+    SubscribeRequestFilterSlots, SubscribeRequestFilterTransactions, SubscribeRequestPing,
+};
+
+use continuum_cp_swap_client::accounts::{
+    decode, CpSwapPoolRegistry, FifoState, OrderState, PoolFifoState, RelayerInfo,
+};
+use continuum_cp_swap_client::events::parse_logs;
+use continuum_cp_swap_client::PROGRAM_ID;
+
+use crate::config::IndexerConfig;
+use crate::db::Database;
+
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Name of every filter in the subscription
+const FILTER: &str = "continuum";
+
+/// Write one program account. Account types the index doesn't cover are
+/// skipped.
+async fn index_account(db: &Database, address: &Pubkey, data: &[u8], slot: u64) -> Result<()> {
+    if let Ok(order) = decode::<OrderState>(data) {
+        db.upsert_order(address, &order, slot).await
+    } else if let Ok(pool) = decode::<CpSwapPoolRegistry>(data) {
+        db.upsert_pool(address, &pool, slot).await
+    } else if let Ok(relayer) = decode::<RelayerInfo>(data) {
+        db.upsert_relayer(address, &relayer, slot).await
+    } else if let Ok(fifo) = decode::<FifoState>(data) {
+        db.upsert_fifo(address, &fifo, slot).await
+    } else if let Ok(fifo) = decode::<PoolFifoState>(data) {
+        db.upsert_pool_fifo(address, &fifo, slot).await
+    } else {
+        Ok(())
+    }
+}
+
+/// Load every program account as of the current slot
+async fn backfill(rpc: &RpcClient, db: &Database) -> Result<()> {
+    let slot = rpc.get_slot().await?;
+    let accounts = rpc.get_program_accounts(&PROGRAM_ID).await?;
+    for (address, account) in &accounts {
+        index_account(db, address, &account.data, slot).await?;
+    }
+    tracing::info!(slot, accounts = accounts.len(), "loaded program accounts");
+    Ok(())
+}
+
+fn subscribe_request(commitment: CommitmentLevel, from_slot: Option<u64>) -> SubscribeRequest {
+    let program = PROGRAM_ID.to_string();
+    SubscribeRequest {
+        accounts: HashMap::from([(
+            FILTER.to_string(),
+            SubscribeRequestFilterAccounts {
+                owner: vec![program.clone()],
+                ..SubscribeRequestFilterAccounts::default()
+            },
+        )]),
+        transactions: HashMap::from([(
+            FILTER.to_string(),
+            SubscribeRequestFilterTransactions {
+                vote: Some(false),
+                failed: Some(false),
+                account_include: vec![program],
+                ..SubscribeRequestFilterTransactions::default()
+            },
+        )]),
+        slots: HashMap::from([(
+            FILTER.to_string(),
+            SubscribeRequestFilterSlots {
+                filter_by_commitment: Some(true),
+                ..SubscribeRequestFilterSlots::default()
+            },
+        )]),
+        commitment: Some(commitment as i32),
+        from_slot,
+        ..SubscribeRequest::default()
+    }
+}
+
+async fn stream(config: &IndexerConfig, db: &Database, rpc: &RpcClient) -> Result<()> {
+    let mut builder = GeyserGrpcClient::build_from_shared(config.grpc_url.clone())?
+        .x_token(config.x_token.clone())?;
+    if config.grpc_url.starts_with("https") {
+        builder = builder.tls_config(ClientTlsConfig::new().with_native_roots())?;
+    }
+    let mut client = builder.connect().await?;
+
+    let from_slot = match config.replay {
+        true => db.last_slot().await?,
+        false => None,
+    };
+    let request = subscribe_request(config.commitment()?, from_slot);
+    let (mut requests, mut updates) = client.subscribe_with_request(Some(request)).await?;
+    tracing::info!(from_slot, "subscribed to geyser stream");
+
+    // Subscribed first, so nothing that changes during the backfill is missed
+    backfill(rpc, db).await?;
+
+    while let Some(update) = updates.next().await {
+        match update?.update_oneof {
+            Some(UpdateOneof::Account(update)) => {
+                let Some(account) = update.account else { continue };
+                let address = Pubkey::try_from(account.pubkey.as_slice())
+                    .map_err(|_| anyhow!("invalid account pubkey in update"))?;
+                index_account(db, &address, &account.data, update.slot).await?;
+            }
+            Some(UpdateOneof::Transaction(update)) => {
+                let Some(transaction) = update.transaction else { continue };
+                let Some(meta) = transaction.meta else { continue };
+                let events = parse_logs(&meta.log_messages);
+                if events.is_empty() {
+                    continue;
+                }
+                let signature = Signature::try_from(transaction.signature.as_slice())
+                    .map_err(|_| anyhow!("invalid signature in update"))?;
+                db.record_events(&signature.to_string(), update.slot, &events).await?;
+            }
+            Some(UpdateOneof::Slot(update)) => db.set_last_slot(update.slot).await?,
+            Some(UpdateOneof::Ping(_)) => {
+                requests
+                    .send(SubscribeRequest {
+                        ping: Some(SubscribeRequestPing { id: 1 }),
+                        ..SubscribeRequest::default()
+                    })
+                    .await?;
+            }
+            _ => {}
+        }
+    }
+    Err(anyhow!("geyser stream closed"))
+}
+
+/// Index until the process is stopped, reconnecting on failure
+pub async fn run(config: &IndexerConfig, db: &Database) -> Result<()> {
+    let rpc = RpcClient::new(config.rpc_url.clone());
+    let mut backoff = Duration::from_secs(1);
+    loop {
+        if let Err(err) = stream(config, db, &rpc).await {
+            tracing::warn!(error = format!("{:#}", err), "geyser stream dropped");
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_RECONNECT_BACKOFF);
+    }
+}
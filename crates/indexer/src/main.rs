@@ -0,0 +1,46 @@
+//! Continuum indexer. Follows the program's accounts and transactions over a
+//! Yellowstone gRPC stream and writes them to Postgres, as described in
+//! `schema.sql`: current account state for the FIFO, pools, orders and
+//! relayers, plus every emitted event. Order history and analytics are
+//! served from these tables, and pending rows of `orders` give relayers a
+//! second source for reconciliation besides `getProgramAccounts`.
+
+mod config;
+mod db;
+mod indexer;
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+use tracing_subscriber::EnvFilter;
+
+use crate::config::IndexerConfig;
+use crate::db::Database;
+
+#[derive(Parser)]
+#[command(name = "continuum-indexer", version, about)]
+struct Cli {
+    /// Indexer config file
+    #[arg(short, long, default_value = "indexer.toml")]
+    config: PathBuf,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .init();
+
+    let cli = Cli::parse();
+    let config = IndexerConfig::load(&cli.config)?;
+    let db = Database::connect(&config.database_url).await?;
+
+    tokio::select! {
+        result = indexer::run(&config, &db) => result,
+        _ = tokio::signal::ctrl_c() => {
+            tracing::info!("shutting down");
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! The generated operations.
+
+use proptest::prelude::*;
+use proptest::sample::Index;
+
+pub const USERS: usize = 3;
+pub const POOLS: usize = 2;
+
+#[derive(Debug, Clone)]
+pub enum Op {
+    Submit {
+        user: usize,
+        pool: usize,
+        sell_token_0: bool,
+        amount: u64,
+    },
+    /// Submit a signed intent expiring `expires_in` seconds from now, which
+    /// may already have passed
+    SubmitIntent {
+        user: usize,
+        pool: usize,
+        sell_token_0: bool,
+        amount: u64,
+        expires_in: i64,
+    },
+    /// Cancel one of the orders submitted so far, as its owner
+    Cancel { order: Index },
+    Execute { order: Index },
+    /// Move the mock clock forward
+    Advance { secs: i64 },
+}
+
+pub fn op() -> impl Strategy<Value = Op> {
+    let amount = 1u64..=1_000_000;
+    prop_oneof![
+        4 => (0..USERS, 0..POOLS, any::<bool>(), amount.clone()).prop_map(|(user, pool, sell_token_0, amount)| Op::Submit {
+            user,
+            pool,
+            sell_token_0,
+            amount,
+        }),
+        2 => (0..USERS, 0..POOLS, any::<bool>(), amount, -120i64..600).prop_map(
+            |(user, pool, sell_token_0, amount, expires_in)| Op::SubmitIntent {
+                user,
+                pool,
+                sell_token_0,
+                amount,
+                expires_in,
+            }
+        ),
+        2 => any::<Index>().prop_map(|order| Op::Cancel { order }),
+        4 => any::<Index>().prop_map(|order| Op::Execute { order }),
+        1 => (1i64..3_600).prop_map(|secs| Op::Advance { secs }),
+    ]
+}
@@ -0,0 +1,387 @@
+//! The fixture a case runs against, the model of its orders and the
+//! invariants checked after every step.
+
+use std::collections::HashMap;
+
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+use anyhow::{anyhow, Result};
+use proptest::test_runner::TestCaseError;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use continuum_cp_swap_client::accounts::{FifoState, OrderState, OrderStatus, PoolFifoState};
+use continuum_cp_swap_client::intent::OrderIntent;
+use continuum_cp_swap_client::pda;
+use continuum_program_test::{Fixture, FixtureConfig, Order, Pool};
+
+use crate::ops::{Op, POOLS, USERS};
+
+/// Mock clock time every case starts at
+const START_TIME: i64 = 1_700_000_000;
+const LIQUIDITY: u64 = 1_000_000_000_000;
+const USER_TOKENS: u64 = 100_000_000_000;
+const USER_LAMPORTS: u64 = 10_000_000_000;
+
+fn violation(message: String) -> TestCaseError {
+    TestCaseError::fail(message)
+}
+
+struct Tracked {
+    order: Order,
+    pool: usize,
+    amount_in: u64,
+    /// Status seen after the last step
+    status: OrderStatus,
+}
+
+pub struct World {
+    fixture: Fixture,
+    mints: Vec<Pubkey>,
+    pools: Vec<Pool>,
+    users: Vec<Keypair>,
+    executor: Keypair,
+    orders: Vec<Tracked>,
+    /// Orders accepted so far on each pool, which its FIFO sequence must
+    /// match
+    sequences: Vec<u64>,
+    now: i64,
+    next_nonce: u64,
+    /// Every token account holding a mint: the payer's, the users', and the
+    /// pool and fee vaults
+    holders: HashMap<Pubkey, Vec<Pubkey>>,
+    /// Users' token account balances at the start of the case
+    initial_balances: HashMap<Pubkey, u64>,
+}
+
+impl World {
+    /// Two pools sharing a mint, and users holding all three mints
+    pub async fn new() -> Result<Self> {
+        let mut fixture = Fixture::start(FixtureConfig::default()).await?;
+        fixture.set_time(Some(START_TIME)).await?;
+
+        let mut mints = Vec::with_capacity(POOLS + 1);
+        for _ in 0..=POOLS {
+            mints.push(fixture.create_mint(6).await?);
+        }
+        let mut pools = Vec::with_capacity(POOLS);
+        for quote in &mints[1..] {
+            pools.push(fixture.create_pool(mints[0], *quote, LIQUIDITY, LIQUIDITY).await?);
+        }
+        let balances: Vec<(Pubkey, u64)> = mints.iter().map(|mint| (*mint, USER_TOKENS)).collect();
+        let mut users = Vec::with_capacity(USERS);
+        for _ in 0..USERS {
+            users.push(fixture.create_user(USER_LAMPORTS, &balances).await?);
+        }
+
+        let executor = fixture.payer();
+        let mut holders: HashMap<Pubkey, Vec<Pubkey>> = HashMap::new();
+        let mut initial_balances = HashMap::new();
+        for mint in &mints {
+            let accounts = holders.entry(*mint).or_default();
+            accounts.push(get_associated_token_address(&executor.pubkey(), mint));
+            for user in &users {
+                let account = get_associated_token_address(&user.pubkey(), mint);
+                accounts.push(account);
+                initial_balances.insert(account, USER_TOKENS);
+            }
+        }
+        for pool in &pools {
+            let addresses = &pool.addresses;
+            for (mint, vault) in [
+                (addresses.token_0_mint, addresses.token_0_vault),
+                (addresses.token_1_mint, addresses.token_1_vault),
+            ] {
+                let accounts = holders.entry(mint).or_default();
+                accounts.push(vault);
+                accounts.push(pda::find_fee_vault(&pool.id(), &mint).0);
+            }
+        }
+
+        Ok(Self {
+            fixture,
+            mints,
+            pools,
+            users,
+            executor,
+            orders: Vec::new(),
+            sequences: vec![0; POOLS],
+            now: START_TIME,
+            next_nonce: 0,
+            holders,
+            initial_balances,
+        })
+    }
+
+    /// Apply `op` and check every invariant. Calls the program rejects are
+    /// fine; only the state they leave behind is judged.
+    pub async fn step(&mut self, op: &Op) -> Result<(), TestCaseError> {
+        match *op {
+            Op::Submit {
+                user,
+                pool,
+                sell_token_0,
+                amount,
+            } => {
+                let input_mint = self.input_mint(pool, sell_token_0);
+                let pool_info = self.pools[pool];
+                if let Ok(order) = self
+                    .fixture
+                    .submit_order(&self.users[user], &pool_info, input_mint, amount, 0)
+                    .await
+                {
+                    self.track(order, pool).await?;
+                }
+            }
+            Op::SubmitIntent {
+                user,
+                pool,
+                sell_token_0,
+                amount,
+                expires_in,
+            } => {
+                let input_mint = self.input_mint(pool, sell_token_0);
+                let pool_info = self.pools[pool];
+                let intent = OrderIntent {
+                    user: self.users[user].pubkey(),
+                    pool_id: pool_info.id(),
+                    amount_in: amount,
+                    min_amount_out: 0,
+                    is_base_input: true,
+                    input_mint,
+                    recipient: None,
+                    nonce: self.next_nonce,
+                    expires_at: self.now + expires_in,
+                };
+                self.next_nonce += 1;
+                if let Ok(order) = self
+                    .fixture
+                    .submit_order_intent(&self.users[user], &pool_info, intent)
+                    .await
+                {
+                    if expires_in < 0 {
+                        return Err(violation(format!(
+                            "intent that expired {}s ago was accepted as order {}",
+                            -expires_in, order.sequence
+                        )));
+                    }
+                    self.track(order, pool).await?;
+                }
+            }
+            Op::Cancel { order } => {
+                if self.orders.is_empty() {
+                    return Ok(());
+                }
+                let order = self.orders[order.index(self.orders.len())].order;
+                let user = self
+                    .users
+                    .iter()
+                    .find(|user| user.pubkey() == order.user)
+                    .expect("orders are submitted by known users");
+                let _ = self.fixture.cancel_order(user, &order).await;
+            }
+            Op::Execute { order } => {
+                if self.orders.is_empty() {
+                    return Ok(());
+                }
+                let tracked = &self.orders[order.index(self.orders.len())];
+                let (order, pool) = (tracked.order, self.pools[tracked.pool]);
+                let _ = self.fixture.execute_order(&self.executor, &pool, &order).await;
+            }
+            Op::Advance { secs } => {
+                self.now += secs;
+                self.fixture
+                    .set_time(Some(self.now))
+                    .await
+                    .map_err(|err| violation(format!("failed to move the mock clock: {}", err)))?;
+            }
+        }
+        self.check().await
+    }
+
+    fn input_mint(&self, pool: usize, sell_token_0: bool) -> Pubkey {
+        let addresses = &self.pools[pool].addresses;
+        if sell_token_0 {
+            addresses.token_0_mint
+        } else {
+            addresses.token_1_mint
+        }
+    }
+
+    /// Record an accepted order, which must take its pool's next sequence
+    /// number
+    async fn track(&mut self, order: Order, pool: usize) -> Result<(), TestCaseError> {
+        let state = self.order_state(&order).await?;
+        if state.sequence != self.sequences[pool] + 1 {
+            return Err(violation(format!(
+                "order accepted on pool {} after sequence {} was given sequence {}",
+                pool, self.sequences[pool], state.sequence
+            )));
+        }
+        if state.submitted_at != self.now {
+            return Err(violation(format!(
+                "order {} was submitted at {} instead of the mock clock's {}",
+                state.sequence, state.submitted_at, self.now
+            )));
+        }
+        self.sequences[pool] = state.sequence;
+        self.orders.push(Tracked {
+            order,
+            pool,
+            amount_in: state.amount_in,
+            status: state.status,
+        });
+        Ok(())
+    }
+
+    async fn order_state(&mut self, order: &Order) -> Result<OrderState, TestCaseError> {
+        self.fixture
+            .account(order.address)
+            .await
+            .map_err(|err| violation(format!("order {} unreadable: {}", order.sequence, err)))
+    }
+
+    async fn check(&mut self) -> Result<(), TestCaseError> {
+        let fifo: FifoState = self
+            .fixture
+            .account(pda::find_fifo_state().0)
+            .await
+            .map_err(|err| violation(format!("FIFO state unreadable: {}", err)))?;
+        for (pool, sequence) in self.sequences.iter().enumerate() {
+            let pool_id = self.pools[pool].id();
+            let pool_fifo: PoolFifoState = self
+                .fixture
+                .account(pda::find_pool_fifo(&pool_id).0)
+                .await
+                .map_err(|err| violation(format!("FIFO queue of pool {} unreadable: {}", pool, err)))?;
+            if pool_fifo.current_sequence != *sequence {
+                return Err(violation(format!(
+                    "FIFO sequence of pool {} is {} after {} accepted orders",
+                    pool, pool_fifo.current_sequence, sequence
+                )));
+            }
+        }
+
+        // Terminal statuses are final
+        let mut executed = Vec::new();
+        for index in 0..self.orders.len() {
+            let order = self.orders[index].order;
+            let state = self.order_state(&order).await?;
+            let tracked = &mut self.orders[index];
+            if tracked.status != state.status {
+                if tracked.status != OrderStatus::Pending {
+                    return Err(violation(format!(
+                        "order {} went from {:?} to {:?}",
+                        order.sequence, tracked.status, state.status
+                    )));
+                }
+                if state.status == OrderStatus::Executed {
+                    executed.push(index);
+                }
+                tracked.status = state.status;
+            }
+        }
+
+        // No order executes while an earlier order on its pool is pending
+        for index in executed {
+            let filled = &self.orders[index];
+            if let Some(earlier) = self.orders.iter().find(|other| {
+                other.pool == filled.pool
+                    && other.order.sequence < filled.order.sequence
+                    && other.status == OrderStatus::Pending
+            }) {
+                return Err(violation(format!(
+                    "order {} executed while earlier order {} on the same pool was pending",
+                    filled.order.sequence, earlier.order.sequence
+                )));
+            }
+        }
+
+        self.check_conservation(&fifo).await
+    }
+
+    /// Tokens are only moved, never created or lost, and a user is only
+    /// debited for orders of theirs that executed
+    async fn check_conservation(&mut self, fifo: &FifoState) -> Result<(), TestCaseError> {
+        for mint in self.mints.clone() {
+            let supply = self.supply(&mint).await?;
+            let mut held = 0u64;
+            for account in self.holders[&mint].clone() {
+                held += self.balance(&account).await?;
+            }
+            if held != supply {
+                return Err(violation(format!(
+                    "{} of mint {} is held by known accounts, but the supply is {}",
+                    held, mint, supply
+                )));
+            }
+        }
+
+        for user in self.users.iter().map(|user| user.pubkey()).collect::<Vec<_>>() {
+            for mint in self.mints.clone() {
+                let account = get_associated_token_address(&user, &mint);
+                let debited = self.initial_balances[&account].saturating_sub(self.balance(&account).await?);
+                let allowed: u64 = self
+                    .orders
+                    .iter()
+                    .filter(|tracked| {
+                        tracked.order.user == user
+                            && tracked.order.input_mint == mint
+                            && tracked.status == OrderStatus::Executed
+                    })
+                    .map(|tracked| {
+                        let (protocol_fee, relayer_fee) = fifo.compute_fees(tracked.amount_in);
+                        tracked.amount_in + protocol_fee + relayer_fee
+                    })
+                    .sum();
+                if debited > allowed {
+                    return Err(violation(format!(
+                        "user {} lost {} of mint {} but executed orders account for {}",
+                        user, debited, mint, allowed
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn balance(&mut self, account: &Pubkey) -> Result<u64, TestCaseError> {
+        self.fixture
+            .token_balance(*account)
+            .await
+            .map_err(|err| violation(format!("token account {} unreadable: {}", account, err)))
+    }
+
+    async fn supply(&mut self, mint: &Pubkey) -> Result<u64, TestCaseError> {
+        let account = self
+            .fixture
+            .context
+            .banks_client
+            .get_account(*mint)
+            .await
+            .ok()
+            .flatten()
+            .ok_or_else(|| violation(format!("mint {} unreadable", mint)))?;
+        spl_token::state::Mint::unpack(&account.data)
+            .map(|mint| mint.supply)
+            .map_err(|err| violation(format!("mint {} invalid: {}", mint, err)))
+    }
+}
+
+/// Run one generated sequence on a fresh fixture
+pub async fn run_case(ops: &[Op]) -> Result<(), TestCaseError> {
+    let mut world = World::new()
+        .await
+        .map_err(|err| TestCaseError::fail(format!("setup failed: {:#}", err)))?;
+    for op in ops {
+        world.step(op).await?;
+    }
+    Ok(())
+}
+
+/// Fail before running cases if the fixture can't be set up at all, e.g.
+/// because a program binary is missing
+pub async fn smoke() -> Result<()> {
+    World::new().await.map(drop).map_err(|err| anyhow!("fixture setup failed: {:#}", err))
+}
@@ -0,0 +1,112 @@
+//! Setting up the programs on a running validator: the FIFO state, mints,
+//! registered pools and the relayer.
+
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use anchor_spl::token::{self, spl_token};
+use anyhow::{anyhow, Context, Result};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_system_interface::instruction as system_instruction;
+
+use continuum_cp_swap_client::cp_swap::PoolAddresses;
+use continuum_cp_swap_client::instructions;
+use continuum_cp_swap_client::retry::{send_with_retry, RetryPolicy};
+use continuum_cp_swap_client::transaction::TransactionBuilder;
+
+/// Pool creation runs the CP-Swap initialize through a CPI
+const POOL_COMPUTE_UNITS: u32 = 400_000;
+
+pub struct Bootstrap {
+    pub rpc: RpcClient,
+    /// FIFO admin, mint authority and liquidity provider
+    pub payer: Keypair,
+    pub cp_swap_program: Pubkey,
+    pub amm_config: Pubkey,
+    pub policy: RetryPolicy,
+}
+
+impl Bootstrap {
+    async fn send(&self, builder: TransactionBuilder, signers: &[&Keypair], what: &str) -> Result<()> {
+        let mut all_signers = vec![&self.payer];
+        all_signers.extend_from_slice(signers);
+        send_with_retry(&self.rpc, builder, &all_signers, &self.policy)
+            .await
+            .map_err(|err| anyhow!(err))
+            .with_context(|| format!("failed to {}", what))?;
+        Ok(())
+    }
+
+    fn builder(&self, ixs: Vec<Instruction>) -> TransactionBuilder {
+        TransactionBuilder::new(self.payer.pubkey()).instructions(ixs)
+    }
+
+    pub async fn initialize(&self) -> Result<()> {
+        let ix = instructions::initialize(self.payer.pubkey());
+        self.send(self.builder(vec![ix]), &[], "initialize the FIFO state").await
+    }
+
+    /// Create an SPL Token mint and mint `amount` of it to the payer
+    pub async fn create_mint(&self, decimals: u8, amount: u64) -> Result<Pubkey> {
+        let payer = self.payer.pubkey();
+        let mint = Keypair::new();
+        let len = spl_token::state::Mint::LEN;
+        let rent = self.rpc.get_minimum_balance_for_rent_exemption(len).await?;
+        let account = get_associated_token_address(&payer, &mint.pubkey());
+
+        let builder = self.builder(vec![
+            system_instruction::create_account(&payer, &mint.pubkey(), rent, len as u64, &token::ID),
+            spl_token::instruction::initialize_mint2(&token::ID, &mint.pubkey(), &payer, None, decimals)?,
+            create_associated_token_account_idempotent(&payer, &payer, &mint.pubkey(), &token::ID),
+            spl_token::instruction::mint_to(&token::ID, &mint.pubkey(), &account, &payer, &[], amount)?,
+        ]);
+        self.send(builder, &[&mint], "create a mint").await?;
+        Ok(mint.pubkey())
+    }
+
+    /// Create and register a pool seeded with `liquidity` of each mint from
+    /// the payer's accounts
+    pub async fn create_pool(&self, mint_a: Pubkey, mint_b: Pubkey, liquidity: u64) -> Result<PoolAddresses> {
+        let admin = self.payer.pubkey();
+        let pool = PoolAddresses::derive(&self.cp_swap_program, &self.amm_config, mint_a, mint_b);
+        let create_pool_fee = get_associated_token_address(&admin, &pool.token_0_mint);
+
+        let builder = self
+            .builder(vec![instructions::initialize_cp_swap_pool(
+                admin,
+                None,
+                pool.pool_state,
+                self.cp_swap_program,
+                liquidity,
+                liquidity,
+                0,
+                pool.initialize_accounts(admin, self.amm_config, create_pool_fee),
+            )])
+            .compute_unit_limit(POOL_COMPUTE_UNITS);
+        self.send(builder, &[], "create the pool").await?;
+
+        let ix = instructions::initialize_fee_ledger(admin, pool.pool_state, pool.token_0_mint, pool.token_1_mint);
+        self.send(self.builder(vec![ix]), &[], "initialize the fee ledger").await?;
+        Ok(pool)
+    }
+
+    /// Register the relayer, send it `lamports` and open its fee balance on
+    /// every pool
+    pub async fn fund_relayer(&self, relayer: &Keypair, lamports: u64, pools: &[Pubkey]) -> Result<()> {
+        let payer = self.payer.pubkey();
+        let builder = self.builder(vec![
+            instructions::add_relayer(payer, relayer.pubkey()),
+            system_instruction::transfer(&payer, &relayer.pubkey(), lamports),
+        ]);
+        self.send(builder, &[], "fund the relayer").await?;
+
+        let ixs = pools
+            .iter()
+            .map(|pool| instructions::open_relayer_fee_balance(relayer.pubkey(), *pool))
+            .collect();
+        self.send(self.builder(ixs), &[relayer], "open the relayer fee balances").await
+    }
+}
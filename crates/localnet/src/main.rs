@@ -0,0 +1,233 @@
+//! `continuum-localnet`: one command to a working development environment.
+//!
+//! Starts `solana-test-validator` with the Continuum and CP-Swap programs
+//! and an AMM config loaded at genesis, initializes the FIFO state, creates
+//! mints and registered pools, funds a relayer and prints every address.
+//! Keypairs, `localnet.json` with the addresses and a ready-to-use
+//! `relayer.toml` are written to the output directory. The validator runs
+//! until interrupted.
+
+mod bootstrap;
+mod validator;
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use serde::Serialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, write_keypair_file, Keypair, Signer};
+
+use continuum_cp_swap_client::retry::RetryPolicy;
+use continuum_cp_swap_client::{pda, CP_SWAP_PROGRAM_ID, PROGRAM_ID};
+
+use crate::bootstrap::Bootstrap;
+use crate::validator::Genesis;
+
+#[derive(Parser)]
+#[command(name = "continuum-localnet", version, about)]
+struct Cli {
+    /// Keypairs, ledger, logs and generated config
+    #[arg(long, default_value = ".localnet")]
+    out_dir: PathBuf,
+
+    #[arg(long, default_value = "target/deploy/continuum_cp_swap.so")]
+    continuum_so: PathBuf,
+
+    /// CP-Swap binary, e.g. from `solana program dump`
+    #[arg(long, default_value = "target/deploy/raydium_cp_swap.so")]
+    cp_swap_so: PathBuf,
+
+    #[arg(long, default_value_t = CP_SWAP_PROGRAM_ID)]
+    cp_swap_program: Pubkey,
+
+    #[arg(long, default_value_t = 8899)]
+    rpc_port: u16,
+
+    /// Pools created, each pairing the base mint with its own quote mint
+    #[arg(long, default_value_t = 2)]
+    pools: usize,
+
+    /// Initial reserves of each side of every pool, in base units
+    #[arg(long, default_value_t = 1_000_000_000_000)]
+    liquidity: u64,
+
+    /// Tokens of each mint left with the payer for trading, in base units
+    #[arg(long, default_value_t = 1_000_000_000_000)]
+    payer_tokens: u64,
+
+    /// CP-Swap trade fee in millionths
+    #[arg(long, default_value_t = 2_500)]
+    trade_fee_rate: u64,
+
+    #[arg(long, default_value_t = 100 * LAMPORTS_PER_SOL)]
+    relayer_lamports: u64,
+
+    /// Stop the validator once set up instead of keeping it running
+    #[arg(long)]
+    exit: bool,
+}
+
+#[derive(Serialize)]
+struct PoolInfo {
+    pool_state: String,
+    registry: String,
+    token_0_mint: String,
+    token_1_mint: String,
+    lp_mint: String,
+}
+
+#[derive(Serialize)]
+struct Addresses {
+    rpc_url: String,
+    ws_url: String,
+    program_id: String,
+    cp_swap_program: String,
+    amm_config: String,
+    fifo_state: String,
+    payer: String,
+    relayer: String,
+    base_mint: String,
+    quote_mints: Vec<String>,
+    pools: Vec<PoolInfo>,
+}
+
+/// Reuse the keypair at `path` so addresses stay stable across runs
+fn load_or_create_keypair(path: &Path) -> Result<Keypair> {
+    if path.exists() {
+        return read_keypair_file(path).map_err(|err| anyhow!("failed to read keypair {}: {}", path.display(), err));
+    }
+    let keypair = Keypair::new();
+    write_keypair_file(&keypair, path).map_err(|err| anyhow!("failed to write keypair {}: {}", path.display(), err))?;
+    Ok(keypair)
+}
+
+fn relayer_config(addresses: &Addresses, relayer_keypair: &Path) -> String {
+    let pools = addresses
+        .pools
+        .iter()
+        .map(|pool| format!("\"{}\"", pool.pool_state))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "rpc_url = \"{}\"\nws_url = \"{}\"\nkeypair = \"{}\"\ncp_swap_program = \"{}\"\npools = [{}]\n",
+        addresses.rpc_url,
+        addresses.ws_url,
+        relayer_keypair.display(),
+        addresses.cp_swap_program,
+        pools,
+    )
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    std::fs::create_dir_all(&cli.out_dir).with_context(|| format!("failed to create {}", cli.out_dir.display()))?;
+    let out_dir = cli.out_dir.canonicalize()?;
+
+    let payer_path = out_dir.join("payer.json");
+    let relayer_path = out_dir.join("relayer.json");
+    let payer = load_or_create_keypair(&payer_path)?;
+    let relayer = load_or_create_keypair(&relayer_path)?;
+
+    let genesis = Genesis {
+        ledger: out_dir.join("ledger"),
+        rpc_port: cli.rpc_port,
+        continuum_so: cli.continuum_so,
+        cp_swap_so: cli.cp_swap_so,
+        cp_swap_program: cli.cp_swap_program,
+        trade_fee_rate: cli.trade_fee_rate,
+        mint_authority: payer.pubkey(),
+    };
+    println!("Starting validator...");
+    let (mut validator, amm_config) = validator::start(&genesis, &out_dir).await?;
+
+    let rpc_url = validator::url(cli.rpc_port);
+    let bootstrap = Bootstrap {
+        rpc: RpcClient::new_with_commitment(rpc_url.clone(), CommitmentConfig::confirmed()),
+        payer,
+        cp_swap_program: cli.cp_swap_program,
+        amm_config,
+        policy: RetryPolicy::default(),
+    };
+
+    println!("Initializing FIFO state...");
+    bootstrap.initialize().await?;
+
+    println!("Creating {} pools...", cli.pools);
+    let minted = cli.liquidity.saturating_mul(cli.pools as u64).saturating_add(cli.payer_tokens);
+    let base_mint = bootstrap.create_mint(9, minted).await?;
+    let mut quote_mints = Vec::with_capacity(cli.pools);
+    let mut pools = Vec::with_capacity(cli.pools);
+    for _ in 0..cli.pools {
+        let quote_mint = bootstrap.create_mint(6, cli.liquidity.saturating_add(cli.payer_tokens)).await?;
+        pools.push(bootstrap.create_pool(base_mint, quote_mint, cli.liquidity).await?);
+        quote_mints.push(quote_mint);
+    }
+
+    println!("Funding relayer...");
+    let pool_ids: Vec<Pubkey> = pools.iter().map(|pool| pool.pool_state).collect();
+    bootstrap.fund_relayer(&relayer, cli.relayer_lamports, &pool_ids).await?;
+
+    let addresses = Addresses {
+        rpc_url,
+        ws_url: validator::ws_url(cli.rpc_port),
+        program_id: PROGRAM_ID.to_string(),
+        cp_swap_program: cli.cp_swap_program.to_string(),
+        amm_config: amm_config.to_string(),
+        fifo_state: pda::find_fifo_state().0.to_string(),
+        payer: bootstrap.payer.pubkey().to_string(),
+        relayer: relayer.pubkey().to_string(),
+        base_mint: base_mint.to_string(),
+        quote_mints: quote_mints.iter().map(ToString::to_string).collect(),
+        pools: pools
+            .iter()
+            .map(|pool| PoolInfo {
+                pool_state: pool.pool_state.to_string(),
+                registry: pda::find_pool_registry(&pool.pool_state).0.to_string(),
+                token_0_mint: pool.token_0_mint.to_string(),
+                token_1_mint: pool.token_1_mint.to_string(),
+                lp_mint: pool.lp_mint.to_string(),
+            })
+            .collect(),
+    };
+    let addresses_path = out_dir.join("localnet.json");
+    std::fs::write(&addresses_path, serde_json::to_vec_pretty(&addresses)?)?;
+    let relayer_config_path = out_dir.join("relayer.toml");
+    std::fs::write(&relayer_config_path, relayer_config(&addresses, &relayer_path))?;
+
+    println!();
+    println!("RPC:              {}", addresses.rpc_url);
+    println!("Websocket:        {}", addresses.ws_url);
+    println!("Continuum:        {}", addresses.program_id);
+    println!("CP-Swap:          {}", addresses.cp_swap_program);
+    println!("AMM config:       {}", addresses.amm_config);
+    println!("FIFO state:       {}", addresses.fifo_state);
+    println!("Payer (admin):    {} ({})", addresses.payer, payer_path.display());
+    println!("Relayer:          {} ({})", addresses.relayer, relayer_path.display());
+    println!("Base mint:        {}", addresses.base_mint);
+    for (quote_mint, pool) in addresses.quote_mints.iter().zip(&addresses.pools) {
+        println!("Pool:             {} (quote mint {}, registry {})", pool.pool_state, quote_mint, pool.registry);
+    }
+    println!();
+    println!("Addresses:        {}", addresses_path.display());
+    println!("Relayer config:   {}", relayer_config_path.display());
+
+    if cli.exit {
+        validator.kill().await?;
+        return Ok(());
+    }
+    println!();
+    println!("Validator running; Ctrl-C to stop");
+    tokio::select! {
+        status = validator.wait() => {
+            return Err(anyhow!("validator exited with {}; see {}", status?, out_dir.join("validator.log").display()));
+        }
+        _ = tokio::signal::ctrl_c() => {}
+    }
+    validator.kill().await?;
+    Ok(())
+}
@@ -0,0 +1,106 @@
+//! Running `solana-test-validator` with both programs and the CP-Swap AMM
+//! config loaded at genesis.
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use base64::Engine;
+use serde_json::json;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use tokio::process::{Child, Command};
+
+use continuum_cp_swap_client::cp_swap::{amm_config_data, find_amm_config, AMM_CONFIG_LEN};
+use continuum_cp_swap_client::PROGRAM_ID;
+
+/// How long the validator gets to start answering RPC requests
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub struct Genesis {
+    pub ledger: PathBuf,
+    pub rpc_port: u16,
+    pub continuum_so: PathBuf,
+    pub cp_swap_so: PathBuf,
+    pub cp_swap_program: Pubkey,
+    pub trade_fee_rate: u64,
+    /// Receives the faucet's lamports
+    pub mint_authority: Pubkey,
+}
+
+/// Write the AMM config account in the `solana account --output json`
+/// format `--account` expects
+fn write_amm_config(genesis: &Genesis, path: &Path) -> Result<Pubkey> {
+    let address = find_amm_config(&genesis.cp_swap_program, 0);
+    let data = amm_config_data(&genesis.cp_swap_program, 0, genesis.trade_fee_rate);
+    let account = json!({
+        "pubkey": address.to_string(),
+        "account": {
+            "lamports": Rent::default().minimum_balance(AMM_CONFIG_LEN),
+            "data": [base64::engine::general_purpose::STANDARD.encode(data), "base64"],
+            "owner": genesis.cp_swap_program.to_string(),
+            "executable": false,
+            "rentEpoch": 0,
+            "space": AMM_CONFIG_LEN,
+        },
+    });
+    std::fs::write(path, serde_json::to_vec_pretty(&account)?)
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(address)
+}
+
+/// Start a fresh validator, logging to `validator.log` next to the ledger,
+/// and wait until it serves requests
+pub async fn start(genesis: &Genesis, out_dir: &Path) -> Result<(Child, Pubkey)> {
+    for so in [&genesis.continuum_so, &genesis.cp_swap_so] {
+        if !so.exists() {
+            bail!("program binary {} not found", so.display());
+        }
+    }
+    let amm_config_path = out_dir.join("amm_config.json");
+    let amm_config = write_amm_config(genesis, &amm_config_path)?;
+
+    let log_path = out_dir.join("validator.log");
+    let log = File::create(&log_path).with_context(|| format!("failed to create {}", log_path.display()))?;
+    let mut child = Command::new("solana-test-validator")
+        .arg("--reset")
+        .arg("--quiet")
+        .arg("--ledger")
+        .arg(&genesis.ledger)
+        .args(["--rpc-port", &genesis.rpc_port.to_string()])
+        .args(["--mint", &genesis.mint_authority.to_string()])
+        .args(["--bpf-program", &PROGRAM_ID.to_string()])
+        .arg(&genesis.continuum_so)
+        .args(["--bpf-program", &genesis.cp_swap_program.to_string()])
+        .arg(&genesis.cp_swap_so)
+        .args(["--account", &amm_config.to_string()])
+        .arg(&amm_config_path)
+        .stdout(log.try_clone()?)
+        .stderr(log)
+        .kill_on_drop(true)
+        .spawn()
+        .context("failed to run solana-test-validator; is the Solana CLI installed?")?;
+
+    let rpc = RpcClient::new(url(genesis.rpc_port));
+    let deadline = tokio::time::Instant::now() + STARTUP_TIMEOUT;
+    while rpc.get_health().await.is_err() {
+        if let Some(status) = child.try_wait()? {
+            bail!("validator exited with {}; see {}", status, log_path.display());
+        }
+        if tokio::time::Instant::now() > deadline {
+            bail!("validator didn't start within {:?}; see {}", STARTUP_TIMEOUT, log_path.display());
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    Ok((child, amm_config))
+}
+
+pub fn url(rpc_port: u16) -> String {
+    format!("http://127.0.0.1:{}", rpc_port)
+}
+
+pub fn ws_url(rpc_port: u16) -> String {
+    format!("ws://127.0.0.1:{}", rpc_port + 1)
+}
@@ -0,0 +1,457 @@
+use anchor_lang::AccountDeserialize;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token::spl_token::solana_program::program_option::COption;
+use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+use anchor_spl::token::{self, spl_token};
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_system_interface::instruction as system_instruction;
+
+use continuum_cp_swap_client::accounts::{decode, PoolFifoState};
+use continuum_cp_swap_client::cp_swap::{amm_config_data, find_amm_config, PoolAddresses, AMM_CONFIG_LEN};
+use continuum_cp_swap_client::instructions::{self, ExecuteOrderParams, OrderOptions};
+use continuum_cp_swap_client::intent::OrderIntent;
+use continuum_cp_swap_client::transaction::TransactionBuilder;
+use continuum_cp_swap_client::{pda, CP_SWAP_PROGRAM_ID, PROGRAM_ID};
+
+pub type Result<T> = std::result::Result<T, BanksClientError>;
+
+/// Compute units requested for every fixture transaction
+const COMPUTE_UNITS: u32 = 400_000;
+
+fn missing(what: &'static str) -> BanksClientError {
+    BanksClientError::ClientError(what)
+}
+
+pub struct FixtureConfig {
+    pub cp_swap_program: Pubkey,
+    /// Trade fee of the AMM config, in millionths
+    pub trade_fee_rate: u64,
+    /// Pool creation fee receiver required by the CP-Swap build, created as
+    /// a wrapped SOL account. Without one, pools are created with the
+    /// admin's token 0 account as the receiver.
+    pub create_pool_fee: Option<Pubkey>,
+}
+
+impl Default for FixtureConfig {
+    fn default() -> Self {
+        Self {
+            cp_swap_program: CP_SWAP_PROGRAM_ID,
+            trade_fee_rate: 2_500,
+            create_pool_fee: None,
+        }
+    }
+}
+
+/// A registered pool
+#[derive(Debug, Clone, Copy)]
+pub struct Pool {
+    pub addresses: PoolAddresses,
+    pub amm_config: Pubkey,
+}
+
+impl Pool {
+    pub fn id(&self) -> Pubkey {
+        self.addresses.pool_state
+    }
+}
+
+/// A submitted order
+#[derive(Debug, Clone, Copy)]
+pub struct Order {
+    pub address: Pubkey,
+    pub sequence: u64,
+    pub pool_id: Pubkey,
+    pub user: Pubkey,
+    pub input_mint: Pubkey,
+}
+
+pub struct Fixture {
+    pub context: ProgramTestContext,
+    pub cp_swap_program: Pubkey,
+    pub amm_config: Pubkey,
+    create_pool_fee: Option<Pubkey>,
+    /// Whether the mock clock has been created
+    #[cfg(feature = "test-clock")]
+    mock_clock: bool,
+}
+
+impl Fixture {
+    /// Start the bank and initialize the FIFO state with the payer as admin
+    pub async fn start(config: FixtureConfig) -> Result<Self> {
+        let mut program_test = ProgramTest::default();
+        program_test.prefer_bpf(true);
+        program_test.add_program("continuum_cp_swap", PROGRAM_ID, None);
+        program_test.add_program("raydium_cp_swap", config.cp_swap_program, None);
+
+        let amm_config = find_amm_config(&config.cp_swap_program, 0);
+        program_test.add_account(amm_config, amm_config_account(&config));
+        if let Some(receiver) = config.create_pool_fee {
+            program_test.add_account(receiver, wrapped_sol_account());
+        }
+
+        let context = program_test.start_with_context().await;
+        let mut fixture = Self {
+            context,
+            cp_swap_program: config.cp_swap_program,
+            amm_config,
+            create_pool_fee: config.create_pool_fee,
+            #[cfg(feature = "test-clock")]
+            mock_clock: false,
+        };
+        let admin = fixture.payer().pubkey();
+        fixture.process(vec![instructions::initialize(admin)], &[]).await?;
+        Ok(fixture)
+    }
+
+    /// The fee payer, which is also the FIFO admin and mint authority
+    pub fn payer(&self) -> Keypair {
+        self.context.payer.insecure_clone()
+    }
+
+    /// Send `ixs` paid by the payer, signed by it and `signers`
+    pub async fn process(&mut self, ixs: Vec<Instruction>, signers: &[&Keypair]) -> Result<()> {
+        let payer = self.payer();
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+
+        let blockhash = self.context.banks_client.get_latest_blockhash().await?;
+        let tx = TransactionBuilder::new(payer.pubkey())
+            .compute_unit_limit(COMPUTE_UNITS)
+            .instructions(ixs)
+            .sign(blockhash, &all_signers)
+            .map_err(|_| missing("failed to build transaction"))?;
+        self.context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn account<T: AccountDeserialize>(&mut self, address: Pubkey) -> Result<T> {
+        let account = self
+            .context
+            .banks_client
+            .get_account(address)
+            .await?
+            .ok_or_else(|| missing("account not found"))?;
+        decode(&account.data).map_err(|_| missing("invalid account data"))
+    }
+
+    pub async fn token_balance(&mut self, address: Pubkey) -> Result<u64> {
+        let account = self
+            .context
+            .banks_client
+            .get_account(address)
+            .await?
+            .ok_or_else(|| missing("token account not found"))?;
+        spl_token::state::Account::unpack(&account.data)
+            .map(|token| token.amount)
+            .map_err(|_| missing("invalid token account"))
+    }
+
+    /// Create an SPL Token mint with the payer as authority
+    pub async fn create_mint(&mut self, decimals: u8) -> Result<Pubkey> {
+        let mint = Keypair::new();
+        let payer = self.payer().pubkey();
+        let len = spl_token::state::Mint::LEN;
+        self.process(
+            vec![
+                system_instruction::create_account(
+                    &payer,
+                    &mint.pubkey(),
+                    Rent::default().minimum_balance(len),
+                    len as u64,
+                    &token::ID,
+                ),
+                spl_token::instruction::initialize_mint2(&token::ID, &mint.pubkey(), &payer, None, decimals)
+                    .map_err(|_| missing("invalid mint instruction"))?,
+            ],
+            &[&mint],
+        )
+        .await?;
+        Ok(mint.pubkey())
+    }
+
+    /// Mint `amount` to the owner's associated token account, creating it
+    /// if needed
+    pub async fn mint_to(&mut self, mint: Pubkey, owner: Pubkey, amount: u64) -> Result<Pubkey> {
+        let payer = self.payer().pubkey();
+        let account = get_associated_token_address(&owner, &mint);
+        self.process(
+            vec![
+                create_associated_token_account_idempotent(&payer, &owner, &mint, &token::ID),
+                spl_token::instruction::mint_to(&token::ID, &mint, &account, &payer, &[], amount)
+                    .map_err(|_| missing("invalid mint_to instruction"))?,
+            ],
+            &[],
+        )
+        .await?;
+        Ok(account)
+    }
+
+    /// A new keypair holding `lamports` and the given token balances
+    pub async fn create_user(&mut self, lamports: u64, balances: &[(Pubkey, u64)]) -> Result<Keypair> {
+        let user = Keypair::new();
+        let payer = self.payer().pubkey();
+        self.process(vec![system_instruction::transfer(&payer, &user.pubkey(), lamports)], &[])
+            .await?;
+        for (mint, amount) in balances {
+            self.mint_to(*mint, user.pubkey(), *amount).await?;
+        }
+        Ok(user)
+    }
+
+    /// Create a CP-Swap pool under the Continuum pool authority, seeded from
+    /// newly minted liquidity, and register it with its fee ledger
+    pub async fn create_pool(&mut self, mint_a: Pubkey, mint_b: Pubkey, amount_a: u64, amount_b: u64) -> Result<Pool> {
+        let admin = self.payer().pubkey();
+        let addresses = PoolAddresses::derive(&self.cp_swap_program, &self.amm_config, mint_a, mint_b);
+        let (amount_0, amount_1) = if addresses.token_0_mint == mint_a {
+            (amount_a, amount_b)
+        } else {
+            (amount_b, amount_a)
+        };
+        let admin_0 = self.mint_to(addresses.token_0_mint, admin, amount_0).await?;
+        self.mint_to(addresses.token_1_mint, admin, amount_1).await?;
+
+        let create_pool_fee = self.create_pool_fee.unwrap_or(admin_0);
+        self.process(
+            vec![instructions::initialize_cp_swap_pool(
+                admin,
+                None,
+                addresses.pool_state,
+                self.cp_swap_program,
+                amount_0,
+                amount_1,
+                0,
+                addresses.initialize_accounts(admin, self.amm_config, create_pool_fee),
+            )],
+            &[],
+        )
+        .await?;
+        self.process(
+            vec![instructions::initialize_fee_ledger(
+                admin,
+                addresses.pool_state,
+                addresses.token_0_mint,
+                addresses.token_1_mint,
+            )],
+            &[],
+        )
+        .await?;
+
+        Ok(Pool {
+            addresses,
+            amm_config: self.amm_config,
+        })
+    }
+
+    /// Submit an order selling exactly `amount_in` of `input_mint` from the
+    /// user's associated token account, approving the pool authority for it
+    /// and creating the output account if needed
+    pub async fn submit_order(
+        &mut self,
+        user: &Keypair,
+        pool: &Pool,
+        input_mint: Pubkey,
+        amount_in: u64,
+        min_amount_out: u64,
+    ) -> Result<Order> {
+        let payer = self.payer().pubkey();
+        let owner = user.pubkey();
+        let output_mint = if input_mint == pool.addresses.token_0_mint {
+            pool.addresses.token_1_mint
+        } else {
+            pool.addresses.token_0_mint
+        };
+        let fifo: PoolFifoState = self.account(pda::find_pool_fifo(&pool.id()).0).await?;
+
+        self.process(
+            vec![
+                create_associated_token_account_idempotent(&payer, &owner, &output_mint, &token::ID),
+                spl_token::instruction::approve(
+                    &token::ID,
+                    &get_associated_token_address(&owner, &input_mint),
+                    &pda::find_pool_authority(&pool.id()).0,
+                    &owner,
+                    &[],
+                    amount_in,
+                )
+                .map_err(|_| missing("invalid approve instruction"))?,
+                self.order_instruction(instructions::submit_order(
+                    owner,
+                    pool.id(),
+                    input_mint,
+                    fifo.current_sequence,
+                    amount_in,
+                    min_amount_out,
+                    true,
+                    OrderOptions {
+                        from_wallet: true,
+                        ..OrderOptions::default()
+                    },
+                )),
+            ],
+            &[user],
+        )
+        .await?;
+
+        Ok(Order {
+            address: pda::find_order(&pool.id(), fifo.current_sequence + 1).0,
+            sequence: fifo.current_sequence + 1,
+            pool_id: pool.id(),
+            user: owner,
+            input_mint,
+        })
+    }
+
+    /// Submit an order intent signed by `user`, paid for by the payer,
+    /// approving the pool authority for `intent.amount_in` of its input mint
+    /// and creating the output account if needed
+    pub async fn submit_order_intent(&mut self, user: &Keypair, pool: &Pool, intent: OrderIntent) -> Result<Order> {
+        let payer = self.payer().pubkey();
+        let owner = user.pubkey();
+        let input_mint = intent.input_mint;
+        let output_mint = if input_mint == pool.addresses.token_0_mint {
+            pool.addresses.token_1_mint
+        } else {
+            pool.addresses.token_0_mint
+        };
+        let fifo: PoolFifoState = self.account(pda::find_pool_fifo(&intent.pool_id).0).await?;
+        let signature: [u8; 64] = user.sign_message(&intent.message()).into();
+
+        let mut ixs = vec![
+            create_associated_token_account_idempotent(&payer, &owner, &output_mint, &token::ID),
+            spl_token::instruction::approve(
+                &token::ID,
+                &get_associated_token_address(&owner, &input_mint),
+                &pda::find_pool_authority(&pool.id()).0,
+                &owner,
+                &[],
+                intent.amount_in,
+            )
+            .map_err(|_| missing("invalid approve instruction"))?,
+        ];
+        let [verify, submit]: [Instruction; 2] = instructions::submit_order_intent(payer, intent, fifo.current_sequence, &signature, None)
+            .try_into()
+            .map_err(|_| missing("unexpected intent instructions"))?;
+        ixs.push(verify);
+        ixs.push(self.order_instruction(submit));
+        self.process(ixs, &[user]).await?;
+
+        Ok(Order {
+            address: pda::find_order(&pool.id(), fifo.current_sequence + 1).0,
+            sequence: fifo.current_sequence + 1,
+            pool_id: pool.id(),
+            user: owner,
+            input_mint,
+        })
+    }
+
+    pub async fn cancel_order(&mut self, user: &Keypair, order: &Order) -> Result<()> {
+        let ix = self.order_instruction(instructions::cancel_order(user.pubkey(), order.pool_id, order.address, None, None, None));
+        self.process(vec![ix], &[user]).await
+    }
+
+    /// Execute an order as `executor`, opening its relayer fee balance on
+    /// the pool first if it has none
+    pub async fn execute_order(&mut self, executor: &Keypair, pool: &Pool, order: &Order) -> Result<()> {
+        let relayer = executor.pubkey();
+        let fee_balance = pda::find_relayer_fee_balance(&pool.id(), &relayer).0;
+        if self.context.banks_client.get_account(fee_balance).await?.is_none() {
+            self.process(vec![instructions::open_relayer_fee_balance(relayer, pool.id())], &[executor])
+                .await?;
+        }
+
+        let swap = pool
+            .addresses
+            .swap_accounts(order.user, pool.amm_config, order.input_mint);
+        let ix = instructions::execute_order(ExecuteOrderParams {
+            executor: relayer,
+            order: order.address,
+            user: order.user,
+            sequence: order.sequence,
+            pool_id: pool.id(),
+            user_source: swap.input_token_account,
+            user_destination: swap.output_token_account,
+            source_mint: order.input_mint,
+            cp_swap_program: self.cp_swap_program,
+            with_receipt: false,
+            with_relayer_info: false,
+            with_memo: false,
+            callback_program: None,
+            post_fill: None,
+            parent_order: None,
+            oracle: None,
+            shared_escrow: None,
+            delegated_input: false,
+            permit: None,
+            escrow_refund: None,
+            max_fill_amount: None,
+            remaining_accounts: swap.execute_remaining_accounts(),
+        });
+        self.process(vec![self.order_instruction(ix)], &[executor]).await
+    }
+
+    /// Pin the time seen by order submission and execution to
+    /// `unix_timestamp`, or go back to the bank clock with `None`. Needs
+    /// the Continuum binary built with `test-clock`.
+    #[cfg(feature = "test-clock")]
+    pub async fn set_time(&mut self, unix_timestamp: Option<i64>) -> Result<()> {
+        let admin = self.payer().pubkey();
+        let ix = if self.mock_clock {
+            instructions::set_mock_clock(admin, unix_timestamp)
+        } else {
+            instructions::initialize_mock_clock(admin, unix_timestamp)
+        };
+        self.process(vec![ix], &[]).await?;
+        self.mock_clock = true;
+        Ok(())
+    }
+
+    fn order_instruction(&self, ix: Instruction) -> Instruction {
+        #[cfg(feature = "test-clock")]
+        if self.mock_clock {
+            return instructions::with_mock_clock(ix);
+        }
+        ix
+    }
+}
+
+fn amm_config_account(config: &FixtureConfig) -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(AMM_CONFIG_LEN),
+        data: amm_config_data(&config.cp_swap_program, 0, config.trade_fee_rate),
+        owner: config.cp_swap_program,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn wrapped_sol_account() -> Account {
+    let len = spl_token::state::Account::LEN;
+    let reserve = Rent::default().minimum_balance(len);
+    let mut data = vec![0; len];
+    spl_token::state::Account {
+        mint: native_mint::ID,
+        owner: Pubkey::new_unique(),
+        amount: 0,
+        delegate: COption::None,
+        state: spl_token::state::AccountState::Initialized,
+        is_native: COption::Some(reserve),
+        delegated_amount: 0,
+        close_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+
+    Account {
+        lamports: reserve,
+        data,
+        owner: token::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
@@ -0,0 +1,31 @@
+//! Integration test fixture for programs and services built on Continuum.
+//!
+//! [`Fixture::start`] boots a `solana-program-test` bank with the Continuum
+//! and CP-Swap programs, an AMM config and an initialized FIFO state. Mints,
+//! funded users and registered pools are then one call each, as are order
+//! submission and execution:
+//!
+//! ```ignore
+//! let mut fixture = Fixture::start(FixtureConfig::default()).await?;
+//! let usdc = fixture.create_mint(6).await?;
+//! let wsol = fixture.create_mint(9).await?;
+//! let pool = fixture.create_pool(usdc, wsol, 1_000_000_000, 1_000_000_000).await?;
+//! let user = fixture.create_user(1_000_000_000, &[(usdc, 10_000_000)]).await?;
+//! let order = fixture.submit_order(&user, &pool, usdc, 1_000_000, 0).await?;
+//! let executor = fixture.payer();
+//! fixture.execute_order(&executor, &pool, &order).await?;
+//! ```
+//!
+//! Both programs are loaded as SBF binaries named `continuum_cp_swap.so`
+//! and `raydium_cp_swap.so`, looked up in `SBF_OUT_DIR` (or `BPF_OUT_DIR`)
+//! and `tests/fixtures`. Build the first with `anchor build` and dump the
+//! second from the cluster with `solana program dump`.
+//!
+//! With the `test-clock` feature, and Continuum built with it too
+//! (`anchor build -- --features test-clock`), [`Fixture::set_time`] pins
+//! the timestamp orders are submitted and executed at.
+
+mod fixture;
+
+pub use fixture::{Fixture, FixtureConfig, Order, Pool, Result};
+pub use solana_program_test;
@@ -0,0 +1,367 @@
+//! Relayer configuration, read from a TOML file.
+//!
+//! ```toml
+//! # Optional: default to the public endpoints of the build's cluster
+//! rpc_url = "https://api.devnet.solana.com"
+//! ws_url = "wss://api.devnet.solana.com"
+//! keypair = "relayer-keypair.json"
+//! # Optional: more executor keys to rotate through
+//! keypairs = ["relayer-keypair-2.json"]
+//! status_addr = "127.0.0.1:8080"
+//! # Executions in flight at once across all pools. Above 1, orders are
+//! # still sent in sequence but may land out of order.
+//! max_concurrent_executions = 1
+//! # Optional: persist orders and execution attempts
+//! database_url = "sqlite://relayer.db?mode=rwc"
+//!
+//! [balance]
+//! min_lamports = 50000000
+//! # Optional: refill low executor keys from this wallet
+//! funding_keypair = "funding-keypair.json"
+//! top_up_lamports = 500000000
+//!
+//! [priority_fee]
+//! percentile = 75
+//! max_micro_lamports = 1000000
+//!
+//! [health]
+//! max_slot_lag = 150
+//! # Optional: compare against another node instead of trusting getHealth
+//! reference_rpc_url = "https://api.mainnet-beta.solana.com"
+//!
+//! # Optional: submit executions as Jito bundles
+//! [jito]
+//! block_engine_url = "https://mainnet.block-engine.jito.wtf"
+//! tip_lamports = 10000
+//! max_tip_lamports = 100000
+//!
+//! # One table per pool; a bare address string uses the defaults
+//! [[pools]]
+//! id = "<pool state address>"
+//! lookup_tables = ["<address lookup table>"]
+//! max_in_flight = 1
+//! # Optional: fill exact input orders in parts of at most this share of
+//! # the pool's input reserve, in bps
+//! max_fill_bps = 500
+//! priority_fee = { percentile = 90, max_micro_lamports = 2000000 }
+//! ```
+
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair};
+
+use continuum_cp_swap_client::{cluster, CP_SWAP_PROGRAM_ID};
+
+fn default_rpc_url() -> String {
+    cluster::DEFAULT_RPC_URL.to_string()
+}
+
+fn default_ws_url() -> String {
+    cluster::DEFAULT_WS_URL.to_string()
+}
+
+fn default_status_addr() -> SocketAddr {
+    SocketAddr::from(([127, 0, 0, 1], 8080))
+}
+
+fn default_max_attempts() -> u32 {
+    5
+}
+
+fn default_gap_timeout_secs() -> u64 {
+    30
+}
+
+fn default_reconcile_interval_secs() -> u64 {
+    10
+}
+
+fn default_fee_percentile() -> u8 {
+    75
+}
+
+fn default_max_micro_lamports() -> u64 {
+    1_000_000
+}
+
+/// Compute-unit price of executions, sampled from recent prioritization
+/// fees on the accounts they write
+#[derive(Debug, Clone, Deserialize)]
+pub struct PriorityFeeConfig {
+    /// Percentile of recent fees to pay
+    #[serde(default = "default_fee_percentile")]
+    pub percentile: u8,
+    /// Upper bound, including the increases made on retries
+    #[serde(default = "default_max_micro_lamports")]
+    pub max_micro_lamports: u64,
+}
+
+impl Default for PriorityFeeConfig {
+    fn default() -> Self {
+        Self {
+            percentile: default_fee_percentile(),
+            max_micro_lamports: default_max_micro_lamports(),
+        }
+    }
+}
+
+fn default_min_lamports() -> u64 {
+    50_000_000
+}
+
+fn default_top_up_lamports() -> u64 {
+    500_000_000
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BalanceConfig {
+    /// Executor keys below this balance are reported and taken out of
+    /// rotation
+    #[serde(default = "default_min_lamports")]
+    pub min_lamports: u64,
+    /// Wallet that refills low executor keys
+    #[serde(default)]
+    pub funding_keypair: Option<PathBuf>,
+    #[serde(default = "default_top_up_lamports")]
+    pub top_up_lamports: u64,
+}
+
+impl Default for BalanceConfig {
+    fn default() -> Self {
+        Self {
+            min_lamports: default_min_lamports(),
+            funding_keypair: None,
+            top_up_lamports: default_top_up_lamports(),
+        }
+    }
+}
+
+fn default_tip_lamports() -> u64 {
+    10_000
+}
+
+fn default_max_tip_lamports() -> u64 {
+    100_000
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JitoConfig {
+    pub block_engine_url: String,
+    /// Tip paid with the first attempt, and added again on every retry
+    #[serde(default = "default_tip_lamports")]
+    pub tip_lamports: u64,
+    #[serde(default = "default_max_tip_lamports")]
+    pub max_tip_lamports: u64,
+    /// Tip accounts to rotate through; fetched from the block engine if empty
+    #[serde(default)]
+    pub tip_accounts: Vec<String>,
+}
+
+impl JitoConfig {
+    pub fn tip_accounts(&self) -> Result<Vec<Pubkey>> {
+        self.tip_accounts
+            .iter()
+            .map(|account| account.parse().map_err(|_| anyhow!("invalid tip account `{}`", account)))
+            .collect()
+    }
+}
+
+fn default_max_slot_lag() -> u64 {
+    150
+}
+
+fn default_health_check_interval_secs() -> u64 {
+    5
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HealthConfig {
+    /// Execution pauses while the RPC node is more than this many slots
+    /// behind the cluster
+    #[serde(default = "default_max_slot_lag")]
+    pub max_slot_lag: u64,
+    /// RPC endpoint whose slot is taken as the cluster's; without one the
+    /// node's own `getHealth` report is used
+    #[serde(default)]
+    pub reference_rpc_url: Option<String>,
+    #[serde(default = "default_health_check_interval_secs")]
+    pub check_interval_secs: u64,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            max_slot_lag: default_max_slot_lag(),
+            reference_rpc_url: None,
+            check_interval_secs: default_health_check_interval_secs(),
+        }
+    }
+}
+
+fn default_max_in_flight() -> usize {
+    1
+}
+
+fn default_max_concurrent_executions() -> usize {
+    1
+}
+
+fn parse_pubkey(field: &str, value: &str) -> Result<Pubkey> {
+    value.parse().map_err(|_| anyhow!("invalid {} `{}`", field, value))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PoolConfig {
+    pub id: String,
+    /// Address lookup tables added to this pool's execution transactions,
+    /// besides the one recorded in the pool's registry
+    #[serde(default)]
+    pub lookup_tables: Vec<String>,
+    /// Executions on this pool in flight at once
+    #[serde(default = "default_max_in_flight")]
+    pub max_in_flight: usize,
+    /// Largest share of the pool's input reserve, in bps, one fill of an
+    /// exact input order may sell; larger orders are partially filled
+    #[serde(default)]
+    pub max_fill_bps: Option<u16>,
+    /// Overrides the global priority fee policy
+    #[serde(default)]
+    pub priority_fee: Option<PriorityFeeConfig>,
+    /// Overrides the global CP-Swap program
+    #[serde(default)]
+    pub cp_swap_program: Option<String>,
+}
+
+impl PoolConfig {
+    pub fn pool_id(&self) -> Result<Pubkey> {
+        parse_pubkey("pool address", &self.id)
+    }
+
+    pub fn lookup_tables(&self) -> Result<Vec<Pubkey>> {
+        self.lookup_tables
+            .iter()
+            .map(|table| parse_pubkey("lookup table", table))
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum PoolEntry {
+    Id(String),
+    Config(PoolConfig),
+}
+
+impl From<PoolEntry> for PoolConfig {
+    fn from(entry: PoolEntry) -> Self {
+        match entry {
+            PoolEntry::Id(id) => PoolConfig {
+                id,
+                lookup_tables: Vec::new(),
+                max_in_flight: default_max_in_flight(),
+                max_fill_bps: None,
+                priority_fee: None,
+                cp_swap_program: None,
+            },
+            PoolEntry::Config(config) => config,
+        }
+    }
+}
+
+fn read_keypair(path: &Path) -> Result<Keypair> {
+    read_keypair_file(path).map_err(|err| anyhow!("failed to read keypair {}: {}", path.display(), err))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RelayerConfig {
+    /// Defaults to the public endpoints of the cluster the relayer was built for
+    #[serde(default = "default_rpc_url")]
+    pub rpc_url: String,
+    #[serde(default = "default_ws_url")]
+    pub ws_url: String,
+    /// Executor keypair file
+    pub keypair: PathBuf,
+    /// Further executor keypair files; executions rotate across all keys
+    #[serde(default)]
+    pub keypairs: Vec<PathBuf>,
+    /// Pools this relayer executes orders for
+    pub pools: Vec<PoolEntry>,
+    #[serde(default = "default_max_concurrent_executions")]
+    pub max_concurrent_executions: usize,
+    #[serde(default)]
+    pub cp_swap_program: Option<String>,
+    /// Address of the HTTP API
+    #[serde(default = "default_status_addr")]
+    pub status_addr: SocketAddr,
+    /// Send attempts per execution before it is recorded as failed
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    /// How long to wait for a missing sequence number before moving past it
+    #[serde(default = "default_gap_timeout_secs")]
+    pub gap_timeout_secs: u64,
+    /// How often pending orders are re-read with `getProgramAccounts` to
+    /// pick up anything the log subscription missed
+    #[serde(default = "default_reconcile_interval_secs")]
+    pub reconcile_interval_secs: u64,
+    /// Create an execution receipt for every fill
+    #[serde(default)]
+    pub with_receipts: bool,
+    /// Every executor key is a registered relayer, so the fee it posted is
+    /// charged on pools with a relayer fee cap. Required when the program
+    /// only lets registered relayers execute
+    #[serde(default)]
+    pub use_posted_fee: bool,
+    /// Accept signed order intents at `POST /orders` and pay for their
+    /// submission
+    #[serde(default)]
+    pub intake: bool,
+    /// SQLite or Postgres URL of the order store
+    #[serde(default)]
+    pub database_url: Option<String>,
+    #[serde(default)]
+    pub balance: BalanceConfig,
+    #[serde(default)]
+    pub priority_fee: PriorityFeeConfig,
+    #[serde(default)]
+    pub health: HealthConfig,
+    /// Submit executions as Jito bundles instead of through the RPC node
+    #[serde(default)]
+    pub jito: Option<JitoConfig>,
+}
+
+impl RelayerConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        toml::from_str(&contents).with_context(|| format!("invalid config {}", path.display()))
+    }
+
+    /// Every executor keypair, `keypair` first
+    pub fn keypairs(&self) -> Result<Vec<Keypair>> {
+        std::iter::once(&self.keypair)
+            .chain(&self.keypairs)
+            .map(|path| read_keypair(path))
+            .collect()
+    }
+
+    pub fn funding_keypair(&self) -> Result<Option<Keypair>> {
+        self.balance.funding_keypair.as_deref().map(read_keypair).transpose()
+    }
+
+    /// Take the pool entries, with bare addresses expanded to defaults
+    pub fn take_pools(&mut self) -> Vec<PoolConfig> {
+        std::mem::take(&mut self.pools).into_iter().map(PoolConfig::from).collect()
+    }
+
+    /// The CP-Swap program of a pool, falling back to the global setting
+    pub fn cp_swap_program(&self, pool: &PoolConfig) -> Result<Pubkey> {
+        match pool.cp_swap_program.as_ref().or(self.cp_swap_program.as_ref()) {
+            Some(program) => parse_pubkey("cp_swap_program", program),
+            None => Ok(CP_SWAP_PROGRAM_ID),
+        }
+    }
+}
@@ -0,0 +1,447 @@
+//! Building and sending `execute_order` transactions.
+
+use anchor_lang::AccountDeserialize;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token::TokenAccount;
+use std::sync::Arc;
+
+use anchor_lang::solana_program::instruction::Instruction;
+use anyhow::{anyhow, bail, Context, Result};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcTransactionConfig};
+use solana_client::rpc_filter::RpcFilterType;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_transaction_status_client_types::UiTransactionEncoding;
+
+use continuum_cp_swap_client::accounts::{decode, ExecutionPolicy, OrderState, OrderStatus, PoolFifoState};
+use continuum_cp_swap_client::events::{find_rejection, RejectionReason};
+use continuum_cp_swap_client::fees::{estimate_compute_unit_price, writable_accounts};
+use continuum_cp_swap_client::filters::OrderQuery;
+use continuum_cp_swap_client::instructions::{self, ExecuteOrderParams, OrderVault, PostFillParams};
+use continuum_cp_swap_client::pda;
+use continuum_cp_swap_client::retry::{send_with_retry, RetryPolicy, SendError};
+use continuum_cp_swap_client::transaction::{TransactionBuilder, DEFAULT_EXECUTION_COMPUTE_UNITS};
+use continuum_cp_swap_client::PROGRAM_ID;
+
+use crate::jito::JitoClient;
+use crate::metrics::Metrics;
+use crate::wallets::Wallets;
+use crate::pool::PoolRoute;
+
+/// Most `skip_order` instructions sent ahead of an execution in its own
+/// transaction; longer runs are skipped in transactions of their own first
+const MAX_SKIPS_PER_EXECUTION: usize = 8;
+
+pub struct Executor {
+    pub wallets: Arc<Wallets>,
+    pub policy: RetryPolicy,
+    pub with_receipts: bool,
+    pub use_posted_fee: bool,
+    pub jito: Option<JitoClient>,
+    pub metrics: Arc<Metrics>,
+}
+
+/// The token accounts an order is settled between
+struct Route {
+    input_mint: Pubkey,
+    source: Pubkey,
+    output_mint: Pubkey,
+    destination: Pubkey,
+}
+
+/// Orders don't record which mint they sell. An order with a source account
+/// (a template occurrence or a bridged order) sells that account's mint.
+/// Otherwise the user approves the pool authority on the account being sold
+/// from, so the source is whichever of the user's two associated token
+/// accounts carries that approval.
+async fn resolve_route(rpc: &RpcClient, pool: &PoolRoute, order: &OrderState) -> Result<Route> {
+    let s = &pool.state;
+    if let Some(source) = order.source_account {
+        let data = rpc
+            .get_account_data(&source)
+            .await
+            .with_context(|| format!("failed to fetch source account {}", source))?;
+        let token = TokenAccount::try_deserialize(&mut &data[..])
+            .with_context(|| format!("invalid source account {}", source))?;
+        let (input_mint, output_mint, output_program) = if token.mint == s.token_0_mint {
+            (s.token_0_mint, s.token_1_mint, s.token_1_program)
+        } else {
+            (s.token_1_mint, s.token_0_mint, s.token_0_program)
+        };
+        return Ok(Route {
+            input_mint,
+            source,
+            output_mint,
+            destination: route_destination(order, output_mint, output_program),
+        });
+    }
+    let pool_authority = pda::find_pool_authority(&pool.pool_id).0;
+    let required = if order.is_base_input {
+        order.amount_in
+    } else {
+        order.min_amount_out
+    };
+
+    let user_0 = get_associated_token_address_with_program_id(&order.user, &s.token_0_mint, &s.token_0_program);
+    let user_1 = get_associated_token_address_with_program_id(&order.user, &s.token_1_mint, &s.token_1_program);
+    let accounts = rpc
+        .get_multiple_accounts(&[user_0, user_1])
+        .await
+        .context("failed to fetch user token accounts")?;
+
+    let approved = |account: &Option<solana_sdk::account::Account>| {
+        account
+            .as_ref()
+            .and_then(|account| TokenAccount::try_deserialize(&mut &account.data[..]).ok())
+            .is_some_and(|token| {
+                token.delegate == Some(pool_authority).into() && token.delegated_amount >= required
+            })
+    };
+
+    let (input_mint, source, output_mint, output_program) = match (approved(&accounts[0]), approved(&accounts[1])) {
+        (true, false) => (s.token_0_mint, user_0, s.token_1_mint, s.token_1_program),
+        (false, true) => (s.token_1_mint, user_1, s.token_0_mint, s.token_0_program),
+        (true, true) => bail!("both token accounts of {} approve the pool authority", order.user),
+        (false, false) => bail!("no token account of {} approves the pool authority", order.user),
+    };
+
+    Ok(Route {
+        input_mint,
+        source,
+        output_mint,
+        destination: route_destination(order, output_mint, output_program),
+    })
+}
+
+fn route_destination(order: &OrderState, output_mint: Pubkey, output_program: Pubkey) -> Pubkey {
+    match (order.post_fill, order.destination_account) {
+        (Some(_), _) => pda::find_post_fill_vault(&output_mint).0,
+        (None, Some(destination)) => destination,
+        (None, None) => get_associated_token_address_with_program_id(&order.output_owner(), &output_mint, &output_program),
+    }
+}
+
+/// The `skip_order` instructions that bring the pool's queue up to `order`,
+/// with `expire_order` for pending orders ahead past their time to live.
+/// Conditional orders wait their turn too, unless they were already passed
+/// over. Fails when an unconditional pending order is still ahead in the
+/// queue, unless it is next in line and has been for the grace period.
+async fn queue_skips(rpc: &RpcClient, pool_id: Pubkey, order: &OrderState) -> Result<Vec<Instruction>> {
+    let data = rpc
+        .get_account_data(&pda::find_pool_fifo(&pool_id).0)
+        .await
+        .with_context(|| format!("failed to fetch FIFO queue of {}", pool_id))?;
+    let fifo: PoolFifoState = decode(&data).map_err(|err| anyhow!("invalid FIFO queue of {}: {}", pool_id, err))?;
+    if order.sequence <= fifo.next_execution_sequence() {
+        return Ok(Vec::new());
+    }
+
+    let query = OrderQuery::default().pool(pool_id);
+    let accounts = rpc
+        .get_program_accounts_with_config(
+            &PROGRAM_ID,
+            RpcProgramAccountsConfig {
+                filters: Some(query.filters().into_iter().map(RpcFilterType::from).collect()),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    ..RpcAccountInfoConfig::default()
+                },
+                ..RpcProgramAccountsConfig::default()
+            },
+        )
+        .await
+        .with_context(|| format!("failed to fetch orders of {}", pool_id))?;
+    let mut ahead: Vec<_> = query
+        .decode_orders(accounts.into_iter().map(|(address, account)| (address, account.data)))
+        .into_iter()
+        .filter(|(_, ahead)| ahead.sequence > fifo.head && ahead.sequence < order.sequence)
+        .collect();
+    ahead.sort_by_key(|(_, ahead)| ahead.sequence);
+
+    let now = crate::relayer::unix_now();
+    let mut skips = Vec::with_capacity(ahead.len());
+    for (expected, (address, ahead)) in (fifo.next_execution_sequence()..).zip(&ahead) {
+        if ahead.sequence != expected {
+            bail!("order at sequence {} on {} not found", expected, pool_id);
+        }
+        if ahead.status == OrderStatus::Pending && ahead.is_expired(now) {
+            skips.push(instructions::expire_order(
+                ahead.user,
+                pool_id,
+                *address,
+                ahead.shared_escrow,
+                OrderVault::of(ahead),
+            ));
+            continue;
+        }
+        let stalled = expected == fifo.next_execution_sequence() && fifo.head_stalled(ahead.submitted_at, now);
+        if ahead.status == OrderStatus::Pending && !ahead.is_conditional() && !stalled {
+            bail!("order {} is still pending ahead in the queue of {}", ahead.sequence, pool_id);
+        }
+        skips.push(instructions::skip_order(pool_id, *address));
+    }
+    if skips.len() as u64 != order.sequence - fifo.next_execution_sequence() {
+        bail!("orders ahead in the queue of {} are missing", pool_id);
+    }
+    Ok(skips)
+}
+
+/// Whether the pool's spot price meets `order`'s limit price, read from the
+/// vaults as execution reads it
+pub async fn limit_reached(rpc: &RpcClient, pool: &PoolRoute, order: &OrderState) -> Result<bool> {
+    let route = resolve_route(rpc, pool, order).await?;
+    Ok(order.limit_reached(reserves(rpc, pool, &route.input_mint).await?))
+}
+
+/// Input to cap the order's next fill at under the pool's `max_fill_bps`,
+/// or `None` to fill what remains of it
+async fn max_fill_amount(rpc: &RpcClient, pool: &PoolRoute, order: &OrderState, input_mint: &Pubkey) -> Result<Option<u64>> {
+    let Some(max_fill_bps) = pool.max_fill_bps.filter(|_| order.is_base_input && order.twap.is_none()) else {
+        return Ok(None);
+    };
+    let Some((input_reserve, _)) = reserves(rpc, pool, input_mint).await? else {
+        return Ok(None);
+    };
+    let cap = (input_reserve as u128 * max_fill_bps as u128 / 10_000) as u64;
+    Ok((cap < order.amount_in_remaining).then_some(cap.max(1)))
+}
+
+/// The pool's reserves of `input_mint` and of the other mint, if both vaults
+/// could be read
+async fn reserves(rpc: &RpcClient, pool: &PoolRoute, input_mint: &Pubkey) -> Result<Option<(u64, u64)>> {
+    let s = &pool.state;
+    let (input_vault, output_vault) = if *input_mint == s.token_0_mint {
+        (s.token_0_vault, s.token_1_vault)
+    } else {
+        (s.token_1_vault, s.token_0_vault)
+    };
+    let accounts = rpc
+        .get_multiple_accounts(&[input_vault, output_vault])
+        .await
+        .with_context(|| format!("failed to fetch vaults of {}", pool.pool_id))?;
+    // Amount sits at the same offset in SPL Token and Token-2022 accounts
+    let balance = |account: &Option<solana_sdk::account::Account>| {
+        let data = &account.as_ref()?.data;
+        Some(u64::from_le_bytes(data.get(64..72)?.try_into().ok()?))
+    };
+    Ok(balance(&accounts[0]).zip(balance(&accounts[1])))
+}
+
+/// Fee charged for a landed transaction, priority fee included
+async fn transaction_fee(rpc: &RpcClient, signature: &Signature) -> Result<u64> {
+    let tx = rpc
+        .get_transaction_with_config(
+            signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        )
+        .await?;
+    tx.transaction
+        .meta
+        .map(|meta| meta.fee)
+        .ok_or_else(|| anyhow!("transaction {} has no status meta", signature))
+}
+
+/// Why the program refused a failed execution, read from its logs
+async fn rejection_reason(rpc: &RpcClient, signature: &Signature) -> Option<RejectionReason> {
+    let tx = rpc
+        .get_transaction_with_config(
+            signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        )
+        .await
+        .ok()?;
+    let logs: Option<Vec<String>> = tx.transaction.meta?.log_messages.into();
+    find_rejection(&logs?).map(|rejection| rejection.reason)
+}
+
+impl Executor {
+    pub async fn execute(
+        &self,
+        rpc: &RpcClient,
+        pool: &PoolRoute,
+        address: Pubkey,
+        order: &OrderState,
+    ) -> Result<Signature> {
+        let signer = self
+            .wallets
+            .next()
+            .ok_or_else(|| anyhow!("every executor key is below the minimum balance"))?;
+        let executor = signer.pubkey();
+        let route = resolve_route(rpc, pool, order).await?;
+        let mut skips = queue_skips(rpc, pool.pool_id, order).await?;
+        while skips.len() > MAX_SKIPS_PER_EXECUTION {
+            let rest = skips.split_off(MAX_SKIPS_PER_EXECUTION);
+            tracing::info!(pool = %pool.pool_id, count = skips.len(), "skipping settled orders in the pool queue");
+            let builder = TransactionBuilder::new(executor).instructions(skips);
+            send_with_retry(rpc, builder, &[signer], &self.policy)
+                .await
+                .map_err(|err| anyhow!(err))
+                .with_context(|| format!("failed to skip orders in the queue of {}", pool.pool_id))?;
+            skips = rest;
+        }
+        let swap = pool.swap_accounts(order.user, route.input_mint, route.source, route.destination);
+        let max_fill_amount = max_fill_amount(rpc, pool, order, &route.input_mint).await?;
+        if let Some(amount) = max_fill_amount {
+            tracing::info!(sequence = order.sequence, amount, remaining = order.amount_in_remaining, "partially filling order");
+        }
+
+        let ix = instructions::execute_order(ExecuteOrderParams {
+            executor,
+            order: address,
+            user: order.user,
+            sequence: order.sequence,
+            pool_id: pool.pool_id,
+            user_source: route.source,
+            user_destination: route.destination,
+            source_mint: route.input_mint,
+            cp_swap_program: pool.cp_swap_program,
+            // A receipt is only taken once per order, so an order filled in
+            // parts takes it on its last fill
+            with_receipt: self.with_receipts
+                && max_fill_amount.is_none()
+                && order.twap.is_none_or(|twap| twap.is_last_slice()),
+            with_relayer_info: self.use_posted_fee,
+            with_memo: false,
+            callback_program: order.callback_program,
+            post_fill: order.post_fill.map(|action| PostFillParams {
+                action,
+                output_mint: route.output_mint,
+            }),
+            parent_order: order.parent_order,
+            oracle: order.oracle(),
+            shared_escrow: order.shared_escrow,
+            delegated_input: order.delegated_input,
+            permit: order.permit,
+            escrow_refund: order.escrow_refund,
+            max_fill_amount,
+            remaining_accounts: swap.execute_remaining_accounts(),
+        });
+
+        let policy = RetryPolicy {
+            max_priority_fee: pool.priority_fee.max_micro_lamports,
+            ..self.policy.clone()
+        };
+        let price = match estimate_compute_unit_price(
+            rpc,
+            &writable_accounts([&ix]),
+            pool.priority_fee.percentile,
+            policy.max_priority_fee,
+        )
+        .await
+        {
+            Ok(price) => price,
+            Err(err) => {
+                tracing::warn!(error = %err, "failed to sample prioritization fees");
+                0
+            }
+        };
+        self.metrics.priority_fee_micro_lamports.set(price as i64);
+        let mut builder = TransactionBuilder::new(executor)
+            .compute_unit_limit(DEFAULT_EXECUTION_COMPUTE_UNITS)
+            .instructions(skips)
+            .instruction(ix)
+            .compute_unit_price(price);
+        for table in &pool.lookup_tables {
+            builder = builder.lookup_table(table.clone());
+        }
+        let sent = match &self.jito {
+            Some(jito) => {
+                jito.send_with_retry(rpc, executor, builder, &[signer], &policy)
+                    .await
+            }
+            None => send_with_retry(rpc, builder, &[signer], &policy)
+                .await
+                .map_err(|err| anyhow!(err)),
+        };
+        let signature = match sent {
+            Ok(signature) => signature,
+            Err(err) => {
+                let failed = match err.downcast_ref() {
+                    Some(SendError::Failed { signature, .. }) => Some(*signature),
+                    _ => None,
+                };
+                let reason = match &failed {
+                    Some(signature) => rejection_reason(rpc, signature).await,
+                    None => None,
+                };
+                // The execution landed and failed, so the order couldn't be
+                // filled; one immediate-or-cancel is cancelled instead
+                if failed.is_some() && order.execution_policy == ExecutionPolicy::ImmediateOrCancel {
+                    match self.cancel_unfilled(rpc, pool, address, order, signer).await {
+                        Ok(signature) => tracing::info!(sequence = order.sequence, %signature, "cancelled unfilled immediate-or-cancel order"),
+                        Err(err) => tracing::warn!(sequence = order.sequence, error = format!("{:#}", err), "failed to cancel immediate-or-cancel order"),
+                    }
+                }
+                return Err(match reason {
+                    Some(reason) => err.context(format!("execution rejected: {:?}", reason)),
+                    None => err,
+                });
+            }
+        };
+
+        match transaction_fee(rpc, &signature).await {
+            Ok(fee) => self.metrics.fees_paid_lamports.inc_by(fee),
+            Err(err) => tracing::debug!(error = %err, "failed to fetch execution fee"),
+        }
+        Ok(signature)
+    }
+
+    /// Cancel an immediate-or-cancel order whose execution failed, moving the
+    /// pool's queue up to it first as the execution would have
+    async fn cancel_unfilled(
+        &self,
+        rpc: &RpcClient,
+        pool: &PoolRoute,
+        address: Pubkey,
+        order: &OrderState,
+        signer: &Keypair,
+    ) -> Result<Signature> {
+        let skips = queue_skips(rpc, pool.pool_id, order).await?;
+        let cancel = instructions::cancel_ioc_order(
+            signer.pubkey(),
+            order.user,
+            pool.pool_id,
+            address,
+            order.shared_escrow,
+            OrderVault::of(order),
+            self.use_posted_fee,
+        );
+        let builder = TransactionBuilder::new(signer.pubkey()).instructions(skips).instruction(cancel);
+        send_with_retry(rpc, builder, &[signer], &self.policy)
+            .await
+            .map_err(|err| anyhow!(err))
+    }
+
+    /// Open a fee balance for every executor key on each pool that doesn't
+    /// have one yet
+    pub async fn ensure_fee_balances(&self, rpc: &RpcClient, pools: &[Pubkey]) -> Result<()> {
+        for signer in self.wallets.keys() {
+            let relayer = signer.pubkey();
+            for pool_id in pools {
+                let address = pda::find_relayer_fee_balance(pool_id, &relayer).0;
+                if rpc.get_account(&address).await.is_ok() {
+                    continue;
+                }
+                tracing::info!(pool = %pool_id, wallet = %relayer, "opening relayer fee balance");
+                let builder = TransactionBuilder::new(relayer)
+                    .instruction(instructions::open_relayer_fee_balance(relayer, *pool_id));
+                send_with_retry(rpc, builder, &[signer], &self.policy)
+                    .await
+                    .map_err(|err| anyhow!(err))
+                    .with_context(|| format!("failed to open fee balance on {} for {}", pool_id, relayer))?;
+            }
+        }
+        Ok(())
+    }
+}
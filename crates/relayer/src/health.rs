@@ -0,0 +1,100 @@
+//! Liveness and readiness at `GET /healthz` and `GET /readyz`, and the slot
+//! lag check that pauses execution while the RPC node is behind the cluster.
+//! Executions built on a lagging node would quote stale pool state.
+
+use anyhow::{anyhow, Result};
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Serialize;
+use solana_client::client_error::ClientErrorKind;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_request::{RpcError, RpcResponseErrorData};
+use solana_sdk::commitment_config::CommitmentConfig;
+
+use crate::status::SharedStatus;
+
+/// Measures how far the relayer's RPC node is behind
+pub struct SlotLag {
+    /// Node whose slot stands in for the cluster's. Without one, the lag the
+    /// node reports about itself through `getHealth` is used.
+    pub reference: Option<RpcClient>,
+    /// Execution pauses while the node is more than this many slots behind
+    pub max_slots: u64,
+}
+
+impl SlotLag {
+    pub fn new(reference_rpc_url: Option<String>, max_slots: u64) -> Self {
+        Self {
+            reference: reference_rpc_url.map(|url| {
+                RpcClient::new_with_commitment(url, CommitmentConfig::processed())
+            }),
+            max_slots,
+        }
+    }
+
+    pub async fn measure(&self, rpc: &RpcClient) -> Result<u64> {
+        match &self.reference {
+            Some(reference) => {
+                let slot = rpc.get_slot_with_commitment(CommitmentConfig::processed()).await?;
+                let cluster = reference.get_slot_with_commitment(CommitmentConfig::processed()).await?;
+                Ok(cluster.saturating_sub(slot))
+            }
+            None => match rpc.get_health().await {
+                Ok(()) => Ok(0),
+                Err(err) => match err.kind() {
+                    ClientErrorKind::RpcError(RpcError::RpcResponseError {
+                        data: RpcResponseErrorData::NodeUnhealthy { num_slots_behind },
+                        ..
+                    }) => Ok(num_slots_behind.unwrap_or(u64::MAX)),
+                    _ => Err(anyhow!(err)),
+                },
+            },
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Readiness {
+    ready: bool,
+    reasons: Vec<String>,
+}
+
+async fn healthz() -> &'static str {
+    "ok"
+}
+
+/// Ready once the first snapshot is loaded, while the log subscription is up
+/// and execution isn't paused
+async fn readyz(State(status): State<SharedStatus>) -> (StatusCode, Json<Readiness>) {
+    let status = status.read().unwrap();
+    let mut reasons = Vec::new();
+    if status.last_reconciled.is_none() {
+        reasons.push("order snapshot not loaded".to_string());
+    }
+    if !status.subscribed {
+        reasons.push("log subscription down".to_string());
+    }
+    if status.paused {
+        reasons.push(match status.slot_lag {
+            Some(lag) => format!("execution paused, rpc node {} slots behind", lag),
+            None => "execution paused".to_string(),
+        });
+    }
+
+    let ready = reasons.is_empty();
+    let code = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (code, Json(Readiness { ready, reasons }))
+}
+
+pub fn router(status: SharedStatus) -> Router {
+    Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .with_state(status)
+}
@@ -0,0 +1,187 @@
+//! Gasless order intake at `POST /orders`. A user signs an [`OrderIntent`]
+//! off-chain; the relayer checks it, pays for its submission and answers with
+//! the transaction signature, and the sequence number and trace ID the order
+//! was given.
+
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use tokio::sync::Mutex;
+
+use continuum_cp_swap_client::accounts::{decode, CpSwapPoolRegistry, PoolFifoState};
+use continuum_cp_swap_client::intent::OrderIntent;
+use continuum_cp_swap_client::retry::{send_with_retry, RetryPolicy, SendError};
+use continuum_cp_swap_client::transaction::TransactionBuilder;
+use continuum_cp_swap_client::{instructions, pda, trace};
+
+/// Submissions tried per intent. The order account is derived from the FIFO
+/// sequence, so a submission fails if another order lands first and has to
+/// be rebuilt.
+const SUBMIT_ATTEMPTS: usize = 3;
+
+#[derive(Deserialize)]
+pub struct IntentRequest {
+    pub user: String,
+    pub pool_id: String,
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+    pub is_base_input: bool,
+    pub input_mint: String,
+    #[serde(default)]
+    pub recipient: Option<String>,
+    pub nonce: u64,
+    pub expires_at: i64,
+    /// Base58 ed25519 signature of the intent message by `user`
+    pub signature: String,
+}
+
+#[derive(Serialize)]
+pub struct IntentResponse {
+    pub signature: String,
+    pub sequence: u64,
+    pub order: String,
+    /// Trace ID, in hex, carried by the order's events and relayer updates
+    pub trace_id: String,
+}
+
+#[derive(Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+type Rejection = (StatusCode, Json<ErrorResponse>);
+
+fn reject(status: StatusCode, error: impl ToString) -> Rejection {
+    (
+        status,
+        Json(ErrorResponse {
+            error: error.to_string(),
+        }),
+    )
+}
+
+fn parse_pubkey(field: &str, value: &str) -> Result<Pubkey, Rejection> {
+    Pubkey::from_str(value)
+        .map_err(|_| reject(StatusCode::BAD_REQUEST, format!("invalid {} `{}`", field, value)))
+}
+
+pub struct Intake {
+    pub rpc: Arc<RpcClient>,
+    pub signer: Keypair,
+    pub pools: HashSet<Pubkey>,
+    pub policy: RetryPolicy,
+    /// Submissions are serialized so this relayer doesn't race itself for
+    /// the next sequence number
+    pub submitting: Mutex<()>,
+}
+
+impl Intake {
+    fn validate(&self, request: &IntentRequest) -> Result<(OrderIntent, [u8; 64]), Rejection> {
+        let intent = OrderIntent {
+            user: parse_pubkey("user", &request.user)?,
+            pool_id: parse_pubkey("pool_id", &request.pool_id)?,
+            amount_in: request.amount_in,
+            min_amount_out: request.min_amount_out,
+            is_base_input: request.is_base_input,
+            input_mint: parse_pubkey("input_mint", &request.input_mint)?,
+            recipient: request
+                .recipient
+                .as_deref()
+                .map(|recipient| parse_pubkey("recipient", recipient))
+                .transpose()?,
+            nonce: request.nonce,
+            expires_at: request.expires_at,
+        };
+
+        if !self.pools.contains(&intent.pool_id) {
+            return Err(reject(StatusCode::UNPROCESSABLE_ENTITY, "pool is not served by this relayer"));
+        }
+        if intent.amount_in == 0 {
+            return Err(reject(StatusCode::UNPROCESSABLE_ENTITY, "amount_in must be positive"));
+        }
+        if intent.expires_at <= crate::relayer::unix_now() {
+            return Err(reject(StatusCode::UNPROCESSABLE_ENTITY, "intent has expired"));
+        }
+
+        let signature = Signature::from_str(&request.signature)
+            .map_err(|_| reject(StatusCode::BAD_REQUEST, "invalid signature encoding"))?;
+        if !signature.verify(intent.user.as_ref(), &intent.message()) {
+            return Err(reject(StatusCode::UNAUTHORIZED, "signature does not match the intent"));
+        }
+        Ok((intent, signature.into()))
+    }
+
+    async fn submit(&self, intent: OrderIntent, signature: [u8; 64]) -> Result<IntentResponse, Rejection> {
+        let _submitting = self.submitting.lock().await;
+        let used_intent = pda::find_used_intent(&intent.user, intent.nonce).0;
+        let unavailable = |err: &dyn std::fmt::Display| reject(StatusCode::BAD_GATEWAY, err);
+
+        let data = self
+            .rpc
+            .get_account_data(&pda::find_pool_registry(&intent.pool_id).0)
+            .await
+            .map_err(|err| unavailable(&err))?;
+        let registry: CpSwapPoolRegistry = decode(&data).map_err(|err| unavailable(&err))?;
+
+        let mut last_error = None;
+        for _ in 0..SUBMIT_ATTEMPTS {
+            if self.rpc.get_account(&used_intent).await.is_ok() {
+                return Err(reject(StatusCode::CONFLICT, "intent nonce has already been used"));
+            }
+            let data = self
+                .rpc
+                .get_account_data(&pda::find_pool_fifo(&intent.pool_id).0)
+                .await
+                .map_err(|err| unavailable(&err))?;
+            let fifo: PoolFifoState = decode(&data).map_err(|err| unavailable(&err))?;
+
+            let builder = TransactionBuilder::new(self.signer.pubkey()).instructions(
+                instructions::submit_order_intent(
+                    self.signer.pubkey(),
+                    intent.clone(),
+                    fifo.current_sequence,
+                    &signature,
+                    registry.compliance_program(),
+                ),
+            );
+            match send_with_retry(&self.rpc, builder, &[&self.signer], &self.policy).await {
+                Ok(tx_signature) => {
+                    let sequence = fifo.current_sequence + 1;
+                    let trace_id = trace::to_hex(&trace::default_trace_id(&intent.pool_id, sequence));
+                    tracing::info!(sequence, %trace_id, user = %intent.user, %tx_signature, "submitted order intent");
+                    return Ok(IntentResponse {
+                        signature: tx_signature.to_string(),
+                        sequence,
+                        order: pda::find_order(&intent.pool_id, sequence).0.to_string(),
+                        trace_id,
+                    });
+                }
+                // Most likely another order took the sequence; rebuild
+                Err(err @ SendError::Failed { .. }) => last_error = Some(err),
+                Err(err) => return Err(unavailable(&err)),
+            }
+        }
+        Err(unavailable(&last_error.expect("at least one attempt")))
+    }
+}
+
+async fn submit_intent(
+    State(intake): State<Arc<Intake>>,
+    Json(request): Json<IntentRequest>,
+) -> Result<Json<IntentResponse>, Rejection> {
+    let (intent, signature) = intake.validate(&request)?;
+    intake.submit(intent, signature).await.map(Json)
+}
+
+pub fn router(intake: Arc<Intake>) -> Router {
+    Router::new().route("/orders", post(submit_intent)).with_state(intake)
+}
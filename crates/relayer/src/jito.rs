@@ -0,0 +1,135 @@
+//! Submitting executions as Jito bundles. The block engine includes a bundle
+//! whole or not at all and never lets other transactions into it, so an
+//! execution can't be front-run or reordered at the block-builder level.
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::prelude::{Engine, BASE64_STANDARD};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::signer::signers::Signers;
+use solana_sdk::transaction::VersionedTransaction;
+use solana_system_interface::instruction as system_instruction;
+
+use continuum_cp_swap_client::retry::{confirm, Outcome, RetryPolicy};
+use continuum_cp_swap_client::transaction::TransactionBuilder;
+
+use crate::config::JitoConfig;
+
+#[derive(Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
+pub struct JitoClient {
+    http: reqwest::Client,
+    bundles_url: String,
+    tip_accounts: Vec<Pubkey>,
+    tip_lamports: u64,
+    max_tip_lamports: u64,
+}
+
+impl JitoClient {
+    /// Uses the configured tip accounts, or asks the block engine for them
+    pub async fn connect(config: &JitoConfig) -> Result<Self> {
+        let mut client = Self {
+            http: reqwest::Client::new(),
+            bundles_url: format!("{}/api/v1/bundles", config.block_engine_url.trim_end_matches('/')),
+            tip_accounts: config.tip_accounts()?,
+            tip_lamports: config.tip_lamports,
+            max_tip_lamports: config.max_tip_lamports.max(config.tip_lamports),
+        };
+        if client.tip_accounts.is_empty() {
+            let accounts: Vec<String> = client.call("getTipAccounts", json!([])).await?;
+            client.tip_accounts = accounts
+                .iter()
+                .map(|account| account.parse().map_err(|_| anyhow!("invalid tip account `{}`", account)))
+                .collect::<Result<_>>()?;
+        }
+        if client.tip_accounts.is_empty() {
+            bail!("block engine returned no tip accounts");
+        }
+        Ok(client)
+    }
+
+    async fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        let response: RpcResponse<T> = self
+            .http
+            .post(&self.bundles_url)
+            .json(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }))
+            .send()
+            .await
+            .with_context(|| format!("{} request failed", method))?
+            .json()
+            .await
+            .with_context(|| format!("invalid {} response", method))?;
+        match (response.result, response.error) {
+            (_, Some(err)) => bail!("{} failed ({}): {}", method, err.code, err.message),
+            (Some(result), None) => Ok(result),
+            (None, None) => bail!("{} returned no result", method),
+        }
+    }
+
+    /// Send transactions as one bundle, returning its id
+    pub async fn send_bundle(&self, transactions: &[VersionedTransaction]) -> Result<String> {
+        let encoded = transactions
+            .iter()
+            .map(|tx| bincode::serialize(tx).map(|bytes| BASE64_STANDARD.encode(bytes)))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.call("sendBundle", json!([encoded, { "encoding": "base64" }]))
+            .await
+    }
+
+    /// The tip paid on the given attempt, raised by `tip_lamports` each retry
+    fn tip(&self, attempt: u32) -> u64 {
+        self.tip_lamports
+            .saturating_mul(u64::from(attempt) + 1)
+            .min(self.max_tip_lamports)
+    }
+
+    /// Sign and send the transaction as a single-transaction bundle with a
+    /// tip from `payer` appended, retrying with a higher tip and a fresh
+    /// blockhash until it confirms or the policy gives up
+    pub async fn send_with_retry<T: Signers + ?Sized>(
+        &self,
+        rpc: &RpcClient,
+        payer: Pubkey,
+        builder: TransactionBuilder,
+        signers: &T,
+        policy: &RetryPolicy,
+    ) -> Result<Signature> {
+        for attempt in 0..policy.max_attempts {
+            let tip = self.tip(attempt);
+            let tip_account = self.tip_accounts[attempt as usize % self.tip_accounts.len()];
+            // The tip goes in the same transaction so it is only paid if the
+            // execution itself lands
+            let builder = builder
+                .clone()
+                .instruction(system_instruction::transfer(&payer, &tip_account, tip));
+
+            let (blockhash, last_valid_block_height) = rpc
+                .get_latest_blockhash_with_commitment(policy.commitment)
+                .await?;
+            let tx = builder.sign(blockhash, signers)?;
+            let signature = tx.signatures[0];
+            let bundle_id = self.send_bundle(&[tx]).await?;
+            tracing::debug!(%bundle_id, %signature, tip, "sent bundle");
+
+            match confirm(rpc, &[signature], Some(last_valid_block_height), policy).await? {
+                Outcome::Confirmed(signature) => return Ok(signature),
+                Outcome::Expired => tracing::debug!(%bundle_id, "bundle expired without landing"),
+            }
+        }
+        bail!("bundle not included after {} attempts", policy.max_attempts)
+    }
+}
@@ -0,0 +1,174 @@
+//! Reference relayer for the Continuum CP-Swap program. Follows every
+//! sequenced order on the configured pools and executes them strictly in
+//! FIFO order, exposing its progress at `GET /status` and streaming order
+//! updates over a websocket at `GET /stream`, with Prometheus metrics at
+//! `GET /metrics` and health checks at `GET /healthz` and `GET /readyz`.
+//! With `intake`
+//! enabled it also submits users' signed order intents from `POST /orders`.
+
+mod config;
+mod executor;
+mod health;
+mod intake;
+mod jito;
+mod metrics;
+mod pool;
+mod queue;
+mod relayer;
+mod status;
+mod store;
+mod stream;
+mod wallets;
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::Parser;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use tokio::sync::Mutex;
+use tracing_subscriber::EnvFilter;
+
+use continuum_cp_swap_client::accounts::{decode, PoolRelayers};
+use continuum_cp_swap_client::pda;
+use continuum_cp_swap_client::retry::RetryPolicy;
+
+use crate::config::RelayerConfig;
+use crate::executor::Executor;
+use crate::health::SlotLag;
+use crate::intake::Intake;
+use crate::jito::JitoClient;
+use crate::metrics::Metrics;
+use crate::pool::PoolRoute;
+use crate::relayer::Relayer;
+use crate::status::RelayerStatus;
+use crate::store::Store;
+use crate::wallets::{Funding, Wallets};
+
+#[derive(Parser)]
+#[command(name = "continuum-relayer", version, about)]
+struct Cli {
+    /// Relayer config file
+    #[arg(short, long, default_value = "relayer.toml")]
+    config: PathBuf,
+}
+
+/// Executions on a pool restricted to other relayers will fail, so say so up front
+async fn warn_unassigned(rpc: &RpcClient, pool_id: &Pubkey, wallets: &Wallets) {
+    let Ok(data) = rpc.get_account_data(&pda::find_pool_relayers(pool_id).0).await else {
+        return;
+    };
+    let Ok(assignment) = decode::<PoolRelayers>(&data) else {
+        return;
+    };
+    for key in wallets.keys() {
+        if !assignment.allows(&key.pubkey()) {
+            tracing::warn!(pool = %pool_id, wallet = %key.pubkey(), "executor key is not assigned to pool");
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .init();
+
+    let cli = Cli::parse();
+    let mut config = RelayerConfig::load(&cli.config)?;
+    let rpc = Arc::new(RpcClient::new_with_commitment(
+        config.rpc_url.clone(),
+        CommitmentConfig::confirmed(),
+    ));
+
+    let mut pools = HashMap::new();
+    for pool in config.take_pools() {
+        let cp_swap_program = config.cp_swap_program(&pool)?;
+        let route = PoolRoute::fetch(&rpc, &pool, cp_swap_program, &config.priority_fee).await?;
+        pools.insert(route.pool_id, route);
+    }
+
+    let jito = match &config.jito {
+        Some(jito) => {
+            tracing::info!(block_engine = %jito.block_engine_url, "submitting executions as Jito bundles");
+            Some(JitoClient::connect(jito).await?)
+        }
+        None => None,
+    };
+    let metrics = Arc::new(Metrics::new()?);
+    let funding = config.funding_keypair()?.map(|signer| Funding {
+        signer,
+        top_up_lamports: config.balance.top_up_lamports,
+    });
+    let wallets = Arc::new(Wallets::new(config.keypairs()?, config.balance.min_lamports, funding)?);
+    for pool_id in pools.keys() {
+        warn_unassigned(&rpc, pool_id, &wallets).await;
+    }
+    let executor = Executor {
+        wallets: wallets.clone(),
+        policy: RetryPolicy {
+            max_attempts: config.max_attempts,
+            ..RetryPolicy::default()
+        },
+        with_receipts: config.with_receipts,
+        use_posted_fee: config.use_posted_fee,
+        jito,
+        metrics: metrics.clone(),
+    };
+    let status = Arc::new(RwLock::new(RelayerStatus {
+        wallets: wallets.balances(),
+        pools: pools.keys().map(ToString::to_string).collect(),
+        ..RelayerStatus::default()
+    }));
+    tracing::info!(wallets = wallets.keys().len(), pools = pools.len(), "starting relayer");
+
+    let store = match &config.database_url {
+        Some(url) => Some(Store::connect(url).await?),
+        None => None,
+    };
+
+    let (updates, _) = tokio::sync::broadcast::channel(stream::UPDATE_BUFFER);
+    let mut app = status::router(status.clone())
+        .merge(stream::router(updates.clone()))
+        .merge(metrics::router(metrics.clone()))
+        .merge(health::router(status.clone()));
+    if config.intake {
+        app = app.merge(intake::router(Arc::new(Intake {
+            rpc: rpc.clone(),
+            signer: wallets.primary().insecure_clone(),
+            pools: pools.keys().copied().collect::<HashSet<_>>(),
+            policy: executor.policy.clone(),
+            submitting: Mutex::new(()),
+        })));
+    }
+    tokio::spawn(status::serve(config.status_addr, app));
+
+    let relayer = Relayer {
+        rpc,
+        ws_url: config.ws_url.clone(),
+        executor,
+        pools,
+        gap_timeout: Duration::from_secs(config.gap_timeout_secs),
+        max_concurrent: config.max_concurrent_executions.max(1),
+        reconcile_interval: Duration::from_secs(config.reconcile_interval_secs),
+        slot_lag: SlotLag::new(config.health.reference_rpc_url.clone(), config.health.max_slot_lag),
+        health_interval: Duration::from_secs(config.health.check_interval_secs),
+        status,
+        store,
+        updates,
+        metrics,
+    };
+
+    tokio::select! {
+        result = relayer.run() => result,
+        _ = tokio::signal::ctrl_c() => {
+            tracing::info!("shutting down");
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Prometheus metrics, exposed at `GET /metrics`.
+
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::header::CONTENT_TYPE;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry, TextEncoder,
+};
+
+pub struct Metrics {
+    registry: Registry,
+    pub queue_depth: IntGauge,
+    /// Lowest sequence not yet settled in each pool's queue
+    pub next_sequence: IntGaugeVec,
+    /// Time from handing an order out to its execution confirming or failing
+    pub execution_seconds: Histogram,
+    /// Executions by outcome: `filled`, `failed_transaction` when it landed
+    /// and failed on-chain (including the CP-Swap CPI), or `error`
+    pub executions: IntCounterVec,
+    /// Compute-unit price the latest execution was first sent with
+    pub priority_fee_micro_lamports: IntGauge,
+    /// Transaction fees of landed executions, priority fees included
+    pub fees_paid_lamports: IntCounter,
+    /// SOL balance of each executor key
+    pub wallet_balance_lamports: IntGaugeVec,
+    /// Executor keys below the minimum balance
+    pub low_balance_wallets: IntGauge,
+    pub funding_balance_lamports: IntGauge,
+    /// Slots the RPC node is behind the cluster
+    pub slot_lag: IntGauge,
+    /// 1 while execution is paused for slot lag
+    pub execution_paused: IntGauge,
+}
+
+impl Metrics {
+    pub fn new() -> prometheus::Result<Self> {
+        let registry = Registry::new_custom(Some("continuum_relayer".to_string()), None)?;
+
+        let queue_depth = IntGauge::new("queue_depth", "Orders waiting for execution")?;
+        let next_sequence = IntGaugeVec::new(
+            Opts::new("next_sequence", "Lowest sequence number not yet settled in a pool's queue"),
+            &["pool"],
+        )?;
+        let execution_seconds = Histogram::with_opts(
+            HistogramOpts::new("execution_seconds", "Time to execute an order")
+                .buckets(vec![0.5, 1.0, 2.0, 5.0, 10.0, 20.0, 40.0, 80.0]),
+        )?;
+        let executions = IntCounterVec::new(
+            Opts::new("executions_total", "Order executions by outcome"),
+            &["outcome"],
+        )?;
+        let priority_fee_micro_lamports = IntGauge::new(
+            "priority_fee_micro_lamports",
+            "Compute-unit price of the latest execution",
+        )?;
+        let fees_paid_lamports =
+            IntCounter::new("fees_paid_lamports_total", "Transaction fees paid for executions")?;
+        let wallet_balance_lamports = IntGaugeVec::new(
+            Opts::new("wallet_balance_lamports", "SOL balance of an executor key"),
+            &["wallet"],
+        )?;
+        let low_balance_wallets =
+            IntGauge::new("low_balance_wallets", "Executor keys below the minimum balance")?;
+        let funding_balance_lamports =
+            IntGauge::new("funding_balance_lamports", "SOL balance of the funding wallet")?;
+
+        let slot_lag = IntGauge::new("slot_lag", "Slots the RPC node is behind the cluster")?;
+        let execution_paused =
+            IntGauge::new("execution_paused", "Whether execution is paused for slot lag")?;
+
+        registry.register(Box::new(queue_depth.clone()))?;
+        registry.register(Box::new(next_sequence.clone()))?;
+        registry.register(Box::new(execution_seconds.clone()))?;
+        registry.register(Box::new(executions.clone()))?;
+        registry.register(Box::new(priority_fee_micro_lamports.clone()))?;
+        registry.register(Box::new(fees_paid_lamports.clone()))?;
+        registry.register(Box::new(wallet_balance_lamports.clone()))?;
+        registry.register(Box::new(low_balance_wallets.clone()))?;
+        registry.register(Box::new(funding_balance_lamports.clone()))?;
+        registry.register(Box::new(slot_lag.clone()))?;
+        registry.register(Box::new(execution_paused.clone()))?;
+
+        Ok(Self {
+            registry,
+            queue_depth,
+            next_sequence,
+            execution_seconds,
+            executions,
+            priority_fee_micro_lamports,
+            fees_paid_lamports,
+            wallet_balance_lamports,
+            low_balance_wallets,
+            funding_balance_lamports,
+            slot_lag,
+            execution_paused,
+        })
+    }
+}
+
+async fn metrics(State(metrics): State<Arc<Metrics>>) -> impl IntoResponse {
+    let encoder = TextEncoder::new();
+    let mut body = Vec::new();
+    // Encoding gathered metric families into a Vec can't fail
+    let _ = encoder.encode(&metrics.registry.gather(), &mut body);
+    ([(CONTENT_TYPE, encoder.format_type().to_string())], body)
+}
+
+pub fn router(metrics: Arc<Metrics>) -> Router {
+    Router::new().route("/metrics", get(self::metrics)).with_state(metrics)
+}
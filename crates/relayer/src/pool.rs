@@ -0,0 +1,93 @@
+//! Static accounts and settings of the pools the relayer serves, fetched
+//! once at startup.
+
+use anyhow::{anyhow, Context, Result};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::message::AddressLookupTableAccount;
+use solana_sdk::pubkey::Pubkey;
+
+use continuum_cp_swap_client::accounts::{decode, CpSwapPoolRegistry};
+use continuum_cp_swap_client::cp_swap::PoolStateInfo;
+use continuum_cp_swap_client::instructions::CpSwapSwapAccounts;
+use continuum_cp_swap_client::pda;
+use continuum_cp_swap_client::transaction::decode_lookup_table;
+
+use crate::config::{PoolConfig, PriorityFeeConfig};
+
+pub struct PoolRoute {
+    pub pool_id: Pubkey,
+    pub cp_swap_program: Pubkey,
+    pub state: PoolStateInfo,
+    pub lookup_tables: Vec<AddressLookupTableAccount>,
+    pub max_in_flight: usize,
+    pub max_fill_bps: Option<u16>,
+    pub priority_fee: PriorityFeeConfig,
+}
+
+impl PoolRoute {
+    pub async fn fetch(
+        rpc: &RpcClient,
+        config: &PoolConfig,
+        cp_swap_program: Pubkey,
+        default_priority_fee: &PriorityFeeConfig,
+    ) -> Result<Self> {
+        let pool_id = config.pool_id()?;
+        let data = rpc
+            .get_account_data(&pool_id)
+            .await
+            .with_context(|| format!("failed to fetch pool {}", pool_id))?;
+        let state = PoolStateInfo::parse(&data).ok_or_else(|| anyhow!("invalid pool {}", pool_id))?;
+
+        // The pool's own table, recorded in its registry, comes after the
+        // configured ones
+        let mut addresses = config.lookup_tables()?;
+        let data = rpc
+            .get_account_data(&pda::find_pool_registry(&pool_id).0)
+            .await
+            .with_context(|| format!("failed to fetch the registry of pool {}", pool_id))?;
+        let registry: CpSwapPoolRegistry = decode(&data).with_context(|| format!("invalid registry of pool {}", pool_id))?;
+        if let Some(table) = registry.lookup_table().filter(|table| !addresses.contains(table)) {
+            addresses.push(table);
+        }
+
+        let mut lookup_tables = Vec::new();
+        for address in addresses {
+            let data = rpc
+                .get_account_data(&address)
+                .await
+                .with_context(|| format!("failed to fetch lookup table {}", address))?;
+            lookup_tables.push(decode_lookup_table(address, &data)?);
+        }
+
+        Ok(Self {
+            pool_id,
+            cp_swap_program,
+            state,
+            lookup_tables,
+            max_in_flight: config.max_in_flight.max(1),
+            max_fill_bps: config.max_fill_bps,
+            priority_fee: config
+                .priority_fee
+                .clone()
+                .unwrap_or_else(|| default_priority_fee.clone()),
+        })
+    }
+
+    /// Swap accounts for an order selling `input_mint`
+    pub fn swap_accounts(
+        &self,
+        user: Pubkey,
+        input_mint: Pubkey,
+        input_token_account: Pubkey,
+        output_token_account: Pubkey,
+    ) -> CpSwapSwapAccounts {
+        self.state.swap_accounts(
+            &self.cp_swap_program,
+            self.pool_id,
+            user,
+            input_mint,
+            input_token_account,
+            output_token_account,
+        )
+    }
+}
@@ -0,0 +1,271 @@
+//! The relayer's view of a pool's queue. Orders are released for execution
+//! strictly in the pool's sequence: an order is only handed out once every
+//! lower sequence number on its pool is known to need no execution by this
+//! relayer.
+//! The exception is an order whose `valid_from` hasn't come yet, whose
+//! parent order isn't known to have filled, whose trailing stop hasn't
+//! triggered at the last price seen, or whose limit price the pool's price
+//! didn't meet at the last check: it is set aside so it doesn't hold up the
+//! rest of the sequence, and handed out as soon as it is ready.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use solana_sdk::pubkey::Pubkey;
+
+use continuum_cp_swap_client::accounts::OrderState;
+
+#[derive(Clone)]
+pub enum Entry {
+    /// A pending order
+    Order { address: Pubkey, order: Box<OrderState> },
+    /// Nothing for this relayer to do: a cancelled, expired or already
+    /// executed order
+    Settled,
+}
+
+pub struct OrderQueue {
+    /// Lowest sequence number not yet settled
+    next: u64,
+    /// Every sequence up to here that has no entry is known to be settled,
+    /// from a snapshot of the pending orders
+    known_through: u64,
+    entries: BTreeMap<u64, Entry>,
+    /// Orders passed over until their `valid_from`, their parent's fill,
+    /// their trailing stop or trigger, or their next TWAP slice
+    deferred: BTreeMap<u64, (Pubkey, Box<OrderState>)>,
+    /// Executed orders that queued orders name as their parent
+    filled: HashSet<Pubkey>,
+    /// Last price seen of each oracle queued trailing stops follow
+    prices: HashMap<Pubkey, i64>,
+    /// Limit orders whose limit the pool's price met at the last check
+    marketable: HashSet<Pubkey>,
+    gap_since: Option<Instant>,
+}
+
+impl OrderQueue {
+    /// Start from a snapshot taken when the pool's sequence was
+    /// `current_sequence`, containing all of its pending orders
+    pub fn from_snapshot(current_sequence: u64, pending: Vec<(Pubkey, OrderState)>) -> Self {
+        let mut queue = Self {
+            next: current_sequence + 1,
+            known_through: current_sequence,
+            entries: BTreeMap::new(),
+            deferred: BTreeMap::new(),
+            filled: HashSet::new(),
+            prices: HashMap::new(),
+            marketable: HashSet::new(),
+            gap_since: None,
+        };
+        for (address, order) in pending {
+            queue.next = queue.next.min(order.sequence);
+            queue.entries.insert(
+                order.sequence,
+                Entry::Order {
+                    address,
+                    order: Box::new(order),
+                },
+            );
+        }
+        queue
+    }
+
+    pub fn next_sequence(&self) -> u64 {
+        self.next
+    }
+
+    /// Number of orders waiting for execution
+    pub fn depth(&self) -> usize {
+        self.entries
+            .values()
+            .filter(|entry| matches!(entry, Entry::Order { .. }))
+            .count()
+            + self.deferred.len()
+    }
+
+    /// Record what a sequence number turned out to be. A settled entry
+    /// replaces a pending order, e.g. when it is cancelled.
+    pub fn insert(&mut self, sequence: u64, entry: Entry) {
+        if matches!(entry, Entry::Settled) {
+            self.deferred.remove(&sequence);
+        }
+        if sequence < self.next {
+            return;
+        }
+        match (self.entries.get(&sequence), &entry) {
+            (Some(Entry::Settled), Entry::Order { .. }) => {}
+            _ => {
+                self.entries.insert(sequence, entry);
+            }
+        }
+    }
+
+    /// Merge a snapshot taken when the pool's sequence was `current_sequence`.
+    /// Sequences it covers that are not pending need no execution, including
+    /// queued orders that were executed or cancelled while events were missed.
+    pub fn reconcile(&mut self, current_sequence: u64, pending: Vec<(Pubkey, OrderState)>) {
+        let pending: BTreeMap<u64, (Pubkey, OrderState)> = pending
+            .into_iter()
+            .map(|(address, order)| (order.sequence, (address, order)))
+            .collect();
+        self.entries.retain(|sequence, entry| {
+            *sequence > current_sequence
+                || matches!(entry, Entry::Settled)
+                || pending.contains_key(sequence)
+        });
+        self.deferred
+            .retain(|sequence, _| *sequence > current_sequence || pending.contains_key(sequence));
+        for (sequence, (address, order)) in pending {
+            self.insert(
+                sequence,
+                Entry::Order {
+                    address,
+                    order: Box::new(order),
+                },
+            );
+        }
+        self.known_through = self.known_through.max(current_sequence);
+
+        let queued = self.entries.values().filter_map(|entry| match entry {
+            Entry::Order { order, .. } => Some(order),
+            Entry::Settled => None,
+        });
+        let parents: HashSet<Pubkey> = queued
+            .chain(self.deferred.values().map(|(_, order)| order))
+            .filter_map(|order| order.parent_order)
+            .collect();
+        self.filled.retain(|parent| parents.contains(parent));
+        let oracles = self.oracles();
+        self.prices.retain(|oracle, _| oracles.contains(oracle));
+    }
+
+    fn orders_mut(&mut self) -> impl Iterator<Item = &mut OrderState> {
+        let queued = self.entries.values_mut().filter_map(|entry| match entry {
+            Entry::Order { order, .. } => Some(&mut **order),
+            Entry::Settled => None,
+        });
+        queued.chain(self.deferred.values_mut().map(|(_, order)| &mut **order))
+    }
+
+    /// Oracles followed by the trailing stops and triggers of queued orders
+    pub fn oracles(&self) -> HashSet<Pubkey> {
+        let queued = self.entries.values().filter_map(|entry| match entry {
+            Entry::Order { order, .. } => Some(order),
+            Entry::Settled => None,
+        });
+        queued
+            .chain(self.deferred.values().map(|(_, order)| order))
+            .filter_map(|order| order.oracle())
+            .collect()
+    }
+
+    /// Record the price of `oracle`, ratcheting the trailing stops that
+    /// follow it the way execution would
+    pub fn set_price(&mut self, oracle: Pubkey, price: i64) {
+        for order in self.orders_mut() {
+            if let Some(stop) = order.trailing_stop.as_mut().filter(|stop| stop.oracle == oracle) {
+                stop.observe(price);
+            }
+        }
+        self.prices.insert(oracle, price);
+    }
+
+    /// Queued orders with a limit price, to check against their pools
+    pub fn limit_orders(&self) -> Vec<(Pubkey, OrderState)> {
+        let queued = self.entries.values().filter_map(|entry| match entry {
+            Entry::Order { address, order } => Some((address, order)),
+            Entry::Settled => None,
+        });
+        queued
+            .chain(self.deferred.values().map(|(address, order)| (address, order)))
+            .filter(|(_, order)| order.limit_price.is_some())
+            .map(|(address, order)| (*address, (**order).clone()))
+            .collect()
+    }
+
+    /// Record which limit orders the pools' prices meet, releasing them
+    pub fn set_marketable(&mut self, marketable: HashSet<Pubkey>) {
+        self.marketable = marketable;
+    }
+
+    /// Record that the order at `address` executed, releasing orders that
+    /// name it as their parent
+    pub fn record_fill(&mut self, address: Pubkey) {
+        self.filled.insert(address);
+    }
+
+    /// Put an order back after part of it filled: a TWAP order is held
+    /// until its next slice is due
+    pub fn requeue(&mut self, sequence: u64, address: Pubkey, order: OrderState) {
+        self.deferred.insert(sequence, (address, Box::new(order)));
+    }
+
+    /// Whether the order at `address` can be handed out at `now`
+    fn is_ready(&self, address: &Pubkey, order: &OrderState, now: i64) -> bool {
+        order.is_valid_at(now)
+            && (order.limit_price.is_none() || self.marketable.contains(address))
+            && order.parent_order.is_none_or(|parent| self.filled.contains(&parent))
+            && order.twap.is_none_or(|twap| twap.slice_due(now))
+            && order.trailing_stop.is_none_or(|stop| {
+                self.prices.get(&stop.oracle).is_some_and(|price| stop.triggered(*price))
+            })
+            && order.trigger.is_none_or(|trigger| {
+                self.prices.get(&trigger.oracle).is_some_and(|price| trigger.triggered(*price))
+            })
+    }
+
+    /// The next order to execute, if every lower sequence is settled. A
+    /// missing sequence is waited on for `gap_timeout`, then skipped. Orders
+    /// not ready at `now` are deferred; deferred orders that have become
+    /// ready come first.
+    pub fn next_ready(&mut self, gap_timeout: Duration, now: i64) -> Option<(u64, Pubkey, OrderState)> {
+        if let Some((&sequence, (address, order))) =
+            self.deferred.iter().find(|(_, (address, order))| self.is_ready(address, order, now))
+        {
+            return Some((sequence, *address, (**order).clone()));
+        }
+        loop {
+            match self.entries.get(&self.next) {
+                Some(Entry::Settled) => {
+                    self.entries.remove(&self.next);
+                    self.advance();
+                }
+                Some(Entry::Order { address, order }) if !self.is_ready(address, order, now) => {
+                    let deferred = (*address, order.clone());
+                    self.deferred.insert(self.next, deferred);
+                    self.entries.remove(&self.next);
+                    self.advance();
+                }
+                Some(Entry::Order { address, order }) => {
+                    return Some((self.next, *address, (**order).clone()));
+                }
+                None if self.next <= self.known_through => self.advance(),
+                None => {
+                    let (&first, _) = self.entries.first_key_value()?;
+                    let since = *self.gap_since.get_or_insert_with(Instant::now);
+                    if since.elapsed() < gap_timeout {
+                        return None;
+                    }
+                    tracing::warn!(from = self.next, to = first, "skipping missing sequence numbers");
+                    self.next = first;
+                    self.gap_since = None;
+                }
+            }
+        }
+    }
+
+    /// Take the order returned by [`Self::next_ready`] out of the queue once
+    /// it has been handed to an executor, whatever the outcome
+    pub fn dispatch(&mut self, sequence: u64) {
+        self.entries.remove(&sequence);
+        self.deferred.remove(&sequence);
+        if sequence == self.next {
+            self.advance();
+        }
+    }
+
+    fn advance(&mut self) {
+        self.next += 1;
+        self.gap_since = None;
+    }
+}
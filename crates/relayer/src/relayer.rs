@@ -0,0 +1,722 @@
+//! The relayer loop: follow the program's events, keep each served pool's
+//! queue current and execute pending orders as they become next in line.
+//!
+//! Log subscriptions drop events while the websocket is down, so pending
+//! orders are also re-read periodically and after every reconnect.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Context, Result};
+use futures_util::future::LocalBoxFuture;
+use futures_util::stream::FuturesUnordered;
+use futures_util::{FutureExt, StreamExt};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{
+    RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcTransactionLogsConfig,
+    RpcTransactionLogsFilter,
+};
+use solana_client::rpc_filter::RpcFilterType;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use tokio::sync::mpsc;
+
+use continuum_cp_swap_client::accounts::{decode, OrderState, OrderStatus, PoolFifoState};
+use continuum_cp_swap_client::events::{parse_logs, ContinuumEvent};
+use continuum_cp_swap_client::filters::OrderQuery;
+use continuum_cp_swap_client::oracle::{OraclePrice, MAX_PRICE_AGE, PYTH_RECEIVER_PROGRAM_ID};
+use continuum_cp_swap_client::retry::SendError;
+use continuum_cp_swap_client::trace::{self, TraceId};
+use continuum_cp_swap_client::{pda, PROGRAM_ID};
+
+use crate::executor::{self, Executor};
+use crate::health::SlotLag;
+use crate::metrics::Metrics;
+use crate::pool::PoolRoute;
+use crate::queue::{Entry, OrderQueue};
+use crate::status::{ExecutionRecord, SharedStatus};
+use crate::store::{Store, STATUS_EXECUTING, STATUS_PENDING};
+use crate::stream::{OrderUpdate, Updates};
+
+/// How often the queue is re-checked when no events arrive, so skipped
+/// gaps are noticed
+const TICK: Duration = Duration::from_secs(1);
+
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Most accounts `getMultipleAccounts` returns at once
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+pub struct Relayer {
+    pub rpc: Arc<RpcClient>,
+    pub ws_url: String,
+    pub executor: Executor,
+    pub pools: HashMap<Pubkey, PoolRoute>,
+    pub gap_timeout: Duration,
+    /// Executions in flight at once across all pools
+    pub max_concurrent: usize,
+    pub reconcile_interval: Duration,
+    pub slot_lag: SlotLag,
+    pub health_interval: Duration,
+    pub status: SharedStatus,
+    pub store: Option<Store>,
+    pub updates: Updates,
+    pub metrics: Arc<Metrics>,
+}
+
+/// A finished execution attempt
+struct Execution {
+    sequence: u64,
+    address: Pubkey,
+    order: OrderState,
+    result: Result<Signature>,
+    elapsed: Duration,
+}
+
+/// What the log subscription reports
+enum Observed {
+    Submitted {
+        sequence: u64,
+        user: Pubkey,
+        pool_id: Pubkey,
+        amount_in: u64,
+        trace_id: TraceId,
+        signature: String,
+    },
+    Executed {
+        sequence: u64,
+        pool_id: Pubkey,
+        user: Pubkey,
+        amount_out: u64,
+        executor: Pubkey,
+        trace_id: TraceId,
+        signature: String,
+    },
+    Cancelled {
+        sequence: u64,
+        pool_id: Pubkey,
+        user: Pubkey,
+        trace_id: TraceId,
+        signature: String,
+    },
+    Expired {
+        sequence: u64,
+        pool_id: Pubkey,
+        user: Pubkey,
+        trace_id: TraceId,
+        signature: String,
+    },
+    /// The subscription was re-established; events may have been missed
+    Resubscribed,
+}
+
+impl Observed {
+    fn from_event(event: ContinuumEvent, signature: &str) -> Option<Self> {
+        let signature = signature.to_string();
+        Some(match event {
+            ContinuumEvent::OrderSubmitted(e) => Observed::Submitted {
+                sequence: e.sequence,
+                user: e.user,
+                pool_id: e.pool_id,
+                amount_in: e.amount_in,
+                trace_id: e.trace_id,
+                signature,
+            },
+            ContinuumEvent::OrderCancelled(e) => Observed::Cancelled {
+                sequence: e.sequence,
+                pool_id: e.pool_id,
+                user: e.user,
+                trace_id: e.trace_id,
+                signature,
+            },
+            ContinuumEvent::OrderExpired(e) => Observed::Expired {
+                sequence: e.sequence,
+                pool_id: e.pool_id,
+                user: e.user,
+                trace_id: e.trace_id,
+                signature,
+            },
+            ContinuumEvent::OrderExecuted(e) => Observed::Executed {
+                sequence: e.sequence,
+                pool_id: e.pool_id,
+                user: e.user,
+                amount_out: e.amount_out,
+                executor: e.executor,
+                trace_id: e.trace_id,
+                signature,
+            },
+            _ => return None,
+        })
+    }
+}
+
+pub(crate) fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// The store is an audit trail and a guard against re-sending; a write
+/// failing is logged rather than stopping execution, which the program
+/// itself keeps idempotent
+fn log_store_error<T>(result: Result<T>) -> Option<T> {
+    result
+        .map_err(|err| tracing::warn!(error = format!("{:#}", err), "order store error"))
+        .ok()
+}
+
+/// Forward sequence events from the program's logs, reconnecting on failure
+async fn subscribe(ws_url: String, sender: mpsc::UnboundedSender<Observed>, status: SharedStatus) {
+    let mut backoff = Duration::from_secs(1);
+    let mut first = true;
+    loop {
+        match stream_events(&ws_url, &sender, &status, first).await {
+            Ok(()) => return,
+            Err(err) => tracing::warn!(error = %err, "log subscription dropped"),
+        }
+        status.write().unwrap().subscribed = false;
+        first = false;
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_RECONNECT_BACKOFF);
+    }
+}
+
+/// Returns `Ok` once the receiving side has gone away
+async fn stream_events(
+    ws_url: &str,
+    sender: &mpsc::UnboundedSender<Observed>,
+    status: &SharedStatus,
+    first: bool,
+) -> Result<()> {
+    let client = PubsubClient::new(ws_url).await?;
+    let (mut logs, _unsubscribe) = client
+        .logs_subscribe(
+            RpcTransactionLogsFilter::Mentions(vec![PROGRAM_ID.to_string()]),
+            RpcTransactionLogsConfig {
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )
+        .await?;
+    status.write().unwrap().subscribed = true;
+    tracing::info!("subscribed to program logs");
+    if !first && sender.send(Observed::Resubscribed).is_err() {
+        return Ok(());
+    }
+
+    while let Some(response) = logs.next().await {
+        if response.value.err.is_some() {
+            continue;
+        }
+        for parsed in parse_logs(&response.value.logs) {
+            if let Some(observed) = Observed::from_event(parsed.event, &response.value.signature) {
+                if sender.send(observed).is_err() {
+                    return Ok(());
+                }
+            }
+        }
+    }
+    Err(anyhow!("log stream closed"))
+}
+
+impl Relayer {
+    /// Each served pool's sequence and every order on it still pending at
+    /// that sequence. The sequence is read first, so any order at or below it
+    /// that is missing from the result has been settled.
+    async fn snapshot(&self) -> Result<HashMap<Pubkey, (u64, Vec<(Pubkey, OrderState)>)>> {
+        let mut snapshot = HashMap::new();
+        for pool_id in self.pools.keys() {
+            let data = self
+                .rpc
+                .get_account_data(&pda::find_pool_fifo(pool_id).0)
+                .await
+                .with_context(|| format!("failed to fetch FIFO queue of {}", pool_id))?;
+            let fifo: PoolFifoState =
+                decode(&data).map_err(|err| anyhow!("invalid FIFO queue of {}: {}", pool_id, err))?;
+
+            let query = OrderQuery::pending().pool(*pool_id);
+            let accounts = self
+                .rpc
+                .get_program_accounts_with_config(
+                    &PROGRAM_ID,
+                    RpcProgramAccountsConfig {
+                        filters: Some(query.filters().into_iter().map(RpcFilterType::from).collect()),
+                        account_config: RpcAccountInfoConfig {
+                            encoding: Some(UiAccountEncoding::Base64),
+                            ..RpcAccountInfoConfig::default()
+                        },
+                        ..RpcProgramAccountsConfig::default()
+                    },
+                )
+                .await
+                .with_context(|| format!("failed to fetch pending orders of {}", pool_id))?;
+            let pending = query.decode_orders(
+                accounts
+                    .into_iter()
+                    .map(|(address, account)| (address, account.data)),
+            );
+            snapshot.insert(*pool_id, (fifo.current_sequence, pending));
+        }
+
+        Ok(snapshot)
+    }
+
+    fn update_progress(&self, queues: &HashMap<Pubkey, OrderQueue>) {
+        let mut status = self.status.write().unwrap();
+        status.next_sequences = queues
+            .iter()
+            .map(|(pool_id, queue)| (pool_id.to_string(), queue.next_sequence()))
+            .collect();
+        status.queue_depth = queues.values().map(OrderQueue::depth).sum();
+        for (pool_id, queue) in queues {
+            self.metrics
+                .next_sequence
+                .with_label_values(&[&pool_id.to_string()])
+                .set(queue.next_sequence() as i64);
+        }
+        self.metrics.queue_depth.set(status.queue_depth as i64);
+    }
+
+    async fn refresh_wallets(&self) {
+        let wallets = &self.executor.wallets;
+        wallets.refresh(&self.rpc, &self.executor.policy, &self.metrics).await;
+        self.status.write().unwrap().wallets = wallets.balances();
+    }
+
+    async fn reconcile(&self, queues: &mut HashMap<Pubkey, OrderQueue>) {
+        self.refresh_wallets().await;
+
+        match self.snapshot().await {
+            Ok(snapshot) => {
+                for (pool_id, (sequence, pending)) in snapshot {
+                    tracing::debug!(%pool_id, sequence, pending = pending.len(), "reconciled pending orders");
+                    let filled = self.filled_parents(pending.iter().map(|(_, order)| order)).await;
+                    let queue = queues.get_mut(&pool_id).expect("a queue for every served pool");
+                    queue.reconcile(sequence, pending);
+                    for parent in filled {
+                        queue.record_fill(parent);
+                    }
+                }
+                self.status.write().unwrap().last_reconciled = Some(unix_now());
+            }
+            Err(err) => tracing::warn!(error = format!("{:#}", err), "reconciliation failed"),
+        }
+    }
+
+    /// The parents named by `orders` that have already executed. Fills seen
+    /// as events are recorded as they happen; this covers parents that filled
+    /// before the relayer was following, or while events were missed.
+    async fn filled_parents<'o>(&self, orders: impl IntoIterator<Item = &'o OrderState>) -> Vec<Pubkey> {
+        let parents: Vec<Pubkey> = orders.into_iter().filter_map(|order| order.parent_order).collect();
+        let mut filled = Vec::new();
+        for chunk in parents.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let accounts = match self.rpc.get_multiple_accounts(chunk).await {
+                Ok(accounts) => accounts,
+                Err(err) => {
+                    tracing::warn!(error = %err, "failed to fetch parent orders");
+                    continue;
+                }
+            };
+            filled.extend(chunk.iter().zip(accounts).filter_map(|(parent, account)| {
+                let order = decode::<OrderState>(&account?.data).ok()?;
+                (order.status == OrderStatus::Executed).then_some(*parent)
+            }));
+        }
+        filled
+    }
+
+    /// Read the oracles of queued trailing stops, so the queue releases the
+    /// ones that have triggered. Stale or unreadable prices are left out, as
+    /// execution would reject them.
+    async fn refresh_prices(&self, queues: &mut HashMap<Pubkey, OrderQueue>) {
+        let oracles: Vec<Pubkey> = queues
+            .values()
+            .flat_map(OrderQueue::oracles)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let now = unix_now();
+        for chunk in oracles.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let accounts = match self.rpc.get_multiple_accounts(chunk).await {
+                Ok(accounts) => accounts,
+                Err(err) => {
+                    tracing::warn!(error = %err, "failed to fetch oracle prices");
+                    continue;
+                }
+            };
+            for (oracle, account) in chunk.iter().zip(accounts) {
+                let price = account
+                    .filter(|account| account.owner == PYTH_RECEIVER_PROGRAM_ID)
+                    .and_then(|account| OraclePrice::parse(&account.data).ok())
+                    .filter(|price| now - price.publish_time <= MAX_PRICE_AGE);
+                match price {
+                    Some(price) => queues.values_mut().for_each(|queue| queue.set_price(*oracle, price.price)),
+                    None => tracing::debug!(%oracle, "no usable oracle price"),
+                }
+            }
+        }
+    }
+
+    /// Check the pools of queued limit orders, so the queue releases the ones
+    /// whose limit the pool's price has reached. Orders that can't be
+    /// checked stay held.
+    async fn refresh_limits(&self, queues: &mut HashMap<Pubkey, OrderQueue>) {
+        for (pool_id, queue) in queues.iter_mut() {
+            let pool = &self.pools[pool_id];
+            let mut marketable = HashSet::new();
+            for (address, order) in queue.limit_orders() {
+                match executor::limit_reached(&self.rpc, pool, &order).await {
+                    Ok(true) => {
+                        marketable.insert(address);
+                    }
+                    Ok(false) => {}
+                    Err(err) => tracing::debug!(%address, error = format!("{:#}", err), "failed to check limit price"),
+                }
+            }
+            queue.set_marketable(marketable);
+        }
+    }
+
+    /// Pause execution while the RPC node lags the cluster, and resume once
+    /// it has caught up. A failed check leaves the state as it was.
+    async fn check_slot_lag(&self) {
+        let lag = match self.slot_lag.measure(&self.rpc).await {
+            Ok(lag) => lag,
+            Err(err) => {
+                tracing::warn!(error = format!("{:#}", err), "slot lag check failed");
+                return;
+            }
+        };
+        let lagging = lag > self.slot_lag.max_slots;
+        self.metrics.slot_lag.set(lag.min(i64::MAX as u64) as i64);
+        self.metrics.execution_paused.set(lagging as i64);
+
+        let mut status = self.status.write().unwrap();
+        if lagging && !status.paused {
+            tracing::error!(lag, max = self.slot_lag.max_slots, "rpc node is behind the cluster, pausing execution");
+        } else if !lagging && status.paused {
+            tracing::info!(lag, "rpc node caught up, resuming execution");
+        }
+        status.slot_lag = Some(lag);
+        status.paused = lagging;
+    }
+
+    /// Push an update to stream clients, if there are any
+    fn publish(&self, update: OrderUpdate) {
+        let _ = self.updates.send(update);
+    }
+
+    /// Work out what a sequence number submitted on a served pool needs from
+    /// this relayer. `None` when nothing is known of it: simple submissions
+    /// number an order from the global sequence without creating its
+    /// account, so a missing account says nothing about the pool's queue.
+    async fn resolve(&self, sequence: u64, pool_id: Pubkey) -> Option<Entry> {
+        let address = pda::find_order(&pool_id, sequence).0;
+        let data = self.rpc.get_account_data(&address).await.ok()?;
+        Some(match decode::<OrderState>(&data).ok() {
+            Some(order) if order.status == OrderStatus::Pending => {
+                if let Some(store) = &self.store {
+                    log_store_error(store.record_order(&address, &order, unix_now()).await);
+                }
+                Entry::Order {
+                    address,
+                    order: Box::new(order),
+                }
+            }
+            _ => Entry::Settled,
+        })
+    }
+
+    /// Hand out each pool's orders in its sequence while the concurrency
+    /// limits allow. A pool's next order waits while the pool is at
+    /// `max_in_flight`.
+    async fn dispatch_ready<'a>(
+        &'a self,
+        queues: &mut HashMap<Pubkey, OrderQueue>,
+        in_flight: &mut FuturesUnordered<LocalBoxFuture<'a, Execution>>,
+        pool_in_flight: &mut HashMap<Pubkey, usize>,
+    ) {
+        if self.status.read().unwrap().paused {
+            return;
+        }
+        for (pool_id, queue) in queues.iter_mut() {
+            self.dispatch_pool(pool_id, queue, in_flight, pool_in_flight).await;
+        }
+    }
+
+    async fn dispatch_pool<'a>(
+        &'a self,
+        pool_id: &Pubkey,
+        queue: &mut OrderQueue,
+        in_flight: &mut FuturesUnordered<LocalBoxFuture<'a, Execution>>,
+        pool_in_flight: &mut HashMap<Pubkey, usize>,
+    ) {
+        let pool = &self.pools[pool_id];
+        while in_flight.len() < self.max_concurrent {
+            let running = pool_in_flight.entry(pool.pool_id).or_default();
+            if *running >= pool.max_in_flight {
+                break;
+            }
+            let Some((sequence, address, order)) = queue.next_ready(self.gap_timeout, unix_now()) else {
+                break;
+            };
+
+            if let Some(store) = &self.store {
+                // An order filled in parts is recorded as executed after each
+                if order.amount_in_remaining == order.amount_in
+                    && log_store_error(store.is_executed(pool_id, sequence).await) == Some(true)
+                {
+                    tracing::debug!(%pool_id, sequence, "order already executed by an earlier run");
+                    queue.dispatch(sequence);
+                    continue;
+                }
+                log_store_error(store.record_order(&address, &order, unix_now()).await);
+                log_store_error(store.set_status(pool_id, sequence, STATUS_EXECUTING, unix_now()).await);
+            }
+            self.publish(OrderUpdate::Executing {
+                sequence,
+                pool: pool_id.to_string(),
+                order: address.to_string(),
+                user: order.user.to_string(),
+                trace_id: trace::to_hex(&order.trace_id),
+            });
+
+            queue.dispatch(sequence);
+            *running += 1;
+            in_flight.push(
+                async move {
+                    let started = Instant::now();
+                    let result = self.executor.execute(&self.rpc, pool, address, &order).await;
+                    Execution {
+                        sequence,
+                        address,
+                        order,
+                        result,
+                        elapsed: started.elapsed(),
+                    }
+                }
+                .boxed_local(),
+            );
+        }
+    }
+
+    /// The order as it is after a successful execution that may have left
+    /// part of it pending: a TWAP slice, or a fill capped by the pool's
+    /// `max_fill_bps`
+    async fn remaining(&self, execution: &Execution) -> Option<OrderState> {
+        let order = &execution.order;
+        let capped = self.pools.get(&order.pool_id).is_some_and(|pool| pool.max_fill_bps.is_some());
+        if execution.result.is_err() || (order.twap.is_none() && !capped) {
+            return None;
+        }
+        let data = self.rpc.get_account_data(&execution.address).await.ok()?;
+        decode::<OrderState>(&data)
+            .ok()
+            .filter(|order| order.status == OrderStatus::Pending)
+    }
+
+    async fn finish(&self, execution: Execution) {
+        let Execution {
+            sequence,
+            address,
+            order,
+            result,
+            elapsed,
+        } = execution;
+
+        self.metrics.execution_seconds.observe(elapsed.as_secs_f64());
+        let outcome = match &result {
+            Ok(_) => "filled",
+            Err(err) if matches!(err.downcast_ref(), Some(SendError::Failed { .. })) => {
+                "failed_transaction"
+            }
+            Err(_) => "error",
+        };
+        self.metrics.executions.with_label_values(&[outcome]).inc();
+
+        let trace_id = trace::to_hex(&order.trace_id);
+        let pool_id = order.pool_id;
+        match &result {
+            Ok(signature) => tracing::info!(%pool_id, sequence, %trace_id, %signature, "executed order"),
+            Err(err) => {
+                tracing::error!(%pool_id, sequence, %trace_id, error = %err, "failed to execute order");
+                self.publish(OrderUpdate::Failed {
+                    sequence,
+                    pool: pool_id.to_string(),
+                    user: order.user.to_string(),
+                    trace_id: trace_id.clone(),
+                    error: format!("{:#}", err),
+                });
+            }
+        }
+
+        let record = ExecutionRecord {
+            sequence,
+            order: address.to_string(),
+            pool: order.pool_id.to_string(),
+            trace_id,
+            signature: result.as_ref().ok().map(ToString::to_string),
+            error: result.as_ref().err().map(|err| format!("{:#}", err)),
+            finished_at: unix_now(),
+        };
+        if let Some(store) = &self.store {
+            log_store_error(
+                store
+                    .record_attempt(
+                        &pool_id,
+                        sequence,
+                        record.signature.clone(),
+                        record.error.clone(),
+                        record.finished_at,
+                    )
+                    .await,
+            );
+        }
+        self.status.write().unwrap().record(record);
+    }
+
+    pub async fn run(self) -> Result<()> {
+        let pool_ids: Vec<Pubkey> = self.pools.keys().copied().collect();
+        self.refresh_wallets().await;
+        self.executor.ensure_fee_balances(&self.rpc, &pool_ids).await?;
+
+        // An execution cut off mid-send may or may not have landed; the
+        // order is retried if the snapshot still shows it pending
+        if let Some(store) = &self.store {
+            for (pool_id, sequence) in store.interrupted().await? {
+                tracing::warn!(%pool_id, sequence, "execution was interrupted by the last shutdown");
+                store.set_status(&pool_id, sequence, STATUS_PENDING, unix_now()).await?;
+            }
+        }
+
+        // Subscribe before taking the snapshot so nothing submitted in
+        // between is missed
+        let (sender, mut events) = mpsc::unbounded_channel();
+        tokio::spawn(subscribe(self.ws_url.clone(), sender, self.status.clone()));
+        let mut queues = HashMap::new();
+        for (pool_id, (sequence, pending)) in self.snapshot().await? {
+            tracing::info!(%pool_id, sequence, pending = pending.len(), "loaded order snapshot");
+            let filled = self.filled_parents(pending.iter().map(|(_, order)| order)).await;
+            let mut queue = OrderQueue::from_snapshot(sequence, pending);
+            for parent in filled {
+                queue.record_fill(parent);
+            }
+            queues.insert(pool_id, queue);
+        }
+        self.status.write().unwrap().last_reconciled = Some(unix_now());
+
+        let mut in_flight = FuturesUnordered::new();
+        let mut pool_in_flight: HashMap<Pubkey, usize> = HashMap::new();
+        let mut tick = tokio::time::interval(TICK);
+        let mut reconcile = tokio::time::interval(self.reconcile_interval);
+        reconcile.reset();
+        let mut health = tokio::time::interval(self.health_interval);
+        health.reset();
+        self.check_slot_lag().await;
+        loop {
+            tokio::select! {
+                observed = events.recv() => {
+                    match observed.ok_or_else(|| anyhow!("event subscription ended"))? {
+                        Observed::Submitted { sequence, user, pool_id, amount_in, trace_id, signature } => {
+                            let entry = if queues.contains_key(&pool_id) {
+                                self.resolve(sequence, pool_id).await
+                            } else {
+                                None
+                            };
+                            if let Some(entry) = entry {
+                                let queue = queues.get_mut(&pool_id).expect("a served pool");
+                                if let Entry::Order { address, order } = &entry {
+                                    for parent in self.filled_parents([&**order]).await {
+                                        queue.record_fill(parent);
+                                    }
+                                    self.publish(OrderUpdate::Submitted {
+                                        sequence,
+                                        order: address.to_string(),
+                                        user: user.to_string(),
+                                        pool: pool_id.to_string(),
+                                        amount_in,
+                                        trace_id: trace::to_hex(&trace_id),
+                                        signature,
+                                    });
+                                }
+                                queue.insert(sequence, entry);
+                            }
+                        }
+                        Observed::Executed { sequence, pool_id, user, amount_out, executor, trace_id, signature } => {
+                            // Parents may be on any pool
+                            let address = pda::find_order(&pool_id, sequence).0;
+                            queues.values_mut().for_each(|queue| queue.record_fill(address));
+                            self.publish(OrderUpdate::Filled {
+                                sequence,
+                                pool: pool_id.to_string(),
+                                user: user.to_string(),
+                                amount_out,
+                                executor: executor.to_string(),
+                                trace_id: trace::to_hex(&trace_id),
+                                signature,
+                            });
+                            if let Some(queue) = queues.get_mut(&pool_id) {
+                                queue.insert(sequence, Entry::Settled);
+                            }
+                        }
+                        Observed::Cancelled { sequence, pool_id, user, trace_id, signature } => {
+                            self.publish(OrderUpdate::Cancelled {
+                                sequence,
+                                pool: pool_id.to_string(),
+                                user: user.to_string(),
+                                trace_id: trace::to_hex(&trace_id),
+                                signature,
+                            });
+                            if let Some(queue) = queues.get_mut(&pool_id) {
+                                queue.insert(sequence, Entry::Settled);
+                            }
+                        }
+                        Observed::Expired { sequence, pool_id, user, trace_id, signature } => {
+                            self.publish(OrderUpdate::Expired {
+                                sequence,
+                                pool: pool_id.to_string(),
+                                user: user.to_string(),
+                                trace_id: trace::to_hex(&trace_id),
+                                signature,
+                            });
+                            if let Some(queue) = queues.get_mut(&pool_id) {
+                                queue.insert(sequence, Entry::Settled);
+                            }
+                        }
+                        Observed::Resubscribed => {
+                            self.reconcile(&mut queues).await;
+                            reconcile.reset();
+                        }
+                    }
+                }
+                Some(execution) = in_flight.next() => {
+                    let execution: Execution = execution;
+                    if let Some(running) = pool_in_flight.get_mut(&execution.order.pool_id) {
+                        *running -= 1;
+                    }
+                    let remaining = self.remaining(&execution).await.map(|order| (execution.sequence, execution.address, order));
+                    self.finish(execution).await;
+                    if let Some((sequence, address, order)) = remaining {
+                        if let Some(queue) = queues.get_mut(&order.pool_id) {
+                            queue.requeue(sequence, address, order);
+                        }
+                    }
+                }
+                _ = reconcile.tick() => self.reconcile(&mut queues).await,
+                _ = health.tick() => self.check_slot_lag().await,
+                _ = tick.tick() => {
+                    self.refresh_prices(&mut queues).await;
+                    self.refresh_limits(&mut queues).await;
+                }
+            }
+
+            self.dispatch_ready(&mut queues, &mut in_flight, &mut pool_in_flight)
+                .await;
+            self.update_progress(&queues);
+        }
+    }
+}
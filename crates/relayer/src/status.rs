@@ -0,0 +1,77 @@
+//! Relayer state exposed over HTTP at `GET /status`.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+
+use axum::extract::State;
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Serialize;
+
+use crate::wallets::WalletBalance;
+
+/// Executions kept in the status history
+const RECENT_EXECUTIONS: usize = 100;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecutionRecord {
+    pub sequence: u64,
+    pub order: String,
+    pub pool: String,
+    /// The order's trace ID, in hex
+    pub trace_id: String,
+    pub signature: Option<String>,
+    pub error: Option<String>,
+    pub finished_at: i64,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct RelayerStatus {
+    pub wallets: Vec<WalletBalance>,
+    pub pools: Vec<String>,
+    pub subscribed: bool,
+    /// Lowest sequence not yet settled in each pool's queue
+    pub next_sequences: BTreeMap<String, u64>,
+    pub queue_depth: usize,
+    /// Unix time of the last successful reconciliation
+    pub last_reconciled: Option<i64>,
+    /// Slots the RPC node was behind at the last check
+    pub slot_lag: Option<u64>,
+    /// Execution is held while the RPC node lags
+    pub paused: bool,
+    pub executed: u64,
+    pub failed: u64,
+    pub recent: VecDeque<ExecutionRecord>,
+}
+
+impl RelayerStatus {
+    pub fn record(&mut self, record: ExecutionRecord) {
+        if record.error.is_some() {
+            self.failed += 1;
+        } else {
+            self.executed += 1;
+        }
+        if self.recent.len() == RECENT_EXECUTIONS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(record);
+    }
+}
+
+pub type SharedStatus = Arc<RwLock<RelayerStatus>>;
+
+async fn status(State(status): State<SharedStatus>) -> Json<RelayerStatus> {
+    Json(status.read().unwrap().clone())
+}
+
+pub fn router(status: SharedStatus) -> Router {
+    Router::new().route("/status", get(self::status)).with_state(status)
+}
+
+pub async fn serve(addr: SocketAddr, app: Router) -> anyhow::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!(%addr, "http api listening");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
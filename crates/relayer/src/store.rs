@@ -0,0 +1,158 @@
+//! Persistent record of the orders the relayer has seen and every execution
+//! attempt, in SQLite or Postgres. Executed orders are never handed out
+//! again after a restart, even if a lagging RPC node still reports them
+//! pending, and operators can audit what was sent and why it failed.
+
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use solana_sdk::pubkey::Pubkey;
+use sqlx::any::{install_default_drivers, AnyPoolOptions};
+use sqlx::AnyPool;
+
+use continuum_cp_swap_client::accounts::OrderState;
+use continuum_cp_swap_client::trace;
+
+/// Orders are keyed by their pool and their sequence in its queue. Stores
+/// from before orders were sequenced by their pool keep their `orders` and
+/// `attempts` tables, keyed by the global sequence, as they were.
+const SCHEMA: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS pool_orders (
+        pool TEXT NOT NULL,
+        sequence BIGINT NOT NULL,
+        address TEXT NOT NULL,
+        owner TEXT NOT NULL,
+        amount_in BIGINT NOT NULL,
+        min_amount_out BIGINT NOT NULL,
+        is_base_input BOOLEAN NOT NULL,
+        status TEXT NOT NULL,
+        first_seen BIGINT NOT NULL,
+        updated_at BIGINT NOT NULL,
+        trace_id TEXT NOT NULL,
+        PRIMARY KEY (pool, sequence)
+    )",
+    "CREATE TABLE IF NOT EXISTS pool_attempts (
+        pool TEXT NOT NULL,
+        sequence BIGINT NOT NULL,
+        signature TEXT,
+        error TEXT,
+        attempted_at BIGINT NOT NULL
+    )",
+    "CREATE INDEX IF NOT EXISTS pool_attempts_sequence ON pool_attempts (pool, sequence)",
+];
+
+/// Order status as stored in the `orders` table
+pub const STATUS_PENDING: &str = "pending";
+pub const STATUS_EXECUTING: &str = "executing";
+pub const STATUS_EXECUTED: &str = "executed";
+pub const STATUS_FAILED: &str = "failed";
+
+pub struct Store {
+    pool: AnyPool,
+}
+
+impl Store {
+    /// Connect to `sqlite://...?mode=rwc` or `postgres://...` and create
+    /// the tables if needed
+    pub async fn connect(url: &str) -> Result<Self> {
+        install_default_drivers();
+        let pool = AnyPoolOptions::new()
+            .max_connections(4)
+            .connect(url)
+            .await
+            .context("failed to connect to the order store")?;
+        for statement in SCHEMA {
+            sqlx::query(statement).execute(&pool).await?;
+        }
+        Ok(Self { pool })
+    }
+
+    /// Record a pending order the first time it is seen
+    pub async fn record_order(&self, address: &Pubkey, order: &OrderState, now: i64) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO pool_orders (pool, sequence, address, owner, amount_in, min_amount_out,
+                is_base_input, status, first_seen, updated_at, trace_id)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+             ON CONFLICT (pool, sequence) DO NOTHING",
+        )
+        .bind(order.pool_id.to_string())
+        .bind(order.sequence as i64)
+        .bind(address.to_string())
+        .bind(order.user.to_string())
+        .bind(order.amount_in as i64)
+        .bind(order.min_amount_out as i64)
+        .bind(order.is_base_input)
+        .bind(STATUS_PENDING)
+        .bind(now)
+        .bind(now)
+        .bind(trace::to_hex(&order.trace_id))
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn set_status(&self, pool_id: &Pubkey, sequence: u64, status: &str, now: i64) -> Result<()> {
+        sqlx::query("UPDATE pool_orders SET status = $1, updated_at = $2 WHERE pool = $3 AND sequence = $4")
+            .bind(status)
+            .bind(now)
+            .bind(pool_id.to_string())
+            .bind(sequence as i64)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Record the outcome of one execution and update the order's status
+    pub async fn record_attempt(
+        &self,
+        pool_id: &Pubkey,
+        sequence: u64,
+        signature: Option<String>,
+        error: Option<String>,
+        now: i64,
+    ) -> Result<()> {
+        let status = if error.is_none() {
+            STATUS_EXECUTED
+        } else {
+            STATUS_FAILED
+        };
+        sqlx::query(
+            "INSERT INTO pool_attempts (pool, sequence, signature, error, attempted_at)
+             VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(pool_id.to_string())
+        .bind(sequence as i64)
+        .bind(signature)
+        .bind(error)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+        self.set_status(pool_id, sequence, status, now).await
+    }
+
+    pub async fn is_executed(&self, pool_id: &Pubkey, sequence: u64) -> Result<bool> {
+        let executed: Option<(String,)> =
+            sqlx::query_as("SELECT status FROM pool_orders WHERE pool = $1 AND sequence = $2 AND status = $3")
+                .bind(pool_id.to_string())
+                .bind(sequence as i64)
+                .bind(STATUS_EXECUTED)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(executed.is_some())
+    }
+
+    /// Orders an earlier run started executing but never recorded an
+    /// outcome for, i.e. it stopped mid-send
+    pub async fn interrupted(&self) -> Result<Vec<(Pubkey, u64)>> {
+        let rows: Vec<(String, i64)> = sqlx::query_as("SELECT pool, sequence FROM pool_orders WHERE status = $1")
+            .bind(STATUS_EXECUTING)
+            .fetch_all(&self.pool)
+            .await?;
+        rows.into_iter()
+            .map(|(pool, sequence)| {
+                let pool_id = Pubkey::from_str(&pool).with_context(|| format!("invalid pool {} in the order store", pool))?;
+                Ok((pool_id, sequence as u64))
+            })
+            .collect()
+    }
+}
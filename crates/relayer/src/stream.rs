@@ -0,0 +1,129 @@
+//! Order lifecycle updates pushed to websocket clients at `GET /stream`,
+//! optionally filtered to one user with `?user=<pubkey>`. Each message is
+//! one JSON-encoded [`OrderUpdate`], carrying the order's trace ID in hex.
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::response::Response;
+use axum::routing::get;
+use axum::Router;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+/// Updates buffered per client before a slow client starts missing them
+pub const UPDATE_BUFFER: usize = 1024;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OrderUpdate {
+    Submitted {
+        sequence: u64,
+        order: String,
+        user: String,
+        pool: String,
+        amount_in: u64,
+        trace_id: String,
+        signature: String,
+    },
+    /// This relayer started executing the order
+    Executing {
+        sequence: u64,
+        pool: String,
+        order: String,
+        user: String,
+        trace_id: String,
+    },
+    /// Executed, by this or any other relayer
+    Filled {
+        sequence: u64,
+        pool: String,
+        user: String,
+        amount_out: u64,
+        executor: String,
+        trace_id: String,
+        signature: String,
+    },
+    /// This relayer's execution failed; the order may still be pending
+    Failed {
+        sequence: u64,
+        pool: String,
+        user: String,
+        trace_id: String,
+        error: String,
+    },
+    Cancelled {
+        sequence: u64,
+        pool: String,
+        user: String,
+        trace_id: String,
+        signature: String,
+    },
+    /// Passed its time to live without executing
+    Expired {
+        sequence: u64,
+        pool: String,
+        user: String,
+        trace_id: String,
+        signature: String,
+    },
+}
+
+impl OrderUpdate {
+    fn user(&self) -> &str {
+        match self {
+            Self::Submitted { user, .. }
+            | Self::Executing { user, .. }
+            | Self::Filled { user, .. }
+            | Self::Failed { user, .. }
+            | Self::Cancelled { user, .. }
+            | Self::Expired { user, .. } => user,
+        }
+    }
+}
+
+pub type Updates = broadcast::Sender<OrderUpdate>;
+
+#[derive(Deserialize)]
+struct StreamFilter {
+    user: Option<String>,
+}
+
+async fn forward(mut socket: WebSocket, mut updates: broadcast::Receiver<OrderUpdate>, filter: StreamFilter) {
+    loop {
+        tokio::select! {
+            update = updates.recv() => match update {
+                Ok(update) => {
+                    if filter.user.as_deref().is_some_and(|user| user != update.user()) {
+                        continue;
+                    }
+                    let Ok(text) = serde_json::to_string(&update) else { continue };
+                    if socket.send(Message::Text(text)).await.is_err() {
+                        return;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::debug!(skipped, "stream client lagging, updates dropped");
+                }
+                Err(RecvError::Closed) => return,
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+async fn stream(
+    upgrade: WebSocketUpgrade,
+    Query(filter): Query<StreamFilter>,
+    State(updates): State<Updates>,
+) -> Response {
+    let receiver = updates.subscribe();
+    upgrade.on_upgrade(move |socket| forward(socket, receiver, filter))
+}
+
+pub fn router(updates: Updates) -> Router {
+    Router::new().route("/stream", get(stream)).with_state(updates)
+}
@@ -0,0 +1,147 @@
+//! The executor keys. Executions rotate across every key with enough SOL,
+//! so one key running dry doesn't stop the relayer; low keys are reported
+//! and, with a funding wallet configured, topped up from it.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::RwLock;
+
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_system_interface::instruction as system_instruction;
+
+use continuum_cp_swap_client::retry::{send_with_retry, RetryPolicy};
+use continuum_cp_swap_client::transaction::TransactionBuilder;
+
+use crate::metrics::Metrics;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WalletBalance {
+    pub pubkey: String,
+    /// Unknown until the first balance check
+    pub lamports: Option<u64>,
+    pub low: bool,
+}
+
+pub struct Funding {
+    pub signer: Keypair,
+    /// Lamports sent to a key that falls below the minimum
+    pub top_up_lamports: u64,
+}
+
+pub struct Wallets {
+    keys: Vec<Keypair>,
+    balances: RwLock<Vec<Option<u64>>>,
+    next: AtomicUsize,
+    /// Keys below this balance are skipped until they are refilled
+    min_lamports: u64,
+    funding: Option<Funding>,
+}
+
+impl Wallets {
+    pub fn new(keys: Vec<Keypair>, min_lamports: u64, funding: Option<Funding>) -> Result<Self> {
+        if keys.is_empty() {
+            return Err(anyhow!("at least one executor keypair is required"));
+        }
+        Ok(Self {
+            balances: RwLock::new(vec![None; keys.len()]),
+            keys,
+            next: AtomicUsize::new(0),
+            min_lamports,
+            funding,
+        })
+    }
+
+    pub fn keys(&self) -> &[Keypair] {
+        &self.keys
+    }
+
+    /// The first configured key, which pays for intake submissions
+    pub fn primary(&self) -> &Keypair {
+        &self.keys[0]
+    }
+
+    /// The next key in rotation that isn't known to be low
+    pub fn next(&self) -> Option<&Keypair> {
+        let balances = self.balances.read().unwrap();
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        (0..self.keys.len())
+            .map(|offset| (start + offset) % self.keys.len())
+            .find(|&index| balances[index].is_none_or(|lamports| lamports >= self.min_lamports))
+            .map(|index| &self.keys[index])
+    }
+
+    pub fn balances(&self) -> Vec<WalletBalance> {
+        let balances = self.balances.read().unwrap();
+        self.keys
+            .iter()
+            .zip(balances.iter())
+            .map(|(key, lamports)| WalletBalance {
+                pubkey: key.pubkey().to_string(),
+                lamports: *lamports,
+                low: lamports.is_some_and(|lamports| lamports < self.min_lamports),
+            })
+            .collect()
+    }
+
+    /// Re-read every key's balance, warn about low keys and top them up if
+    /// a funding wallet is configured
+    pub async fn refresh(&self, rpc: &RpcClient, policy: &RetryPolicy, metrics: &Metrics) {
+        for (index, key) in self.keys.iter().enumerate() {
+            let pubkey = key.pubkey();
+            let mut lamports = match rpc.get_balance(&pubkey).await {
+                Ok(lamports) => lamports,
+                Err(err) => {
+                    tracing::debug!(wallet = %pubkey, error = %err, "failed to fetch balance");
+                    continue;
+                }
+            };
+
+            if lamports < self.min_lamports {
+                tracing::warn!(wallet = %pubkey, lamports, min = self.min_lamports, "executor balance low");
+                if let Some(funding) = &self.funding {
+                    match self.top_up(rpc, policy, funding, &pubkey).await {
+                        Ok(()) => lamports += funding.top_up_lamports,
+                        Err(err) => tracing::error!(wallet = %pubkey, error = format!("{:#}", err), "top-up failed"),
+                    }
+                }
+            }
+
+            self.balances.write().unwrap()[index] = Some(lamports);
+            metrics
+                .wallet_balance_lamports
+                .with_label_values(&[&pubkey.to_string()])
+                .set(lamports as i64);
+        }
+
+        let low = self.balances().iter().filter(|balance| balance.low).count();
+        metrics.low_balance_wallets.set(low as i64);
+
+        if let Some(funding) = &self.funding {
+            let pubkey = funding.signer.pubkey();
+            if let Ok(lamports) = rpc.get_balance(&pubkey).await {
+                metrics.funding_balance_lamports.set(lamports as i64);
+                if lamports < funding.top_up_lamports {
+                    tracing::warn!(wallet = %pubkey, lamports, "funding wallet can't cover another top-up");
+                }
+            }
+        }
+    }
+
+    async fn top_up(&self, rpc: &RpcClient, policy: &RetryPolicy, funding: &Funding, to: &Pubkey) -> Result<()> {
+        let from = funding.signer.pubkey();
+        let builder = TransactionBuilder::new(from).instruction(system_instruction::transfer(
+            &from,
+            to,
+            funding.top_up_lamports,
+        ));
+        let signature = send_with_retry(rpc, builder, &[&funding.signer], policy)
+            .await
+            .map_err(|err| anyhow!(err))
+            .context("transfer from funding wallet failed")?;
+        tracing::info!(wallet = %to, lamports = funding.top_up_lamports, %signature, "topped up executor");
+        Ok(())
+    }
+}
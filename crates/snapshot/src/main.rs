@@ -0,0 +1,241 @@
+//! `continuum-snapshot`: dumps the Continuum program accounts (the FIFO
+//! state, pool registries and queues, orders and relayer infos) for audits,
+//! migrations and support investigations.
+//!
+//! Every account is read with a single `getProgramAccounts` call, so the
+//! snapshot is consistent as of the slot it reports. RPC nodes only serve
+//! current state: `--min-slot` makes the node wait until it has reached a
+//! slot, and the slot actually read is always recorded in the output.
+
+mod rows;
+
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Parser, ValueEnum};
+use serde::Serialize;
+use serde_json::json;
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_response::{OptionalContext, RpcKeyedAccount};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+
+use continuum_cp_swap_client::accounts::{CpSwapPoolRegistry, FifoState, OrderState, OrderStatus, PoolFifoState, RelayerInfo};
+use continuum_cp_swap_client::{cluster, PROGRAM_ID};
+
+use crate::rows::{FifoRow, MetaRow, OrderRow, PoolQueueRow, PoolRow, RelayerRow, Snapshot};
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// One JSON document
+    Json,
+    /// One CSV file per account type in the output directory
+    Csv,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum StatusArg {
+    Pending,
+    Executed,
+    Cancelled,
+    Failed,
+    Expired,
+}
+
+impl From<StatusArg> for OrderStatus {
+    fn from(status: StatusArg) -> Self {
+        match status {
+            StatusArg::Pending => OrderStatus::Pending,
+            StatusArg::Executed => OrderStatus::Executed,
+            StatusArg::Cancelled => OrderStatus::Cancelled,
+            StatusArg::Failed => OrderStatus::Failed,
+            StatusArg::Expired => OrderStatus::Expired,
+        }
+    }
+}
+
+#[derive(Parser)]
+#[command(name = "continuum-snapshot", version, about)]
+struct Cli {
+    #[arg(long, short, default_value = cluster::DEFAULT_RPC_URL)]
+    url: String,
+
+    #[arg(long, value_enum, default_value = "json")]
+    format: Format,
+
+    /// File for JSON [default: stdout], directory for CSV
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+
+    /// Only registries and orders of these pools
+    #[arg(long = "pool")]
+    pools: Vec<Pubkey>,
+
+    /// Only orders with these statuses
+    #[arg(long = "status", value_enum)]
+    statuses: Vec<StatusArg>,
+
+    /// Wait for the node to reach this slot before reading
+    #[arg(long)]
+    min_slot: Option<u64>,
+}
+
+/// Addresses and raw data of program accounts
+type RawAccounts = Vec<(Pubkey, Vec<u8>)>;
+
+/// Every program account, with the slot they were read at
+fn fetch_accounts(rpc: &RpcClient, min_slot: Option<u64>) -> Result<(u64, RawAccounts)> {
+    let config = RpcProgramAccountsConfig {
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(rpc.commitment()),
+            min_context_slot: min_slot,
+            ..RpcAccountInfoConfig::default()
+        },
+        with_context: Some(true),
+        ..RpcProgramAccountsConfig::default()
+    };
+    let response: OptionalContext<Vec<RpcKeyedAccount>> = rpc
+        .send(RpcRequest::GetProgramAccounts, json!([PROGRAM_ID.to_string(), config]))
+        .context("failed to fetch program accounts")?;
+    let OptionalContext::Context(response) = response else {
+        bail!("RPC node returned program accounts without a context slot");
+    };
+
+    let accounts = response
+        .value
+        .into_iter()
+        .map(|keyed| {
+            let address: Pubkey = keyed
+                .pubkey
+                .parse()
+                .map_err(|_| anyhow!("invalid account address `{}`", keyed.pubkey))?;
+            let data = keyed
+                .account
+                .data
+                .decode()
+                .ok_or_else(|| anyhow!("undecodable data for {}", address))?;
+            Ok((address, data))
+        })
+        .collect::<Result<_>>()?;
+    Ok((response.context.slot, accounts))
+}
+
+fn decode_as<T: AccountDeserialize + Discriminator>(data: &[u8]) -> Option<T> {
+    data.starts_with(T::DISCRIMINATOR)
+        .then(|| T::try_deserialize(&mut &data[..]).ok())
+        .flatten()
+}
+
+fn snapshot(cli: &Cli, slot: u64, accounts: RawAccounts) -> Snapshot {
+    let pools: HashSet<Pubkey> = cli.pools.iter().copied().collect();
+    let statuses: Vec<OrderStatus> = cli.statuses.iter().copied().map(Into::into).collect();
+    let pool_selected = |pool_id: &Pubkey| pools.is_empty() || pools.contains(pool_id);
+
+    let mut snapshot = Snapshot {
+        program_id: PROGRAM_ID.to_string(),
+        slot,
+        fifo_state: None,
+        pools: Vec::new(),
+        pool_queues: Vec::new(),
+        orders: Vec::new(),
+        relayers: Vec::new(),
+    };
+    let mut orders = Vec::new();
+    for (address, data) in &accounts {
+        if let Some(fifo) = decode_as::<FifoState>(data) {
+            snapshot.fifo_state = Some(FifoRow::new(address, &fifo));
+        } else if let Some(pool) = decode_as::<CpSwapPoolRegistry>(data) {
+            if pool_selected(&pool.pool_id) {
+                snapshot.pools.push(PoolRow::new(address, &pool));
+            }
+        } else if let Some(fifo) = decode_as::<PoolFifoState>(data) {
+            if pool_selected(&fifo.pool_id) {
+                snapshot.pool_queues.push(PoolQueueRow::new(address, &fifo));
+            }
+        } else if let Some(order) = decode_as::<OrderState>(data) {
+            if pool_selected(&order.pool_id) && (statuses.is_empty() || statuses.contains(&order.status)) {
+                orders.push((*address, order));
+            }
+        } else if let Some(relayer) = decode_as::<RelayerInfo>(data) {
+            snapshot.relayers.push(RelayerRow::new(address, &relayer));
+        }
+    }
+
+    orders.sort_by_key(|(_, order)| (order.pool_id.to_string(), order.sequence));
+    snapshot.orders = orders.iter().map(|(address, order)| OrderRow::new(address, order)).collect();
+    snapshot.pools.sort_by(|a, b| a.pool_id.cmp(&b.pool_id));
+    snapshot.pool_queues.sort_by(|a, b| a.pool_id.cmp(&b.pool_id));
+    snapshot.relayers.sort_by(|a, b| a.relayer.cmp(&b.relayer));
+    snapshot
+}
+
+fn write_csv<T: Serialize>(dir: &Path, name: &str, rows: &[T]) -> Result<()> {
+    let path = dir.join(name);
+    let mut writer = csv::Writer::from_path(&path).with_context(|| format!("failed to create {}", path.display()))?;
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn write(cli: &Cli, snapshot: &Snapshot) -> Result<()> {
+    match cli.format {
+        Format::Json => {
+            let json = serde_json::to_string_pretty(snapshot)?;
+            match &cli.output {
+                Some(path) => std::fs::write(path, json).with_context(|| format!("failed to write {}", path.display()))?,
+                None => writeln!(std::io::stdout(), "{}", json)?,
+            }
+        }
+        Format::Csv => {
+            let dir = cli.output.as_deref().ok_or_else(|| anyhow!("--output is required for CSV"))?;
+            std::fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+            write_csv(
+                dir,
+                "meta.csv",
+                &[MetaRow {
+                    program_id: &snapshot.program_id,
+                    slot: snapshot.slot,
+                    fifo_state: snapshot.fifo_state.iter().count(),
+                    pools: snapshot.pools.len(),
+                    pool_queues: snapshot.pool_queues.len(),
+                    orders: snapshot.orders.len(),
+                    relayers: snapshot.relayers.len(),
+                }],
+            )?;
+            write_csv(dir, "fifo_state.csv", snapshot.fifo_state.as_slice())?;
+            write_csv(dir, "pools.csv", &snapshot.pools)?;
+            write_csv(dir, "pool_queues.csv", &snapshot.pool_queues)?;
+            write_csv(dir, "orders.csv", &snapshot.orders)?;
+            write_csv(dir, "relayers.csv", &snapshot.relayers)?;
+        }
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let rpc = RpcClient::new_with_commitment(cli.url.clone(), CommitmentConfig::confirmed());
+
+    let (slot, accounts) = fetch_accounts(&rpc, cli.min_slot)?;
+    let snapshot = snapshot(&cli, slot, accounts);
+    write(&cli, &snapshot)?;
+
+    // Progress goes to stderr so JSON on stdout stays clean
+    eprintln!(
+        "Snapshot at slot {}: {} pool(s), {} order(s), {} relayer(s)",
+        snapshot.slot,
+        snapshot.pools.len(),
+        snapshot.orders.len(),
+        snapshot.relayers.len()
+    );
+    Ok(())
+}
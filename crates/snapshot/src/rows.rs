@@ -0,0 +1,260 @@
+//! Flat, serializable views of the program accounts. Every field is a
+//! scalar so the same rows serve as JSON objects and CSV records.
+
+use anchor_lang::prelude::Pubkey;
+use serde::Serialize;
+
+use continuum_cp_swap_client::accounts::{CpSwapPoolRegistry, FifoState, OrderState, OrderStatus, PoolFifoState, RelayerInfo};
+use continuum_cp_swap_client::trace;
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+pub fn status_name(status: OrderStatus) -> &'static str {
+    match status {
+        OrderStatus::Pending => "pending",
+        OrderStatus::Executed => "executed",
+        OrderStatus::Cancelled => "cancelled",
+        OrderStatus::Failed => "failed",
+        OrderStatus::Expired => "expired",
+    }
+}
+
+#[derive(Serialize)]
+pub struct FifoRow {
+    pub address: String,
+    pub current_sequence: u64,
+    pub admin: String,
+    pub emergency_pause: bool,
+    pub archive_tree: String,
+    pub archived_orders: u64,
+    pub checkpoint_interval: u64,
+    pub protocol_fee_bps: u16,
+    pub relayer_fee_bps: u16,
+    pub risk_authority: String,
+    pub max_swaps_per_window: u32,
+    pub swap_rate_window: u32,
+    pub insurance_share_bps: u16,
+    pub cp_swap_program: String,
+    pub permissionless_execution: bool,
+}
+
+impl FifoRow {
+    pub fn new(address: &Pubkey, fifo: &FifoState) -> Self {
+        Self {
+            address: address.to_string(),
+            current_sequence: fifo.current_sequence,
+            admin: fifo.admin.to_string(),
+            emergency_pause: fifo.emergency_pause,
+            archive_tree: fifo.archive_tree.to_string(),
+            archived_orders: fifo.archived_orders,
+            checkpoint_interval: fifo.checkpoint_interval,
+            protocol_fee_bps: fifo.protocol_fee_bps,
+            relayer_fee_bps: fifo.relayer_fee_bps,
+            risk_authority: fifo.risk_authority.to_string(),
+            max_swaps_per_window: fifo.max_swaps_per_window,
+            swap_rate_window: fifo.swap_rate_window,
+            insurance_share_bps: fifo.insurance_share_bps,
+            cp_swap_program: fifo.cp_swap_program.to_string(),
+            permissionless_execution: fifo.permissionless_execution,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct PoolRow {
+    pub registry: String,
+    pub pool_id: String,
+    pub token_0: String,
+    pub token_1: String,
+    pub continuum_authority: String,
+    pub created_at: i64,
+    pub is_active: bool,
+    /// Lifetime volumes are u128, so they're written as strings
+    pub volume_0: String,
+    pub volume_1: String,
+    pub volume_window_start: i64,
+    pub max_relayer_fee_bps: u16,
+    pub compliance_program: Option<String>,
+    /// Cumulative price of token_0 in token_1, a u128 like the volumes
+    pub price_0_cumulative: String,
+    pub price_0_last: u64,
+    pub price_0_observed_at: i64,
+    pub lookup_table: Option<String>,
+    pub is_paused: bool,
+    pub amm_config: String,
+    pub token_0_vault: String,
+    pub token_1_vault: String,
+    pub observation_state: String,
+}
+
+impl PoolRow {
+    pub fn new(address: &Pubkey, pool: &CpSwapPoolRegistry) -> Self {
+        Self {
+            registry: address.to_string(),
+            pool_id: pool.pool_id.to_string(),
+            token_0: pool.token_0.to_string(),
+            token_1: pool.token_1.to_string(),
+            continuum_authority: pool.continuum_authority.to_string(),
+            created_at: pool.created_at,
+            is_active: pool.is_active,
+            volume_0: pool.volume_0.lifetime.to_string(),
+            volume_1: pool.volume_1.lifetime.to_string(),
+            volume_window_start: pool.volume_window_start,
+            max_relayer_fee_bps: pool.max_relayer_fee_bps,
+            compliance_program: pool.compliance_program().map(|program| program.to_string()),
+            price_0_cumulative: pool.price_0.cumulative.to_string(),
+            price_0_last: pool.price_0.last_price,
+            price_0_observed_at: pool.price_0.last_observed_at,
+            lookup_table: pool.lookup_table().map(|table| table.to_string()),
+            is_paused: pool.is_paused,
+            amm_config: pool.amm_config.to_string(),
+            token_0_vault: pool.token_0_vault.to_string(),
+            token_1_vault: pool.token_1_vault.to_string(),
+            observation_state: pool.observation_state.to_string(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct PoolQueueRow {
+    pub address: String,
+    pub pool_id: String,
+    pub current_sequence: u64,
+    pub head: u64,
+    pub order_stream_hash: String,
+    pub checkpoint_root: String,
+    pub checkpoint_sequence: u64,
+    pub immediate_swaps: u64,
+    pub event_sequence: u64,
+}
+
+impl PoolQueueRow {
+    pub fn new(address: &Pubkey, fifo: &PoolFifoState) -> Self {
+        Self {
+            address: address.to_string(),
+            pool_id: fifo.pool_id.to_string(),
+            current_sequence: fifo.current_sequence,
+            head: fifo.head,
+            order_stream_hash: hex(&fifo.order_stream_hash),
+            checkpoint_root: hex(&fifo.checkpoint_root),
+            checkpoint_sequence: fifo.checkpoint_sequence,
+            immediate_swaps: fifo.immediate_swaps,
+            event_sequence: fifo.event_sequence,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct OrderRow {
+    pub address: String,
+    pub sequence: u64,
+    pub user: String,
+    pub pool_id: String,
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+    pub is_base_input: bool,
+    pub status: &'static str,
+    pub submitted_at: i64,
+    pub executed_at: Option<i64>,
+    pub recipient: Option<String>,
+    pub callback_program: Option<String>,
+    pub destination_account: Option<String>,
+    pub post_fill: Option<String>,
+    pub valid_from: Option<i64>,
+    pub source_account: Option<String>,
+    pub parent_order: Option<String>,
+    pub trailing_stop: Option<String>,
+    pub shared_escrow: Option<String>,
+    pub delegated_input: bool,
+    pub permit: Option<String>,
+    pub co_signer: Option<String>,
+    pub quoted_protocol_fee_bps: u16,
+    pub quoted_relayer_fee_bps: u16,
+    pub trace_id: String,
+    pub escrow_refund: Option<String>,
+    pub expires_at: Option<i64>,
+    pub limit_price: Option<String>,
+}
+
+impl OrderRow {
+    pub fn new(address: &Pubkey, order: &OrderState) -> Self {
+        Self {
+            address: address.to_string(),
+            sequence: order.sequence,
+            user: order.user.to_string(),
+            pool_id: order.pool_id.to_string(),
+            amount_in: order.amount_in,
+            min_amount_out: order.min_amount_out,
+            is_base_input: order.is_base_input,
+            status: status_name(order.status),
+            submitted_at: order.submitted_at,
+            executed_at: order.executed_at,
+            recipient: order.recipient.map(|recipient| recipient.to_string()),
+            callback_program: order.callback_program.map(|program| program.to_string()),
+            destination_account: order.destination_account.map(|account| account.to_string()),
+            post_fill: order.post_fill.map(|action| format!("{:?}", action)),
+            valid_from: order.valid_from,
+            source_account: order.source_account.map(|account| account.to_string()),
+            parent_order: order.parent_order.map(|parent| parent.to_string()),
+            trailing_stop: order.trailing_stop.map(|stop| format!("{:?}", stop)),
+            shared_escrow: order.shared_escrow.map(|escrow| escrow.to_string()),
+            delegated_input: order.delegated_input,
+            permit: order.permit.map(|permit| permit.to_string()),
+            co_signer: order.co_signer.map(|co_signer| format!("{:?}", co_signer)),
+            quoted_protocol_fee_bps: order.fee_quote.protocol_fee_bps,
+            quoted_relayer_fee_bps: order.fee_quote.relayer_fee_bps,
+            trace_id: trace::to_hex(&order.trace_id),
+            escrow_refund: order.escrow_refund.map(|refund| refund.to_string()),
+            expires_at: order.expires_at,
+            limit_price: order.limit_price.map(|limit| format!("{}/{}", limit.numerator, limit.denominator)),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct RelayerRow {
+    pub address: String,
+    pub relayer: String,
+    pub registered_at: i64,
+    pub last_active: i64,
+    pub last_active_slot: u64,
+    pub fee_bps: u16,
+}
+
+impl RelayerRow {
+    pub fn new(address: &Pubkey, relayer: &RelayerInfo) -> Self {
+        Self {
+            address: address.to_string(),
+            relayer: relayer.relayer.to_string(),
+            registered_at: relayer.registered_at,
+            last_active: relayer.last_active,
+            last_active_slot: relayer.last_active_slot,
+            fee_bps: relayer.fee_bps,
+        }
+    }
+}
+
+/// Everything exported, as of `slot`
+#[derive(Serialize)]
+pub struct Snapshot {
+    pub program_id: String,
+    pub slot: u64,
+    pub fifo_state: Option<FifoRow>,
+    pub pools: Vec<PoolRow>,
+    pub pool_queues: Vec<PoolQueueRow>,
+    pub orders: Vec<OrderRow>,
+    pub relayers: Vec<RelayerRow>,
+}
+
+#[derive(Serialize)]
+pub struct MetaRow<'a> {
+    pub program_id: &'a str,
+    pub slot: u64,
+    pub fifo_state: usize,
+    pub pools: usize,
+    pub pool_queues: usize,
+    pub orders: usize,
+    pub relayers: usize,
+}
@@ -0,0 +1,865 @@
+//! One instruction per program entry point, built from the sample keys.
+//! Instructions the client has a builder for go through it, so the vectors
+//! also pin the builders' account lists; the rest are assembled from the
+//! program's own `accounts` and `instruction` types.
+
+use std::collections::HashMap;
+
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use anchor_spl::token;
+
+use continuum_cp_swap::compression::{ACCOUNT_COMPRESSION_PROGRAM_ID, NOOP_PROGRAM_ID};
+use continuum_cp_swap::consts::{
+    ARCHIVE_AUTHORITY_SEED, CUSTODY_AUTHORITY_SEED, CUSTODY_SEED, EMITTER_SEED, FEE_COLLECTOR_SEED, FILL_ATTESTATION_SEED,
+    FOREIGN_EMITTER_SEED, RECEIVED_SEED, REDEEMED_TRANSFER_SEED,
+};
+use continuum_cp_swap::state::ArchivedOrder;
+use continuum_cp_swap::wormhole::WORMHOLE_PROGRAM_ID;
+use continuum_cp_swap::{accounts, instruction};
+use continuum_cp_swap_client::accounts::{
+    CoSignScope, CoSigner, DcaSchedule, FeeTier, Iceberg, LimitPrice, OrderStatus, OrderTemplate, TriggerDirection,
+};
+use continuum_cp_swap_client::instructions::{
+    self as builders, BatchExecution, BatchOrder, DcaScheduleParams, Escrow, ExecuteOrderParams, OrderOptions, OrderTemplateParams, OrderVault,
+};
+use continuum_cp_swap_client::intent::OrderIntent;
+use continuum_cp_swap_client::{pda, CP_SWAP_PROGRAM_ID, PROGRAM_ID};
+
+use crate::keys::Keys;
+use crate::pdas::{self, DCA_SCHEDULE_ID, EMITTER_CHAIN, ICEBERG_ID, NONCE, ORG_ID, SEQUENCE, TEMPLATE_ID, VAA_SEQUENCE};
+
+/// Sample amounts and times
+const AMOUNT_IN: u64 = 1_000_000;
+const MIN_AMOUNT_OUT: u64 = 990_000;
+const EXPIRES_AT: i64 = 1_800_000_000;
+const TTL: i64 = 3_600;
+const TRIGGER_PRICE: i64 = 15_000_000_000;
+const TRACE_ID: [u8; 16] = *b"continuum-trace!";
+const CLIENT_ORDER_ID: [u8; 32] = *b"continuum-client-order-id-000042";
+
+pub fn instructions(keys: &Keys) -> Vec<(&'static str, Instruction)> {
+    let order = pda::find_order(&keys.pool_id, SEQUENCE + 1).0;
+    let receipt = pda::find_receipt(&keys.pool_id, SEQUENCE + 1).0;
+    let organization = pda::find_organization(&keys.admin, ORG_ID).0;
+    let shared_escrow = pda::find_shared_escrow(&keys.user, &keys.token_0_mint).0;
+    let swap = keys.pool.swap_accounts(keys.user, keys.amm_config, keys.token_0_mint);
+
+    let template = OrderTemplate {
+        user: keys.user,
+        template_id: TEMPLATE_ID,
+        pool_id: keys.pool_id,
+        amount_in: AMOUNT_IN,
+        min_amount_out: MIN_AMOUNT_OUT,
+        is_base_input: true,
+        interval: 3_600,
+        total_occurrences: 24,
+        occurrences: 0,
+        next_at: EXPIRES_AT,
+    };
+    let dca_schedule = DcaSchedule {
+        user: keys.user,
+        schedule_id: DCA_SCHEDULE_ID,
+        pool_id: keys.pool_id,
+        input_mint: keys.token_0_mint,
+        destination: keys.user_token_1,
+        amount_per_tick: AMOUNT_IN,
+        min_amount_out_per_tick: MIN_AMOUNT_OUT,
+        interval: 3_600,
+        budget_remaining: AMOUNT_IN * 24,
+        ticks_executed: 0,
+        amount_out_total: 0,
+        next_execution_time: EXPIRES_AT,
+    };
+    let iceberg = Iceberg {
+        user: keys.user,
+        iceberg_id: ICEBERG_ID,
+        pool_id: keys.pool_id,
+        tranche_in: AMOUNT_IN,
+        tranche_min_out: MIN_AMOUNT_OUT,
+        active_order: None,
+        tranches: 0,
+    };
+    let intent = OrderIntent {
+        user: keys.user,
+        pool_id: keys.pool_id,
+        amount_in: AMOUNT_IN,
+        min_amount_out: MIN_AMOUNT_OUT,
+        is_base_input: true,
+        input_mint: keys.token_0_mint,
+        recipient: None,
+        nonce: NONCE,
+        expires_at: EXPIRES_AT,
+    };
+    let tiers = vec![
+        FeeTier { min_volume: 0, protocol_fee_bps: 30 },
+        FeeTier { min_volume: 1_000_000_000, protocol_fee_bps: 20 },
+    ];
+
+    let mut ixs = vec![
+        ("initialize", builders::initialize(keys.admin)),
+        (
+            "initialize_cp_swap_pool",
+            builders::initialize_cp_swap_pool(
+                keys.admin,
+                Some(keys.risk_authority),
+                keys.pool_id,
+                CP_SWAP_PROGRAM_ID,
+                AMOUNT_IN,
+                AMOUNT_IN,
+                0,
+                keys.pool.initialize_accounts(keys.admin, keys.amm_config, keys.recipient),
+            ),
+        ),
+        (
+            "submit_order",
+            builders::submit_order(
+                keys.user,
+                keys.pool_id,
+                keys.token_0_mint,
+                SEQUENCE,
+                AMOUNT_IN,
+                MIN_AMOUNT_OUT,
+                true,
+                OrderOptions {
+                    from_wallet: true,
+                    ..OrderOptions::default()
+                },
+            ),
+        ),
+        (
+            "submit_order_escrowed",
+            builders::submit_order(
+                keys.user,
+                keys.pool_id,
+                keys.token_0_mint,
+                SEQUENCE,
+                AMOUNT_IN,
+                MIN_AMOUNT_OUT,
+                true,
+                OrderOptions {
+                    escrow: Some(Escrow {
+                        source: keys.user_token_0,
+                    }),
+                    ..OrderOptions::default()
+                },
+            ),
+        ),
+        (
+            "submit_limit_order",
+            builders::submit_limit_order(
+                keys.user,
+                keys.pool_id,
+                keys.token_0_mint,
+                SEQUENCE,
+                AMOUNT_IN,
+                MIN_AMOUNT_OUT,
+                true,
+                LimitPrice {
+                    numerator: MIN_AMOUNT_OUT,
+                    denominator: AMOUNT_IN,
+                },
+                OrderOptions {
+                    ttl: Some(TTL),
+                    escrow: Some(Escrow {
+                        source: keys.user_token_0,
+                    }),
+                    ..OrderOptions::default()
+                },
+            ),
+        ),
+        (
+            "submit_trigger_order",
+            builders::submit_trigger_order(
+                keys.user,
+                keys.pool_id,
+                keys.token_0_mint,
+                SEQUENCE,
+                AMOUNT_IN,
+                MIN_AMOUNT_OUT,
+                true,
+                keys.oracle,
+                TRIGGER_PRICE,
+                TriggerDirection::Below,
+                OrderOptions {
+                    escrow: Some(Escrow {
+                        source: keys.user_token_0,
+                    }),
+                    ..OrderOptions::default()
+                },
+            ),
+        ),
+        (
+            "submit_twap_order",
+            builders::submit_twap_order(
+                keys.user,
+                keys.pool_id,
+                keys.token_0_mint,
+                SEQUENCE,
+                AMOUNT_IN,
+                MIN_AMOUNT_OUT,
+                4,
+                60,
+                OrderOptions {
+                    escrow: Some(Escrow {
+                        source: keys.user_token_0,
+                    }),
+                    ..OrderOptions::default()
+                },
+            ),
+        ),
+        (
+            "submit_order_with_options",
+            builders::submit_order(
+                keys.user,
+                keys.pool_id,
+                keys.token_0_mint,
+                SEQUENCE,
+                AMOUNT_IN,
+                MIN_AMOUNT_OUT,
+                false,
+                OrderOptions {
+                    recipient: Some(keys.recipient),
+                    valid_from: Some(EXPIRES_AT),
+                    co_signer: Some(CoSigner { key: keys.co_signer, scope: CoSignScope::Both }),
+                    trace_id: Some(TRACE_ID),
+                    from_wallet: true,
+                    ..OrderOptions::default()
+                },
+            ),
+        ),
+        (
+            "submit_order_with_client_order_id",
+            builders::submit_order(
+                keys.user,
+                keys.pool_id,
+                keys.token_0_mint,
+                SEQUENCE,
+                AMOUNT_IN,
+                MIN_AMOUNT_OUT,
+                true,
+                OrderOptions {
+                    client_order_id: Some(CLIENT_ORDER_ID),
+                    memo: Some("rebalance 42".to_string()),
+                    from_wallet: true,
+                    ..OrderOptions::default()
+                },
+            ),
+        ),
+        (
+            "execute_order",
+            builders::execute_order(ExecuteOrderParams {
+                executor: keys.relayer,
+                order,
+                user: keys.user,
+                sequence: SEQUENCE + 1,
+                pool_id: keys.pool_id,
+                user_source: swap.input_token_account,
+                user_destination: swap.output_token_account,
+                source_mint: keys.token_0_mint,
+                cp_swap_program: CP_SWAP_PROGRAM_ID,
+                with_receipt: true,
+                with_relayer_info: true,
+                with_memo: false,
+                callback_program: None,
+                post_fill: None,
+                parent_order: None,
+                oracle: None,
+                shared_escrow: None,
+                delegated_input: false,
+                permit: None,
+                escrow_refund: None,
+                max_fill_amount: None,
+                remaining_accounts: swap.execute_remaining_accounts(),
+            }),
+        ),
+        (
+            "execute_order_partial",
+            builders::execute_order(ExecuteOrderParams {
+                executor: keys.relayer,
+                order,
+                user: keys.user,
+                sequence: SEQUENCE + 1,
+                pool_id: keys.pool_id,
+                user_source: swap.input_token_account,
+                user_destination: swap.output_token_account,
+                source_mint: keys.token_0_mint,
+                cp_swap_program: CP_SWAP_PROGRAM_ID,
+                with_receipt: false,
+                with_relayer_info: false,
+                with_memo: false,
+                callback_program: None,
+                post_fill: None,
+                parent_order: None,
+                oracle: None,
+                shared_escrow: None,
+                delegated_input: false,
+                permit: None,
+                escrow_refund: None,
+                max_fill_amount: Some(AMOUNT_IN / 2),
+                remaining_accounts: swap.execute_remaining_accounts(),
+            }),
+        ),
+        (
+            "execute_orders_batch",
+            builders::execute_orders_batch(
+                keys.relayer,
+                keys.pool_id,
+                keys.token_0_mint,
+                keys.token_1_mint,
+                CP_SWAP_PROGRAM_ID,
+                true,
+                vec![BatchExecution {
+                    order,
+                    user: keys.user,
+                    user_source: swap.input_token_account,
+                    user_destination: swap.output_token_account,
+                    vault: None,
+                    remaining_accounts: swap.execute_remaining_accounts(),
+                }],
+            ),
+        ),
+        ("cancel_order", builders::cancel_order(keys.user, keys.pool_id, order, None, None, None)),
+        (
+            "modify_order",
+            builders::modify_order(keys.user, keys.pool_id, order, AMOUNT_IN, MIN_AMOUNT_OUT + 1, None, None, None),
+        ),
+        (
+            "modify_order_requeued",
+            builders::modify_order(keys.user, keys.pool_id, order, AMOUNT_IN * 2, MIN_AMOUNT_OUT * 2, Some(SEQUENCE + 1), None, None),
+        ),
+        (
+            "modify_escrowed_order_requeued",
+            builders::modify_order(
+                keys.user,
+                keys.pool_id,
+                order,
+                AMOUNT_IN * 2,
+                MIN_AMOUNT_OUT * 2,
+                Some(SEQUENCE + 1),
+                None,
+                Some(OrderVault {
+                    pool_id: keys.pool_id,
+                    refund: keys.user_token_0,
+                    input_mint: keys.token_0_mint,
+                }),
+            ),
+        ),
+        (
+            "cancel_escrowed_order",
+            builders::cancel_order(
+                keys.user,
+                keys.pool_id,
+                order,
+                None,
+                None,
+                Some(OrderVault {
+                    pool_id: keys.pool_id,
+                    refund: keys.user_token_0,
+                    input_mint: keys.token_0_mint,
+                }),
+            ),
+        ),
+        ("skip_order", builders::skip_order(keys.pool_id, order)),
+        ("expire_order", builders::expire_order(keys.user, keys.pool_id, order, None, None)),
+        (
+            "migrate_order",
+            builders::migrate_order(keys.user, SEQUENCE + 1, keys.pool_id, keys.token_0_mint, SEQUENCE),
+        ),
+        (
+            "cancel_ioc_order",
+            builders::cancel_ioc_order(keys.relayer, keys.user, keys.pool_id, order, None, None, true),
+        ),
+        (
+            "submit_orders",
+            builders::submit_orders(
+                keys.user,
+                &HashMap::from([(keys.pool_id, SEQUENCE)]),
+                vec![
+                    BatchOrder {
+                        pool_id: keys.pool_id,
+                        amount_in: AMOUNT_IN,
+                        min_amount_out: MIN_AMOUNT_OUT,
+                        is_base_input: true,
+                        input_mint: keys.token_0_mint,
+                        recipient: None,
+                        trace_id: None,
+                        from_wallet: true,
+                    },
+                    BatchOrder {
+                        pool_id: keys.pool_id,
+                        amount_in: AMOUNT_IN,
+                        min_amount_out: MIN_AMOUNT_OUT,
+                        is_base_input: false,
+                        input_mint: keys.token_1_mint,
+                        recipient: Some(keys.recipient),
+                        trace_id: Some(TRACE_ID),
+                        from_wallet: true,
+                    },
+                ],
+            ),
+        ),
+        (
+            "swap_immediate",
+            builders::swap_immediate(CP_SWAP_PROGRAM_ID, &swap, AMOUNT_IN, MIN_AMOUNT_OUT, true, Some(EXPIRES_AT), None),
+        ),
+        ("set_fee_config", builders::set_fee_config(keys.admin, 30, 10)),
+        (
+            "initialize_fee_ledger",
+            builders::initialize_fee_ledger(keys.admin, keys.pool_id, keys.token_0_mint, keys.token_1_mint),
+        ),
+        ("initialize_pool_fifo", builders::initialize_pool_fifo(keys.admin, keys.pool_id)),
+        ("migrate_fifo_state", builders::migrate_fifo_state(keys.admin)),
+        ("migrate_pool_registry", builders::migrate_pool_registry(keys.admin, keys.pool_id)),
+        ("open_relayer_fee_balance", builders::open_relayer_fee_balance(keys.relayer, keys.pool_id)),
+        (
+            "claim_relayer_fees",
+            builders::claim_relayer_fees(
+                keys.relayer,
+                keys.pool_id,
+                keys.token_0_mint,
+                keys.token_1_mint,
+                keys.user_token_0,
+                keys.user_token_1,
+            ),
+        ),
+        (
+            "withdraw_protocol_fees",
+            builders::withdraw_protocol_fees(
+                keys.admin,
+                keys.pool_id,
+                keys.token_0_mint,
+                keys.token_1_mint,
+                keys.user_token_0,
+                keys.user_token_1,
+            ),
+        ),
+        ("set_insurance_share", builders::set_insurance_share(keys.admin, 2_000)),
+        (
+            "payout_insurance",
+            builders::payout_insurance(
+                keys.admin,
+                Some(keys.risk_authority),
+                keys.pool_id,
+                keys.token_0_mint,
+                keys.user_token_0,
+                AMOUNT_IN,
+            ),
+        ),
+        (
+            "compensate_order",
+            builders::compensate_order(
+                keys.admin,
+                Some(keys.risk_authority),
+                keys.pool_id,
+                receipt,
+                keys.token_0_mint,
+                keys.user_token_0,
+                AMOUNT_IN,
+                [7; 32],
+            ),
+        ),
+        ("add_relayer", builders::add_relayer(keys.admin, keys.relayer)),
+        ("remove_relayer", builders::remove_relayer(keys.admin, keys.relayer)),
+        ("set_relayer_fee", builders::set_relayer_fee(keys.relayer, 5)),
+        ("set_relayer_fee_cap", builders::set_relayer_fee_cap(keys.admin, keys.pool_id, 10)),
+        ("initialize_fee_schedule", builders::initialize_fee_schedule(keys.admin, tiers.clone())),
+        ("set_fee_schedule", builders::set_fee_schedule(keys.admin, tiers)),
+        ("open_user_volume", builders::open_user_volume(keys.payer, keys.pool_id, keys.user)),
+        ("initialize_pool_relayers", builders::initialize_pool_relayers(keys.admin, keys.pool_id, vec![keys.relayer])),
+        ("set_pool_relayers", builders::set_pool_relayers(keys.admin, keys.pool_id, vec![keys.relayer, keys.delegate])),
+        ("set_risk_authority", builders::set_risk_authority(keys.admin, None, keys.risk_authority)),
+        ("set_cp_swap_program", builders::set_cp_swap_program(keys.admin, Some(keys.risk_authority), CP_SWAP_PROGRAM_ID)),
+        ("set_swap_rate_limit", builders::set_swap_rate_limit(keys.admin, 10, 60)),
+        ("set_permissionless_execution", builders::set_permissionless_execution(keys.admin, false)),
+        ("initialize_relayer_quota", builders::initialize_relayer_quota(keys.admin, keys.relayer, 100, AMOUNT_IN, 86_400)),
+        ("set_relayer_quota", builders::set_relayer_quota(keys.admin, keys.relayer, 200, AMOUNT_IN, 86_400)),
+        ("reset_relayer_quota", builders::reset_relayer_quota(keys.relayer)),
+        ("set_compliance_program", builders::set_compliance_program(keys.admin, keys.pool_id, keys.compliance_program)),
+        ("set_pool_lookup_table", builders::set_pool_lookup_table(keys.admin, keys.pool_id, keys.lookup_table)),
+        ("initialize_post_fill_vault", builders::initialize_post_fill_vault(keys.payer, keys.token_1_mint)),
+        ("approve_stake_program", builders::approve_stake_program(keys.admin, keys.stake_program)),
+        ("revoke_stake_program", builders::revoke_stake_program(keys.admin, keys.stake_program)),
+        (
+            "create_order_template",
+            builders::create_order_template(
+                keys.user,
+                keys.pool_id,
+                TEMPLATE_ID,
+                keys.token_0_mint,
+                keys.user_token_0,
+                OrderTemplateParams {
+                    amount_in: template.amount_in,
+                    min_amount_out: template.min_amount_out,
+                    is_base_input: template.is_base_input,
+                    interval: template.interval,
+                    total_occurrences: template.total_occurrences,
+                },
+                AMOUNT_IN * 24,
+            ),
+        ),
+        ("materialize_order_template", builders::materialize_order_template(keys.payer, &template, SEQUENCE)),
+        (
+            "close_order_template",
+            builders::close_order_template(keys.user, keys.pool_id, TEMPLATE_ID, keys.user_token_0),
+        ),
+        (
+            "create_dca_schedule",
+            builders::create_dca_schedule(
+                keys.user,
+                keys.pool_id,
+                DCA_SCHEDULE_ID,
+                keys.token_0_mint,
+                keys.user_token_0,
+                keys.user_token_1,
+                DcaScheduleParams {
+                    amount_per_tick: dca_schedule.amount_per_tick,
+                    min_amount_out_per_tick: dca_schedule.min_amount_out_per_tick,
+                    interval: dca_schedule.interval,
+                },
+                dca_schedule.budget_remaining,
+            ),
+        ),
+        (
+            "execute_dca_tick",
+            builders::execute_dca_tick(keys.relayer, CP_SWAP_PROGRAM_ID, &dca_schedule, &swap, true),
+        ),
+        (
+            "close_dca_schedule",
+            builders::close_dca_schedule(keys.user, keys.pool_id, DCA_SCHEDULE_ID, keys.user_token_0),
+        ),
+        (
+            "create_iceberg",
+            builders::create_iceberg(
+                keys.user,
+                keys.pool_id,
+                ICEBERG_ID,
+                keys.token_0_mint,
+                keys.user_token_0,
+                AMOUNT_IN,
+                MIN_AMOUNT_OUT,
+                AMOUNT_IN * 10,
+            ),
+        ),
+        ("rearm_iceberg", builders::rearm_iceberg(keys.payer, &iceberg, SEQUENCE)),
+        ("close_iceberg", builders::close_iceberg(keys.user, keys.pool_id, ICEBERG_ID, keys.user_token_0)),
+        ("update_trail", builders::update_trail(keys.pool_id, order, keys.oracle)),
+        ("open_shared_escrow", builders::open_shared_escrow(keys.user, keys.token_0_mint)),
+        (
+            "deposit_shared_escrow",
+            builders::deposit_shared_escrow(keys.user, keys.token_0_mint, keys.user_token_0, AMOUNT_IN),
+        ),
+        (
+            "withdraw_shared_escrow",
+            builders::withdraw_shared_escrow(keys.user, keys.token_0_mint, keys.user_token_0, AMOUNT_IN),
+        ),
+        ("close_shared_escrow", builders::close_shared_escrow(keys.user, keys.token_0_mint, keys.user_token_0)),
+        (
+            "approve_user_delegation",
+            builders::approve_user_delegation(keys.user, keys.delegate, keys.payer, AMOUNT_IN, Some(EXPIRES_AT)),
+        ),
+        ("revoke_user_delegation", builders::revoke_user_delegation(keys.user, keys.delegate, keys.payer)),
+        (
+            "submit_order_as_delegate",
+            builders::submit_order_as_delegate(
+                keys.delegate,
+                keys.user,
+                keys.pool_id,
+                keys.token_0_mint,
+                SEQUENCE,
+                AMOUNT_IN,
+                MIN_AMOUNT_OUT,
+                true,
+                None,
+            ),
+        ),
+        (
+            "cancel_order_as_delegate",
+            builders::cancel_order_as_delegate(keys.delegate, keys.user, keys.pool_id, order, Some(shared_escrow), None, None),
+        ),
+        ("create_permit", builders::create_permit(keys.user, keys.user_token_0, AMOUNT_IN, EXPIRES_AT)),
+        ("update_permit", builders::update_permit(keys.user, keys.user_token_0, AMOUNT_IN / 2, EXPIRES_AT)),
+        ("close_permit", builders::close_permit(keys.user, keys.user_token_0)),
+        ("create_organization", builders::create_organization(keys.admin, ORG_ID)),
+        (
+            "set_org_member",
+            builders::set_org_member(keys.admin, ORG_ID, keys.user, AMOUNT_IN, vec![keys.pool_id]),
+        ),
+        ("remove_org_member", builders::remove_org_member(keys.admin, ORG_ID, keys.user)),
+        ("open_org_escrow", builders::open_org_escrow(keys.admin, ORG_ID, keys.token_0_mint)),
+        (
+            "withdraw_org_escrow",
+            builders::withdraw_org_escrow(keys.admin, ORG_ID, keys.token_0_mint, keys.user_token_0, AMOUNT_IN),
+        ),
+        (
+            "submit_org_order",
+            builders::submit_org_order(
+                keys.user,
+                organization,
+                keys.pool_id,
+                SEQUENCE,
+                keys.token_0_mint,
+                keys.token_1_mint,
+                AMOUNT_IN,
+                MIN_AMOUNT_OUT,
+                true,
+                None,
+            ),
+        ),
+        (
+            "cancel_org_order",
+            builders::cancel_org_order(
+                keys.user,
+                organization,
+                keys.pool_id,
+                pda::find_order(&keys.pool_id, SEQUENCE + 1).0,
+                pda::find_shared_escrow(&organization, &keys.token_0_mint).0,
+            ),
+        ),
+        ("set_pause", builders::set_pause(keys.admin, None, true, None)),
+        (
+            "set_pool_pause",
+            builders::set_pause(keys.admin, Some(keys.pool_id), true, Some("pool incident".to_string())),
+        ),
+        ("enable_user_ordering", builders::enable_user_ordering(keys.user)),
+        ("disable_user_ordering", builders::disable_user_ordering(keys.user)),
+    ];
+
+    #[cfg(feature = "test-clock")]
+    ixs.extend([
+        ("initialize_mock_clock", builders::initialize_mock_clock(keys.admin, Some(EXPIRES_AT))),
+        ("set_mock_clock", builders::set_mock_clock(keys.admin, None)),
+    ]);
+
+    // The intent builder prepends the ed25519 verification, which isn't a
+    // Continuum instruction
+    let submit_order_intent = builders::submit_order_intent(keys.payer, intent, SEQUENCE, &[9; 64], None)
+        .into_iter()
+        .find(|ix| ix.program_id == PROGRAM_ID)
+        .expect("the intent builder includes the submission");
+    ixs.push(("submit_order_intent", submit_order_intent));
+
+    ixs.extend(without_builders(keys));
+    ixs
+}
+
+/// Instructions the client has no builders for
+fn without_builders(keys: &Keys) -> Vec<(&'static str, Instruction)> {
+    let fifo_state = pda::find_fifo_state().0;
+    let archive_authority = pdas::find(&[ARCHIVE_AUTHORITY_SEED], &PROGRAM_ID);
+    let custody_authority = pdas::find(&[CUSTODY_AUTHORITY_SEED], &PROGRAM_ID);
+    let custody = pdas::find(&[CUSTODY_SEED, keys.token_0_mint.as_ref()], &PROGRAM_ID);
+    let chain = EMITTER_CHAIN.to_le_bytes();
+    let foreign_emitter = pdas::find(&[FOREIGN_EMITTER_SEED, &chain], &PROGRAM_ID);
+    let order = pda::find_order(&keys.pool_id, SEQUENCE + 1).0;
+    let receipt = pda::find_receipt(&keys.pool_id, SEQUENCE + 1).0;
+    let wormhole_emitter = pdas::find(&[EMITTER_SEED], &PROGRAM_ID);
+
+    let ix = |accounts: Vec<_>, data: Vec<u8>| Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data,
+    };
+
+    vec![
+        (
+            "submit_order_simple",
+            ix(
+                accounts::SubmitOrderSimple {
+                    fifo_state,
+                    pool_registry: pda::find_pool_registry(&keys.pool_id).0,
+                    pool_fifo: pda::find_pool_fifo(&keys.pool_id).0,
+                    user: keys.user,
+                    pool_id: keys.pool_id,
+                    user_source_token: keys.user_token_0,
+                    user_destination_token: keys.user_token_1,
+                    token_program: token::ID,
+                    clock: sysvar::clock::ID,
+                }
+                .to_account_metas(None),
+                instruction::SubmitOrderSimple {
+                    amount_in: AMOUNT_IN,
+                    min_amount_out: MIN_AMOUNT_OUT,
+                    is_base_input: true,
+                }
+                .data(),
+            ),
+        ),
+        (
+            "initialize_order_archive",
+            ix(
+                accounts::InitializeOrderArchive {
+                    fifo_state,
+                    archive_authority,
+                    merkle_tree: keys.merkle_tree,
+                    admin: keys.admin,
+                    compression_program: ACCOUNT_COMPRESSION_PROGRAM_ID,
+                    noop_program: NOOP_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                instruction::InitializeOrderArchive {
+                    max_depth: 14,
+                    max_buffer_size: 64,
+                }
+                .data(),
+            ),
+        ),
+        (
+            "archive_order",
+            ix(
+                accounts::ArchiveOrder {
+                    fifo_state,
+                    order_state: order,
+                    pool_fifo: pda::find_pool_fifo(&keys.pool_id).0,
+                    user: keys.user,
+                    archive_authority,
+                    merkle_tree: keys.merkle_tree,
+                    compression_program: ACCOUNT_COMPRESSION_PROGRAM_ID,
+                    noop_program: NOOP_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                instruction::ArchiveOrder {}.data(),
+            ),
+        ),
+        (
+            "verify_archived_order",
+            ix(
+                accounts::VerifyArchivedOrder {
+                    fifo_state,
+                    merkle_tree: keys.merkle_tree,
+                    compression_program: ACCOUNT_COMPRESSION_PROGRAM_ID,
+                }
+                .to_account_metas(None),
+                instruction::VerifyArchivedOrder {
+                    order: ArchivedOrder {
+                        sequence: SEQUENCE + 1,
+                        user: keys.user,
+                        pool_id: keys.pool_id,
+                        amount_in: AMOUNT_IN,
+                        min_amount_out: MIN_AMOUNT_OUT,
+                        is_base_input: true,
+                        status: OrderStatus::Executed,
+                        submitted_at: EXPIRES_AT,
+                        executed_at: Some(EXPIRES_AT + 1),
+                        recipient: None,
+                    },
+                    root: [3; 32],
+                    leaf_index: 0,
+                }
+                .data(),
+            ),
+        ),
+        (
+            "set_checkpoint_interval",
+            ix(
+                accounts::SetCheckpointInterval {
+                    fifo_state,
+                    admin: keys.admin,
+                }
+                .to_account_metas(None),
+                instruction::SetCheckpointInterval { interval: 100 }.data(),
+            ),
+        ),
+        (
+            "relayer_heartbeat",
+            ix(
+                accounts::RelayerHeartbeat {
+                    relayer_info: pda::find_relayer_info(&keys.relayer).0,
+                    relayer: keys.relayer,
+                }
+                .to_account_metas(None),
+                instruction::RelayerHeartbeat {}.data(),
+            ),
+        ),
+        (
+            "register_foreign_emitter",
+            ix(
+                accounts::RegisterForeignEmitter {
+                    fifo_state,
+                    foreign_emitter,
+                    admin: keys.admin,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                instruction::RegisterForeignEmitter {
+                    chain: EMITTER_CHAIN,
+                    address: [5; 32],
+                }
+                .data(),
+            ),
+        ),
+        (
+            "initialize_custody",
+            ix(
+                accounts::InitializeCustody {
+                    fifo_state,
+                    mint: keys.token_0_mint,
+                    custody,
+                    custody_authority,
+                    admin: keys.admin,
+                    token_program: token::ID,
+                    system_program: system_program::ID,
+                    rent: sysvar::rent::ID,
+                }
+                .to_account_metas(None),
+                instruction::InitializeCustody {}.data(),
+            ),
+        ),
+        (
+            "submit_order_from_vaa",
+            ix(
+                accounts::SubmitOrderFromVaa {
+                    fifo_state,
+                    pool_registry: pda::find_pool_registry(&keys.pool_id).0,
+                    pool_fifo: pda::find_pool_fifo(&keys.pool_id).0,
+                    foreign_emitter,
+                    posted_vaa: keys.posted_vaa,
+                    received: pdas::find(&[RECEIVED_SEED, &chain, &VAA_SEQUENCE.to_le_bytes()], &PROGRAM_ID),
+                    transfer_vaa: keys.transfer_vaa,
+                    transfer_claim: keys.transfer_claim,
+                    redeemed_transfer: pdas::find(&[REDEEMED_TRANSFER_SEED, keys.transfer_vaa.as_ref()], &PROGRAM_ID),
+                    order_state: order,
+                    user: keys.user,
+                    mint: keys.token_0_mint,
+                    custody,
+                    custody_authority,
+                    order_vault: pda::find_order_vault(&order).0,
+                    escrow_refund: custody,
+                    pool_authority: pda::find_pool_authority(&keys.pool_id).0,
+                    payer: keys.payer,
+                    token_program: token::ID,
+                    system_program: system_program::ID,
+                    rent: sysvar::rent::ID,
+                }
+                .to_account_metas(None),
+                instruction::SubmitOrderFromVaa {
+                    emitter_chain: EMITTER_CHAIN,
+                    vaa_sequence: VAA_SEQUENCE,
+                }
+                .data(),
+            ),
+        ),
+        (
+            "attest_fill",
+            ix(
+                accounts::AttestFill {
+                    receipt,
+                    pool_fifo: pda::find_pool_fifo(&keys.pool_id).0,
+                    wormhole_bridge: pdas::find(&[b"Bridge"], &WORMHOLE_PROGRAM_ID),
+                    wormhole_message: pdas::find(&[FILL_ATTESTATION_SEED, receipt.as_ref()], &PROGRAM_ID),
+                    wormhole_emitter,
+                    wormhole_sequence: pdas::find(&[b"Sequence", wormhole_emitter.as_ref()], &WORMHOLE_PROGRAM_ID),
+                    wormhole_fee_collector: pdas::find(&[FEE_COLLECTOR_SEED], &WORMHOLE_PROGRAM_ID),
+                    payer: keys.payer,
+                    wormhole_program: WORMHOLE_PROGRAM_ID,
+                    system_program: system_program::ID,
+                    clock: sysvar::clock::ID,
+                    rent: sysvar::rent::ID,
+                }
+                .to_account_metas(None),
+                instruction::AttestFill {}.data(),
+            ),
+        ),
+    ]
+}
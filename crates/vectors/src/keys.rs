@@ -0,0 +1,120 @@
+//! The fixed keys every vector is built from. Each is the SHA-256 of
+//! `continuum-vectors:<label>`, so other SDKs can rebuild the same inputs
+//! without copying the addresses. The pool and its accounts are the CP-Swap
+//! PDAs of the two labelled mints under AMM config 0.
+
+use std::collections::BTreeMap;
+
+use solana_sdk::hash::hash;
+use solana_sdk::pubkey::Pubkey;
+
+use continuum_cp_swap_client::cp_swap::{find_amm_config, PoolAddresses};
+use continuum_cp_swap_client::CP_SWAP_PROGRAM_ID;
+
+pub const LABEL_PREFIX: &str = "continuum-vectors:";
+
+fn key(label: &str) -> Pubkey {
+    Pubkey::new_from_array(hash(format!("{}{}", LABEL_PREFIX, label).as_bytes()).to_bytes())
+}
+
+pub struct Keys {
+    pub admin: Pubkey,
+    pub risk_authority: Pubkey,
+    pub user: Pubkey,
+    pub relayer: Pubkey,
+    pub delegate: Pubkey,
+    pub co_signer: Pubkey,
+    pub payer: Pubkey,
+    pub recipient: Pubkey,
+    pub pool_id: Pubkey,
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+    pub user_token_0: Pubkey,
+    pub user_token_1: Pubkey,
+    pub cp_swap_authority: Pubkey,
+    pub amm_config: Pubkey,
+    pub token_0_vault: Pubkey,
+    pub token_1_vault: Pubkey,
+    pub observation_state: Pubkey,
+    pub oracle: Pubkey,
+    pub compliance_program: Pubkey,
+    pub stake_program: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub posted_vaa: Pubkey,
+    pub transfer_vaa: Pubkey,
+    pub transfer_claim: Pubkey,
+    pub lookup_table: Pubkey,
+    pub pool: PoolAddresses,
+}
+
+impl Keys {
+    pub fn new() -> Self {
+        let amm_config = find_amm_config(&CP_SWAP_PROGRAM_ID, 0);
+        let pool = PoolAddresses::derive(&CP_SWAP_PROGRAM_ID, &amm_config, key("mint_a"), key("mint_b"));
+
+        Self {
+            admin: key("admin"),
+            risk_authority: key("risk_authority"),
+            user: key("user"),
+            relayer: key("relayer"),
+            delegate: key("delegate"),
+            co_signer: key("co_signer"),
+            payer: key("payer"),
+            recipient: key("recipient"),
+            pool_id: pool.pool_state,
+            token_0_mint: pool.token_0_mint,
+            token_1_mint: pool.token_1_mint,
+            user_token_0: key("user_token_0"),
+            user_token_1: key("user_token_1"),
+            cp_swap_authority: pool.authority,
+            amm_config,
+            token_0_vault: pool.token_0_vault,
+            token_1_vault: pool.token_1_vault,
+            observation_state: pool.observation_state,
+            oracle: key("oracle"),
+            compliance_program: key("compliance_program"),
+            stake_program: key("stake_program"),
+            merkle_tree: key("merkle_tree"),
+            posted_vaa: key("posted_vaa"),
+            transfer_vaa: key("transfer_vaa"),
+            transfer_claim: key("transfer_claim"),
+            lookup_table: key("lookup_table"),
+            pool,
+        }
+    }
+
+    /// Every key by name, as written to the vectors
+    pub fn labelled(&self) -> BTreeMap<&'static str, String> {
+        [
+            ("admin", self.admin),
+            ("risk_authority", self.risk_authority),
+            ("user", self.user),
+            ("relayer", self.relayer),
+            ("delegate", self.delegate),
+            ("co_signer", self.co_signer),
+            ("payer", self.payer),
+            ("recipient", self.recipient),
+            ("pool_id", self.pool_id),
+            ("token_0_mint", self.token_0_mint),
+            ("token_1_mint", self.token_1_mint),
+            ("user_token_0", self.user_token_0),
+            ("user_token_1", self.user_token_1),
+            ("cp_swap_authority", self.cp_swap_authority),
+            ("amm_config", self.amm_config),
+            ("token_0_vault", self.token_0_vault),
+            ("token_1_vault", self.token_1_vault),
+            ("observation_state", self.observation_state),
+            ("oracle", self.oracle),
+            ("compliance_program", self.compliance_program),
+            ("stake_program", self.stake_program),
+            ("merkle_tree", self.merkle_tree),
+            ("posted_vaa", self.posted_vaa),
+            ("transfer_vaa", self.transfer_vaa),
+            ("transfer_claim", self.transfer_claim),
+            ("lookup_table", self.lookup_table),
+        ]
+        .into_iter()
+        .map(|(label, key)| (label, key.to_string()))
+        .collect()
+    }
+}
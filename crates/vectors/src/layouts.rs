@@ -0,0 +1,103 @@
+//! Discriminators and allocated sizes of the program accounts, and the
+//! discriminators of its events.
+
+use anchor_lang::Discriminator;
+use serde::Serialize;
+
+use continuum_cp_swap::instructions::SwapExecuted;
+use continuum_cp_swap::state::*;
+
+use crate::hex;
+
+#[derive(Serialize)]
+pub struct AccountLayout {
+    pub name: &'static str,
+    pub discriminator: String,
+    /// Bytes allocated for the account, discriminator included
+    pub space: usize,
+}
+
+#[derive(Serialize)]
+pub struct EventLayout {
+    pub name: &'static str,
+    pub discriminator: String,
+}
+
+macro_rules! accounts {
+    ($($ty:ident),* $(,)?) => {
+        vec![$(AccountLayout {
+            name: stringify!($ty),
+            discriminator: hex($ty::DISCRIMINATOR),
+            space: $ty::LEN,
+        }),*]
+    };
+}
+
+macro_rules! events {
+    ($($ty:ident),* $(,)?) => {
+        vec![$(EventLayout {
+            name: stringify!($ty),
+            discriminator: hex($ty::DISCRIMINATOR),
+        }),*]
+    };
+}
+
+pub fn accounts() -> Vec<AccountLayout> {
+    accounts![
+        FifoState,
+        CpSwapPoolRegistry,
+        PoolFeeLedger,
+        RelayerFeeBalance,
+        FeeSchedule,
+        UserVolume,
+        OrderState,
+        OrderTemplate,
+        DcaSchedule,
+        Iceberg,
+        SharedEscrow,
+        ApprovedStakeProgram,
+        RelayerInfo,
+        PoolRelayers,
+        PoolFifoState,
+        RelayerQuota,
+        ForeignEmitter,
+        ReceivedVaa,
+        UsedIntent,
+        UserDelegation,
+        Permit,
+        Organization,
+        UserOrdering,
+        MockClock,
+        ExecutionReceipt,
+        Compensation,
+    ]
+}
+
+pub fn events() -> Vec<EventLayout> {
+    events![
+        OrderSubmitted,
+        OrderExecuted,
+        ExecutionRejected,
+        TrailUpdated,
+        OrderCancelled,
+        PoolRegistered,
+        OrderArchived,
+        CheckpointRecorded,
+        FeesAccrued,
+        RelayerFeesClaimed,
+        ProtocolFeesWithdrawn,
+        InsurancePaidOut,
+        OrderCompensated,
+        RelayerAdded,
+        RelayerRemoved,
+        CrossChainOrderReceived,
+        FillAttested,
+        SwapExecuted,
+        PauseSet,
+        OrderExpired,
+        TwapSliceExecuted,
+        OrderPartiallyFilled,
+        DcaTickExecuted,
+        OrderModified,
+    ]
+}
@@ -0,0 +1,124 @@
+//! `continuum-vectors`: emits canonical encodings generated from the
+//! program's Rust types, so SDKs in other languages and auditors can check
+//! their own encodings against ground truth.
+//!
+//! The vectors cover every instruction (data and account metas), every
+//! program derived address (seeds, address and bump), and the
+//! discriminators and sizes of the program accounts and events. Everything
+//! is derived from fixed sample keys, see `keys`, so the output only changes
+//! when an encoding does. With `--output`, the raw instruction data is also
+//! written as one `.bin` file per instruction.
+
+mod instructions;
+mod keys;
+mod layouts;
+mod pdas;
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anchor_lang::solana_program::instruction::Instruction;
+use anyhow::{Context, Result};
+use clap::Parser;
+use serde::Serialize;
+
+use continuum_cp_swap_client::PROGRAM_ID;
+
+use crate::keys::Keys;
+use crate::layouts::{AccountLayout, EventLayout};
+use crate::pdas::PdaVector;
+
+#[derive(Parser)]
+#[command(name = "continuum-vectors", version, about)]
+struct Cli {
+    /// Directory for `vectors.json` and the raw instruction data
+    /// [default: JSON to stdout]
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Serialize)]
+struct Vectors {
+    program_id: String,
+    key_label_prefix: &'static str,
+    keys: BTreeMap<&'static str, String>,
+    pdas: Vec<PdaVector>,
+    instructions: Vec<InstructionVector>,
+    accounts: Vec<AccountLayout>,
+    events: Vec<EventLayout>,
+}
+
+#[derive(Serialize)]
+struct InstructionVector {
+    name: &'static str,
+    program_id: String,
+    discriminator: String,
+    data: String,
+    accounts: Vec<AccountMetaVector>,
+}
+
+#[derive(Serialize)]
+struct AccountMetaVector {
+    pubkey: String,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+impl InstructionVector {
+    fn new(name: &'static str, ix: &Instruction) -> Self {
+        Self {
+            name,
+            program_id: ix.program_id.to_string(),
+            discriminator: hex(&ix.data[..8]),
+            data: hex(&ix.data),
+            accounts: ix
+                .accounts
+                .iter()
+                .map(|meta| AccountMetaVector {
+                    pubkey: meta.pubkey.to_string(),
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+        }
+    }
+}
+
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let keys = Keys::new();
+    let ixs = instructions::instructions(&keys);
+
+    let vectors = Vectors {
+        program_id: PROGRAM_ID.to_string(),
+        key_label_prefix: keys::LABEL_PREFIX,
+        keys: keys.labelled(),
+        pdas: pdas::vectors(&keys)?,
+        instructions: ixs.iter().map(|(name, ix)| InstructionVector::new(name, ix)).collect(),
+        accounts: layouts::accounts(),
+        events: layouts::events(),
+    };
+
+    let Some(dir) = cli.output else {
+        println!("{}", serde_json::to_string_pretty(&vectors)?);
+        return Ok(());
+    };
+
+    let raw = dir.join("instructions");
+    fs::create_dir_all(&raw).with_context(|| format!("creating {}", raw.display()))?;
+    for (name, ix) in &ixs {
+        let path = raw.join(format!("{}.bin", name));
+        fs::write(&path, &ix.data).with_context(|| format!("writing {}", path.display()))?;
+    }
+
+    let path = dir.join("vectors.json");
+    fs::write(&path, serde_json::to_string_pretty(&vectors)?).with_context(|| format!("writing {}", path.display()))?;
+    eprintln!("Wrote {} instructions, {} PDAs and {} account layouts to {}", ixs.len(), vectors.pdas.len(), vectors.accounts.len(), dir.display());
+
+    Ok(())
+}
@@ -0,0 +1,188 @@
+//! Every program derived address, derived here from its raw seeds and
+//! checked against the client's `find_*` helper where it has one.
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use continuum_cp_swap::consts::{
+    ARCHIVE_AUTHORITY_SEED, CUSTODY_AUTHORITY_SEED, CUSTODY_SEED, EMITTER_SEED, FEE_COLLECTOR_SEED, FILL_ATTESTATION_SEED,
+    FOREIGN_EMITTER_SEED, RECEIVED_SEED, REDEEMED_TRANSFER_SEED,
+};
+use continuum_cp_swap::wormhole::WORMHOLE_PROGRAM_ID;
+use continuum_cp_swap_client::pda::*;
+use continuum_cp_swap_client::PROGRAM_ID;
+
+use crate::hex;
+use crate::keys::Keys;
+
+/// Sample arguments of the seeds that aren't keys
+pub const SEQUENCE: u64 = 7;
+pub const TEMPLATE_ID: u64 = 1;
+pub const ICEBERG_ID: u64 = 2;
+pub const ORG_ID: u64 = 3;
+pub const NONCE: u64 = 4;
+pub const EMITTER_CHAIN: u16 = 2;
+pub const VAA_SEQUENCE: u64 = 5;
+pub const DCA_SCHEDULE_ID: u64 = 6;
+
+#[derive(Serialize)]
+pub struct PdaVector {
+    pub name: &'static str,
+    pub program_id: String,
+    /// Hex of each seed, in order, without the bump
+    pub seeds: Vec<String>,
+    pub address: String,
+    pub bump: u8,
+}
+
+struct Builder {
+    vectors: Vec<PdaVector>,
+}
+
+impl Builder {
+    /// Derive `name` from `seeds` under `program_id`, failing if the client
+    /// derives something else
+    fn push(&mut self, name: &'static str, seeds: &[&[u8]], program_id: &Pubkey, client: Option<(Pubkey, u8)>) -> Result<Pubkey> {
+        let (address, bump) = Pubkey::find_program_address(seeds, program_id);
+        if let Some(client) = client {
+            if client != (address, bump) {
+                bail!("{}: seeds derive {} but the client derives {}", name, address, client.0);
+            }
+        }
+
+        self.vectors.push(PdaVector {
+            name,
+            program_id: program_id.to_string(),
+            seeds: seeds.iter().map(|seed| hex(seed)).collect(),
+            address: address.to_string(),
+            bump,
+        });
+        Ok(address)
+    }
+}
+
+pub fn vectors(keys: &Keys) -> Result<Vec<PdaVector>> {
+    let mut pdas = Builder { vectors: Vec::new() };
+    let program = &PROGRAM_ID;
+    let pool = keys.pool_id.as_ref();
+    let user = keys.user.as_ref();
+    let relayer = keys.relayer.as_ref();
+    let mint = keys.token_0_mint.as_ref();
+    let sequence = SEQUENCE.to_le_bytes();
+
+    pdas.push("fifo_state", &[FIFO_STATE_SEED], program, Some(find_fifo_state()))?;
+    pdas.push("pool_registry", &[POOL_REGISTRY_SEED, pool], program, Some(find_pool_registry(&keys.pool_id)))?;
+    pdas.push("pool_authority", &[POOL_AUTHORITY_SEED, pool], program, Some(find_pool_authority(&keys.pool_id)))?;
+    let order = pdas.push("order", &[ORDER_SEED, pool, &sequence], program, Some(find_order(&keys.pool_id, SEQUENCE)))?;
+    let legacy_sequence = (SEQUENCE - 1).to_le_bytes();
+    pdas.push("legacy_order", &[ORDER_SEED, user, &legacy_sequence], program, Some(find_legacy_order(&keys.user, SEQUENCE)))?;
+    pdas.push("order_escrow", &[ESCROW_SEED, order.as_ref()], program, Some(find_order_escrow(&order)))?;
+    pdas.push("order_vault", &[ORDER_VAULT_SEED, order.as_ref()], program, Some(find_order_vault(&order)))?;
+    let receipt = pdas.push("receipt", &[RECEIPT_SEED, pool, &sequence], program, Some(find_receipt(&keys.pool_id, SEQUENCE)))?;
+    pdas.push("compensation", &[COMPENSATION_SEED, receipt.as_ref()], program, Some(find_compensation(&receipt)))?;
+    pdas.push("relayer_info", &[RELAYER_SEED, relayer], program, Some(find_relayer_info(&keys.relayer)))?;
+    pdas.push("relayer_quota", &[RELAYER_QUOTA_SEED, relayer], program, Some(find_relayer_quota(&keys.relayer)))?;
+    pdas.push("fee_ledger", &[FEE_LEDGER_SEED, pool], program, Some(find_fee_ledger(&keys.pool_id)))?;
+    pdas.push("fee_vault", &[FEE_VAULT_SEED, pool, mint], program, Some(find_fee_vault(&keys.pool_id, &keys.token_0_mint)))?;
+    pdas.push(
+        "relayer_fee_balance",
+        &[RELAYER_FEES_SEED, pool, relayer],
+        program,
+        Some(find_relayer_fee_balance(&keys.pool_id, &keys.relayer)),
+    )?;
+    pdas.push("used_intent", &[INTENT_SEED, user, &NONCE.to_le_bytes()], program, Some(find_used_intent(&keys.user, NONCE)))?;
+    pdas.push("user_ordering", &[USER_ORDERING_SEED, user], program, Some(find_user_ordering(&keys.user)))?;
+    pdas.push("fee_schedule", &[FEE_SCHEDULE_SEED], program, Some(find_fee_schedule()))?;
+    pdas.push("user_volume", &[USER_VOLUME_SEED, pool, user], program, Some(find_user_volume(&keys.pool_id, &keys.user)))?;
+    pdas.push("pool_relayers", &[POOL_RELAYERS_SEED, pool], program, Some(find_pool_relayers(&keys.pool_id)))?;
+    pdas.push("pool_fifo", &[POOL_FIFO_SEED, pool], program, Some(find_pool_fifo(&keys.pool_id)))?;
+    pdas.push(
+        "compliance_record",
+        &[COMPLIANCE_RECORD_SEED, user],
+        &keys.compliance_program,
+        Some(find_compliance_record(&keys.compliance_program, &keys.user)),
+    )?;
+    let template = pdas.push(
+        "order_template",
+        &[ORDER_TEMPLATE_SEED, user, &TEMPLATE_ID.to_le_bytes()],
+        program,
+        Some(find_order_template(&keys.user, TEMPLATE_ID)),
+    )?;
+    pdas.push("template_escrow", &[TEMPLATE_ESCROW_SEED, template.as_ref()], program, Some(find_template_escrow(&template)))?;
+    let dca_schedule = pdas.push(
+        "dca_schedule",
+        &[DCA_SCHEDULE_SEED, user, &DCA_SCHEDULE_ID.to_le_bytes()],
+        program,
+        Some(find_dca_schedule(&keys.user, DCA_SCHEDULE_ID)),
+    )?;
+    pdas.push("dca_escrow", &[DCA_ESCROW_SEED, dca_schedule.as_ref()], program, Some(find_dca_escrow(&dca_schedule)))?;
+    let iceberg = pdas.push(
+        "iceberg",
+        &[ICEBERG_SEED, user, &ICEBERG_ID.to_le_bytes()],
+        program,
+        Some(find_iceberg(&keys.user, ICEBERG_ID)),
+    )?;
+    pdas.push("iceberg_escrow", &[ICEBERG_ESCROW_SEED, iceberg.as_ref()], program, Some(find_iceberg_escrow(&iceberg)))?;
+    let shared_escrow = pdas.push(
+        "shared_escrow",
+        &[SHARED_ESCROW_SEED, user, mint],
+        program,
+        Some(find_shared_escrow(&keys.user, &keys.token_0_mint)),
+    )?;
+    pdas.push(
+        "shared_escrow_vault",
+        &[SHARED_ESCROW_VAULT_SEED, shared_escrow.as_ref()],
+        program,
+        Some(find_shared_escrow_vault(&shared_escrow)),
+    )?;
+    pdas.push(
+        "user_delegation",
+        &[USER_DELEGATION_SEED, user, keys.delegate.as_ref()],
+        program,
+        Some(find_user_delegation(&keys.user, &keys.delegate)),
+    )?;
+    pdas.push(
+        "organization",
+        &[ORGANIZATION_SEED, keys.admin.as_ref(), &ORG_ID.to_le_bytes()],
+        program,
+        Some(find_organization(&keys.admin, ORG_ID)),
+    )?;
+    pdas.push("permit", &[PERMIT_SEED, keys.user_token_0.as_ref()], program, Some(find_permit(&keys.user_token_0)))?;
+    pdas.push("post_fill_vault", &[POST_FILL_VAULT_SEED, mint], program, Some(find_post_fill_vault(&keys.token_0_mint)))?;
+    pdas.push("post_fill_authority", &[POST_FILL_AUTHORITY_SEED], program, Some(find_post_fill_authority()))?;
+    pdas.push(
+        "approved_stake_program",
+        &[STAKE_PROGRAM_SEED, keys.stake_program.as_ref()],
+        program,
+        Some(find_approved_stake_program(&keys.stake_program)),
+    )?;
+    pdas.push(
+        "stake_deposit",
+        &[STAKE_DEPOSIT_SEED, mint],
+        &keys.stake_program,
+        Some(find_stake_deposit(&keys.stake_program, &keys.token_0_mint)),
+    )?;
+    pdas.push("mock_clock", &[MOCK_CLOCK_SEED], program, Some(find_mock_clock()))?;
+
+    // Archive and Wormhole accounts, which the client has no helpers for
+    pdas.push("archive_authority", &[ARCHIVE_AUTHORITY_SEED], program, None)?;
+    let chain = EMITTER_CHAIN.to_le_bytes();
+    pdas.push("foreign_emitter", &[FOREIGN_EMITTER_SEED, &chain], program, None)?;
+    pdas.push("received_vaa", &[RECEIVED_SEED, &chain, &VAA_SEQUENCE.to_le_bytes()], program, None)?;
+    pdas.push("redeemed_transfer", &[REDEEMED_TRANSFER_SEED, keys.transfer_vaa.as_ref()], program, None)?;
+    pdas.push("custody", &[CUSTODY_SEED, mint], program, None)?;
+    pdas.push("custody_authority", &[CUSTODY_AUTHORITY_SEED], program, None)?;
+    pdas.push("fill_attestation", &[FILL_ATTESTATION_SEED, receipt.as_ref()], program, None)?;
+    let emitter = pdas.push("wormhole_emitter", &[EMITTER_SEED], program, None)?;
+    pdas.push("wormhole_bridge", &[b"Bridge"], &WORMHOLE_PROGRAM_ID, None)?;
+    pdas.push("wormhole_sequence", &[b"Sequence", emitter.as_ref()], &WORMHOLE_PROGRAM_ID, None)?;
+    pdas.push("wormhole_fee_collector", &[FEE_COLLECTOR_SEED], &WORMHOLE_PROGRAM_ID, None)?;
+
+    Ok(pdas.vectors)
+}
+
+/// The address of a PDA without a client helper, for the instruction vectors
+pub fn find(seeds: &[&[u8]], program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(seeds, program_id).0
+}
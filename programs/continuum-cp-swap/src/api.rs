@@ -0,0 +1,842 @@
+//! The program's instructions. `#[program]` generates their dispatch and
+//! Anchor's IDL instructions, which resize accounts with the deprecated
+//! `AccountInfo::realloc`, alongside this module, hence its own file.
+
+use super::*;
+use crate::intent::OrderIntent;
+use crate::state::{ArchivedOrder, FeeTier, LimitPrice, TraceId, TriggerDirection};
+
+#[program]
+pub mod continuum_cp_swap {
+    use super::*;
+
+    /// Initialize the global FIFO state
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        instructions::initialize(ctx)
+    }
+
+    /// Initialize a CP-Swap pool with Continuum as custom authority
+    pub fn initialize_cp_swap_pool<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitializeCpSwapPool<'info>>,
+        init_amount_0: u64,
+        init_amount_1: u64,
+        open_time: u64,
+    ) -> Result<()> {
+        instructions::initialize_cp_swap_pool(ctx, init_amount_0, init_amount_1, open_time)
+    }
+
+    /// Submit a swap order selling the `input_mint` account, one of the
+    /// pool's, to its pool's FIFO queue. Its `params` can deliver
+    /// the output to a token account owned by `recipient` or into `destination_account`,
+    /// e.g. to pay an invoice with the fill. `post_fill` burns or stakes the
+    /// output instead. The order can't be executed before `valid_from`, nor
+    /// before `parent_order` has executed, nor until the oracle price reaches
+    /// `trailing_stop`. With `delegated_input` the user approves the order's
+    /// escrow PDA on their source account instead of the pool authority, and
+    /// the execution uses up that approval. Passing a permit spends through it
+    /// instead, without any approval. A `co_signer` must sign alongside the
+    /// user when its scope covers the submission or a cancellation. The
+    /// order's events carry `trace_id`, or one derived from its sequence.
+    /// Otherwise the order must escrow the input and fees in its vault, see
+    /// `order_vault`, unless `from_wallet` leaves them in the user's wallet
+    /// for the pool authority to spend by approval. With `ttl`, the order
+    /// expires that many seconds after submission, see `expire_order`. `execution_policy` defaults to
+    /// good-till-cancelled; an immediate-or-cancel or fill-or-kill order
+    /// can't wait on a condition.
+    pub fn submit_order(
+        ctx: Context<SubmitOrder>,
+        amount_in: u64,
+        min_amount_out: u64,
+        is_base_input: bool,
+        params: SubmitOrderParams,
+    ) -> Result<()> {
+        instructions::submit_order(ctx, amount_in, min_amount_out, is_base_input, params, OrderConditions::default())
+    }
+
+    /// Submit an order that only executes while the pool's spot price is at
+    /// least `limit_price`. It takes `submit_order`'s accounts, and may
+    /// execute out of its pool's line once the price is reached.
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit_limit_order(
+        ctx: Context<SubmitOrder>,
+        amount_in: u64,
+        min_amount_out: u64,
+        is_base_input: bool,
+        limit_price: LimitPrice,
+        recipient: Option<Pubkey>,
+        trace_id: Option<TraceId>,
+        ttl: Option<i64>,
+    ) -> Result<()> {
+        instructions::submit_limit_order(
+            ctx,
+            amount_in,
+            min_amount_out,
+            is_base_input,
+            limit_price,
+            recipient,
+            trace_id,
+            ttl,
+        )
+    }
+
+    /// Submit an exact input order sold in `num_slices` slices at least
+    /// `interval_seconds` apart, escrowed in its order vault, see
+    /// `TwapSchedule`. It takes `submit_order`'s accounts.
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit_twap_order(
+        ctx: Context<SubmitOrder>,
+        amount_in: u64,
+        min_amount_out: u64,
+        num_slices: u16,
+        interval_seconds: i64,
+        recipient: Option<Pubkey>,
+        trace_id: Option<TraceId>,
+        ttl: Option<i64>,
+    ) -> Result<()> {
+        instructions::submit_twap_order(
+            ctx,
+            amount_in,
+            min_amount_out,
+            num_slices,
+            interval_seconds,
+            recipient,
+            trace_id,
+            ttl,
+        )
+    }
+
+    /// Submit a stop-loss or take-profit order, held until the price of the
+    /// passed oracle crosses `trigger_price` in `direction`. It takes
+    /// `submit_order`'s accounts, and may execute out of its pool's line
+    /// once triggered.
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit_trigger_order(
+        ctx: Context<SubmitOrder>,
+        amount_in: u64,
+        min_amount_out: u64,
+        is_base_input: bool,
+        trigger_price: i64,
+        direction: TriggerDirection,
+        recipient: Option<Pubkey>,
+        trace_id: Option<TraceId>,
+        ttl: Option<i64>,
+    ) -> Result<()> {
+        instructions::submit_trigger_order(
+            ctx,
+            amount_in,
+            min_amount_out,
+            is_base_input,
+            trigger_price,
+            direction,
+            recipient,
+            trace_id,
+            ttl,
+        )
+    }
+
+    /// Execute the next order in the FIFO queue. With `max_fill_amount`,
+    /// fill at most that much of an exact input order's remaining input,
+    /// leaving the rest pending.
+    pub fn execute_order<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteOrder<'info>>,
+        expected_sequence: u64,
+        max_fill_amount: Option<u64>,
+    ) -> Result<()> {
+        instructions::execute_order(ctx, expected_sequence, max_fill_amount)
+    }
+
+    /// Execute the next `count` orders in a pool's queue in one instruction
+    pub fn execute_orders_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteOrdersBatch<'info>>,
+        count: u8,
+    ) -> Result<()> {
+        instructions::execute_orders_batch(ctx, count)
+    }
+
+    /// Cancel an order (only by original submitter)
+    pub fn cancel_order(
+        ctx: Context<CancelOrder>,
+    ) -> Result<()> {
+        instructions::cancel_order(ctx)
+    }
+
+    /// Change a pending order's amounts, in place when only its limit is
+    /// tightened, otherwise by cancelling it and queueing a replacement at
+    /// the tail (only by original submitter)
+    pub fn modify_order(
+        ctx: Context<ModifyOrder>,
+        amount_in: u64,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        instructions::modify_order(ctx, amount_in, min_amount_out)
+    }
+
+    /// Move a pending order of the first deployment, at the address
+    /// submissions used to derive from the global sequence, to the tail of
+    /// its pool's queue. Signed by the order's user, who names the mint it
+    /// sells.
+    pub fn migrate_order(ctx: Context<MigrateOrder>, pool_id: Pubkey) -> Result<()> {
+        instructions::migrate_order(ctx, pool_id)
+    }
+
+    /// Grow the FIFO state of the first deployment to the current layout
+    /// (admin only)
+    pub fn migrate_fifo_state(ctx: Context<MigrateFifoState>) -> Result<()> {
+        instructions::migrate_fifo_state(ctx)
+    }
+
+    /// Grow a pool registry of the first deployment to the current layout
+    /// (admin only)
+    pub fn migrate_pool_registry(ctx: Context<MigratePoolRegistry>) -> Result<()> {
+        instructions::migrate_pool_registry(ctx)
+    }
+
+    /// Settle a pending order past its expiry, refunding its escrow, and move
+    /// its pool's queue past it when it is next in line (permissionless)
+    pub fn expire_order(ctx: Context<ExpireOrder>) -> Result<()> {
+        instructions::expire_order(ctx)
+    }
+
+    /// Cancel an immediate-or-cancel order next in its pool's line that
+    /// couldn't be filled, refunding its escrow (any relayer the pool allows)
+    pub fn cancel_ioc_order(ctx: Context<CancelIocOrder>) -> Result<()> {
+        instructions::cancel_ioc_order(ctx)
+    }
+
+    /// Move a pool's queue past the order next in line when it was settled
+    /// without executing, is conditional, or has been next in line for the
+    /// grace period (permissionless)
+    pub fn skip_order(ctx: Context<SkipOrder>) -> Result<()> {
+        instructions::skip_order(ctx)
+    }
+
+    /// Simplified submit order without PDA
+    pub fn submit_order_simple(
+        ctx: Context<SubmitOrderSimple>,
+        amount_in: u64,
+        min_amount_out: u64,
+        is_base_input: bool,
+    ) -> Result<()> {
+        instructions::submit_order_simple(ctx, amount_in, min_amount_out, is_base_input)
+    }
+
+    /// Submit several orders, each at the next sequence of its pool, in one
+    /// instruction
+    pub fn submit_orders<'info>(
+        ctx: Context<'_, '_, '_, 'info, SubmitOrders<'info>>,
+        orders: Vec<BatchOrder>,
+    ) -> Result<()> {
+        instructions::submit_orders(ctx, orders)
+    }
+
+    /// Immediate swap - submit and execute in one transaction, aborting if
+    /// it lands after `deadline` (unix seconds) (registered relayers only)
+    pub fn swap_immediate<'info>(
+        ctx: Context<'_, '_, '_, 'info, SwapImmediate<'info>>,
+        amount_in: u64,
+        min_amount_out: u64,
+        is_base_input: bool,
+        pool_id: Pubkey,
+        pool_authority_bump: u8,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        instructions::swap_immediate(ctx, amount_in, min_amount_out, is_base_input, pool_id, pool_authority_bump, deadline)
+    }
+
+    /// Initialize the concurrent Merkle tree used to archive closed orders
+    pub fn initialize_order_archive(
+        ctx: Context<InitializeOrderArchive>,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        instructions::initialize_order_archive(ctx, max_depth, max_buffer_size)
+    }
+
+    /// Close a finished order and append it to the archive tree
+    pub fn archive_order(
+        ctx: Context<ArchiveOrder>,
+    ) -> Result<()> {
+        instructions::archive_order(ctx)
+    }
+
+    /// Verify an archived order against a root of the archive tree
+    pub fn verify_archived_order<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyArchivedOrder<'info>>,
+        order: ArchivedOrder,
+        root: [u8; 32],
+        leaf_index: u32,
+    ) -> Result<()> {
+        instructions::verify_archived_order(ctx, order, root, leaf_index)
+    }
+
+    /// Set how many orders are folded into each order-stream checkpoint
+    pub fn set_checkpoint_interval(
+        ctx: Context<SetCheckpointInterval>,
+        interval: u64,
+    ) -> Result<()> {
+        instructions::set_checkpoint_interval(ctx, interval)
+    }
+
+    /// Set the protocol and relayer fees charged on executed orders
+    pub fn set_fee_config(
+        ctx: Context<SetFeeConfig>,
+        protocol_fee_bps: u16,
+        relayer_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::set_fee_config(ctx, protocol_fee_bps, relayer_fee_bps)
+    }
+
+    /// Create the fee ledger and fee vaults for a registered pool
+    pub fn initialize_fee_ledger(
+        ctx: Context<InitializeFeeLedger>,
+    ) -> Result<()> {
+        instructions::initialize_fee_ledger(ctx)
+    }
+
+    /// Create the FIFO queue of a registered pool, which sequences its orders
+    /// and their accounts
+    pub fn initialize_pool_fifo(
+        ctx: Context<InitializePoolFifo>,
+    ) -> Result<()> {
+        instructions::initialize_pool_fifo(ctx)
+    }
+
+    /// Open a relayer's claimable fee balance on a pool
+    pub fn open_relayer_fee_balance(
+        ctx: Context<OpenRelayerFeeBalance>,
+    ) -> Result<()> {
+        instructions::open_relayer_fee_balance(ctx)
+    }
+
+    /// Claim a relayer's accrued fees on a pool
+    pub fn claim_relayer_fees(
+        ctx: Context<ClaimRelayerFees>,
+    ) -> Result<()> {
+        instructions::claim_relayer_fees(ctx)
+    }
+
+    /// Withdraw a pool's accrued protocol fees to the treasury
+    pub fn withdraw_protocol_fees(
+        ctx: Context<WithdrawProtocolFees>,
+    ) -> Result<()> {
+        instructions::withdraw_protocol_fees(ctx)
+    }
+
+    /// Set the share of protocol fees set aside for the insurance fund (admin only)
+    pub fn set_insurance_share(
+        ctx: Context<SetInsuranceShare>,
+        insurance_share_bps: u16,
+    ) -> Result<()> {
+        instructions::set_insurance_share(ctx, insurance_share_bps)
+    }
+
+    /// Pay out of a pool's insurance fund (admin and risk authority)
+    pub fn payout_insurance(
+        ctx: Context<PayoutInsurance>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::payout_insurance(ctx, amount)
+    }
+
+    /// Compensate a mishandled order's owner out of the insurance fund, once
+    /// per execution receipt (admin and risk authority)
+    pub fn compensate_order(
+        ctx: Context<CompensateOrder>,
+        amount: u64,
+        reason: [u8; 32],
+    ) -> Result<()> {
+        instructions::compensate_order(ctx, amount, reason)
+    }
+
+    /// Register a relayer (admin only)
+    pub fn add_relayer(
+        ctx: Context<AddRelayer>,
+        relayer: Pubkey,
+    ) -> Result<()> {
+        instructions::add_relayer(ctx, relayer)
+    }
+
+    /// Deregister a relayer and close its info account (admin only)
+    pub fn remove_relayer(
+        ctx: Context<RemoveRelayer>,
+    ) -> Result<()> {
+        instructions::remove_relayer(ctx)
+    }
+
+    /// Record that a relayer is alive
+    pub fn relayer_heartbeat(
+        ctx: Context<RelayerHeartbeat>,
+    ) -> Result<()> {
+        instructions::relayer_heartbeat(ctx)
+    }
+
+    /// Post the fee a registered relayer charges, applied up to each pool's cap
+    pub fn set_relayer_fee(
+        ctx: Context<SetRelayerFee>,
+        fee_bps: u16,
+    ) -> Result<()> {
+        instructions::set_relayer_fee(ctx, fee_bps)
+    }
+
+    /// Cap the relayer fee on a pool, letting relayers post their own (admin only)
+    pub fn set_relayer_fee_cap(
+        ctx: Context<SetRelayerFeeCap>,
+        max_relayer_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::set_relayer_fee_cap(ctx, max_relayer_fee_bps)
+    }
+
+    /// Create the volume-tiered protocol fee schedule (admin only)
+    pub fn initialize_fee_schedule(
+        ctx: Context<InitializeFeeSchedule>,
+        tiers: Vec<FeeTier>,
+    ) -> Result<()> {
+        instructions::initialize_fee_schedule(ctx, tiers)
+    }
+
+    /// Replace the volume-tiered protocol fee schedule (admin only)
+    pub fn set_fee_schedule(
+        ctx: Context<SetFeeSchedule>,
+        tiers: Vec<FeeTier>,
+    ) -> Result<()> {
+        instructions::set_fee_schedule(ctx, tiers)
+    }
+
+    /// Start tracking a user's volume on a pool for fee tiers
+    pub fn open_user_volume(
+        ctx: Context<OpenUserVolume>,
+        user: Pubkey,
+    ) -> Result<()> {
+        instructions::open_user_volume(ctx, user)
+    }
+
+    /// Restrict a pool to a set of relayers (admin only)
+    pub fn initialize_pool_relayers(
+        ctx: Context<InitializePoolRelayers>,
+        relayers: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::initialize_pool_relayers(ctx, relayers)
+    }
+
+    /// Replace the relayers assigned to a pool; an empty list opens it to all (admin only)
+    pub fn set_pool_relayers(
+        ctx: Context<SetPoolRelayers>,
+        relayers: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_pool_relayers(ctx, relayers)
+    }
+
+    /// Set the key that must co-sign pool registrations (admin and current risk authority)
+    pub fn set_risk_authority(
+        ctx: Context<SetRiskAuthority>,
+        risk_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::set_risk_authority(ctx, risk_authority)
+    }
+
+    /// Set the CP-Swap program executions and pool registrations CPI into (admin and risk authority)
+    pub fn set_cp_swap_program(
+        ctx: Context<SetCpSwapProgram>,
+        cp_swap_program: Pubkey,
+    ) -> Result<()> {
+        instructions::set_cp_swap_program(ctx, cp_swap_program)
+    }
+
+    /// Cap immediate swaps per registered relayer per sliding window; 0 lifts the cap (admin only)
+    pub fn set_swap_rate_limit(
+        ctx: Context<SetSwapRateLimit>,
+        max_swaps: u32,
+        window_secs: u32,
+    ) -> Result<()> {
+        instructions::set_swap_rate_limit(ctx, max_swaps, window_secs)
+    }
+
+    /// Let any signer execute queued orders, or only registered relayers (admin only)
+    pub fn set_permissionless_execution(
+        ctx: Context<SetPermissionlessExecution>,
+        permissionless: bool,
+    ) -> Result<()> {
+        instructions::set_permissionless_execution(ctx, permissionless)
+    }
+
+    /// Put a relayer on an execution quota per period; 0 leaves a limit off (admin only)
+    pub fn initialize_relayer_quota(
+        ctx: Context<InitializeRelayerQuota>,
+        relayer: Pubkey,
+        max_executions: u32,
+        max_volume: u64,
+        period: i64,
+    ) -> Result<()> {
+        instructions::initialize_relayer_quota(ctx, relayer, max_executions, max_volume, period)
+    }
+
+    /// Change a relayer's execution quota (admin only)
+    pub fn set_relayer_quota(
+        ctx: Context<SetRelayerQuota>,
+        max_executions: u32,
+        max_volume: u64,
+        period: i64,
+    ) -> Result<()> {
+        instructions::set_relayer_quota(ctx, max_executions, max_volume, period)
+    }
+
+    /// Permissionless crank: reset a relayer's quota counters once its period has ended
+    pub fn reset_relayer_quota(
+        ctx: Context<ResetRelayerQuota>,
+    ) -> Result<()> {
+        instructions::reset_relayer_quota(ctx)
+    }
+
+    /// Set the program vetting submissions on a pool; the default pubkey removes it (admin only)
+    pub fn set_compliance_program(
+        ctx: Context<SetComplianceProgram>,
+        compliance_program: Pubkey,
+    ) -> Result<()> {
+        instructions::set_compliance_program(ctx, compliance_program)
+    }
+
+    /// Record the address lookup table of a pool's static execution
+    /// accounts; the default pubkey removes it (admin only)
+    pub fn set_pool_lookup_table(
+        ctx: Context<SetPoolLookupTable>,
+        lookup_table: Pubkey,
+    ) -> Result<()> {
+        instructions::set_pool_lookup_table(ctx, lookup_table)
+    }
+
+    /// Create the vault swap-and-burn and swap-and-stake output passes through for a mint
+    pub fn initialize_post_fill_vault(
+        ctx: Context<InitializePostFillVault>,
+    ) -> Result<()> {
+        instructions::initialize_post_fill_vault(ctx)
+    }
+
+    /// Allow swap-and-stake orders to deposit into a staking program (admin only)
+    pub fn approve_stake_program(
+        ctx: Context<ApproveStakeProgram>,
+        program: Pubkey,
+    ) -> Result<()> {
+        instructions::approve_stake_program(ctx, program)
+    }
+
+    /// Stop swap-and-stake orders depositing into a staking program (admin only)
+    pub fn revoke_stake_program(
+        ctx: Context<RevokeStakeProgram>,
+    ) -> Result<()> {
+        instructions::revoke_stake_program(ctx)
+    }
+
+    /// Create a repeating order funded by `deposit` into the template's escrow
+    pub fn create_order_template(
+        ctx: Context<CreateOrderTemplate>,
+        template_id: u64,
+        params: OrderTemplateParams,
+        deposit: u64,
+    ) -> Result<()> {
+        instructions::create_order_template(ctx, template_id, params, deposit)
+    }
+
+    /// Enqueue a template's next occurrence once it is due (permissionless)
+    pub fn materialize_order_template(
+        ctx: Context<MaterializeOrderTemplate>,
+    ) -> Result<()> {
+        instructions::materialize_order_template(ctx)
+    }
+
+    /// Stop a template and refund its escrow
+    pub fn close_order_template(
+        ctx: Context<CloseOrderTemplate>,
+    ) -> Result<()> {
+        instructions::close_order_template(ctx)
+    }
+
+    /// Create a recurring swap of `params.amount_per_tick` funded by `budget`
+    /// into the schedule's escrow
+    pub fn create_dca_schedule(
+        ctx: Context<CreateDcaSchedule>,
+        schedule_id: u64,
+        params: DcaScheduleParams,
+        budget: u64,
+    ) -> Result<()> {
+        instructions::create_dca_schedule(ctx, schedule_id, params, budget)
+    }
+
+    /// Swap a DCA schedule's tick once it is due (any relayer the pool allows)
+    pub fn execute_dca_tick<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteDcaTick<'info>>,
+    ) -> Result<()> {
+        instructions::execute_dca_tick(ctx)
+    }
+
+    /// Stop a DCA schedule and refund its budget
+    pub fn close_dca_schedule(
+        ctx: Context<CloseDcaSchedule>,
+    ) -> Result<()> {
+        instructions::close_dca_schedule(ctx)
+    }
+
+    /// Create an order enqueued in tranches of `tranche_in`, funded by
+    /// `deposit` into the iceberg's escrow
+    pub fn create_iceberg(
+        ctx: Context<CreateIceberg>,
+        iceberg_id: u64,
+        tranche_in: u64,
+        tranche_min_out: u64,
+        deposit: u64,
+    ) -> Result<()> {
+        instructions::create_iceberg(ctx, iceberg_id, tranche_in, tranche_min_out, deposit)
+    }
+
+    /// Enqueue an iceberg's next tranche once the last has executed (permissionless)
+    pub fn rearm_iceberg(
+        ctx: Context<RearmIceberg>,
+    ) -> Result<()> {
+        instructions::rearm_iceberg(ctx)
+    }
+
+    /// Stop an iceberg and refund its escrow
+    pub fn close_iceberg(
+        ctx: Context<CloseIceberg>,
+    ) -> Result<()> {
+        instructions::close_iceberg(ctx)
+    }
+
+    /// Ratchet a pending order's trailing stop with its oracle's price (permissionless)
+    pub fn update_trail(
+        ctx: Context<UpdateTrail>,
+    ) -> Result<()> {
+        instructions::update_trail(ctx)
+    }
+
+    /// Open the signer's shared escrow for a mint, which orders on any pool can spend from
+    pub fn open_shared_escrow(
+        ctx: Context<OpenSharedEscrow>,
+    ) -> Result<()> {
+        instructions::open_shared_escrow(ctx)
+    }
+
+    /// Move tokens into the signer's shared escrow
+    pub fn deposit_shared_escrow(
+        ctx: Context<DepositSharedEscrow>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::deposit_shared_escrow(ctx, amount)
+    }
+
+    /// Move tokens no pending order reserves out of the signer's shared escrow
+    pub fn withdraw_shared_escrow(
+        ctx: Context<WithdrawSharedEscrow>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_shared_escrow(ctx, amount)
+    }
+
+    /// Close a shared escrow no pending order reserves from and refund it
+    pub fn close_shared_escrow(
+        ctx: Context<CloseSharedEscrow>,
+    ) -> Result<()> {
+        instructions::close_shared_escrow(ctx)
+    }
+
+    /// Let a delegate submit and cancel the signer's orders; programs approve
+    /// for their PDAs by CPI
+    pub fn approve_user_delegation(
+        ctx: Context<ApproveUserDelegation>,
+        max_amount_in: u64,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        instructions::approve_user_delegation(ctx, max_amount_in, expires_at)
+    }
+
+    /// Withdraw a delegation
+    pub fn revoke_user_delegation(
+        ctx: Context<RevokeUserDelegation>,
+    ) -> Result<()> {
+        instructions::revoke_user_delegation(ctx)
+    }
+
+    /// Submit an order for a user who delegated to the signer
+    pub fn submit_order_as_delegate(
+        ctx: Context<SubmitOrderAsDelegate>,
+        amount_in: u64,
+        min_amount_out: u64,
+        is_base_input: bool,
+    ) -> Result<()> {
+        instructions::submit_order_as_delegate(ctx, amount_in, min_amount_out, is_base_input)
+    }
+
+    /// Cancel a pending order of a user who delegated to the signer
+    pub fn cancel_order_as_delegate(
+        ctx: Context<CancelOrderAsDelegate>,
+    ) -> Result<()> {
+        instructions::cancel_order_as_delegate(ctx)
+    }
+
+    /// Approve a permit for one of the signer's token accounts, which orders
+    /// then spend through up to an allowance without approving each time
+    pub fn create_permit(
+        ctx: Context<CreatePermit>,
+        allowance: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::create_permit(ctx, allowance, expires_at)
+    }
+
+    /// Change a permit's allowance and expiry
+    pub fn update_permit(
+        ctx: Context<UpdatePermit>,
+        allowance: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::update_permit(ctx, allowance, expires_at)
+    }
+
+    /// Close a permit and revoke its approval
+    pub fn close_permit(
+        ctx: Context<ClosePermit>,
+    ) -> Result<()> {
+        instructions::close_permit(ctx)
+    }
+
+    /// Create an organization owned by the signer
+    pub fn create_organization(
+        ctx: Context<CreateOrganization>,
+        org_id: u64,
+    ) -> Result<()> {
+        instructions::create_organization(ctx, org_id)
+    }
+
+    /// Add or update an organization member's limits (owner only)
+    pub fn set_org_member(
+        ctx: Context<SetOrgMember>,
+        member: Pubkey,
+        max_amount_in: u64,
+        pools: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_org_member(ctx, member, max_amount_in, pools)
+    }
+
+    /// Remove an organization member (owner only)
+    pub fn remove_org_member(
+        ctx: Context<RemoveOrgMember>,
+        member: Pubkey,
+    ) -> Result<()> {
+        instructions::remove_org_member(ctx, member)
+    }
+
+    /// Open an organization's shared escrow for a mint (owner only)
+    pub fn open_org_escrow(
+        ctx: Context<OpenOrgEscrow>,
+    ) -> Result<()> {
+        instructions::open_org_escrow(ctx)
+    }
+
+    /// Move unreserved tokens out of an organization's escrow (owner only)
+    pub fn withdraw_org_escrow(
+        ctx: Context<WithdrawOrgEscrow>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_org_escrow(ctx, amount)
+    }
+
+    /// Submit an order for an organization as a member, within their limits
+    pub fn submit_org_order(
+        ctx: Context<SubmitOrgOrder>,
+        amount_in: u64,
+        min_amount_out: u64,
+        is_base_input: bool,
+    ) -> Result<()> {
+        instructions::submit_org_order(ctx, amount_in, min_amount_out, is_base_input)
+    }
+
+    /// Cancel a pending organization order as its owner or a member
+    pub fn cancel_org_order(
+        ctx: Context<CancelOrgOrder>,
+    ) -> Result<()> {
+        instructions::cancel_org_order(ctx)
+    }
+
+    /// Trust a Continuum emitter on another Wormhole chain (admin only)
+    pub fn register_foreign_emitter(
+        ctx: Context<RegisterForeignEmitter>,
+        chain: u16,
+        address: [u8; 32],
+    ) -> Result<()> {
+        instructions::register_foreign_emitter(ctx, chain, address)
+    }
+
+    /// Create the custody account for a bridged mint (admin only)
+    pub fn initialize_custody(
+        ctx: Context<InitializeCustody>,
+    ) -> Result<()> {
+        instructions::initialize_custody(ctx)
+    }
+
+    /// Enqueue an order carried by a verified Wormhole VAA, funded by the
+    /// token bridge transfer it names
+    pub fn submit_order_from_vaa(
+        ctx: Context<SubmitOrderFromVaa>,
+        emitter_chain: u16,
+        vaa_sequence: u64,
+    ) -> Result<()> {
+        instructions::submit_order_from_vaa(ctx, emitter_chain, vaa_sequence)
+    }
+
+    /// Publish a Wormhole message attesting to an executed order
+    pub fn attest_fill(
+        ctx: Context<AttestFill>,
+    ) -> Result<()> {
+        instructions::attest_fill(ctx)
+    }
+
+    /// Pause or resume order submission and immediate swaps, on every pool
+    /// or only on the pool whose registry is passed, recording `reason` in
+    /// the event
+    pub fn set_pause(
+        ctx: Context<SetPause>,
+        paused: bool,
+        reason: Option<String>,
+    ) -> Result<()> {
+        instructions::set_pause(ctx, paused, reason)
+    }
+
+    /// Submit an order signed off-chain by its owner, paid for by the caller
+    pub fn submit_order_intent(
+        ctx: Context<SubmitOrderIntent>,
+        intent: OrderIntent,
+    ) -> Result<()> {
+        instructions::submit_order_intent(ctx, intent)
+    }
+
+    /// Have the user's future orders settle in submission order
+    pub fn enable_user_ordering(
+        ctx: Context<EnableUserOrdering>,
+    ) -> Result<()> {
+        instructions::enable_user_ordering(ctx)
+    }
+
+    /// Leave per-user ordering and close its account
+    pub fn disable_user_ordering(
+        ctx: Context<DisableUserOrdering>,
+    ) -> Result<()> {
+        instructions::disable_user_ordering(ctx)
+    }
+
+    /// Create the mock clock overriding order timestamps (`test-clock` builds only)
+    #[cfg(feature = "test-clock")]
+    pub fn initialize_mock_clock(
+        ctx: Context<InitializeMockClock>,
+        unix_timestamp: Option<i64>,
+    ) -> Result<()> {
+        instructions::initialize_mock_clock(ctx, unix_timestamp)
+    }
+
+    /// Set or clear the mock clock's timestamp (`test-clock` builds only)
+    #[cfg(feature = "test-clock")]
+    pub fn set_mock_clock(
+        ctx: Context<SetMockClock>,
+        unix_timestamp: Option<i64>,
+    ) -> Result<()> {
+        instructions::set_mock_clock(ctx, unix_timestamp)
+    }
+}
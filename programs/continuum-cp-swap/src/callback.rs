@@ -0,0 +1,44 @@
+//! Post-fill callbacks. An order may name a program Continuum invokes once
+//! the order has filled, with a [`FillNotification`] as instruction data
+//! after [`ON_FILL_DISCRIMINATOR`] and the order account as its only,
+//! read-only, account. The callee should check that the account is owned by
+//! Continuum and executed before trusting the notification, since anyone can
+//! invoke it directly.
+//!
+//! A failing callback fails the execution, which only holds up the order
+//! that named it; the relayer pays for the compute it uses.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+};
+
+use crate::consts::ON_FILL_DISCRIMINATOR;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct FillNotification {
+    pub sequence: u64,
+    pub user: Pubkey,
+    pub pool_id: Pubkey,
+    /// Input actually spent, before fees
+    pub amount_in: u64,
+    pub amount_out: u64,
+}
+
+/// Invoke `program` with `notification`, passing `order_state` read-only
+pub fn notify<'info>(
+    program: &AccountInfo<'info>,
+    order_state: &AccountInfo<'info>,
+    notification: &FillNotification,
+) -> Result<()> {
+    let mut data = ON_FILL_DISCRIMINATOR.to_vec();
+    notification.serialize(&mut data)?;
+    let ix = Instruction {
+        program_id: program.key(),
+        accounts: vec![AccountMeta::new_readonly(order_state.key(), false)],
+        data,
+    };
+    invoke(&ix, &[order_state.clone(), program.clone()])?;
+    Ok(())
+}
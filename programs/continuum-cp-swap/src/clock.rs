@@ -0,0 +1,35 @@
+//! The current time as seen by the order lifecycle.
+//!
+//! Builds with the `test-clock` feature let the admin create a
+//! [`MockClock`](crate::state::MockClock). Passed among an instruction's
+//! remaining accounts, its timestamp replaces the cluster clock's, so
+//! expiries and other time-dependent paths can be tested deterministically
+//! in program-test without warping the validator clock. Never deploy a
+//! build with the feature enabled.
+
+use anchor_lang::prelude::*;
+
+/// Unix timestamp of `clock`, or of a set mock clock among
+/// `remaining_accounts` in `test-clock` builds
+pub fn unix_timestamp(clock: &Clock, remaining_accounts: &[AccountInfo]) -> i64 {
+    #[cfg(feature = "test-clock")]
+    if let Some(timestamp) = mock_timestamp(remaining_accounts) {
+        return timestamp;
+    }
+    #[cfg(not(feature = "test-clock"))]
+    let _ = remaining_accounts;
+    clock.unix_timestamp
+}
+
+/// Only the admin can create the single mock clock PDA, so any program-owned
+/// account holding one is it
+#[cfg(feature = "test-clock")]
+fn mock_timestamp(remaining_accounts: &[AccountInfo]) -> Option<i64> {
+    remaining_accounts
+        .iter()
+        .filter(|account| account.owner == &crate::ID)
+        .find_map(|account| {
+            let data = account.try_borrow_data().ok()?;
+            crate::state::MockClock::try_deserialize(&mut &data[..]).ok()?.unix_timestamp
+        })
+}
@@ -0,0 +1,45 @@
+//! Addresses and defaults that differ between clusters. Builds target devnet
+//! by default; exactly one of the `devnet` and `mainnet` features must be
+//! enabled.
+
+use anchor_lang::prelude::*;
+
+#[cfg(all(feature = "devnet", feature = "mainnet"))]
+compile_error!("the `devnet` and `mainnet` features are mutually exclusive");
+#[cfg(not(any(feature = "devnet", feature = "mainnet")))]
+compile_error!("enable the `devnet` or the `mainnet` feature to select a cluster");
+
+#[cfg(feature = "devnet")]
+pub const NAME: &str = "devnet";
+#[cfg(feature = "mainnet")]
+pub const NAME: &str = "mainnet-beta";
+
+/// Raydium CP-Swap program Continuum pools are deployed on
+#[cfg(feature = "devnet")]
+pub const CP_SWAP_PROGRAM_ID: Pubkey = pubkey!("GkenxCtvEabZrwFf15D3E6LjoZTywH2afNwiqDwthyDp");
+#[cfg(feature = "mainnet")]
+pub const CP_SWAP_PROGRAM_ID: Pubkey = pubkey!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+
+/// Wormhole core bridge cross-chain orders are verified by and fill
+/// attestations published through
+#[cfg(feature = "devnet")]
+pub const WORMHOLE_PROGRAM_ID: Pubkey = pubkey!("3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5");
+#[cfg(feature = "mainnet")]
+pub const WORMHOLE_PROGRAM_ID: Pubkey = pubkey!("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+
+/// Wormhole token bridge that redeems the transfers funding cross-chain
+/// orders into custody
+#[cfg(feature = "devnet")]
+pub const TOKEN_BRIDGE_PROGRAM_ID: Pubkey = pubkey!("DZnkkTmCiFWfYTfT41X3Rd1kDgozqzxWaHqsw6W4x2oe");
+#[cfg(feature = "mainnet")]
+pub const TOKEN_BRIDGE_PROGRAM_ID: Pubkey = pubkey!("wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb");
+
+/// Public RPC endpoints, used when no other is configured
+#[cfg(feature = "devnet")]
+pub const DEFAULT_RPC_URL: &str = "https://api.devnet.solana.com";
+#[cfg(feature = "devnet")]
+pub const DEFAULT_WS_URL: &str = "wss://api.devnet.solana.com";
+#[cfg(feature = "mainnet")]
+pub const DEFAULT_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
+#[cfg(feature = "mainnet")]
+pub const DEFAULT_WS_URL: &str = "wss://api.mainnet-beta.solana.com";
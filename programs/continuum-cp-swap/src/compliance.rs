@@ -0,0 +1,67 @@
+//! Pre-trade compliance checks. A pool may name a compliance program that
+//! every submission and immediate swap on it is put to; the program vetoes
+//! by failing, which fails the submission with it. It is invoked with a
+//! [`ComplianceQuery`] as instruction data after
+//! [`COMPLIANCE_CHECK_DISCRIMINATOR`] and one read-only account: the user's
+//! record under the compliance program, seeds ["compliance", user], which
+//! may be uninitialized for users it knows nothing about.
+//!
+//! Orders arriving over Wormhole are not queried, since a veto would strand
+//! their bridged funds in custody.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+};
+
+use crate::consts::{COMPLIANCE_CHECK_DISCRIMINATOR, COMPLIANCE_RECORD_SEED};
+use crate::errors::ContinuumError;
+use crate::state::CpSwapPoolRegistry;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ComplianceQuery {
+    pub user: Pubkey,
+    pub pool_id: Pubkey,
+    pub amount_in: u64,
+    pub is_base_input: bool,
+    /// Owner the output goes to, when it isn't the user
+    pub recipient: Option<Pubkey>,
+}
+
+/// Address of `user`'s record under `compliance_program`
+pub fn record_address(compliance_program: &Pubkey, user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[COMPLIANCE_RECORD_SEED, user.as_ref()], compliance_program).0
+}
+
+/// Put `query` to the pool's compliance program, if it has one. The program
+/// and the user's record must both be passed for such pools.
+pub fn check<'info>(
+    pool_registry: &CpSwapPoolRegistry,
+    program: Option<&AccountInfo<'info>>,
+    record: Option<&AccountInfo<'info>>,
+    query: &ComplianceQuery,
+) -> Result<()> {
+    let Some(compliance_program) = pool_registry.compliance_program() else {
+        return Ok(());
+    };
+    let (Some(program), Some(record)) = (program, record) else {
+        return err!(ContinuumError::MissingComplianceCheck);
+    };
+    require_keys_eq!(program.key(), compliance_program, ContinuumError::MissingComplianceCheck);
+    require_keys_eq!(
+        record.key(),
+        record_address(&compliance_program, &query.user),
+        ContinuumError::MissingComplianceCheck
+    );
+
+    let mut data = COMPLIANCE_CHECK_DISCRIMINATOR.to_vec();
+    query.serialize(&mut data)?;
+    let ix = Instruction {
+        program_id: compliance_program,
+        accounts: vec![AccountMeta::new_readonly(record.key(), false)],
+        data,
+    };
+    invoke(&ix, &[record.clone(), program.clone()])?;
+    Ok(())
+}
@@ -0,0 +1,152 @@
+//! Building blocks for programs that route their swaps through Continuum,
+//! alongside the Anchor-generated [`cpi`](crate::cpi) module and its typed
+//! [`cpi::accounts`](crate::cpi::accounts) structs. Enabled by the `cpi`
+//! feature.
+//!
+//! A vault queueing an order for its PDA, which signs as the user:
+//!
+//! ```ignore
+//! // The uninitialized `order_state` account passed in must be at this address
+//! let order_address = compose::order_address(&pool_state.key(), pool_fifo.current_sequence);
+//! continuum_cp_swap::cpi::submit_order(
+//!     CpiContext::new_with_signer(
+//!         continuum_program.to_account_info(),
+//!         continuum_cp_swap::cpi::accounts::SubmitOrder {
+//!             fifo_state: fifo_state.to_account_info(),
+//!             pool_registry: pool_registry.to_account_info(),
+//!             pool_fifo: pool_fifo.to_account_info(),
+//!             order_state: order_state.to_account_info(),
+//!             user: vault.to_account_info(),
+//!             pool_id: pool_state.to_account_info(),
+//!             system_program: system_program.to_account_info(),
+//!             clock: clock.to_account_info(),
+//!             input_mint: input_mint.to_account_info(),
+//!         },
+//!         &[vault_seeds],
+//!     ),
+//!     amount_in,
+//!     min_amount_out,
+//!     true,
+//!     continuum_cp_swap::instructions::SubmitOrderParams {
+//!         from_wallet: true,
+//!         ..Default::default()
+//!     },
+//! )?;
+//! ```
+//!
+//! As with any user spending from its wallet, the vault approves the pool
+//! authority on its source token account before the order is executed. [`swap_immediate`] swaps
+//! in the same instruction instead.
+//!
+//! A vault that can't sign every submission itself, e.g. a treasury run by
+//! a keeper, instead signs `approve_user_delegation` once by CPI. The
+//! delegate then calls `submit_order_as_delegate` and
+//! `cancel_order_as_delegate` directly, within the delegation's limits, and
+//! the output of those orders always goes to the vault.
+
+use anchor_lang::prelude::*;
+
+use crate::consts::*;
+use crate::cpi;
+
+pub fn fifo_state_address() -> Pubkey {
+    Pubkey::find_program_address(&[FIFO_STATE_SEED], &crate::ID).0
+}
+
+pub fn pool_registry_address(pool_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[POOL_REGISTRY_SEED, pool_id.as_ref()], &crate::ID).0
+}
+
+/// Continuum's authority over a CP-Swap pool, and its bump
+pub fn pool_authority(pool_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_AUTHORITY_SEED, pool_id.as_ref()], &crate::ID)
+}
+
+/// Queue of the orders on `pool_id`
+pub fn pool_fifo_address(pool_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[POOL_FIFO_SEED, pool_id.as_ref()], &crate::ID).0
+}
+
+/// Account a `submit_order` on `pool_id` creates. `current_sequence` is
+/// `PoolFifoState::current_sequence` of the pool read before the
+/// submission; the account is derived from the sequence after it, which the
+/// order gets.
+pub fn order_address(pool_id: &Pubkey, current_sequence: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[ORDER_SEED, pool_id.as_ref(), &(current_sequence + 1).to_le_bytes()],
+        &crate::ID,
+    )
+    .0
+}
+
+/// Delegation letting `delegate` submit orders for `user`
+pub fn user_delegation_address(user: &Pubkey, delegate: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[USER_DELEGATION_SEED, user.as_ref(), delegate.as_ref()], &crate::ID).0
+}
+
+/// Registration of `relayer`, which `swap_immediate` requires of its user
+pub fn relayer_info_address(relayer: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[RELAYER_SEED, relayer.as_ref()], &crate::ID).0
+}
+
+/// CP-Swap accounts of an immediate swap, in the order `swap_immediate`
+/// passes them through. `user` must sign.
+pub struct SwapAccounts<'info> {
+    pub user: AccountInfo<'info>,
+    /// CP-Swap vault authority
+    pub authority: AccountInfo<'info>,
+    pub amm_config: AccountInfo<'info>,
+    pub pool_state: AccountInfo<'info>,
+    pub input_token_account: AccountInfo<'info>,
+    pub output_token_account: AccountInfo<'info>,
+    pub input_vault: AccountInfo<'info>,
+    pub output_vault: AccountInfo<'info>,
+    pub input_token_program: AccountInfo<'info>,
+    pub output_token_program: AccountInfo<'info>,
+    pub input_mint: AccountInfo<'info>,
+    pub output_mint: AccountInfo<'info>,
+    pub observation_state: AccountInfo<'info>,
+}
+
+impl<'info> SwapAccounts<'info> {
+    pub fn into_remaining_accounts(self) -> Vec<AccountInfo<'info>> {
+        vec![
+            self.user,
+            self.authority,
+            self.amm_config,
+            self.pool_state,
+            self.input_token_account,
+            self.output_token_account,
+            self.input_vault,
+            self.output_vault,
+            self.input_token_program,
+            self.output_token_program,
+            self.input_mint,
+            self.output_mint,
+            self.observation_state,
+        ]
+    }
+}
+
+/// Sequence and execute a swap in one CPI, filling in the pool id and pool
+/// authority bump `swap_immediate` expects from `swap`
+pub fn swap_immediate<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, cpi::accounts::SwapImmediate<'info>>,
+    swap: SwapAccounts<'info>,
+    amount_in: u64,
+    min_amount_out: u64,
+    is_base_input: bool,
+    deadline: Option<i64>,
+) -> Result<()> {
+    let pool_id = swap.pool_state.key();
+    let (_, pool_authority_bump) = pool_authority(&pool_id);
+    cpi::swap_immediate(
+        ctx.with_remaining_accounts(swap.into_remaining_accounts()),
+        amount_in,
+        min_amount_out,
+        is_base_input,
+        pool_id,
+        pool_authority_bump,
+        deadline,
+    )
+}
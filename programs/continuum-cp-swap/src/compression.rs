@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use crate::consts::*;
+
+/// SPL Account Compression program
+pub const ACCOUNT_COMPRESSION_PROGRAM_ID: Pubkey = pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+
+/// SPL Noop program, used by account compression to log changelogs
+pub const NOOP_PROGRAM_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+/// Build the account compression `init_empty_merkle_tree` instruction
+pub fn init_empty_merkle_tree_ix(
+    merkle_tree: Pubkey,
+    authority: Pubkey,
+    max_depth: u32,
+    max_buffer_size: u32,
+) -> Instruction {
+    let mut data = Vec::new();
+    data.extend_from_slice(&INIT_EMPTY_MERKLE_TREE_DISCRIMINATOR);
+    data.extend_from_slice(&max_depth.to_le_bytes());
+    data.extend_from_slice(&max_buffer_size.to_le_bytes());
+
+    Instruction {
+        program_id: ACCOUNT_COMPRESSION_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(merkle_tree, false),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new_readonly(NOOP_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+/// Build the account compression `append` instruction
+pub fn append_ix(merkle_tree: Pubkey, authority: Pubkey, leaf: [u8; 32]) -> Instruction {
+    let mut data = Vec::new();
+    data.extend_from_slice(&APPEND_DISCRIMINATOR);
+    data.extend_from_slice(&leaf);
+
+    Instruction {
+        program_id: ACCOUNT_COMPRESSION_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(merkle_tree, false),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new_readonly(NOOP_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+/// Build the account compression `verify_leaf` instruction.
+/// Proof nodes are appended as read-only accounts, leaf to root.
+pub fn verify_leaf_ix(
+    merkle_tree: Pubkey,
+    root: [u8; 32],
+    leaf: [u8; 32],
+    index: u32,
+    proof: &[AccountInfo],
+) -> Instruction {
+    let mut data = Vec::new();
+    data.extend_from_slice(&VERIFY_LEAF_DISCRIMINATOR);
+    data.extend_from_slice(&root);
+    data.extend_from_slice(&leaf);
+    data.extend_from_slice(&index.to_le_bytes());
+
+    let mut accounts = vec![AccountMeta::new_readonly(merkle_tree, false)];
+    for node in proof.iter() {
+        accounts.push(AccountMeta::new_readonly(node.key(), false));
+    }
+
+    Instruction {
+        program_id: ACCOUNT_COMPRESSION_PROGRAM_ID,
+        accounts,
+        data,
+    }
+}
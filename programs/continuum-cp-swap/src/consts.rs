@@ -0,0 +1,106 @@
+//! Canonical seeds, discriminators and account sizes. Integrators linking
+//! the program crate (with `no-entrypoint` or `cpi`) should use these rather
+//! than copying the byte strings.
+
+use crate::state::*;
+
+// PDA seeds
+pub const FIFO_STATE_SEED: &[u8] = b"fifo_state";
+pub const POOL_REGISTRY_SEED: &[u8] = b"pool_registry";
+pub const POOL_AUTHORITY_SEED: &[u8] = b"cp_pool_authority";
+pub const ORDER_SEED: &[u8] = b"order";
+pub const RECEIPT_SEED: &[u8] = b"receipt";
+pub const RELAYER_SEED: &[u8] = b"relayer";
+pub const FEE_LEDGER_SEED: &[u8] = b"fee_ledger";
+pub const FEE_VAULT_SEED: &[u8] = b"fee_vault";
+pub const RELAYER_FEES_SEED: &[u8] = b"relayer_fees";
+pub const INTENT_SEED: &[u8] = b"intent";
+pub const MOCK_CLOCK_SEED: &[u8] = b"mock_clock";
+pub const USER_ORDERING_SEED: &[u8] = b"user_ordering";
+pub const FEE_SCHEDULE_SEED: &[u8] = b"fee_schedule";
+pub const USER_VOLUME_SEED: &[u8] = b"user_volume";
+pub const POOL_RELAYERS_SEED: &[u8] = b"pool_relayers";
+pub const POOL_FIFO_SEED: &[u8] = b"pool_fifo";
+pub const ARCHIVE_AUTHORITY_SEED: &[u8] = b"archive_authority";
+pub const CUSTODY_SEED: &[u8] = b"custody";
+pub const CUSTODY_AUTHORITY_SEED: &[u8] = b"custody_authority";
+pub const ESCROW_SEED: &[u8] = b"escrow";
+pub const ORDER_VAULT_SEED: &[u8] = b"order_vault";
+pub const FOREIGN_EMITTER_SEED: &[u8] = b"foreign_emitter";
+pub const RECEIVED_SEED: &[u8] = b"received";
+pub const REDEEMED_TRANSFER_SEED: &[u8] = b"redeemed_transfer";
+pub const EMITTER_SEED: &[u8] = b"emitter";
+pub const FEE_COLLECTOR_SEED: &[u8] = b"fee_collector";
+pub const FILL_ATTESTATION_SEED: &[u8] = b"fill_attestation";
+/// User records under a pool's compliance program, see `crate::compliance`
+pub const COMPLIANCE_RECORD_SEED: &[u8] = b"compliance";
+pub const POST_FILL_VAULT_SEED: &[u8] = b"post_fill_vault";
+pub const POST_FILL_AUTHORITY_SEED: &[u8] = b"post_fill_authority";
+pub const ORDER_TEMPLATE_SEED: &[u8] = b"order_template";
+pub const TEMPLATE_ESCROW_SEED: &[u8] = b"template_escrow";
+pub const DCA_SCHEDULE_SEED: &[u8] = b"dca_schedule";
+pub const DCA_ESCROW_SEED: &[u8] = b"dca_escrow";
+pub const ICEBERG_SEED: &[u8] = b"iceberg";
+pub const ICEBERG_ESCROW_SEED: &[u8] = b"iceberg_escrow";
+pub const SHARED_ESCROW_SEED: &[u8] = b"shared_escrow";
+pub const SHARED_ESCROW_VAULT_SEED: &[u8] = b"shared_escrow_vault";
+pub const USER_DELEGATION_SEED: &[u8] = b"user_delegation";
+pub const PERMIT_SEED: &[u8] = b"permit";
+pub const RELAYER_QUOTA_SEED: &[u8] = b"relayer_quota";
+pub const COMPENSATION_SEED: &[u8] = b"compensation";
+pub const ORGANIZATION_SEED: &[u8] = b"organization";
+pub const STAKE_PROGRAM_SEED: &[u8] = b"stake_program";
+/// Deposit accounts under a staking program, see `crate::post_fill`
+pub const STAKE_DEPOSIT_SEED: &[u8] = b"stake_deposit";
+
+/// Longest reason `set_pause` accepts, in bytes
+pub const MAX_PAUSE_REASON_LEN: usize = 200;
+/// Longest memo an order can carry, in bytes
+pub const MAX_ORDER_MEMO_LEN: usize = 64;
+/// Seconds a pending order may stay next in its pool's line before anyone
+/// can skip it, so an order that can never fill can't hold up the pool
+pub const HEAD_GRACE_PERIOD: i64 = 60 * 60;
+
+// Instruction a callback program receives after a fill, sha256("global:on_fill")[..8]
+pub const ON_FILL_DISCRIMINATOR: [u8; 8] = [133, 76, 152, 104, 24, 119, 244, 223];
+
+// Instruction a compliance program receives for each submission, sha256("global:check_order")[..8]
+pub const COMPLIANCE_CHECK_DISCRIMINATOR: [u8; 8] = [231, 212, 129, 80, 0, 41, 193, 197];
+
+// Instruction a whitelisted staking program receives for a swap-and-stake, sha256("global:deposit_for")[..8]
+pub const STAKE_DEPOSIT_DISCRIMINATOR: [u8; 8] = [193, 39, 228, 88, 160, 254, 92, 53];
+
+// SPL Account Compression instruction discriminators
+pub const INIT_EMPTY_MERKLE_TREE_DISCRIMINATOR: [u8; 8] = [191, 11, 119, 7, 180, 107, 220, 110];
+pub const APPEND_DISCRIMINATOR: [u8; 8] = [149, 120, 18, 222, 236, 225, 88, 203];
+pub const VERIFY_LEAF_DISCRIMINATOR: [u8; 8] = [124, 220, 22, 223, 104, 10, 250, 224];
+
+// Fixed-point scale of the realized price in `OrderExecuted` and of pool TWAPs
+pub const PRICE_SCALE: u64 = 1_000_000_000;
+
+// Account sizes, discriminator included
+pub const FIFO_STATE_LEN: usize = FifoState::LEN;
+pub const POOL_REGISTRY_LEN: usize = CpSwapPoolRegistry::LEN;
+pub const POOL_FEE_LEDGER_LEN: usize = PoolFeeLedger::LEN;
+pub const RELAYER_FEE_BALANCE_LEN: usize = RelayerFeeBalance::LEN;
+pub const ORDER_STATE_LEN: usize = OrderState::LEN;
+pub const RELAYER_INFO_LEN: usize = RelayerInfo::LEN;
+pub const FOREIGN_EMITTER_LEN: usize = ForeignEmitter::LEN;
+pub const RECEIVED_VAA_LEN: usize = ReceivedVaa::LEN;
+pub const USED_INTENT_LEN: usize = UsedIntent::LEN;
+pub const MOCK_CLOCK_LEN: usize = MockClock::LEN;
+pub const USER_ORDERING_LEN: usize = UserOrdering::LEN;
+pub const FEE_SCHEDULE_LEN: usize = FeeSchedule::LEN;
+pub const USER_VOLUME_LEN: usize = UserVolume::LEN;
+pub const POOL_RELAYERS_LEN: usize = PoolRelayers::LEN;
+pub const EXECUTION_RECEIPT_LEN: usize = ExecutionReceipt::LEN;
+pub const ORDER_TEMPLATE_LEN: usize = OrderTemplate::LEN;
+pub const DCA_SCHEDULE_LEN: usize = DcaSchedule::LEN;
+pub const ICEBERG_LEN: usize = Iceberg::LEN;
+pub const SHARED_ESCROW_LEN: usize = SharedEscrow::LEN;
+pub const USER_DELEGATION_LEN: usize = UserDelegation::LEN;
+pub const PERMIT_LEN: usize = Permit::LEN;
+pub const RELAYER_QUOTA_LEN: usize = RelayerQuota::LEN;
+pub const COMPENSATION_LEN: usize = Compensation::LEN;
+pub const ORGANIZATION_LEN: usize = Organization::LEN;
+pub const APPROVED_STAKE_PROGRAM_LEN: usize = ApprovedStakeProgram::LEN;
@@ -0,0 +1,405 @@
+//! Typed interface to the CP-Swap program Continuum pools run on, Raydium
+//! CP-Swap with custom pool authorities.
+//!
+//! Each instruction has its arguments as an `InstructionData` type and its
+//! accounts as a struct that puts them in the order CP-Swap declares them,
+//! with CP-Swap's signer and writable flags, so callers never hand-build
+//! instruction data or forward account lists as they came. Accounts a
+//! client passes as remaining accounts are read into these structs by
+//! position, in the same order.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use anchor_lang::{Discriminator, InstructionData};
+
+use crate::errors::ContinuumError;
+use crate::state::CpSwapPoolRegistry;
+
+/// `authority_type` of a pool whose swaps must be signed by its custom authority
+pub const CUSTOM_AUTHORITY: u8 = 1;
+
+/// Arguments of `initialize`
+#[derive(AnchorSerialize)]
+pub struct InitializeArgs {
+    pub init_amount_0: u64,
+    pub init_amount_1: u64,
+    pub open_time: u64,
+    pub authority_type: u8,
+    pub custom_authority: Option<Pubkey>,
+}
+
+/// Arguments of `swap_base_input`
+#[derive(AnchorSerialize)]
+pub struct SwapBaseInputArgs {
+    pub amount_in: u64,
+    pub minimum_amount_out: u64,
+}
+
+/// Arguments of `swap_base_output`
+#[derive(AnchorSerialize)]
+pub struct SwapBaseOutputArgs {
+    pub max_amount_in: u64,
+    pub amount_out: u64,
+}
+
+// sha256("global:<instruction name>")[..8], as for any Anchor program
+impl Discriminator for InitializeArgs {
+    const DISCRIMINATOR: &'static [u8] = &[175, 175, 109, 31, 13, 152, 155, 237];
+}
+impl Discriminator for SwapBaseInputArgs {
+    const DISCRIMINATOR: &'static [u8] = &[143, 190, 90, 218, 196, 30, 51, 222];
+}
+impl Discriminator for SwapBaseOutputArgs {
+    const DISCRIMINATOR: &'static [u8] = &[55, 217, 98, 86, 163, 74, 180, 173];
+}
+impl InstructionData for InitializeArgs {}
+impl InstructionData for SwapBaseInputArgs {}
+impl InstructionData for SwapBaseOutputArgs {}
+
+/// Accounts a CP-Swap pool state records, read from its data at the offsets
+/// of CP-Swap's `PoolState`
+pub struct PoolStateAccounts {
+    pub amm_config: Pubkey,
+    pub token_0_vault: Pubkey,
+    pub token_1_vault: Pubkey,
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+    pub observation_state: Pubkey,
+}
+
+impl PoolStateAccounts {
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let pubkey_at = |offset: usize| {
+            data.get(offset..offset + 32)
+                .map(|bytes| Pubkey::try_from(bytes).unwrap())
+                .ok_or(ContinuumError::InvalidRemainingAccounts)
+        };
+        Ok(Self {
+            amm_config: pubkey_at(8)?,
+            token_0_vault: pubkey_at(72)?,
+            token_1_vault: pubkey_at(104)?,
+            token_0_mint: pubkey_at(168)?,
+            token_1_mint: pubkey_at(200)?,
+            observation_state: pubkey_at(296)?,
+        })
+    }
+}
+
+/// Accounts of `initialize`
+pub struct Initialize<'info> {
+    pub creator: AccountInfo<'info>,
+    pub amm_config: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub pool_state: AccountInfo<'info>,
+    pub token_0_mint: AccountInfo<'info>,
+    pub token_1_mint: AccountInfo<'info>,
+    pub lp_mint: AccountInfo<'info>,
+    pub creator_token_0: AccountInfo<'info>,
+    pub creator_token_1: AccountInfo<'info>,
+    pub creator_lp_token: AccountInfo<'info>,
+    pub token_0_vault: AccountInfo<'info>,
+    pub token_1_vault: AccountInfo<'info>,
+    pub create_pool_fee: AccountInfo<'info>,
+    pub observation_state: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub token_0_program: AccountInfo<'info>,
+    pub token_1_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+}
+
+impl<'info> Initialize<'info> {
+    /// The accounts at the start of `accounts`, in the order of the fields
+    pub fn parse(accounts: &[AccountInfo<'info>]) -> Result<Self> {
+        let [creator, amm_config, authority, pool_state, token_0_mint, token_1_mint, lp_mint, creator_token_0, creator_token_1, creator_lp_token, token_0_vault, token_1_vault, create_pool_fee, observation_state, token_program, token_0_program, token_1_program, system_program, rent, ..] = accounts else {
+            return err!(ContinuumError::InvalidRemainingAccounts);
+        };
+        Ok(Self {
+            creator: creator.clone(),
+            amm_config: amm_config.clone(),
+            authority: authority.clone(),
+            pool_state: pool_state.clone(),
+            token_0_mint: token_0_mint.clone(),
+            token_1_mint: token_1_mint.clone(),
+            lp_mint: lp_mint.clone(),
+            creator_token_0: creator_token_0.clone(),
+            creator_token_1: creator_token_1.clone(),
+            creator_lp_token: creator_lp_token.clone(),
+            token_0_vault: token_0_vault.clone(),
+            token_1_vault: token_1_vault.clone(),
+            create_pool_fee: create_pool_fee.clone(),
+            observation_state: observation_state.clone(),
+            token_program: token_program.clone(),
+            token_0_program: token_0_program.clone(),
+            token_1_program: token_1_program.clone(),
+            system_program: system_program.clone(),
+            rent: rent.clone(),
+        })
+    }
+
+    fn to_account_metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.creator.key(), true),
+            AccountMeta::new_readonly(self.amm_config.key(), false),
+            AccountMeta::new_readonly(self.authority.key(), false),
+            AccountMeta::new(self.pool_state.key(), false),
+            AccountMeta::new_readonly(self.token_0_mint.key(), false),
+            AccountMeta::new_readonly(self.token_1_mint.key(), false),
+            AccountMeta::new(self.lp_mint.key(), false),
+            AccountMeta::new(self.creator_token_0.key(), false),
+            AccountMeta::new(self.creator_token_1.key(), false),
+            AccountMeta::new(self.creator_lp_token.key(), false),
+            AccountMeta::new(self.token_0_vault.key(), false),
+            AccountMeta::new(self.token_1_vault.key(), false),
+            AccountMeta::new(self.create_pool_fee.key(), false),
+            AccountMeta::new(self.observation_state.key(), false),
+            AccountMeta::new_readonly(self.token_program.key(), false),
+            AccountMeta::new_readonly(self.token_0_program.key(), false),
+            AccountMeta::new_readonly(self.token_1_program.key(), false),
+            AccountMeta::new_readonly(self.system_program.key(), false),
+            AccountMeta::new_readonly(self.rent.key(), false),
+        ]
+    }
+
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![
+            self.creator.clone(),
+            self.amm_config.clone(),
+            self.authority.clone(),
+            self.pool_state.clone(),
+            self.token_0_mint.clone(),
+            self.token_1_mint.clone(),
+            self.lp_mint.clone(),
+            self.creator_token_0.clone(),
+            self.creator_token_1.clone(),
+            self.creator_lp_token.clone(),
+            self.token_0_vault.clone(),
+            self.token_1_vault.clone(),
+            self.create_pool_fee.clone(),
+            self.observation_state.clone(),
+            self.token_program.clone(),
+            self.token_0_program.clone(),
+            self.token_1_program.clone(),
+            self.system_program.clone(),
+            self.rent.clone(),
+        ]
+    }
+}
+
+/// The pool's accounts of a swap: all of `swap_base_input` and
+/// `swap_base_output`'s accounts but the payer and its token accounts
+pub struct SwapPool<'info> {
+    /// CP-Swap vault authority
+    pub authority: AccountInfo<'info>,
+    pub amm_config: AccountInfo<'info>,
+    pub pool_state: AccountInfo<'info>,
+    pub input_vault: AccountInfo<'info>,
+    pub output_vault: AccountInfo<'info>,
+    pub input_token_program: AccountInfo<'info>,
+    pub output_token_program: AccountInfo<'info>,
+    pub input_token_mint: AccountInfo<'info>,
+    pub output_token_mint: AccountInfo<'info>,
+    pub observation_state: AccountInfo<'info>,
+}
+
+impl<'info> SwapPool<'info> {
+    /// Number of accounts the pool's accounts take
+    pub const LEN: usize = 10;
+
+    /// The accounts at the start of `accounts`, in the order of the fields
+    pub fn parse(accounts: &[AccountInfo<'info>]) -> Result<Self> {
+        let [authority, amm_config, pool_state, input_vault, output_vault, input_token_program, output_token_program, input_token_mint, output_token_mint, observation_state, ..] = accounts else {
+            return err!(ContinuumError::InvalidRemainingAccounts);
+        };
+        Ok(Self {
+            authority: authority.clone(),
+            amm_config: amm_config.clone(),
+            pool_state: pool_state.clone(),
+            input_vault: input_vault.clone(),
+            output_vault: output_vault.clone(),
+            input_token_program: input_token_program.clone(),
+            output_token_program: output_token_program.clone(),
+            input_token_mint: input_token_mint.clone(),
+            output_token_mint: output_token_mint.clone(),
+            observation_state: observation_state.clone(),
+        })
+    }
+
+    /// Whether these are the accounts `registry` recorded for its pool, with
+    /// each vault holding its mint's side
+    pub fn matches(&self, registry: &CpSwapPoolRegistry) -> bool {
+        let input_mint = self.input_token_mint.key();
+        let output_mint = self.output_token_mint.key();
+        input_mint != output_mint
+            && self.pool_state.key() == registry.pool_id
+            && self.amm_config.key() == registry.amm_config
+            && self.observation_state.key() == registry.observation_state
+            && registry.vault(&input_mint) == Some(self.input_vault.key())
+            && registry.vault(&output_mint) == Some(self.output_vault.key())
+    }
+
+    /// Whether the swap sells `mint`. With [`Self::matches`], the input vault
+    /// is then the pool's vault of `mint` and the output vault its other one.
+    pub fn sells(&self, mint: &Pubkey) -> bool {
+        self.input_token_mint.key() == *mint
+    }
+}
+
+/// Accounts of `swap_base_input` and `swap_base_output`
+pub struct Swap<'info> {
+    /// Owner or delegate of the input token account, signing the swap
+    pub payer: AccountInfo<'info>,
+    pub input_token_account: AccountInfo<'info>,
+    pub output_token_account: AccountInfo<'info>,
+    pub pool: SwapPool<'info>,
+}
+
+impl<'info> Swap<'info> {
+    /// The accounts at the start of `accounts`, in CP-Swap's order: payer,
+    /// authority, amm_config, pool_state, input_token_account,
+    /// output_token_account, then the rest of the pool's accounts
+    pub fn parse(accounts: &[AccountInfo<'info>]) -> Result<Self> {
+        let [payer, authority, amm_config, pool_state, input_token_account, output_token_account, rest @ ..] = accounts else {
+            return err!(ContinuumError::InvalidRemainingAccounts);
+        };
+        let [input_vault, output_vault, input_token_program, output_token_program, input_token_mint, output_token_mint, observation_state, ..] = rest else {
+            return err!(ContinuumError::InvalidRemainingAccounts);
+        };
+        Ok(Self {
+            payer: payer.clone(),
+            input_token_account: input_token_account.clone(),
+            output_token_account: output_token_account.clone(),
+            pool: SwapPool {
+                authority: authority.clone(),
+                amm_config: amm_config.clone(),
+                pool_state: pool_state.clone(),
+                input_vault: input_vault.clone(),
+                output_vault: output_vault.clone(),
+                input_token_program: input_token_program.clone(),
+                output_token_program: output_token_program.clone(),
+                input_token_mint: input_token_mint.clone(),
+                output_token_mint: output_token_mint.clone(),
+                observation_state: observation_state.clone(),
+            },
+        })
+    }
+
+    fn to_account_metas(&self) -> Vec<AccountMeta> {
+        let pool = &self.pool;
+        vec![
+            AccountMeta::new_readonly(self.payer.key(), true),
+            AccountMeta::new_readonly(pool.authority.key(), false),
+            AccountMeta::new_readonly(pool.amm_config.key(), false),
+            AccountMeta::new(pool.pool_state.key(), false),
+            AccountMeta::new(self.input_token_account.key(), false),
+            AccountMeta::new(self.output_token_account.key(), false),
+            AccountMeta::new(pool.input_vault.key(), false),
+            AccountMeta::new(pool.output_vault.key(), false),
+            AccountMeta::new_readonly(pool.input_token_program.key(), false),
+            AccountMeta::new_readonly(pool.output_token_program.key(), false),
+            AccountMeta::new_readonly(pool.input_token_mint.key(), false),
+            AccountMeta::new_readonly(pool.output_token_mint.key(), false),
+            AccountMeta::new(pool.observation_state.key(), false),
+        ]
+    }
+
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        let pool = &self.pool;
+        vec![
+            self.payer.clone(),
+            pool.authority.clone(),
+            pool.amm_config.clone(),
+            pool.pool_state.clone(),
+            self.input_token_account.clone(),
+            self.output_token_account.clone(),
+            pool.input_vault.clone(),
+            pool.output_vault.clone(),
+            pool.input_token_program.clone(),
+            pool.output_token_program.clone(),
+            pool.input_token_mint.clone(),
+            pool.output_token_mint.clone(),
+            pool.observation_state.clone(),
+        ]
+    }
+}
+
+/// Create a pool under `custom_authority`
+pub fn initialize<'info>(
+    program: &AccountInfo<'info>,
+    accounts: &Initialize<'info>,
+    signer_seeds: &[&[&[u8]]],
+    init_amount_0: u64,
+    init_amount_1: u64,
+    open_time: u64,
+    custom_authority: Pubkey,
+) -> Result<()> {
+    let args = InitializeArgs {
+        init_amount_0,
+        init_amount_1,
+        open_time,
+        authority_type: CUSTOM_AUTHORITY,
+        custom_authority: Some(custom_authority),
+    };
+    invoke(program, accounts.to_account_metas(), accounts.to_account_infos(), args.data(), signer_seeds)
+}
+
+/// Swap exactly `amount_in`, for at least `minimum_amount_out`
+pub fn swap_base_input<'info>(
+    program: &AccountInfo<'info>,
+    accounts: &Swap<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<()> {
+    let args = SwapBaseInputArgs { amount_in, minimum_amount_out };
+    invoke(program, accounts.to_account_metas(), accounts.to_account_infos(), args.data(), signer_seeds)
+}
+
+/// Swap for exactly `amount_out`, spending at most `max_amount_in`
+pub fn swap_base_output<'info>(
+    program: &AccountInfo<'info>,
+    accounts: &Swap<'info>,
+    signer_seeds: &[&[&[u8]]],
+    max_amount_in: u64,
+    amount_out: u64,
+) -> Result<()> {
+    let args = SwapBaseOutputArgs { max_amount_in, amount_out };
+    invoke(program, accounts.to_account_metas(), accounts.to_account_infos(), args.data(), signer_seeds)
+}
+
+/// Swap as an order does: `amount_in` is its exact input and `limit` its
+/// least output when `is_base_input`, otherwise they are its exact output
+/// and its most input
+pub fn swap<'info>(
+    program: &AccountInfo<'info>,
+    accounts: &Swap<'info>,
+    signer_seeds: &[&[&[u8]]],
+    is_base_input: bool,
+    amount_in: u64,
+    limit: u64,
+) -> Result<()> {
+    if is_base_input {
+        swap_base_input(program, accounts, signer_seeds, amount_in, limit)
+    } else {
+        swap_base_output(program, accounts, signer_seeds, limit, amount_in)
+    }
+}
+
+fn invoke<'info>(
+    program: &AccountInfo<'info>,
+    accounts: Vec<AccountMeta>,
+    mut account_infos: Vec<AccountInfo<'info>>,
+    data: Vec<u8>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let ix = Instruction {
+        program_id: program.key(),
+        accounts,
+        data,
+    };
+    account_infos.push(program.clone());
+    invoke_signed(&ix, &account_infos, signer_seeds).map_err(Into::into)
+}
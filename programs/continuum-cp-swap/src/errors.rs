@@ -31,4 +31,259 @@ pub enum ContinuumError {
     
     #[msg("Invalid order status")]
     InvalidOrderStatus,
-}
\ No newline at end of file
+    
+    #[msg("Order archive not initialized")]
+    ArchiveNotInitialized,
+    
+    #[msg("Order archive already initialized")]
+    ArchiveAlreadyInitialized,
+    
+    #[msg("Invalid remaining accounts")]
+    InvalidRemainingAccounts,
+    
+    #[msg("Invalid fee configuration")]
+    InvalidFeeConfig,
+    
+    #[msg("Invalid or untrusted VAA")]
+    InvalidVaa,
+    
+    #[msg("Invalid Wormhole account")]
+    InvalidWormholeAccount,
+    
+    #[msg("Destination is not owned by the order recipient")]
+    InvalidRecipient,
+    
+    #[msg("Missing or invalid intent signature")]
+    InvalidIntentSignature,
+    
+    #[msg("Order intent has expired")]
+    IntentExpired,
+    
+    #[msg("Source is not owned by the order user")]
+    InvalidSource,
+    
+    #[msg("An earlier order from this user is still pending")]
+    OutOfUserOrder,
+    
+    #[msg("Too many pending orders in user-ordered mode")]
+    UserOrderingFull,
+    
+    #[msg("User still has pending orders in user-ordered mode")]
+    UserOrderingPending,
+    
+    #[msg("Transaction landed after its deadline")]
+    DeadlineExceeded,
+    
+    #[msg("Relayer is not assigned to this pool")]
+    RelayerNotAssigned,
+    
+    #[msg("Missing the risk authority's co-signature")]
+    MissingRiskApproval,
+    
+    #[msg("Risk authority must differ from the admin")]
+    InvalidRiskAuthority,
+    
+    #[msg("Callback program does not match the order")]
+    InvalidCallback,
+    
+    #[msg("Pool requires its compliance program and the user's record")]
+    MissingComplianceCheck,
+    
+    #[msg("Batch must hold between one and MAX_BATCH_ORDERS orders")]
+    InvalidBatchSize,
+    
+    #[msg("Post-fill action accounts are missing or invalid")]
+    InvalidPostFill,
+    
+    #[msg("Order is not executable before its valid_from time")]
+    NotYetValid,
+    
+    #[msg("Order template has no occurrence due")]
+    TemplateNotDue,
+    
+    #[msg("Escrow can't fund the order")]
+    InsufficientEscrow,
+    
+    #[msg("Order template parameters are invalid")]
+    InvalidTemplate,
+    
+    #[msg("Parent order has not executed")]
+    ParentNotFilled,
+    
+    #[msg("Iceberg's active tranche has not executed")]
+    TrancheNotFilled,
+    
+    #[msg("Iceberg parameters are invalid")]
+    InvalidIceberg,
+    
+    #[msg("Oracle account is not a verified price update for the order")]
+    InvalidOracle,
+    
+    #[msg("Oracle price is too old")]
+    StalePrice,
+    
+    #[msg("Trailing stop has not been reached")]
+    StopNotTriggered,
+    
+    #[msg("Trail must be between 1 and 10000 bps")]
+    InvalidTrailingStop,
+    
+    #[msg("Shared escrow accounts are missing or don't match the order")]
+    InvalidSharedEscrow,
+    
+    #[msg("Shared escrow still backs pending orders")]
+    EscrowInUse,
+    
+    #[msg("Delegation has expired")]
+    DelegationExpired,
+    
+    #[msg("Order exceeds the delegation's maximum input")]
+    DelegationLimitExceeded,
+    
+    #[msg("Permit has expired")]
+    PermitExpired,
+    
+    #[msg("Order exceeds the permit's allowance")]
+    PermitExceeded,
+    
+    #[msg("Permit doesn't match the order or can't be combined with its source")]
+    InvalidPermit,
+    
+    #[msg("Order requires its co-signer's signature")]
+    CoSignerRequired,
+    
+    #[msg("Signer isn't a member of the organization")]
+    NotOrgMember,
+    
+    #[msg("Order is outside the member's limits")]
+    OrgLimitExceeded,
+    
+    #[msg("Too many organization members or member pools")]
+    InvalidOrgMembers,
+    
+    #[msg("Relayer exceeded its immediate swap rate")]
+    SwapRateExceeded,
+    
+    #[msg("A swap rate limit needs a nonzero window")]
+    InvalidSwapRateLimit,
+    
+    #[msg("Relayer has used up its quota for the period")]
+    RelayerQuotaExceeded,
+    
+    #[msg("Relayer quota period hasn't ended")]
+    QuotaPeriodNotOver,
+    
+    #[msg("A relayer quota needs a positive period")]
+    InvalidRelayerQuota,
+    
+    #[msg("Payout exceeds the pool's insurance fund")]
+    InsufficientInsurance,
+    
+    #[msg("Pool FIFO queue not initialized")]
+    PoolFifoNotInitialized,
+    
+    #[msg("Order is not next in its pool's queue")]
+    OutOfPoolOrder,
+    
+    #[msg("Order is still ahead of its pool's queue")]
+    OrderNotPassed,
+    
+    #[msg("Order vault accounts are missing or don't match the order")]
+    InvalidOrderVault,
+    
+    #[msg("Order must escrow its input in its vault or spend it from the wallet")]
+    OrderVaultRequired,
+    
+    #[msg("Pool is paused")]
+    PoolPaused,
+    
+    #[msg("Pause reason too long")]
+    PauseReasonTooLong,
+    
+    #[msg("Order has expired")]
+    OrderExpired,
+    
+    #[msg("Order has not expired")]
+    OrderNotExpired,
+    
+    #[msg("Order time-to-live must be positive")]
+    InvalidTtl,
+    
+    #[msg("Order can't be executed in a batch")]
+    NotBatchable,
+    
+    #[msg("Limit price must have a positive numerator and denominator")]
+    InvalidLimitPrice,
+    
+    #[msg("Pool price has not reached the order's limit")]
+    LimitNotReached,
+    
+    #[msg("TWAP orders sell at least one base unit per slice, in two or more slices at a positive interval")]
+    InvalidTwap,
+    
+    #[msg("TWAP order's next slice is not due yet")]
+    SliceNotDue,
+    
+    #[msg("Partial fills take a positive amount of an exact input order without a TWAP schedule")]
+    InvalidFillAmount,
+    
+    #[msg("Immediate-or-cancel and fill-or-kill orders can't wait on a start time, parent, stop, limit price or schedule")]
+    InvalidExecutionPolicy,
+    
+    #[msg("Fill-or-kill orders fill in full or not at all")]
+    FillOrKill,
+    
+    #[msg("Only an immediate-or-cancel order next in its pool's line can be cancelled by a relayer")]
+    NotImmediateOrCancel,
+    
+    #[msg("Trigger orders need a positive trigger price and its oracle, and can't also trail a stop")]
+    InvalidTrigger,
+    
+    #[msg("Trigger price has not been crossed")]
+    TriggerNotReached,
+    
+    #[msg("DCA schedules swap a positive amount per tick out of a positive budget, at a positive interval")]
+    InvalidDcaSchedule,
+    
+    #[msg("DCA schedule has no tick due")]
+    DcaTickNotDue,
+    
+    #[msg("Order memo is too long")]
+    MemoTooLong,
+    
+    #[msg("Only orders spending from the user's own account or their vault can be modified, and only unfilled ones re-queued")]
+    OrderNotModifiable,
+    
+    #[msg("A replacement order account is required exactly when the modification re-queues the order")]
+    InvalidReplacement,
+    
+    #[msg("CP-Swap program does not match the one recorded in the FIFO state")]
+    InvalidCpSwapProgram,
+    
+    #[msg("Order account can't be migrated")]
+    OrderNotMigratable,
+    
+    #[msg("Only registered relayers may execute orders")]
+    RelayerNotRegistered,
+    
+    #[msg("Destination balance decreased during the swap")]
+    DestinationBalanceDecreased,
+    
+    #[msg("Account isn't at a layout that can be migrated")]
+    AccountNotMigratable,
+    
+    #[msg("Fee amount overflowed")]
+    FeeOverflow,
+    
+    #[msg("An order's valid_from must be in the future")]
+    InvalidValidFrom,
+    
+    #[msg("Order amount must be positive")]
+    InvalidAmount,
+    
+    #[msg("The bridged transfer wasn't redeemed into custody for at least the order's deposit")]
+    InvalidBridgedTransfer,
+    
+    #[msg("Source balance increased during the swap")]
+    SourceBalanceIncreased,
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(relayer: Pubkey)]
+pub struct AddRelayer<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = RelayerInfo::LEN,
+        seeds = [RELAYER_SEED, relayer.as_ref()],
+        bump
+    )]
+    pub relayer_info: Account<'info, RelayerInfo>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn add_relayer(ctx: Context<AddRelayer>, relayer: Pubkey) -> Result<()> {
+    let clock = Clock::get()?;
+    let relayer_info = &mut ctx.accounts.relayer_info;
+
+    relayer_info.relayer = relayer;
+    relayer_info.registered_at = clock.unix_timestamp;
+    relayer_info.last_active = clock.unix_timestamp;
+    relayer_info.last_active_slot = clock.slot;
+    relayer_info.fee_bps = ctx.accounts.fifo_state.relayer_fee_bps;
+    relayer_info.swap_rate = RateCounter::default();
+
+    emit!(RelayerAdded {
+        relayer,
+    });
+
+    msg!("Relayer {} added", relayer);
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+
+/// Allow swap-and-stake orders to deposit into `program` (admin only)
+#[derive(Accounts)]
+#[instruction(program: Pubkey)]
+pub struct ApproveStakeProgram<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ApprovedStakeProgram::LEN,
+        seeds = [STAKE_PROGRAM_SEED, program.as_ref()],
+        bump
+    )]
+    pub approved_stake_program: Account<'info, ApprovedStakeProgram>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Stop orders depositing into a staking program, including pending ones
+/// (admin only)
+#[derive(Accounts)]
+pub struct RevokeStakeProgram<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [STAKE_PROGRAM_SEED, approved_stake_program.program.as_ref()],
+        bump,
+    )]
+    pub approved_stake_program: Account<'info, ApprovedStakeProgram>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn approve_stake_program(ctx: Context<ApproveStakeProgram>, program: Pubkey) -> Result<()> {
+    let approved = &mut ctx.accounts.approved_stake_program;
+    approved.program = program;
+    approved.approved_at = Clock::get()?.unix_timestamp;
+
+    msg!("Staking program {} approved", program);
+
+    Ok(())
+}
+
+pub fn revoke_stake_program(ctx: Context<RevokeStakeProgram>) -> Result<()> {
+    msg!("Staking program {} revoked", ctx.accounts.approved_stake_program.program);
+
+    Ok(())
+}
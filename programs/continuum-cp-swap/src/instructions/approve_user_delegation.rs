@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Let `delegate` submit orders of up to `max_amount_in` for the signer, and
+/// cancel their pending orders. A program-owned user signs by CPI.
+#[derive(Accounts)]
+pub struct ApproveUserDelegation<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = UserDelegation::LEN,
+        seeds = [USER_DELEGATION_SEED, user.key().as_ref(), delegate.key().as_ref()],
+        bump
+    )]
+    pub user_delegation: Account<'info, UserDelegation>,
+
+    pub user: Signer<'info>,
+
+    /// CHECK: Any account may be a delegate
+    pub delegate: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+/// Withdraw a delegation, returning its rent to `receiver`. Orders the
+/// delegate already submitted stay queued.
+#[derive(Accounts)]
+pub struct RevokeUserDelegation<'info> {
+    #[account(
+        mut,
+        close = receiver,
+        has_one = user @ ContinuumError::Unauthorized,
+        seeds = [USER_DELEGATION_SEED, user.key().as_ref(), user_delegation.delegate.as_ref()],
+        bump,
+    )]
+    pub user_delegation: Account<'info, UserDelegation>,
+
+    pub user: Signer<'info>,
+
+    /// CHECK: Only receives the rent
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+}
+
+pub fn approve_user_delegation(
+    ctx: Context<ApproveUserDelegation>,
+    max_amount_in: u64,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    let user_delegation = &mut ctx.accounts.user_delegation;
+    user_delegation.user = ctx.accounts.user.key();
+    user_delegation.delegate = ctx.accounts.delegate.key();
+    user_delegation.max_amount_in = max_amount_in;
+    user_delegation.expires_at = expires_at;
+    user_delegation.approved_at = crate::clock::unix_timestamp(&ctx.accounts.clock, ctx.remaining_accounts);
+
+    msg!(
+        "User {} delegated orders of up to {} to {}",
+        user_delegation.user,
+        max_amount_in,
+        user_delegation.delegate
+    );
+
+    Ok(())
+}
+
+pub fn revoke_user_delegation(ctx: Context<RevokeUserDelegation>) -> Result<()> {
+    msg!(
+        "User {} revoked the delegation to {}",
+        ctx.accounts.user.key(),
+        ctx.accounts.user_delegation.delegate
+    );
+
+    Ok(())
+}
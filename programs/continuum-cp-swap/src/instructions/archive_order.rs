@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use crate::compression::{self, ACCOUNT_COMPRESSION_PROGRAM_ID, NOOP_PROGRAM_ID};
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Permissionless crank: closes a finished order account, returning rent to
+/// the user, and appends its contents to the archive tree.
+#[derive(Accounts)]
+pub struct ArchiveOrder<'info> {
+    #[account(
+        mut,
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        constraint = fifo_state.archive_tree != Pubkey::default() @ ContinuumError::ArchiveNotInitialized,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [ORDER_SEED, order_state.pool_id.as_ref(), &order_state.sequence.to_le_bytes()],
+        bump,
+        constraint = order_state.status != OrderStatus::Pending @ ContinuumError::InvalidOrderStatus,
+    )]
+    pub order_state: Account<'info, OrderState>,
+
+    /// The order's pool queue, whose head must have passed the order so it
+    /// can't be left blocking the queue
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, order_state.pool_id.as_ref()],
+        bump,
+        constraint = order_state.sequence <= pool_fifo.head @ ContinuumError::OrderNotPassed,
+    )]
+    pub pool_fifo: Account<'info, PoolFifoState>,
+
+    /// CHECK: Receives the order account rent, must be the order owner
+    #[account(
+        mut,
+        address = order_state.user @ ContinuumError::Unauthorized,
+    )]
+    pub user: UncheckedAccount<'info>,
+
+    /// CHECK: This is a PDA that signs the account compression CPI
+    #[account(
+        seeds = [ARCHIVE_AUTHORITY_SEED],
+        bump
+    )]
+    pub archive_authority: UncheckedAccount<'info>,
+
+    /// CHECK: The archive tree registered in fifo_state
+    #[account(
+        mut,
+        address = fifo_state.archive_tree,
+    )]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: The SPL Account Compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: The SPL Noop program
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub noop_program: UncheckedAccount<'info>,
+}
+
+pub fn archive_order(ctx: Context<ArchiveOrder>) -> Result<()> {
+    let archive_authority_bump = ctx.bumps.archive_authority;
+    let archived = ArchivedOrder::from(&*ctx.accounts.order_state);
+    let leaf = archived.leaf_hash();
+
+    let ix = compression::append_ix(
+        ctx.accounts.merkle_tree.key(),
+        ctx.accounts.archive_authority.key(),
+        leaf,
+    );
+
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.merkle_tree.to_account_info(),
+            ctx.accounts.archive_authority.to_account_info(),
+            ctx.accounts.noop_program.to_account_info(),
+        ],
+        &[&[ARCHIVE_AUTHORITY_SEED, &[archive_authority_bump]]],
+    )?;
+
+    let fifo_state = &mut ctx.accounts.fifo_state;
+    let leaf_index = fifo_state.archived_orders;
+    fifo_state.archived_orders = leaf_index + 1;
+
+    emit!(OrderArchived {
+        event_sequence: ctx.accounts.pool_fifo.next_event_sequence(),
+        sequence: archived.sequence,
+        user: archived.user,
+        pool_id: archived.pool_id,
+        leaf,
+        leaf_index,
+    });
+
+    msg!("Order {} archived at leaf {}", archived.sequence, leaf_index);
+
+    Ok(())
+}
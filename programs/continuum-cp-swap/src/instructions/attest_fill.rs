@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::system_program::{self, Transfer};
+use crate::consts::*;
+use crate::state::*;
+use crate::wormhole::{self, FillAttestation, PostMessageAccounts, FINALITY_FINALIZED, WORMHOLE_PROGRAM_ID};
+
+/// Publish a Wormhole message attesting to a fill, sourced from its execution
+/// receipt. Permissionless; each receipt can be attested once since the
+/// message account address is derived from it.
+#[derive(Accounts)]
+pub struct AttestFill<'info> {
+    #[account(
+        seeds = [RECEIPT_SEED, receipt.pool_id.as_ref(), &receipt.sequence.to_le_bytes()],
+        bump,
+    )]
+    pub receipt: Box<Account<'info, ExecutionReceipt>>,
+
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, receipt.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Box<Account<'info, PoolFifoState>>,
+
+    /// CHECK: Core bridge config
+    #[account(
+        mut,
+        seeds = [b"Bridge"],
+        bump,
+        seeds::program = WORMHOLE_PROGRAM_ID,
+    )]
+    pub wormhole_bridge: UncheckedAccount<'info>,
+
+    /// CHECK: Message account created by the core bridge, signed for by this program
+    #[account(
+        mut,
+        seeds = [FILL_ATTESTATION_SEED, receipt.key().as_ref()],
+        bump,
+    )]
+    pub wormhole_message: UncheckedAccount<'info>,
+
+    /// CHECK: This program's Wormhole emitter PDA
+    #[account(
+        seeds = [EMITTER_SEED],
+        bump,
+    )]
+    pub wormhole_emitter: UncheckedAccount<'info>,
+
+    /// CHECK: Core bridge sequence tracker for the emitter
+    #[account(
+        mut,
+        seeds = [b"Sequence", wormhole_emitter.key().as_ref()],
+        bump,
+        seeds::program = WORMHOLE_PROGRAM_ID,
+    )]
+    pub wormhole_sequence: UncheckedAccount<'info>,
+
+    /// CHECK: Core bridge fee collector
+    #[account(
+        mut,
+        seeds = [FEE_COLLECTOR_SEED],
+        bump,
+        seeds::program = WORMHOLE_PROGRAM_ID,
+    )]
+    pub wormhole_fee_collector: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: The Wormhole core bridge program
+    #[account(address = WORMHOLE_PROGRAM_ID)]
+    pub wormhole_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn attest_fill(ctx: Context<AttestFill>) -> Result<()> {
+    let receipt_key = ctx.accounts.receipt.key();
+    let message_bump = ctx.bumps.wormhole_message;
+    let emitter_bump = ctx.bumps.wormhole_emitter;
+
+    // The core bridge requires its message fee in the fee collector
+    let fee = wormhole::message_fee(&ctx.accounts.wormhole_bridge.try_borrow_data()?)?;
+    if fee > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.wormhole_fee_collector.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+    }
+
+    let ix = wormhole::post_message_ix(
+        PostMessageAccounts {
+            bridge: ctx.accounts.wormhole_bridge.key(),
+            message: ctx.accounts.wormhole_message.key(),
+            emitter: ctx.accounts.wormhole_emitter.key(),
+            sequence: ctx.accounts.wormhole_sequence.key(),
+            payer: ctx.accounts.payer.key(),
+            fee_collector: ctx.accounts.wormhole_fee_collector.key(),
+        },
+        0,
+        FillAttestation::encode(&ctx.accounts.receipt),
+        FINALITY_FINALIZED,
+    );
+
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.wormhole_bridge.to_account_info(),
+            ctx.accounts.wormhole_message.to_account_info(),
+            ctx.accounts.wormhole_emitter.to_account_info(),
+            ctx.accounts.wormhole_sequence.to_account_info(),
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.wormhole_fee_collector.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[
+            &[FILL_ATTESTATION_SEED, receipt_key.as_ref(), &[message_bump]],
+            &[EMITTER_SEED, &[emitter_bump]],
+        ],
+    )?;
+
+    let sequence = ctx.accounts.receipt.sequence;
+    let pool_id = ctx.accounts.receipt.pool_id;
+
+    emit!(FillAttested {
+        event_sequence: ctx.accounts.pool_fifo.next_event_sequence(),
+        sequence,
+        pool_id,
+        message: ctx.accounts.wormhole_message.key(),
+    });
+
+    msg!("Fill {} attested via Wormhole", sequence);
+
+    Ok(())
+}
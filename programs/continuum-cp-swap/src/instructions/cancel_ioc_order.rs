@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Cancels an immediate-or-cancel order next in its pool's line that the
+/// executor couldn't fill, releasing its shared escrow reservation,
+/// refunding its vault and moving the queue past it. Any relayer the pool
+/// allows to execute may sign, registered unless execution is
+/// permissionless.
+#[derive(Accounts)]
+pub struct CancelIocOrder<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        mut,
+        seeds = [ORDER_SEED, order_state.pool_id.as_ref(), &order_state.sequence.to_le_bytes()],
+        bump,
+        constraint = order_state.status == OrderStatus::Pending @ ContinuumError::InvalidOrderStatus,
+    )]
+    pub order_state: Account<'info, OrderState>,
+
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, order_state.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Account<'info, PoolFifoState>,
+
+    /// CHECK: The pool's relayer list; may be uninitialized, see `crate::optional`
+    #[account(
+        seeds = [POOL_RELAYERS_SEED, order_state.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_relayers: UncheckedAccount<'info>,
+
+    pub executor: Signer<'info>,
+
+    /// The executor's registration; required unless execution is
+    /// permissionless
+    #[account(
+        seeds = [RELAYER_SEED, executor.key().as_ref()],
+        bump,
+    )]
+    pub relayer_info: Option<Account<'info, RelayerInfo>>,
+
+    /// CHECK: The user's ordering PDA; may be uninitialized, see `crate::ordering`
+    #[account(
+        mut,
+        seeds = [USER_ORDERING_SEED, order_state.user.as_ref()],
+        bump,
+    )]
+    pub user_ordering: UncheckedAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    /// The order's shared escrow, required when it has one
+    #[account(mut)]
+    pub shared_escrow: Option<Account<'info, SharedEscrow>>,
+
+    /// Vault of an escrowed order, required when it has one with the
+    /// accounts below, refunded and closed
+    #[account(
+        mut,
+        seeds = [ORDER_VAULT_SEED, order_state.key().as_ref()],
+        bump,
+    )]
+    pub order_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The order's `escrow_refund` account
+    #[account(mut)]
+    pub escrow_refund: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: This is a PDA that owns the order vault
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, order_state.pool_id.as_ref()],
+        bump
+    )]
+    pub pool_authority: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// CHECK: The order's user, receiving the rent of an escrowed order's vault
+    #[account(
+        mut,
+        address = order_state.user @ ContinuumError::Unauthorized,
+    )]
+    pub user: Option<UncheckedAccount<'info>>,
+}
+
+pub fn cancel_ioc_order(ctx: Context<CancelIocOrder>) -> Result<()> {
+    let now = crate::clock::unix_timestamp(&ctx.accounts.clock, ctx.remaining_accounts);
+    let order_state = &mut ctx.accounts.order_state;
+    require!(
+        order_state.execution_policy == ExecutionPolicy::ImmediateOrCancel
+            && order_state.sequence == ctx.accounts.pool_fifo.next_execution_sequence(),
+        ContinuumError::NotImmediateOrCancel
+    );
+    let assigned = crate::optional::load::<PoolRelayers>(&ctx.accounts.pool_relayers)?
+        .is_none_or(|pool_relayers| pool_relayers.allows(&ctx.accounts.executor.key()));
+    require!(assigned, ContinuumError::RelayerNotAssigned);
+    require!(
+        ctx.accounts.fifo_state.permissionless_execution || ctx.accounts.relayer_info.is_some(),
+        ContinuumError::RelayerNotRegistered
+    );
+
+    let shared_escrow = ctx.accounts.shared_escrow.as_mut();
+    require!(
+        order_state.shared_escrow == shared_escrow.as_ref().map(|escrow| escrow.key()),
+        ContinuumError::InvalidSharedEscrow
+    );
+    if let Some(shared_escrow) = shared_escrow {
+        shared_escrow.release(order_state.max_input());
+    }
+
+    let escrow_refund = ctx.accounts.escrow_refund.as_ref();
+    require!(
+        order_state.escrow_refund == escrow_refund.map(|refund| refund.key()),
+        ContinuumError::InvalidOrderVault
+    );
+    if let Some(escrow_refund) = escrow_refund {
+        let (Some(order_vault), Some(pool_authority), Some(token_program), Some(user), Some(bump)) =
+            (&ctx.accounts.order_vault, &ctx.accounts.pool_authority, &ctx.accounts.token_program, &ctx.accounts.user, ctx.bumps.pool_authority)
+        else {
+            return err!(ContinuumError::InvalidOrderVault);
+        };
+        let refunded = crate::order_vault::settle(
+            token_program.to_account_info(),
+            order_vault.to_account_info(),
+            escrow_refund.to_account_info(),
+            user.to_account_info(),
+            pool_authority.to_account_info(),
+            &[POOL_AUTHORITY_SEED, order_state.pool_id.as_ref(), &[bump]],
+        )?;
+        msg!("Order {} refunded {} from its vault", order_state.sequence, refunded);
+    }
+
+    order_state.status = OrderStatus::Cancelled;
+    order_state.executed_at = Some(now);
+    ctx.accounts.pool_fifo.advance(order_state.sequence, now);
+
+    crate::ordering::remove(&ctx.accounts.user_ordering, &order_state.key())?;
+
+    emit!(OrderCancelled {
+        event_sequence: ctx.accounts.pool_fifo.next_event_sequence(),
+        sequence: order_state.sequence,
+        pool_id: order_state.pool_id,
+        user: order_state.user,
+        trace_id: order_state.trace_id,
+    });
+
+    msg!("Immediate-or-cancel order {} cancelled unfilled", order_state.sequence);
+
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Cancel one of a user's pending orders under their `UserDelegation` to the
+/// signer. Expired delegations can still cancel.
+#[derive(Accounts)]
+pub struct CancelOrderAsDelegate<'info> {
+    #[account(
+        seeds = [USER_DELEGATION_SEED, user_delegation.user.as_ref(), delegate.key().as_ref()],
+        bump,
+    )]
+    pub user_delegation: Account<'info, UserDelegation>,
+
+    #[account(
+        mut,
+        seeds = [ORDER_SEED, order_state.pool_id.as_ref(), &order_state.sequence.to_le_bytes()],
+        bump,
+        constraint = order_state.status == OrderStatus::Pending @ ContinuumError::InvalidOrderStatus,
+        constraint = order_state.user == user_delegation.user @ ContinuumError::Unauthorized,
+    )]
+    pub order_state: Account<'info, OrderState>,
+
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, order_state.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Account<'info, PoolFifoState>,
+
+    /// CHECK: The user's ordering PDA; may be uninitialized, see `crate::ordering`
+    #[account(
+        mut,
+        seeds = [USER_ORDERING_SEED, user_delegation.user.as_ref()],
+        bump,
+    )]
+    pub user_ordering: UncheckedAccount<'info>,
+
+    pub delegate: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    /// The order's shared escrow, required when it has one
+    #[account(mut)]
+    pub shared_escrow: Option<Account<'info, SharedEscrow>>,
+
+    /// The order's co-signer, required when it must sign the cancellation
+    pub co_signer: Option<Signer<'info>>,
+
+    /// Vault of an escrowed order, required when it has one with the
+    /// accounts below, refunded and closed
+    #[account(
+        mut,
+        seeds = [ORDER_VAULT_SEED, order_state.key().as_ref()],
+        bump,
+    )]
+    pub order_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The order's `escrow_refund` account
+    #[account(mut)]
+    pub escrow_refund: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: This is a PDA that owns the order vault
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, order_state.pool_id.as_ref()],
+        bump
+    )]
+    pub pool_authority: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// CHECK: The order's user, receiving the rent of an escrowed order's vault
+    #[account(
+        mut,
+        address = user_delegation.user @ ContinuumError::Unauthorized,
+    )]
+    pub user: Option<UncheckedAccount<'info>>,
+}
+
+pub fn cancel_order_as_delegate(ctx: Context<CancelOrderAsDelegate>) -> Result<()> {
+    let now = crate::clock::unix_timestamp(&ctx.accounts.clock, ctx.remaining_accounts);
+    let order_state = &mut ctx.accounts.order_state;
+
+    let co_signer = ctx.accounts.co_signer.as_ref().map(|signer| signer.key());
+    require!(order_state.cancel_co_signed(co_signer.as_ref()), ContinuumError::CoSignerRequired);
+
+    let shared_escrow = ctx.accounts.shared_escrow.as_mut();
+    require!(
+        order_state.shared_escrow == shared_escrow.as_ref().map(|escrow| escrow.key()),
+        ContinuumError::InvalidSharedEscrow
+    );
+    if let Some(shared_escrow) = shared_escrow {
+        shared_escrow.release(order_state.max_input());
+    }
+
+    let escrow_refund = ctx.accounts.escrow_refund.as_ref();
+    require!(
+        order_state.escrow_refund == escrow_refund.map(|refund| refund.key()),
+        ContinuumError::InvalidOrderVault
+    );
+    if let Some(escrow_refund) = escrow_refund {
+        let (Some(order_vault), Some(pool_authority), Some(token_program), Some(user), Some(bump)) =
+            (&ctx.accounts.order_vault, &ctx.accounts.pool_authority, &ctx.accounts.token_program, &ctx.accounts.user, ctx.bumps.pool_authority)
+        else {
+            return err!(ContinuumError::InvalidOrderVault);
+        };
+        let refunded = crate::order_vault::settle(
+            token_program.to_account_info(),
+            order_vault.to_account_info(),
+            escrow_refund.to_account_info(),
+            user.to_account_info(),
+            pool_authority.to_account_info(),
+            &[POOL_AUTHORITY_SEED, order_state.pool_id.as_ref(), &[bump]],
+        )?;
+        msg!("Order {} refunded {} from its vault", order_state.sequence, refunded);
+    }
+
+    order_state.status = OrderStatus::Cancelled;
+    order_state.executed_at = Some(now);
+
+    crate::ordering::remove(&ctx.accounts.user_ordering, &order_state.key())?;
+
+    emit!(OrderCancelled {
+        event_sequence: ctx.accounts.pool_fifo.next_event_sequence(),
+        sequence: order_state.sequence,
+        pool_id: order_state.pool_id,
+        user: order_state.user,
+        trace_id: order_state.trace_id,
+    });
+
+    msg!("Order {} cancelled by delegate {}", order_state.sequence, ctx.accounts.delegate.key());
+
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ClaimRelayerFees<'info> {
+    #[account(
+        mut,
+        seeds = [FEE_LEDGER_SEED, fee_ledger.pool_id.as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Box<Account<'info, PoolFeeLedger>>,
+
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, fee_ledger.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Box<Account<'info, PoolFifoState>>,
+
+    #[account(
+        mut,
+        seeds = [RELAYER_FEES_SEED, fee_ledger.pool_id.as_ref(), relayer.key().as_ref()],
+        bump,
+        constraint = relayer_fee_balance.relayer == relayer.key() @ ContinuumError::Unauthorized,
+    )]
+    pub relayer_fee_balance: Account<'info, RelayerFeeBalance>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, fee_ledger.pool_id.as_ref(), fee_ledger.token_0.as_ref()],
+        bump,
+    )]
+    pub fee_vault_0: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, fee_ledger.pool_id.as_ref(), fee_ledger.token_1.as_ref()],
+        bump,
+    )]
+    pub fee_vault_1: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: This is a PDA that owns the fee vaults
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, fee_ledger.pool_id.as_ref()],
+        bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = fee_ledger.token_0,
+    )]
+    pub relayer_token_0: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = fee_ledger.token_1,
+    )]
+    pub relayer_token_1: Box<Account<'info, TokenAccount>>,
+
+    pub relayer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn claim_relayer_fees(ctx: Context<ClaimRelayerFees>) -> Result<()> {
+    let pool_id = ctx.accounts.fee_ledger.pool_id;
+    let pool_authority_bump = ctx.bumps.pool_authority;
+    let pool_authority_seeds: &[&[u8]] = &[
+        POOL_AUTHORITY_SEED,
+        pool_id.as_ref(),
+        &[pool_authority_bump],
+    ];
+
+    let amount_0 = ctx.accounts.relayer_fee_balance.claimable_0;
+    let amount_1 = ctx.accounts.relayer_fee_balance.claimable_1;
+
+    if amount_0 > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.fee_vault_0.to_account_info(),
+                    to: ctx.accounts.relayer_token_0.to_account_info(),
+                    authority: ctx.accounts.pool_authority.to_account_info(),
+                },
+                &[pool_authority_seeds],
+            ),
+            amount_0,
+        )?;
+    }
+
+    if amount_1 > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.fee_vault_1.to_account_info(),
+                    to: ctx.accounts.relayer_token_1.to_account_info(),
+                    authority: ctx.accounts.pool_authority.to_account_info(),
+                },
+                &[pool_authority_seeds],
+            ),
+            amount_1,
+        )?;
+    }
+
+    let balance = &mut ctx.accounts.relayer_fee_balance;
+    balance.claimable_0 = 0;
+    balance.claimable_1 = 0;
+    balance.claimed_0 = balance.claimed_0.checked_add(amount_0).ok_or(ContinuumError::FeeOverflow)?;
+    balance.claimed_1 = balance.claimed_1.checked_add(amount_1).ok_or(ContinuumError::FeeOverflow)?;
+
+    let fee_ledger = &mut ctx.accounts.fee_ledger;
+    fee_ledger.relayer_0.pay_out(amount_0)?;
+    fee_ledger.relayer_1.pay_out(amount_1)?;
+
+    emit!(RelayerFeesClaimed {
+        event_sequence: ctx.accounts.pool_fifo.next_event_sequence(),
+        pool_id,
+        relayer: ctx.accounts.relayer.key(),
+        amount_0,
+        amount_1,
+    });
+
+    msg!("Relayer {} claimed {} / {} in fees", ctx.accounts.relayer.key(), amount_0, amount_1);
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Compensate the owner of a mishandled order out of its pool's insurance
+/// fund, e.g. for a fill at an unfair price or one that skipped it past its
+/// turn. The order is identified by its execution receipt, and the record
+/// this creates makes each order compensable once. Governed like insurance
+/// payouts: the admin signs, and so does the risk authority once one is set.
+#[derive(Accounts)]
+pub struct CompensateOrder<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        seeds = [RECEIPT_SEED, receipt.pool_id.as_ref(), &receipt.sequence.to_le_bytes()],
+        bump,
+    )]
+    pub receipt: Box<Account<'info, ExecutionReceipt>>,
+
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, receipt.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Box<Account<'info, PoolFifoState>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Compensation::LEN,
+        seeds = [COMPENSATION_SEED, receipt.key().as_ref()],
+        bump
+    )]
+    pub compensation: Box<Account<'info, Compensation>>,
+
+    #[account(
+        mut,
+        seeds = [FEE_LEDGER_SEED, receipt.pool_id.as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Box<Account<'info, PoolFeeLedger>>,
+
+    /// Fee vault of the mint paid out
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, receipt.pool_id.as_ref(), fee_vault.mint.as_ref()],
+        bump,
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: This is a PDA that owns the fee vaults
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, receipt.pool_id.as_ref()],
+        bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// The order owner's token account of the mint
+    #[account(
+        mut,
+        token::mint = fee_vault.mint,
+        token::authority = receipt.user,
+    )]
+    pub recipient: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Required once the FIFO state has a risk authority
+    pub risk_authority: Option<Signer<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn compensate_order(ctx: Context<CompensateOrder>, amount: u64, reason: [u8; 32]) -> Result<()> {
+    require!(
+        ctx.accounts.fifo_state.risk_approved(ctx.accounts.risk_authority.as_ref().map(|signer| signer.key)),
+        ContinuumError::MissingRiskApproval
+    );
+
+    let mint = ctx.accounts.fee_vault.mint;
+    crate::instructions::payout_insurance::pay(
+        &mut ctx.accounts.fee_ledger,
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.fee_vault.to_account_info(),
+        &mint,
+        ctx.accounts.recipient.to_account_info(),
+        ctx.accounts.pool_authority.to_account_info(),
+        ctx.bumps.pool_authority,
+        amount,
+    )?;
+
+    let receipt = &ctx.accounts.receipt;
+    let compensation = &mut ctx.accounts.compensation;
+    compensation.receipt = receipt.key();
+    compensation.pool_id = receipt.pool_id;
+    compensation.sequence = receipt.sequence;
+    compensation.user = receipt.user;
+    compensation.mint = mint;
+    compensation.amount = amount;
+    compensation.reason = reason;
+    compensation.compensated_at = crate::clock::unix_timestamp(&ctx.accounts.clock, ctx.remaining_accounts);
+
+    emit!(OrderCompensated {
+        event_sequence: ctx.accounts.pool_fifo.next_event_sequence(),
+        sequence: receipt.sequence,
+        pool_id: receipt.pool_id,
+        user: receipt.user,
+        mint,
+        amount,
+        reason,
+    });
+
+    msg!("Order {} on pool {} compensated with {} of {}", receipt.sequence, receipt.pool_id, amount, mint);
+
+    Ok(())
+}
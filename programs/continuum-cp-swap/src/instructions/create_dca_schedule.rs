@@ -0,0 +1,219 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// The recurring swap a DCA schedule executes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DcaScheduleParams {
+    pub amount_per_tick: u64,
+    pub min_amount_out_per_tick: u64,
+    /// Seconds between ticks
+    pub interval: i64,
+}
+
+/// Create a DCA schedule on a pool and fund its escrow with a `budget` of
+/// `mint`, the token its ticks sell for the pool's other token, delivered to
+/// `destination`. Pools with a compliance program only take orders through
+/// `submit_order`.
+#[derive(Accounts)]
+#[instruction(schedule_id: u64)]
+pub struct CreateDcaSchedule<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        constraint = !fifo_state.emergency_pause @ ContinuumError::EmergencyPause,
+    )]
+    pub fifo_state: Box<Account<'info, FifoState>>,
+
+    #[account(
+        seeds = [POOL_REGISTRY_SEED, pool_registry.pool_id.as_ref()],
+        bump,
+        constraint = pool_registry.is_active @ ContinuumError::PoolNotRegistered,
+        constraint = !pool_registry.is_paused @ ContinuumError::PoolPaused,
+        constraint = pool_registry.compliance_program().is_none() @ ContinuumError::MissingComplianceCheck,
+    )]
+    pub pool_registry: Box<Account<'info, CpSwapPoolRegistry>>,
+
+    #[account(
+        init,
+        payer = user,
+        space = DcaSchedule::LEN,
+        seeds = [DCA_SCHEDULE_SEED, user.key().as_ref(), &schedule_id.to_le_bytes()],
+        bump
+    )]
+    pub schedule: Box<Account<'info, DcaSchedule>>,
+
+    #[account(
+        constraint = mint.key() == pool_registry.token_0 || mint.key() == pool_registry.token_1
+            @ ContinuumError::InvalidDcaSchedule,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [DCA_ESCROW_SEED, schedule.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = pool_authority,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: This is a PDA that owns the schedule escrow
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, pool_registry.pool_id.as_ref()],
+        bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user,
+    )]
+    pub user_source: Box<Account<'info, TokenAccount>>,
+
+    /// The user's account of the pool's other token, receiving each tick's output
+    #[account(
+        token::authority = user,
+        constraint = destination.mint != mint.key()
+            && (destination.mint == pool_registry.token_0 || destination.mint == pool_registry.token_1)
+            @ ContinuumError::InvalidRecipient,
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Stop a DCA schedule, refunding the budget it has left
+#[derive(Accounts)]
+pub struct CloseDcaSchedule<'info> {
+    #[account(
+        mut,
+        close = user,
+        has_one = user @ ContinuumError::Unauthorized,
+        seeds = [DCA_SCHEDULE_SEED, user.key().as_ref(), &schedule.schedule_id.to_le_bytes()],
+        bump,
+    )]
+    pub schedule: Box<Account<'info, DcaSchedule>>,
+
+    #[account(
+        mut,
+        seeds = [DCA_ESCROW_SEED, schedule.key().as_ref()],
+        bump,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: This is a PDA that owns the schedule escrow
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, schedule.pool_id.as_ref()],
+        bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = escrow.mint,
+    )]
+    pub user_destination: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn create_dca_schedule(
+    ctx: Context<CreateDcaSchedule>,
+    schedule_id: u64,
+    params: DcaScheduleParams,
+    budget: u64,
+) -> Result<()> {
+    require!(
+        params.amount_per_tick > 0 && params.interval > 0 && budget > 0,
+        ContinuumError::InvalidDcaSchedule
+    );
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_source.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        budget,
+    )?;
+
+    let schedule = &mut ctx.accounts.schedule;
+    schedule.user = ctx.accounts.user.key();
+    schedule.schedule_id = schedule_id;
+    schedule.pool_id = ctx.accounts.pool_registry.pool_id;
+    schedule.input_mint = ctx.accounts.mint.key();
+    schedule.destination = ctx.accounts.destination.key();
+    schedule.amount_per_tick = params.amount_per_tick;
+    schedule.min_amount_out_per_tick = params.min_amount_out_per_tick;
+    schedule.interval = params.interval;
+    schedule.budget_remaining = budget;
+    schedule.ticks_executed = 0;
+    schedule.amount_out_total = 0;
+    schedule.next_execution_time = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "DCA schedule {} created by user {}: {} of {} every {}s",
+        schedule_id,
+        schedule.user,
+        params.amount_per_tick,
+        budget,
+        params.interval
+    );
+
+    Ok(())
+}
+
+pub fn close_dca_schedule(ctx: Context<CloseDcaSchedule>) -> Result<()> {
+    let pool_id = ctx.accounts.schedule.pool_id;
+    let seeds: &[&[u8]] = &[POOL_AUTHORITY_SEED, pool_id.as_ref(), &[ctx.bumps.pool_authority]];
+    let refund = ctx.accounts.escrow.amount;
+
+    if refund > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.escrow.to_account_info(),
+                    to: ctx.accounts.user_destination.to_account_info(),
+                    authority: ctx.accounts.pool_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            refund,
+        )?;
+    }
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.escrow.to_account_info(),
+            destination: ctx.accounts.user.to_account_info(),
+            authority: ctx.accounts.pool_authority.to_account_info(),
+        },
+        &[seeds],
+    ))?;
+
+    msg!(
+        "DCA schedule {} closed after {} ticks, {} refunded",
+        ctx.accounts.schedule.schedule_id,
+        ctx.accounts.schedule.ticks_executed,
+        refund
+    );
+
+    Ok(())
+}
@@ -0,0 +1,188 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Create an iceberg on a pool and fund its escrow with `deposit` of `mint`,
+/// the token its tranches sell. No tranche is enqueued until the first
+/// `rearm_iceberg`. Pools with a compliance program only take orders through
+/// `submit_order`.
+#[derive(Accounts)]
+#[instruction(iceberg_id: u64)]
+pub struct CreateIceberg<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        constraint = !fifo_state.emergency_pause @ ContinuumError::EmergencyPause,
+    )]
+    pub fifo_state: Box<Account<'info, FifoState>>,
+
+    #[account(
+        seeds = [POOL_REGISTRY_SEED, pool_registry.pool_id.as_ref()],
+        bump,
+        constraint = pool_registry.is_active @ ContinuumError::PoolNotRegistered,
+        constraint = !pool_registry.is_paused @ ContinuumError::PoolPaused,
+        constraint = pool_registry.compliance_program().is_none() @ ContinuumError::MissingComplianceCheck,
+    )]
+    pub pool_registry: Box<Account<'info, CpSwapPoolRegistry>>,
+
+    #[account(
+        init,
+        payer = user,
+        space = Iceberg::LEN,
+        seeds = [ICEBERG_SEED, user.key().as_ref(), &iceberg_id.to_le_bytes()],
+        bump
+    )]
+    pub iceberg: Box<Account<'info, Iceberg>>,
+
+    #[account(
+        constraint = mint.key() == pool_registry.token_0 || mint.key() == pool_registry.token_1
+            @ ContinuumError::InvalidIceberg,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [ICEBERG_ESCROW_SEED, iceberg.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = pool_authority,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: This is a PDA that owns the iceberg escrow
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, pool_registry.pool_id.as_ref()],
+        bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user,
+    )]
+    pub user_source: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Stop an iceberg, refunding what is left in its escrow. A tranche already
+/// enqueued but not executed can no longer be filled.
+#[derive(Accounts)]
+pub struct CloseIceberg<'info> {
+    #[account(
+        mut,
+        close = user,
+        has_one = user @ ContinuumError::Unauthorized,
+        seeds = [ICEBERG_SEED, user.key().as_ref(), &iceberg.iceberg_id.to_le_bytes()],
+        bump,
+    )]
+    pub iceberg: Box<Account<'info, Iceberg>>,
+
+    #[account(
+        mut,
+        seeds = [ICEBERG_ESCROW_SEED, iceberg.key().as_ref()],
+        bump,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: This is a PDA that owns the iceberg escrow
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, iceberg.pool_id.as_ref()],
+        bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = escrow.mint,
+    )]
+    pub user_destination: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn create_iceberg(
+    ctx: Context<CreateIceberg>,
+    iceberg_id: u64,
+    tranche_in: u64,
+    tranche_min_out: u64,
+    deposit: u64,
+) -> Result<()> {
+    require!(tranche_in > 0, ContinuumError::InvalidIceberg);
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_source.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        deposit,
+    )?;
+
+    let iceberg = &mut ctx.accounts.iceberg;
+    iceberg.user = ctx.accounts.user.key();
+    iceberg.iceberg_id = iceberg_id;
+    iceberg.pool_id = ctx.accounts.pool_registry.pool_id;
+    iceberg.tranche_in = tranche_in;
+    iceberg.tranche_min_out = tranche_min_out;
+    iceberg.active_order = None;
+    iceberg.tranches = 0;
+
+    msg!("Iceberg {} created by user {} with tranches of {}", iceberg_id, iceberg.user, tranche_in);
+
+    Ok(())
+}
+
+pub fn close_iceberg(ctx: Context<CloseIceberg>) -> Result<()> {
+    let pool_id = ctx.accounts.iceberg.pool_id;
+    let seeds: &[&[u8]] = &[POOL_AUTHORITY_SEED, pool_id.as_ref(), &[ctx.bumps.pool_authority]];
+    let refund = ctx.accounts.escrow.amount;
+
+    if refund > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.escrow.to_account_info(),
+                    to: ctx.accounts.user_destination.to_account_info(),
+                    authority: ctx.accounts.pool_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            refund,
+        )?;
+    }
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.escrow.to_account_info(),
+            destination: ctx.accounts.user.to_account_info(),
+            authority: ctx.accounts.pool_authority.to_account_info(),
+        },
+        &[seeds],
+    ))?;
+
+    msg!(
+        "Iceberg {} closed after {} tranches, {} refunded",
+        ctx.accounts.iceberg.iceberg_id,
+        ctx.accounts.iceberg.tranches,
+        refund
+    );
+
+    Ok(())
+}
@@ -0,0 +1,209 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// The repeating order a template enqueues
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct OrderTemplateParams {
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+    pub is_base_input: bool,
+    /// Seconds between occurrences
+    pub interval: i64,
+    pub total_occurrences: u32,
+}
+
+/// Create an order template on a pool and fund its escrow with `deposit` of
+/// `mint`, the token its occurrences sell. Pools with a compliance program
+/// only take orders through `submit_order`.
+#[derive(Accounts)]
+#[instruction(template_id: u64)]
+pub struct CreateOrderTemplate<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        constraint = !fifo_state.emergency_pause @ ContinuumError::EmergencyPause,
+    )]
+    pub fifo_state: Box<Account<'info, FifoState>>,
+
+    #[account(
+        seeds = [POOL_REGISTRY_SEED, pool_registry.pool_id.as_ref()],
+        bump,
+        constraint = pool_registry.is_active @ ContinuumError::PoolNotRegistered,
+        constraint = !pool_registry.is_paused @ ContinuumError::PoolPaused,
+        constraint = pool_registry.compliance_program().is_none() @ ContinuumError::MissingComplianceCheck,
+    )]
+    pub pool_registry: Box<Account<'info, CpSwapPoolRegistry>>,
+
+    #[account(
+        init,
+        payer = user,
+        space = OrderTemplate::LEN,
+        seeds = [ORDER_TEMPLATE_SEED, user.key().as_ref(), &template_id.to_le_bytes()],
+        bump
+    )]
+    pub template: Box<Account<'info, OrderTemplate>>,
+
+    #[account(
+        constraint = mint.key() == pool_registry.token_0 || mint.key() == pool_registry.token_1
+            @ ContinuumError::InvalidTemplate,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [TEMPLATE_ESCROW_SEED, template.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = pool_authority,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: This is a PDA that owns the template escrow
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, pool_registry.pool_id.as_ref()],
+        bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user,
+    )]
+    pub user_source: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Stop a template, refunding what is left in its escrow. Occurrences
+/// already enqueued but not executed can no longer be filled.
+#[derive(Accounts)]
+pub struct CloseOrderTemplate<'info> {
+    #[account(
+        mut,
+        close = user,
+        has_one = user @ ContinuumError::Unauthorized,
+        seeds = [ORDER_TEMPLATE_SEED, user.key().as_ref(), &template.template_id.to_le_bytes()],
+        bump,
+    )]
+    pub template: Box<Account<'info, OrderTemplate>>,
+
+    #[account(
+        mut,
+        seeds = [TEMPLATE_ESCROW_SEED, template.key().as_ref()],
+        bump,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: This is a PDA that owns the template escrow
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, template.pool_id.as_ref()],
+        bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = escrow.mint,
+    )]
+    pub user_destination: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn create_order_template(
+    ctx: Context<CreateOrderTemplate>,
+    template_id: u64,
+    params: OrderTemplateParams,
+    deposit: u64,
+) -> Result<()> {
+    require!(
+        params.amount_in > 0 && params.interval > 0 && params.total_occurrences > 0,
+        ContinuumError::InvalidTemplate
+    );
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_source.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        deposit,
+    )?;
+
+    let template = &mut ctx.accounts.template;
+    template.user = ctx.accounts.user.key();
+    template.template_id = template_id;
+    template.pool_id = ctx.accounts.pool_registry.pool_id;
+    template.amount_in = params.amount_in;
+    template.min_amount_out = params.min_amount_out;
+    template.is_base_input = params.is_base_input;
+    template.interval = params.interval;
+    template.total_occurrences = params.total_occurrences;
+    template.occurrences = 0;
+    template.next_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Order template {} created by user {}: {} occurrences every {}s",
+        template_id,
+        template.user,
+        params.total_occurrences,
+        params.interval
+    );
+
+    Ok(())
+}
+
+pub fn close_order_template(ctx: Context<CloseOrderTemplate>) -> Result<()> {
+    let pool_id = ctx.accounts.template.pool_id;
+    let seeds: &[&[u8]] = &[POOL_AUTHORITY_SEED, pool_id.as_ref(), &[ctx.bumps.pool_authority]];
+    let refund = ctx.accounts.escrow.amount;
+
+    if refund > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.escrow.to_account_info(),
+                    to: ctx.accounts.user_destination.to_account_info(),
+                    authority: ctx.accounts.pool_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            refund,
+        )?;
+    }
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.escrow.to_account_info(),
+            destination: ctx.accounts.user.to_account_info(),
+            authority: ctx.accounts.pool_authority.to_account_info(),
+        },
+        &[seeds],
+    ))?;
+
+    msg!(
+        "Order template {} closed after {} occurrences, {} refunded",
+        ctx.accounts.template.template_id,
+        ctx.accounts.template.occurrences,
+        refund
+    );
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Create an organization owned by the signer, with no members yet
+#[derive(Accounts)]
+#[instruction(org_id: u64)]
+pub struct CreateOrganization<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = Organization::LEN,
+        seeds = [ORGANIZATION_SEED, owner.key().as_ref(), &org_id.to_le_bytes()],
+        bump
+    )]
+    pub organization: Box<Account<'info, Organization>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Add a member to an organization, or replace an existing member's limits
+/// (owner only)
+#[derive(Accounts)]
+pub struct SetOrgMember<'info> {
+    #[account(
+        mut,
+        has_one = owner @ ContinuumError::Unauthorized,
+        seeds = [ORGANIZATION_SEED, owner.key().as_ref(), &organization.org_id.to_le_bytes()],
+        bump,
+    )]
+    pub organization: Box<Account<'info, Organization>>,
+
+    pub owner: Signer<'info>,
+}
+
+/// Remove a member from an organization; orders they already submitted stay
+/// queued (owner only)
+#[derive(Accounts)]
+pub struct RemoveOrgMember<'info> {
+    #[account(
+        mut,
+        has_one = owner @ ContinuumError::Unauthorized,
+        seeds = [ORGANIZATION_SEED, owner.key().as_ref(), &organization.org_id.to_le_bytes()],
+        bump,
+    )]
+    pub organization: Box<Account<'info, Organization>>,
+
+    pub owner: Signer<'info>,
+}
+
+pub fn create_organization(ctx: Context<CreateOrganization>, org_id: u64) -> Result<()> {
+    let organization = &mut ctx.accounts.organization;
+    organization.owner = ctx.accounts.owner.key();
+    organization.org_id = org_id;
+    organization.members = Vec::new();
+
+    msg!("Organization {} created by {}", org_id, organization.owner);
+
+    Ok(())
+}
+
+pub fn set_org_member(
+    ctx: Context<SetOrgMember>,
+    member: Pubkey,
+    max_amount_in: u64,
+    pools: Vec<Pubkey>,
+) -> Result<()> {
+    require!(pools.len() <= OrgMember::MAX_POOLS, ContinuumError::InvalidOrgMembers);
+
+    let organization = &mut ctx.accounts.organization;
+    let entry = OrgMember {
+        member,
+        max_amount_in,
+        pools,
+    };
+    match organization.members.iter_mut().find(|existing| existing.member == member) {
+        Some(existing) => *existing = entry,
+        None => {
+            require!(organization.members.len() < Organization::MAX_MEMBERS, ContinuumError::InvalidOrgMembers);
+            organization.members.push(entry);
+        }
+    }
+
+    msg!("Organization {} member {} may submit up to {}", organization.org_id, member, max_amount_in);
+
+    Ok(())
+}
+
+pub fn remove_org_member(ctx: Context<RemoveOrgMember>, member: Pubkey) -> Result<()> {
+    let organization = &mut ctx.accounts.organization;
+    let count = organization.members.len();
+    organization.members.retain(|existing| existing.member != member);
+    require!(organization.members.len() < count, ContinuumError::NotOrgMember);
+
+    msg!("Organization {} removed member {}", organization.org_id, member);
+
+    Ok(())
+}
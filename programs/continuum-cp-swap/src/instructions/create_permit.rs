@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{self, Approve, Revoke, Token, TokenAccount};
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Approve a permit for one of the signer's token accounts: the permit PDA
+/// becomes the account's delegate for as much as it holds, and orders naming
+/// the permit then spend through it, up to `allowance` until `expires_at`,
+/// without another approval.
+#[derive(Accounts)]
+pub struct CreatePermit<'info> {
+    #[account(
+        init,
+        payer = user,
+        space = Permit::LEN,
+        seeds = [PERMIT_SEED, token_account.key().as_ref()],
+        bump
+    )]
+    pub permit: Account<'info, Permit>,
+
+    #[account(
+        mut,
+        token::authority = user,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+/// Change what a permit's orders may still spend, and until when. Pending
+/// orders spend under the new terms.
+#[derive(Accounts)]
+pub struct UpdatePermit<'info> {
+    #[account(
+        mut,
+        has_one = user @ ContinuumError::Unauthorized,
+        seeds = [PERMIT_SEED, permit.token_account.as_ref()],
+        bump,
+    )]
+    pub permit: Account<'info, Permit>,
+
+    pub user: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+/// Close a permit, revoking its approval if it still holds one. Orders still
+/// naming it can no longer execute.
+#[derive(Accounts)]
+pub struct ClosePermit<'info> {
+    #[account(
+        mut,
+        close = user,
+        has_one = user @ ContinuumError::Unauthorized,
+        has_one = token_account @ ContinuumError::InvalidPermit,
+        seeds = [PERMIT_SEED, token_account.key().as_ref()],
+        bump,
+    )]
+    pub permit: Account<'info, Permit>,
+
+    #[account(mut)]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn create_permit(ctx: Context<CreatePermit>, allowance: u64, expires_at: i64) -> Result<()> {
+    let now = crate::clock::unix_timestamp(&ctx.accounts.clock, ctx.remaining_accounts);
+    require!(expires_at > now, ContinuumError::PermitExpired);
+
+    // The permit enforces the allowance, so the token approval never needs
+    // to change while it stands
+    token::approve(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Approve {
+                to: ctx.accounts.token_account.to_account_info(),
+                delegate: ctx.accounts.permit.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        u64::MAX,
+    )?;
+
+    let permit = &mut ctx.accounts.permit;
+    permit.user = ctx.accounts.user.key();
+    permit.token_account = ctx.accounts.token_account.key();
+    permit.mint = ctx.accounts.token_account.mint;
+    permit.allowance = allowance;
+    permit.expires_at = expires_at;
+    permit.created_at = now;
+
+    msg!(
+        "User {} permitted orders to spend {} from {} until {}",
+        permit.user,
+        allowance,
+        permit.token_account,
+        expires_at
+    );
+
+    Ok(())
+}
+
+pub fn update_permit(ctx: Context<UpdatePermit>, allowance: u64, expires_at: i64) -> Result<()> {
+    let now = crate::clock::unix_timestamp(&ctx.accounts.clock, ctx.remaining_accounts);
+    require!(expires_at > now, ContinuumError::PermitExpired);
+
+    let permit = &mut ctx.accounts.permit;
+    permit.allowance = allowance;
+    permit.expires_at = expires_at;
+
+    msg!("Permit for {} now allows {} until {}", permit.token_account, allowance, expires_at);
+
+    Ok(())
+}
+
+pub fn close_permit(ctx: Context<ClosePermit>) -> Result<()> {
+    // The user may have approved someone else since
+    if ctx.accounts.token_account.delegate == COption::Some(ctx.accounts.permit.key()) {
+        token::revoke(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Revoke {
+                source: ctx.accounts.token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ))?;
+    }
+
+    msg!("Permit for {} closed", ctx.accounts.token_account.key());
+
+    Ok(())
+}
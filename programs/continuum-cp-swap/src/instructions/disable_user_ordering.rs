@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Leave per-user ordering once none of the user's ordered orders is pending
+#[derive(Accounts)]
+pub struct DisableUserOrdering<'info> {
+    #[account(
+        mut,
+        close = user,
+        seeds = [USER_ORDERING_SEED, user.key().as_ref()],
+        bump,
+        constraint = user_ordering.pending.is_empty() @ ContinuumError::UserOrderingPending,
+    )]
+    pub user_ordering: Account<'info, UserOrdering>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+pub fn disable_user_ordering(ctx: Context<DisableUserOrdering>) -> Result<()> {
+    msg!("User ordering disabled for {}", ctx.accounts.user.key());
+
+    Ok(())
+}
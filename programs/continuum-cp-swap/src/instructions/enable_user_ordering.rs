@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+
+/// Opt the user into per-user ordering for the orders they submit from now on
+#[derive(Accounts)]
+pub struct EnableUserOrdering<'info> {
+    #[account(
+        init,
+        payer = user,
+        space = UserOrdering::LEN,
+        seeds = [USER_ORDERING_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub user_ordering: Account<'info, UserOrdering>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn enable_user_ordering(ctx: Context<EnableUserOrdering>) -> Result<()> {
+    let user_ordering = &mut ctx.accounts.user_ordering;
+    user_ordering.user = ctx.accounts.user.key();
+    user_ordering.pending = Vec::new();
+
+    msg!("User ordering enabled for {}", user_ordering.user);
+
+    Ok(())
+}
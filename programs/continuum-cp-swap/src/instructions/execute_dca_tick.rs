@@ -0,0 +1,235 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::consts::*;
+use crate::cp_swap::{self, Swap, SwapPool};
+use crate::state::*;
+use crate::errors::*;
+use super::execute_order::{charge_fees, observe_price, record_relayer_quota, swap_deltas, within_limit, FeeCharge};
+
+/// Execute a DCA schedule's due tick, swapping it from the schedule's escrow
+/// into its destination. Any relayer the pool allows to execute may sign,
+/// registered unless execution is permissionless, as for `execute_order`.
+/// The escrow pays fees on top of each tick as for an order, at the flat
+/// protocol fee, and a tick is cut short so its fees fit the budget left.
+#[derive(Accounts)]
+pub struct ExecuteDcaTick<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        constraint = !fifo_state.emergency_pause @ ContinuumError::EmergencyPause,
+    )]
+    pub fifo_state: Box<Account<'info, FifoState>>,
+
+    #[account(
+        mut,
+        seeds = [DCA_SCHEDULE_SEED, schedule.user.as_ref(), &schedule.schedule_id.to_le_bytes()],
+        bump,
+    )]
+    pub schedule: Box<Account<'info, DcaSchedule>>,
+
+    #[account(
+        mut,
+        seeds = [POOL_REGISTRY_SEED, schedule.pool_id.as_ref()],
+        bump,
+        constraint = pool_registry.is_active @ ContinuumError::PoolNotRegistered,
+        constraint = !pool_registry.is_paused @ ContinuumError::PoolPaused,
+    )]
+    pub pool_registry: Box<Account<'info, CpSwapPoolRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, schedule.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Box<Account<'info, PoolFifoState>>,
+
+    /// CHECK: This is a PDA that owns the schedule escrow and signs the swap
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, schedule.pool_id.as_ref()],
+        bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [DCA_ESCROW_SEED, schedule.key().as_ref()],
+        bump,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        address = schedule.destination @ ContinuumError::InvalidRecipient,
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The pool's relayer list; may be uninitialized, see `crate::optional`
+    #[account(
+        seeds = [POOL_RELAYERS_SEED, schedule.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_relayers: UncheckedAccount<'info>,
+
+    /// The executor's registration, whose posted fee applies on pools with a
+    /// cap; required unless execution is permissionless
+    #[account(
+        seeds = [RELAYER_SEED, executor.key().as_ref()],
+        bump,
+    )]
+    pub relayer_info: Option<Account<'info, RelayerInfo>>,
+
+    /// CHECK: The executor's quota PDA; may be uninitialized, see `crate::optional`
+    #[account(
+        mut,
+        seeds = [RELAYER_QUOTA_SEED, executor.key().as_ref()],
+        bump,
+    )]
+    pub relayer_quota: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [FEE_LEDGER_SEED, schedule.pool_id.as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Box<Account<'info, PoolFeeLedger>>,
+
+    /// Fee vault for the schedule's input token, owned by the pool authority
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, schedule.pool_id.as_ref(), schedule.input_mint.as_ref()],
+        bump,
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [RELAYER_FEES_SEED, schedule.pool_id.as_ref(), executor.key().as_ref()],
+        bump,
+    )]
+    pub relayer_fee_balance: Box<Account<'info, RelayerFeeBalance>>,
+
+    pub executor: Signer<'info>,
+
+    /// CHECK: The CP-Swap program recorded in the FIFO state
+    #[account(address = fifo_state.cp_swap_program @ ContinuumError::InvalidCpSwapProgram)]
+    pub cp_swap_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub clock: Sysvar<'info, Clock>,
+
+    // The pool's CP-Swap accounts follow in remaining_accounts, as for
+    // `execute_order`
+}
+
+pub fn execute_dca_tick<'info>(ctx: Context<'_, '_, 'info, 'info, ExecuteDcaTick<'info>>) -> Result<()> {
+    let now = crate::clock::unix_timestamp(&ctx.accounts.clock, ctx.remaining_accounts);
+    require!(ctx.accounts.schedule.is_due(now), ContinuumError::DcaTickNotDue);
+    let assigned = crate::optional::load::<PoolRelayers>(&ctx.accounts.pool_relayers)?
+        .is_none_or(|pool_relayers| pool_relayers.allows(&ctx.accounts.executor.key()));
+    require!(assigned, ContinuumError::RelayerNotAssigned);
+    require!(
+        ctx.accounts.fifo_state.permissionless_execution || ctx.accounts.relayer_info.is_some(),
+        ContinuumError::RelayerNotRegistered
+    );
+    if let Some(account) = crate::sanitize::writable_program_account(ctx.remaining_accounts) {
+        msg!("Writable Continuum account {} passed to the swap", account.key());
+        return err!(ContinuumError::InvalidRemainingAccounts);
+    }
+    let swap_pool = SwapPool::parse(ctx.remaining_accounts)?;
+    require!(
+        swap_pool.matches(&ctx.accounts.pool_registry) && swap_pool.sells(&ctx.accounts.schedule.input_mint),
+        ContinuumError::InvalidRemainingAccounts
+    );
+
+    let pool_id = ctx.accounts.schedule.pool_id;
+    let protocol_fee_bps = ctx.accounts.fifo_state.protocol_fee_bps;
+    let relayer_fee_bps = ctx.accounts.pool_registry.relayer_fee_bps(
+        ctx.accounts.fifo_state.relayer_fee_bps,
+        ctx.accounts.relayer_info.as_ref().map(|info| info.fee_bps),
+    );
+    let (amount_in, min_amount_out) = ctx.accounts.schedule.next_tick(protocol_fee_bps + relayer_fee_bps);
+
+    // The pool authority signs for its escrow
+    let swap = Swap {
+        payer: ctx.accounts.pool_authority.to_account_info(),
+        input_token_account: ctx.accounts.escrow.to_account_info(),
+        output_token_account: ctx.accounts.destination.to_account_info(),
+        pool: swap_pool,
+    };
+
+    let escrow_start_balance = ctx.accounts.escrow.amount;
+    let start_balance = ctx.accounts.destination.amount;
+    let pool_authority_seeds: &[&[u8]] = &[
+        POOL_AUTHORITY_SEED,
+        pool_id.as_ref(),
+        &[ctx.bumps.pool_authority],
+    ];
+    cp_swap::swap_base_input(&ctx.accounts.cp_swap_program, &swap, &[pool_authority_seeds], amount_in, min_amount_out)?;
+
+    ctx.accounts.escrow.reload()?;
+    ctx.accounts.destination.reload()?;
+    let (amount_spent, amount_out) = swap_deltas(
+        (escrow_start_balance, ctx.accounts.escrow.amount),
+        (start_balance, ctx.accounts.destination.amount),
+    )?;
+    require!(within_limit(true, amount_spent, amount_out, min_amount_out), ContinuumError::SlippageExceeded);
+
+    let input_mint = ctx.accounts.escrow.mint;
+    ctx.accounts.pool_registry.record_volume(&input_mint, amount_spent, amount_out, now);
+    observe_price(&mut ctx.accounts.pool_registry, &swap.pool, &input_mint, &ctx.accounts.destination.mint, now);
+
+    let amount_0 = if input_mint == ctx.accounts.pool_registry.token_0 { amount_spent } else { amount_out };
+    require!(record_relayer_quota(&ctx.accounts.relayer_quota, amount_0)?, ContinuumError::RelayerQuotaExceeded);
+    let (protocol_fee, relayer_fee) =
+        ctx.accounts.fifo_state.compute_fees_with(amount_spent, protocol_fee_bps, relayer_fee_bps);
+    if protocol_fee > 0 || relayer_fee > 0 {
+        charge_fees(
+            FeeCharge {
+                token_program: ctx.accounts.token_program.to_account_info(),
+                source: ctx.accounts.escrow.to_account_info(),
+                mint: input_mint,
+                fee_vault: ctx.accounts.fee_vault.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+                authority_seeds: pool_authority_seeds,
+                fee_ledger: &mut ctx.accounts.fee_ledger,
+                relayer_fee_balance: &mut ctx.accounts.relayer_fee_balance,
+                insurance_share_bps: ctx.accounts.fifo_state.insurance_share_bps,
+            },
+            protocol_fee,
+            relayer_fee,
+        )?;
+    }
+
+    let schedule = &mut ctx.accounts.schedule;
+    let charged = amount_spent
+        .checked_add(protocol_fee)
+        .and_then(|charged| charged.checked_add(relayer_fee))
+        .ok_or(ContinuumError::FeeOverflow)?;
+    schedule.record_tick(charged, amount_out, now);
+
+    emit!(DcaTickExecuted {
+        event_sequence: ctx.accounts.pool_fifo.next_event_sequence(),
+        schedule: schedule.key(),
+        user: schedule.user,
+        pool_id,
+        tick: schedule.ticks_executed,
+        amount_in,
+        amount_out,
+        budget_remaining: schedule.budget_remaining,
+        next_execution_time: schedule.next_execution_time,
+        executor: ctx.accounts.executor.key(),
+        protocol_fee,
+        relayer_fee,
+    });
+
+    msg!(
+        "DCA schedule {} tick {}: {} in, {} out, {} left",
+        schedule.schedule_id,
+        schedule.ticks_executed,
+        amount_in,
+        amount_out,
+        schedule.budget_remaining
+    );
+
+    Ok(())
+}
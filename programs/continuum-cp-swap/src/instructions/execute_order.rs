@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{
-    program::invoke_signed,
-    instruction::{Instruction, AccountMeta},
-};
-use anchor_spl::token::{Token, TokenAccount};
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::memo::{self, BuildMemo, Memo};
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+use crate::callback::FillNotification;
+use crate::cp_swap::{self, Swap, SwapPool};
+use crate::oracle::OraclePrice;
+use crate::post_fill::PostFillAccounts;
+use crate::consts::*;
 use crate::state::*;
 use crate::errors::*;
 
@@ -11,159 +14,1049 @@ use crate::errors::*;
 #[instruction(expected_sequence: u64)]
 pub struct ExecuteOrder<'info> {
     #[account(
-        seeds = [b"fifo_state"],
+        seeds = [FIFO_STATE_SEED],
         bump,
     )]
     pub fifo_state: Account<'info, FifoState>,
     
     #[account(
         mut,
-        seeds = [b"order", order_state.user.as_ref(), &expected_sequence.to_le_bytes()],
+        seeds = [ORDER_SEED, order_state.pool_id.as_ref(), &expected_sequence.to_le_bytes()],
         bump,
-        constraint = order_state.sequence == expected_sequence @ ContinuumError::InvalidSequence,
-        constraint = order_state.status == OrderStatus::Pending @ ContinuumError::InvalidOrderStatus,
     )]
     pub order_state: Account<'info, OrderState>,
     
     #[account(
-        seeds = [b"pool_registry", order_state.pool_id.as_ref()],
+        mut,
+        seeds = [POOL_REGISTRY_SEED, order_state.pool_id.as_ref()],
         bump,
     )]
     pub pool_registry: Account<'info, CpSwapPoolRegistry>,
     
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, order_state.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Box<Account<'info, PoolFifoState>>,
+    
     /// The pool authority PDA that signs for the swap
     /// CHECK: This is a PDA that will be used to sign the CPI
     #[account(
-        seeds = [b"cp_pool_authority", order_state.pool_id.as_ref()],
+        seeds = [POOL_AUTHORITY_SEED, order_state.pool_id.as_ref()],
         bump
     )]
     pub pool_authority: UncheckedAccount<'info>,
     
+    /// CHECK: The user's ordering PDA; may be uninitialized, see `crate::ordering`
+    #[account(
+        mut,
+        seeds = [USER_ORDERING_SEED, order_state.user.as_ref()],
+        bump,
+    )]
+    pub user_ordering: UncheckedAccount<'info>,
+    
     /// The relayer executing the order
     #[account(mut)]
     pub executor: Signer<'info>,
     
-    /// User's source token account (for input tokens)
+    /// CHECK: The pool's relayer assignment PDA; may be uninitialized, see `crate::optional`
+    #[account(
+        seeds = [POOL_RELAYERS_SEED, order_state.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_relayers: UncheckedAccount<'info>,
+    
+    /// The executor's registration, whose posted fee applies on pools with a
+    /// cap; required unless execution is permissionless
+    #[account(
+        seeds = [RELAYER_SEED, executor.key().as_ref()],
+        bump,
+    )]
+    pub relayer_info: Option<Account<'info, RelayerInfo>>,
+    
+    /// CHECK: The executor's quota PDA; may be uninitialized, see `crate::optional`
     #[account(
         mut,
-        constraint = user_source.owner == order_state.user,
+        seeds = [RELAYER_QUOTA_SEED, executor.key().as_ref()],
+        bump,
     )]
+    pub relayer_quota: UncheckedAccount<'info>,
+    
+    /// Source token account (for input tokens): the order's source account,
+    /// or one owned by the user
+    #[account(mut)]
     pub user_source: Box<Account<'info, TokenAccount>>,
     
-    /// User's destination token account (for output tokens)
+    /// Destination token account (for output tokens): the order's destination
+    /// account, or one owned by the order recipient
+    #[account(mut)]
+    pub user_destination: Box<Account<'info, TokenAccount>>,
+    
+    /// Optional receipt recording the fill, paid for by the executor
+    #[account(
+        init,
+        payer = executor,
+        space = ExecutionReceipt::LEN,
+        seeds = [RECEIPT_SEED, order_state.pool_id.as_ref(), &expected_sequence.to_le_bytes()],
+        bump
+    )]
+    pub receipt: Option<Box<Account<'info, ExecutionReceipt>>>,
+    
+    #[account(
+        mut,
+        seeds = [FEE_LEDGER_SEED, order_state.pool_id.as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Box<Account<'info, PoolFeeLedger>>,
+    
+    /// Fee vault for the input token, owned by the pool authority
     #[account(
         mut,
-        constraint = user_destination.owner == order_state.user,
+        seeds = [FEE_VAULT_SEED, order_state.pool_id.as_ref(), user_source.mint.as_ref()],
+        bump,
     )]
-    pub user_destination: Box<Account<'info, TokenAccount>>,
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: The fee tiers PDA; may be uninitialized, see `crate::optional`
+    #[account(
+        seeds = [FEE_SCHEDULE_SEED],
+        bump,
+    )]
+    pub fee_schedule: UncheckedAccount<'info>,
+    
+    /// CHECK: The user's volume PDA on this pool; may be uninitialized
+    #[account(
+        mut,
+        seeds = [USER_VOLUME_SEED, order_state.pool_id.as_ref(), order_state.user.as_ref()],
+        bump,
+    )]
+    pub user_volume: UncheckedAccount<'info>,
     
-    /// CHECK: The CP-Swap program
+    #[account(
+        mut,
+        seeds = [RELAYER_FEES_SEED, order_state.pool_id.as_ref(), executor.key().as_ref()],
+        bump,
+    )]
+    pub relayer_fee_balance: Box<Account<'info, RelayerFeeBalance>>,
+    
+    /// CHECK: The CP-Swap program recorded in the FIFO state
+    #[account(address = fifo_state.cp_swap_program @ ContinuumError::InvalidCpSwapProgram)]
     pub cp_swap_program: UncheckedAccount<'info>,
     
+    /// When present, the fill is tagged with an SPL Memo carrying the order reference
+    pub memo_program: Option<Program<'info, Memo>>,
+    
+    /// CHECK: The order's callback program, required when it names one and
+    /// checked against it in the handler
+    pub callback_program: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Accounts of the order's post-fill action, checked in `crate::post_fill`
+    pub post_fill_authority: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: The output mint, written by a burn
+    #[account(mut)]
+    pub output_mint: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: The staking program of a swap-and-stake
+    pub post_fill_program: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: The staking program's deposit account, checked in `crate::post_fill`
+    #[account(mut)]
+    pub post_fill_target: Option<UncheckedAccount<'info>>,
+    
+    pub approved_stake_program: Option<Box<Account<'info, ApprovedStakeProgram>>>,
+    
+    /// The order's parent, required when it has one
+    pub parent_order: Option<Box<Account<'info, OrderState>>>,
+    
+    /// CHECK: Price update account of the order's trailing stop or trigger, checked in `crate::oracle`
+    pub oracle: Option<UncheckedAccount<'info>>,
+    
+    /// The order's shared escrow, required when it has one
+    #[account(mut)]
+    pub shared_escrow: Option<Box<Account<'info, SharedEscrow>>>,
+    
+    /// Mint of the source account, required with `order_escrow`
+    #[account(address = user_source.mint @ ContinuumError::InvalidSource)]
+    pub input_mint: Option<Box<Account<'info, Mint>>>,
+    
+    /// Required for an order spending by delegation: what is left of the
+    /// user's approval passes through it back to them, using the approval
+    /// up. Created and closed within the execution.
+    #[account(
+        init,
+        payer = executor,
+        seeds = [ESCROW_SEED, order_state.key().as_ref()],
+        bump,
+        token::mint = input_mint,
+        token::authority = pool_authority,
+    )]
+    pub order_escrow: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// The order's permit, required when it has one, which signs the swap
+    /// and is charged for it
+    #[account(mut)]
+    pub permit: Option<Box<Account<'info, Permit>>>,
+    
+    /// The account an escrowed order's deposit came from, required when it
+    /// has one, which is refunded what the vault, `user_source`, has left
+    #[account(mut)]
+    pub escrow_refund: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// CHECK: The order's user, receiving the rent of an escrowed order's vault
+    #[account(
+        mut,
+        address = order_state.user @ ContinuumError::Unauthorized,
+    )]
+    pub user: Option<UncheckedAccount<'info>>,
+    
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
     pub clock: Sysvar<'info, Clock>,
     
-    // Remaining accounts are passed through to CP-Swap swap instruction
+    // The pool's CP-Swap accounts follow in remaining_accounts, see
+    // `cp_swap::SwapPool::parse`
 }
 
-pub fn execute_order(
-    ctx: Context<ExecuteOrder>,
+pub fn execute_order<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteOrder<'info>>,
     expected_sequence: u64,
+    max_fill_amount: Option<u64>,
 ) -> Result<()> {
     let pool_authority_bump = ctx.bumps.pool_authority;
     let pool_id = ctx.accounts.order_state.pool_id;
     let sequence = ctx.accounts.order_state.sequence;
     let user = ctx.accounts.order_state.user;
+    let trace_id = ctx.accounts.order_state.trace_id;
     let is_base_input = ctx.accounts.order_state.is_base_input;
-    let amount_in = ctx.accounts.order_state.amount_in;
-    let min_amount_out = ctx.accounts.order_state.min_amount_out;
+    let (amount_in, min_amount_out) = ctx.accounts.order_state.next_fill(max_fill_amount);
+    let executor = ctx.accounts.executor.key();
+    let execution = Execution { pool_id, sequence, trace_id, executor };
+    
+    // Checked here rather than as account constraints so a rejection can be
+    // reported with its reason before the transaction fails
+    if sequence != expected_sequence {
+        return Err(rejected(pool_id, expected_sequence, trace_id, executor, RejectionReason::InvalidSequence, ContinuumError::InvalidSequence));
+    }
+    let now = crate::clock::unix_timestamp(&ctx.accounts.clock, ctx.remaining_accounts);
+    check_turn(&execution, &ctx.accounts.pool_fifo, &ctx.accounts.order_state, now)?;
+    if max_fill_amount.is_some_and(|max_fill| !ctx.accounts.order_state.accepts_partial_fill(max_fill)) {
+        return Err(rejected(pool_id, sequence, trace_id, executor, RejectionReason::InvalidFillAmount, ContinuumError::InvalidFillAmount));
+    }
+    if !ctx.accounts.order_state.fill_allowed(amount_in) {
+        return Err(rejected(pool_id, sequence, trace_id, executor, RejectionReason::FillOrKill, ContinuumError::FillOrKill));
+    }
+    if ctx.accounts.order_state.twap.is_some_and(|twap| !twap.slice_due(now)) {
+        return Err(rejected(pool_id, sequence, trace_id, executor, RejectionReason::SliceNotDue, ContinuumError::SliceNotDue));
+    }
+    let parent = ctx.accounts.parent_order.as_ref().map(|parent| (parent.key(), &***parent));
+    if !ctx.accounts.order_state.parent_filled(parent) {
+        return Err(rejected(pool_id, sequence, trace_id, executor, RejectionReason::ParentNotFilled, ContinuumError::ParentNotFilled));
+    }
+    if let Some(mut stop) = ctx.accounts.order_state.trailing_stop {
+        let price = ctx.accounts.oracle.as_ref()
+            .filter(|oracle| oracle.key() == stop.oracle)
+            .and_then(|oracle| OraclePrice::load(oracle, now).ok());
+        // Ratchet first, so a price that moved the stop can't also trigger it
+        let triggered = price.is_some_and(|price| {
+            stop.observe(price.price);
+            stop.triggered(price.price)
+        });
+        if !triggered {
+            return Err(rejected(pool_id, sequence, trace_id, executor, RejectionReason::StopNotTriggered, ContinuumError::StopNotTriggered));
+        }
+        ctx.accounts.order_state.trailing_stop = Some(stop);
+    }
+    if let Some(trigger) = ctx.accounts.order_state.trigger {
+        let triggered = ctx.accounts.oracle.as_ref()
+            .filter(|oracle| oracle.key() == trigger.oracle)
+            .and_then(|oracle| OraclePrice::load(oracle, now).ok())
+            .is_some_and(|price| trigger.triggered(price.price));
+        if !triggered {
+            return Err(rejected(pool_id, sequence, trace_id, executor, RejectionReason::TriggerNotReached, ContinuumError::TriggerNotReached));
+        }
+    }
+    // The swap is handed the same vaults, which are checked against the
+    // registry below
+    let swap_pool = SwapPool::parse(ctx.remaining_accounts).ok();
+    check_limit(
+        &execution,
+        &ctx.accounts.order_state,
+        swap_pool.as_ref(),
+        &ctx.accounts.user_source.mint,
+        &ctx.accounts.user_destination.mint,
+    )?;
+    let escrow_matches = ctx.accounts.order_state.shared_escrow ==
+        ctx.accounts.shared_escrow.as_ref().map(|escrow| escrow.key());
+    let permit_matches = ctx.accounts.order_state.permit ==
+        ctx.accounts.permit.as_ref().map(|permit| permit.key());
+    let source_ok = source_matches(
+        &ctx.accounts.order_state,
+        &ctx.accounts.user_source.key(),
+        &ctx.accounts.user_source,
+        ctx.accounts.escrow_refund.as_ref().map(|refund| refund.key()),
+        ctx.accounts.user.as_ref().map(|user| user.key()),
+    );
+    if !escrow_matches || !permit_matches || !source_ok {
+        return Err(rejected(pool_id, sequence, trace_id, executor, RejectionReason::InvalidSource, ContinuumError::InvalidSource));
+    }
+    // An order spending by delegation swaps with its escrow PDA as the
+    // delegate, which must be the one the user approved
+    let order_key = ctx.accounts.order_state.key();
+    let delegated_input = ctx.accounts.order_state.delegated_input;
+    let escrow_bump = match (delegated_input, ctx.accounts.order_escrow.as_ref()) {
+        (true, Some(order_escrow)) => {
+            let (address, bump) = Pubkey::find_program_address(&[ESCROW_SEED, order_key.as_ref()], &crate::ID);
+            (order_escrow.key() == address && ctx.accounts.user_source.delegate == COption::Some(address)).then_some(bump)
+        }
+        // Never signed with: the pool authority signs instead
+        (false, None) => Some(0),
+        _ => None,
+    };
+    let Some(escrow_bump) = escrow_bump else {
+        return Err(rejected(pool_id, sequence, trace_id, executor, RejectionReason::InvalidSource, ContinuumError::InvalidSource));
+    };
+    if let Some(permit) = ctx.accounts.permit.as_ref() {
+        if !permit.covers(ctx.accounts.order_state.max_input(), now) {
+            let error = if permit.is_expired(now) { ContinuumError::PermitExpired } else { ContinuumError::PermitExceeded };
+            return Err(rejected(pool_id, sequence, trace_id, executor, RejectionReason::PermitUnavailable, error));
+        }
+    }
+    let post_fill = ctx.accounts.order_state.post_fill;
+    let destination_ok = match post_fill {
+        Some(_) => ctx.accounts.user_destination.key() == crate::post_fill::vault_address(&ctx.accounts.user_destination.mint),
+        None => ctx.accounts.order_state.accepts_destination(&ctx.accounts.user_destination.key(), &ctx.accounts.user_destination.owner),
+    };
+    if !destination_ok {
+        return Err(rejected(pool_id, sequence, trace_id, executor, RejectionReason::InvalidRecipient, ContinuumError::InvalidRecipient));
+    }
+    if post_fill.is_some_and(|action| !post_fill_accounts(ctx.accounts).validate(&action)) {
+        return Err(rejected(pool_id, sequence, trace_id, executor, RejectionReason::InvalidPostFill, ContinuumError::InvalidPostFill));
+    }
+    let assigned = crate::optional::load::<PoolRelayers>(&ctx.accounts.pool_relayers)?
+        .is_none_or(|pool_relayers| pool_relayers.allows(&executor));
+    check_executor(&execution, &ctx.accounts.fifo_state, assigned, ctx.accounts.relayer_info.is_some())?;
+    let callback_matches = ctx.accounts.order_state.callback_program ==
+        ctx.accounts.callback_program.as_ref().map(|program| program.key());
+    if !callback_matches {
+        return Err(rejected(pool_id, sequence, trace_id, executor, RejectionReason::InvalidCallback, ContinuumError::InvalidCallback));
+    }
+    let swap_pool = check_swap_accounts(
+        &execution,
+        &ctx.accounts.user_ordering,
+        &order_key,
+        &ctx.accounts.order_state,
+        &ctx.accounts.pool_registry,
+        ctx.remaining_accounts,
+        swap_pool,
+    )?;
     
     // Log sequence information for debugging
-    msg!("Execute order - Expected sequence param: {}, Order sequence: {}, Current FIFO sequence: {}", 
+    msg!("Execute order - Expected sequence param: {}, Order sequence: {}, Current pool sequence: {}", 
         expected_sequence, 
         sequence,
-        ctx.accounts.fifo_state.current_sequence
+        ctx.accounts.pool_fifo.current_sequence
     );
     msg!("Order user: {}, Order pool: {}", user, pool_id);
     
-    // Build the swap instruction data
-    let mut ix_data = Vec::new();
+    // The swap is signed by the pool authority, or for an order spending by
+    // delegation by its escrow PDA, or through a permit by the permit
+    let pool_authority_seeds: &[&[u8]] = &[
+        POOL_AUTHORITY_SEED,
+        pool_id.as_ref(),
+        &[pool_authority_bump],
+    ];
+    let escrow_seeds: &[&[u8]] = &[ESCROW_SEED, order_key.as_ref(), &[escrow_bump]];
+    let source_key = ctx.accounts.user_source.key();
+    let permit_bump = match ctx.accounts.permit.as_ref() {
+        Some(_) => Pubkey::find_program_address(&[PERMIT_SEED, source_key.as_ref()], &crate::ID).1,
+        None => 0,
+    };
+    let permit_seeds: &[&[u8]] = &[PERMIT_SEED, source_key.as_ref(), &[permit_bump]];
+    let (input_authority, input_seeds) = match (ctx.accounts.order_escrow.as_ref(), ctx.accounts.permit.as_ref()) {
+        (Some(order_escrow), _) => (order_escrow.to_account_info(), escrow_seeds),
+        (None, Some(permit)) => (permit.to_account_info(), permit_seeds),
+        (None, None) => (ctx.accounts.pool_authority.to_account_info(), pool_authority_seeds),
+    };
     
-    if is_base_input {
-        // swap_base_input discriminator
-        ix_data.extend_from_slice(&[143, 190, 90, 218, 196, 30, 51, 222]); 
-        ix_data.extend_from_slice(&amount_in.to_le_bytes());
-        ix_data.extend_from_slice(&min_amount_out.to_le_bytes());
-    } else {
-        // swap_base_output discriminator
-        ix_data.extend_from_slice(&[55, 217, 98, 86, 163, 74, 180, 173]);
-        ix_data.extend_from_slice(&min_amount_out.to_le_bytes()); // max_amount_in
-        ix_data.extend_from_slice(&amount_in.to_le_bytes()); // amount_out
+    // The input authority pays from the user's source account into their
+    // destination, through the pool's accounts
+    let swap = Swap {
+        payer: input_authority.clone(),
+        input_token_account: ctx.accounts.user_source.to_account_info(),
+        output_token_account: ctx.accounts.user_destination.to_account_info(),
+        pool: swap_pool,
+    };
+    
+    // A shared escrow lets the pool authority spend this order's reservation
+    // and the unreserved balance, for the swap and its fees
+    if let Some(shared_escrow) = ctx.accounts.shared_escrow.as_ref() {
+        let allowance = shared_escrow.available(ctx.accounts.user_source.amount) + ctx.accounts.order_state.max_input();
+        crate::shared_escrow::approve(
+            ctx.accounts.token_program.to_account_info(),
+            shared_escrow,
+            ctx.accounts.user_source.to_account_info(),
+            ctx.accounts.pool_authority.to_account_info(),
+            allowance,
+        )?;
     }
     
-    // Build account metas
-    let mut account_metas = vec![];
+    // Get the starting balances for calculating amounts in and out
+    let source_start_balance = ctx.accounts.user_source.amount;
+    let start_balance = ctx.accounts.user_destination.amount;
     
-    // Add the pool authority as the first account (signer)
-    account_metas.push(AccountMeta::new_readonly(ctx.accounts.pool_authority.key(), true));
+    cp_swap::swap(
+        &ctx.accounts.cp_swap_program,
+        &swap,
+        &[input_seeds],
+        is_base_input,
+        amount_in,
+        min_amount_out,
+    )?;
     
-    // Add user token accounts
-    account_metas.push(AccountMeta::new(ctx.accounts.user_source.key(), false));
-    account_metas.push(AccountMeta::new(ctx.accounts.user_destination.key(), false));
+    // Charge fees on top of the input actually spent. Like the swap itself,
+    // this relies on the input authority being approved on the user's source account.
+    let fill = settle_execution(
+        &execution,
+        Settlement {
+            fifo_state: &ctx.accounts.fifo_state,
+            pool_registry: &mut ctx.accounts.pool_registry,
+            pool_fifo: &mut ctx.accounts.pool_fifo,
+            user_ordering: &ctx.accounts.user_ordering,
+            relayer_quota: &ctx.accounts.relayer_quota,
+            posted_fee_bps: ctx.accounts.relayer_info.as_ref().map(|info| info.fee_bps),
+            fee_schedule: &ctx.accounts.fee_schedule,
+            user_volume: &ctx.accounts.user_volume,
+            destination: ctx.accounts.user_destination.to_account_info(),
+            fees: FeeCharge {
+                token_program: ctx.accounts.token_program.to_account_info(),
+                source: ctx.accounts.user_source.to_account_info(),
+                mint: ctx.accounts.user_source.mint,
+                fee_vault: ctx.accounts.fee_vault.to_account_info(),
+                authority: input_authority.clone(),
+                authority_seeds: input_seeds,
+                fee_ledger: &mut ctx.accounts.fee_ledger,
+                relayer_fee_balance: &mut ctx.accounts.relayer_fee_balance,
+                insurance_share_bps: ctx.accounts.fifo_state.insurance_share_bps,
+            },
+            vault_refund: ctx.accounts.escrow_refund.as_ref().zip(ctx.accounts.user.as_ref())
+                .map(|(refund, user)| (refund.to_account_info(), user.to_account_info())),
+            pool_authority: ctx.accounts.pool_authority.to_account_info(),
+            pool_authority_seeds,
+        },
+        &mut ctx.accounts.order_state,
+        &order_key,
+        &swap.pool,
+        (amount_in, min_amount_out),
+        (source_start_balance, start_balance),
+        now,
+    )?;
     
-    // Add remaining accounts (pool state, vaults, etc.)
-    for account in ctx.remaining_accounts.iter() {
-        account_metas.push(if account.is_writable {
-            AccountMeta::new(account.key(), false)
-        } else {
-            AccountMeta::new_readonly(account.key(), false)
-        });
+    if let (true, Some(shared_escrow)) = (fill.closed, ctx.accounts.shared_escrow.as_mut()) {
+        shared_escrow.release(ctx.accounts.order_state.max_input());
+        crate::shared_escrow::revoke(
+            ctx.accounts.token_program.to_account_info(),
+            shared_escrow,
+            ctx.accounts.user_source.to_account_info(),
+        )?;
     }
     
-    // Create the instruction
-    let ix = Instruction {
-        program_id: ctx.accounts.cp_swap_program.key(),
-        accounts: account_metas,
-        data: ix_data,
-    };
+    if let Some(permit) = ctx.accounts.permit.as_mut() {
+        let charged = fill.amount_in
+            .checked_add(fill.protocol_fee)
+            .and_then(|charged| charged.checked_add(fill.relayer_fee))
+            .ok_or(ContinuumError::FeeOverflow)?;
+        require!(permit.spend(charged), ContinuumError::PermitExceeded);
+    }
     
-    // Execute swap with pool authority signer
-    let pool_authority_seeds = &[
-        b"cp_pool_authority",
-        pool_id.as_ref(),
-        &[pool_authority_bump],
-    ];
+    if let (true, Some(order_escrow)) = (fill.closed, ctx.accounts.order_escrow.as_ref()) {
+        use_up_delegation(ctx.accounts, order_escrow, escrow_seeds, pool_authority_seeds)?;
+    }
     
-    // Get the starting balance for calculating amount_out
-    let start_balance = ctx.accounts.user_destination.amount;
+    if let Some(receipt) = ctx.accounts.receipt.as_mut() {
+        receipt.sequence = sequence;
+        receipt.user = user;
+        receipt.pool_id = pool_id;
+        receipt.amount_in = fill.amount_in;
+        receipt.amount_out = fill.amount_out;
+        receipt.executor = ctx.accounts.executor.key();
+        receipt.slot = ctx.accounts.clock.slot;
+        receipt.executed_at = now;
+    }
     
-    invoke_signed(
-        &ix,
-        &[
-            ctx.accounts.pool_authority.to_account_info(),
-            ctx.accounts.user_source.to_account_info(),
-            ctx.accounts.user_destination.to_account_info(),
-        ],
-        &[pool_authority_seeds],
-    )?;
+    if let Some(memo_program) = ctx.accounts.memo_program.as_ref() {
+        memo::build_memo(
+            CpiContext::new(memo_program.to_account_info(), BuildMemo {}),
+            order_reference(&pool_id, sequence).as_bytes(),
+        )?;
+    }
     
-    // Update order status
-    let order_state = &mut ctx.accounts.order_state;
-    order_state.status = OrderStatus::Executed;
-    order_state.executed_at = Some(ctx.accounts.clock.unix_timestamp);
+    if let Some(action) = post_fill {
+        let beneficiary = ctx.accounts.order_state.output_owner();
+        post_fill_accounts(ctx.accounts).run(&action, fill.amount_out, beneficiary)?;
+    }
     
-    // Reload destination account to get final balance
-    ctx.accounts.user_destination.reload()?;
-    let amount_out = ctx.accounts.user_destination.amount - start_balance;
+    if let Some(callback_program) = ctx.accounts.callback_program.as_ref() {
+        // Write the executed order out first so the callback reads it as filled
+        ctx.accounts.order_state.exit(&crate::ID)?;
+        crate::callback::notify(
+            callback_program,
+            &ctx.accounts.order_state.to_account_info(),
+            &FillNotification {
+                sequence,
+                user,
+                pool_id,
+                amount_in: fill.amount_in,
+                amount_out: fill.amount_out,
+            },
+        )?;
+    }
     
+    report_fill(&execution, &mut ctx.accounts.pool_fifo, &ctx.accounts.order_state, &fill);
+    
+    Ok(())
+}
+
+/// Who an execution is by and of, for reporting its rejection
+#[derive(Clone, Copy)]
+pub(crate) struct Execution {
+    pub pool_id: Pubkey,
+    pub sequence: u64,
+    pub trace_id: TraceId,
+    pub executor: Pubkey,
+}
+
+impl Execution {
+    /// Report the execution rejected for `reason`, see [`rejected`]
+    pub(crate) fn reject(&self, reason: RejectionReason, error: ContinuumError) -> Error {
+        rejected(self.pool_id, self.sequence, self.trace_id, self.executor, reason, error)
+    }
+}
+
+/// Reject an order that isn't pending, isn't due in its pool's line, or is
+/// outside its validity at `now`
+pub(crate) fn check_turn(execution: &Execution, pool_fifo: &PoolFifoState, order: &OrderState, now: i64) -> Result<()> {
+    if order.status != OrderStatus::Pending {
+        return Err(execution.reject(RejectionReason::InvalidOrderStatus, ContinuumError::InvalidOrderStatus));
+    }
+    if !pool_fifo.may_execute(order) {
+        return Err(execution.reject(RejectionReason::OutOfPoolOrder, ContinuumError::OutOfPoolOrder));
+    }
+    if !order.is_valid_at(now) {
+        return Err(execution.reject(RejectionReason::NotYetValid, ContinuumError::NotYetValid));
+    }
+    if order.is_expired(now) {
+        return Err(execution.reject(RejectionReason::Expired, ContinuumError::OrderExpired));
+    }
+    Ok(())
+}
+
+/// Reject an order whose limit price the reserves of `swap_pool`, selling
+/// `input_mint` for `output_mint`, don't reach
+pub(crate) fn check_limit(
+    execution: &Execution,
+    order: &OrderState,
+    swap_pool: Option<&SwapPool>,
+    input_mint: &Pubkey,
+    output_mint: &Pubkey,
+) -> Result<()> {
+    let reserves = vault_reserve(swap_pool.map(|pool| &pool.input_vault), input_mint)
+        .zip(vault_reserve(swap_pool.map(|pool| &pool.output_vault), output_mint));
+    if !order.limit_reached(reserves) {
+        return Err(execution.reject(RejectionReason::LimitNotReached, ContinuumError::LimitNotReached));
+    }
+    Ok(())
+}
+
+/// Whether `source`, at `source_key`, is the account the order spends from
+/// and, for an order with a vault, `escrow_refund` is its refund account and
+/// `user` its user, who gets the vault's rent back
+pub(crate) fn source_matches(
+    order: &OrderState,
+    source_key: &Pubkey,
+    source: &TokenAccount,
+    escrow_refund: Option<Pubkey>,
+    user: Option<Pubkey>,
+) -> bool {
+    order.escrow_refund == escrow_refund
+        && (order.escrow_refund.is_none() || user == Some(order.user))
+        && order.accepts_source(source_key, &source.owner, &source.mint)
+}
+
+/// Reject an executor the pool doesn't assign, or that isn't registered
+/// while execution isn't permissionless
+pub(crate) fn check_executor(execution: &Execution, fifo_state: &FifoState, assigned: bool, registered: bool) -> Result<()> {
+    if !assigned {
+        return Err(execution.reject(RejectionReason::RelayerNotAssigned, ContinuumError::RelayerNotAssigned));
+    }
+    if !fifo_state.permissionless_execution && !registered {
+        return Err(execution.reject(RejectionReason::RelayerNotRegistered, ContinuumError::RelayerNotRegistered));
+    }
+    Ok(())
+}
+
+/// Reject an order that isn't its user's next, or whose swap accounts, from
+/// which `swap_pool` was parsed, write to this program's accounts or aren't
+/// its pool's; returns the pool's accounts
+pub(crate) fn check_swap_accounts<'info>(
+    execution: &Execution,
+    user_ordering: &AccountInfo,
+    order_key: &Pubkey,
+    order: &OrderState,
+    registry: &CpSwapPoolRegistry,
+    swap_accounts: &[AccountInfo<'info>],
+    swap_pool: Option<SwapPool<'info>>,
+) -> Result<SwapPool<'info>> {
+    if !crate::ordering::is_next(user_ordering, order_key)? {
+        return Err(execution.reject(RejectionReason::OutOfUserOrder, ContinuumError::OutOfUserOrder));
+    }
+    if let Some(account) = crate::sanitize::writable_program_account(swap_accounts) {
+        msg!("Writable Continuum account {} passed to the swap", account.key());
+        return Err(execution.reject(RejectionReason::InvalidRemainingAccounts, ContinuumError::InvalidRemainingAccounts));
+    }
+    let Some(swap_pool) = swap_pool.filter(|pool| pool.matches(registry) && pool.sells(&order.input_mint)) else {
+        msg!("Swap accounts don't match pool {}", execution.pool_id);
+        return Err(execution.reject(RejectionReason::InvalidRemainingAccounts, ContinuumError::InvalidRemainingAccounts));
+    };
+    Ok(swap_pool)
+}
+
+/// The accounts a swap of an order is settled on
+pub(crate) struct Settlement<'a, 'info> {
+    pub fifo_state: &'a FifoState,
+    pub pool_registry: &'a mut CpSwapPoolRegistry,
+    pub pool_fifo: &'a mut PoolFifoState,
+    pub user_ordering: &'a AccountInfo<'info>,
+    pub relayer_quota: &'a AccountInfo<'info>,
+    /// The executor's posted fee, when it is registered
+    pub posted_fee_bps: Option<u16>,
+    pub fee_schedule: &'a AccountInfo<'info>,
+    pub user_volume: &'a AccountInfo<'info>,
+    /// Account the swap paid out to
+    pub destination: AccountInfo<'info>,
+    /// The swap's source account and signer, which pay its fees
+    pub fees: FeeCharge<'a, 'info>,
+    /// The refund account and user of an order with a vault, the source
+    /// account, which is settled once the order closes
+    pub vault_refund: Option<(AccountInfo<'info>, AccountInfo<'info>)>,
+    pub pool_authority: AccountInfo<'info>,
+    pub pool_authority_seeds: &'a [&'a [u8]],
+}
+
+/// What a settled swap filled and charged
+pub(crate) struct Fill {
+    /// Input the swap spent
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Whether the order closed, filled or with its rest cancelled
+    pub closed: bool,
+    pub protocol_fee: u64,
+    pub relayer_fee: u64,
+    /// What the fees charged are under the flat ones
+    pub rebate: u64,
+}
+
+/// Settle the swap of `fill`, the amount in and minimum out it was made
+/// with, from the source and destination balances before it: record the fill
+/// on the order, moving its pool's head and its user's ordering once it
+/// closes, enforce its limit, record volume and price, charge fees, and
+/// settle the vault of an order that closed
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn settle_execution(
+    execution: &Execution,
+    settlement: Settlement,
+    order: &mut OrderState,
+    order_key: &Pubkey,
+    swap_pool: &SwapPool,
+    (amount_in, min_amount_out): (u64, u64),
+    (source_start_balance, destination_start_balance): (u64, u64),
+    now: i64,
+) -> Result<Fill> {
+    let Settlement {
+        fifo_state,
+        pool_registry,
+        pool_fifo,
+        user_ordering,
+        relayer_quota,
+        posted_fee_bps,
+        fee_schedule,
+        user_volume,
+        destination,
+        fees,
+        vault_refund,
+        pool_authority,
+        pool_authority_seeds,
+    } = settlement;
+    let source = token_account(&fees.source)?;
+    let destination = token_account(&destination)?;
+    let (amount_spent, amount_out) = swap_deltas(
+        (source_start_balance, source.amount),
+        (destination_start_balance, destination.amount),
+    )?;
+
+    // A partly filled or TWAP order stays pending until its last fill,
+    // except that an immediate-or-cancel order's unfilled rest is cancelled
+    let closed = order.settle_fill(amount_in, amount_out, now);
+    if closed {
+        crate::ordering::remove(user_ordering, order_key)?;
+        pool_fifo.advance(order.sequence, now);
+    }
+
+    if !within_limit(order.is_base_input, amount_spent, amount_out, min_amount_out) {
+        return Err(execution.reject(RejectionReason::SlippageExceeded, ContinuumError::SlippageExceeded));
+    }
+
+    pool_registry.record_volume(&source.mint, amount_spent, amount_out, now);
+    observe_price(pool_registry, swap_pool, &source.mint, &destination.mint, now);
+
+    let relayer_fee_bps = pool_registry.relayer_fee_bps(fifo_state.relayer_fee_bps, posted_fee_bps);
+    let amount_0 = if source.mint == pool_registry.token_0 { amount_spent } else { amount_out };
+    if !record_relayer_quota(relayer_quota, amount_0)? {
+        return Err(execution.reject(RejectionReason::RelayerQuotaExceeded, ContinuumError::RelayerQuotaExceeded));
+    }
+    let protocol_fee_bps = tiered_protocol_fee_bps(fifo_state, fee_schedule, user_volume, amount_0, now)?;
+    let (protocol_fee_bps, relayer_fee_bps) = order.fee_quote.apply(protocol_fee_bps, relayer_fee_bps);
+    let (protocol_fee, relayer_fee) = fifo_state.compute_fees_with(amount_spent, protocol_fee_bps, relayer_fee_bps);
+    let (flat_protocol_fee, flat_relayer_fee) = fifo_state.compute_fees(amount_spent);
+    let token_program = fees.token_program.clone();
+    let vault = fees.source.clone();
+    if protocol_fee > 0 || relayer_fee > 0 {
+        charge_fees(fees, protocol_fee, relayer_fee)?;
+
+        emit!(FeesAccrued {
+            event_sequence: pool_fifo.next_event_sequence(),
+            sequence: order.sequence,
+            pool_id: order.pool_id,
+            mint: source.mint,
+            protocol_fee,
+            relayer_fee,
+            relayer: execution.executor,
+        });
+    }
+
+    if let (true, Some((escrow_refund, user))) = (closed, vault_refund) {
+        let refunded = crate::order_vault::settle(
+            token_program,
+            vault,
+            escrow_refund,
+            user,
+            pool_authority,
+            pool_authority_seeds,
+        )?;
+        msg!("Order {} refunded {} from its vault", order.sequence, refunded);
+    }
+
+    Ok(Fill {
+        amount_in: amount_spent,
+        amount_out,
+        closed,
+        protocol_fee,
+        relayer_fee,
+        rebate: flat_protocol_fee.saturating_add(flat_relayer_fee).saturating_sub(protocol_fee.saturating_add(relayer_fee)),
+    })
+}
+
+/// Emit the events of a settled fill of `order`: a TWAP slice, a partial
+/// fill with an immediate-or-cancel order's rest cancelled, or the execution
+pub(crate) fn report_fill(execution: &Execution, pool_fifo: &mut PoolFifoState, order: &OrderState, fill: &Fill) {
+    let Execution { pool_id, sequence, trace_id, executor } = *execution;
+    let filled = order.status == OrderStatus::Executed;
+    if let (false, Some(twap)) = (filled, order.twap) {
+        let slice = twap.slices_executed;
+        emit!(TwapSliceExecuted {
+            event_sequence: pool_fifo.next_event_sequence(),
+            sequence,
+            pool_id,
+            user: order.user,
+            slice,
+            amount_in: fill.amount_in,
+            amount_out: fill.amount_out,
+            executor,
+            protocol_fee: fill.protocol_fee,
+            relayer_fee: fill.relayer_fee,
+            trace_id,
+        });
+        msg!("Order {} executed slice {}", sequence, slice);
+        return;
+    }
+    if !filled {
+        emit!(OrderPartiallyFilled {
+            event_sequence: pool_fifo.next_event_sequence(),
+            sequence,
+            pool_id,
+            user: order.user,
+            amount_in: fill.amount_in,
+            amount_out: fill.amount_out,
+            amount_in_filled: order.amount_in - order.amount_in_remaining,
+            amount_out_filled: order.amount_out_filled,
+            amount_in_remaining: order.amount_in_remaining,
+            executor,
+            protocol_fee: fill.protocol_fee,
+            relayer_fee: fill.relayer_fee,
+            trace_id,
+        });
+        msg!("Order {} partially filled, {} left", sequence, order.amount_in_remaining);
+        if fill.closed {
+            emit!(OrderCancelled {
+                event_sequence: pool_fifo.next_event_sequence(),
+                sequence,
+                pool_id,
+                user: order.user,
+                trace_id,
+            });
+            msg!("Immediate-or-cancel order {} cancelled its unfilled rest", sequence);
+        }
+        return;
+    }
+
     emit!(OrderExecuted {
+        event_sequence: pool_fifo.next_event_sequence(),
         sequence,
-        user,
-        amount_out,
-        executor: ctx.accounts.executor.key(),
+        pool_id,
+        user: order.user,
+        amount_out: fill.amount_out,
+        executor,
+        amount_in: fill.amount_in,
+        price: OrderExecuted::realized_price(fill.amount_in, fill.amount_out),
+        protocol_fee: fill.protocol_fee,
+        relayer_fee: fill.relayer_fee,
+        rebate: fill.rebate,
+        trace_id,
     });
-    
+
     msg!("Order {} executed successfully", sequence);
-    
+}
+
+/// What a swap spent and delivered, from the source and destination
+/// balances before and after it
+pub(crate) fn swap_deltas(
+    (source_start, source_end): (u64, u64),
+    (destination_start, destination_end): (u64, u64),
+) -> Result<(u64, u64)> {
+    let amount_spent = source_start.checked_sub(source_end).ok_or(ContinuumError::SourceBalanceIncreased)?;
+    let amount_out = destination_end
+        .checked_sub(destination_start)
+        .ok_or(ContinuumError::DestinationBalanceDecreased)?;
+    Ok((amount_spent, amount_out))
+}
+
+/// Whether a swap that spent `amount_spent` for `amount_out` kept to the
+/// limit it was made with: the minimum out of a base input swap, the
+/// maximum in of a base output one. CP-Swap enforces the limit itself; this
+/// catches a swap program that doesn't.
+pub(crate) fn within_limit(is_base_input: bool, amount_spent: u64, amount_out: u64, limit: u64) -> bool {
+    if is_base_input {
+        amount_out >= limit
+    } else {
+        amount_spent <= limit
+    }
+}
+
+/// An SPL Token account, read from its data
+pub(crate) fn token_account(info: &AccountInfo) -> Result<TokenAccount> {
+    require_keys_eq!(*info.owner, token::ID, ErrorCode::AccountOwnedByWrongProgram);
+    TokenAccount::try_deserialize(&mut &info.try_borrow_data()?[..])
+}
+
+/// Balance of a pool vault among the remaining accounts, or `None` if the
+/// account there isn't a token account of `mint`. CP-Swap checks the vaults
+/// against the pool in the swap they are passed to.
+pub(crate) fn vault_reserve(vault: Option<&AccountInfo>, mint: &Pubkey) -> Option<u64> {
+    let data = vault?.try_borrow_data().ok()?;
+    // Mint and amount sit at the same offsets in SPL Token and Token-2022 accounts
+    if data.len() < 72 || data[..32] != mint.to_bytes() {
+        return None;
+    }
+    Some(u64::from_le_bytes(data[64..72].try_into().ok()?))
+}
+
+/// Record the pool's price from the reserves of the vaults a swap of
+/// `input_mint` for `output_mint` went through, when both read as such
+pub(crate) fn observe_price(
+    registry: &mut CpSwapPoolRegistry,
+    pool: &SwapPool,
+    input_mint: &Pubkey,
+    output_mint: &Pubkey,
+    now: i64,
+) {
+    let input_reserve = vault_reserve(Some(&pool.input_vault), input_mint);
+    let output_reserve = vault_reserve(Some(&pool.output_vault), output_mint);
+    if let (Some(input_reserve), Some(output_reserve)) = (input_reserve, output_reserve) {
+        let (reserve_0, reserve_1) = if *input_mint == registry.token_0 {
+            (input_reserve, output_reserve)
+        } else {
+            (output_reserve, input_reserve)
+        };
+        registry.price_0.observe(reserve_0, reserve_1, now);
+    }
+}
+
+/// Count an execution of `amount_0` against the executor's quota, returning
+/// whether it stays within it. Executors without a quota are unlimited.
+pub(crate) fn record_relayer_quota(relayer_quota: &AccountInfo, amount_0: u64) -> Result<bool> {
+    let Some(mut quota) = crate::optional::load::<RelayerQuota>(relayer_quota)? else {
+        return Ok(true);
+    };
+    let within = quota.record(amount_0);
+    crate::optional::store(relayer_quota, &quota)?;
+    Ok(within)
+}
+
+/// Where a swap's fees are paid from and accounted to
+pub(crate) struct FeeCharge<'a, 'info> {
+    pub token_program: AccountInfo<'info>,
+    /// Account the swap spent from, of `mint`
+    pub source: AccountInfo<'info>,
+    pub mint: Pubkey,
+    pub fee_vault: AccountInfo<'info>,
+    /// Signer of the swap, which pays the fees too
+    pub authority: AccountInfo<'info>,
+    pub authority_seeds: &'a [&'a [u8]],
+    pub fee_ledger: &'a mut PoolFeeLedger,
+    pub relayer_fee_balance: &'a mut RelayerFeeBalance,
+    pub insurance_share_bps: u16,
+}
+
+/// Pay `protocol_fee` and `relayer_fee` into the fee vault and accrue them
+/// to the pool's ledger and the executor's claimable balance
+pub(crate) fn charge_fees(charge: FeeCharge, protocol_fee: u64, relayer_fee: u64) -> Result<()> {
+    token::transfer(
+        CpiContext::new_with_signer(
+            charge.token_program,
+            Transfer {
+                from: charge.source,
+                to: charge.fee_vault,
+                authority: charge.authority,
+            },
+            &[charge.authority_seeds],
+        ),
+        protocol_fee.checked_add(relayer_fee).ok_or(ContinuumError::FeeOverflow)?,
+    )?;
+
+    let is_token_0 = charge.mint == charge.fee_ledger.token_0;
+    charge.fee_ledger.accrue(is_token_0, protocol_fee, relayer_fee, charge.insurance_share_bps)?;
+    let claimable = if is_token_0 {
+        &mut charge.relayer_fee_balance.claimable_0
+    } else {
+        &mut charge.relayer_fee_balance.claimable_1
+    };
+    *claimable = claimable.checked_add(relayer_fee).ok_or(ContinuumError::FeeOverflow)?;
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Protocol fee for a user by their volume before this fill, which is then
+/// added to it. Users without a volume account, or below every tier, pay the
+/// flat protocol fee.
+pub(crate) fn tiered_protocol_fee_bps(
+    fifo_state: &FifoState,
+    fee_schedule: &AccountInfo,
+    user_volume: &AccountInfo,
+    amount_0: u64,
+    now: i64,
+) -> Result<u16> {
+    let Some(mut volume) = crate::optional::load::<UserVolume>(user_volume)? else {
+        return Ok(fifo_state.protocol_fee_bps);
+    };
+    let tier_fee_bps = crate::optional::load::<FeeSchedule>(fee_schedule)?
+        .and_then(|schedule| schedule.protocol_fee_bps(volume.rolling_volume(now)));
+    volume.record(amount_0, now);
+    crate::optional::store(user_volume, &volume)?;
+    Ok(tier_fee_bps.unwrap_or(fifo_state.protocol_fee_bps))
+}
+
+/// Pass what is left of the user's approval of `order_escrow` through it
+/// back to the source account, which clears the delegation, then close it.
+/// Only the owner can revoke an approval, so one the source's balance no
+/// longer covers is used up as far as the balance goes, see
+/// [`delegation_leftover`].
+fn use_up_delegation<'info>(
+    accounts: &ExecuteOrder<'info>,
+    order_escrow: &Account<'info, TokenAccount>,
+    escrow_seeds: &[&[u8]],
+    pool_authority_seeds: &[&[u8]],
+) -> Result<()> {
+    let token_program = accounts.token_program.to_account_info();
+    let source = accounts.user_source.to_account_info();
+    let escrow = order_escrow.to_account_info();
+    let pool_authority = accounts.pool_authority.to_account_info();
+
+    let user_source = TokenAccount::try_deserialize(&mut &source.try_borrow_data()?[..])?;
+    let leftover = delegation_leftover(&user_source, &escrow.key());
+    if leftover > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                Transfer {
+                    from: source.clone(),
+                    to: escrow.clone(),
+                    authority: escrow.clone(),
+                },
+                &[escrow_seeds],
+            ),
+            leftover,
+        )?;
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                Transfer {
+                    from: escrow.clone(),
+                    to: source,
+                    authority: pool_authority.clone(),
+                },
+                &[pool_authority_seeds],
+            ),
+            leftover,
+        )?;
+    }
+    token::close_account(CpiContext::new_with_signer(
+        token_program,
+        CloseAccount {
+            account: escrow,
+            destination: accounts.executor.to_account_info(),
+            authority: pool_authority,
+        },
+        &[pool_authority_seeds],
+    ))
+}
+
+/// What is left of `delegate`'s approval of `source`, capped at its balance
+/// so passing it through can't fail the execution
+pub(crate) fn delegation_leftover(source: &token::spl_token::state::Account, delegate: &Pubkey) -> u64 {
+    match source.delegate {
+        COption::Some(key) if key == *delegate => source.delegated_amount.min(source.amount),
+        _ => 0,
+    }
+}
+
+fn post_fill_accounts<'a, 'info>(accounts: &'a ExecuteOrder<'info>) -> PostFillAccounts<'a, 'info> {
+    PostFillAccounts {
+        vault: accounts.user_destination.to_account_info(),
+        mint: accounts.user_destination.mint,
+        authority: accounts.post_fill_authority.as_ref().map(|account| account.as_ref()),
+        mint_account: accounts.output_mint.as_ref().map(|account| account.as_ref()),
+        program: accounts.post_fill_program.as_ref().map(|account| account.as_ref()),
+        target: accounts.post_fill_target.as_ref().map(|account| account.as_ref()),
+        approval: accounts.approved_stake_program.as_deref().map(|approval| &**approval),
+        token_program: accounts.token_program.to_account_info(),
+    }
+}
+
+/// Emit an [`ExecutionRejected`] for the order at `sequence` on `pool_id`
+/// and return `error` to fail with
+pub(crate) fn rejected(pool_id: Pubkey, sequence: u64, trace_id: TraceId, executor: Pubkey, reason: RejectionReason, error: ContinuumError) -> Error {
+    emit!(ExecutionRejected {
+        sequence,
+        pool_id,
+        executor,
+        reason,
+        trace_id,
+    });
+    msg!("Order {} rejected: {:?}", sequence, reason);
+    error.into()
+}
+
+/// Reference written to the memo of an execution, e.g. `continuum:<pool>:<sequence>`
+pub fn order_reference(pool_id: &Pubkey, sequence: u64) -> String {
+    format!("continuum:{}:{}", pool_id, sequence)
+}
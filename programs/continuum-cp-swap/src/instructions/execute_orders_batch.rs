@@ -0,0 +1,293 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::consts::*;
+use crate::cp_swap::{self, Swap, SwapPool};
+use crate::state::*;
+use crate::errors::*;
+use super::execute_order::{
+    check_executor, check_limit, check_swap_accounts, check_turn, report_fill, settle_execution, source_matches, token_account,
+    Execution, FeeCharge, Settlement,
+};
+
+/// Most orders one `execute_orders_batch` can execute
+pub const MAX_BATCH_EXECUTIONS: usize = 4;
+
+/// Remaining accounts of each order in a batch: its order account, its
+/// user's ordering PDA, source and destination token accounts, volume PDA
+/// on the pool, and for an order with a vault its `escrow_refund` account and
+/// user, otherwise this program's ID twice, then the pool's CP-Swap
+/// accounts, see `SwapPool`
+pub const BATCH_EXECUTION_ACCOUNTS: usize = 7 + SwapPool::LEN;
+
+/// Execute the next orders in one pool's queue, in queue order, each as
+/// `execute_order` would. Only orders that take none of `execute_order`'s
+/// optional accounts but those of a vault can be batched, see
+/// `OrderState::is_batchable`, and
+/// a rejection of any of them fails the whole batch. For each order, the
+/// remaining accounts hold `BATCH_EXECUTION_ACCOUNTS` accounts.
+#[derive(Accounts)]
+pub struct ExecuteOrdersBatch<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        mut,
+        seeds = [POOL_REGISTRY_SEED, pool_registry.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_registry: Box<Account<'info, CpSwapPoolRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, pool_registry.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Box<Account<'info, PoolFifoState>>,
+
+    /// CHECK: This is a PDA that will be used to sign the CPIs
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, pool_registry.pool_id.as_ref()],
+        bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// The relayer executing the orders
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    /// CHECK: The pool's relayer assignment PDA; may be uninitialized, see `crate::optional`
+    #[account(
+        seeds = [POOL_RELAYERS_SEED, pool_registry.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_relayers: UncheckedAccount<'info>,
+
+    /// The executor's registration, whose posted fee applies on pools with a
+    /// cap; required unless execution is permissionless
+    #[account(
+        seeds = [RELAYER_SEED, executor.key().as_ref()],
+        bump,
+    )]
+    pub relayer_info: Option<Account<'info, RelayerInfo>>,
+
+    /// CHECK: The executor's quota PDA; may be uninitialized, see `crate::optional`
+    #[account(
+        mut,
+        seeds = [RELAYER_QUOTA_SEED, executor.key().as_ref()],
+        bump,
+    )]
+    pub relayer_quota: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [FEE_LEDGER_SEED, pool_registry.pool_id.as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Box<Account<'info, PoolFeeLedger>>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, pool_registry.pool_id.as_ref(), pool_registry.token_0.as_ref()],
+        bump,
+    )]
+    pub fee_vault_0: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, pool_registry.pool_id.as_ref(), pool_registry.token_1.as_ref()],
+        bump,
+    )]
+    pub fee_vault_1: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The fee tiers PDA; may be uninitialized, see `crate::optional`
+    #[account(
+        seeds = [FEE_SCHEDULE_SEED],
+        bump,
+    )]
+    pub fee_schedule: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [RELAYER_FEES_SEED, pool_registry.pool_id.as_ref(), executor.key().as_ref()],
+        bump,
+    )]
+    pub relayer_fee_balance: Box<Account<'info, RelayerFeeBalance>>,
+
+    /// CHECK: The CP-Swap program recorded in the FIFO state
+    #[account(address = fifo_state.cp_swap_program @ ContinuumError::InvalidCpSwapProgram)]
+    pub cp_swap_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn execute_orders_batch<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteOrdersBatch<'info>>,
+    count: u8,
+) -> Result<()> {
+    let executions = batch_executions(ctx.remaining_accounts, count)?;
+    let now = crate::clock::unix_timestamp(&ctx.accounts.clock, ctx.remaining_accounts);
+    let executor = ctx.accounts.executor.key();
+    let assigned = crate::optional::load::<PoolRelayers>(&ctx.accounts.pool_relayers)?
+        .is_none_or(|pool_relayers| pool_relayers.allows(&executor));
+    let pool_id = ctx.accounts.pool_registry.pool_id;
+    let pool_authority_bump = ctx.bumps.pool_authority;
+    let pool_authority_seeds: &[&[u8]] = &[
+        POOL_AUTHORITY_SEED,
+        pool_id.as_ref(),
+        &[pool_authority_bump],
+    ];
+
+    for accounts in executions {
+        execute_one(ctx.accounts, accounts, now, assigned, pool_authority_seeds)?;
+    }
+
+    msg!("Executed {} orders on pool {}", count, pool_id);
+
+    Ok(())
+}
+
+/// The accounts of each of the first `count` orders among the remaining
+/// accounts, which may hold more after them
+pub(crate) fn batch_executions<T>(remaining_accounts: &[T], count: u8) -> Result<std::slice::ChunksExact<'_, T>> {
+    let count = count as usize;
+    require!(
+        count > 0 && count <= MAX_BATCH_EXECUTIONS,
+        ContinuumError::InvalidBatchSize
+    );
+    let accounts = remaining_accounts
+        .get(..count * BATCH_EXECUTION_ACCOUNTS)
+        .ok_or(ContinuumError::InvalidRemainingAccounts)?;
+    Ok(accounts.chunks_exact(BATCH_EXECUTION_ACCOUNTS))
+}
+
+/// Execute the order whose accounts are `accounts`, with the checks and
+/// settlement of `execute_order`
+fn execute_one<'info>(
+    batch: &mut ExecuteOrdersBatch<'info>,
+    accounts: &[AccountInfo<'info>],
+    now: i64,
+    assigned: bool,
+    pool_authority_seeds: &[&[u8]],
+) -> Result<()> {
+    let [order_info, user_ordering, source_info, destination_info, user_volume, escrow_refund, user_info, swap_accounts @ ..] = accounts else {
+        return err!(ContinuumError::InvalidRemainingAccounts);
+    };
+
+    let mut order = crate::optional::load::<OrderState>(order_info)?
+        .ok_or(ContinuumError::InvalidRemainingAccounts)?;
+    let pool_id = batch.pool_registry.pool_id;
+    require_keys_eq!(order.pool_id, pool_id, ContinuumError::InvalidRemainingAccounts);
+    require_keys_eq!(
+        order_info.key(),
+        OrderState::address(&pool_id, order.sequence),
+        ContinuumError::InvalidRemainingAccounts
+    );
+    let ordering_address = Pubkey::find_program_address(&[USER_ORDERING_SEED, order.user.as_ref()], &crate::ID).0;
+    require_keys_eq!(user_ordering.key(), ordering_address, ContinuumError::InvalidRemainingAccounts);
+    let volume_address =
+        Pubkey::find_program_address(&[USER_VOLUME_SEED, pool_id.as_ref(), order.user.as_ref()], &crate::ID).0;
+    require_keys_eq!(user_volume.key(), volume_address, ContinuumError::InvalidRemainingAccounts);
+
+    let execution = Execution {
+        pool_id,
+        sequence: order.sequence,
+        trace_id: order.trace_id,
+        executor: batch.executor.key(),
+    };
+
+    // Checked in the same order as `execute_order`, so a batched order is
+    // rejected for the same reason it would be on its own
+    check_turn(&execution, &batch.pool_fifo, &order, now)?;
+    if !order.is_batchable() {
+        return Err(execution.reject(RejectionReason::NotBatchable, ContinuumError::NotBatchable));
+    }
+    let source = token_account(source_info)?;
+    let destination = token_account(destination_info)?;
+    let swap_pool = SwapPool::parse(swap_accounts).ok();
+    check_limit(&execution, &order, swap_pool.as_ref(), &source.mint, &destination.mint)?;
+    let is_token_0 = source.mint == batch.pool_registry.token_0;
+    let pool_mint = is_token_0 || source.mint == batch.pool_registry.token_1;
+    // This program's ID stands in for the refund account and user of an
+    // order without a vault
+    let optional = |info: &AccountInfo| Some(info.key()).filter(|key| *key != crate::ID);
+    let (escrow_refund_key, user_key) = (optional(escrow_refund), optional(user_info));
+    if !pool_mint || !source_matches(&order, &source_info.key(), &source, escrow_refund_key, user_key) {
+        return Err(execution.reject(RejectionReason::InvalidSource, ContinuumError::InvalidSource));
+    }
+    if !order.accepts_destination(&destination_info.key(), &destination.owner) {
+        return Err(execution.reject(RejectionReason::InvalidRecipient, ContinuumError::InvalidRecipient));
+    }
+    check_executor(&execution, &batch.fifo_state, assigned, batch.relayer_info.is_some())?;
+    let swap_pool = check_swap_accounts(
+        &execution,
+        user_ordering,
+        &order_info.key(),
+        &order,
+        &batch.pool_registry,
+        swap_accounts,
+        swap_pool,
+    )?;
+
+    // What remains of the order, which a partial fill may have reduced
+    let (amount_in, min_amount_out) = order.next_fill(None);
+    let swap = Swap {
+        payer: batch.pool_authority.to_account_info(),
+        input_token_account: source_info.clone(),
+        output_token_account: destination_info.clone(),
+        pool: swap_pool,
+    };
+    cp_swap::swap(
+        &batch.cp_swap_program,
+        &swap,
+        &[pool_authority_seeds],
+        order.is_base_input,
+        amount_in,
+        min_amount_out,
+    )?;
+
+    let fee_vault = if is_token_0 { &batch.fee_vault_0 } else { &batch.fee_vault_1 };
+    let fill = settle_execution(
+        &execution,
+        Settlement {
+            fifo_state: &batch.fifo_state,
+            pool_registry: &mut batch.pool_registry,
+            pool_fifo: &mut batch.pool_fifo,
+            user_ordering,
+            relayer_quota: &batch.relayer_quota,
+            posted_fee_bps: batch.relayer_info.as_ref().map(|info| info.fee_bps),
+            fee_schedule: &batch.fee_schedule,
+            user_volume,
+            destination: destination_info.clone(),
+            fees: FeeCharge {
+                token_program: batch.token_program.to_account_info(),
+                source: source_info.clone(),
+                mint: source.mint,
+                fee_vault: fee_vault.to_account_info(),
+                authority: batch.pool_authority.to_account_info(),
+                authority_seeds: pool_authority_seeds,
+                fee_ledger: &mut batch.fee_ledger,
+                relayer_fee_balance: &mut batch.relayer_fee_balance,
+                insurance_share_bps: batch.fifo_state.insurance_share_bps,
+            },
+            vault_refund: escrow_refund_key.map(|_| (escrow_refund.clone(), user_info.clone())),
+            pool_authority: batch.pool_authority.to_account_info(),
+            pool_authority_seeds,
+        },
+        &mut order,
+        &order_info.key(),
+        &swap.pool,
+        (amount_in, min_amount_out),
+        (source.amount, destination.amount),
+        now,
+    )?;
+
+    crate::optional::store(order_info, &order)?;
+
+    report_fill(&execution, &mut batch.pool_fifo, &order, &fill);
+
+    Ok(())
+}
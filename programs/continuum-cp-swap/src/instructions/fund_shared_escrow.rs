@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Move tokens from one of the signer's accounts into their shared escrow
+#[derive(Accounts)]
+pub struct DepositSharedEscrow<'info> {
+    #[account(
+        has_one = user @ ContinuumError::Unauthorized,
+        seeds = [SHARED_ESCROW_SEED, user.key().as_ref(), shared_escrow.mint.as_ref()],
+        bump,
+    )]
+    pub shared_escrow: Box<Account<'info, SharedEscrow>>,
+
+    #[account(
+        mut,
+        seeds = [SHARED_ESCROW_VAULT_SEED, shared_escrow.key().as_ref()],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = shared_escrow.mint,
+        token::authority = user,
+    )]
+    pub user_source: Box<Account<'info, TokenAccount>>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Move tokens no pending order reserves out of the signer's shared escrow
+#[derive(Accounts)]
+pub struct WithdrawSharedEscrow<'info> {
+    #[account(
+        has_one = user @ ContinuumError::Unauthorized,
+        seeds = [SHARED_ESCROW_SEED, user.key().as_ref(), shared_escrow.mint.as_ref()],
+        bump,
+    )]
+    pub shared_escrow: Box<Account<'info, SharedEscrow>>,
+
+    #[account(
+        mut,
+        seeds = [SHARED_ESCROW_VAULT_SEED, shared_escrow.key().as_ref()],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = shared_escrow.mint,
+    )]
+    pub user_destination: Box<Account<'info, TokenAccount>>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn deposit_shared_escrow(ctx: Context<DepositSharedEscrow>, amount: u64) -> Result<()> {
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_source.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    msg!("Deposited {} into shared escrow of {}", amount, ctx.accounts.shared_escrow.mint);
+
+    Ok(())
+}
+
+pub fn withdraw_shared_escrow(ctx: Context<WithdrawSharedEscrow>, amount: u64) -> Result<()> {
+    let available = ctx.accounts.shared_escrow.available(ctx.accounts.vault.amount);
+    require!(amount <= available, ContinuumError::InsufficientEscrow);
+
+    crate::shared_escrow::transfer(
+        ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.shared_escrow,
+        ctx.accounts.vault.to_account_info(),
+        ctx.accounts.user_destination.to_account_info(),
+        amount,
+    )?;
+
+    msg!(
+        "Withdrew {} from shared escrow of {}, {} still reserved",
+        amount,
+        ctx.accounts.shared_escrow.mint,
+        ctx.accounts.shared_escrow.reserved
+    );
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::consts::*;
 use crate::state::*;
 
 #[derive(Accounts)]
@@ -7,7 +8,7 @@ pub struct Initialize<'info> {
         init,
         payer = admin,
         space = FifoState::LEN,
-        seeds = [b"fifo_state"],
+        seeds = [FIFO_STATE_SEED],
         bump
     )]
     pub fifo_state: Account<'info, FifoState>,
@@ -24,6 +25,17 @@ pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
     fifo_state.current_sequence = 0;
     fifo_state.admin = ctx.accounts.admin.key();
     fifo_state.emergency_pause = false;
+    fifo_state.archive_tree = Pubkey::default();
+    fifo_state.archived_orders = 0;
+    fifo_state.checkpoint_interval = FifoState::DEFAULT_CHECKPOINT_INTERVAL;
+    fifo_state.protocol_fee_bps = 0;
+    fifo_state.relayer_fee_bps = 0;
+    fifo_state.risk_authority = Pubkey::default();
+    fifo_state.max_swaps_per_window = 0;
+    fifo_state.swap_rate_window = 0;
+    fifo_state.insurance_share_bps = 0;
+    fifo_state.cp_swap_program = crate::cluster::CP_SWAP_PROGRAM_ID;
+    fifo_state.permissionless_execution = true;
     
     msg!("Continuum FIFO initialized with admin: {}", ctx.accounts.admin.key());
     
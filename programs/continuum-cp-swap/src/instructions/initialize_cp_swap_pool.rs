@@ -1,14 +1,13 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{
-    program::invoke_signed,
-    instruction::{Instruction, AccountMeta},
-};
+use crate::consts::*;
+use crate::cp_swap;
 use crate::state::*;
+use crate::errors::*;
 
 #[derive(Accounts)]
 pub struct InitializeCpSwapPool<'info> {
     #[account(
-        seeds = [b"fifo_state"],
+        seeds = [FIFO_STATE_SEED],
         bump,
         has_one = admin,
     )]
@@ -18,7 +17,7 @@ pub struct InitializeCpSwapPool<'info> {
         init,
         payer = admin,
         space = CpSwapPoolRegistry::LEN,
-        seeds = [b"pool_registry", pool_state.key().as_ref()],
+        seeds = [POOL_REGISTRY_SEED, pool_state.key().as_ref()],
         bump
     )]
     pub pool_registry: Account<'info, CpSwapPoolRegistry>,
@@ -27,7 +26,7 @@ pub struct InitializeCpSwapPool<'info> {
     /// Seeds: ["cp_pool_authority", pool_state]
     /// CHECK: This is a PDA that will be set as the custom authority for the pool
     #[account(
-        seeds = [b"cp_pool_authority", pool_state.key().as_ref()],
+        seeds = [POOL_AUTHORITY_SEED, pool_state.key().as_ref()],
         bump
     )]
     pub pool_authority: UncheckedAccount<'info>,
@@ -35,94 +34,79 @@ pub struct InitializeCpSwapPool<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
     
+    /// Co-signer required once `fifo_state.risk_authority` is set
+    pub risk_authority: Option<Signer<'info>>,
+    
     /// CHECK: The pool state account that will be created by CP-Swap
     pub pool_state: UncheckedAccount<'info>,
     
-    /// CHECK: The CP-Swap program
+    /// CHECK: The CP-Swap program recorded in the FIFO state
+    #[account(address = fifo_state.cp_swap_program @ ContinuumError::InvalidCpSwapProgram)]
     pub cp_swap_program: UncheckedAccount<'info>,
     
     pub system_program: Program<'info, System>,
     
-    // Remaining accounts are passed through to CP-Swap initialize instruction
+    // The CP-Swap initialize accounts follow in remaining_accounts, see
+    // `cp_swap::Initialize::parse`
 }
 
-pub fn initialize_cp_swap_pool(
-    ctx: Context<InitializeCpSwapPool>,
+pub fn initialize_cp_swap_pool<'info>(
+    ctx: Context<'_, '_, '_, 'info, InitializeCpSwapPool<'info>>,
     init_amount_0: u64,
     init_amount_1: u64,
     open_time: u64,
 ) -> Result<()> {
+    require!(
+        ctx.accounts.fifo_state.risk_approved(ctx.accounts.risk_authority.as_ref().map(|signer| signer.key)),
+        ContinuumError::MissingRiskApproval
+    );
+    
     let pool_state_key = ctx.accounts.pool_state.key();
     let pool_authority_bump = ctx.bumps.pool_authority;
     
-    // Build the CPI instruction data for CP-Swap initialize
-    // This matches the CP-Swap initialize instruction signature:
-    // initialize(init_amount_0, init_amount_1, open_time, authority_type, custom_authority)
-    let mut ix_data = Vec::new();
-    
-    // Add the instruction discriminator (this is specific to CP-Swap)
-    // You'll need to check the actual discriminator from the CP-Swap IDL
-    ix_data.extend_from_slice(&[175, 175, 109, 31, 13, 152, 155, 237]); // initialize discriminator
-    
-    // Add parameters
-    ix_data.extend_from_slice(&init_amount_0.to_le_bytes());
-    ix_data.extend_from_slice(&init_amount_1.to_le_bytes());
-    ix_data.extend_from_slice(&open_time.to_le_bytes());
-    ix_data.push(1); // authority_type = 1 (custom)
-    ix_data.push(1); // Option<Pubkey> is Some
-    ix_data.extend_from_slice(&ctx.accounts.pool_authority.key().to_bytes());
-    
-    // Build account metas for CPI
-    let mut account_metas = vec![];
-    
-    // Add all remaining accounts in the order expected by CP-Swap
-    for account in ctx.remaining_accounts.iter() {
-        account_metas.push(if account.is_writable {
-            if account.is_signer {
-                AccountMeta::new(account.key(), true)
-            } else {
-                AccountMeta::new(account.key(), false)
-            }
-        } else {
-            if account.is_signer {
-                AccountMeta::new_readonly(account.key(), true)
-            } else {
-                AccountMeta::new_readonly(account.key(), false)
-            }
-        });
-    }
-    
-    // Create the instruction
-    let ix = Instruction {
-        program_id: ctx.accounts.cp_swap_program.key(),
-        accounts: account_metas,
-        data: ix_data,
-    };
-    
-    // Invoke CP-Swap with pool authority signer
+    // The CP-Swap initialize accounts are passed as remaining accounts and
+    // must create the pool being registered
+    let accounts = cp_swap::Initialize::parse(ctx.remaining_accounts)?;
+    require_keys_eq!(accounts.pool_state.key(), pool_state_key, ContinuumError::InvalidRemainingAccounts);
+    
+    // Invoke CP-Swap with pool authority signer, making it the pool's
+    // custom authority
     let pool_authority_seeds = &[
-        b"cp_pool_authority",
+        POOL_AUTHORITY_SEED,
         pool_state_key.as_ref(),
         &[pool_authority_bump],
     ];
     
-    invoke_signed(
-        &ix,
-        ctx.remaining_accounts,
+    cp_swap::initialize(
+        &ctx.accounts.cp_swap_program,
+        &accounts,
         &[pool_authority_seeds],
+        init_amount_0,
+        init_amount_1,
+        open_time,
+        ctx.accounts.pool_authority.key(),
     )?;
     
     // Register the pool
     let registry = &mut ctx.accounts.pool_registry;
     registry.pool_id = pool_state_key;
+    registry.token_0 = accounts.token_0_mint.key();
+    registry.token_1 = accounts.token_1_mint.key();
     registry.continuum_authority = ctx.accounts.pool_authority.key();
     registry.created_at = Clock::get()?.unix_timestamp;
     registry.is_active = true;
-    
-    // TODO: Extract token mints from remaining accounts
-    // For now, we'll need to pass them as additional parameters or extract from pool state
-    registry.token_0 = Pubkey::default(); // To be filled
-    registry.token_1 = Pubkey::default(); // To be filled
+    registry.volume_0 = VolumeStats::default();
+    registry.volume_1 = VolumeStats::default();
+    registry.volume_window_start = registry.created_at;
+    registry.max_relayer_fee_bps = 0;
+    registry.compliance_program = Pubkey::default();
+    registry.price_0 = PriceAccumulator::default();
+    registry.lookup_table = Pubkey::default();
+    registry.is_paused = false;
+    registry.amm_config = accounts.amm_config.key();
+    registry.token_0_vault = accounts.token_0_vault.key();
+    registry.token_1_vault = accounts.token_1_vault.key();
+    registry.observation_state = accounts.observation_state.key();
     
     emit!(PoolRegistered {
         pool_id: pool_state_key,
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::consts::*;
+use crate::state::*;
+
+/// Create the custody account that receives bridged tokens of a mint
+#[derive(Accounts)]
+pub struct InitializeCustody<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [CUSTODY_SEED, mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = custody_authority,
+    )]
+    pub custody: Account<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA that owns the custody accounts
+    #[account(
+        seeds = [CUSTODY_AUTHORITY_SEED],
+        bump
+    )]
+    pub custody_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn initialize_custody(ctx: Context<InitializeCustody>) -> Result<()> {
+    msg!("Custody account {} created for mint {}", ctx.accounts.custody.key(), ctx.accounts.mint.key());
+
+    Ok(())
+}
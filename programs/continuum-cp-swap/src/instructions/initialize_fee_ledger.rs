@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::consts::*;
+use crate::state::*;
+
+/// Create the fee ledger and the two fee vaults of a registered pool.
+/// The vaults are owned by the pool authority PDA.
+#[derive(Accounts)]
+pub struct InitializeFeeLedger<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        seeds = [POOL_REGISTRY_SEED, pool_registry.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_registry: Account<'info, CpSwapPoolRegistry>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = PoolFeeLedger::LEN,
+        seeds = [FEE_LEDGER_SEED, pool_registry.pool_id.as_ref()],
+        bump
+    )]
+    pub fee_ledger: Account<'info, PoolFeeLedger>,
+
+    /// CHECK: This is a PDA that owns the fee vaults
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, pool_registry.pool_id.as_ref()],
+        bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(address = pool_registry.token_0)]
+    pub token_0_mint: Box<Account<'info, Mint>>,
+
+    #[account(address = pool_registry.token_1)]
+    pub token_1_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [FEE_VAULT_SEED, pool_registry.pool_id.as_ref(), token_0_mint.key().as_ref()],
+        bump,
+        token::mint = token_0_mint,
+        token::authority = pool_authority,
+    )]
+    pub fee_vault_0: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [FEE_VAULT_SEED, pool_registry.pool_id.as_ref(), token_1_mint.key().as_ref()],
+        bump,
+        token::mint = token_1_mint,
+        token::authority = pool_authority,
+    )]
+    pub fee_vault_1: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn initialize_fee_ledger(ctx: Context<InitializeFeeLedger>) -> Result<()> {
+    let fee_ledger = &mut ctx.accounts.fee_ledger;
+
+    fee_ledger.pool_id = ctx.accounts.pool_registry.pool_id;
+    fee_ledger.token_0 = ctx.accounts.token_0_mint.key();
+    fee_ledger.token_1 = ctx.accounts.token_1_mint.key();
+    fee_ledger.protocol_0 = FeeCounter::default();
+    fee_ledger.protocol_1 = FeeCounter::default();
+    fee_ledger.relayer_0 = FeeCounter::default();
+    fee_ledger.relayer_1 = FeeCounter::default();
+    fee_ledger.insurance_0 = FeeCounter::default();
+    fee_ledger.insurance_1 = FeeCounter::default();
+
+    msg!("Fee ledger initialized for pool {}", fee_ledger.pool_id);
+
+    Ok(())
+}
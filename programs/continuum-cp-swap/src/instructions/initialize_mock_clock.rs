@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct InitializeMockClock<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = MockClock::LEN,
+        seeds = [MOCK_CLOCK_SEED],
+        bump
+    )]
+    pub mock_clock: Account<'info, MockClock>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_mock_clock(ctx: Context<InitializeMockClock>, unix_timestamp: Option<i64>) -> Result<()> {
+    ctx.accounts.mock_clock.unix_timestamp = unix_timestamp;
+
+    msg!("Mock clock initialized at {:?}", unix_timestamp);
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use crate::compression::{self, ACCOUNT_COMPRESSION_PROGRAM_ID, NOOP_PROGRAM_ID};
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct InitializeOrderArchive<'info> {
+    #[account(
+        mut,
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+        constraint = fifo_state.archive_tree == Pubkey::default() @ ContinuumError::ArchiveAlreadyInitialized,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    /// The PDA that owns the archive tree
+    /// CHECK: This is a PDA that signs the account compression CPI
+    #[account(
+        seeds = [ARCHIVE_AUTHORITY_SEED],
+        bump
+    )]
+    pub archive_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Pre-allocated tree account, initialized by account compression
+    #[account(
+        mut,
+        owner = ACCOUNT_COMPRESSION_PROGRAM_ID,
+    )]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    pub admin: Signer<'info>,
+
+    /// CHECK: The SPL Account Compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: The SPL Noop program
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub noop_program: UncheckedAccount<'info>,
+}
+
+pub fn initialize_order_archive(
+    ctx: Context<InitializeOrderArchive>,
+    max_depth: u32,
+    max_buffer_size: u32,
+) -> Result<()> {
+    let archive_authority_bump = ctx.bumps.archive_authority;
+
+    let ix = compression::init_empty_merkle_tree_ix(
+        ctx.accounts.merkle_tree.key(),
+        ctx.accounts.archive_authority.key(),
+        max_depth,
+        max_buffer_size,
+    );
+
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.merkle_tree.to_account_info(),
+            ctx.accounts.archive_authority.to_account_info(),
+            ctx.accounts.noop_program.to_account_info(),
+        ],
+        &[&[ARCHIVE_AUTHORITY_SEED, &[archive_authority_bump]]],
+    )?;
+
+    let fifo_state = &mut ctx.accounts.fifo_state;
+    fifo_state.archive_tree = ctx.accounts.merkle_tree.key();
+    fifo_state.archived_orders = 0;
+
+    msg!("Order archive initialized: {} (depth {}, buffer {})",
+        ctx.accounts.merkle_tree.key(),
+        max_depth,
+        max_buffer_size
+    );
+
+    Ok(())
+}
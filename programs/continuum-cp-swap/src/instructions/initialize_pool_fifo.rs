@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+
+/// Create the FIFO queue of a registered pool, which orders on it can't be
+/// submitted without
+#[derive(Accounts)]
+pub struct InitializePoolFifo<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        seeds = [POOL_REGISTRY_SEED, pool_registry.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_registry: Account<'info, CpSwapPoolRegistry>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = PoolFifoState::LEN,
+        seeds = [POOL_FIFO_SEED, pool_registry.pool_id.as_ref()],
+        bump
+    )]
+    pub pool_fifo: Account<'info, PoolFifoState>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_pool_fifo(ctx: Context<InitializePoolFifo>) -> Result<()> {
+    let pool_fifo = &mut ctx.accounts.pool_fifo;
+
+    pool_fifo.pool_id = ctx.accounts.pool_registry.pool_id;
+    pool_fifo.current_sequence = 0;
+    pool_fifo.head = 0;
+    pool_fifo.order_stream_hash = [0u8; 32];
+    pool_fifo.checkpoint_root = [0u8; 32];
+    pool_fifo.checkpoint_sequence = 0;
+    pool_fifo.immediate_swaps = 0;
+    pool_fifo.event_sequence = 0;
+    pool_fifo.head_moved_at = 0;
+
+    msg!("FIFO queue initialized for pool {}", pool_fifo.pool_id);
+
+    Ok(())
+}
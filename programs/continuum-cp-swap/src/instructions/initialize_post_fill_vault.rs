@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::consts::*;
+
+/// Create the account that receives the output of swap-and-burn and
+/// swap-and-stake orders in a mint. Swap output is only held there for the
+/// duration of the execution.
+#[derive(Accounts)]
+pub struct InitializePostFillVault<'info> {
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [POST_FILL_VAULT_SEED, mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = post_fill_authority,
+    )]
+    pub post_fill_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA that owns the post-fill vaults
+    #[account(
+        seeds = [POST_FILL_AUTHORITY_SEED],
+        bump
+    )]
+    pub post_fill_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn initialize_post_fill_vault(ctx: Context<InitializePostFillVault>) -> Result<()> {
+    msg!("Post-fill vault {} created for mint {}", ctx.accounts.post_fill_vault.key(), ctx.accounts.mint.key());
+
+    Ok(())
+}
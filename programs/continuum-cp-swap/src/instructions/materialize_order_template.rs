@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Permissionless crank: enqueue a template's next occurrence once it is due,
+/// as an order spending from the template's escrow. The caller pays for the
+/// order account, whose rent goes to the user when it is archived.
+#[derive(Accounts)]
+pub struct MaterializeOrderTemplate<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        constraint = !fifo_state.emergency_pause @ ContinuumError::EmergencyPause,
+    )]
+    pub fifo_state: Box<Account<'info, FifoState>>,
+
+    #[account(
+        mut,
+        seeds = [ORDER_TEMPLATE_SEED, template.user.as_ref(), &template.template_id.to_le_bytes()],
+        bump,
+    )]
+    pub template: Box<Account<'info, OrderTemplate>>,
+
+    #[account(
+        seeds = [POOL_REGISTRY_SEED, template.pool_id.as_ref()],
+        bump,
+        constraint = pool_registry.is_active @ ContinuumError::PoolNotRegistered,
+        constraint = !pool_registry.is_paused @ ContinuumError::PoolPaused,
+    )]
+    pub pool_registry: Box<Account<'info, CpSwapPoolRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, template.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Box<Account<'info, PoolFifoState>>,
+
+    #[account(
+        seeds = [TEMPLATE_ESCROW_SEED, template.key().as_ref()],
+        bump,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = OrderState::LEN,
+        seeds = [ORDER_SEED, template.pool_id.as_ref(), &pool_fifo.upcoming_sequence().to_le_bytes()],
+        bump
+    )]
+    pub order_state: Box<Account<'info, OrderState>>,
+
+    /// CHECK: The user's ordering PDA; may be uninitialized, see `crate::ordering`
+    #[account(
+        mut,
+        seeds = [USER_ORDERING_SEED, template.user.as_ref()],
+        bump,
+    )]
+    pub user_ordering: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn materialize_order_template(ctx: Context<MaterializeOrderTemplate>) -> Result<()> {
+    let now = crate::clock::unix_timestamp(&ctx.accounts.clock, ctx.remaining_accounts);
+    let template = &mut ctx.accounts.template;
+    require!(!template.is_exhausted() && now >= template.next_at, ContinuumError::TemplateNotDue);
+    require!(template.amount_in > 0, ContinuumError::InvalidAmount);
+    require!(ctx.accounts.escrow.amount >= template.max_input(), ContinuumError::InsufficientEscrow);
+
+    // Catch up from now rather than enqueueing every missed occurrence at once
+    template.occurrences += 1;
+    template.next_at = now.saturating_add(template.interval);
+
+    let pool_fifo = &mut ctx.accounts.pool_fifo;
+    let order_state = &mut ctx.accounts.order_state;
+    order_state.set_inner(OrderState {
+        source_account: Some(ctx.accounts.escrow.key()),
+        ..pool_fifo.enqueue(
+            NewOrder {
+                user: template.user,
+                amount_in: template.amount_in,
+                min_amount_out: template.min_amount_out,
+                is_base_input: template.is_base_input,
+                input_mint: ctx.accounts.escrow.mint,
+            },
+            &ctx.accounts.fifo_state,
+            &ctx.accounts.pool_registry,
+            now,
+        )
+    });
+    let sequence = order_state.sequence;
+
+    crate::ordering::push(&ctx.accounts.user_ordering, order_state.key())?;
+
+    emit!(OrderSubmitted {
+        event_sequence: pool_fifo.next_event_sequence(),
+        sequence,
+        user: template.user,
+        pool_id: template.pool_id,
+        amount_in: template.amount_in,
+        is_base_input: template.is_base_input,
+        trace_id: order_state.trace_id,
+        client_order_id: order_state.client_order_id,
+        memo: order_state.memo.clone(),
+    });
+
+    msg!(
+        "Order {} materialized from template {} of user {} ({}/{})",
+        sequence,
+        template.template_id,
+        template.user,
+        template.occurrences,
+        template.total_occurrences
+    );
+
+    Ok(())
+}
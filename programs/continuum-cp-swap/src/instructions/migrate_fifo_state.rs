@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::legacy::LegacyFifoStateV0;
+use crate::state::*;
+use crate::errors::*;
+
+/// Grow a FIFO state created by the first deployment, in the layout of
+/// `LegacyFifoStateV0`, to the current one. Its sequence, admin and pause
+/// are kept and the fields added since are set as `initialize` sets them.
+#[derive(Accounts)]
+pub struct MigrateFifoState<'info> {
+    /// CHECK: Decoded as `LegacyFifoStateV0` in the handler, whose admin must sign
+    #[account(
+        mut,
+        seeds = [FIFO_STATE_SEED],
+        bump,
+    )]
+    pub fifo_state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_fifo_state(ctx: Context<MigrateFifoState>) -> Result<()> {
+    let legacy = crate::legacy::load(&ctx.accounts.fifo_state, LegacyFifoStateV0::decode)?;
+    require_keys_eq!(legacy.admin, ctx.accounts.admin.key(), ContinuumError::Unauthorized);
+
+    let fifo_state = FifoState {
+        current_sequence: legacy.current_sequence,
+        admin: legacy.admin,
+        emergency_pause: legacy.emergency_pause,
+        archive_tree: Pubkey::default(),
+        archived_orders: 0,
+        checkpoint_interval: FifoState::DEFAULT_CHECKPOINT_INTERVAL,
+        protocol_fee_bps: 0,
+        relayer_fee_bps: 0,
+        risk_authority: Pubkey::default(),
+        max_swaps_per_window: 0,
+        swap_rate_window: 0,
+        insurance_share_bps: 0,
+        cp_swap_program: crate::cluster::CP_SWAP_PROGRAM_ID,
+        permissionless_execution: true,
+    };
+    crate::legacy::rewrite(
+        &ctx.accounts.fifo_state,
+        &fifo_state,
+        FifoState::LEN,
+        &ctx.accounts.admin,
+        &ctx.accounts.system_program,
+    )?;
+
+    msg!("FIFO state migrated at sequence {}", legacy.current_sequence);
+
+    Ok(())
+}
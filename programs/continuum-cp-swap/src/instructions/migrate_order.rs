@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::consts::*;
+use crate::legacy::{LegacyOrderStateV0, LegacyOrderStatusV0};
+use crate::state::*;
+use crate::errors::*;
+
+/// Move a pending order created by the first deployment, at
+/// ["order", user, sequence - 1] and in the layout of `LegacyOrderStateV0`,
+/// to the tail of its pool's queue at ["order", pool_id, sequence]. That
+/// layout doesn't record which mint the order sells, so the user signs as
+/// the payer and names it. The order is quoted the fees in force now. The
+/// legacy account's rent goes to the user, who pays for the new one and
+/// gets that back when it is archived. The FIFO state and the
+/// pool's registry must have been migrated first, and its queue created.
+#[derive(Accounts)]
+#[instruction(pool_id: Pubkey)]
+pub struct MigrateOrder<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+    )]
+    pub fifo_state: Box<Account<'info, FifoState>>,
+
+    #[account(
+        seeds = [POOL_REGISTRY_SEED, pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_registry: Box<Account<'info, CpSwapPoolRegistry>>,
+
+    /// CHECK: Decoded as `LegacyOrderStateV0` and checked against its
+    /// address in the handler, then closed
+    #[account(mut)]
+    pub legacy_order: UncheckedAccount<'info>,
+
+    /// Mint the order sells, one of the pool's
+    #[account(
+        constraint = input_mint.key() == pool_registry.token_0 || input_mint.key() == pool_registry.token_1
+            @ ContinuumError::InvalidSource,
+    )]
+    pub input_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Box<Account<'info, PoolFifoState>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = OrderState::LEN,
+        seeds = [ORDER_SEED, pool_id.as_ref(), &pool_fifo.upcoming_sequence().to_le_bytes()],
+        bump
+    )]
+    pub order_state: Box<Account<'info, OrderState>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_order(ctx: Context<MigrateOrder>, pool_id: Pubkey) -> Result<()> {
+    let legacy_address = ctx.accounts.legacy_order.key();
+    let legacy = crate::legacy::load(&ctx.accounts.legacy_order, LegacyOrderStateV0::decode)?;
+    let expected_address = Pubkey::find_program_address(
+        &[ORDER_SEED, legacy.user.as_ref(), &legacy.sequence.saturating_sub(1).to_le_bytes()],
+        &crate::ID,
+    ).0;
+    require!(
+        legacy.sequence > 0
+            && legacy.amount_in > 0
+            && legacy_address == expected_address
+            && legacy.pool_id == pool_id
+            && legacy.user == ctx.accounts.payer.key()
+            && legacy.status == LegacyOrderStatusV0::Pending,
+        ContinuumError::OrderNotMigratable
+    );
+
+    let sequence = ctx.accounts.pool_fifo.next_sequence();
+    let order = OrderState::new(
+        pool_id,
+        sequence,
+        NewOrder {
+            user: legacy.user,
+            amount_in: legacy.amount_in,
+            min_amount_out: legacy.min_amount_out,
+            is_base_input: legacy.is_base_input,
+            input_mint: ctx.accounts.input_mint.key(),
+        },
+        FeeQuote::new(&ctx.accounts.fifo_state, &ctx.accounts.pool_registry),
+        legacy.submitted_at,
+    );
+    ctx.accounts.order_state.set_inner(order);
+    crate::legacy::close(&ctx.accounts.legacy_order, &ctx.accounts.payer)?;
+
+    msg!("Order {} migrated from {} to {} as order {}", legacy.sequence, legacy_address, ctx.accounts.order_state.key(), sequence);
+
+    Ok(())
+}
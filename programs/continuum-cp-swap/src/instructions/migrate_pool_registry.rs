@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::cp_swap::PoolStateAccounts;
+use crate::legacy::LegacyPoolRegistryV0;
+use crate::state::*;
+use crate::errors::*;
+
+/// Grow a pool registry created by the first deployment, in the layout of
+/// `LegacyPoolRegistryV0`, to the current one. The CP-Swap accounts swaps
+/// are checked against are read from the pool state, and the other fields
+/// added since are set as `initialize_cp_swap_pool` sets them. The FIFO
+/// state must have been migrated first.
+#[derive(Accounts)]
+pub struct MigratePoolRegistry<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Box<Account<'info, FifoState>>,
+
+    /// CHECK: Decoded as `LegacyPoolRegistryV0` in the handler
+    #[account(
+        mut,
+        seeds = [POOL_REGISTRY_SEED, pool_state.key().as_ref()],
+        bump,
+    )]
+    pub pool_registry: UncheckedAccount<'info>,
+
+    /// CHECK: The registered CP-Swap pool, read in `cp_swap::PoolStateAccounts`
+    #[account(owner = fifo_state.cp_swap_program @ ContinuumError::InvalidCpSwapProgram)]
+    pub pool_state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_pool_registry(ctx: Context<MigratePoolRegistry>) -> Result<()> {
+    let legacy = crate::legacy::load(&ctx.accounts.pool_registry, LegacyPoolRegistryV0::decode)?;
+    let pool = PoolStateAccounts::parse(&ctx.accounts.pool_state.try_borrow_data()?)?;
+    require!(
+        legacy.pool_id == ctx.accounts.pool_state.key()
+            && pool.token_0_mint == legacy.token_0
+            && pool.token_1_mint == legacy.token_1,
+        ContinuumError::AccountNotMigratable
+    );
+
+    let registry = CpSwapPoolRegistry {
+        pool_id: legacy.pool_id,
+        token_0: legacy.token_0,
+        token_1: legacy.token_1,
+        continuum_authority: legacy.continuum_authority,
+        created_at: legacy.created_at,
+        is_active: legacy.is_active,
+        volume_0: VolumeStats::default(),
+        volume_1: VolumeStats::default(),
+        volume_window_start: legacy.created_at,
+        max_relayer_fee_bps: 0,
+        compliance_program: Pubkey::default(),
+        price_0: PriceAccumulator::default(),
+        lookup_table: Pubkey::default(),
+        is_paused: false,
+        amm_config: pool.amm_config,
+        token_0_vault: pool.token_0_vault,
+        token_1_vault: pool.token_1_vault,
+        observation_state: pool.observation_state,
+    };
+    crate::legacy::rewrite(
+        &ctx.accounts.pool_registry,
+        &registry,
+        CpSwapPoolRegistry::LEN,
+        &ctx.accounts.admin,
+        &ctx.accounts.system_program,
+    )?;
+
+    msg!("Pool {} registry migrated", legacy.pool_id);
+
+    Ok(())
+}
@@ -1,15 +1,155 @@
 pub mod initialize;
 pub mod initialize_cp_swap_pool;
 pub mod submit_order;
+pub mod submit_limit_order;
+pub mod submit_twap_order;
+pub mod submit_trigger_order;
 pub mod submit_order_simple;
+pub mod submit_orders;
 pub mod execute_order;
+pub mod execute_orders_batch;
 pub mod cancel_order;
+pub mod modify_order;
+pub mod migrate_fifo_state;
+pub mod migrate_order;
+pub mod migrate_pool_registry;
 pub mod swap_immediate;
+pub mod initialize_order_archive;
+pub mod archive_order;
+pub mod skip_order;
+pub mod expire_order;
+pub mod cancel_ioc_order;
+pub mod verify_archived_order;
+pub mod set_checkpoint_interval;
+pub mod set_fee_config;
+pub mod initialize_fee_ledger;
+pub mod initialize_pool_fifo;
+pub mod open_relayer_fee_balance;
+pub mod claim_relayer_fees;
+pub mod withdraw_protocol_fees;
+pub mod set_insurance_share;
+pub mod payout_insurance;
+pub mod compensate_order;
+pub mod add_relayer;
+pub mod remove_relayer;
+pub mod relayer_heartbeat;
+pub mod set_relayer_fee;
+pub mod set_relayer_fee_cap;
+pub mod set_fee_schedule;
+pub mod open_user_volume;
+pub mod set_pool_relayers;
+pub mod set_risk_authority;
+pub mod set_cp_swap_program;
+pub mod set_swap_rate_limit;
+pub mod set_permissionless_execution;
+pub mod set_relayer_quota;
+pub mod set_compliance_program;
+pub mod set_pool_lookup_table;
+pub mod initialize_post_fill_vault;
+pub mod approve_stake_program;
+pub mod create_order_template;
+pub mod materialize_order_template;
+pub mod create_dca_schedule;
+pub mod execute_dca_tick;
+pub mod create_iceberg;
+pub mod rearm_iceberg;
+pub mod update_trail;
+pub mod open_shared_escrow;
+pub mod fund_shared_escrow;
+pub mod approve_user_delegation;
+pub mod submit_order_as_delegate;
+pub mod cancel_order_as_delegate;
+pub mod create_permit;
+pub mod create_organization;
+pub mod open_org_escrow;
+pub mod submit_org_order;
+pub mod register_foreign_emitter;
+pub mod initialize_custody;
+pub mod submit_order_from_vaa;
+pub mod attest_fill;
+pub mod set_pause;
+pub mod submit_order_intent;
+pub mod enable_user_ordering;
+pub mod disable_user_ordering;
+#[cfg(feature = "test-clock")]
+pub mod initialize_mock_clock;
+#[cfg(feature = "test-clock")]
+pub mod set_mock_clock;
 
 pub use initialize::*;
 pub use initialize_cp_swap_pool::*;
 pub use submit_order::*;
+pub use submit_limit_order::*;
+pub use submit_twap_order::*;
+pub use submit_trigger_order::*;
 pub use submit_order_simple::*;
+pub use submit_orders::*;
 pub use execute_order::*;
+pub use execute_orders_batch::*;
 pub use cancel_order::*;
-pub use swap_immediate::*;
\ No newline at end of file
+pub use modify_order::*;
+pub use migrate_fifo_state::*;
+pub use migrate_order::*;
+pub use migrate_pool_registry::*;
+pub use swap_immediate::*;
+pub use initialize_order_archive::*;
+pub use archive_order::*;
+pub use skip_order::*;
+pub use expire_order::*;
+pub use cancel_ioc_order::*;
+pub use verify_archived_order::*;
+pub use set_checkpoint_interval::*;
+pub use set_fee_config::*;
+pub use initialize_fee_ledger::*;
+pub use initialize_pool_fifo::*;
+pub use open_relayer_fee_balance::*;
+pub use claim_relayer_fees::*;
+pub use withdraw_protocol_fees::*;
+pub use set_insurance_share::*;
+pub use payout_insurance::*;
+pub use compensate_order::*;
+pub use add_relayer::*;
+pub use remove_relayer::*;
+pub use relayer_heartbeat::*;
+pub use set_relayer_fee::*;
+pub use set_relayer_fee_cap::*;
+pub use set_fee_schedule::*;
+pub use open_user_volume::*;
+pub use set_pool_relayers::*;
+pub use set_risk_authority::*;
+pub use set_cp_swap_program::*;
+pub use set_swap_rate_limit::*;
+pub use set_permissionless_execution::*;
+pub use set_relayer_quota::*;
+pub use set_compliance_program::*;
+pub use set_pool_lookup_table::*;
+pub use initialize_post_fill_vault::*;
+pub use approve_stake_program::*;
+pub use create_order_template::*;
+pub use materialize_order_template::*;
+pub use create_dca_schedule::*;
+pub use execute_dca_tick::*;
+pub use create_iceberg::*;
+pub use rearm_iceberg::*;
+pub use update_trail::*;
+pub use open_shared_escrow::*;
+pub use fund_shared_escrow::*;
+pub use approve_user_delegation::*;
+pub use submit_order_as_delegate::*;
+pub use cancel_order_as_delegate::*;
+pub use create_permit::*;
+pub use create_organization::*;
+pub use open_org_escrow::*;
+pub use submit_org_order::*;
+pub use register_foreign_emitter::*;
+pub use initialize_custody::*;
+pub use submit_order_from_vaa::*;
+pub use attest_fill::*;
+pub use set_pause::*;
+pub use submit_order_intent::*;
+pub use enable_user_ordering::*;
+pub use disable_user_ordering::*;
+#[cfg(feature = "test-clock")]
+pub use initialize_mock_clock::*;
+#[cfg(feature = "test-clock")]
+pub use set_mock_clock::*;
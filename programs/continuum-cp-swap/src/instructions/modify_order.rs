@@ -0,0 +1,243 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Change a pending order's amounts in one instruction. An order whose
+/// limit is only tightened is updated in place and keeps its place in line.
+/// Any other change cancels it and queues a copy with the new amounts at the
+/// tail of its pool's line, at `replacement`; orders waiting on it as their
+/// parent keep waiting on the cancelled one. An escrowed order re-queued
+/// moves its deposit into the replacement's vault, topped up from or
+/// refunded to its `escrow_refund` account for the difference.
+#[derive(Accounts)]
+pub struct ModifyOrder<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        constraint = !fifo_state.emergency_pause @ ContinuumError::EmergencyPause,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        mut,
+        seeds = [ORDER_SEED, order_state.pool_id.as_ref(), &order_state.sequence.to_le_bytes()],
+        bump,
+        constraint = order_state.status == OrderStatus::Pending @ ContinuumError::InvalidOrderStatus,
+        constraint = order_state.user == user.key() @ ContinuumError::Unauthorized,
+    )]
+    pub order_state: Account<'info, OrderState>,
+
+    #[account(
+        seeds = [POOL_REGISTRY_SEED, order_state.pool_id.as_ref()],
+        bump,
+        constraint = pool_registry.is_active @ ContinuumError::PoolNotRegistered,
+        constraint = !pool_registry.is_paused @ ContinuumError::PoolPaused,
+    )]
+    pub pool_registry: Account<'info, CpSwapPoolRegistry>,
+
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, order_state.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Account<'info, PoolFifoState>,
+
+    /// The re-queued order, required when the modification re-queues it
+    #[account(
+        init,
+        payer = user,
+        space = OrderState::LEN,
+        seeds = [ORDER_SEED, order_state.pool_id.as_ref(), &pool_fifo.upcoming_sequence().to_le_bytes()],
+        bump
+    )]
+    pub replacement: Option<Box<Account<'info, OrderState>>>,
+
+    /// CHECK: The user's ordering PDA; may be uninitialized, see `crate::ordering`
+    #[account(
+        mut,
+        seeds = [USER_ORDERING_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub user_ordering: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// The order's co-signer, required when it must sign the cancellation,
+    /// or the submission of a re-queued order
+    pub co_signer: Option<Signer<'info>>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+
+    /// Vault of an escrowed order, required when it has one and the
+    /// modification re-queues it, with the accounts below; settled and closed
+    #[account(
+        mut,
+        seeds = [ORDER_VAULT_SEED, order_state.key().as_ref()],
+        bump,
+    )]
+    pub order_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Vault of the re-queued order, created holding its deposit
+    #[account(
+        init,
+        payer = user,
+        seeds = [ORDER_VAULT_SEED, pool_fifo.upcoming_order().as_ref()],
+        bump,
+        token::mint = input_mint,
+        token::authority = pool_authority,
+    )]
+    pub replacement_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(address = order_state.input_mint @ ContinuumError::InvalidOrderVault)]
+    pub input_mint: Option<Box<Account<'info, Mint>>>,
+
+    /// The order's `escrow_refund` account, which tops up the deposit or
+    /// is refunded what the vault has over it
+    #[account(mut)]
+    pub escrow_refund: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: This is a PDA that owns the order vaults
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, order_state.pool_id.as_ref()],
+        bump
+    )]
+    pub pool_authority: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+pub fn modify_order(ctx: Context<ModifyOrder>, amount_in: u64, min_amount_out: u64) -> Result<()> {
+    let now = crate::clock::unix_timestamp(&ctx.accounts.clock, ctx.remaining_accounts);
+    let fifo_state = &ctx.accounts.fifo_state;
+    let pool_fifo = &mut ctx.accounts.pool_fifo;
+    let order_state = &mut ctx.accounts.order_state;
+
+    require!(amount_in > 0, ContinuumError::InvalidAmount);
+    let co_signer = ctx.accounts.co_signer.as_ref().map(|signer| signer.key());
+    require!(order_state.cancel_co_signed(co_signer.as_ref()), ContinuumError::CoSignerRequired);
+    require!(order_state.is_modifiable(), ContinuumError::OrderNotModifiable);
+
+    let requeued = !order_state.is_tightened_by(amount_in, min_amount_out);
+    require!(requeued == ctx.accounts.replacement.is_some(), ContinuumError::InvalidReplacement);
+    require!(
+        ctx.accounts.replacement_vault.is_none() || (requeued && order_state.escrow_refund.is_some()),
+        ContinuumError::InvalidOrderVault
+    );
+
+    if !requeued {
+        order_state.min_amount_out = min_amount_out;
+
+        emit!(OrderModified {
+            event_sequence: pool_fifo.next_event_sequence(),
+            sequence: order_state.sequence,
+            pool_id: order_state.pool_id,
+            new_sequence: order_state.sequence,
+            user: order_state.user,
+            amount_in,
+            min_amount_out,
+            requeued,
+            trace_id: order_state.trace_id,
+        });
+
+        msg!("Order {} limit tightened to {}", order_state.sequence, min_amount_out);
+
+        return Ok(());
+    }
+
+    require!(order_state.is_unfilled(), ContinuumError::OrderNotModifiable);
+    require!(
+        ctx.accounts.pool_registry.compliance_program().is_none(),
+        ContinuumError::MissingComplianceCheck
+    );
+    if let Some(required) = order_state.co_signer.filter(|required| required.scope.on_submission()) {
+        require!(required.signed_by(co_signer.as_ref()), ContinuumError::CoSignerRequired);
+    }
+
+    let new_sequence = pool_fifo.sequence_order(&order_state.user, amount_in, order_state.is_base_input, fifo_state.checkpoint_interval);
+
+    // The replacement keeps everything but the amounts, with a new place in
+    // line and the fees in force now
+    let mut replacement = (**order_state).clone();
+    replacement.sequence = new_sequence;
+    replacement.amount_in = amount_in;
+    replacement.min_amount_out = min_amount_out;
+    replacement.submitted_at = now;
+    replacement.fee_quote = FeeQuote::new(fifo_state, &ctx.accounts.pool_registry);
+    replacement.amount_in_remaining = amount_in;
+
+    if order_state.escrow_refund.is_some() {
+        let (Some(order_vault), Some(replacement_vault), Some(escrow_refund), Some(pool_authority), Some(token_program), Some(bump)) = (
+            &ctx.accounts.order_vault,
+            &ctx.accounts.replacement_vault,
+            &ctx.accounts.escrow_refund,
+            &ctx.accounts.pool_authority,
+            &ctx.accounts.token_program,
+            ctx.bumps.pool_authority,
+        ) else {
+            return err!(ContinuumError::InvalidOrderVault);
+        };
+        require!(order_state.escrow_refund == Some(escrow_refund.key()), ContinuumError::InvalidOrderVault);
+        let (topped_up, refunded) = crate::order_vault::requeue(
+            token_program.to_account_info(),
+            order_vault.to_account_info(),
+            replacement_vault.to_account_info(),
+            escrow_refund.to_account_info(),
+            ctx.accounts.user.to_account_info(),
+            pool_authority.to_account_info(),
+            &[POOL_AUTHORITY_SEED, order_state.pool_id.as_ref(), &[bump]],
+            crate::order_vault::deposit(&replacement)?,
+        )?;
+        replacement.source_account = Some(replacement_vault.key());
+        msg!("Order {} deposit moved to {}, topped up {}, refunded {}", order_state.sequence, replacement_vault.key(), topped_up, refunded);
+    }
+
+    order_state.status = OrderStatus::Cancelled;
+    order_state.executed_at = Some(now);
+
+    crate::ordering::remove(&ctx.accounts.user_ordering, &order_state.key())?;
+    if let Some(account) = ctx.accounts.replacement.as_ref() {
+        crate::ordering::push(&ctx.accounts.user_ordering, account.key())?;
+    }
+
+    emit!(OrderCancelled {
+        event_sequence: pool_fifo.next_event_sequence(),
+        sequence: order_state.sequence,
+        pool_id: order_state.pool_id,
+        user: order_state.user,
+        trace_id: order_state.trace_id,
+    });
+    emit!(OrderSubmitted {
+        event_sequence: pool_fifo.next_event_sequence(),
+        sequence: new_sequence,
+        user: replacement.user,
+        pool_id: replacement.pool_id,
+        amount_in,
+        is_base_input: replacement.is_base_input,
+        trace_id: replacement.trace_id,
+        client_order_id: replacement.client_order_id,
+        memo: replacement.memo.clone(),
+    });
+    emit!(OrderModified {
+        event_sequence: pool_fifo.next_event_sequence(),
+        sequence: order_state.sequence,
+        pool_id: order_state.pool_id,
+        new_sequence,
+        user: order_state.user,
+        amount_in,
+        min_amount_out,
+        requeued,
+        trace_id: order_state.trace_id,
+    });
+
+    msg!("Order {} replaced by order {}", order_state.sequence, new_sequence);
+
+    if let Some(account) = ctx.accounts.replacement.as_mut() {
+        account.set_inner(replacement);
+    }
+
+    Ok(())
+}
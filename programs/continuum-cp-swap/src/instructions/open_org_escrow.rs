@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Open an organization's shared escrow for `mint` (owner only). Anyone can
+/// fund it by transferring into its vault, members' orders spend from it,
+/// and fills of orders buying the mint are paid into it.
+#[derive(Accounts)]
+pub struct OpenOrgEscrow<'info> {
+    #[account(
+        has_one = owner @ ContinuumError::Unauthorized,
+        seeds = [ORGANIZATION_SEED, owner.key().as_ref(), &organization.org_id.to_le_bytes()],
+        bump,
+    )]
+    pub organization: Box<Account<'info, Organization>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = SharedEscrow::LEN,
+        seeds = [SHARED_ESCROW_SEED, organization.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub shared_escrow: Box<Account<'info, SharedEscrow>>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [SHARED_ESCROW_VAULT_SEED, shared_escrow.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = shared_escrow,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Move tokens no pending order reserves out of an organization's escrow
+/// (owner only)
+#[derive(Accounts)]
+pub struct WithdrawOrgEscrow<'info> {
+    #[account(
+        has_one = owner @ ContinuumError::Unauthorized,
+        seeds = [ORGANIZATION_SEED, owner.key().as_ref(), &organization.org_id.to_le_bytes()],
+        bump,
+    )]
+    pub organization: Box<Account<'info, Organization>>,
+
+    #[account(
+        seeds = [SHARED_ESCROW_SEED, organization.key().as_ref(), shared_escrow.mint.as_ref()],
+        bump,
+    )]
+    pub shared_escrow: Box<Account<'info, SharedEscrow>>,
+
+    #[account(
+        mut,
+        seeds = [SHARED_ESCROW_VAULT_SEED, shared_escrow.key().as_ref()],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = shared_escrow.mint,
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn open_org_escrow(ctx: Context<OpenOrgEscrow>) -> Result<()> {
+    let shared_escrow = &mut ctx.accounts.shared_escrow;
+    shared_escrow.user = ctx.accounts.organization.key();
+    shared_escrow.mint = ctx.accounts.mint.key();
+    shared_escrow.reserved = 0;
+    shared_escrow.open_orders = 0;
+
+    msg!("Organization {} opened an escrow of {}", ctx.accounts.organization.org_id, shared_escrow.mint);
+
+    Ok(())
+}
+
+pub fn withdraw_org_escrow(ctx: Context<WithdrawOrgEscrow>, amount: u64) -> Result<()> {
+    let available = ctx.accounts.shared_escrow.available(ctx.accounts.vault.amount);
+    require!(amount <= available, ContinuumError::InsufficientEscrow);
+
+    crate::shared_escrow::transfer(
+        ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.shared_escrow,
+        ctx.accounts.vault.to_account_info(),
+        ctx.accounts.destination.to_account_info(),
+        amount,
+    )?;
+
+    msg!(
+        "Organization {} withdrew {} of {}",
+        ctx.accounts.organization.org_id,
+        amount,
+        ctx.accounts.shared_escrow.mint
+    );
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct OpenRelayerFeeBalance<'info> {
+    #[account(
+        seeds = [FEE_LEDGER_SEED, fee_ledger.pool_id.as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Account<'info, PoolFeeLedger>,
+
+    #[account(
+        init,
+        payer = relayer,
+        space = RelayerFeeBalance::LEN,
+        seeds = [RELAYER_FEES_SEED, fee_ledger.pool_id.as_ref(), relayer.key().as_ref()],
+        bump
+    )]
+    pub relayer_fee_balance: Account<'info, RelayerFeeBalance>,
+
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn open_relayer_fee_balance(ctx: Context<OpenRelayerFeeBalance>) -> Result<()> {
+    let balance = &mut ctx.accounts.relayer_fee_balance;
+
+    balance.relayer = ctx.accounts.relayer.key();
+    balance.pool_id = ctx.accounts.fee_ledger.pool_id;
+    balance.claimable_0 = 0;
+    balance.claimable_1 = 0;
+    balance.claimed_0 = 0;
+    balance.claimed_1 = 0;
+
+    msg!("Relayer fee balance opened for {} on pool {}", balance.relayer, balance.pool_id);
+
+    Ok(())
+}
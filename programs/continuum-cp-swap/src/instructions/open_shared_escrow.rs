@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Open the signer's shared escrow for `mint`, with an empty vault funded by
+/// `deposit_shared_escrow`. Orders on any pool trading the mint can then
+/// spend from it.
+#[derive(Accounts)]
+pub struct OpenSharedEscrow<'info> {
+    #[account(
+        init,
+        payer = user,
+        space = SharedEscrow::LEN,
+        seeds = [SHARED_ESCROW_SEED, user.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub shared_escrow: Box<Account<'info, SharedEscrow>>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [SHARED_ESCROW_VAULT_SEED, shared_escrow.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = shared_escrow,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Close a shared escrow no pending order reserves from, refunding its vault
+#[derive(Accounts)]
+pub struct CloseSharedEscrow<'info> {
+    #[account(
+        mut,
+        close = user,
+        has_one = user @ ContinuumError::Unauthorized,
+        seeds = [SHARED_ESCROW_SEED, user.key().as_ref(), shared_escrow.mint.as_ref()],
+        bump,
+        constraint = shared_escrow.open_orders == 0 @ ContinuumError::EscrowInUse,
+    )]
+    pub shared_escrow: Box<Account<'info, SharedEscrow>>,
+
+    #[account(
+        mut,
+        seeds = [SHARED_ESCROW_VAULT_SEED, shared_escrow.key().as_ref()],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = shared_escrow.mint,
+    )]
+    pub user_destination: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn open_shared_escrow(ctx: Context<OpenSharedEscrow>) -> Result<()> {
+    let shared_escrow = &mut ctx.accounts.shared_escrow;
+    shared_escrow.user = ctx.accounts.user.key();
+    shared_escrow.mint = ctx.accounts.mint.key();
+    shared_escrow.reserved = 0;
+    shared_escrow.open_orders = 0;
+
+    msg!("Shared escrow of {} opened by user {}", shared_escrow.mint, shared_escrow.user);
+
+    Ok(())
+}
+
+pub fn close_shared_escrow(ctx: Context<CloseSharedEscrow>) -> Result<()> {
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let vault = ctx.accounts.vault.to_account_info();
+    let refund = ctx.accounts.vault.amount;
+
+    if refund > 0 {
+        crate::shared_escrow::transfer(
+            token_program.clone(),
+            &ctx.accounts.shared_escrow,
+            vault.clone(),
+            ctx.accounts.user_destination.to_account_info(),
+            refund,
+        )?;
+    }
+    crate::shared_escrow::close_vault(
+        token_program,
+        &ctx.accounts.shared_escrow,
+        vault,
+        ctx.accounts.user.to_account_info(),
+    )?;
+
+    msg!("Shared escrow of {} closed, {} refunded", ctx.accounts.shared_escrow.mint, refund);
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+
+/// Start tracking a user's volume on a pool so it counts toward fee tiers.
+/// Anyone may pay for it.
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct OpenUserVolume<'info> {
+    #[account(
+        seeds = [POOL_REGISTRY_SEED, pool_registry.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_registry: Account<'info, CpSwapPoolRegistry>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = UserVolume::LEN,
+        seeds = [USER_VOLUME_SEED, pool_registry.pool_id.as_ref(), user.as_ref()],
+        bump
+    )]
+    pub user_volume: Account<'info, UserVolume>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn open_user_volume(ctx: Context<OpenUserVolume>, user: Pubkey) -> Result<()> {
+    let user_volume = &mut ctx.accounts.user_volume;
+    user_volume.user = user;
+    user_volume.pool_id = ctx.accounts.pool_registry.pool_id;
+    user_volume.volume = VolumeStats::default();
+    user_volume.window_start = Clock::get()?.unix_timestamp;
+
+    msg!("Tracking volume of {} on pool {}", user, user_volume.pool_id);
+
+    Ok(())
+}
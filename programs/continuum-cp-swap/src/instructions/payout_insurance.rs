@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Pay a user harmed by a program fault or relayer misbehavior out of a
+/// pool's insurance fund. Governed like pool registrations: the admin signs,
+/// and so does the risk authority once one is set.
+#[derive(Accounts)]
+pub struct PayoutInsurance<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        mut,
+        seeds = [FEE_LEDGER_SEED, fee_ledger.pool_id.as_ref()],
+        bump,
+    )]
+    pub fee_ledger: Box<Account<'info, PoolFeeLedger>>,
+
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, fee_ledger.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Box<Account<'info, PoolFifoState>>,
+
+    /// Fee vault of the mint paid out
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, fee_ledger.pool_id.as_ref(), fee_vault.mint.as_ref()],
+        bump,
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: This is a PDA that owns the fee vaults
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, fee_ledger.pool_id.as_ref()],
+        bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = fee_vault.mint,
+    )]
+    pub recipient: Box<Account<'info, TokenAccount>>,
+
+    pub admin: Signer<'info>,
+
+    /// Required once the FIFO state has a risk authority
+    pub risk_authority: Option<Signer<'info>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn payout_insurance(ctx: Context<PayoutInsurance>, amount: u64) -> Result<()> {
+    require!(
+        ctx.accounts.fifo_state.risk_approved(ctx.accounts.risk_authority.as_ref().map(|signer| signer.key)),
+        ContinuumError::MissingRiskApproval
+    );
+
+    let mint = ctx.accounts.fee_vault.mint;
+    let pool_id = ctx.accounts.fee_ledger.pool_id;
+    pay(
+        &mut ctx.accounts.fee_ledger,
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.fee_vault.to_account_info(),
+        &mint,
+        ctx.accounts.recipient.to_account_info(),
+        ctx.accounts.pool_authority.to_account_info(),
+        ctx.bumps.pool_authority,
+        amount,
+    )?;
+
+    emit!(InsurancePaidOut {
+        event_sequence: ctx.accounts.pool_fifo.next_event_sequence(),
+        pool_id,
+        mint,
+        recipient: ctx.accounts.recipient.key(),
+        amount,
+    });
+
+    msg!("Insurance fund of pool {} paid {} of {} to {}", pool_id, amount, mint, ctx.accounts.recipient.key());
+
+    Ok(())
+}
+
+/// Transfer `amount` of `mint` out of a pool's insurance fund, held in its
+/// fee vault of the mint
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn pay<'info>(
+    fee_ledger: &mut PoolFeeLedger,
+    token_program: AccountInfo<'info>,
+    fee_vault: AccountInfo<'info>,
+    mint: &Pubkey,
+    recipient: AccountInfo<'info>,
+    pool_authority: AccountInfo<'info>,
+    pool_authority_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let insurance = fee_ledger.insurance_mut(mint).ok_or(ContinuumError::InvalidFeeConfig)?;
+    require!(amount <= insurance.outstanding(), ContinuumError::InsufficientInsurance);
+    insurance.pay_out(amount)?;
+
+    let pool_authority_seeds: &[&[u8]] = &[
+        POOL_AUTHORITY_SEED,
+        fee_ledger.pool_id.as_ref(),
+        &[pool_authority_bump],
+    ];
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program,
+            Transfer {
+                from: fee_vault,
+                to: recipient,
+                authority: pool_authority,
+            },
+            &[pool_authority_seeds],
+        ),
+        amount,
+    )
+}
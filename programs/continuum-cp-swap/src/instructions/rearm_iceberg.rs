@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Permissionless crank: enqueue an iceberg's next tranche at the tail of the
+/// queue once its last one has executed, passed as `active_order`. The caller
+/// pays for the order account, whose rent goes to the user when it is archived.
+#[derive(Accounts)]
+pub struct RearmIceberg<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        constraint = !fifo_state.emergency_pause @ ContinuumError::EmergencyPause,
+    )]
+    pub fifo_state: Box<Account<'info, FifoState>>,
+
+    #[account(
+        mut,
+        seeds = [ICEBERG_SEED, iceberg.user.as_ref(), &iceberg.iceberg_id.to_le_bytes()],
+        bump,
+    )]
+    pub iceberg: Box<Account<'info, Iceberg>>,
+
+    #[account(
+        seeds = [POOL_REGISTRY_SEED, iceberg.pool_id.as_ref()],
+        bump,
+        constraint = pool_registry.is_active @ ContinuumError::PoolNotRegistered,
+        constraint = !pool_registry.is_paused @ ContinuumError::PoolPaused,
+    )]
+    pub pool_registry: Box<Account<'info, CpSwapPoolRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, iceberg.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Box<Account<'info, PoolFifoState>>,
+
+    #[account(
+        seeds = [ICEBERG_ESCROW_SEED, iceberg.key().as_ref()],
+        bump,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// The tranche last enqueued, required once there is one
+    pub active_order: Option<Box<Account<'info, OrderState>>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = OrderState::LEN,
+        seeds = [ORDER_SEED, iceberg.pool_id.as_ref(), &pool_fifo.upcoming_sequence().to_le_bytes()],
+        bump
+    )]
+    pub order_state: Box<Account<'info, OrderState>>,
+
+    /// CHECK: The user's ordering PDA; may be uninitialized, see `crate::ordering`
+    #[account(
+        mut,
+        seeds = [USER_ORDERING_SEED, iceberg.user.as_ref()],
+        bump,
+    )]
+    pub user_ordering: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn rearm_iceberg(ctx: Context<RearmIceberg>) -> Result<()> {
+    let iceberg = &mut ctx.accounts.iceberg;
+    let last_filled = match iceberg.active_order {
+        Some(address) => ctx.accounts.active_order.as_ref().is_some_and(|active| {
+            active.key() == address && active.status == OrderStatus::Executed
+        }),
+        None => true,
+    };
+    require!(last_filled, ContinuumError::TrancheNotFilled);
+    require!(ctx.accounts.escrow.amount >= iceberg.tranche_in, ContinuumError::InsufficientEscrow);
+
+    let now = crate::clock::unix_timestamp(&ctx.accounts.clock, ctx.remaining_accounts);
+
+    let pool_fifo = &mut ctx.accounts.pool_fifo;
+    let order_state = &mut ctx.accounts.order_state;
+    order_state.set_inner(OrderState {
+        source_account: Some(ctx.accounts.escrow.key()),
+        ..pool_fifo.enqueue(
+            NewOrder {
+                user: iceberg.user,
+                amount_in: iceberg.tranche_in,
+                min_amount_out: iceberg.tranche_min_out,
+                is_base_input: true,
+                input_mint: ctx.accounts.escrow.mint,
+            },
+            &ctx.accounts.fifo_state,
+            &ctx.accounts.pool_registry,
+            now,
+        )
+    });
+    let sequence = order_state.sequence;
+
+    iceberg.active_order = Some(order_state.key());
+    iceberg.tranches += 1;
+
+    crate::ordering::push(&ctx.accounts.user_ordering, order_state.key())?;
+
+    emit!(OrderSubmitted {
+        event_sequence: pool_fifo.next_event_sequence(),
+        sequence,
+        user: iceberg.user,
+        pool_id: iceberg.pool_id,
+        amount_in: iceberg.tranche_in,
+        is_base_input: true,
+        trace_id: order_state.trace_id,
+        client_order_id: order_state.client_order_id,
+        memo: order_state.memo.clone(),
+    });
+
+    msg!("Iceberg {} of user {} armed tranche {} as order {}", iceberg.iceberg_id, iceberg.user, iceberg.tranches, sequence);
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+pub struct RegisterForeignEmitter<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ForeignEmitter::LEN,
+        seeds = [FOREIGN_EMITTER_SEED, chain.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_foreign_emitter(
+    ctx: Context<RegisterForeignEmitter>,
+    chain: u16,
+    address: [u8; 32],
+) -> Result<()> {
+    let foreign_emitter = &mut ctx.accounts.foreign_emitter;
+
+    foreign_emitter.chain = chain;
+    foreign_emitter.address = address;
+
+    msg!("Foreign emitter registered for chain {}", chain);
+
+    Ok(())
+}
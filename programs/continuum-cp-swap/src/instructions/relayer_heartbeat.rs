@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct RelayerHeartbeat<'info> {
+    #[account(
+        mut,
+        seeds = [RELAYER_SEED, relayer.key().as_ref()],
+        bump,
+        constraint = relayer_info.relayer == relayer.key() @ ContinuumError::Unauthorized,
+    )]
+    pub relayer_info: Account<'info, RelayerInfo>,
+
+    pub relayer: Signer<'info>,
+}
+
+pub fn relayer_heartbeat(ctx: Context<RelayerHeartbeat>) -> Result<()> {
+    let clock = Clock::get()?;
+    let relayer_info = &mut ctx.accounts.relayer_info;
+
+    relayer_info.last_active = clock.unix_timestamp;
+    relayer_info.last_active_slot = clock.slot;
+
+    Ok(())
+}
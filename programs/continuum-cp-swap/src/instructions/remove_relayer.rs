@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct RemoveRelayer<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [RELAYER_SEED, relayer_info.relayer.as_ref()],
+        bump
+    )]
+    pub relayer_info: Account<'info, RelayerInfo>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn remove_relayer(ctx: Context<RemoveRelayer>) -> Result<()> {
+    let relayer = ctx.accounts.relayer_info.relayer;
+
+    emit!(RelayerRemoved {
+        relayer,
+    });
+
+    msg!("Relayer {} removed", relayer);
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetCheckpointInterval<'info> {
+    #[account(
+        mut,
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn set_checkpoint_interval(ctx: Context<SetCheckpointInterval>, interval: u64) -> Result<()> {
+    ctx.accounts.fifo_state.checkpoint_interval = interval;
+
+    msg!("Checkpoint interval set to {}", interval);
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetComplianceProgram<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        mut,
+        seeds = [POOL_REGISTRY_SEED, pool_registry.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_registry: Account<'info, CpSwapPoolRegistry>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn set_compliance_program(ctx: Context<SetComplianceProgram>, compliance_program: Pubkey) -> Result<()> {
+    let pool_registry = &mut ctx.accounts.pool_registry;
+    pool_registry.compliance_program = compliance_program;
+
+    msg!("Compliance program on pool {} set to {}", pool_registry.pool_id, compliance_program);
+
+    Ok(())
+}
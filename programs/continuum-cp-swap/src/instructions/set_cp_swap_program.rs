@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Point executions and pool registrations at another CP-Swap program. The
+/// pool authority signs every CPI into it, so once a risk authority is set
+/// it must co-sign the change like it does pool registrations.
+#[derive(Accounts)]
+pub struct SetCpSwapProgram<'info> {
+    #[account(
+        mut,
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    pub admin: Signer<'info>,
+
+    /// Co-signer required once `fifo_state.risk_authority` is set
+    pub risk_authority: Option<Signer<'info>>,
+}
+
+pub fn set_cp_swap_program(ctx: Context<SetCpSwapProgram>, cp_swap_program: Pubkey) -> Result<()> {
+    let fifo_state = &mut ctx.accounts.fifo_state;
+
+    require!(
+        fifo_state.risk_approved(ctx.accounts.risk_authority.as_ref().map(|signer| signer.key)),
+        ContinuumError::MissingRiskApproval
+    );
+    require!(cp_swap_program != Pubkey::default(), ContinuumError::InvalidCpSwapProgram);
+
+    fifo_state.cp_swap_program = cp_swap_program;
+
+    msg!("CP-Swap program set to {}", cp_swap_program);
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetFeeConfig<'info> {
+    #[account(
+        mut,
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn set_fee_config(
+    ctx: Context<SetFeeConfig>,
+    protocol_fee_bps: u16,
+    relayer_fee_bps: u16,
+) -> Result<()> {
+    require!(
+        protocol_fee_bps as u32 + relayer_fee_bps as u32 <= FifoState::MAX_TOTAL_FEE_BPS as u32,
+        ContinuumError::InvalidFeeConfig
+    );
+
+    let fifo_state = &mut ctx.accounts.fifo_state;
+    fifo_state.protocol_fee_bps = protocol_fee_bps;
+    fifo_state.relayer_fee_bps = relayer_fee_bps;
+
+    msg!("Fees set: protocol {} bps, relayer {} bps", protocol_fee_bps, relayer_fee_bps);
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Create the protocol fee tiers (admin only)
+#[derive(Accounts)]
+pub struct InitializeFeeSchedule<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = FeeSchedule::LEN,
+        seeds = [FEE_SCHEDULE_SEED],
+        bump
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Replace the protocol fee tiers (admin only)
+#[derive(Accounts)]
+pub struct SetFeeSchedule<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        mut,
+        seeds = [FEE_SCHEDULE_SEED],
+        bump,
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+
+    pub admin: Signer<'info>,
+}
+
+fn validate_tiers(fifo_state: &FifoState, tiers: &[FeeTier]) -> Result<()> {
+    require!(tiers.len() <= FeeSchedule::MAX_TIERS, ContinuumError::InvalidFeeConfig);
+    require!(
+        tiers.windows(2).all(|pair| pair[0].min_volume < pair[1].min_volume),
+        ContinuumError::InvalidFeeConfig
+    );
+    require!(
+        tiers.iter().all(|tier| {
+            tier.protocol_fee_bps as u32 + fifo_state.relayer_fee_bps as u32
+                <= FifoState::MAX_TOTAL_FEE_BPS as u32
+        }),
+        ContinuumError::InvalidFeeConfig
+    );
+    Ok(())
+}
+
+pub fn initialize_fee_schedule(ctx: Context<InitializeFeeSchedule>, tiers: Vec<FeeTier>) -> Result<()> {
+    validate_tiers(&ctx.accounts.fifo_state, &tiers)?;
+    msg!("Fee schedule created with {} tiers", tiers.len());
+    ctx.accounts.fee_schedule.tiers = tiers;
+
+    Ok(())
+}
+
+pub fn set_fee_schedule(ctx: Context<SetFeeSchedule>, tiers: Vec<FeeTier>) -> Result<()> {
+    validate_tiers(&ctx.accounts.fifo_state, &tiers)?;
+    msg!("Fee schedule set to {} tiers", tiers.len());
+    ctx.accounts.fee_schedule.tiers = tiers;
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetInsuranceShare<'info> {
+    #[account(
+        mut,
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn set_insurance_share(ctx: Context<SetInsuranceShare>, insurance_share_bps: u16) -> Result<()> {
+    require!(insurance_share_bps <= 10_000, ContinuumError::InvalidFeeConfig);
+
+    ctx.accounts.fifo_state.insurance_share_bps = insurance_share_bps;
+
+    msg!("Insurance share set to {} bps of protocol fees", insurance_share_bps);
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetMockClock<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        mut,
+        seeds = [MOCK_CLOCK_SEED],
+        bump,
+    )]
+    pub mock_clock: Account<'info, MockClock>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn set_mock_clock(ctx: Context<SetMockClock>, unix_timestamp: Option<i64>) -> Result<()> {
+    ctx.accounts.mock_clock.unix_timestamp = unix_timestamp;
+
+    msg!("Mock clock set to {:?}", unix_timestamp);
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(
+        mut,
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    /// The pool to pause alone; every pool when absent
+    #[account(
+        mut,
+        seeds = [POOL_REGISTRY_SEED, pool_registry.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_registry: Option<Account<'info, CpSwapPoolRegistry>>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn set_pause(ctx: Context<SetPause>, paused: bool, reason: Option<String>) -> Result<()> {
+    let reason = reason.unwrap_or_default();
+    require!(reason.len() <= MAX_PAUSE_REASON_LEN, ContinuumError::PauseReasonTooLong);
+
+    let pool_id = match ctx.accounts.pool_registry.as_mut() {
+        Some(registry) => {
+            registry.is_paused = paused;
+            msg!("Pause of pool {} set to {}", registry.pool_id, paused);
+            Some(registry.pool_id)
+        }
+        None => {
+            ctx.accounts.fifo_state.emergency_pause = paused;
+            msg!("Emergency pause set to {}", paused);
+            None
+        }
+    };
+
+    emit!(PauseSet {
+        pool_id,
+        paused,
+        reason,
+    });
+
+    Ok(())
+}
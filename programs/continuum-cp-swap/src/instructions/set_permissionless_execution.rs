@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+
+/// Let any signer execute queued orders, or only relayers registered with
+/// `add_relayer`.
+#[derive(Accounts)]
+pub struct SetPermissionlessExecution<'info> {
+    #[account(
+        mut,
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn set_permissionless_execution(ctx: Context<SetPermissionlessExecution>, permissionless: bool) -> Result<()> {
+    ctx.accounts.fifo_state.permissionless_execution = permissionless;
+
+    if permissionless {
+        msg!("Any signer may execute orders");
+    } else {
+        msg!("Only registered relayers may execute orders");
+    }
+
+    Ok(())
+}
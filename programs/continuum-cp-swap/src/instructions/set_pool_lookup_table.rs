@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetPoolLookupTable<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        mut,
+        seeds = [POOL_REGISTRY_SEED, pool_registry.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_registry: Account<'info, CpSwapPoolRegistry>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn set_pool_lookup_table(ctx: Context<SetPoolLookupTable>, lookup_table: Pubkey) -> Result<()> {
+    let pool_registry = &mut ctx.accounts.pool_registry;
+    pool_registry.lookup_table = lookup_table;
+
+    msg!("Lookup table of pool {} set to {}", pool_registry.pool_id, lookup_table);
+
+    Ok(())
+}
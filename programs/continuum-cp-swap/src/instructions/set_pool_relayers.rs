@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Create a pool's relayer assignment (admin only)
+#[derive(Accounts)]
+pub struct InitializePoolRelayers<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        seeds = [POOL_REGISTRY_SEED, pool_registry.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_registry: Account<'info, CpSwapPoolRegistry>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = PoolRelayers::LEN,
+        seeds = [POOL_RELAYERS_SEED, pool_registry.pool_id.as_ref()],
+        bump
+    )]
+    pub pool_relayers: Account<'info, PoolRelayers>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Replace the relayers assigned to a pool (admin only)
+#[derive(Accounts)]
+pub struct SetPoolRelayers<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        mut,
+        seeds = [POOL_RELAYERS_SEED, pool_relayers.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_relayers: Account<'info, PoolRelayers>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn initialize_pool_relayers(ctx: Context<InitializePoolRelayers>, relayers: Vec<Pubkey>) -> Result<()> {
+    require!(relayers.len() <= PoolRelayers::MAX_RELAYERS, ContinuumError::InvalidPoolConfig);
+
+    let pool_relayers = &mut ctx.accounts.pool_relayers;
+    pool_relayers.pool_id = ctx.accounts.pool_registry.pool_id;
+    pool_relayers.relayers = relayers;
+
+    msg!("Pool {} assigned {} relayers", pool_relayers.pool_id, pool_relayers.relayers.len());
+
+    Ok(())
+}
+
+pub fn set_pool_relayers(ctx: Context<SetPoolRelayers>, relayers: Vec<Pubkey>) -> Result<()> {
+    require!(relayers.len() <= PoolRelayers::MAX_RELAYERS, ContinuumError::InvalidPoolConfig);
+
+    let pool_relayers = &mut ctx.accounts.pool_relayers;
+    pool_relayers.relayers = relayers;
+
+    msg!("Pool {} assigned {} relayers", pool_relayers.pool_id, pool_relayers.relayers.len());
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetRelayerFee<'info> {
+    #[account(
+        mut,
+        seeds = [RELAYER_SEED, relayer.key().as_ref()],
+        bump,
+        constraint = relayer_info.relayer == relayer.key() @ ContinuumError::Unauthorized,
+    )]
+    pub relayer_info: Account<'info, RelayerInfo>,
+
+    pub relayer: Signer<'info>,
+}
+
+pub fn set_relayer_fee(ctx: Context<SetRelayerFee>, fee_bps: u16) -> Result<()> {
+    require!(fee_bps <= FifoState::MAX_TOTAL_FEE_BPS, ContinuumError::InvalidFeeConfig);
+
+    let relayer_info = &mut ctx.accounts.relayer_info;
+    relayer_info.fee_bps = fee_bps;
+
+    msg!("Relayer {} posted a fee of {} bps", relayer_info.relayer, fee_bps);
+
+    Ok(())
+}
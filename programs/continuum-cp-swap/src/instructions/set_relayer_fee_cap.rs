@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetRelayerFeeCap<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        mut,
+        seeds = [POOL_REGISTRY_SEED, pool_registry.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_registry: Account<'info, CpSwapPoolRegistry>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn set_relayer_fee_cap(ctx: Context<SetRelayerFeeCap>, max_relayer_fee_bps: u16) -> Result<()> {
+    require!(
+        ctx.accounts.fifo_state.protocol_fee_bps as u32 + max_relayer_fee_bps as u32
+            <= FifoState::MAX_TOTAL_FEE_BPS as u32,
+        ContinuumError::InvalidFeeConfig
+    );
+
+    let pool_registry = &mut ctx.accounts.pool_registry;
+    pool_registry.max_relayer_fee_bps = max_relayer_fee_bps;
+
+    msg!("Relayer fee cap on pool {} set to {} bps", pool_registry.pool_id, max_relayer_fee_bps);
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Put a relayer on a quota, starting its first period now (admin only)
+#[derive(Accounts)]
+#[instruction(relayer: Pubkey)]
+pub struct InitializeRelayerQuota<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = RelayerQuota::LEN,
+        seeds = [RELAYER_QUOTA_SEED, relayer.as_ref()],
+        bump
+    )]
+    pub relayer_quota: Account<'info, RelayerQuota>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+/// Change a relayer's quota. Counters for the current period are kept, so a
+/// lowered limit can leave the relayer blocked until the next reset (admin only).
+#[derive(Accounts)]
+pub struct SetRelayerQuota<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    #[account(
+        mut,
+        seeds = [RELAYER_QUOTA_SEED, relayer_quota.relayer.as_ref()],
+        bump,
+    )]
+    pub relayer_quota: Account<'info, RelayerQuota>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Permissionless crank: zero a relayer's counters once its period has ended
+#[derive(Accounts)]
+pub struct ResetRelayerQuota<'info> {
+    #[account(
+        mut,
+        seeds = [RELAYER_QUOTA_SEED, relayer_quota.relayer.as_ref()],
+        bump,
+    )]
+    pub relayer_quota: Account<'info, RelayerQuota>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn initialize_relayer_quota(
+    ctx: Context<InitializeRelayerQuota>,
+    relayer: Pubkey,
+    max_executions: u32,
+    max_volume: u64,
+    period: i64,
+) -> Result<()> {
+    require!(period > 0, ContinuumError::InvalidRelayerQuota);
+
+    let now = crate::clock::unix_timestamp(&ctx.accounts.clock, ctx.remaining_accounts);
+    let relayer_quota = &mut ctx.accounts.relayer_quota;
+    relayer_quota.relayer = relayer;
+    relayer_quota.max_executions = max_executions;
+    relayer_quota.max_volume = max_volume;
+    relayer_quota.period = period;
+    relayer_quota.period_start = now;
+    relayer_quota.executions = 0;
+    relayer_quota.volume = 0;
+
+    msg!(
+        "Relayer {} limited to {} executions and {} volume per {}s",
+        relayer,
+        max_executions,
+        max_volume,
+        period
+    );
+
+    Ok(())
+}
+
+pub fn set_relayer_quota(
+    ctx: Context<SetRelayerQuota>,
+    max_executions: u32,
+    max_volume: u64,
+    period: i64,
+) -> Result<()> {
+    require!(period > 0, ContinuumError::InvalidRelayerQuota);
+
+    let relayer_quota = &mut ctx.accounts.relayer_quota;
+    relayer_quota.max_executions = max_executions;
+    relayer_quota.max_volume = max_volume;
+    relayer_quota.period = period;
+
+    msg!(
+        "Relayer {} limited to {} executions and {} volume per {}s",
+        relayer_quota.relayer,
+        max_executions,
+        max_volume,
+        period
+    );
+
+    Ok(())
+}
+
+pub fn reset_relayer_quota(ctx: Context<ResetRelayerQuota>) -> Result<()> {
+    let now = crate::clock::unix_timestamp(&ctx.accounts.clock, ctx.remaining_accounts);
+    let relayer_quota = &mut ctx.accounts.relayer_quota;
+    require!(relayer_quota.period_over(now), ContinuumError::QuotaPeriodNotOver);
+
+    msg!(
+        "Relayer {} quota reset after {} executions and {} volume",
+        relayer_quota.relayer,
+        relayer_quota.executions,
+        relayer_quota.volume
+    );
+    relayer_quota.reset(now);
+
+    Ok(())
+}
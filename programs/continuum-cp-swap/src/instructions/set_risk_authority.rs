@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Set the key that must co-sign pool registrations alongside the admin, or
+/// clear it with the default pubkey. Once set, changing it also needs the
+/// current risk authority's signature, so the admin can't swap it out alone.
+#[derive(Accounts)]
+pub struct SetRiskAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    pub admin: Signer<'info>,
+
+    /// The risk authority being replaced, required once one is set
+    pub current_risk_authority: Option<Signer<'info>>,
+}
+
+pub fn set_risk_authority(ctx: Context<SetRiskAuthority>, risk_authority: Pubkey) -> Result<()> {
+    let fifo_state = &mut ctx.accounts.fifo_state;
+
+    require!(
+        fifo_state.risk_approved(ctx.accounts.current_risk_authority.as_ref().map(|signer| signer.key)),
+        ContinuumError::MissingRiskApproval
+    );
+    require!(risk_authority != fifo_state.admin, ContinuumError::InvalidRiskAuthority);
+
+    fifo_state.risk_authority = risk_authority;
+
+    msg!("Risk authority set to {}", risk_authority);
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Cap how many immediate swaps each registered relayer may make per
+/// sliding window. A zero `max_swaps` lifts the cap.
+#[derive(Accounts)]
+pub struct SetSwapRateLimit<'info> {
+    #[account(
+        mut,
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        has_one = admin,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn set_swap_rate_limit(ctx: Context<SetSwapRateLimit>, max_swaps: u32, window_secs: u32) -> Result<()> {
+    require!(max_swaps == 0 || window_secs > 0, ContinuumError::InvalidSwapRateLimit);
+
+    let fifo_state = &mut ctx.accounts.fifo_state;
+    fifo_state.max_swaps_per_window = max_swaps;
+    fifo_state.swap_rate_window = window_secs;
+
+    msg!("Relayers limited to {} immediate swaps per {}s", max_swaps, window_secs);
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Permissionless crank: moves a pool's queue past the order next in line
+/// when it needs no execution in line, i.e. it was cancelled or otherwise
+/// settled, or it is conditional and may still execute out of line, or when
+/// it has been next in line for `HEAD_GRACE_PERIOD` without executing. A
+/// pending unconditional order skipped this way can only be cancelled.
+#[derive(Accounts)]
+pub struct SkipOrder<'info> {
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, order_state.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Account<'info, PoolFifoState>,
+
+    #[account(
+        seeds = [ORDER_SEED, order_state.pool_id.as_ref(), &order_state.sequence.to_le_bytes()],
+        bump,
+        constraint = order_state.pool_id == pool_fifo.pool_id @ ContinuumError::OutOfPoolOrder,
+    )]
+    pub order_state: Account<'info, OrderState>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn skip_order(ctx: Context<SkipOrder>) -> Result<()> {
+    let now = crate::clock::unix_timestamp(&ctx.accounts.clock, ctx.remaining_accounts);
+    let order_state = &ctx.accounts.order_state;
+    let pool_fifo = &mut ctx.accounts.pool_fifo;
+
+    require!(
+        order_state.sequence == pool_fifo.next_execution_sequence(),
+        ContinuumError::OutOfPoolOrder
+    );
+    require!(pool_fifo.may_skip(order_state, now), ContinuumError::InvalidOrderStatus);
+    pool_fifo.advance(order_state.sequence, now);
+
+    msg!("Order {} skipped, pool {} queue at {}", order_state.sequence, pool_fifo.pool_id, pool_fifo.head);
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use super::submit_order::{submit_order, OrderConditions, SubmitOrder, SubmitOrderParams};
+
+/// Enqueue an order held until the pool's spot price reaches `limit_price`,
+/// checked by `execute_order` against the pool's vaults. The optional
+/// accounts of `submit_order` fund it the same way.
+#[allow(clippy::too_many_arguments)]
+pub fn submit_limit_order(
+    ctx: Context<SubmitOrder>,
+    amount_in: u64,
+    min_amount_out: u64,
+    is_base_input: bool,
+    limit_price: LimitPrice,
+    recipient: Option<Pubkey>,
+    trace_id: Option<TraceId>,
+    ttl: Option<i64>,
+) -> Result<()> {
+    require!(limit_price.is_valid(), ContinuumError::InvalidLimitPrice);
+
+    submit_order(
+        ctx,
+        amount_in,
+        min_amount_out,
+        is_base_input,
+        SubmitOrderParams {
+            recipient,
+            trace_id,
+            ttl,
+            ..Default::default()
+        },
+        OrderConditions {
+            limit_price: Some(limit_price),
+            ..Default::default()
+        },
+    )
+}
@@ -1,33 +1,91 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::compliance::ComplianceQuery;
+use crate::consts::*;
+use crate::oracle::OraclePrice;
 use crate::state::*;
 use crate::errors::*;
 
+/// Trailing stop an order waits on, started from the oracle's current price
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct TrailingStopParams {
+    pub direction: TrailDirection,
+    pub trail_bps: u16,
+}
+
+/// Optional behaviour of a submitted order, see `submit_order`; the default
+/// is a plain good-till-cancelled order delivering to the user, escrowed in
+/// its vault
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct SubmitOrderParams {
+    pub recipient: Option<Pubkey>,
+    pub callback_program: Option<Pubkey>,
+    pub destination_account: Option<Pubkey>,
+    pub post_fill: Option<PostFillAction>,
+    pub valid_from: Option<i64>,
+    pub parent_order: Option<Pubkey>,
+    pub trailing_stop: Option<TrailingStopParams>,
+    pub delegated_input: bool,
+    pub co_signer: Option<CoSigner>,
+    pub trace_id: Option<TraceId>,
+    pub ttl: Option<i64>,
+    pub execution_policy: Option<ExecutionPolicy>,
+    pub client_order_id: Option<ClientOrderId>,
+    pub memo: Option<String>,
+    /// Leave the input in the user's wallet, for the pool authority to spend
+    /// by approval, instead of escrowing it in the order's vault
+    pub from_wallet: bool,
+}
+
+/// Price conditions set by the order kinds built on `submit_order`
+#[derive(Default)]
+pub(crate) struct OrderConditions {
+    pub limit_price: Option<LimitPrice>,
+    pub twap: Option<TwapSchedule>,
+    pub trigger: Option<TriggerCondition>,
+}
+
 #[derive(Accounts)]
 pub struct SubmitOrder<'info> {
     #[account(
-        mut,
-        seeds = [b"fifo_state"],
+        seeds = [FIFO_STATE_SEED],
         bump,
         constraint = !fifo_state.emergency_pause @ ContinuumError::EmergencyPause,
     )]
     pub fifo_state: Account<'info, FifoState>,
     
     #[account(
-        seeds = [b"pool_registry", pool_id.key().as_ref()],
+        seeds = [POOL_REGISTRY_SEED, pool_id.key().as_ref()],
         bump,
         constraint = pool_registry.is_active @ ContinuumError::PoolNotRegistered,
+        constraint = !pool_registry.is_paused @ ContinuumError::PoolPaused,
     )]
     pub pool_registry: Account<'info, CpSwapPoolRegistry>,
     
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, pool_id.key().as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Account<'info, PoolFifoState>,
+    
     #[account(
         init,
         payer = user,
         space = OrderState::LEN,
-        seeds = [b"order", user.key().as_ref(), &fifo_state.current_sequence.to_le_bytes()],
+        seeds = [ORDER_SEED, pool_id.key().as_ref(), &pool_fifo.upcoming_sequence().to_le_bytes()],
         bump
     )]
     pub order_state: Account<'info, OrderState>,
     
+    /// CHECK: The user's ordering PDA; may be uninitialized, see `crate::ordering`
+    #[account(
+        mut,
+        seeds = [USER_ORDERING_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub user_ordering: UncheckedAccount<'info>,
+    
     #[account(mut)]
     pub user: Signer<'info>,
     
@@ -36,48 +94,259 @@ pub struct SubmitOrder<'info> {
     
     pub system_program: Program<'info, System>,
     pub clock: Sysvar<'info, Clock>,
+    
+    /// CHECK: The pool's compliance program, required when it has one
+    pub compliance_program: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: The user's record under the compliance program, checked in `crate::compliance`
+    pub compliance_record: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Price update account of a trailing stop or trigger, checked in `crate::oracle`
+    pub oracle: Option<UncheckedAccount<'info>>,
+    
+    /// The user's shared escrow to spend from, which reserves the order's
+    /// maximum input
+    #[account(
+        mut,
+        has_one = user @ ContinuumError::InvalidSharedEscrow,
+    )]
+    pub shared_escrow: Option<Box<Account<'info, SharedEscrow>>>,
+    
+    /// Vault of `shared_escrow`, checked in the handler
+    pub shared_escrow_vault: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// The user's permit to spend through, whose token account becomes the
+    /// order's source
+    #[account(has_one = user @ ContinuumError::InvalidPermit)]
+    pub permit: Option<Box<Account<'info, Permit>>>,
+    
+    /// The order's co-signer, required when it must sign the submission
+    pub co_signer: Option<Signer<'info>>,
+    
+    /// Vault the order's deposit is escrowed in, see `crate::order_vault`;
+    /// created when present, with `escrow_source`, `pool_authority` and
+    /// `token_program`. Required unless the order
+    /// spends from a shared escrow, a permit, a delegated approval or
+    /// `SubmitOrderParams::from_wallet`
+    #[account(
+        init,
+        payer = user,
+        seeds = [ORDER_VAULT_SEED, order_state.key().as_ref()],
+        bump,
+        token::mint = input_mint,
+        token::authority = pool_authority,
+    )]
+    pub order_vault: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// Mint the order sells, one of the pool's
+    #[account(
+        constraint = input_mint.key() == pool_registry.token_0 || input_mint.key() == pool_registry.token_1
+            @ ContinuumError::InvalidSource,
+    )]
+    pub input_mint: Box<Account<'info, Mint>>,
+    
+    /// The user's account the deposit is taken from, and refunded to
+    #[account(
+        mut,
+        token::authority = user,
+    )]
+    pub escrow_source: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// CHECK: This is a PDA that owns the order vault
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, pool_id.key().as_ref()],
+        bump
+    )]
+    pub pool_authority: Option<UncheckedAccount<'info>>,
+    
+    pub token_program: Option<Program<'info, Token>>,
 }
 
-pub fn submit_order(
+pub(crate) fn submit_order(
     ctx: Context<SubmitOrder>,
     amount_in: u64,
     min_amount_out: u64,
     is_base_input: bool,
+    params: SubmitOrderParams,
+    conditions: OrderConditions,
 ) -> Result<()> {
-    let fifo_state = &mut ctx.accounts.fifo_state;
+    let SubmitOrderParams {
+        recipient,
+        callback_program,
+        destination_account,
+        post_fill,
+        valid_from,
+        parent_order,
+        trailing_stop,
+        delegated_input,
+        co_signer,
+        trace_id,
+        ttl,
+        execution_policy,
+        client_order_id,
+        memo,
+        from_wallet,
+    } = params;
+    let OrderConditions { limit_price, twap, trigger } = conditions;
+    require!(amount_in > 0, ContinuumError::InvalidAmount);
+    
+    if let Some(required) = co_signer.filter(|required| required.scope.on_submission()) {
+        let signer = ctx.accounts.co_signer.as_ref().map(|signer| signer.key());
+        require!(required.signed_by(signer.as_ref()), ContinuumError::CoSignerRequired);
+    }
+    
+    crate::compliance::check(
+        &ctx.accounts.pool_registry,
+        ctx.accounts.compliance_program.as_ref().map(|program| program.as_ref()),
+        ctx.accounts.compliance_record.as_ref().map(|record| record.as_ref()),
+        &ComplianceQuery {
+            user: ctx.accounts.user.key(),
+            pool_id: ctx.accounts.pool_id.key(),
+            amount_in,
+            is_base_input,
+            recipient,
+        },
+    )?;
+    
+    let fifo_state = &ctx.accounts.fifo_state;
+    let pool_fifo = &mut ctx.accounts.pool_fifo;
     let order_state = &mut ctx.accounts.order_state;
-    let clock = &ctx.accounts.clock;
-    
-    // Get current sequence for PDA (before increment)
-    let pda_sequence = fifo_state.current_sequence;
-    msg!("Submit order - Current FIFO sequence: {}", pda_sequence);
-    
-    // Increment sequence for next order
-    let new_sequence = fifo_state.current_sequence + 1;
-    fifo_state.current_sequence = new_sequence;
-    msg!("Submit order - New FIFO sequence: {}", new_sequence);
-    
-    // Store order details with the incremented sequence
-    order_state.sequence = new_sequence;
-    msg!("Submit order - Order stored with sequence: {}", new_sequence);
-    order_state.user = ctx.accounts.user.key();
-    order_state.pool_id = ctx.accounts.pool_id.key();
-    order_state.amount_in = amount_in;
-    order_state.min_amount_out = min_amount_out;
-    order_state.is_base_input = is_base_input;
-    order_state.status = OrderStatus::Pending;
-    order_state.submitted_at = clock.unix_timestamp;
-    order_state.executed_at = None;
+    let now = crate::clock::unix_timestamp(&ctx.accounts.clock, ctx.remaining_accounts);
+    
+    let expires_at = match ttl {
+        Some(ttl) => {
+            require!(ttl > 0, ContinuumError::InvalidTtl);
+            Some(now.checked_add(ttl).ok_or(ContinuumError::InvalidTtl)?)
+        }
+        None => None,
+    };
+    // A start time already reached would only let the order out of line
+    require!(
+        valid_from.is_none_or(|valid_from| valid_from > now),
+        ContinuumError::InvalidValidFrom
+    );
+    
+    let trailing_stop = match trailing_stop {
+        Some(params) => {
+            require!(params.trail_bps > 0 && params.trail_bps <= 10_000, ContinuumError::InvalidTrailingStop);
+            let oracle = ctx.accounts.oracle.as_ref().ok_or(ContinuumError::InvalidOracle)?;
+            let price = OraclePrice::load(oracle, now)?;
+            Some(TrailingStop::new(oracle.key(), params.direction, params.trail_bps, price.price))
+        }
+        None => None,
+    };
+    if let Some(trigger) = trigger {
+        require!(trigger.trigger_price > 0 && trailing_stop.is_none(), ContinuumError::InvalidTrigger);
+        let oracle = ctx.accounts.oracle.as_ref().ok_or(ContinuumError::InvalidOracle)?;
+        require_keys_eq!(oracle.key(), trigger.oracle, ContinuumError::InvalidOracle);
+        OraclePrice::load(oracle, now)?;
+    }
+    require!(
+        memo.as_ref().is_none_or(|memo| memo.len() <= MAX_ORDER_MEMO_LEN),
+        ContinuumError::MemoTooLong
+    );
+    
+    let order = pool_fifo.enqueue(
+        NewOrder {
+            user: ctx.accounts.user.key(),
+            amount_in,
+            min_amount_out,
+            is_base_input,
+            input_mint: ctx.accounts.input_mint.key(),
+        },
+        fifo_state,
+        &ctx.accounts.pool_registry,
+        now,
+    );
+    let new_sequence = order.sequence;
+    order_state.set_inner(OrderState {
+        recipient,
+        callback_program,
+        destination_account,
+        post_fill,
+        valid_from,
+        parent_order,
+        trailing_stop,
+        delegated_input,
+        co_signer,
+        trace_id: trace_id.unwrap_or(order.trace_id),
+        expires_at,
+        limit_price,
+        twap,
+        execution_policy: execution_policy.unwrap_or(ExecutionPolicy::GoodTillCancelled),
+        trigger,
+        client_order_id,
+        memo,
+        ..order
+    });
+    require!(
+        order_state.execution_policy.allows_conditions() || !order_state.is_conditional(),
+        ContinuumError::InvalidExecutionPolicy
+    );
+    
+    if let Some(shared_escrow) = ctx.accounts.shared_escrow.as_mut() {
+        require!(!delegated_input, ContinuumError::InvalidSharedEscrow);
+        let vault = ctx.accounts.shared_escrow_vault.as_ref().ok_or(ContinuumError::InvalidSharedEscrow)?;
+        require_keys_eq!(vault.key(), crate::shared_escrow::vault_address(&shared_escrow.key()), ContinuumError::InvalidSharedEscrow);
+        require_keys_eq!(shared_escrow.mint, order_state.input_mint, ContinuumError::InvalidSharedEscrow);
+        let reservation = order_state.max_input();
+        require!(shared_escrow.available(vault.amount) >= reservation, ContinuumError::InsufficientEscrow);
+        shared_escrow.reserve(reservation);
+        order_state.source_account = Some(vault.key());
+        order_state.shared_escrow = Some(shared_escrow.key());
+    }
+    
+    if let Some(permit) = ctx.accounts.permit.as_ref() {
+        require!(!delegated_input && order_state.shared_escrow.is_none(), ContinuumError::InvalidPermit);
+        require_keys_eq!(permit.mint, order_state.input_mint, ContinuumError::InvalidPermit);
+        require!(!permit.is_expired(now), ContinuumError::PermitExpired);
+        require!(permit.allowance >= order_state.max_input(), ContinuumError::PermitExceeded);
+        order_state.source_account = Some(permit.token_account);
+        order_state.permit = Some(permit.key());
+    }
+    
+    let funded_elsewhere = delegated_input || order_state.source_account.is_some();
+    require!(!(from_wallet && funded_elsewhere), ContinuumError::InvalidSource);
+    if let Some(order_vault) = ctx.accounts.order_vault.as_ref() {
+        require!(!from_wallet && !funded_elsewhere, ContinuumError::InvalidSource);
+        let escrow_source = ctx.accounts.escrow_source.as_ref().ok_or(ContinuumError::InvalidOrderVault)?;
+        let token_program = ctx.accounts.token_program.as_ref().ok_or(ContinuumError::InvalidOrderVault)?;
+        require_keys_eq!(escrow_source.mint, order_vault.mint, ContinuumError::InvalidOrderVault);
+        let deposit = crate::order_vault::deposit(order_state)?;
+        require!(escrow_source.amount >= deposit, ContinuumError::InsufficientEscrow);
+        token::transfer(
+            CpiContext::new(
+                token_program.to_account_info(),
+                Transfer {
+                    from: escrow_source.to_account_info(),
+                    to: order_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            deposit,
+        )?;
+        order_state.source_account = Some(order_vault.key());
+        order_state.escrow_refund = Some(escrow_source.key());
+        msg!("Order {} escrowed {} in {}", new_sequence, deposit, order_vault.key());
+    } else {
+        require!(from_wallet || funded_elsewhere, ContinuumError::OrderVaultRequired);
+    }
+    
+    crate::ordering::push(&ctx.accounts.user_ordering, order_state.key())?;
     
     emit!(OrderSubmitted {
+        event_sequence: pool_fifo.next_event_sequence(),
         sequence: new_sequence,
         user: ctx.accounts.user.key(),
         pool_id: ctx.accounts.pool_id.key(),
         amount_in,
         is_base_input,
+        trace_id: order_state.trace_id,
+        client_order_id: order_state.client_order_id,
+        memo: order_state.memo.clone(),
     });
     
-    msg!("Order {} submitted by user {} (PDA uses sequence {})", new_sequence, ctx.accounts.user.key(), pda_sequence);
+    msg!("Order {} submitted by user {}", new_sequence, ctx.accounts.user.key());
     
     Ok(())
 }
\ No newline at end of file
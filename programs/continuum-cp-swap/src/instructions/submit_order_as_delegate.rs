@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::compliance::ComplianceQuery;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Enqueue an order for a user under their `UserDelegation` to the signer,
+/// who pays for the order account. The output goes to the user, from whose
+/// own token accounts the input is spent as with `submit_order`.
+#[derive(Accounts)]
+pub struct SubmitOrderAsDelegate<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        constraint = !fifo_state.emergency_pause @ ContinuumError::EmergencyPause,
+    )]
+    pub fifo_state: Box<Account<'info, FifoState>>,
+
+    #[account(
+        seeds = [POOL_REGISTRY_SEED, pool_id.key().as_ref()],
+        bump,
+        constraint = pool_registry.is_active @ ContinuumError::PoolNotRegistered,
+        constraint = !pool_registry.is_paused @ ContinuumError::PoolPaused,
+    )]
+    pub pool_registry: Box<Account<'info, CpSwapPoolRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, pool_id.key().as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Box<Account<'info, PoolFifoState>>,
+
+    #[account(
+        seeds = [USER_DELEGATION_SEED, user_delegation.user.as_ref(), delegate.key().as_ref()],
+        bump,
+    )]
+    pub user_delegation: Box<Account<'info, UserDelegation>>,
+
+    #[account(
+        init,
+        payer = delegate,
+        space = OrderState::LEN,
+        seeds = [ORDER_SEED, pool_id.key().as_ref(), &pool_fifo.upcoming_sequence().to_le_bytes()],
+        bump
+    )]
+    pub order_state: Box<Account<'info, OrderState>>,
+
+    /// CHECK: The user's ordering PDA; may be uninitialized, see `crate::ordering`
+    #[account(
+        mut,
+        seeds = [USER_ORDERING_SEED, user_delegation.user.as_ref()],
+        bump,
+    )]
+    pub user_ordering: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub delegate: Signer<'info>,
+
+    /// CHECK: The pool ID to validate against registry
+    pub pool_id: UncheckedAccount<'info>,
+
+    /// Mint the order sells, one of the pool's
+    #[account(
+        constraint = input_mint.key() == pool_registry.token_0 || input_mint.key() == pool_registry.token_1
+            @ ContinuumError::InvalidSource,
+    )]
+    pub input_mint: Box<Account<'info, Mint>>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+
+    /// CHECK: The pool's compliance program, required when it has one
+    pub compliance_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The user's record under the compliance program, checked in `crate::compliance`
+    pub compliance_record: Option<UncheckedAccount<'info>>,
+}
+
+pub fn submit_order_as_delegate(
+    ctx: Context<SubmitOrderAsDelegate>,
+    amount_in: u64,
+    min_amount_out: u64,
+    is_base_input: bool,
+) -> Result<()> {
+    let user = ctx.accounts.user_delegation.user;
+    let pool_id = ctx.accounts.pool_id.key();
+    let now = crate::clock::unix_timestamp(&ctx.accounts.clock, ctx.remaining_accounts);
+    require!(amount_in > 0, ContinuumError::InvalidAmount);
+
+    crate::compliance::check(
+        &ctx.accounts.pool_registry,
+        ctx.accounts.compliance_program.as_ref().map(|program| program.as_ref()),
+        ctx.accounts.compliance_record.as_ref().map(|record| record.as_ref()),
+        &ComplianceQuery {
+            user,
+            pool_id,
+            amount_in,
+            is_base_input,
+            recipient: None,
+        },
+    )?;
+
+    let pool_fifo = &mut ctx.accounts.pool_fifo;
+    let order_state = &mut ctx.accounts.order_state;
+    order_state.set_inner(pool_fifo.enqueue(
+        NewOrder {
+            user,
+            amount_in,
+            min_amount_out,
+            is_base_input,
+            input_mint: ctx.accounts.input_mint.key(),
+        },
+        &ctx.accounts.fifo_state,
+        &ctx.accounts.pool_registry,
+        now,
+    ));
+    let sequence = order_state.sequence;
+
+    let user_delegation = &ctx.accounts.user_delegation;
+    require!(!user_delegation.is_expired(now), ContinuumError::DelegationExpired);
+    require!(order_state.max_input() <= user_delegation.max_amount_in, ContinuumError::DelegationLimitExceeded);
+
+    crate::ordering::push(&ctx.accounts.user_ordering, order_state.key())?;
+
+    emit!(OrderSubmitted {
+        event_sequence: pool_fifo.next_event_sequence(),
+        sequence,
+        user,
+        pool_id,
+        amount_in,
+        is_base_input,
+        trace_id: order_state.trace_id,
+        client_order_id: order_state.client_order_id,
+        memo: order_state.memo.clone(),
+    });
+
+    msg!("Order {} submitted for user {} by delegate {}", sequence, user, ctx.accounts.delegate.key());
+
+    Ok(())
+}
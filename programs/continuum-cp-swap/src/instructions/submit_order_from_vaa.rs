@@ -0,0 +1,256 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::wormhole::{CrossChainOrder, PostedVaa, TokenTransfer, SOLANA_CHAIN_ID, TOKEN_BRIDGE_PROGRAM_ID, WORMHOLE_PROGRAM_ID};
+
+/// Enqueue an order sent from another chain. The token bridge transfer the
+/// order names must already have been redeemed into the program's custody
+/// account for the mint, for at least the order's deposit, as for
+/// `submit_order`; everything it delivered is moved from custody into the
+/// order's vault, see `crate::order_vault`, and the transfer can't fund
+/// another order. What the order leaves unspent is refunded to the VAA's
+/// refund account when it executes or, once past its expiry, through
+/// `expire_order`.
+#[derive(Accounts)]
+#[instruction(emitter_chain: u16, vaa_sequence: u64)]
+pub struct SubmitOrderFromVaa<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        constraint = !fifo_state.emergency_pause @ ContinuumError::EmergencyPause,
+    )]
+    pub fifo_state: Box<Account<'info, FifoState>>,
+
+    #[account(
+        seeds = [POOL_REGISTRY_SEED, pool_registry.pool_id.as_ref()],
+        bump,
+        constraint = pool_registry.is_active @ ContinuumError::PoolNotRegistered,
+        constraint = !pool_registry.is_paused @ ContinuumError::PoolPaused,
+    )]
+    pub pool_registry: Box<Account<'info, CpSwapPoolRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, pool_registry.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Box<Account<'info, PoolFifoState>>,
+
+    #[account(
+        seeds = [FOREIGN_EMITTER_SEED, emitter_chain.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub foreign_emitter: Box<Account<'info, ForeignEmitter>>,
+
+    /// CHECK: Posted VAA account, verified and written by the core bridge
+    #[account(owner = WORMHOLE_PROGRAM_ID @ ContinuumError::InvalidVaa)]
+    pub posted_vaa: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ReceivedVaa::LEN,
+        seeds = [RECEIVED_SEED, emitter_chain.to_le_bytes().as_ref(), &vaa_sequence.to_le_bytes()],
+        bump
+    )]
+    pub received: Box<Account<'info, ReceivedVaa>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = OrderState::LEN,
+        seeds = [ORDER_SEED, pool_registry.pool_id.as_ref(), &pool_fifo.upcoming_sequence().to_le_bytes()],
+        bump
+    )]
+    pub order_state: Box<Account<'info, OrderState>>,
+
+    /// CHECK: Posted VAA of the token bridge transfer funding the order,
+    /// verified and written by the core bridge
+    #[account(owner = WORMHOLE_PROGRAM_ID @ ContinuumError::InvalidBridgedTransfer)]
+    pub transfer_vaa: UncheckedAccount<'info>,
+
+    /// CHECK: The token bridge's claim of the transfer, checked in the handler
+    #[account(owner = TOKEN_BRIDGE_PROGRAM_ID @ ContinuumError::InvalidBridgedTransfer)]
+    pub transfer_claim: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ReceivedVaa::LEN,
+        seeds = [REDEEMED_TRANSFER_SEED, transfer_vaa.key().as_ref()],
+        bump
+    )]
+    pub redeemed_transfer: Box<Account<'info, ReceivedVaa>>,
+
+    /// CHECK: The order owner named in the VAA payload
+    pub user: UncheckedAccount<'info>,
+
+    /// Mint the order sells, one of the pool's
+    #[account(
+        constraint = mint.key() == pool_registry.token_0 || mint.key() == pool_registry.token_1
+            @ ContinuumError::InvalidSource,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [CUSTODY_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub custody: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: This is a PDA that owns the custody accounts
+    #[account(
+        seeds = [CUSTODY_AUTHORITY_SEED],
+        bump
+    )]
+    pub custody_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [ORDER_VAULT_SEED, order_state.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = pool_authority,
+    )]
+    pub order_vault: Box<Account<'info, TokenAccount>>,
+
+    /// The refund account named in the VAA payload
+    #[account(token::mint = mint)]
+    pub escrow_refund: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: This is a PDA that owns the order vault
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, pool_registry.pool_id.as_ref()],
+        bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn submit_order_from_vaa(
+    ctx: Context<SubmitOrderFromVaa>,
+    emitter_chain: u16,
+    vaa_sequence: u64,
+) -> Result<()> {
+    let vaa = PostedVaa::parse(&ctx.accounts.posted_vaa.try_borrow_data()?)?;
+    require!(
+        vaa.emitter_chain == emitter_chain
+            && vaa.sequence == vaa_sequence
+            && vaa.emitter_address == ctx.accounts.foreign_emitter.address,
+        ContinuumError::InvalidVaa
+    );
+
+    let order = CrossChainOrder::parse(&vaa.payload)?;
+    require_keys_eq!(order.pool_id, ctx.accounts.pool_registry.pool_id, ContinuumError::InvalidVaa);
+    require_keys_eq!(order.user, ctx.accounts.user.key(), ContinuumError::InvalidVaa);
+    require_keys_eq!(order.mint, ctx.accounts.mint.key(), ContinuumError::InvalidVaa);
+    require_keys_eq!(order.refund_account, ctx.accounts.escrow_refund.key(), ContinuumError::InvalidVaa);
+    require!(order.amount_in > 0, ContinuumError::InvalidAmount);
+
+    // The transfer must come from the order's chain, be the one it names and
+    // have been redeemed into custody
+    let transfer_vaa = PostedVaa::parse(&ctx.accounts.transfer_vaa.try_borrow_data()?)?;
+    require!(
+        transfer_vaa.emitter_chain == emitter_chain && transfer_vaa.sequence == order.transfer_sequence,
+        ContinuumError::InvalidBridgedTransfer
+    );
+    let claim = crate::wormhole::claim_address(&transfer_vaa.emitter_address, transfer_vaa.emitter_chain, transfer_vaa.sequence);
+    require_keys_eq!(ctx.accounts.transfer_claim.key(), claim, ContinuumError::InvalidBridgedTransfer);
+    require!(
+        ctx.accounts.transfer_claim.try_borrow_data()?.first() == Some(&1),
+        ContinuumError::InvalidBridgedTransfer
+    );
+    let transfer = TokenTransfer::parse(&transfer_vaa.payload)?;
+    require!(
+        transfer.to == ctx.accounts.custody.key() && transfer.to_chain == SOLANA_CHAIN_ID,
+        ContinuumError::InvalidBridgedTransfer
+    );
+    let received = transfer.received(ctx.accounts.mint.decimals).ok_or(ContinuumError::InvalidBridgedTransfer)?;
+
+    let now = crate::clock::unix_timestamp(&Clock::get()?, ctx.remaining_accounts);
+    let pool_fifo = &mut ctx.accounts.pool_fifo;
+    let order_state = &mut ctx.accounts.order_state;
+    order_state.set_inner(OrderState {
+        source_account: Some(ctx.accounts.order_vault.key()),
+        escrow_refund: Some(order.refund_account),
+        expires_at: Some(order.expires_at),
+        ..pool_fifo.enqueue(
+            NewOrder {
+                user: order.user,
+                amount_in: order.amount_in,
+                min_amount_out: order.min_amount_out,
+                is_base_input: order.is_base_input,
+                input_mint: ctx.accounts.mint.key(),
+            },
+            &ctx.accounts.fifo_state,
+            &ctx.accounts.pool_registry,
+            now,
+        )
+    });
+    let sequence = order_state.sequence;
+
+    // Move the transfer's funds out of custody into the order's vault; what
+    // it delivered over the deposit is refunded with the rest
+    let deposit = crate::order_vault::deposit(order_state)?;
+    require!(received >= deposit, ContinuumError::InvalidBridgedTransfer);
+    let custody_authority_bump = ctx.bumps.custody_authority;
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.custody.to_account_info(),
+                to: ctx.accounts.order_vault.to_account_info(),
+                authority: ctx.accounts.custody_authority.to_account_info(),
+            },
+            &[&[CUSTODY_AUTHORITY_SEED, &[custody_authority_bump]]],
+        ),
+        received,
+    )?;
+
+    let redeemed_transfer = &mut ctx.accounts.redeemed_transfer;
+    redeemed_transfer.emitter_chain = transfer_vaa.emitter_chain;
+    redeemed_transfer.vaa_sequence = transfer_vaa.sequence;
+    redeemed_transfer.order_sequence = sequence;
+    redeemed_transfer.received_at = now;
+
+    let received = &mut ctx.accounts.received;
+    received.emitter_chain = emitter_chain;
+    received.vaa_sequence = vaa_sequence;
+    received.order_sequence = sequence;
+    received.received_at = now;
+
+    emit!(OrderSubmitted {
+        event_sequence: pool_fifo.next_event_sequence(),
+        sequence,
+        user: order.user,
+        pool_id: order.pool_id,
+        amount_in: order.amount_in,
+        is_base_input: order.is_base_input,
+        trace_id: order_state.trace_id,
+        client_order_id: order_state.client_order_id,
+        memo: order_state.memo.clone(),
+    });
+
+    emit!(CrossChainOrderReceived {
+        event_sequence: pool_fifo.next_event_sequence(),
+        sequence,
+        pool_id: order.pool_id,
+        emitter_chain,
+        vaa_sequence,
+        user: order.user,
+    });
+
+    msg!("Cross-chain order {} received from chain {} (VAA sequence {})", sequence, emitter_chain, vaa_sequence);
+
+    Ok(())
+}
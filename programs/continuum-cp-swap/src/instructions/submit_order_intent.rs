@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use crate::compliance::ComplianceQuery;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::intent::{verify_previous_ed25519, OrderIntent};
+
+/// Enqueue an order the user signed off-chain. The user's signature over
+/// `intent.message()` must be verified by an ed25519 program instruction
+/// placed directly before this one; the caller pays for the accounts, so the
+/// user needs no SOL. As with `submit_order`, the user approves the pool
+/// authority on their source token account before the order is executed.
+#[derive(Accounts)]
+#[instruction(intent: OrderIntent)]
+pub struct SubmitOrderIntent<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        constraint = !fifo_state.emergency_pause @ ContinuumError::EmergencyPause,
+    )]
+    pub fifo_state: Box<Account<'info, FifoState>>,
+
+    #[account(
+        seeds = [POOL_REGISTRY_SEED, intent.pool_id.as_ref()],
+        bump,
+        constraint = pool_registry.is_active @ ContinuumError::PoolNotRegistered,
+        constraint = !pool_registry.is_paused @ ContinuumError::PoolPaused,
+    )]
+    pub pool_registry: Box<Account<'info, CpSwapPoolRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, intent.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Box<Account<'info, PoolFifoState>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = UsedIntent::LEN,
+        seeds = [INTENT_SEED, intent.user.as_ref(), &intent.nonce.to_le_bytes()],
+        bump
+    )]
+    pub used_intent: Box<Account<'info, UsedIntent>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = OrderState::LEN,
+        seeds = [ORDER_SEED, intent.pool_id.as_ref(), &pool_fifo.upcoming_sequence().to_le_bytes()],
+        bump
+    )]
+    pub order_state: Box<Account<'info, OrderState>>,
+
+    /// CHECK: The user's ordering PDA; may be uninitialized, see `crate::ordering`
+    #[account(
+        mut,
+        seeds = [USER_ORDERING_SEED, intent.user.as_ref()],
+        bump,
+    )]
+    pub user_ordering: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: The instructions sysvar, read to find the ed25519 verification
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: The pool's compliance program, required when it has one
+    pub compliance_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The user's record under the compliance program, checked in `crate::compliance`
+    pub compliance_record: Option<UncheckedAccount<'info>>,
+}
+
+pub fn submit_order_intent(ctx: Context<SubmitOrderIntent>, intent: OrderIntent) -> Result<()> {
+    verify_previous_ed25519(
+        &ctx.accounts.instructions.to_account_info(),
+        &intent.user,
+        &intent.message(),
+    )?;
+
+    let now = crate::clock::unix_timestamp(&Clock::get()?, ctx.remaining_accounts);
+    require!(now <= intent.expires_at, ContinuumError::IntentExpired);
+    require!(intent.amount_in > 0, ContinuumError::InvalidAmount);
+    require!(ctx.accounts.pool_registry.vault(&intent.input_mint).is_some(), ContinuumError::InvalidSource);
+
+    crate::compliance::check(
+        &ctx.accounts.pool_registry,
+        ctx.accounts.compliance_program.as_ref().map(|program| program.as_ref()),
+        ctx.accounts.compliance_record.as_ref().map(|record| record.as_ref()),
+        &ComplianceQuery {
+            user: intent.user,
+            pool_id: intent.pool_id,
+            amount_in: intent.amount_in,
+            is_base_input: intent.is_base_input,
+            recipient: intent.recipient,
+        },
+    )?;
+
+    let pool_fifo = &mut ctx.accounts.pool_fifo;
+    let order_state = &mut ctx.accounts.order_state;
+    order_state.set_inner(OrderState {
+        recipient: intent.recipient,
+        ..pool_fifo.enqueue(
+            NewOrder {
+                user: intent.user,
+                amount_in: intent.amount_in,
+                min_amount_out: intent.min_amount_out,
+                is_base_input: intent.is_base_input,
+                input_mint: intent.input_mint,
+            },
+            &ctx.accounts.fifo_state,
+            &ctx.accounts.pool_registry,
+            now,
+        )
+    });
+    let sequence = order_state.sequence;
+
+    crate::ordering::push(&ctx.accounts.user_ordering, order_state.key())?;
+
+    let used_intent = &mut ctx.accounts.used_intent;
+    used_intent.user = intent.user;
+    used_intent.nonce = intent.nonce;
+    used_intent.order_sequence = sequence;
+    used_intent.relayer = ctx.accounts.payer.key();
+    used_intent.submitted_at = now;
+
+    emit!(OrderSubmitted {
+        event_sequence: pool_fifo.next_event_sequence(),
+        sequence,
+        user: intent.user,
+        pool_id: intent.pool_id,
+        amount_in: intent.amount_in,
+        is_base_input: intent.is_base_input,
+        trace_id: order_state.trace_id,
+        client_order_id: order_state.client_order_id,
+        memo: order_state.memo.clone(),
+    });
+
+    msg!("Order {} submitted for user {} by relayer {}", sequence, intent.user, ctx.accounts.payer.key());
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount};
+use crate::consts::*;
 use crate::state::*;
 use crate::errors::*;
 
@@ -7,19 +8,27 @@ use crate::errors::*;
 pub struct SubmitOrderSimple<'info> {
     #[account(
         mut,
-        seeds = [b"fifo_state"],
+        seeds = [FIFO_STATE_SEED],
         bump,
         constraint = !fifo_state.emergency_pause @ ContinuumError::EmergencyPause,
     )]
     pub fifo_state: Account<'info, FifoState>,
     
     #[account(
-        seeds = [b"pool_registry", pool_id.key().as_ref()],
+        seeds = [POOL_REGISTRY_SEED, pool_id.key().as_ref()],
         bump,
         constraint = pool_registry.is_active @ ContinuumError::PoolNotRegistered,
+        constraint = !pool_registry.is_paused @ ContinuumError::PoolPaused,
     )]
     pub pool_registry: Account<'info, CpSwapPoolRegistry>,
     
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, pool_id.key().as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Account<'info, PoolFifoState>,
+    
     #[account(mut)]
     pub user: Signer<'info>,
     
@@ -52,7 +61,8 @@ pub fn submit_order_simple(
 ) -> Result<()> {
     let fifo_state = &mut ctx.accounts.fifo_state;
     
-    // Just increment sequence and emit event
+    // Just increment sequence and emit event; no order takes a place in the
+    // pool's queue, so the pool's stream hash is left alone
     let sequence = fifo_state.current_sequence + 1;
     fifo_state.current_sequence = sequence;
     
@@ -63,11 +73,15 @@ pub fn submit_order_simple(
     );
     
     emit!(OrderSubmitted {
+        event_sequence: ctx.accounts.pool_fifo.next_event_sequence(),
         sequence,
         user: ctx.accounts.user.key(),
         pool_id: ctx.accounts.pool_id.key(),
         amount_in,
         is_base_input,
+        trace_id: default_trace_id(&ctx.accounts.pool_id.key(), sequence),
+        client_order_id: None,
+        memo: None,
     });
     
     Ok(())
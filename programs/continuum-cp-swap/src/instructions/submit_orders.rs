@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Most orders one `submit_orders` can enqueue
+pub const MAX_BATCH_ORDERS: usize = 8;
+
+/// One leg of a `submit_orders` batch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BatchOrder {
+    pub pool_id: Pubkey,
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+    pub is_base_input: bool,
+    /// Mint the order sells, one of its pool's
+    pub input_mint: Pubkey,
+    pub recipient: Option<Pubkey>,
+    /// Chosen trace ID, see `submit_order`
+    pub trace_id: Option<TraceId>,
+    /// Must be set: batched orders have no vault, so their input stays in
+    /// the user's wallet, for the pool authority to spend by approval, see
+    /// `SubmitOrderParams::from_wallet`
+    pub from_wallet: bool,
+}
+
+/// Enqueue several orders, possibly on different pools, each at the next
+/// sequence of its pool. For each order, in order, the remaining accounts
+/// hold its pool's registry, its pool's FIFO queue and its uninitialized
+/// order account, derived like `submit_order`'s from the pool and the
+/// order's sequence. Pools with a compliance program, and orders escrowed in
+/// a vault, only go through `submit_order`.
+#[derive(Accounts)]
+pub struct SubmitOrders<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        constraint = !fifo_state.emergency_pause @ ContinuumError::EmergencyPause,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    /// CHECK: The user's ordering PDA; may be uninitialized, see `crate::ordering`
+    #[account(
+        mut,
+        seeds = [USER_ORDERING_SEED, user.key().as_ref()],
+        bump,
+    )]
+    pub user_ordering: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn submit_orders<'info>(
+    ctx: Context<'_, '_, '_, 'info, SubmitOrders<'info>>,
+    orders: Vec<BatchOrder>,
+) -> Result<()> {
+    require!(
+        !orders.is_empty() && orders.len() <= MAX_BATCH_ORDERS,
+        ContinuumError::InvalidBatchSize
+    );
+    require!(
+        ctx.remaining_accounts.len() >= orders.len() * 3,
+        ContinuumError::InvalidRemainingAccounts
+    );
+
+    let user = ctx.accounts.user.key();
+    let now = crate::clock::unix_timestamp(&ctx.accounts.clock, ctx.remaining_accounts);
+
+    for (order, accounts) in orders.iter().zip(ctx.remaining_accounts.chunks_exact(3)) {
+        let [registry_info, pool_fifo_info, order_info] = accounts else {
+            return err!(ContinuumError::InvalidRemainingAccounts);
+        };
+        require!(order.amount_in > 0, ContinuumError::InvalidAmount);
+        require!(order.from_wallet, ContinuumError::OrderVaultRequired);
+
+        let registry_address = Pubkey::find_program_address(&[POOL_REGISTRY_SEED, order.pool_id.as_ref()], &crate::ID).0;
+        require_keys_eq!(registry_info.key(), registry_address, ContinuumError::PoolNotRegistered);
+        let registry = crate::optional::load::<CpSwapPoolRegistry>(registry_info)?
+            .filter(|registry| registry.is_active)
+            .ok_or(ContinuumError::PoolNotRegistered)?;
+        require!(!registry.is_paused, ContinuumError::PoolPaused);
+        require!(registry.compliance_program().is_none(), ContinuumError::MissingComplianceCheck);
+        require!(registry.vault(&order.input_mint).is_some(), ContinuumError::InvalidSource);
+
+        let pool_fifo_address = Pubkey::find_program_address(&[POOL_FIFO_SEED, order.pool_id.as_ref()], &crate::ID).0;
+        require_keys_eq!(pool_fifo_info.key(), pool_fifo_address, ContinuumError::PoolFifoNotInitialized);
+        let mut pool_fifo = crate::optional::load::<PoolFifoState>(pool_fifo_info)?
+            .ok_or(ContinuumError::PoolFifoNotInitialized)?;
+
+        let sequence = pool_fifo.upcoming_sequence();
+        let (order_address, bump) =
+            Pubkey::find_program_address(&[ORDER_SEED, order.pool_id.as_ref(), &sequence.to_le_bytes()], &crate::ID);
+        require_keys_eq!(order_info.key(), order_address, ContinuumError::InvalidRemainingAccounts);
+
+        let queued = pool_fifo.enqueue(
+            NewOrder {
+                user,
+                amount_in: order.amount_in,
+                min_amount_out: order.min_amount_out,
+                is_base_input: order.is_base_input,
+                input_mint: order.input_mint,
+            },
+            &ctx.accounts.fifo_state,
+            &registry,
+            now,
+        );
+        let order_state = OrderState {
+            recipient: order.recipient,
+            trace_id: order.trace_id.unwrap_or(queued.trace_id),
+            ..queued
+        };
+
+        crate::optional::create(
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            order_info,
+            OrderState::LEN,
+            &[ORDER_SEED, order.pool_id.as_ref(), &sequence.to_le_bytes(), &[bump]],
+        )?;
+        crate::optional::store(order_info, &order_state)?;
+
+        crate::ordering::push(&ctx.accounts.user_ordering, order_address)?;
+
+        emit!(OrderSubmitted {
+            event_sequence: pool_fifo.next_event_sequence(),
+            sequence,
+            user,
+            pool_id: order.pool_id,
+            amount_in: order.amount_in,
+            is_base_input: order.is_base_input,
+            trace_id: order_state.trace_id,
+            client_order_id: None,
+            memo: None,
+        });
+        crate::optional::store(pool_fifo_info, &pool_fifo)?;
+    }
+
+    msg!("{} orders submitted by user {}", orders.len(), user);
+
+    Ok(())
+}
@@ -0,0 +1,253 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::compliance::ComplianceQuery;
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Enqueue an order for an organization as one of its members, within the
+/// member's limits. The order spends from the organization's escrow of the
+/// input mint, which reserves its maximum input, and pays into its escrow of
+/// the output mint. The member pays for the order account.
+#[derive(Accounts)]
+pub struct SubmitOrgOrder<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        constraint = !fifo_state.emergency_pause @ ContinuumError::EmergencyPause,
+    )]
+    pub fifo_state: Box<Account<'info, FifoState>>,
+
+    #[account(
+        seeds = [POOL_REGISTRY_SEED, pool_id.key().as_ref()],
+        bump,
+        constraint = pool_registry.is_active @ ContinuumError::PoolNotRegistered,
+        constraint = !pool_registry.is_paused @ ContinuumError::PoolPaused,
+    )]
+    pub pool_registry: Box<Account<'info, CpSwapPoolRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, pool_id.key().as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Box<Account<'info, PoolFifoState>>,
+
+    #[account(
+        seeds = [ORGANIZATION_SEED, organization.owner.as_ref(), &organization.org_id.to_le_bytes()],
+        bump,
+    )]
+    pub organization: Box<Account<'info, Organization>>,
+
+    #[account(
+        init,
+        payer = member,
+        space = OrderState::LEN,
+        seeds = [ORDER_SEED, pool_id.key().as_ref(), &pool_fifo.upcoming_sequence().to_le_bytes()],
+        bump
+    )]
+    pub order_state: Box<Account<'info, OrderState>>,
+
+    /// CHECK: The organization's ordering PDA; may be uninitialized, see `crate::ordering`
+    #[account(
+        mut,
+        seeds = [USER_ORDERING_SEED, organization.key().as_ref()],
+        bump,
+    )]
+    pub user_ordering: UncheckedAccount<'info>,
+
+    /// The organization's escrow of the input mint
+    #[account(
+        mut,
+        seeds = [SHARED_ESCROW_SEED, organization.key().as_ref(), input_escrow.mint.as_ref()],
+        bump,
+    )]
+    pub input_escrow: Box<Account<'info, SharedEscrow>>,
+
+    #[account(
+        seeds = [SHARED_ESCROW_VAULT_SEED, input_escrow.key().as_ref()],
+        bump,
+    )]
+    pub input_vault: Box<Account<'info, TokenAccount>>,
+
+    /// The organization's escrow of the output mint
+    #[account(
+        seeds = [SHARED_ESCROW_SEED, organization.key().as_ref(), output_escrow.mint.as_ref()],
+        bump,
+    )]
+    pub output_escrow: Box<Account<'info, SharedEscrow>>,
+
+    #[account(
+        seeds = [SHARED_ESCROW_VAULT_SEED, output_escrow.key().as_ref()],
+        bump,
+    )]
+    pub output_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    /// CHECK: The pool ID to validate against registry
+    pub pool_id: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+
+    /// CHECK: The pool's compliance program, required when it has one
+    pub compliance_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The organization's record under the compliance program, checked in `crate::compliance`
+    pub compliance_record: Option<UncheckedAccount<'info>>,
+}
+
+/// Cancel a pending order of an organization as its owner or a member,
+/// releasing the order's reservation
+#[derive(Accounts)]
+pub struct CancelOrgOrder<'info> {
+    #[account(
+        seeds = [ORGANIZATION_SEED, organization.owner.as_ref(), &organization.org_id.to_le_bytes()],
+        bump,
+    )]
+    pub organization: Box<Account<'info, Organization>>,
+
+    #[account(
+        mut,
+        seeds = [ORDER_SEED, order_state.pool_id.as_ref(), &order_state.sequence.to_le_bytes()],
+        bump,
+        constraint = order_state.status == OrderStatus::Pending @ ContinuumError::InvalidOrderStatus,
+        constraint = order_state.user == organization.key() @ ContinuumError::Unauthorized,
+    )]
+    pub order_state: Account<'info, OrderState>,
+
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, order_state.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Account<'info, PoolFifoState>,
+
+    /// CHECK: The organization's ordering PDA; may be uninitialized, see `crate::ordering`
+    #[account(
+        mut,
+        seeds = [USER_ORDERING_SEED, organization.key().as_ref()],
+        bump,
+    )]
+    pub user_ordering: UncheckedAccount<'info>,
+
+    /// The order's escrow
+    #[account(mut)]
+    pub shared_escrow: Account<'info, SharedEscrow>,
+
+    pub signer: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn submit_org_order(
+    ctx: Context<SubmitOrgOrder>,
+    amount_in: u64,
+    min_amount_out: u64,
+    is_base_input: bool,
+) -> Result<()> {
+    let organization = ctx.accounts.organization.key();
+    let pool_id = ctx.accounts.pool_id.key();
+    let now = crate::clock::unix_timestamp(&ctx.accounts.clock, ctx.remaining_accounts);
+    require!(amount_in > 0, ContinuumError::InvalidAmount);
+
+    let registry = &ctx.accounts.pool_registry;
+    let (input_mint, output_mint) = (ctx.accounts.input_escrow.mint, ctx.accounts.output_escrow.mint);
+    let pair = (input_mint == registry.token_0 && output_mint == registry.token_1) ||
+        (input_mint == registry.token_1 && output_mint == registry.token_0);
+    require!(pair, ContinuumError::InvalidSharedEscrow);
+
+    crate::compliance::check(
+        registry,
+        ctx.accounts.compliance_program.as_ref().map(|program| program.as_ref()),
+        ctx.accounts.compliance_record.as_ref().map(|record| record.as_ref()),
+        &ComplianceQuery {
+            user: organization,
+            pool_id,
+            amount_in,
+            is_base_input,
+            recipient: None,
+        },
+    )?;
+
+    let pool_fifo = &mut ctx.accounts.pool_fifo;
+    let order_state = &mut ctx.accounts.order_state;
+    order_state.set_inner(OrderState {
+        destination_account: Some(ctx.accounts.output_vault.key()),
+        source_account: Some(ctx.accounts.input_vault.key()),
+        shared_escrow: Some(ctx.accounts.input_escrow.key()),
+        ..pool_fifo.enqueue(
+            NewOrder {
+                user: organization,
+                amount_in,
+                min_amount_out,
+                is_base_input,
+                input_mint,
+            },
+            &ctx.accounts.fifo_state,
+            &ctx.accounts.pool_registry,
+            now,
+        )
+    });
+    let sequence = order_state.sequence;
+
+    let member = ctx.accounts.organization.member(&ctx.accounts.member.key()).ok_or(ContinuumError::NotOrgMember)?;
+    let reservation = order_state.max_input();
+    require!(member.allows(&pool_id, reservation), ContinuumError::OrgLimitExceeded);
+
+    let input_escrow = &mut ctx.accounts.input_escrow;
+    require!(input_escrow.available(ctx.accounts.input_vault.amount) >= reservation, ContinuumError::InsufficientEscrow);
+    input_escrow.reserve(reservation);
+
+    crate::ordering::push(&ctx.accounts.user_ordering, order_state.key())?;
+
+    emit!(OrderSubmitted {
+        event_sequence: pool_fifo.next_event_sequence(),
+        sequence,
+        user: organization,
+        pool_id,
+        amount_in,
+        is_base_input,
+        trace_id: order_state.trace_id,
+        client_order_id: order_state.client_order_id,
+        memo: order_state.memo.clone(),
+    });
+
+    msg!(
+        "Order {} submitted for organization {} by member {}",
+        sequence,
+        ctx.accounts.organization.org_id,
+        ctx.accounts.member.key()
+    );
+
+    Ok(())
+}
+
+pub fn cancel_org_order(ctx: Context<CancelOrgOrder>) -> Result<()> {
+    require!(ctx.accounts.organization.includes(&ctx.accounts.signer.key()), ContinuumError::NotOrgMember);
+
+    let now = crate::clock::unix_timestamp(&ctx.accounts.clock, ctx.remaining_accounts);
+    let order_state = &mut ctx.accounts.order_state;
+    let shared_escrow = &mut ctx.accounts.shared_escrow;
+    require!(order_state.shared_escrow == Some(shared_escrow.key()), ContinuumError::InvalidSharedEscrow);
+    shared_escrow.release(order_state.max_input());
+
+    order_state.status = OrderStatus::Cancelled;
+    order_state.executed_at = Some(now);
+
+    crate::ordering::remove(&ctx.accounts.user_ordering, &order_state.key())?;
+
+    emit!(OrderCancelled {
+        event_sequence: ctx.accounts.pool_fifo.next_event_sequence(),
+        sequence: order_state.sequence,
+        pool_id: order_state.pool_id,
+        user: order_state.user,
+        trace_id: order_state.trace_id,
+    });
+
+    msg!("Order {} of organization {} cancelled by {}", order_state.sequence, ctx.accounts.organization.org_id, ctx.accounts.signer.key());
+
+    Ok(())
+}
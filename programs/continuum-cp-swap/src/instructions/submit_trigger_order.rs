@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use super::submit_order::{submit_order, OrderConditions, SubmitOrder, SubmitOrderParams};
+
+/// Enqueue a stop-loss or take-profit order, held until the price of the
+/// passed oracle account crosses `trigger_price` in `direction`. Its
+/// execution checks the oracle, which must be fresh at submission too. The
+/// optional accounts of `submit_order` fund it the same way.
+#[allow(clippy::too_many_arguments)]
+pub fn submit_trigger_order(
+    ctx: Context<SubmitOrder>,
+    amount_in: u64,
+    min_amount_out: u64,
+    is_base_input: bool,
+    trigger_price: i64,
+    direction: TriggerDirection,
+    recipient: Option<Pubkey>,
+    trace_id: Option<TraceId>,
+    ttl: Option<i64>,
+) -> Result<()> {
+    let oracle = ctx.accounts.oracle.as_ref().ok_or(ContinuumError::InvalidOracle)?.key();
+    let trigger = TriggerCondition {
+        oracle,
+        trigger_price,
+        direction,
+    };
+
+    submit_order(
+        ctx,
+        amount_in,
+        min_amount_out,
+        is_base_input,
+        SubmitOrderParams {
+            recipient,
+            trace_id,
+            ttl,
+            ..Default::default()
+        },
+        OrderConditions {
+            trigger: Some(trigger),
+            ..Default::default()
+        },
+    )
+}
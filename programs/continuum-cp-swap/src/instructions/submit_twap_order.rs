@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use super::submit_order::{submit_order, OrderConditions, SubmitOrder, SubmitOrderParams};
+
+/// Enqueue a TWAP order selling `amount_in` in `num_slices` slices at least
+/// `interval_seconds` apart, for at least `min_amount_out` in total and the
+/// same share of it per slice. The whole input is escrowed in the order's
+/// vault, so its accounts must be passed; the vault is settled when the
+/// last slice executes or the order is cancelled or expires.
+#[allow(clippy::too_many_arguments)]
+pub fn submit_twap_order(
+    ctx: Context<SubmitOrder>,
+    amount_in: u64,
+    min_amount_out: u64,
+    num_slices: u16,
+    interval_seconds: i64,
+    recipient: Option<Pubkey>,
+    trace_id: Option<TraceId>,
+    ttl: Option<i64>,
+) -> Result<()> {
+    require!(
+        num_slices >= 2 && interval_seconds > 0 && amount_in >= num_slices as u64,
+        ContinuumError::InvalidTwap
+    );
+    require!(ctx.accounts.order_vault.is_some(), ContinuumError::InvalidOrderVault);
+
+    submit_order(
+        ctx,
+        amount_in,
+        min_amount_out,
+        true,
+        SubmitOrderParams {
+            recipient,
+            trace_id,
+            ttl,
+            ..Default::default()
+        },
+        OrderConditions {
+            twap: Some(TwapSchedule::new(num_slices, interval_seconds)),
+            ..Default::default()
+        },
+    )
+}
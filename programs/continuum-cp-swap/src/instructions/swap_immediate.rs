@@ -1,101 +1,148 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{
-    program::invoke_signed,
-    instruction::{Instruction, AccountMeta},
-};
+use anchor_spl::token::TokenAccount;
+use crate::compliance::ComplianceQuery;
+use crate::consts::*;
+use crate::cp_swap::{self, Swap};
 use crate::state::*;
 use crate::errors::ContinuumError;
+use super::execute_order::{swap_deltas, within_limit};
 
 #[derive(Accounts)]
+#[instruction(amount_in: u64, min_amount_out: u64, is_base_input: bool, pool_id: Pubkey)]
 pub struct SwapImmediate<'info> {
     #[account(
-        mut,
-        seeds = [b"fifo_state"],
+        seeds = [FIFO_STATE_SEED],
         bump,
         constraint = !fifo_state.emergency_pause @ ContinuumError::EmergencyPause,
     )]
     pub fifo_state: Account<'info, FifoState>,
     
-    /// CHECK: The CP-Swap program
+    #[account(
+        mut,
+        seeds = [POOL_REGISTRY_SEED, pool_id.as_ref()],
+        bump,
+        constraint = !pool_registry.is_paused @ ContinuumError::PoolPaused,
+    )]
+    pub pool_registry: Box<Account<'info, CpSwapPoolRegistry>>,
+    
+    /// The pool's queue, which numbers its immediate swaps; they take no
+    /// place in it
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Box<Account<'info, PoolFifoState>>,
+    
+    /// CHECK: The CP-Swap program recorded in the FIFO state
+    #[account(address = fifo_state.cp_swap_program @ ContinuumError::InvalidCpSwapProgram)]
     pub cp_swap_program: UncheckedAccount<'info>,
     
-    // All other accounts (user, pool_authority, pool_id, user accounts, etc.) 
-    // are passed through in remaining_accounts to avoid deserialization
+    /// CHECK: The pool's compliance program, required when it has one
+    pub compliance_program: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: The user's record under the compliance program, checked in `crate::compliance`
+    pub compliance_record: Option<UncheckedAccount<'info>>,
+    
+    /// Registration of the relayer paying for the swap, checked below; its
+    /// swaps count against the rate limit
+    #[account(mut)]
+    pub relayer_info: Box<Account<'info, RelayerInfo>>,
+    
+    // The CP-Swap swap accounts, user first, follow in remaining_accounts
+    // to avoid deserialization, see `cp_swap::Swap::parse`
 }
 
-pub fn swap_immediate(
-    ctx: Context<SwapImmediate>,
+pub fn swap_immediate<'info>(
+    ctx: Context<'_, '_, '_, 'info, SwapImmediate<'info>>,
     amount_in: u64,
     min_amount_out: u64,
     is_base_input: bool,
     pool_id: Pubkey,
     pool_authority_bump: u8,
+    deadline: Option<i64>,
 ) -> Result<()> {
-    let fifo_state = &mut ctx.accounts.fifo_state;
+    let now = crate::clock::unix_timestamp(&Clock::get()?, ctx.remaining_accounts);
+    if let Some(deadline) = deadline {
+        require!(now <= deadline, ContinuumError::DeadlineExceeded);
+    }
     
-    // Increment sequence for tracking
-    let sequence = fifo_state.current_sequence + 1;
-    fifo_state.current_sequence = sequence;
+    if let Some(account) = crate::sanitize::writable_program_account(ctx.remaining_accounts) {
+        msg!("Writable Continuum account {} passed to the swap", account.key());
+        return err!(ContinuumError::InvalidRemainingAccounts);
+    }
+    let swap = Swap::parse(ctx.remaining_accounts)?;
+    require!(swap.pool.matches(&ctx.accounts.pool_registry), ContinuumError::InvalidRemainingAccounts);
     
-    msg!("Immediate swap {} on pool {}", sequence, pool_id);
+    let fifo_state = &ctx.accounts.fifo_state;
     
-    // Build the swap instruction data
-    let mut ix_data = Vec::new();
-    
-    if is_base_input {
-        // swap_base_input discriminator
-        ix_data.extend_from_slice(&[143, 190, 90, 218, 196, 30, 51, 222]); 
-        ix_data.extend_from_slice(&amount_in.to_le_bytes());
-        ix_data.extend_from_slice(&min_amount_out.to_le_bytes());
-    } else {
-        // swap_base_output discriminator
-        ix_data.extend_from_slice(&[55, 217, 98, 86, 163, 74, 180, 173]);
-        ix_data.extend_from_slice(&min_amount_out.to_le_bytes()); // max_amount_in
-        ix_data.extend_from_slice(&amount_in.to_le_bytes()); // amount_out
-    }
+    // Number the swap among the pool's immediate swaps for tracking
+    let pool_fifo = &mut ctx.accounts.pool_fifo;
+    pool_fifo.immediate_swaps += 1;
+    let sequence = pool_fifo.immediate_swaps;
     
-    // Build account metas from remaining accounts
-    // The client must pass accounts in the correct order for CP-Swap
-    let mut account_metas = vec![];
-    
-    // Add all remaining accounts as they were passed
-    for (i, account) in ctx.remaining_accounts.iter().enumerate() {
-        // First account should be the user (payer for CP-Swap)
-        if i == 0 {
-            account_metas.push(AccountMeta::new_readonly(account.key(), true));
-        } else {
-            account_metas.push(if account.is_writable {
-                AccountMeta::new(account.key(), false)
-            } else {
-                AccountMeta::new_readonly(account.key(), false)
-            });
-        }
+    // The user pays for the swap
+    let user = swap.payer.key();
+    let relayer_address = Pubkey::find_program_address(&[RELAYER_SEED, user.as_ref()], &crate::ID).0;
+    require_keys_eq!(ctx.accounts.relayer_info.key(), relayer_address, ContinuumError::RelayerNotRegistered);
+    let relayer_info = &mut ctx.accounts.relayer_info;
+    if fifo_state.max_swaps_per_window > 0 {
+        require!(
+            relayer_info.swap_rate.record(now, fifo_state.swap_rate_window, fifo_state.max_swaps_per_window),
+            ContinuumError::SwapRateExceeded
+        );
     }
-    
-    // Create the instruction
-    let ix = Instruction {
-        program_id: ctx.accounts.cp_swap_program.key(),
-        accounts: account_metas,
-        data: ix_data,
-    };
+    crate::compliance::check(
+        &ctx.accounts.pool_registry,
+        ctx.accounts.compliance_program.as_ref().map(|program| program.as_ref()),
+        ctx.accounts.compliance_record.as_ref().map(|record| record.as_ref()),
+        &ComplianceQuery {
+            user,
+            pool_id,
+            amount_in,
+            is_base_input,
+            recipient: None,
+        },
+    )?;
+    msg!("Immediate swap {} on pool {}", sequence, pool_id);
     
     // Invoke CP-Swap with pool authority signer
     let pool_authority_seeds = &[
-        b"cp_pool_authority",
+        POOL_AUTHORITY_SEED,
         pool_id.as_ref(),
         &[pool_authority_bump],
     ];
     
-    // Pass all remaining accounts directly to invoke_signed
-    // The client must ensure the correct ordering
-    invoke_signed(
-        &ix,
-        ctx.remaining_accounts,
+    // Snapshot the user's balances for volume accounting and the slippage check
+    let (input_mint, input_start_balance) = token_account_state(&swap.input_token_account)?;
+    let (_, output_start_balance) = token_account_state(&swap.output_token_account)?;
+    
+    cp_swap::swap(
+        &ctx.accounts.cp_swap_program,
+        &swap,
         &[pool_authority_seeds],
+        is_base_input,
+        amount_in,
+        min_amount_out,
+    )?;
+    
+    let (_, input_end_balance) = token_account_state(&swap.input_token_account)?;
+    let (_, output_end_balance) = token_account_state(&swap.output_token_account)?;
+    let (amount_spent, amount_out) = swap_deltas(
+        (input_start_balance, input_end_balance),
+        (output_start_balance, output_end_balance),
     )?;
+    require!(
+        within_limit(is_base_input, amount_spent, amount_out, min_amount_out),
+        ContinuumError::SlippageExceeded
+    );
+    
+    ctx.accounts.pool_registry.record_volume(&input_mint, amount_spent, amount_out, now);
     
     emit!(SwapExecuted {
+        event_sequence: pool_fifo.next_event_sequence(),
         sequence,
+        user,
         pool_id,
         amount_in,
         is_base_input,
@@ -106,9 +153,19 @@ pub fn swap_immediate(
     Ok(())
 }
 
+/// Read the mint and balance of an SPL token account passed as a remaining account
+fn token_account_state(account: &AccountInfo) -> Result<(Pubkey, u64)> {
+    let data = account.try_borrow_data()?;
+    let token_account = TokenAccount::try_deserialize(&mut &data[..])?;
+    Ok((token_account.mint, token_account.amount))
+}
+
 #[event]
 pub struct SwapExecuted {
+    pub event_sequence: u64,
+    /// The swap's number among the pool's immediate swaps
     pub sequence: u64,
+    pub user: Pubkey,
     pub pool_id: Pubkey,
     pub amount_in: u64,
     pub is_base_input: bool,
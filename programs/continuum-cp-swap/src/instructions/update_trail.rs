@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::consts::*;
+use crate::oracle::OraclePrice;
+use crate::state::*;
+use crate::errors::*;
+
+/// Permissionless crank: move a pending order's trailing stop after the
+/// oracle price. The stop only ever tightens, so calling it often is what
+/// keeps the trail close to the best price seen.
+#[derive(Accounts)]
+pub struct UpdateTrail<'info> {
+    #[account(
+        mut,
+        seeds = [ORDER_SEED, order_state.pool_id.as_ref(), &order_state.sequence.to_le_bytes()],
+        bump,
+        constraint = order_state.status == OrderStatus::Pending @ ContinuumError::InvalidOrderStatus,
+    )]
+    pub order_state: Account<'info, OrderState>,
+
+    #[account(
+        mut,
+        seeds = [POOL_FIFO_SEED, order_state.pool_id.as_ref()],
+        bump,
+    )]
+    pub pool_fifo: Account<'info, PoolFifoState>,
+
+    /// CHECK: Price update account of the order's trailing stop, checked in `crate::oracle`
+    pub oracle: UncheckedAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn update_trail(ctx: Context<UpdateTrail>) -> Result<()> {
+    let mut stop = ctx.accounts.order_state.trailing_stop.ok_or(ContinuumError::InvalidTrailingStop)?;
+    require_keys_eq!(ctx.accounts.oracle.key(), stop.oracle, ContinuumError::InvalidOracle);
+
+    let now = crate::clock::unix_timestamp(&ctx.accounts.clock, ctx.remaining_accounts);
+    let price = OraclePrice::load(&ctx.accounts.oracle, now)?;
+    if !stop.observe(price.price) {
+        return Ok(());
+    }
+
+    let order_state = &mut ctx.accounts.order_state;
+    order_state.trailing_stop = Some(stop);
+
+    emit!(TrailUpdated {
+        event_sequence: ctx.accounts.pool_fifo.next_event_sequence(),
+        sequence: order_state.sequence,
+        pool_id: order_state.pool_id,
+        reference_price: stop.reference_price,
+        stop_price: stop.stop_price,
+    });
+
+    msg!("Order {} trailing stop moved to {}", order_state.sequence, stop.stop_price);
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use crate::compression::{self, ACCOUNT_COMPRESSION_PROGRAM_ID};
+use crate::consts::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct VerifyArchivedOrder<'info> {
+    #[account(
+        seeds = [FIFO_STATE_SEED],
+        bump,
+        constraint = fifo_state.archive_tree != Pubkey::default() @ ContinuumError::ArchiveNotInitialized,
+    )]
+    pub fifo_state: Account<'info, FifoState>,
+
+    /// CHECK: The archive tree registered in fifo_state
+    #[account(address = fifo_state.archive_tree)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: The SPL Account Compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    // Remaining accounts are the proof nodes, ordered from leaf to root
+}
+
+pub fn verify_archived_order<'info>(
+    ctx: Context<'_, '_, 'info, 'info, VerifyArchivedOrder<'info>>,
+    order: ArchivedOrder,
+    root: [u8; 32],
+    leaf_index: u32,
+) -> Result<()> {
+    let leaf = order.leaf_hash();
+
+    let ix = compression::verify_leaf_ix(
+        ctx.accounts.merkle_tree.key(),
+        root,
+        leaf,
+        leaf_index,
+        ctx.remaining_accounts,
+    );
+
+    let mut account_infos = vec![ctx.accounts.merkle_tree.to_account_info()];
+    account_infos.extend_from_slice(ctx.remaining_accounts);
+
+    // Fails the transaction if the proof does not match the tree
+    invoke(&ix, &account_infos)?;
+
+    msg!("Archived order {} verified at leaf {}", order.sequence, leaf_index);
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use crate::errors::ContinuumError;
+
+/// Prefix of every signed intent message, so an intent signature can't be
+/// mistaken for a transaction or another protocol's message
+pub const INTENT_DOMAIN: &[u8] = b"continuum-order-intent-v1";
+
+/// Length of the ed25519 program's instruction header: signature count and
+/// one padding byte
+const ED25519_HEADER_LEN: usize = 2;
+/// Length of one signature's offsets entry in an ed25519 instruction
+const ED25519_OFFSETS_LEN: usize = 14;
+
+/// An order signed off-chain by its owner, submitted on their behalf by a
+/// relayer that pays for the transaction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct OrderIntent {
+    pub user: Pubkey,
+    pub pool_id: Pubkey,
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+    pub is_base_input: bool,
+    /// Mint the order sells, one of the pool's
+    pub input_mint: Pubkey,
+    pub recipient: Option<Pubkey>,
+    /// Chosen by the user; each nonce can be submitted once
+    pub nonce: u64,
+    /// Unix timestamp after which the intent can no longer be submitted
+    pub expires_at: i64,
+}
+
+impl OrderIntent {
+    /// The bytes the user signs
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = INTENT_DOMAIN.to_vec();
+        // Serializing into a Vec can't fail
+        self.serialize(&mut message).unwrap();
+        message
+    }
+}
+
+/// Check that the instruction before the current one is an ed25519 program
+/// verification of `signer`'s signature over `message`, see
+/// [`check_ed25519_data`]
+pub fn verify_previous_ed25519(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current = load_current_index_checked(instructions_sysvar)?;
+    require!(current > 0, ContinuumError::InvalidIntentSignature);
+    let ix = load_instruction_at_checked(current as usize - 1, instructions_sysvar)?;
+    require_keys_eq!(ix.program_id, ed25519_program::ID, ContinuumError::InvalidIntentSignature);
+    check_ed25519_data(&ix.data, signer, message)
+}
+
+/// Check that `data`, an ed25519 program instruction's, verifies exactly one
+/// signature by `signer` over `message`, with all of its data inside it
+pub(crate) fn check_ed25519_data(data: &[u8], signer: &Pubkey, message: &[u8]) -> Result<()> {
+    require!(
+        data.len() >= ED25519_HEADER_LEN + ED25519_OFFSETS_LEN && data[0] == 1,
+        ContinuumError::InvalidIntentSignature
+    );
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]) as usize;
+    let offsets = ED25519_HEADER_LEN;
+    let public_key_offset = read_u16(offsets + 4);
+    let message_offset = read_u16(offsets + 8);
+    let message_size = read_u16(offsets + 10);
+    // u16::MAX means "this instruction" for the signature, public key and
+    // message locations
+    for index_at in [offsets + 2, offsets + 6, offsets + 12] {
+        require!(read_u16(index_at) == u16::MAX as usize, ContinuumError::InvalidIntentSignature);
+    }
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(ContinuumError::InvalidIntentSignature)?;
+    let signed = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ContinuumError::InvalidIntentSignature)?;
+    require!(
+        public_key == signer.as_ref() && signed == message,
+        ContinuumError::InvalidIntentSignature
+    );
+    Ok(())
+}
@@ -0,0 +1,127 @@
+//! Account layouts of the first deployment, frozen so the accounts it
+//! created can still be read after the live types grew. Each is decoded
+//! only from data of exactly its length under the live type's
+//! discriminator, and migrated by `migrate_order`, `migrate_fifo_state` or
+//! `migrate_pool_registry`.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+
+use crate::errors::ContinuumError;
+use crate::state::{CpSwapPoolRegistry, FifoState, OrderState};
+
+/// `OrderState` as first deployed, at ["order", user, sequence - 1]
+#[derive(AnchorDeserialize)]
+pub struct LegacyOrderStateV0 {
+    pub sequence: u64,
+    pub user: Pubkey,
+    pub pool_id: Pubkey,
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+    pub is_base_input: bool,
+    pub status: LegacyOrderStatusV0,
+    pub submitted_at: i64,
+    pub executed_at: Option<i64>,
+}
+
+impl LegacyOrderStateV0 {
+    pub const LEN: usize = 8 + 8 + 32 + 32 + 8 + 8 + 1 + 1 + 8 + 9;
+
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        decode(data, OrderState::DISCRIMINATOR, Self::LEN)
+    }
+}
+
+/// `OrderStatus` as first deployed
+#[derive(AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum LegacyOrderStatusV0 {
+    Pending,
+    Executed,
+    Cancelled,
+    Failed,
+}
+
+/// `FifoState` as first deployed
+#[derive(AnchorDeserialize)]
+pub struct LegacyFifoStateV0 {
+    pub current_sequence: u64,
+    pub admin: Pubkey,
+    pub emergency_pause: bool,
+}
+
+impl LegacyFifoStateV0 {
+    pub const LEN: usize = 8 + 8 + 32 + 1;
+
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        decode(data, FifoState::DISCRIMINATOR, Self::LEN)
+    }
+}
+
+/// `CpSwapPoolRegistry` as first deployed
+#[derive(AnchorDeserialize)]
+pub struct LegacyPoolRegistryV0 {
+    pub pool_id: Pubkey,
+    pub token_0: Pubkey,
+    pub token_1: Pubkey,
+    pub continuum_authority: Pubkey,
+    pub created_at: i64,
+    pub is_active: bool,
+}
+
+impl LegacyPoolRegistryV0 {
+    pub const LEN: usize = 8 + 32 * 4 + 8 + 1;
+
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        decode(data, CpSwapPoolRegistry::DISCRIMINATOR, Self::LEN)
+    }
+}
+
+/// Decode a legacy account of this program with `decode`
+pub fn load<T>(info: &AccountInfo, decode: impl FnOnce(&[u8]) -> Result<T>) -> Result<T> {
+    require_keys_eq!(*info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+    decode(&info.try_borrow_data()?)
+}
+
+fn decode<T: AnchorDeserialize>(data: &[u8], discriminator: &[u8], len: usize) -> Result<T> {
+    require!(
+        data.len() == len && data.starts_with(discriminator),
+        ContinuumError::AccountNotMigratable
+    );
+    T::deserialize(&mut &data[discriminator.len()..]).map_err(Into::into)
+}
+
+/// Grow `info` to `len` bytes, with `payer` topping its rent up, and write
+/// `account` over it
+pub fn rewrite<'info, T: AccountSerialize>(
+    info: &AccountInfo<'info>,
+    account: &T,
+    len: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let shortfall = Rent::get()?.minimum_balance(len).saturating_sub(info.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    info.resize(len)?;
+    crate::optional::store(info, account)
+}
+
+/// Close a legacy account, sending its rent to `destination`
+pub fn close<'info>(info: &AccountInfo<'info>, destination: &AccountInfo<'info>) -> Result<()> {
+    let lamports = info.lamports();
+    **destination.try_borrow_mut_lamports()? += lamports;
+    **info.try_borrow_mut_lamports()? = 0;
+    info.assign(&system_program::ID);
+    info.resize(0).map_err(Into::into)
+}
@@ -1,76 +1,43 @@
 use anchor_lang::prelude::*;
 
+// Selected by cluster feature, see `cluster`
+#[cfg(feature = "devnet")]
 declare_id!("EaeWUSam5Li1fzCcCs33oE4jCLQT4F6RJXgrPYZaoKqq");
 
+// Taken from the mainnet deployment keypair at build time, e.g.
+// CONTINUUM_PROGRAM_ID=$(solana-keygen pubkey target/deploy/continuum_cp_swap-keypair.json)
+#[cfg(feature = "mainnet")]
+declare_id!(Pubkey::from_str_const(env!(
+    "CONTINUUM_PROGRAM_ID",
+    "mainnet builds take the program ID from CONTINUUM_PROGRAM_ID"
+)));
+
+pub mod callback;
+pub mod clock;
+pub mod cluster;
+pub mod compliance;
+#[cfg(feature = "cpi")]
+pub mod compose;
+pub mod compression;
+pub mod consts;
+pub mod cp_swap;
 pub mod errors;
 pub mod instructions;
+pub mod intent;
+pub mod legacy;
+pub mod optional;
+pub mod oracle;
+pub mod order_vault;
+pub mod ordering;
+pub mod post_fill;
+pub mod sanitize;
+pub mod shared_escrow;
 pub mod state;
+pub mod wormhole;
 
 use instructions::*;
 
-#[program]
-pub mod continuum_cp_swap {
-    use super::*;
+#[allow(deprecated)]
+mod api;
 
-    /// Initialize the global FIFO state
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        instructions::initialize(ctx)
-    }
-
-    /// Initialize a CP-Swap pool with Continuum as custom authority
-    pub fn initialize_cp_swap_pool(
-        ctx: Context<InitializeCpSwapPool>,
-        init_amount_0: u64,
-        init_amount_1: u64,
-        open_time: u64,
-    ) -> Result<()> {
-        instructions::initialize_cp_swap_pool(ctx, init_amount_0, init_amount_1, open_time)
-    }
-
-    /// Submit a swap order to the FIFO queue
-    pub fn submit_order(
-        ctx: Context<SubmitOrder>,
-        amount_in: u64,
-        min_amount_out: u64,
-        is_base_input: bool,
-    ) -> Result<()> {
-        instructions::submit_order(ctx, amount_in, min_amount_out, is_base_input)
-    }
-
-    /// Execute the next order in the FIFO queue
-    pub fn execute_order(
-        ctx: Context<ExecuteOrder>,
-        expected_sequence: u64,
-    ) -> Result<()> {
-        instructions::execute_order(ctx, expected_sequence)
-    }
-
-    /// Cancel an order (only by original submitter)
-    pub fn cancel_order(
-        ctx: Context<CancelOrder>,
-    ) -> Result<()> {
-        instructions::cancel_order(ctx)
-    }
-
-    /// Simplified submit order without PDA
-    pub fn submit_order_simple(
-        ctx: Context<SubmitOrderSimple>,
-        amount_in: u64,
-        min_amount_out: u64,
-        is_base_input: bool,
-    ) -> Result<()> {
-        instructions::submit_order_simple(ctx, amount_in, min_amount_out, is_base_input)
-    }
-
-    /// Immediate swap - submit and execute in one transaction
-    pub fn swap_immediate(
-        ctx: Context<SwapImmediate>,
-        amount_in: u64,
-        min_amount_out: u64,
-        is_base_input: bool,
-        pool_id: Pubkey,
-        pool_authority_bump: u8,
-    ) -> Result<()> {
-        instructions::swap_immediate(ctx, amount_in, min_amount_out, is_base_input, pool_id, pool_authority_bump)
-    }
-}
\ No newline at end of file
+pub use api::*;
@@ -0,0 +1,50 @@
+//! Program accounts at a fixed PDA that may not have been created.
+//!
+//! Some features are opted into by creating an account, and instructions
+//! take its PDA whether or not it exists so a caller can't opt a user out by
+//! leaving the account off. These read and write such an account by hand.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, Transfer};
+
+/// The account's contents, or `None` if it was never created
+pub fn load<T: AccountDeserialize + Owner>(info: &AccountInfo) -> Result<Option<T>> {
+    if info.data_is_empty() {
+        return Ok(None);
+    }
+    require_keys_eq!(*info.owner, T::owner(), ErrorCode::AccountOwnedByWrongProgram);
+    T::try_deserialize(&mut &info.try_borrow_data()?[..]).map(Some)
+}
+
+/// Write back an account read with [`load`]
+pub fn store<T: AccountSerialize>(info: &AccountInfo, account: &T) -> Result<()> {
+    account.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])
+}
+
+/// Create the account at `info` with `space` bytes, owned by this program,
+/// as Anchor's `init` does: `payer` tops it up to rent exemption, then
+/// `seeds` sign its allocation and assignment, so lamports already sent to
+/// the address can't block its creation
+pub fn create<'info>(
+    system_program: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    info: &AccountInfo<'info>,
+    space: usize,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space).saturating_sub(info.lamports());
+    if rent > 0 {
+        system_program::transfer(
+            CpiContext::new(system_program.clone(), Transfer { from: payer.clone(), to: info.clone() }),
+            rent,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(system_program.clone(), Allocate { account_to_allocate: info.clone() }, &[seeds]),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(system_program.clone(), Assign { account_to_assign: info.clone() }, &[seeds]),
+        &crate::ID,
+    )
+}
@@ -0,0 +1,57 @@
+//! Oracle prices for trigger orders, read from Pyth price update accounts.
+
+use anchor_lang::prelude::*;
+use crate::errors::ContinuumError;
+
+/// Pyth Solana receiver program, which owns price update accounts
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// Anchor discriminator of the receiver's `PriceUpdateV2` accounts
+const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// Most seconds a price may lag the clock to be used
+pub const MAX_PRICE_AGE: i64 = 60;
+
+/// The price in a Pyth `PriceUpdateV2` account, in units of 10^`exponent`
+pub struct OraclePrice {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+impl OraclePrice {
+    /// Parse a price update account. Only fully verified updates are accepted.
+    /// The caller must check the account is owned by the receiver program.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        require!(data.starts_with(&PRICE_UPDATE_V2_DISCRIMINATOR), ContinuumError::InvalidOracle);
+        let mut reader = &data[PRICE_UPDATE_V2_DISCRIMINATOR.len()..];
+
+        let _write_authority = Pubkey::deserialize(&mut reader)?;
+        // VerificationLevel: Partial { num_signatures: u8 } = 0, Full = 1
+        let verification_level = u8::deserialize(&mut reader)?;
+        require!(verification_level == 1, ContinuumError::InvalidOracle);
+        let feed_id = <[u8; 32]>::deserialize(&mut reader)?;
+        let price = i64::deserialize(&mut reader)?;
+        let conf = u64::deserialize(&mut reader)?;
+        let exponent = i32::deserialize(&mut reader)?;
+        let publish_time = i64::deserialize(&mut reader)?;
+
+        Ok(Self {
+            feed_id,
+            price,
+            conf,
+            exponent,
+            publish_time,
+        })
+    }
+
+    /// Read `account` as a price no older than [`MAX_PRICE_AGE`] at `now`
+    pub fn load(account: &AccountInfo, now: i64) -> Result<Self> {
+        require_keys_eq!(*account.owner, PYTH_RECEIVER_PROGRAM_ID, ContinuumError::InvalidOracle);
+        let price = Self::parse(&account.try_borrow_data()?)?;
+        require!(now - price.publish_time <= MAX_PRICE_AGE, ContinuumError::StalePrice);
+        Ok(price)
+    }
+}
@@ -0,0 +1,116 @@
+//! Order vaults. An order submitted with a vault deposits its maximum input,
+//! plus the most fees its quote can charge on it, into a token account,
+//! seeds ["order_vault", order], whose token authority is the pool
+//! authority. The order then spends from the vault like an order of a
+//! template does from the template's escrow, so moving tokens out of the
+//! user's wallet can't fail its execution. Whatever the vault has left is
+//! refunded to the account the deposit came from, and the vault closed,
+//! when the order executes or is cancelled. An order re-queued by
+//! `modify_order` moves its deposit to the replacement's vault, see
+//! [`requeue`].
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, TokenAccount, Transfer};
+
+use crate::consts::ORDER_VAULT_SEED;
+use crate::errors::ContinuumError;
+use crate::state::OrderState;
+
+pub fn vault_address(order: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[ORDER_VAULT_SEED, order.as_ref()], &crate::ID).0
+}
+
+/// What the user deposits for `order`
+pub fn deposit(order: &OrderState) -> Result<u64> {
+    let max_input = order.max_input();
+    let deposit = order.fee_quote.max_fees(max_input).and_then(|fees| max_input.checked_add(fees));
+    Ok(deposit.ok_or(ContinuumError::FeeOverflow)?)
+}
+
+/// Refund what is left in `vault` to `refund` and close it, returning its
+/// rent to `user`. `pool_authority_seeds` sign as the vault's authority.
+/// Returns the amount refunded.
+pub fn settle<'info>(
+    token_program: AccountInfo<'info>,
+    vault: AccountInfo<'info>,
+    refund: AccountInfo<'info>,
+    user: AccountInfo<'info>,
+    pool_authority: AccountInfo<'info>,
+    pool_authority_seeds: &[&[u8]],
+) -> Result<u64> {
+    let leftover = TokenAccount::try_deserialize(&mut &vault.try_borrow_data()?[..])?.amount;
+    if leftover > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                Transfer {
+                    from: vault.clone(),
+                    to: refund,
+                    authority: pool_authority.clone(),
+                },
+                &[pool_authority_seeds],
+            ),
+            leftover,
+        )?;
+    }
+    token::close_account(CpiContext::new_with_signer(
+        token_program,
+        CloseAccount {
+            account: vault,
+            destination: user,
+            authority: pool_authority,
+        },
+        &[pool_authority_seeds],
+    ))?;
+    Ok(leftover)
+}
+
+/// Move the deposit of an order re-queued with new amounts from `vault` to
+/// `replacement_vault`, which must end up holding `deposit`: up to that much
+/// of the vault's balance moves across, `refund` tops up any shortfall with
+/// `user` signing, and `vault` is then settled. `pool_authority_seeds` sign
+/// as both vaults' authority. Returns the amounts topped up and refunded.
+#[allow(clippy::too_many_arguments)]
+pub fn requeue<'info>(
+    token_program: AccountInfo<'info>,
+    vault: AccountInfo<'info>,
+    replacement_vault: AccountInfo<'info>,
+    refund: AccountInfo<'info>,
+    user: AccountInfo<'info>,
+    pool_authority: AccountInfo<'info>,
+    pool_authority_seeds: &[&[u8]],
+    deposit: u64,
+) -> Result<(u64, u64)> {
+    let held = TokenAccount::try_deserialize(&mut &vault.try_borrow_data()?[..])?.amount;
+    let moved = held.min(deposit);
+    if moved > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                Transfer {
+                    from: vault.clone(),
+                    to: replacement_vault.clone(),
+                    authority: pool_authority.clone(),
+                },
+                &[pool_authority_seeds],
+            ),
+            moved,
+        )?;
+    }
+    let top_up = deposit - moved;
+    if top_up > 0 {
+        token::transfer(
+            CpiContext::new(
+                token_program.clone(),
+                Transfer {
+                    from: refund.clone(),
+                    to: replacement_vault,
+                    authority: user.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    let refunded = settle(token_program, vault, refund, user, pool_authority, pool_authority_seeds)?;
+    Ok((top_up, refunded))
+}
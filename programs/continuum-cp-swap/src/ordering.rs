@@ -0,0 +1,48 @@
+//! Optional per-user ordering.
+//!
+//! A user who creates their [`UserOrdering`] account has every order they
+//! submit afterwards appended to it, and such an order can only be executed
+//! while it is the oldest one still pending. Cancelling takes an order out of
+//! the list wherever it is, so a cancelled order never blocks later ones.
+//!
+//! Instructions take the user's ordering PDA whether or not it exists, so a
+//! relayer can't bypass the ordering by leaving the account out.
+
+use anchor_lang::prelude::*;
+
+use crate::errors::ContinuumError;
+use crate::optional::{load, store};
+use crate::state::UserOrdering;
+
+/// Record a newly submitted order, if the user is in ordered mode
+pub fn push(info: &AccountInfo, order: Pubkey) -> Result<()> {
+    let Some(mut ordering) = load::<UserOrdering>(info)? else {
+        return Ok(());
+    };
+    require!(ordering.pending.len() < UserOrdering::MAX_PENDING, ContinuumError::UserOrderingFull);
+    ordering.pending.push(order);
+    store(info, &ordering)
+}
+
+/// Whether an order can settle now: either it isn't tracked, or every
+/// earlier tracked order of the user has settled
+pub fn is_next(info: &AccountInfo, order: &Pubkey) -> Result<bool> {
+    Ok(match load::<UserOrdering>(info)? {
+        Some(ordering) => {
+            !ordering.pending.contains(order) || ordering.pending.first() == Some(order)
+        }
+        None => true,
+    })
+}
+
+/// Drop a settled order from the user's pending list
+pub fn remove(info: &AccountInfo, order: &Pubkey) -> Result<()> {
+    let Some(mut ordering) = load::<UserOrdering>(info)? else {
+        return Ok(());
+    };
+    if let Some(index) = ordering.pending.iter().position(|pending| pending == order) {
+        ordering.pending.remove(index);
+        store(info, &ordering)?;
+    }
+    Ok(())
+}
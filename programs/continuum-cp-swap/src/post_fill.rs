@@ -0,0 +1,124 @@
+//! Post-fill actions. An order with a [`PostFillAction`] has its output
+//! delivered to the post-fill vault for the output mint, seeds
+//! ["post_fill_vault", mint], and in the same execution either burned or
+//! deposited into a staking program the admin approved. The vaults are
+//! owned by the post-fill authority PDA, which only ever holds the output of
+//! the fill in progress, so unlike the pool authority it is safe to sign
+//! with towards a staking program.
+//!
+//! A deposit invokes the staking program with [`STAKE_DEPOSIT_DISCRIMINATOR`],
+//! the amount (u64) and the beneficiary (the order's output owner) as data,
+//! and accounts: the vault (writable), the post-fill authority (signer), the
+//! mint, the program's deposit account for the mint (writable), at its PDA
+//! ["stake_deposit", mint], and the token program.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use anchor_spl::token::{self, Burn};
+
+use crate::consts::{POST_FILL_AUTHORITY_SEED, POST_FILL_VAULT_SEED, STAKE_DEPOSIT_DISCRIMINATOR, STAKE_DEPOSIT_SEED};
+use crate::errors::ContinuumError;
+use crate::state::{ApprovedStakeProgram, PostFillAction};
+
+pub fn vault_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[POST_FILL_VAULT_SEED, mint.as_ref()], &crate::ID).0
+}
+
+pub fn authority_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POST_FILL_AUTHORITY_SEED], &crate::ID)
+}
+
+/// Deposit account of staking `program` for `mint`
+pub fn target_address(program: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[STAKE_DEPOSIT_SEED, mint.as_ref()], program).0
+}
+
+/// The accounts an execution passes for a post-fill action; only the vault
+/// and token program are always present
+pub struct PostFillAccounts<'a, 'info> {
+    pub vault: AccountInfo<'info>,
+    pub mint: Pubkey,
+    pub authority: Option<&'a AccountInfo<'info>>,
+    pub mint_account: Option<&'a AccountInfo<'info>>,
+    pub program: Option<&'a AccountInfo<'info>>,
+    pub target: Option<&'a AccountInfo<'info>>,
+    pub approval: Option<&'a ApprovedStakeProgram>,
+    pub token_program: AccountInfo<'info>,
+}
+
+impl<'info> PostFillAccounts<'_, 'info> {
+    /// Whether the accounts needed by `action` are present and the right ones
+    pub fn validate(&self, action: &PostFillAction) -> bool {
+        let common = self.vault.key() == vault_address(&self.mint)
+            && self.authority.is_some_and(|authority| authority.key() == authority_address().0)
+            && self.mint_account.is_some_and(|mint| mint.key() == self.mint);
+        common
+            && match action {
+                PostFillAction::Burn => true,
+                PostFillAction::Stake { program } => {
+                    self.program.is_some_and(|account| account.key() == *program)
+                        && self.target.is_some_and(|target| target.key() == target_address(program, &self.mint))
+                        && self.approval.is_some_and(|approval| approval.program == *program)
+                }
+            }
+    }
+
+    /// Run `action` on `amount` of the vault, after [`Self::validate`]
+    pub fn run(&self, action: &PostFillAction, amount: u64, beneficiary: Pubkey) -> Result<()> {
+        let (Some(authority), Some(mint_account)) = (self.authority, self.mint_account) else {
+            return err!(ContinuumError::InvalidPostFill);
+        };
+        let bump = authority_address().1;
+        let seeds: &[&[u8]] = &[POST_FILL_AUTHORITY_SEED, &[bump]];
+
+        match action {
+            PostFillAction::Burn => token::burn(
+                CpiContext::new_with_signer(
+                    self.token_program.clone(),
+                    Burn {
+                        mint: mint_account.clone(),
+                        from: self.vault.clone(),
+                        authority: authority.clone(),
+                    },
+                    &[seeds],
+                ),
+                amount,
+            ),
+            PostFillAction::Stake { program } => {
+                let (Some(program_account), Some(target)) = (self.program, self.target) else {
+                    return err!(ContinuumError::InvalidPostFill);
+                };
+                let mut data = STAKE_DEPOSIT_DISCRIMINATOR.to_vec();
+                data.extend_from_slice(&amount.to_le_bytes());
+                data.extend_from_slice(beneficiary.as_ref());
+                let ix = Instruction {
+                    program_id: *program,
+                    accounts: vec![
+                        AccountMeta::new(self.vault.key(), false),
+                        AccountMeta::new_readonly(authority.key(), true),
+                        AccountMeta::new_readonly(mint_account.key(), false),
+                        AccountMeta::new(target.key(), false),
+                        AccountMeta::new_readonly(self.token_program.key(), false),
+                    ],
+                    data,
+                };
+                invoke_signed(
+                    &ix,
+                    &[
+                        self.vault.clone(),
+                        authority.clone(),
+                        mint_account.clone(),
+                        target.clone(),
+                        self.token_program.clone(),
+                        program_account.clone(),
+                    ],
+                    &[seeds],
+                )?;
+                Ok(())
+            }
+        }
+    }
+}
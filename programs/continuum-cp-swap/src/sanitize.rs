@@ -0,0 +1,18 @@
+//! Checks on the remaining accounts forwarded to CP-Swap.
+//!
+//! Swaps are invoked under the pool authority's signature with whatever
+//! accounts the caller appends, so a malicious relayer could slip in one of
+//! this program's own state accounts and have it handed to the callee as
+//! writable. CP-Swap never needs to write to a Continuum account; read-only
+//! ones, such as the mock clock in `test-clock` builds, are let through.
+
+use anchor_lang::prelude::*;
+
+/// The first remaining account that is writable and owned by this program
+pub fn writable_program_account<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Option<&'a AccountInfo<'info>> {
+    remaining_accounts
+        .iter()
+        .find(|account| account.is_writable && account.owner == &crate::ID)
+}
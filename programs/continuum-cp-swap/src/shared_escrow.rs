@@ -0,0 +1,112 @@
+//! Shared escrows. A [`SharedEscrow`] holds a user's tokens of one mint in a
+//! vault, seeds ["shared_escrow_vault", escrow], whose token authority is the
+//! escrow account itself, so that orders on any pool with the mint can spend
+//! from it. The pool authority that signs a swap is only approved on the
+//! vault for the duration of an execution, and for no more than the order's
+//! reservation plus the unreserved balance, so one order can't spend what
+//! another has reserved.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Approve, CloseAccount, Revoke, Transfer};
+
+use crate::consts::{SHARED_ESCROW_SEED, SHARED_ESCROW_VAULT_SEED};
+use crate::state::SharedEscrow;
+
+pub fn vault_address(escrow: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[SHARED_ESCROW_VAULT_SEED, escrow.as_ref()], &crate::ID).0
+}
+
+/// Sign as `escrow` for `f`
+fn with_signer<T>(escrow: &SharedEscrow, f: impl FnOnce(&[&[&[u8]]]) -> T) -> T {
+    let (_, bump) = Pubkey::find_program_address(
+        &[SHARED_ESCROW_SEED, escrow.user.as_ref(), escrow.mint.as_ref()],
+        &crate::ID,
+    );
+    f(&[&[SHARED_ESCROW_SEED, escrow.user.as_ref(), escrow.mint.as_ref(), &[bump]]])
+}
+
+/// Let `delegate` spend up to `amount` from the vault
+pub fn approve<'info>(
+    token_program: AccountInfo<'info>,
+    escrow: &Account<'info, SharedEscrow>,
+    vault: AccountInfo<'info>,
+    delegate: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    with_signer(escrow, |seeds| {
+        token::approve(
+            CpiContext::new_with_signer(
+                token_program,
+                Approve {
+                    to: vault,
+                    delegate,
+                    authority: escrow.to_account_info(),
+                },
+                seeds,
+            ),
+            amount,
+        )
+    })
+}
+
+/// Withdraw any delegation left on the vault
+pub fn revoke<'info>(
+    token_program: AccountInfo<'info>,
+    escrow: &Account<'info, SharedEscrow>,
+    vault: AccountInfo<'info>,
+) -> Result<()> {
+    with_signer(escrow, |seeds| {
+        token::revoke(CpiContext::new_with_signer(
+            token_program,
+            Revoke {
+                source: vault,
+                authority: escrow.to_account_info(),
+            },
+            seeds,
+        ))
+    })
+}
+
+/// Move `amount` out of the vault to `to`
+pub fn transfer<'info>(
+    token_program: AccountInfo<'info>,
+    escrow: &Account<'info, SharedEscrow>,
+    vault: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    with_signer(escrow, |seeds| {
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program,
+                Transfer {
+                    from: vault,
+                    to,
+                    authority: escrow.to_account_info(),
+                },
+                seeds,
+            ),
+            amount,
+        )
+    })
+}
+
+/// Close the empty vault, returning its rent to `destination`
+pub fn close_vault<'info>(
+    token_program: AccountInfo<'info>,
+    escrow: &Account<'info, SharedEscrow>,
+    vault: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+) -> Result<()> {
+    with_signer(escrow, |seeds| {
+        token::close_account(CpiContext::new_with_signer(
+            token_program,
+            CloseAccount {
+                account: vault,
+                destination,
+                authority: escrow.to_account_info(),
+            },
+            seeds,
+        ))
+    })
+}
@@ -1,16 +1,104 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use crate::consts::{HEAD_GRACE_PERIOD, MAX_ORDER_MEMO_LEN, ORDER_SEED, RECEIPT_SEED};
+use crate::errors::ContinuumError;
 
 #[account]
 pub struct FifoState {
+    /// Sequence of the latest `submit_order_simple` submission, which
+    /// creates no order; queued orders are sequenced by their pool, see
+    /// [`PoolFifoState`]
     pub current_sequence: u64,
     pub admin: Pubkey,
     pub emergency_pause: bool,
+    /// Concurrent Merkle tree holding archived orders (default until initialized)
+    pub archive_tree: Pubkey,
+    /// Number of orders appended to the archive tree, i.e. the next leaf index
+    pub archived_orders: u64,
+    /// Number of orders on a pool between its checkpoints (0 disables
+    /// checkpointing)
+    pub checkpoint_interval: u64,
+    /// Protocol fee charged on the input amount of executed orders
+    pub protocol_fee_bps: u16,
+    /// Relayer fee charged on the input amount of executed orders
+    pub relayer_fee_bps: u16,
+    /// Second key that must co-sign pool registrations (default when not set)
+    pub risk_authority: Pubkey,
+    /// Most `swap_immediate` calls a registered relayer may make per
+    /// `swap_rate_window` (0 disables the limit)
+    pub max_swaps_per_window: u32,
+    /// Length of the sliding window `max_swaps_per_window` counts over, in seconds
+    pub swap_rate_window: u32,
+    /// Share of each protocol fee set aside for the insurance fund, in bps of the fee
+    pub insurance_share_bps: u16,
+    /// The only CP-Swap program the pool authority signs CPIs into
+    pub cp_swap_program: Pubkey,
+    /// Whether any signer may execute queued orders, rather than only
+    /// registered relayers
+    pub permissionless_execution: bool,
 }
 
 impl FifoState {
-    pub const LEN: usize = 8 + 8 + 32 + 1;
+    pub const LEN: usize = 8 + 8 + 32 + 1 + 32 + 8 + 8 + 2 + 2 + 32 + 4 + 4 + 2 + 32 + 1;
+    pub const DEFAULT_CHECKPOINT_INTERVAL: u64 = 100;
+    pub const MAX_TOTAL_FEE_BPS: u16 = 1_000;
+
+    /// Whether `signer` satisfies the risk co-signature a pool registration
+    /// needs; always true while no risk authority is set
+    pub fn risk_approved(&self, signer: Option<&Pubkey>) -> bool {
+        self.risk_authority == Pubkey::default()
+            || signer.is_some_and(|signer| *signer == self.risk_authority && *signer != self.admin)
+    }
+
+    /// Split the fees owed on `amount` into (protocol_fee, relayer_fee)
+    pub fn compute_fees(&self, amount: u64) -> (u64, u64) {
+        self.compute_fees_with(amount, self.protocol_fee_bps, self.relayer_fee_bps)
+    }
+
+    /// As [`Self::compute_fees`], charging the given rates instead of the
+    /// global ones
+    pub fn compute_fees_with(&self, amount: u64, protocol_fee_bps: u16, relayer_fee_bps: u16) -> (u64, u64) {
+        let protocol_fee = amount as u128 * protocol_fee_bps as u128 / 10_000;
+        let relayer_fee = amount as u128 * relayer_fee_bps as u128 / 10_000;
+        (protocol_fee as u64, relayer_fee as u64)
+    }
+}
+
+/// Hash of the sequenced fields of an order, as published in its submission event
+pub fn order_hash(
+    sequence: u64,
+    user: &Pubkey,
+    pool_id: &Pubkey,
+    amount_in: u64,
+    is_base_input: bool,
+) -> [u8; 32] {
+    keccak::hashv(&[
+        &sequence.to_le_bytes(),
+        user.as_ref(),
+        pool_id.as_ref(),
+        &amount_in.to_le_bytes(),
+        &[is_base_input as u8],
+    ])
+    .to_bytes()
+}
+
+/// Identifier following an order through its events and off-chain systems
+pub type TraceId = [u8; 16];
+
+/// Trace ID of the order at `sequence` on `pool_id` when its submitter
+/// didn't choose one: the first 16 bytes of keccak("trace", pool_id, sequence)
+pub fn default_trace_id(pool_id: &Pubkey, sequence: u64) -> TraceId {
+    let hash = keccak::hashv(&[b"trace", pool_id.as_ref(), &sequence.to_le_bytes()]).to_bytes();
+    let mut trace_id = [0; 16];
+    trace_id.copy_from_slice(&hash[..16]);
+    trace_id
 }
 
+/// Identifier the submitter gives an order to match it with their own
+/// records; unlike the trace ID it is only carried by the order and its
+/// `OrderSubmitted` event
+pub type ClientOrderId = [u8; 32];
+
 #[account]
 pub struct CpSwapPoolRegistry {
     pub pool_id: Pubkey,
@@ -19,14 +107,353 @@ pub struct CpSwapPoolRegistry {
     pub continuum_authority: Pubkey,
     pub created_at: i64,
     pub is_active: bool,
+    /// Traded volume of token_0 (both directions)
+    pub volume_0: VolumeStats,
+    /// Traded volume of token_1 (both directions)
+    pub volume_1: VolumeStats,
+    /// Start of the current 24h volume window
+    pub volume_window_start: i64,
+    /// Highest fee a relayer may post for this pool; 0 charges the global
+    /// relayer fee regardless of what relayers post
+    pub max_relayer_fee_bps: u16,
+    /// Program vetting submissions on this pool, see `crate::compliance`
+    /// (default when there is none)
+    pub compliance_program: Pubkey,
+    /// Price of token_0 in token_1, observed from the vaults after each
+    /// execution
+    pub price_0: PriceAccumulator,
+    /// Address lookup table holding the pool's static execution accounts
+    /// (default when there is none)
+    pub lookup_table: Pubkey,
+    /// Submissions and immediate swaps on the pool are halted, like all of
+    /// them are under `FifoState::emergency_pause`
+    pub is_paused: bool,
+    /// CP-Swap accounts of the pool, recorded at registration so the
+    /// accounts swaps are handed can be checked, see `crate::cp_swap::SwapPool::matches`
+    pub amm_config: Pubkey,
+    pub token_0_vault: Pubkey,
+    pub token_1_vault: Pubkey,
+    pub observation_state: Pubkey,
 }
 
 impl CpSwapPoolRegistry {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 8 + 1;
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 8 + 1 + VolumeStats::LEN * 2 + 8 + 2 + 32 + PriceAccumulator::LEN + 32 + 1 + 32 * 4;
+    pub const VOLUME_WINDOW: i64 = 24 * 60 * 60;
+
+    /// Relayer fee for an execution on this pool by a relayer that posted
+    /// `posted_fee_bps`, or by an unregistered one when `None`
+    pub fn relayer_fee_bps(&self, default_fee_bps: u16, posted_fee_bps: Option<u16>) -> u16 {
+        if self.max_relayer_fee_bps == 0 {
+            return default_fee_bps;
+        }
+        posted_fee_bps
+            .unwrap_or(default_fee_bps)
+            .min(self.max_relayer_fee_bps)
+    }
+
+    pub fn compliance_program(&self) -> Option<Pubkey> {
+        (self.compliance_program != Pubkey::default()).then_some(self.compliance_program)
+    }
+
+    pub fn lookup_table(&self) -> Option<Pubkey> {
+        (self.lookup_table != Pubkey::default()).then_some(self.lookup_table)
+    }
+
+    /// The pool's vault of `mint`, if it is one of the pool's tokens
+    pub fn vault(&self, mint: &Pubkey) -> Option<Pubkey> {
+        if *mint == self.token_0 {
+            Some(self.token_0_vault)
+        } else if *mint == self.token_1 {
+            Some(self.token_1_vault)
+        } else {
+            None
+        }
+    }
+
+    /// Record an executed swap. The input mint decides which side of the pool
+    /// each amount belongs to.
+    pub fn record_volume(&mut self, input_mint: &Pubkey, amount_in: u64, amount_out: u64, now: i64) {
+        self.roll_volume_window(now);
+
+        let (amount_0, amount_1) = if *input_mint == self.token_0 {
+            (amount_in, amount_out)
+        } else {
+            (amount_out, amount_in)
+        };
+        self.volume_0.add(amount_0);
+        self.volume_1.add(amount_1);
+    }
+
+    /// Approximate volume over the trailing 24h for (token_0, token_1)
+    pub fn rolling_volume(&self, now: i64) -> (u64, u64) {
+        // A clock behind the window start reads as its start
+        let elapsed = now.saturating_sub(self.volume_window_start).max(0);
+        if elapsed >= Self::VOLUME_WINDOW * 2 {
+            return (0, 0);
+        }
+        let into_window = elapsed % Self::VOLUME_WINDOW;
+        let rolled = elapsed >= Self::VOLUME_WINDOW;
+        (
+            self.volume_0.rolling(into_window, Self::VOLUME_WINDOW, rolled),
+            self.volume_1.rolling(into_window, Self::VOLUME_WINDOW, rolled),
+        )
+    }
+
+    fn roll_volume_window(&mut self, now: i64) {
+        let elapsed = now.saturating_sub(self.volume_window_start).max(0);
+        if elapsed < Self::VOLUME_WINDOW {
+            return;
+        }
+        let windows = elapsed / Self::VOLUME_WINDOW;
+        self.volume_0.roll(windows);
+        self.volume_1.roll(windows);
+        self.volume_window_start += windows * Self::VOLUME_WINDOW;
+    }
+}
+
+/// Fees collected on a pool, held in its two fee vaults.
+/// Seeds: ["fee_ledger", pool_id]
+#[account]
+pub struct PoolFeeLedger {
+    pub pool_id: Pubkey,
+    pub token_0: Pubkey,
+    pub token_1: Pubkey,
+    /// Protocol fees less the insurance share
+    pub protocol_0: FeeCounter,
+    pub protocol_1: FeeCounter,
+    pub relayer_0: FeeCounter,
+    pub relayer_1: FeeCounter,
+    /// The pool's part of the insurance fund: the insurance share of its
+    /// protocol fees, kept in the fee vaults until paid out to harmed users
+    pub insurance_0: FeeCounter,
+    pub insurance_1: FeeCounter,
+}
+
+impl PoolFeeLedger {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + FeeCounter::LEN * 6;
+
+    /// Record fees paid into the fee vault of one side, setting
+    /// `insurance_share_bps` of the protocol fee aside for insurance
+    pub fn accrue(&mut self, is_token_0: bool, protocol_fee: u64, relayer_fee: u64, insurance_share_bps: u16) -> Result<()> {
+        let insurance_fee = (protocol_fee as u128 * insurance_share_bps as u128 / 10_000) as u64;
+        let (protocol, relayer, insurance) = if is_token_0 {
+            (&mut self.protocol_0, &mut self.relayer_0, &mut self.insurance_0)
+        } else {
+            (&mut self.protocol_1, &mut self.relayer_1, &mut self.insurance_1)
+        };
+        let protocol_fee = protocol_fee.checked_sub(insurance_fee).ok_or(ContinuumError::FeeOverflow)?;
+        protocol.accrued = protocol.accrued.checked_add(protocol_fee).ok_or(ContinuumError::FeeOverflow)?;
+        relayer.accrued = relayer.accrued.checked_add(relayer_fee).ok_or(ContinuumError::FeeOverflow)?;
+        insurance.accrued = insurance.accrued.checked_add(insurance_fee).ok_or(ContinuumError::FeeOverflow)?;
+        Ok(())
+    }
+
+    /// Insurance counter of the side holding `mint`, if it is one of the pool's
+    pub fn insurance_mut(&mut self, mint: &Pubkey) -> Option<&mut FeeCounter> {
+        if *mint == self.token_0 {
+            Some(&mut self.insurance_0)
+        } else if *mint == self.token_1 {
+            Some(&mut self.insurance_1)
+        } else {
+            None
+        }
+    }
+}
+
+/// Accrued and paid-out totals for one fee stream
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct FeeCounter {
+    pub accrued: u64,
+    pub paid_out: u64,
+}
+
+impl FeeCounter {
+    pub const LEN: usize = 8 + 8;
+
+    pub fn outstanding(&self) -> u64 {
+        self.accrued.saturating_sub(self.paid_out)
+    }
+
+    /// Record `amount` paid out of the counter
+    pub fn pay_out(&mut self, amount: u64) -> Result<()> {
+        self.paid_out = self.paid_out.checked_add(amount).ok_or(ContinuumError::FeeOverflow)?;
+        Ok(())
+    }
+}
+
+/// Relayer fees earned on a pool that have not been claimed yet.
+/// Seeds: ["relayer_fees", pool_id, relayer]
+#[account]
+pub struct RelayerFeeBalance {
+    pub relayer: Pubkey,
+    pub pool_id: Pubkey,
+    pub claimable_0: u64,
+    pub claimable_1: u64,
+    /// Lifetime fees claimed, so earnings are claimed plus claimable
+    pub claimed_0: u64,
+    pub claimed_1: u64,
+}
+
+impl RelayerFeeBalance {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8;
+}
+
+/// Lifetime and windowed volume counters for one token
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct VolumeStats {
+    pub lifetime: u128,
+    pub current_window: u64,
+    pub previous_window: u64,
+}
+
+impl VolumeStats {
+    pub const LEN: usize = 16 + 8 + 8;
+
+    pub fn add(&mut self, amount: u64) {
+        self.lifetime = self.lifetime.saturating_add(amount as u128);
+        self.current_window = self.current_window.saturating_add(amount);
+    }
+
+    /// Advance by `windows` full windows
+    pub fn roll(&mut self, windows: i64) {
+        self.previous_window = if windows == 1 { self.current_window } else { 0 };
+        self.current_window = 0;
+    }
+
+    /// Current window plus the part of the previous window still inside the
+    /// trailing period. `rolled` means the stored current window has ended and
+    /// should itself be treated as the previous one.
+    pub fn rolling(&self, into_window: i64, window: i64, rolled: bool) -> u64 {
+        let (current, previous) = if rolled {
+            (0, self.current_window)
+        } else {
+            (self.current_window, self.previous_window)
+        };
+        let remaining = (window - into_window) as u128;
+        let carried = previous as u128 * remaining / window as u128;
+        current.saturating_add(carried as u64)
+    }
+}
+
+/// Cumulative price of a pool, for time-weighted averages over sequenced
+/// trades. Each observation first credits the price left by the previous
+/// execution for the time it held, so a price moved within one execution
+/// only counts from then on. Read the cumulative at two times and divide the
+/// difference by the seconds between them, see [`Self::twap`].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct PriceAccumulator {
+    /// Sum of the price, scaled by [`crate::consts::PRICE_SCALE`], times the
+    /// seconds it held, as of `last_observed_at`. Wraps on overflow.
+    pub cumulative: u128,
+    /// Vault ratio after the last execution, scaled by [`crate::consts::PRICE_SCALE`]
+    pub last_price: u64,
+    /// 0 until the first execution
+    pub last_observed_at: i64,
+}
+
+impl PriceAccumulator {
+    pub const LEN: usize = 16 + 8 + 8;
+
+    /// Record vault balances after an execution. Does nothing while the base
+    /// side is empty.
+    pub fn observe(&mut self, base_reserve: u64, quote_reserve: u64, now: i64) {
+        if base_reserve == 0 {
+            return;
+        }
+        self.cumulative = self.cumulative_at(now);
+        let price = quote_reserve as u128 * crate::consts::PRICE_SCALE as u128 / base_reserve as u128;
+        self.last_price = price.min(u64::MAX as u128) as u64;
+        self.last_observed_at = now;
+    }
+
+    /// The cumulative extended to `now` at the last observed price
+    pub fn cumulative_at(&self, now: i64) -> u128 {
+        if self.last_observed_at == 0 {
+            return self.cumulative;
+        }
+        let elapsed = now.saturating_sub(self.last_observed_at).max(0) as u128;
+        self.cumulative.wrapping_add(self.last_price as u128 * elapsed)
+    }
+
+    /// Average price, scaled by [`crate::consts::PRICE_SCALE`], between two
+    /// readings of [`Self::cumulative_at`]; `None` unless `end` is after `start`
+    pub fn twap(start_cumulative: u128, start: i64, end_cumulative: u128, end: i64) -> Option<u64> {
+        let elapsed = end.checked_sub(start).filter(|elapsed| *elapsed > 0)?;
+        let average = end_cumulative.wrapping_sub(start_cumulative) / elapsed as u128;
+        Some(average.min(u64::MAX as u128) as u64)
+    }
+}
+
+/// One step of the volume-tiered protocol fee
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeTier {
+    /// Trailing 30-day volume, in the pool's token_0, from which this tier applies
+    pub min_volume: u64,
+    pub protocol_fee_bps: u16,
+}
+
+/// Protocol fee tiers by user volume, replacing the flat protocol fee for
+/// users who qualify. Seeds: ["fee_schedule"]
+#[account]
+pub struct FeeSchedule {
+    /// Ordered by ascending `min_volume`
+    pub tiers: Vec<FeeTier>,
+}
+
+impl FeeSchedule {
+    pub const MAX_TIERS: usize = 8;
+    pub const LEN: usize = 8 + 4 + (8 + 2) * Self::MAX_TIERS;
+
+    /// Protocol fee of the highest tier `volume` reaches, if any
+    pub fn protocol_fee_bps(&self, volume: u64) -> Option<u16> {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| volume >= tier.min_volume)
+            .map(|tier| tier.protocol_fee_bps)
+    }
+}
+
+/// A user's executed volume on one pool, counted in the pool's token_0 like
+/// the registry's `volume_0`. Seeds: ["user_volume", pool_id, user]
+#[account]
+pub struct UserVolume {
+    pub user: Pubkey,
+    pub pool_id: Pubkey,
+    pub volume: VolumeStats,
+    /// Start of the current 30-day window
+    pub window_start: i64,
+}
+
+impl UserVolume {
+    pub const LEN: usize = 8 + 32 + 32 + VolumeStats::LEN + 8;
+    pub const WINDOW: i64 = 30 * 24 * 60 * 60;
+
+    pub fn record(&mut self, amount: u64, now: i64) {
+        // A clock behind the window start reads as its start
+        let elapsed = now.saturating_sub(self.window_start).max(0);
+        if elapsed >= Self::WINDOW {
+            let windows = elapsed / Self::WINDOW;
+            self.volume.roll(windows);
+            self.window_start += windows * Self::WINDOW;
+        }
+        self.volume.add(amount);
+    }
+
+    /// Approximate volume over the trailing 30 days
+    pub fn rolling_volume(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.window_start).max(0);
+        if elapsed >= Self::WINDOW * 2 {
+            return 0;
+        }
+        self.volume
+            .rolling(elapsed % Self::WINDOW, Self::WINDOW, elapsed >= Self::WINDOW)
+    }
 }
 
 #[account]
 pub struct OrderState {
+    /// Position in the pool's queue, see [`PoolFifoState`]
     pub sequence: u64,
     pub user: Pubkey,
     pub pool_id: Pubkey,
@@ -36,45 +463,2612 @@ pub struct OrderState {
     pub status: OrderStatus,
     pub submitted_at: i64,
     pub executed_at: Option<i64>,
+    /// Owner of the token account receiving the output, if not the user
+    pub recipient: Option<Pubkey>,
+    /// Program invoked after the order fills, see `crate::callback`
+    pub callback_program: Option<Pubkey>,
+    /// Exact token account the output is paid into, whoever owns it
+    pub destination_account: Option<Pubkey>,
+    /// What happens to the output once filled, see `crate::post_fill`
+    pub post_fill: Option<PostFillAction>,
+    /// Unix timestamp before which the order can't be executed
+    pub valid_from: Option<i64>,
+    /// Token account the input is spent from instead of one of the user's,
+    /// e.g. the escrow of the template the order came from
+    pub source_account: Option<Pubkey>,
+    /// Order that must have executed before this one can, e.g. the entry
+    /// leg of an exit order
+    pub parent_order: Option<Pubkey>,
+    /// Oracle stop the order waits on, ratcheted by `update_trail`
+    pub trailing_stop: Option<TrailingStop>,
+    /// Shared escrow holding the order's reservation; `source_account` is
+    /// then its vault
+    pub shared_escrow: Option<Pubkey>,
+    /// The user approved the order's escrow PDA, ["escrow", order], as a
+    /// delegate on their source account rather than the pool authority
+    pub delegated_input: bool,
+    /// Permit the order spends through; `source_account` is then its token
+    /// account
+    pub permit: Option<Pubkey>,
+    /// Second signer the order needs, e.g. a risk officer alongside the trader
+    pub co_signer: Option<CoSigner>,
+    /// Fee rates in force at submission, which the order is never charged
+    /// more than
+    pub fee_quote: FeeQuote,
+    /// Carried by every event about the order, see [`default_trace_id`]
+    pub trace_id: TraceId,
+    /// The user's token account an escrowed order's deposit came from, see
+    /// `crate::order_vault`; `source_account` is then the order's vault
+    pub escrow_refund: Option<Pubkey>,
+    /// Unix timestamp after which the order can't be executed and anyone
+    /// may settle it with `expire_order`
+    pub expires_at: Option<i64>,
+    /// Worst spot price the pool may be at for the order to execute
+    pub limit_price: Option<LimitPrice>,
+    /// Slices of a TWAP order, which stays pending until the last executes
+    pub twap: Option<TwapSchedule>,
+    /// Input of an exact input order not yet filled; partial fills keep
+    /// the order pending until it reaches zero
+    pub amount_in_remaining: u64,
+    /// Output delivered by the order's fills so far
+    pub amount_out_filled: u64,
+    pub execution_policy: ExecutionPolicy,
+    /// Oracle price a stop-loss or take-profit order waits on
+    pub trigger: Option<TriggerCondition>,
+    pub client_order_id: Option<ClientOrderId>,
+    /// Mint the order sells, one of its pool's, fixed at submission so an
+    /// executor can't swap the order in the other direction
+    pub input_mint: Pubkey,
+    /// Free text from the submitter, at most [`MAX_ORDER_MEMO_LEN`] bytes
+    pub memo: Option<String>,
+}
+
+/// What every order is submitted with, see [`PoolFifoState::enqueue`]
+pub struct NewOrder {
+    pub user: Pubkey,
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+    pub is_base_input: bool,
+    pub input_mint: Pubkey,
 }
 
 impl OrderState {
-    pub const LEN: usize = 8 + 8 + 32 + 32 + 8 + 8 + 1 + 1 + 8 + 9;
+    pub const LEN: usize = 8 + 8 + 32 + 32 + 8 + 8 + 1 + 1 + 8 + 9 + 33 + 33 + 33 + 1 + PostFillAction::LEN + 9 + 33 + 33 + 1 + TrailingStop::LEN + 33 + 1 + 33 + 1 + CoSigner::LEN + FeeQuote::LEN + 16 + 33 + 9 + 1 + LimitPrice::LEN + 1 + TwapSchedule::LEN + 8 + 8 + 1 + 1 + TriggerCondition::LEN + 33 + 32 + 1 + 4 + MAX_ORDER_MEMO_LEN;
+
+    /// A pending, good-till-cancelled order at `sequence` on `pool_id`,
+    /// submitted at `now`, delivering to its user and spending from its
+    /// wallet; its submitter sets anything else it was submitted with
+    pub fn new(pool_id: Pubkey, sequence: u64, order: NewOrder, fee_quote: FeeQuote, now: i64) -> Self {
+        Self {
+            sequence,
+            user: order.user,
+            pool_id,
+            amount_in: order.amount_in,
+            min_amount_out: order.min_amount_out,
+            is_base_input: order.is_base_input,
+            status: OrderStatus::Pending,
+            submitted_at: now,
+            executed_at: None,
+            recipient: None,
+            callback_program: None,
+            destination_account: None,
+            post_fill: None,
+            valid_from: None,
+            source_account: None,
+            parent_order: None,
+            trailing_stop: None,
+            shared_escrow: None,
+            delegated_input: false,
+            permit: None,
+            co_signer: None,
+            fee_quote,
+            trace_id: default_trace_id(&pool_id, sequence),
+            escrow_refund: None,
+            expires_at: None,
+            limit_price: None,
+            twap: None,
+            amount_in_remaining: order.amount_in,
+            amount_out_filled: 0,
+            execution_policy: ExecutionPolicy::GoodTillCancelled,
+            trigger: None,
+            client_order_id: None,
+            input_mint: order.input_mint,
+            memo: None,
+        }
+    }
+
+    /// Account of the order at `sequence` on `pool_id`.
+    /// Seeds: ["order", pool_id, sequence]
+    pub fn address(pool_id: &Pubkey, sequence: u64) -> Pubkey {
+        Pubkey::find_program_address(&[ORDER_SEED, pool_id.as_ref(), &sequence.to_le_bytes()], &crate::ID).0
+    }
+
+    /// Most input the order can spend: the amount in, or for exact output
+    /// orders the maximum in
+    pub fn max_input(&self) -> u64 {
+        if self.is_base_input {
+            self.amount_in
+        } else {
+            self.min_amount_out
+        }
+    }
+
+    /// Whether the order waits on a start time, a parent, a stop, a
+    /// trigger, a limit price or its next slice, and so may execute out of
+    /// its pool's line
+    pub fn is_conditional(&self) -> bool {
+        self.valid_from.is_some()
+            || self.parent_order.is_some()
+            || self.trailing_stop.is_some()
+            || self.trigger.is_some()
+            || self.limit_price.is_some()
+            || self.twap.is_some()
+    }
+
+    /// Price update account the order's trailing stop or trigger follows
+    pub fn oracle(&self) -> Option<Pubkey> {
+        self.trailing_stop
+            .map(|stop| stop.oracle)
+            .or(self.trigger.map(|trigger| trigger.oracle))
+    }
+
+    /// Input and minimum output of the order's next execution: for a TWAP
+    /// order its next slice, otherwise what remains of it, up to
+    /// `max_fill` of input. A fill's minimum is its share of the order's,
+    /// rounded up.
+    pub fn next_fill(&self, max_fill: Option<u64>) -> (u64, u64) {
+        if let Some(twap) = self.twap {
+            return twap.next_slice(self.amount_in, self.min_amount_out);
+        }
+        if !self.is_base_input {
+            return (self.amount_in, self.min_amount_out);
+        }
+        let fill = max_fill.map_or(self.amount_in_remaining, |max_fill| max_fill.min(self.amount_in_remaining));
+        (fill, share_rounded_up(self.min_amount_out, fill, self.amount_in))
+    }
+
+    /// Whether `max_fill` may cap the order's next execution: a positive
+    /// amount, on an exact input order without a TWAP schedule
+    pub fn accepts_partial_fill(&self, max_fill: u64) -> bool {
+        max_fill > 0 && self.is_base_input && self.twap.is_none()
+    }
+
+    /// Whether a fill of `amount_in` is one the order's execution policy
+    /// allows: a fill-or-kill order only fills in full
+    pub fn fill_allowed(&self, amount_in: u64) -> bool {
+        self.execution_policy != ExecutionPolicy::FillOrKill
+            || !self.is_base_input
+            || amount_in >= self.amount_in_remaining
+    }
+
+    /// Record a fill of `amount_in` that delivered `amount_out`, returning
+    /// whether the order is now filled in full
+    pub fn record_fill(&mut self, amount_in: u64, amount_out: u64, now: i64) -> bool {
+        self.amount_out_filled = self.amount_out_filled.saturating_add(amount_out);
+        if self.is_base_input {
+            self.amount_in_remaining = self.amount_in_remaining.saturating_sub(amount_in);
+        } else {
+            self.amount_in_remaining = 0;
+        }
+        match self.twap.as_mut() {
+            Some(twap) => twap.record_slice(now),
+            None => self.amount_in_remaining == 0,
+        }
+    }
+
+    /// Record a fill like [`Self::record_fill`] and close the order when it
+    /// is filled in full, or when it is immediate-or-cancel and the rest is
+    /// cancelled; returns whether it closed. Until it closes, the order stays
+    /// pending and at the head of its pool's queue.
+    pub fn settle_fill(&mut self, amount_in: u64, amount_out: u64, now: i64) -> bool {
+        let filled = self.record_fill(amount_in, amount_out, now);
+        let cancels_rest = !filled && self.execution_policy == ExecutionPolicy::ImmediateOrCancel;
+        if filled || cancels_rest {
+            self.status = if filled { OrderStatus::Executed } else { OrderStatus::Cancelled };
+            self.executed_at = Some(now);
+        }
+        filled || cancels_rest
+    }
+
+    /// Whether the order takes none of `execute_order`'s optional accounts
+    /// but its vault's refund account and user, so `execute_orders_batch`
+    /// can execute it
+    pub fn is_batchable(&self) -> bool {
+        self.callback_program.is_none()
+            && self.post_fill.is_none()
+            && self.parent_order.is_none()
+            && self.trailing_stop.is_none()
+            && self.trigger.is_none()
+            && self.shared_escrow.is_none()
+            && !self.delegated_input
+            && self.permit.is_none()
+            && self.twap.is_none()
+    }
+
+    /// Whether `modify_order` can change the order: it spends from the
+    /// user's own account, or from its vault, which a re-queue tops up or
+    /// refunds for the difference, so nothing was reserved or approved
+    /// elsewhere for its amounts
+    pub fn is_modifiable(&self) -> bool {
+        (self.source_account.is_none() || self.escrow_refund.is_some())
+            && self.shared_escrow.is_none()
+            && !self.delegated_input
+            && self.permit.is_none()
+    }
+
+    /// Whether changing the order to `amount_in` for `min_amount_out` only
+    /// tightens its limit: the same amount for at least its minimum out, or
+    /// for an exact output order at most its maximum in
+    pub fn is_tightened_by(&self, amount_in: u64, min_amount_out: u64) -> bool {
+        amount_in == self.amount_in
+            && if self.is_base_input {
+                min_amount_out >= self.min_amount_out
+            } else {
+                min_amount_out <= self.min_amount_out
+            }
+    }
+
+    /// Whether no part of the order has filled yet
+    pub fn is_unfilled(&self) -> bool {
+        self.amount_out_filled == 0
+            && self.amount_in_remaining == self.amount_in
+            && self.twap.is_none_or(|twap| twap.slices_executed == 0)
+    }
+
+    /// Owner the swap output must be delivered to
+    pub fn output_owner(&self) -> Pubkey {
+        self.recipient.unwrap_or(self.user)
+    }
+
+    /// Whether the order's not-before time, if any, has passed at `now`
+    pub fn is_valid_at(&self, now: i64) -> bool {
+        self.valid_from.is_none_or(|valid_from| now >= valid_from)
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now > expires_at)
+    }
+
+    /// Whether the pool's reserves of the input and output mints, read
+    /// before the swap, meet the order's limit price if it has one
+    pub fn limit_reached(&self, reserves: Option<(u64, u64)>) -> bool {
+        match self.limit_price {
+            Some(limit) => reserves.is_some_and(|(input, output)| limit.met_by(input, output)),
+            None => true,
+        }
+    }
+
+    /// Whether `parent`, the account passed for the order's parent if any,
+    /// lets it execute: it must be the parent and have executed
+    pub fn parent_filled(&self, parent: Option<(Pubkey, &OrderState)>) -> bool {
+        match self.parent_order {
+            Some(address) => parent.is_some_and(|(key, order)| key == address && order.status == OrderStatus::Executed),
+            None => true,
+        }
+    }
+
+    /// Whether the input may be spent from `account`, owned by `owner` and
+    /// holding `mint`: an account of the order's input mint, and the order's
+    /// source account if it has one, otherwise any account of the user
+    pub fn accepts_source(&self, account: &Pubkey, owner: &Pubkey, mint: &Pubkey) -> bool {
+        *mint == self.input_mint
+            && match self.source_account {
+                Some(source) => *account == source,
+                None => *owner == self.user,
+            }
+    }
+
+    /// Whether the output may be delivered to `account`, owned by `owner`:
+    /// the pinned destination account if the order has one, otherwise any
+    /// account of the output owner
+    pub fn accepts_destination(&self, account: &Pubkey, owner: &Pubkey) -> bool {
+        match self.destination_account {
+            Some(destination) => *account == destination,
+            None => *owner == self.output_owner(),
+        }
+    }
+
+    /// Whether the order may be cancelled with `co_signer` signing, if any
+    pub fn cancel_co_signed(&self, co_signer: Option<&Pubkey>) -> bool {
+        self.co_signer
+            .is_none_or(|required| !required.scope.on_cancellation() || required.signed_by(co_signer))
+    }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub enum OrderStatus {
-    Pending,
-    Executed,
-    Cancelled,
-    Failed,
+/// Fee rates an order is quoted when it is submitted. Changes to the fee
+/// configuration while it is queued only apply when they lower its fees.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeQuote {
+    pub protocol_fee_bps: u16,
+    /// The most any relayer may charge on the pool
+    pub relayer_fee_bps: u16,
 }
 
-#[event]
-pub struct OrderSubmitted {
-    pub sequence: u64,
+impl FeeQuote {
+    pub const LEN: usize = 2 + 2;
+
+    pub fn new(fifo_state: &FifoState, registry: &CpSwapPoolRegistry) -> Self {
+        let relayer_fee_bps = match registry.max_relayer_fee_bps {
+            0 => fifo_state.relayer_fee_bps,
+            cap => cap,
+        };
+        Self {
+            protocol_fee_bps: fifo_state.protocol_fee_bps,
+            relayer_fee_bps,
+        }
+    }
+
+    /// The rates to charge given the current ones
+    pub fn apply(&self, protocol_fee_bps: u16, relayer_fee_bps: u16) -> (u16, u16) {
+        (protocol_fee_bps.min(self.protocol_fee_bps), relayer_fee_bps.min(self.relayer_fee_bps))
+    }
+
+    /// Most fees the quote can charge on spending `amount`, or `None` if
+    /// they don't fit a u64
+    pub fn max_fees(&self, amount: u64) -> Option<u64> {
+        let protocol_fee = amount as u128 * self.protocol_fee_bps as u128 / 10_000;
+        let relayer_fee = amount as u128 * self.relayer_fee_bps as u128 / 10_000;
+        u64::try_from(protocol_fee + relayer_fee).ok()
+    }
+}
+
+/// How long an order rests in the queue
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExecutionPolicy {
+    /// Pending until executed, cancelled or expired
+    GoodTillCancelled,
+    /// Executed as far as it can be once next in its pool's line; whatever
+    /// isn't filled is cancelled, by the fill itself or by `cancel_ioc_order`
+    ImmediateOrCancel,
+    /// Executed in full or not at all
+    FillOrKill,
+}
+
+impl ExecutionPolicy {
+    /// Whether an order under this policy may wait on a condition
+    pub fn allows_conditions(&self) -> bool {
+        matches!(self, Self::GoodTillCancelled)
+    }
+}
+
+/// Which actions on an order need its co-signer's signature
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoSignScope {
+    Submission,
+    Cancellation,
+    Both,
+}
+
+impl CoSignScope {
+    pub fn on_submission(&self) -> bool {
+        matches!(self, Self::Submission | Self::Both)
+    }
+
+    pub fn on_cancellation(&self) -> bool {
+        matches!(self, Self::Cancellation | Self::Both)
+    }
+}
+
+/// A key that must sign an order's submission and/or cancellation along
+/// with the user or their delegate
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CoSigner {
+    pub key: Pubkey,
+    pub scope: CoSignScope,
+}
+
+impl CoSigner {
+    pub const LEN: usize = 32 + 1;
+
+    pub fn signed_by(&self, signer: Option<&Pubkey>) -> bool {
+        signer == Some(&self.key)
+    }
+}
+
+/// Which way a trailing stop follows the oracle price
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrailDirection {
+    /// Trails below the highest price seen and fires when the price falls to it
+    Sell,
+    /// Trails above the lowest price seen and fires when the price rises to it
+    Buy,
+}
+
+/// A stop `trail_bps` away from the best oracle price seen since the order
+/// was submitted. Prices are in the oracle feed's own units.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TrailingStop {
+    /// Price update account of the feed the stop follows
+    pub oracle: Pubkey,
+    pub direction: TrailDirection,
+    pub trail_bps: u16,
+    /// Best price seen: the high for a sell, the low for a buy
+    pub reference_price: i64,
+    pub stop_price: i64,
+}
+
+impl TrailingStop {
+    pub const LEN: usize = 32 + 1 + 2 + 8 + 8;
+
+    pub fn new(oracle: Pubkey, direction: TrailDirection, trail_bps: u16, price: i64) -> Self {
+        let mut stop = Self {
+            oracle,
+            direction,
+            trail_bps,
+            reference_price: price,
+            stop_price: 0,
+        };
+        stop.stop_price = stop.stop_for(price);
+        stop
+    }
+
+    fn stop_for(&self, price: i64) -> i64 {
+        let trail = (price as i128 * self.trail_bps as i128 / 10_000) as i64;
+        match self.direction {
+            TrailDirection::Sell => price.saturating_sub(trail),
+            TrailDirection::Buy => price.saturating_add(trail),
+        }
+    }
+
+    /// Ratchet the stop with `price`, returning whether it moved
+    pub fn observe(&mut self, price: i64) -> bool {
+        let better = match self.direction {
+            TrailDirection::Sell => price > self.reference_price,
+            TrailDirection::Buy => price < self.reference_price,
+        };
+        if better {
+            self.reference_price = price;
+            self.stop_price = self.stop_for(price);
+        }
+        better
+    }
+
+    /// Whether `price` has reached the stop
+    pub fn triggered(&self, price: i64) -> bool {
+        match self.direction {
+            TrailDirection::Sell => price <= self.stop_price,
+            TrailDirection::Buy => price >= self.stop_price,
+        }
+    }
+}
+
+/// Which way the oracle price must cross a trigger
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriggerDirection {
+    /// Fires once the price is at or above the trigger, e.g. a sell's take-profit
+    Above,
+    /// Fires once the price is at or below the trigger, e.g. a sell's stop-loss
+    Below,
+}
+
+/// A fixed oracle price an order is held until, for stop-loss and
+/// take-profit orders. Prices are in the oracle feed's own units.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TriggerCondition {
+    /// Price update account of the feed the trigger watches
+    pub oracle: Pubkey,
+    pub trigger_price: i64,
+    pub direction: TriggerDirection,
+}
+
+impl TriggerCondition {
+    pub const LEN: usize = 32 + 8 + 1;
+
+    /// Whether `price` has crossed the trigger
+    pub fn triggered(&self, price: i64) -> bool {
+        match self.direction {
+            TriggerDirection::Above => price >= self.trigger_price,
+            TriggerDirection::Below => price <= self.trigger_price,
+        }
+    }
+}
+
+/// Least output per unit of input, as `numerator / denominator` in the
+/// mints' base units, the pool's spot price must offer for a limit order to
+/// execute
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LimitPrice {
+    pub numerator: u64,
+    pub denominator: u64,
+}
+
+impl LimitPrice {
+    pub const LEN: usize = 8 + 8;
+
+    pub fn is_valid(&self) -> bool {
+        self.numerator > 0 && self.denominator > 0
+    }
+
+    /// Whether the spot price of pool reserves `input` and `output` is at
+    /// least the limit
+    pub fn met_by(&self, input: u64, output: u64) -> bool {
+        output as u128 * self.denominator as u128 >= input as u128 * self.numerator as u128
+    }
+}
+
+/// Schedule of a TWAP order, which sells its input in `num_slices` equal
+/// slices, executed at least `interval_seconds` apart
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TwapSchedule {
+    pub num_slices: u16,
+    pub interval_seconds: i64,
+    pub slices_executed: u16,
+    /// When the last slice executed
+    pub last_slice_at: i64,
+}
+
+impl TwapSchedule {
+    pub const LEN: usize = 2 + 8 + 2 + 8;
+
+    pub fn new(num_slices: u16, interval_seconds: i64) -> Self {
+        Self {
+            num_slices,
+            interval_seconds,
+            slices_executed: 0,
+            last_slice_at: 0,
+        }
+    }
+
+    /// Whether the next slice may execute at `now`
+    pub fn slice_due(&self, now: i64) -> bool {
+        self.slices_executed == 0 || now >= self.last_slice_at.saturating_add(self.interval_seconds)
+    }
+
+    pub fn is_last_slice(&self) -> bool {
+        self.slices_executed + 1 >= self.num_slices
+    }
+
+    /// Input and minimum output of the next slice of an order of `amount_in`
+    /// for at least `min_amount_out`. The last slice takes what rounding
+    /// left; each slice's minimum is its share of the order's, rounded up.
+    pub fn next_slice(&self, amount_in: u64, min_amount_out: u64) -> (u64, u64) {
+        let slice = amount_in / self.num_slices as u64;
+        let slice_in = if self.is_last_slice() {
+            amount_in - slice * self.slices_executed as u64
+        } else {
+            slice
+        };
+        (slice_in, share_rounded_up(min_amount_out, slice_in, amount_in))
+    }
+
+    /// Record a slice executed at `now`, returning whether it was the last
+    pub fn record_slice(&mut self, now: i64) -> bool {
+        self.slices_executed += 1;
+        self.last_slice_at = now;
+        self.slices_executed >= self.num_slices
+    }
+}
+
+/// `part / whole` of `total`, rounded up; nothing of an empty whole, which
+/// submission rejects
+fn share_rounded_up(total: u64, part: u64, whole: u64) -> u64 {
+    if whole == 0 {
+        return 0;
+    }
+    (total as u128 * part as u128).div_ceil(whole as u128) as u64
+}
+
+/// Action run on an order's output inside its execution
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PostFillAction {
+    /// Burn the output, e.g. for buyback-and-burn
+    Burn,
+    /// Deposit the output into a whitelisted staking program on behalf of
+    /// the order's output owner
+    Stake { program: Pubkey },
+}
+
+impl PostFillAction {
+    /// Serialized size of the largest variant
+    pub const LEN: usize = 1 + 32;
+}
+
+/// A repeating order. `materialize_order_template` enqueues an occurrence
+/// every `interval` seconds until `total_occurrences` have been, each spending
+/// from the template's escrow owned by the pool authority.
+/// Seeds: ["order_template", user, template_id]
+#[account]
+pub struct OrderTemplate {
     pub user: Pubkey,
+    pub template_id: u64,
     pub pool_id: Pubkey,
     pub amount_in: u64,
+    pub min_amount_out: u64,
     pub is_base_input: bool,
+    pub interval: i64,
+    pub total_occurrences: u32,
+    /// Occurrences enqueued so far
+    pub occurrences: u32,
+    /// Unix timestamp from which the next occurrence can be enqueued
+    pub next_at: i64,
 }
 
-#[event]
-pub struct OrderExecuted {
-    pub sequence: u64,
+impl OrderTemplate {
+    pub const LEN: usize = 8 + 32 + 8 + 32 + 8 + 8 + 1 + 8 + 4 + 4 + 8;
+
+    /// Most input one occurrence can spend: the amount in, or for exact
+    /// output orders the maximum in
+    pub fn max_input(&self) -> u64 {
+        if self.is_base_input {
+            self.amount_in
+        } else {
+            self.min_amount_out
+        }
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.occurrences >= self.total_occurrences
+    }
+}
+
+/// A recurring swap. `execute_dca_tick` swaps `amount_per_tick` of the
+/// schedule's budget, escrowed with the pool authority, into `destination`
+/// every `interval` seconds until the budget is spent.
+/// Seeds: ["dca_schedule", user, schedule_id]
+#[account]
+pub struct DcaSchedule {
     pub user: Pubkey,
-    pub amount_out: u64,
-    pub executor: Pubkey,
+    pub schedule_id: u64,
+    pub pool_id: Pubkey,
+    pub input_mint: Pubkey,
+    /// Token account receiving each tick's output
+    pub destination: Pubkey,
+    pub amount_per_tick: u64,
+    /// Minimum output of a full tick; a smaller last tick's is scaled down
+    pub min_amount_out_per_tick: u64,
+    pub interval: i64,
+    /// Escrowed input not yet swapped
+    pub budget_remaining: u64,
+    pub ticks_executed: u32,
+    /// Output received over all ticks so far
+    pub amount_out_total: u64,
+    /// Unix timestamp from which the next tick can execute
+    pub next_execution_time: i64,
 }
 
-#[event]
-pub struct OrderCancelled {
-    pub sequence: u64,
+impl DcaSchedule {
+    pub const LEN: usize = 8 + 32 + 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 4 + 8 + 8;
+
+    pub fn is_due(&self, now: i64) -> bool {
+        !self.is_exhausted() && now >= self.next_execution_time
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.budget_remaining == 0
+    }
+
+    /// Input and minimum output of the next tick paying `fee_bps` on top: a
+    /// full tick, or what the budget has left after its fees for its
+    /// minimum's share, rounded up
+    pub fn next_tick(&self, fee_bps: u16) -> (u64, u64) {
+        let fees = |amount: u64| (amount as u128 * fee_bps as u128 / 10_000) as u64;
+        let amount_in = if self.amount_per_tick.saturating_add(fees(self.amount_per_tick)) <= self.budget_remaining {
+            self.amount_per_tick
+        } else {
+            self.budget_remaining.saturating_sub(fees(self.budget_remaining))
+        };
+        let min_amount_out = (self.min_amount_out_per_tick as u128 * amount_in as u128)
+            .div_ceil(self.amount_per_tick as u128) as u64;
+        (amount_in, min_amount_out)
+    }
+
+    /// Record a tick executed at `now` spending `amount_in`, fees included,
+    /// for `amount_out`
+    pub fn record_tick(&mut self, amount_in: u64, amount_out: u64, now: i64) {
+        self.budget_remaining = self.budget_remaining.saturating_sub(amount_in);
+        self.ticks_executed += 1;
+        self.amount_out_total = self.amount_out_total.saturating_add(amount_out);
+        self.next_execution_time = now.saturating_add(self.interval);
+    }
+}
+
+/// An order shown to the queue one tranche at a time. Each tranche is an
+/// exact-input order of `tranche_in` spending from the iceberg's escrow, and
+/// `rearm_iceberg` enqueues the next one at the tail once the last has
+/// filled, so only the escrow reveals the size left.
+/// Seeds: ["iceberg", user, iceberg_id]
+#[account]
+pub struct Iceberg {
     pub user: Pubkey,
+    pub iceberg_id: u64,
+    pub pool_id: Pubkey,
+    pub tranche_in: u64,
+    /// Minimum output of each tranche
+    pub tranche_min_out: u64,
+    /// Order account of the tranche last enqueued
+    pub active_order: Option<Pubkey>,
+    /// Tranches enqueued so far
+    pub tranches: u32,
 }
 
-#[event]
-pub struct PoolRegistered {
+impl Iceberg {
+    pub const LEN: usize = 8 + 32 + 8 + 32 + 8 + 8 + 33 + 4;
+}
+
+/// A user's balance of `mint` that their orders on any pool can spend from.
+/// Submitting against it reserves the order's maximum input until the order
+/// executes or is cancelled, and only the unreserved balance can be spent
+/// otherwise. The tokens sit in a vault owned by this account, see
+/// `crate::shared_escrow`.
+/// Seeds: ["shared_escrow", user, mint]
+#[account]
+pub struct SharedEscrow {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    /// Sum of the maximum inputs of the orders against it
+    pub reserved: u64,
+    /// Pending orders against it, which must be settled before it is closed
+    pub open_orders: u32,
+}
+
+impl SharedEscrow {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 4;
+
+    /// Part of a vault balance no order has reserved
+    pub fn available(&self, balance: u64) -> u64 {
+        balance.saturating_sub(self.reserved)
+    }
+
+    pub fn reserve(&mut self, amount: u64) {
+        self.reserved += amount;
+        self.open_orders += 1;
+    }
+
+    pub fn release(&mut self, amount: u64) {
+        self.reserved = self.reserved.saturating_sub(amount);
+        self.open_orders = self.open_orders.saturating_sub(1);
+    }
+}
+
+/// A staking program the admin allows swap-and-stake orders to deposit
+/// into. Seeds: ["stake_program", program]
+#[account]
+pub struct ApprovedStakeProgram {
+    pub program: Pubkey,
+    pub approved_at: i64,
+}
+
+impl ApprovedStakeProgram {
+    pub const LEN: usize = 8 + 32 + 8;
+}
+
+/// Registration of an off-chain relayer. Seeds: ["relayer", relayer]
+#[account]
+pub struct RelayerInfo {
+    pub relayer: Pubkey,
+    pub registered_at: i64,
+    /// Unix timestamp of the relayer's last heartbeat
+    pub last_active: i64,
+    /// Slot of the relayer's last heartbeat
+    pub last_active_slot: u64,
+    /// Fee the relayer asks for, charged up to each pool's cap
+    pub fee_bps: u16,
+    /// The relayer's recent `swap_immediate` calls
+    pub swap_rate: RateCounter,
+}
+
+impl RelayerInfo {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 2 + RateCounter::LEN;
+}
+
+/// Sliding-window event count, approximated from the current and previous
+/// fixed windows like [`VolumeStats`]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct RateCounter {
+    pub window_start: i64,
+    pub current: u32,
+    pub previous: u32,
+}
+
+impl RateCounter {
+    pub const LEN: usize = 8 + 4 + 4;
+
+    /// Count one event at `now` if fewer than `limit` fell within the
+    /// trailing `window` seconds, returning whether it was counted
+    pub fn record(&mut self, now: i64, window: u32, limit: u32) -> bool {
+        let window = window.max(1) as i64;
+        let elapsed = now.saturating_sub(self.window_start);
+        if elapsed >= window {
+            self.previous = if elapsed < window * 2 { self.current } else { 0 };
+            self.current = 0;
+            self.window_start = now - elapsed % window;
+        }
+
+        // A clock behind the window start reads as its start
+        let into_window = now.saturating_sub(self.window_start).max(0);
+        let carried = self.previous as i64 * (window - into_window) / window;
+        if self.current as i64 + carried >= limit as i64 {
+            return false;
+        }
+        self.current += 1;
+        true
+    }
+}
+
+/// The queue of one pool's orders, sequenced independently of other pools
+/// so their orders don't contend: an order's sequence is its position here,
+/// and its account is derived from the pool and that sequence. Orders
+/// execute strictly in sequence; settled orders, conditional ones still
+/// waiting, see [`OrderState::is_conditional`], and pending ones next in
+/// line for longer than [`HEAD_GRACE_PERIOD`] are stepped over with
+/// `skip_order`. A conditional order passed over may then execute out of
+/// line.
+/// Seeds: ["pool_fifo", pool_id]
+#[account]
+pub struct PoolFifoState {
     pub pool_id: Pubkey,
-    pub continuum_authority: Pubkey,
-}
\ No newline at end of file
+    /// Sequence of the latest order submitted on the pool
+    pub current_sequence: u64,
+    /// Sequence through which every order was executed or skipped
+    pub head: u64,
+    /// Running hash chain over the orders submitted on the pool
+    pub order_stream_hash: [u8; 32],
+    /// Stream hash at the last checkpoint
+    pub checkpoint_root: [u8; 32],
+    /// Sequence the last checkpoint was taken at
+    pub checkpoint_sequence: u64,
+    /// Number of immediate swaps made on the pool, which number their
+    /// `SwapExecuted` events
+    pub immediate_swaps: u64,
+    /// Number of events emitted about the pool so far; every such event
+    /// carries its own number
+    pub event_sequence: u64,
+    /// Unix timestamp `head` last moved at
+    pub head_moved_at: i64,
+}
+
+impl PoolFifoState {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 32 + 32 + 8 + 8 + 8 + 8;
+
+    /// Sequence the next order submitted on the pool gets; its account is
+    /// derived from it
+    pub fn upcoming_sequence(&self) -> u64 {
+        self.current_sequence + 1
+    }
+
+    /// Account of the next order submitted on the pool
+    pub fn upcoming_order(&self) -> Pubkey {
+        OrderState::address(&self.pool_id, self.upcoming_sequence())
+    }
+
+    /// Claim the sequence of a newly submitted order
+    pub fn next_sequence(&mut self) -> u64 {
+        self.current_sequence += 1;
+        self.current_sequence
+    }
+
+    /// Claim the next number among the pool's events
+    pub fn next_event_sequence(&mut self) -> u64 {
+        self.event_sequence += 1;
+        self.event_sequence
+    }
+
+    /// Sequence of the order next in line
+    pub fn next_execution_sequence(&self) -> u64 {
+        self.head + 1
+    }
+
+    /// Record that the order at `sequence` was executed or skipped at
+    /// `now`; the head only moves for the order next in line
+    pub fn advance(&mut self, sequence: u64, now: i64) {
+        if sequence == self.next_execution_sequence() {
+            self.head = sequence;
+            self.head_moved_at = now;
+        }
+    }
+
+    /// Whether `order` may execute as far as the queue goes: it is next in
+    /// line, or it is conditional and was passed over waiting on its
+    /// condition. Conditional orders wait their turn like any other.
+    pub fn may_execute(&self, order: &OrderState) -> bool {
+        order.sequence == self.next_execution_sequence()
+            || (order.sequence <= self.head && order.is_conditional())
+    }
+
+    /// Whether the order next in line, submitted at `submitted_at`, has been
+    /// so for [`HEAD_GRACE_PERIOD`] at `now`, so that it may be skipped even
+    /// while pending
+    pub fn head_stalled(&self, submitted_at: i64, now: i64) -> bool {
+        now.saturating_sub(self.head_moved_at.max(submitted_at)) >= HEAD_GRACE_PERIOD
+    }
+
+    /// Claim the next sequence for an order of `user` and fold it into the
+    /// stream hash, emitting `CheckpointRecorded` when it completes a
+    /// checkpoint interval. The order's account is the [`upcoming_order`]
+    /// from before the call.
+    ///
+    /// [`upcoming_order`]: Self::upcoming_order
+    pub fn sequence_order(&mut self, user: &Pubkey, amount_in: u64, is_base_input: bool, checkpoint_interval: u64) -> u64 {
+        let sequence = self.next_sequence();
+        let hash = order_hash(sequence, user, &self.pool_id, amount_in, is_base_input);
+        if self.fold_order(sequence, hash, checkpoint_interval) {
+            emit!(CheckpointRecorded {
+                event_sequence: self.next_event_sequence(),
+                sequence,
+                pool_id: self.pool_id,
+                root: self.checkpoint_root,
+            });
+        }
+        sequence
+    }
+
+    /// Sequence `order`, see [`sequence_order`], with the fees in force on
+    /// `registry`, returning it as [`OrderState::new`] submitted at `now`
+    ///
+    /// [`sequence_order`]: Self::sequence_order
+    pub fn enqueue(&mut self, order: NewOrder, fifo_state: &FifoState, registry: &CpSwapPoolRegistry, now: i64) -> OrderState {
+        let sequence = self.sequence_order(&order.user, order.amount_in, order.is_base_input, fifo_state.checkpoint_interval);
+        OrderState::new(self.pool_id, sequence, order, FeeQuote::new(fifo_state, registry), now)
+    }
+
+    /// Whether `skip_order` may move the queue past `order`: it must be this
+    /// pool's order next in line, and either need no execution in line or
+    /// have stalled there
+    pub fn may_skip(&self, order: &OrderState, now: i64) -> bool {
+        order.pool_id == self.pool_id
+            && order.sequence == self.next_execution_sequence()
+            && (order.status != OrderStatus::Pending
+                || order.is_conditional()
+                || self.head_stalled(order.submitted_at, now))
+    }
+
+    /// Fold a newly sequenced order into the stream hash. Returns true when
+    /// this order completes a checkpoint interval and the root was updated.
+    pub fn fold_order(&mut self, sequence: u64, order_hash: [u8; 32], checkpoint_interval: u64) -> bool {
+        self.order_stream_hash = keccak::hashv(&[&self.order_stream_hash, &order_hash]).to_bytes();
+
+        if checkpoint_interval == 0 || !sequence.is_multiple_of(checkpoint_interval) {
+            return false;
+        }
+
+        self.checkpoint_root = self.order_stream_hash;
+        self.checkpoint_sequence = sequence;
+        true
+    }
+}
+
+/// Relayers allowed to execute orders on a pool; a pool without one, or with
+/// an empty list, is open to every relayer. Seeds: ["pool_relayers", pool_id]
+#[account]
+pub struct PoolRelayers {
+    pub pool_id: Pubkey,
+    pub relayers: Vec<Pubkey>,
+}
+
+impl PoolRelayers {
+    pub const MAX_RELAYERS: usize = 16;
+    pub const LEN: usize = 8 + 32 + 4 + 32 * Self::MAX_RELAYERS;
+
+    pub fn allows(&self, relayer: &Pubkey) -> bool {
+        self.relayers.is_empty() || self.relayers.contains(relayer)
+    }
+}
+
+/// Executions a relayer may make per period, in count and in volume counted
+/// in each pool's token_0 like the registry's `volume_0`. A relayer without
+/// one is unlimited. The counters only reset when `reset_relayer_quota` is
+/// cranked after the period ends. Seeds: ["relayer_quota", relayer]
+#[account]
+pub struct RelayerQuota {
+    pub relayer: Pubkey,
+    /// Most executions per period (0 for no limit)
+    pub max_executions: u32,
+    /// Most volume per period (0 for no limit)
+    pub max_volume: u64,
+    /// Length of a period in seconds, e.g. a day
+    pub period: i64,
+    pub period_start: i64,
+    pub executions: u32,
+    pub volume: u64,
+}
+
+impl RelayerQuota {
+    pub const LEN: usize = 8 + 32 + 4 + 8 + 8 + 8 + 4 + 8;
+
+    /// Count an execution of `amount_0`, returning whether it stays within
+    /// the quota
+    pub fn record(&mut self, amount_0: u64) -> bool {
+        self.executions = self.executions.saturating_add(1);
+        self.volume = self.volume.saturating_add(amount_0);
+        (self.max_executions == 0 || self.executions <= self.max_executions)
+            && (self.max_volume == 0 || self.volume <= self.max_volume)
+    }
+
+    pub fn period_over(&self, now: i64) -> bool {
+        now.saturating_sub(self.period_start) >= self.period
+    }
+
+    /// Zero the counters and start the period `now` falls in
+    pub fn reset(&mut self, now: i64) {
+        let elapsed = now.saturating_sub(self.period_start);
+        self.period_start += elapsed - elapsed % self.period;
+        self.executions = 0;
+        self.volume = 0;
+    }
+}
+
+/// A trusted Continuum emitter on another Wormhole chain.
+/// Seeds: ["foreign_emitter", chain]
+#[account]
+pub struct ForeignEmitter {
+    pub chain: u16,
+    pub address: [u8; 32],
+}
+
+impl ForeignEmitter {
+    pub const LEN: usize = 8 + 2 + 32;
+}
+
+/// Replay protection for consumed order VAAs, seeds ["received",
+/// emitter_chain, vaa_sequence], and for the token bridge transfers funding
+/// them, seeds ["redeemed_transfer", transfer VAA account]
+#[account]
+pub struct ReceivedVaa {
+    pub emitter_chain: u16,
+    pub vaa_sequence: u64,
+    pub order_sequence: u64,
+    pub received_at: i64,
+}
+
+impl ReceivedVaa {
+    pub const LEN: usize = 8 + 2 + 8 + 8 + 8;
+}
+
+/// Replay protection for submitted order intents.
+/// Seeds: ["intent", user, nonce]
+#[account]
+pub struct UsedIntent {
+    pub user: Pubkey,
+    pub nonce: u64,
+    pub order_sequence: u64,
+    pub relayer: Pubkey,
+    pub submitted_at: i64,
+}
+
+impl UsedIntent {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 32 + 8;
+}
+
+/// Lets `delegate` submit and cancel orders for `user`, which may be a PDA of
+/// another program that approves it by CPI. Delegated orders always deliver
+/// their output to the user.
+/// Seeds: ["user_delegation", user, delegate]
+#[account]
+pub struct UserDelegation {
+    pub user: Pubkey,
+    pub delegate: Pubkey,
+    /// Largest maximum input of one delegated order
+    pub max_amount_in: u64,
+    /// Unix timestamp after which the delegation can no longer be used
+    pub expires_at: Option<i64>,
+    pub approved_at: i64,
+}
+
+impl UserDelegation {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 9 + 8;
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now > expires_at)
+    }
+}
+
+/// A standing approval of one of a user's token accounts, Permit2 style: the
+/// permit PDA is the account's SPL delegate once, and orders naming it spend
+/// through it while it hasn't expired and up to its allowance, which the user
+/// can change without approving again.
+/// Seeds: ["permit", token_account]
+#[account]
+pub struct Permit {
+    pub user: Pubkey,
+    pub token_account: Pubkey,
+    pub mint: Pubkey,
+    /// What orders may still spend through the permit, fees included
+    pub allowance: u64,
+    /// Unix timestamp after which the permit can no longer be spent through
+    pub expires_at: i64,
+    pub created_at: i64,
+}
+
+impl Permit {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8;
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now > self.expires_at
+    }
+
+    /// Whether an order spending up to `amount` can go through at `now`
+    pub fn covers(&self, amount: u64, now: i64) -> bool {
+        !self.is_expired(now) && self.allowance >= amount
+    }
+
+    /// Take `amount` off the allowance, which must cover it
+    pub fn spend(&mut self, amount: u64) -> bool {
+        match self.allowance.checked_sub(amount) {
+            Some(allowance) => {
+                self.allowance = allowance;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// An organization trading from its own shared escrows: members submit
+/// orders for it within their limits, and the owner manages the members and
+/// withdraws. The organization account is the `user` of its orders and
+/// escrows.
+/// Seeds: ["organization", owner, org_id]
+#[account]
+pub struct Organization {
+    pub owner: Pubkey,
+    pub org_id: u64,
+    pub members: Vec<OrgMember>,
+}
+
+impl Organization {
+    pub const MAX_MEMBERS: usize = 16;
+    pub const LEN: usize = 8 + 32 + 8 + 4 + OrgMember::LEN * Self::MAX_MEMBERS;
+
+    pub fn member(&self, key: &Pubkey) -> Option<&OrgMember> {
+        self.members.iter().find(|member| member.member == *key)
+    }
+
+    /// Whether `key` is the owner or a member
+    pub fn includes(&self, key: &Pubkey) -> bool {
+        self.owner == *key || self.member(key).is_some()
+    }
+}
+
+/// What one member of an organization may submit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct OrgMember {
+    pub member: Pubkey,
+    /// Largest maximum input of one order
+    pub max_amount_in: u64,
+    /// Pools the member may trade; empty for any
+    pub pools: Vec<Pubkey>,
+}
+
+impl OrgMember {
+    pub const MAX_POOLS: usize = 4;
+    pub const LEN: usize = 32 + 8 + 4 + 32 * Self::MAX_POOLS;
+
+    pub fn allows(&self, pool_id: &Pubkey, max_input: u64) -> bool {
+        max_input <= self.max_amount_in && (self.pools.is_empty() || self.pools.contains(pool_id))
+    }
+}
+
+/// Opt-in per-user ordering: while it exists, the user's orders settle in
+/// submission order even when the queue would let them interleave.
+/// Seeds: ["user_ordering", user]
+#[account]
+pub struct UserOrdering {
+    pub user: Pubkey,
+    /// Addresses of the user's pending orders, oldest first
+    pub pending: Vec<Pubkey>,
+}
+
+impl UserOrdering {
+    /// Pending orders a user can have in ordered mode
+    pub const MAX_PENDING: usize = 32;
+    pub const LEN: usize = 8 + 32 + 4 + 32 * Self::MAX_PENDING;
+}
+
+/// Timestamp override for `test-clock` builds; `None` defers to the cluster
+/// clock. Seeds: ["mock_clock"]
+#[account]
+pub struct MockClock {
+    pub unix_timestamp: Option<i64>,
+}
+
+impl MockClock {
+    pub const LEN: usize = 8 + 9;
+}
+
+/// Minimal proof that an order was filled, created by the executor at execution
+/// time. Seeds: ["receipt", pool_id, sequence]
+#[account]
+pub struct ExecutionReceipt {
+    pub sequence: u64,
+    pub user: Pubkey,
+    pub pool_id: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub executor: Pubkey,
+    pub slot: u64,
+    pub executed_at: i64,
+}
+
+impl ExecutionReceipt {
+    pub const LEN: usize = 8 + 8 + 32 + 32 + 8 + 8 + 32 + 8 + 8;
+
+    /// Derive the receipt address for an order, so settlement programs can check
+    /// that a receipt account they were handed is the canonical one
+    pub fn find_address(pool_id: &Pubkey, sequence: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[RECEIPT_SEED, pool_id.as_ref(), &sequence.to_le_bytes()],
+            &crate::ID,
+        )
+    }
+}
+
+/// Compensation paid to the owner of a mishandled order out of the insurance
+/// fund. Seeds: ["compensation", receipt]
+#[account]
+pub struct Compensation {
+    /// Execution receipt of the order
+    pub receipt: Pubkey,
+    pub pool_id: Pubkey,
+    pub sequence: u64,
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    /// Hash of the off-chain finding behind the compensation
+    pub reason: [u8; 32],
+    pub compensated_at: i64,
+}
+
+impl Compensation {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 32 + 32 + 8 + 32 + 8;
+}
+
+/// The fields of a closed order that are committed to the archive tree
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ArchivedOrder {
+    pub sequence: u64,
+    pub user: Pubkey,
+    pub pool_id: Pubkey,
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+    pub is_base_input: bool,
+    pub status: OrderStatus,
+    pub submitted_at: i64,
+    pub executed_at: Option<i64>,
+    pub recipient: Option<Pubkey>,
+}
+
+impl ArchivedOrder {
+    /// Keccak hash of the serialized order, used as the Merkle leaf
+    pub fn leaf_hash(&self) -> [u8; 32] {
+        keccak::hash(&borsh::to_vec(self).unwrap()).to_bytes()
+    }
+}
+
+impl From<&OrderState> for ArchivedOrder {
+    fn from(order: &OrderState) -> Self {
+        Self {
+            sequence: order.sequence,
+            user: order.user,
+            pool_id: order.pool_id,
+            amount_in: order.amount_in,
+            min_amount_out: order.min_amount_out,
+            is_base_input: order.is_base_input,
+            status: order.status,
+            submitted_at: order.submitted_at,
+            executed_at: order.executed_at,
+            recipient: order.recipient,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrderStatus {
+    Pending,
+    Executed,
+    Cancelled,
+    Failed,
+    /// Settled by `expire_order` after `OrderState::expires_at`
+    Expired,
+}
+
+/// Like every event about a pool, numbered by `event_sequence` among the
+/// pool's events, see [`PoolFifoState::event_sequence`]
+#[event]
+pub struct OrderSubmitted {
+    pub event_sequence: u64,
+    pub sequence: u64,
+    pub user: Pubkey,
+    pub pool_id: Pubkey,
+    pub amount_in: u64,
+    pub is_base_input: bool,
+    pub trace_id: TraceId,
+    pub client_order_id: Option<ClientOrderId>,
+    pub memo: Option<String>,
+}
+
+#[event]
+pub struct OrderExecuted {
+    pub event_sequence: u64,
+    pub sequence: u64,
+    pub pool_id: Pubkey,
+    pub user: Pubkey,
+    pub amount_out: u64,
+    pub executor: Pubkey,
+    /// Input actually spent by the swap, fees excluded
+    pub amount_in: u64,
+    /// Output per unit of input, scaled by [`crate::consts::PRICE_SCALE`]
+    pub price: u64,
+    pub protocol_fee: u64,
+    pub relayer_fee: u64,
+    /// Fees the flat protocol and relayer rates would have charged, less
+    /// those actually charged after tiers, posted fees and the order's quote
+    pub rebate: u64,
+    pub trace_id: TraceId,
+}
+
+impl OrderExecuted {
+    /// `amount_out` per unit of `amount_in`, scaled by [`crate::consts::PRICE_SCALE`];
+    /// 0 when nothing was spent
+    pub fn realized_price(amount_in: u64, amount_out: u64) -> u64 {
+        if amount_in == 0 {
+            return 0;
+        }
+        let price = amount_out as u128 * crate::consts::PRICE_SCALE as u128 / amount_in as u128;
+        price.min(u64::MAX as u128) as u64
+    }
+}
+
+/// Why an execution was refused, carried by [`ExecutionRejected`]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RejectionReason {
+    /// The order's sequence differs from the one the executor expected
+    InvalidSequence,
+    /// The order is no longer pending
+    InvalidOrderStatus,
+    /// The source token account isn't one the order may spend from
+    InvalidSource,
+    /// The destination token account isn't the order recipient's
+    InvalidRecipient,
+    /// The swap filled outside the order's limit
+    SlippageExceeded,
+    /// A writable Continuum account was passed through to the swap
+    InvalidRemainingAccounts,
+    /// An earlier order from the same user-ordered user is still pending
+    OutOfUserOrder,
+    /// The pool is restricted to relayers the executor isn't one of
+    RelayerNotAssigned,
+    /// The callback program passed isn't the one the order names
+    InvalidCallback,
+    /// The accounts for the order's post-fill action are missing or wrong
+    InvalidPostFill,
+    /// The order's `valid_from` hasn't been reached
+    NotYetValid,
+    /// The order's parent hasn't executed, or wasn't passed
+    ParentNotFilled,
+    /// The order's trailing stop hasn't been reached, or its oracle is
+    /// missing or stale
+    StopNotTriggered,
+    /// The order's permit has expired or its allowance can't cover the order
+    PermitUnavailable,
+    /// The fill would take the executor past its quota for the period
+    RelayerQuotaExceeded,
+    /// An earlier order on the pool hasn't been executed or skipped
+    OutOfPoolOrder,
+    /// The order is past its expiry
+    Expired,
+    /// The order takes accounts only `execute_order` does
+    NotBatchable,
+    /// The pool's spot price doesn't meet the order's limit price
+    LimitNotReached,
+    /// The TWAP order's next slice isn't due yet
+    SliceNotDue,
+    /// The fill amount is zero, or the order can't be partially filled
+    InvalidFillAmount,
+    /// A fill-or-kill order was offered less than all of it
+    FillOrKill,
+    /// The order's trigger price hasn't been crossed, or its oracle is
+    /// missing or stale
+    TriggerNotReached,
+    /// Execution is restricted to registered relayers and the executor isn't one
+    RelayerNotRegistered,
+}
+
+/// Emitted just before `execute_order` fails a check. It only appears in the
+/// logs of failed transactions, so unlike other events it carries no
+/// `event_sequence`.
+#[event]
+pub struct ExecutionRejected {
+    pub sequence: u64,
+    pub pool_id: Pubkey,
+    pub executor: Pubkey,
+    pub reason: RejectionReason,
+    pub trace_id: TraceId,
+}
+
+#[event]
+pub struct TrailUpdated {
+    pub event_sequence: u64,
+    pub sequence: u64,
+    pub pool_id: Pubkey,
+    pub reference_price: i64,
+    pub stop_price: i64,
+}
+
+#[event]
+pub struct OrderCancelled {
+    pub event_sequence: u64,
+    pub sequence: u64,
+    pub pool_id: Pubkey,
+    pub user: Pubkey,
+    pub trace_id: TraceId,
+}
+
+/// A pending order's amounts changed by `modify_order`. An order whose limit
+/// was only tightened keeps its place, and `new_sequence` is its own;
+/// otherwise it was cancelled and `new_sequence` queued at the tail of its
+/// pool's line in its stead.
+#[event]
+pub struct OrderModified {
+    pub event_sequence: u64,
+    pub sequence: u64,
+    pub pool_id: Pubkey,
+    pub new_sequence: u64,
+    pub user: Pubkey,
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+    pub requeued: bool,
+    pub trace_id: TraceId,
+}
+
+/// A slice of a TWAP order executed, other than its last, which emits
+/// [`OrderExecuted`] instead
+#[event]
+pub struct TwapSliceExecuted {
+    pub event_sequence: u64,
+    pub sequence: u64,
+    pub pool_id: Pubkey,
+    pub user: Pubkey,
+    /// Slices executed so far, this one included
+    pub slice: u16,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub executor: Pubkey,
+    pub protocol_fee: u64,
+    pub relayer_fee: u64,
+    pub trace_id: TraceId,
+}
+
+/// Part of an order filled, with the order's fills so far. The fill
+/// completing it emits [`OrderExecuted`] instead.
+#[event]
+pub struct OrderPartiallyFilled {
+    pub event_sequence: u64,
+    pub sequence: u64,
+    pub pool_id: Pubkey,
+    pub user: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Input filled so far, this fill included
+    pub amount_in_filled: u64,
+    /// Output delivered so far, this fill included
+    pub amount_out_filled: u64,
+    pub amount_in_remaining: u64,
+    pub executor: Pubkey,
+    pub protocol_fee: u64,
+    pub relayer_fee: u64,
+    pub trace_id: TraceId,
+}
+
+/// A tick of a DCA schedule executed
+#[event]
+pub struct DcaTickExecuted {
+    pub event_sequence: u64,
+    pub schedule: Pubkey,
+    pub user: Pubkey,
+    pub pool_id: Pubkey,
+    /// Ticks executed so far, this one included
+    pub tick: u32,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub budget_remaining: u64,
+    pub next_execution_time: i64,
+    pub executor: Pubkey,
+    pub protocol_fee: u64,
+    pub relayer_fee: u64,
+}
+
+#[event]
+pub struct OrderExpired {
+    pub event_sequence: u64,
+    pub sequence: u64,
+    pub pool_id: Pubkey,
+    pub user: Pubkey,
+    pub trace_id: TraceId,
+}
+
+/// Emitted before the pool has a queue to number its events, so it carries
+/// no `event_sequence`
+#[event]
+pub struct PoolRegistered {
+    pub pool_id: Pubkey,
+    pub continuum_authority: Pubkey,
+}
+
+#[event]
+pub struct OrderArchived {
+    pub event_sequence: u64,
+    pub sequence: u64,
+    pub user: Pubkey,
+    pub pool_id: Pubkey,
+    pub leaf: [u8; 32],
+    pub leaf_index: u64,
+}
+
+#[event]
+pub struct CheckpointRecorded {
+    pub event_sequence: u64,
+    pub sequence: u64,
+    pub pool_id: Pubkey,
+    pub root: [u8; 32],
+}
+
+#[event]
+pub struct FeesAccrued {
+    pub event_sequence: u64,
+    pub sequence: u64,
+    pub pool_id: Pubkey,
+    pub mint: Pubkey,
+    pub protocol_fee: u64,
+    pub relayer_fee: u64,
+    pub relayer: Pubkey,
+}
+
+#[event]
+pub struct RelayerFeesClaimed {
+    pub event_sequence: u64,
+    pub pool_id: Pubkey,
+    pub relayer: Pubkey,
+    pub amount_0: u64,
+    pub amount_1: u64,
+}
+
+#[event]
+pub struct ProtocolFeesWithdrawn {
+    pub event_sequence: u64,
+    pub pool_id: Pubkey,
+    pub amount_0: u64,
+    pub amount_1: u64,
+}
+
+#[event]
+pub struct InsurancePaidOut {
+    pub event_sequence: u64,
+    pub pool_id: Pubkey,
+    pub mint: Pubkey,
+    /// Token account paid
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct OrderCompensated {
+    pub event_sequence: u64,
+    pub sequence: u64,
+    pub pool_id: Pubkey,
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub reason: [u8; 32],
+}
+
+/// The emergency pause was set or lifted, for every pool or for `pool_id`.
+/// Like the relayer events it is an admin event outside any pool's
+/// numbering.
+#[event]
+pub struct PauseSet {
+    pub pool_id: Option<Pubkey>,
+    pub paused: bool,
+    pub reason: String,
+}
+
+#[event]
+pub struct RelayerAdded {
+    pub relayer: Pubkey,
+}
+
+#[event]
+pub struct RelayerRemoved {
+    pub relayer: Pubkey,
+}
+
+#[event]
+pub struct CrossChainOrderReceived {
+    pub event_sequence: u64,
+    pub sequence: u64,
+    pub pool_id: Pubkey,
+    pub emitter_chain: u16,
+    pub vaa_sequence: u64,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct FillAttested {
+    pub event_sequence: u64,
+    pub sequence: u64,
+    pub pool_id: Pubkey,
+    pub message: Pubkey,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: i64 = CpSwapPoolRegistry::VOLUME_WINDOW;
+    const START: i64 = 1_700_000_000;
+
+    fn registry() -> CpSwapPoolRegistry {
+        CpSwapPoolRegistry {
+            pool_id: Pubkey::new_unique(),
+            token_0: Pubkey::new_unique(),
+            token_1: Pubkey::new_unique(),
+            continuum_authority: Pubkey::default(),
+            created_at: START,
+            is_active: true,
+            volume_0: VolumeStats::default(),
+            volume_1: VolumeStats::default(),
+            volume_window_start: START,
+            max_relayer_fee_bps: 0,
+            compliance_program: Pubkey::default(),
+            price_0: PriceAccumulator::default(),
+            lookup_table: Pubkey::default(),
+            is_paused: false,
+            amm_config: Pubkey::default(),
+            token_0_vault: Pubkey::default(),
+            token_1_vault: Pubkey::default(),
+            observation_state: Pubkey::default(),
+        }
+    }
+
+    #[test]
+    fn volume_is_recorded_on_the_side_of_each_mint() {
+        let mut registry = registry();
+        let (token_0, token_1) = (registry.token_0, registry.token_1);
+        registry.record_volume(&token_0, 100, 40, START);
+        registry.record_volume(&token_1, 10, 25, START + 60);
+
+        assert_eq!(registry.volume_0.lifetime, 125);
+        assert_eq!(registry.volume_1.lifetime, 50);
+        assert_eq!(registry.rolling_volume(START + 120), (125, 50));
+    }
+
+    #[test]
+    fn crossing_the_window_boundary_carries_the_previous_window() {
+        let mut registry = registry();
+        let token_0 = registry.token_0;
+        registry.record_volume(&token_0, 1_000, 500, START);
+        registry.record_volume(&token_0, 10, 5, START + WINDOW);
+
+        assert_eq!(registry.volume_window_start, START + WINDOW);
+        assert_eq!(registry.volume_0.previous_window, 1_000);
+        assert_eq!(registry.volume_0.current_window, 10);
+        assert_eq!(registry.rolling_volume(START + WINDOW), (1_010, 505));
+        assert_eq!(registry.rolling_volume(START + WINDOW + WINDOW / 2), (510, 255));
+        assert_eq!(registry.volume_0.lifetime, 1_010);
+    }
+
+    #[test]
+    fn rolling_volume_ages_out_without_new_swaps() {
+        let mut registry = registry();
+        let token_0 = registry.token_0;
+        registry.record_volume(&token_0, 1_000, 500, START);
+
+        assert_eq!(registry.rolling_volume(START + WINDOW - 1), (1_000, 500));
+        assert_eq!(registry.rolling_volume(START + WINDOW + WINDOW / 4), (750, 375));
+        assert_eq!(registry.rolling_volume(START + 2 * WINDOW), (0, 0));
+    }
+
+    #[test]
+    fn user_volume_clock_behind_the_window_start_neither_rolls_nor_inflates() {
+        let mut volume = UserVolume {
+            user: Pubkey::new_unique(),
+            pool_id: Pubkey::new_unique(),
+            volume: VolumeStats::default(),
+            window_start: START,
+        };
+        volume.record(25_920_000, START);
+        volume.record(10, START + UserVolume::WINDOW);
+        volume.record(1, START + UserVolume::WINDOW - 30);
+
+        assert_eq!(volume.window_start, START + UserVolume::WINDOW);
+        assert_eq!(volume.volume.previous_window, 25_920_000);
+        assert_eq!(volume.volume.current_window, 11);
+        assert_eq!(volume.rolling_volume(START + UserVolume::WINDOW - 30), 25_920_011);
+        assert_eq!(volume.rolling_volume(START + UserVolume::WINDOW + UserVolume::WINDOW / 2), 12_960_011);
+    }
+
+    #[test]
+    fn skipping_several_windows_drops_the_previous_one() {
+        let mut registry = registry();
+        let token_0 = registry.token_0;
+        registry.record_volume(&token_0, 1_000, 500, START);
+        registry.record_volume(&token_0, 10, 5, START + 3 * WINDOW + 7);
+
+        assert_eq!(registry.volume_window_start, START + 3 * WINDOW);
+        assert_eq!(registry.volume_0.previous_window, 0);
+        assert_eq!(registry.rolling_volume(START + 3 * WINDOW + 7), (10, 5));
+    }
+
+    #[test]
+    fn clock_behind_the_window_start_neither_rolls_nor_inflates() {
+        let mut registry = registry();
+        let token_0 = registry.token_0;
+        registry.record_volume(&token_0, 8_640_000, 500, START);
+        registry.record_volume(&token_0, 10, 5, START + WINDOW);
+        registry.record_volume(&token_0, 1, 1, START + WINDOW - 30);
+
+        assert_eq!(registry.volume_window_start, START + WINDOW);
+        assert_eq!(registry.volume_0.current_window, 11);
+        assert_eq!(registry.rolling_volume(START + WINDOW - 30), (8_640_011, 506));
+    }
+
+    #[test]
+    fn volume_saturates_instead_of_overflowing() {
+        let mut registry = registry();
+        let token_0 = registry.token_0;
+        registry.record_volume(&token_0, u64::MAX, u64::MAX, START);
+        registry.record_volume(&token_0, u64::MAX, 1, START + 1);
+
+        assert_eq!(registry.volume_0.current_window, u64::MAX);
+        assert_eq!(registry.volume_0.lifetime, 2 * u64::MAX as u128);
+        assert_eq!(registry.rolling_volume(START + 2), (u64::MAX, u64::MAX));
+
+        registry.record_volume(&token_0, u64::MAX, u64::MAX, START + WINDOW);
+        assert_eq!(registry.rolling_volume(START + WINDOW + 1), (u64::MAX, u64::MAX));
+    }
+
+    fn record_many(counter: &mut RateCounter, now: i64, window: u32, limit: u32, times: u32) -> u32 {
+        (0..times).filter(|_| counter.record(now, window, limit)).count() as u32
+    }
+
+    #[test]
+    fn rate_counter_counts_up_to_its_limit() {
+        let mut counter = RateCounter { window_start: START, ..Default::default() };
+
+        assert_eq!(record_many(&mut counter, START, 60, 5, 5), 5);
+        assert!(!counter.record(START + 59, 60, 5));
+        assert_eq!(counter.current, 5);
+        assert!(!counter.record(START, 60, 0));
+    }
+
+    #[test]
+    fn rate_counter_previous_window_expires_gradually() {
+        let mut counter = RateCounter { window_start: START, ..Default::default() };
+        assert_eq!(record_many(&mut counter, START, 60, 4, 4), 4);
+
+        // Half the previous window's 4 still count 30s into the next one
+        assert_eq!(record_many(&mut counter, START + 90, 60, 4, 4), 2);
+        assert_eq!(counter.window_start, START + 60);
+        assert_eq!(counter.previous, 4);
+        assert_eq!(counter.current, 2);
+    }
+
+    #[test]
+    fn rate_counter_resets_after_two_windows() {
+        let mut counter = RateCounter { window_start: START, ..Default::default() };
+        assert_eq!(record_many(&mut counter, START, 60, 4, 4), 4);
+
+        assert_eq!(record_many(&mut counter, START + 125, 60, 4, 5), 4);
+        assert_eq!(counter.window_start, START + 120);
+        assert_eq!(counter.previous, 0);
+    }
+
+    #[test]
+    fn rate_counter_treats_a_zero_window_as_one_second() {
+        let mut counter = RateCounter { window_start: START, ..Default::default() };
+        assert!(counter.record(START, 0, 1));
+        assert!(!counter.record(START, 0, 1));
+        assert!(counter.record(START + 2, 0, 1));
+    }
+
+    #[test]
+    fn rate_counter_clock_behind_the_window_start_keeps_the_limit() {
+        let mut counter = RateCounter { window_start: START, ..Default::default() };
+        assert_eq!(record_many(&mut counter, START, 60, 6, 6), 6);
+        assert_eq!(record_many(&mut counter, START + 60, 60, 10, 2), 2);
+
+        // 10s behind: the previous window's 6 count in full, and no more
+        assert_eq!(record_many(&mut counter, START + 50, 60, 10, 4), 2);
+        assert_eq!(counter.window_start, START + 60);
+    }
+
+    fn order(sequence: u64) -> OrderState {
+        OrderState::new(
+            Pubkey::new_unique(),
+            sequence,
+            NewOrder {
+                user: Pubkey::new_unique(),
+                amount_in: 1_000,
+                min_amount_out: 900,
+                is_base_input: true,
+                input_mint: Pubkey::new_unique(),
+            },
+            FeeQuote { protocol_fee_bps: 5, relayer_fee_bps: 10 },
+            START,
+        )
+    }
+
+    fn pool_fifo(head: u64, current_sequence: u64) -> PoolFifoState {
+        PoolFifoState {
+            pool_id: Pubkey::new_unique(),
+            current_sequence,
+            head,
+            order_stream_hash: [0; 32],
+            checkpoint_root: [0; 32],
+            checkpoint_sequence: 0,
+            immediate_swaps: 0,
+            event_sequence: 0,
+            head_moved_at: START,
+        }
+    }
+
+    /// Settle a fill as `execute_order` does, moving the head only when the
+    /// order closes
+    fn execute(fifo: &mut PoolFifoState, order: &mut OrderState, amount_in: u64, amount_out: u64, now: i64) -> bool {
+        assert!(fifo.may_execute(order));
+        let closed = order.settle_fill(amount_in, amount_out, now);
+        if closed {
+            fifo.advance(order.sequence, now);
+        }
+        closed
+    }
+
+    #[test]
+    fn partly_filled_order_stays_pending_at_the_head() {
+        let mut fifo = pool_fifo(4, 6);
+        let mut order = order(5);
+
+        assert!(!execute(&mut fifo, &mut order, 400, 380, START + 1));
+        assert_eq!(order.status, OrderStatus::Pending);
+        assert_eq!(order.amount_in_remaining, 600);
+        assert_eq!(order.executed_at, None);
+        assert_eq!(fifo.next_execution_sequence(), 5);
+
+        assert!(execute(&mut fifo, &mut order, 600, 570, START + 2));
+        assert_eq!(order.status, OrderStatus::Executed);
+        assert_eq!(order.amount_out_filled, 950);
+        assert_eq!(order.executed_at, Some(START + 2));
+        assert_eq!(fifo.next_execution_sequence(), 6);
+    }
+
+    #[test]
+    fn immediate_or_cancel_order_closes_after_its_first_fill() {
+        let mut fifo = pool_fifo(4, 5);
+        let mut order = order(5);
+        order.execution_policy = ExecutionPolicy::ImmediateOrCancel;
+
+        assert!(execute(&mut fifo, &mut order, 400, 380, START + 1));
+        assert_eq!(order.status, OrderStatus::Cancelled);
+        assert_eq!(fifo.next_execution_sequence(), 6);
+    }
+
+    #[test]
+    fn head_only_moves_for_the_order_next_in_line() {
+        let mut fifo = pool_fifo(4, 8);
+        fifo.advance(7, START + 1);
+        assert_eq!(fifo.next_execution_sequence(), 5);
+        assert_eq!(fifo.head_moved_at, START);
+        fifo.advance(5, START + 2);
+        assert_eq!(fifo.next_execution_sequence(), 6);
+        assert_eq!(fifo.head_moved_at, START + 2);
+    }
+
+    #[test]
+    fn never_fillable_head_is_skipped_after_the_grace_period() {
+        let mut fifo = pool_fifo(4, 6);
+        let mut stuck = order(5);
+        stuck.min_amount_out = u64::MAX;
+
+        assert!(!fifo.head_stalled(stuck.submitted_at, START + HEAD_GRACE_PERIOD - 1));
+        assert!(fifo.head_stalled(stuck.submitted_at, START + HEAD_GRACE_PERIOD));
+        fifo.advance(stuck.sequence, START + HEAD_GRACE_PERIOD);
+        assert!(!fifo.may_execute(&stuck));
+
+        let mut next = order(6);
+        assert!(execute(&mut fifo, &mut next, 1_000, 950, START + HEAD_GRACE_PERIOD + 1));
+        assert_eq!(next.status, OrderStatus::Executed);
+        assert_eq!(fifo.next_execution_sequence(), 7);
+    }
+
+    #[test]
+    fn grace_period_runs_from_when_the_order_reached_the_head() {
+        let mut fifo = pool_fifo(4, 5);
+        let mut late = order(5);
+        late.submitted_at = START + 600;
+        assert!(!fifo.head_stalled(late.submitted_at, START + HEAD_GRACE_PERIOD));
+        assert!(fifo.head_stalled(late.submitted_at, START + 600 + HEAD_GRACE_PERIOD));
+
+        fifo.head_moved_at = START + 1_200;
+        assert!(!fifo.head_stalled(late.submitted_at, START + 600 + HEAD_GRACE_PERIOD));
+    }
+
+    #[test]
+    fn conditional_order_waits_its_turn_until_passed_over() {
+        let mut fifo = pool_fifo(4, 7);
+        let mut conditional = order(6);
+        conditional.valid_from = Some(START + 60);
+
+        assert!(!fifo.may_execute(&conditional));
+        fifo.advance(5, START + 1);
+        assert!(fifo.may_execute(&conditional));
+        fifo.advance(6, START + 2);
+        assert!(fifo.may_execute(&conditional));
+
+        let mut unconditional = order(6);
+        unconditional.valid_from = None;
+        assert!(!fifo.may_execute(&unconditional));
+    }
+
+    #[test]
+    fn source_must_hold_the_mint_the_order_sells() {
+        let mut order = order(1);
+        let (user, mint, other_mint) = (order.user, order.input_mint, Pubkey::new_unique());
+        let account = Pubkey::new_unique();
+        assert!(order.accepts_source(&account, &user, &mint));
+        assert!(!order.accepts_source(&account, &user, &other_mint));
+        assert!(!order.accepts_source(&account, &Pubkey::new_unique(), &mint));
+
+        order.source_account = Some(account);
+        assert!(order.accepts_source(&account, &Pubkey::new_unique(), &mint));
+        assert!(!order.accepts_source(&account, &user, &other_mint));
+        assert!(!order.accepts_source(&Pubkey::new_unique(), &user, &mint));
+    }
+
+    fn fee_ledger() -> PoolFeeLedger {
+        PoolFeeLedger {
+            pool_id: Pubkey::new_unique(),
+            token_0: Pubkey::new_unique(),
+            token_1: Pubkey::new_unique(),
+            protocol_0: FeeCounter::default(),
+            protocol_1: FeeCounter::default(),
+            relayer_0: FeeCounter::default(),
+            relayer_1: FeeCounter::default(),
+            insurance_0: FeeCounter::default(),
+            insurance_1: FeeCounter::default(),
+        }
+    }
+
+    #[test]
+    fn fee_ledger_accrual_fails_instead_of_wrapping() {
+        let mut ledger = fee_ledger();
+        ledger.accrue(true, 1_000, 500, 2_000).unwrap();
+        assert_eq!(ledger.protocol_0.accrued, 800);
+        assert_eq!(ledger.insurance_0.accrued, 200);
+        assert_eq!(ledger.relayer_0.accrued, 500);
+
+        ledger.relayer_1.accrued = u64::MAX;
+        assert!(ledger.accrue(false, 0, 1, 0).is_err());
+        assert_eq!(ledger.relayer_1.accrued, u64::MAX);
+    }
+
+    #[test]
+    fn fee_counter_pays_out_without_wrapping() {
+        let mut counter = FeeCounter { accrued: 10, paid_out: 0 };
+        counter.pay_out(4).unwrap();
+        assert_eq!(counter.outstanding(), 6);
+
+        counter.paid_out = 20;
+        assert_eq!(counter.outstanding(), 0);
+        counter.paid_out = u64::MAX;
+        assert!(counter.pay_out(1).is_err());
+    }
+
+    #[test]
+    fn max_fees_does_not_truncate() {
+        let quote = FeeQuote { protocol_fee_bps: 5, relayer_fee_bps: 10 };
+        assert_eq!(quote.max_fees(1_000_000), Some(1_500));
+
+        let whole = FeeQuote { protocol_fee_bps: 10_000, relayer_fee_bps: 10_000 };
+        assert_eq!(whole.max_fees(u64::MAX / 2), Some(u64::MAX - 1));
+        assert_eq!(whole.max_fees(u64::MAX), None);
+    }
+
+    #[test]
+    fn limit_price_is_met_at_the_limit_and_not_one_unit_under() {
+        let limit = LimitPrice { numerator: 3, denominator: 2 };
+        assert!(limit.met_by(1_000, 1_500));
+        assert!(limit.met_by(1_000, 1_501));
+        assert!(!limit.met_by(1_000, 1_499));
+
+        let wide = LimitPrice { numerator: u64::MAX, denominator: u64::MAX - 1 };
+        assert!(wide.met_by(u64::MAX - 1, u64::MAX));
+        assert!(!wide.met_by(u64::MAX - 1, u64::MAX - 1));
+        assert!(wide.met_by(0, 0));
+        assert!(!wide.met_by(u64::MAX, u64::MAX));
+    }
+
+    #[test]
+    fn sell_stop_follows_the_high_and_fires_at_the_trail_distance() {
+        let mut stop = TrailingStop::new(Pubkey::new_unique(), TrailDirection::Sell, 500, 10_000);
+        assert_eq!(stop.stop_price, 9_500);
+
+        assert!(!stop.observe(9_900));
+        assert!(!stop.observe(10_000));
+        assert_eq!((stop.reference_price, stop.stop_price), (10_000, 9_500));
+
+        assert!(stop.observe(12_000));
+        assert_eq!((stop.reference_price, stop.stop_price), (12_000, 11_400));
+        assert!(!stop.triggered(11_401));
+        assert!(stop.triggered(11_400));
+        assert!(stop.triggered(11_399));
+    }
+
+    #[test]
+    fn buy_stop_follows_the_low_and_fires_at_the_trail_distance() {
+        let mut stop = TrailingStop::new(Pubkey::new_unique(), TrailDirection::Buy, 500, 10_000);
+        assert_eq!(stop.stop_price, 10_500);
+
+        assert!(!stop.observe(10_100));
+        assert!(stop.observe(8_000));
+        assert_eq!((stop.reference_price, stop.stop_price), (8_000, 8_400));
+        assert!(!stop.observe(9_000));
+        assert_eq!(stop.stop_price, 8_400);
+
+        assert!(!stop.triggered(8_399));
+        assert!(stop.triggered(8_400));
+        assert!(stop.triggered(8_401));
+    }
+
+    #[test]
+    fn stop_saturates_at_the_ends_of_the_price_range() {
+        let oracle = Pubkey::new_unique();
+        let stop = TrailingStop::new(oracle, TrailDirection::Buy, 500, i64::MAX);
+        assert_eq!(stop.stop_price, i64::MAX);
+        assert!(stop.triggered(i64::MAX));
+        let stop = TrailingStop::new(oracle, TrailDirection::Buy, 10_000, i64::MIN);
+        assert_eq!(stop.stop_price, i64::MIN);
+
+        let mut stop = TrailingStop::new(oracle, TrailDirection::Sell, 10_000, i64::MIN);
+        assert_eq!(stop.stop_price, 0);
+        assert!(stop.observe(i64::MAX));
+        assert_eq!((stop.reference_price, stop.stop_price), (i64::MAX, 0));
+
+        let mut stop = TrailingStop::new(oracle, TrailDirection::Buy, 500, i64::MAX - 1);
+        assert!(stop.observe(i64::MAX - 2));
+        assert_eq!(stop.stop_price, i64::MAX);
+    }
+
+    const SCALE: u128 = crate::consts::PRICE_SCALE as u128;
+
+    #[test]
+    fn cumulative_price_credits_each_price_for_the_time_it_held() {
+        let mut accumulator = PriceAccumulator::default();
+        assert_eq!(accumulator.cumulative_at(START), 0);
+
+        accumulator.observe(1_000, 2_000, START);
+        assert_eq!(accumulator.cumulative, 0);
+        assert_eq!(accumulator.cumulative_at(START + 10), 20 * SCALE);
+
+        accumulator.observe(1_000, 1_000, START + 10);
+        assert_eq!(accumulator.cumulative, 20 * SCALE);
+        let end = accumulator.cumulative_at(START + 40);
+        assert_eq!(end, 50 * SCALE);
+        assert_eq!(PriceAccumulator::twap(0, START, end, START + 40), Some((SCALE * 5 / 4) as u64));
+
+        accumulator.observe(0, 1_000, START + 50);
+        assert_eq!(accumulator.last_observed_at, START + 10);
+    }
+
+    #[test]
+    fn lagging_or_zero_clock_does_not_move_the_cumulative_back() {
+        let mut accumulator = PriceAccumulator::default();
+        accumulator.observe(1_000, 3_000, START);
+        assert_eq!(accumulator.cumulative_at(START - 60), 0);
+        assert_eq!(accumulator.cumulative_at(0), 0);
+
+        accumulator.observe(1_000, 3_000, START + 10);
+        accumulator.observe(1_000, 1_000, START + 5);
+        assert_eq!(accumulator.cumulative, 30 * SCALE);
+        assert_eq!(accumulator.cumulative_at(0), 30 * SCALE);
+
+        assert_eq!(PriceAccumulator::twap(0, START, 30 * SCALE, START), None);
+        assert_eq!(PriceAccumulator::twap(0, START, 30 * SCALE, START - 1), None);
+    }
+
+    #[test]
+    fn twap_order_sells_equal_slices_and_the_remainder_last() {
+        let mut order = order(1);
+        order.twap = Some(TwapSchedule::new(3, 60));
+
+        for (at, remaining) in [(START, 667), (START + 60, 334)] {
+            assert!(order.twap.unwrap().slice_due(at));
+            assert_eq!(order.next_fill(None), (333, 300));
+            assert!(!order.record_fill(333, 320, at));
+            assert_eq!(order.amount_in_remaining, remaining);
+        }
+        let twap = order.twap.unwrap();
+        assert!(!twap.slice_due(START + 119));
+        assert!(twap.slice_due(START + 120));
+        assert!(twap.is_last_slice());
+
+        assert_eq!(order.next_fill(None), (334, 301));
+        assert!(order.record_fill(334, 321, START + 120));
+        assert_eq!(order.amount_in_remaining, 0);
+        assert_eq!(order.twap.unwrap().slices_executed, 3);
+    }
+
+    #[test]
+    fn zero_amount_order_prices_its_fill_without_dividing_by_zero() {
+        let mut order = order(1);
+        order.amount_in = 0;
+        order.amount_in_remaining = 0;
+        assert_eq!(order.next_fill(None), (0, 0));
+        assert_eq!(order.next_fill(Some(10)), (0, 0));
+
+        order.twap = Some(TwapSchedule::new(3, 60));
+        assert_eq!(order.next_fill(None), (0, 0));
+    }
+
+    #[test]
+    fn permit_spends_its_exact_allowance_but_not_one_more() {
+        let mut permit = Permit {
+            user: Pubkey::new_unique(),
+            token_account: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            allowance: 1_000,
+            expires_at: START + WINDOW,
+            created_at: START,
+        };
+        assert!(!permit.covers(1_001, START));
+        assert!(!permit.spend(1_001));
+        assert_eq!(permit.allowance, 1_000);
+
+        assert!(permit.covers(1_000, START));
+        assert!(permit.spend(1_000));
+        assert_eq!(permit.allowance, 0);
+        assert!(!permit.spend(1));
+        assert!(permit.spend(0));
+        assert_eq!(permit.allowance, 0);
+    }
+
+    #[test]
+    fn escrowed_order_is_modifiable_and_its_deposit_follows_the_amounts() {
+        let mut escrowed = order(1);
+        escrowed.source_account = Some(Pubkey::new_unique());
+        escrowed.escrow_refund = Some(Pubkey::new_unique());
+        assert!(escrowed.is_modifiable());
+        let deposit = crate::order_vault::deposit(&escrowed).unwrap();
+        assert_eq!(deposit, 1_000 + 1);
+
+        // Tightening in place keeps the deposit
+        assert!(escrowed.is_tightened_by(1_000, 950));
+        escrowed.min_amount_out = 950;
+        assert_eq!(crate::order_vault::deposit(&escrowed).unwrap(), deposit);
+
+        // A re-queue's replacement needs more, topped up from the refund
+        // account, or less, refunded to it
+        let mut replacement = escrowed.clone();
+        assert!(!escrowed.is_tightened_by(2_000, 1_900));
+        replacement.amount_in = 2_000;
+        replacement.amount_in_remaining = 2_000;
+        assert_eq!(crate::order_vault::deposit(&replacement).unwrap(), 2_000 + 1 + 2);
+        replacement.amount_in = 500;
+        replacement.amount_in_remaining = 500;
+        assert_eq!(crate::order_vault::deposit(&replacement).unwrap(), 500);
+
+        // Spending from an account the order doesn't escrow still can't change
+        escrowed.escrow_refund = None;
+        assert!(!escrowed.is_modifiable());
+    }
+
+    #[test]
+    fn escrowed_order_is_batchable_but_not_one_with_a_shared_escrow() {
+        let mut escrowed = order(1);
+        escrowed.source_account = Some(Pubkey::new_unique());
+        escrowed.escrow_refund = Some(Pubkey::new_unique());
+        assert!(escrowed.is_batchable());
+
+        let mut shared = order(2);
+        shared.shared_escrow = Some(Pubkey::new_unique());
+        assert!(!shared.is_batchable());
+    }
+
+    /// A token bridge transfer payload of `amount` to `to`, with `fee`
+    fn token_transfer(payload_id: u8, amount: u64, to: &Pubkey, fee: u64) -> Vec<u8> {
+        let mut payload = vec![payload_id];
+        payload.extend_from_slice(&[0; 24]);
+        payload.extend_from_slice(&amount.to_be_bytes());
+        payload.extend_from_slice(&[9; 32]);
+        payload.extend_from_slice(&2u16.to_be_bytes());
+        payload.extend_from_slice(to.as_ref());
+        payload.extend_from_slice(&crate::wormhole::SOLANA_CHAIN_ID.to_be_bytes());
+        payload.extend_from_slice(&[0; 24]);
+        payload.extend_from_slice(&fee.to_be_bytes());
+        payload
+    }
+
+    #[test]
+    fn bridged_transfer_counts_what_custody_received_net_of_the_fee() {
+        use crate::wormhole::TokenTransfer;
+
+        let custody = Pubkey::new_unique();
+        let transfer = TokenTransfer::parse(&token_transfer(TokenTransfer::PAYLOAD_ID, 1_000, &custody, 10)).unwrap();
+        assert_eq!((transfer.amount, transfer.to, transfer.to_chain), (990, custody, 1));
+        // Amounts are normalized to 8 decimals
+        assert_eq!(transfer.received(6), Some(990));
+        assert_eq!(transfer.received(9), Some(9_900));
+        assert_eq!(TokenTransfer { amount: u64::MAX, ..transfer }.received(9), None);
+
+        // A transfer with payload carries the sender where the fee would be
+        let with_payload = TokenTransfer::parse(&token_transfer(TokenTransfer::PAYLOAD_ID_WITH_PAYLOAD, 1_000, &custody, 10)).unwrap();
+        assert_eq!(with_payload.amount, 1_000);
+
+        assert!(TokenTransfer::parse(&token_transfer(TokenTransfer::PAYLOAD_ID, 1_000, &custody, 1_001)).is_err());
+        assert!(TokenTransfer::parse(&token_transfer(2, 1_000, &custody, 0)).is_err());
+        let mut too_large = token_transfer(TokenTransfer::PAYLOAD_ID, 1_000, &custody, 0);
+        too_large[1] = 1;
+        assert!(TokenTransfer::parse(&too_large).is_err());
+    }
+
+    #[test]
+    fn only_the_pools_own_head_may_be_skipped() {
+        let fifo = pool_fifo(4, 6);
+        let mut cancelled = order(5);
+        cancelled.pool_id = fifo.pool_id;
+        cancelled.status = OrderStatus::Cancelled;
+        assert!(fifo.may_skip(&cancelled, START));
+
+        let mut elsewhere = cancelled.clone();
+        elsewhere.pool_id = Pubkey::new_unique();
+        assert!(!fifo.may_skip(&elsewhere, START));
+        assert_ne!(OrderState::address(&elsewhere.pool_id, 5), OrderState::address(&fifo.pool_id, 5));
+
+        let mut behind = cancelled.clone();
+        behind.sequence = 6;
+        assert!(!fifo.may_skip(&behind, START));
+    }
+
+    #[test]
+    fn pending_head_is_skippable_only_once_stalled() {
+        let fifo = pool_fifo(4, 6);
+        let mut pending = order(5);
+        pending.pool_id = fifo.pool_id;
+        assert!(!fifo.may_skip(&pending, START + HEAD_GRACE_PERIOD - 1));
+        assert!(fifo.may_skip(&pending, START + HEAD_GRACE_PERIOD));
+
+        let mut conditional = pending.clone();
+        conditional.valid_from = Some(START + 60);
+        assert!(fifo.may_skip(&conditional, START));
+    }
+
+    #[test]
+    fn enqueued_order_takes_the_upcoming_sequence_and_joins_the_stream() {
+        let mut fifo = pool_fifo(4, 9);
+        let upcoming = fifo.upcoming_order();
+        let fifo_state = FifoState {
+            current_sequence: 0,
+            admin: Pubkey::default(),
+            emergency_pause: false,
+            archive_tree: Pubkey::default(),
+            archived_orders: 0,
+            checkpoint_interval: 10,
+            protocol_fee_bps: 5,
+            relayer_fee_bps: 10,
+            risk_authority: Pubkey::default(),
+            max_swaps_per_window: 0,
+            swap_rate_window: 0,
+            insurance_share_bps: 0,
+            cp_swap_program: Pubkey::default(),
+            permissionless_execution: false,
+        };
+        let user = Pubkey::new_unique();
+        let order = fifo.enqueue(
+            NewOrder { user, amount_in: 500, min_amount_out: 450, is_base_input: false, input_mint: Pubkey::new_unique() },
+            &fifo_state,
+            &registry(),
+            START + 5,
+        );
+
+        assert_eq!(order.sequence, 10);
+        assert_eq!(fifo.current_sequence, 10);
+        assert_eq!(OrderState::address(&fifo.pool_id, order.sequence), upcoming);
+        assert_eq!(order.pool_id, fifo.pool_id);
+        assert_eq!((order.status, order.submitted_at, order.amount_in_remaining), (OrderStatus::Pending, START + 5, 500));
+        assert_eq!(order.trace_id, default_trace_id(&fifo.pool_id, 10));
+        assert_eq!((order.fee_quote.protocol_fee_bps, order.fee_quote.relayer_fee_bps), (5, 10));
+
+        let hash = order_hash(10, &user, &fifo.pool_id, 500, false);
+        assert_eq!(fifo.order_stream_hash, keccak::hashv(&[&[0; 32], &hash]).to_bytes());
+        assert_eq!((fifo.checkpoint_sequence, fifo.checkpoint_root), (10, fifo.order_stream_hash));
+    }
+
+    #[test]
+    fn swap_balances_moving_the_wrong_way_are_rejected() {
+        use crate::instructions::execute_order::{swap_deltas, within_limit};
+
+        assert_eq!(swap_deltas((1_000, 400), (50, 650)).unwrap(), (600, 600));
+        assert!(swap_deltas((1_000, 1_001), (50, 650)).is_err());
+        assert!(swap_deltas((1_000, 400), (50, 49)).is_err());
+
+        assert!(within_limit(true, 600, 600, 600) && !within_limit(true, 600, 599, 600));
+        assert!(within_limit(false, 600, 600, 600) && !within_limit(false, 601, 600, 600));
+    }
+
+    #[test]
+    fn delegation_leftover_is_capped_at_the_source_balance() {
+        use crate::instructions::execute_order::delegation_leftover;
+        use anchor_lang::solana_program::program_option::COption;
+        use anchor_spl::token::spl_token::state::{Account, AccountState};
+
+        let escrow = Pubkey::new_unique();
+        let mut source = Account {
+            mint: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            amount: 1_000,
+            delegate: COption::Some(escrow),
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 400,
+            close_authority: COption::None,
+        };
+        assert_eq!(delegation_leftover(&source, &escrow), 400);
+
+        // Spent elsewhere since the approval
+        source.amount = 150;
+        assert_eq!(delegation_leftover(&source, &escrow), 150);
+        source.amount = 0;
+        assert_eq!(delegation_leftover(&source, &escrow), 0);
+
+        source.amount = 1_000;
+        assert_eq!(delegation_leftover(&source, &Pubkey::new_unique()), 0);
+    }
+
+    #[test]
+    fn only_orders_spending_their_own_funds_are_modifiable() {
+        let wallet = order(1);
+        assert!(wallet.is_modifiable());
+
+        let mut template = order(2);
+        template.source_account = Some(Pubkey::new_unique());
+        assert!(!template.is_modifiable());
+
+        let mut shared = order(3);
+        shared.source_account = Some(Pubkey::new_unique());
+        shared.shared_escrow = Some(Pubkey::new_unique());
+        shared.escrow_refund = Some(Pubkey::new_unique());
+        assert!(!shared.is_modifiable());
+
+        let mut delegated = order(4);
+        delegated.delegated_input = true;
+        assert!(!delegated.is_modifiable());
+
+        let mut permitted = order(5);
+        permitted.permit = Some(Pubkey::new_unique());
+        assert!(!permitted.is_modifiable());
+    }
+
+    #[test]
+    fn only_a_tighter_limit_at_the_same_amount_modifies_in_place() {
+        let exact_input = order(1);
+        assert!(exact_input.is_tightened_by(1_000, 900));
+        assert!(exact_input.is_tightened_by(1_000, 901));
+        assert!(!exact_input.is_tightened_by(1_000, 899));
+        assert!(!exact_input.is_tightened_by(999, 950));
+
+        // For an exact output order the limit is the most it may spend
+        let mut exact_output = order(2);
+        exact_output.is_base_input = false;
+        assert!(exact_output.is_tightened_by(1_000, 899));
+        assert!(!exact_output.is_tightened_by(1_000, 901));
+    }
+
+    #[test]
+    fn partly_filled_order_cannot_be_requeued() {
+        let mut filled = order(1);
+        assert!(filled.is_unfilled());
+        filled.settle_fill(400, 380, START + 1);
+        assert!(!filled.is_unfilled());
+        // Its limit can still be tightened in place
+        assert!(filled.is_modifiable() && filled.is_tightened_by(1_000, 950));
+    }
+
+    #[test]
+    fn batch_accounts_are_sliced_per_order_and_bounded() {
+        use crate::instructions::execute_orders_batch::{batch_executions, BATCH_EXECUTION_ACCOUNTS, MAX_BATCH_EXECUTIONS};
+
+        // A trailing account, like the mock clock, is left alone
+        let remaining: Vec<usize> = (0..2 * BATCH_EXECUTION_ACCOUNTS + 1).collect();
+        let executions: Vec<&[usize]> = batch_executions(&remaining, 2).unwrap().collect();
+        assert_eq!(executions.len(), 2);
+        assert_eq!(executions[0], &remaining[..BATCH_EXECUTION_ACCOUNTS]);
+        assert_eq!(executions[1][0], BATCH_EXECUTION_ACCOUNTS);
+        assert_eq!(executions[1].len(), BATCH_EXECUTION_ACCOUNTS);
+
+        assert!(batch_executions(&remaining, 3).is_err());
+        assert!(batch_executions(&remaining, 0).is_err());
+        let enough: Vec<usize> = (0..(MAX_BATCH_EXECUTIONS + 1) * BATCH_EXECUTION_ACCOUNTS).collect();
+        assert!(batch_executions(&enough, MAX_BATCH_EXECUTIONS as u8).is_ok());
+        assert!(batch_executions(&enough, MAX_BATCH_EXECUTIONS as u8 + 1).is_err());
+    }
+
+    #[test]
+    fn dca_tick_is_cut_short_only_when_the_budget_cannot_cover_its_fees() {
+        let mut schedule = DcaSchedule {
+            user: Pubkey::new_unique(),
+            schedule_id: 1,
+            pool_id: Pubkey::new_unique(),
+            input_mint: Pubkey::new_unique(),
+            destination: Pubkey::new_unique(),
+            amount_per_tick: 1_000,
+            min_amount_out_per_tick: 900,
+            interval: 60,
+            budget_remaining: 1_001,
+            ticks_executed: 0,
+            amount_out_total: 0,
+            next_execution_time: START,
+        };
+        // 15 bps of a full tick is 1
+        assert_eq!(schedule.next_tick(15), (1_000, 900));
+        assert_eq!(schedule.next_tick(0), (1_000, 900));
+
+        // One short of a tick and its fees: what is left after the fees on it
+        schedule.budget_remaining = 1_000;
+        assert_eq!(schedule.next_tick(15), (999, 900));
+        assert_eq!(schedule.next_tick(0), (1_000, 900));
+
+        // The minimum out is scaled down, rounding up
+        schedule.budget_remaining = 333;
+        assert_eq!(schedule.next_tick(15), (333, 300));
+
+        schedule.budget_remaining = 0;
+        assert!(!schedule.is_due(START));
+        assert_eq!(schedule.next_tick(15), (0, 0));
+    }
+
+    /// A cross-chain order payload, as the emitter encodes it
+    fn cross_chain_order(expires_at: i64, transfer_sequence: u64) -> (Vec<u8>, [Pubkey; 4]) {
+        let keys = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut payload = vec![crate::wormhole::CrossChainOrder::PAYLOAD_ID];
+        payload.extend_from_slice(keys[0].as_ref());
+        payload.extend_from_slice(keys[1].as_ref());
+        payload.extend_from_slice(keys[2].as_ref());
+        payload.extend_from_slice(&1_000u64.to_be_bytes());
+        payload.extend_from_slice(&900u64.to_be_bytes());
+        payload.push(1);
+        payload.extend_from_slice(keys[3].as_ref());
+        payload.extend_from_slice(&expires_at.to_be_bytes());
+        payload.extend_from_slice(&transfer_sequence.to_be_bytes());
+        (payload, keys)
+    }
+
+    #[test]
+    fn cross_chain_order_round_trips_and_rejects_malformed_payloads() {
+        use crate::wormhole::CrossChainOrder;
+
+        let (payload, [pool_id, user, mint, refund_account]) = cross_chain_order(START, 42);
+        assert_eq!(payload.len(), CrossChainOrder::LEN);
+        let order = CrossChainOrder::parse(&payload).unwrap();
+        assert_eq!((order.pool_id, order.user, order.mint, order.refund_account), (pool_id, user, mint, refund_account));
+        assert_eq!((order.amount_in, order.min_amount_out, order.is_base_input), (1_000, 900, true));
+        assert_eq!((order.expires_at, order.transfer_sequence), (START, 42));
+        assert_eq!(CrossChainOrder::parse(&cross_chain_order(-1, 0).0).unwrap().expires_at, -1);
+
+        assert!(CrossChainOrder::parse(&[]).is_err());
+        assert!(CrossChainOrder::parse(&payload[..CrossChainOrder::LEN - 1]).is_err());
+        let mut trailing = payload.clone();
+        trailing.push(0);
+        assert!(CrossChainOrder::parse(&trailing).is_err());
+        let mut wrong_id = payload;
+        wrong_id[0] = 3;
+        assert!(CrossChainOrder::parse(&wrong_id).is_err());
+    }
+
+    #[test]
+    fn truncated_token_transfer_is_rejected() {
+        use crate::wormhole::TokenTransfer;
+
+        let custody = Pubkey::new_unique();
+        let payload = token_transfer(TokenTransfer::PAYLOAD_ID, 1_000, &custody, 0);
+        assert!(TokenTransfer::parse(&payload).is_ok());
+        assert!(TokenTransfer::parse(&payload[..payload.len() - 1]).is_err());
+        assert!(TokenTransfer::parse(&[]).is_err());
+        // A transfer with payload carries its payload after the fixed fields
+        let mut with_payload = token_transfer(TokenTransfer::PAYLOAD_ID_WITH_PAYLOAD, 1_000, &custody, 0);
+        with_payload.extend_from_slice(b"order");
+        assert_eq!(TokenTransfer::parse(&with_payload).unwrap().to, custody);
+
+        // Fewer decimals than the bridge's aren't scaled
+        let transfer = TokenTransfer::parse(&payload).unwrap();
+        assert_eq!(transfer.received(0), Some(1_000));
+        assert_eq!(transfer.received(8), Some(1_000));
+    }
+
+    #[test]
+    fn posted_vaa_round_trips_and_rejects_malformed_accounts() {
+        use crate::wormhole::PostedVaa;
+
+        let mut data = b"vaa\x01".to_vec();
+        data.push(1);
+        data.extend_from_slice(&7u32.to_le_bytes());
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(&8u32.to_le_bytes());
+        data.extend_from_slice(&9u32.to_le_bytes());
+        data.extend_from_slice(&10u64.to_le_bytes());
+        data.extend_from_slice(&2u16.to_le_bytes());
+        data.extend_from_slice(&[5; 32]);
+        data.extend_from_slice(&3u32.to_le_bytes());
+        data.extend_from_slice(&[1, 2, 3]);
+
+        let vaa = PostedVaa::parse(&data).unwrap();
+        assert_eq!((vaa.consistency_level, vaa.vaa_time, vaa.nonce, vaa.sequence), (1, 7, 9, 10));
+        assert_eq!((vaa.emitter_chain, vaa.emitter_address, vaa.payload), (2, [5; 32], vec![1, 2, 3]));
+
+        assert!(PostedVaa::parse(&data[..data.len() - 1]).is_err());
+        assert!(PostedVaa::parse(&data[..4]).is_err());
+        let mut wrong_magic = data;
+        wrong_magic[3] = 2;
+        assert!(PostedVaa::parse(&wrong_magic).is_err());
+    }
+
+    /// Data of an ed25519 program instruction verifying one signature by
+    /// `signer` over `message`, laid out as the ed25519 program's SDK does
+    fn ed25519_data(signer: &Pubkey, message: &[u8]) -> Vec<u8> {
+        let (public_key_offset, signature_offset) = (16u16, 48u16);
+        let message_offset = signature_offset + 64;
+        let mut data = vec![1, 0];
+        for field in [
+            signature_offset,
+            u16::MAX,
+            public_key_offset,
+            u16::MAX,
+            message_offset,
+            message.len() as u16,
+            u16::MAX,
+        ] {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&[0; 64]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    #[test]
+    fn intent_signature_must_be_the_users_over_the_intent() {
+        use crate::intent::{check_ed25519_data, OrderIntent};
+
+        let intent = OrderIntent {
+            user: Pubkey::new_unique(),
+            pool_id: Pubkey::new_unique(),
+            amount_in: 1_000,
+            min_amount_out: 900,
+            is_base_input: true,
+            input_mint: Pubkey::new_unique(),
+            recipient: None,
+            nonce: 1,
+            expires_at: START,
+        };
+        let message = intent.message();
+        assert!(check_ed25519_data(&ed25519_data(&intent.user, &message), &intent.user, &message).is_ok());
+
+        let other_signer = ed25519_data(&Pubkey::new_unique(), &message);
+        assert!(check_ed25519_data(&other_signer, &intent.user, &message).is_err());
+
+        let other_intent = OrderIntent { min_amount_out: 1, ..intent.clone() }.message();
+        let other_message = ed25519_data(&intent.user, &other_intent);
+        assert!(check_ed25519_data(&other_message, &intent.user, &message).is_err());
+    }
+
+    #[test]
+    fn intent_signature_data_must_be_one_signature_inside_the_instruction() {
+        use crate::intent::check_ed25519_data;
+
+        let user = Pubkey::new_unique();
+        let message = b"intent".to_vec();
+        let data = ed25519_data(&user, &message);
+
+        let mut two_signatures = data.clone();
+        two_signatures[0] = 2;
+        assert!(check_ed25519_data(&two_signatures, &user, &message).is_err());
+
+        // The signature, key and message must come from this instruction
+        for index_at in [4, 8, 14] {
+            let mut elsewhere = data.clone();
+            elsewhere[index_at..index_at + 2].copy_from_slice(&0u16.to_le_bytes());
+            assert!(check_ed25519_data(&elsewhere, &user, &message).is_err());
+        }
+
+        assert!(check_ed25519_data(&data[..data.len() - 1], &user, &message).is_err());
+        assert!(check_ed25519_data(&data[..15], &user, &message).is_err());
+        assert!(check_ed25519_data(&[], &user, &message).is_err());
+    }
+}
@@ -0,0 +1,239 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::Instruction, sysvar};
+use anchor_lang::system_program;
+use crate::errors::ContinuumError;
+use crate::state::ExecutionReceipt;
+
+pub use crate::cluster::{TOKEN_BRIDGE_PROGRAM_ID, WORMHOLE_PROGRAM_ID};
+
+/// Wormhole chain ID of Solana
+pub const SOLANA_CHAIN_ID: u16 = 1;
+
+/// Decimals the token bridge normalizes amounts to
+const BRIDGE_DECIMALS: u8 = 8;
+
+/// Core bridge instruction index of `post_message`
+const POST_MESSAGE_INSTRUCTION: u8 = 1;
+
+/// Offset of the message fee in the core bridge config account:
+/// guardian_set_index (u32) | last_lamports (u64) | guardian_set_expiration_time (u32) | fee (u64)
+const BRIDGE_FEE_OFFSET: usize = 4 + 8 + 4;
+
+/// Consistency level for published messages (finalized)
+pub const FINALITY_FINALIZED: u8 = 1;
+
+/// Posted VAA accounts start with this magic, followed by the VAA version (1)
+const POSTED_VAA_MAGIC: &[u8] = b"vaa\x01";
+
+/// The fields of a posted VAA account written by the core bridge after it has
+/// verified the guardian signatures
+pub struct PostedVaa {
+    pub consistency_level: u8,
+    pub vaa_time: u32,
+    pub nonce: u32,
+    pub sequence: u64,
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub payload: Vec<u8>,
+}
+
+impl PostedVaa {
+    /// Parse a posted VAA account. The caller must check the account is owned
+    /// by the core bridge.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        require!(data.starts_with(POSTED_VAA_MAGIC), ContinuumError::InvalidVaa);
+        let mut reader = &data[POSTED_VAA_MAGIC.len()..];
+
+        let consistency_level = u8::deserialize(&mut reader)?;
+        let vaa_time = u32::deserialize(&mut reader)?;
+        let _signature_set = Pubkey::deserialize(&mut reader)?;
+        let _submission_time = u32::deserialize(&mut reader)?;
+        let nonce = u32::deserialize(&mut reader)?;
+        let sequence = u64::deserialize(&mut reader)?;
+        let emitter_chain = u16::deserialize(&mut reader)?;
+        let emitter_address = <[u8; 32]>::deserialize(&mut reader)?;
+        let payload = Vec::<u8>::deserialize(&mut reader)?;
+
+        Ok(Self {
+            consistency_level,
+            vaa_time,
+            nonce,
+            sequence,
+            emitter_chain,
+            emitter_address,
+            payload,
+        })
+    }
+}
+
+/// Order parameters sent by a Continuum emitter on another chain.
+/// Encoded big-endian, as is the Wormhole convention:
+/// payload_id (u8 = 1) | pool_id (32) | user (32) | mint (32) | amount_in (u64)
+/// | min_amount_out (u64) | is_base_input (u8) | refund_account (32)
+/// | expires_at (i64) | transfer_sequence (u64)
+pub struct CrossChainOrder {
+    pub pool_id: Pubkey,
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+    pub is_base_input: bool,
+    /// Token account of `mint` refunded what the order leaves unspent, e.g.
+    /// the custody account for the emitter to bridge it back
+    pub refund_account: Pubkey,
+    /// After which anyone may expire the order and refund it
+    pub expires_at: i64,
+    /// Sequence of the token bridge transfer, from the same chain, that
+    /// carries the order's input into custody
+    pub transfer_sequence: u64,
+}
+
+impl CrossChainOrder {
+    pub const PAYLOAD_ID: u8 = 1;
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 8 + 8 + 1 + 32 + 8 + 8;
+
+    pub fn parse(payload: &[u8]) -> Result<Self> {
+        require!(
+            payload.len() == Self::LEN && payload[0] == Self::PAYLOAD_ID,
+            ContinuumError::InvalidVaa
+        );
+
+        let pubkey_at = |offset: usize| Pubkey::try_from(&payload[offset..offset + 32]).unwrap();
+        let u64_at = |offset: usize| u64::from_be_bytes(payload[offset..offset + 8].try_into().unwrap());
+
+        Ok(Self {
+            pool_id: pubkey_at(1),
+            user: pubkey_at(33),
+            mint: pubkey_at(65),
+            amount_in: u64_at(97),
+            min_amount_out: u64_at(105),
+            is_base_input: payload[113] != 0,
+            refund_account: pubkey_at(114),
+            expires_at: u64_at(146) as i64,
+            transfer_sequence: u64_at(154),
+        })
+    }
+}
+
+/// The fields Continuum checks of a token bridge transfer, payload 1, or
+/// transfer with payload, payload 3. Encoded big-endian:
+/// payload_id (u8) | amount (u256) | token_address (32) | token_chain (u16)
+/// | to (32) | to_chain (u16) | fee (u256), a relayer fee for payload 1
+/// and the sender for payload 3 | ...
+pub struct TokenTransfer {
+    /// What `to` received, net of the fee, normalized to 8 decimals
+    pub amount: u64,
+    pub to: Pubkey,
+    pub to_chain: u16,
+}
+
+impl TokenTransfer {
+    pub const PAYLOAD_ID: u8 = 1;
+    pub const PAYLOAD_ID_WITH_PAYLOAD: u8 = 3;
+    const MIN_LEN: usize = 1 + 32 + 32 + 2 + 32 + 2 + 32;
+
+    pub fn parse(payload: &[u8]) -> Result<Self> {
+        require!(payload.len() >= Self::MIN_LEN, ContinuumError::InvalidBridgedTransfer);
+        // Amounts are u256 but normalized ones always fit a u64
+        let u256_at = |offset: usize| {
+            let (high, low) = payload[offset..offset + 32].split_at(24);
+            high.iter().all(|byte| *byte == 0).then(|| u64::from_be_bytes(low.try_into().unwrap()))
+        };
+        let amount = u256_at(1).ok_or(ContinuumError::InvalidBridgedTransfer)?;
+        let fee = match payload[0] {
+            Self::PAYLOAD_ID => u256_at(101).ok_or(ContinuumError::InvalidBridgedTransfer)?,
+            Self::PAYLOAD_ID_WITH_PAYLOAD => 0,
+            _ => return err!(ContinuumError::InvalidBridgedTransfer),
+        };
+
+        Ok(Self {
+            amount: amount.checked_sub(fee).ok_or(ContinuumError::InvalidBridgedTransfer)?,
+            to: Pubkey::try_from(&payload[67..99]).unwrap(),
+            to_chain: u16::from_be_bytes(payload[99..101].try_into().unwrap()),
+        })
+    }
+
+    /// What `to` received in units of its mint, of `decimals`
+    pub fn received(&self, decimals: u8) -> Option<u64> {
+        let scale = 10u64.checked_pow(decimals.saturating_sub(BRIDGE_DECIMALS) as u32)?;
+        self.amount.checked_mul(scale)
+    }
+}
+
+/// Address of the token bridge's claim of the VAA from `emitter_address` on
+/// `emitter_chain` at `sequence`, which exists once the transfer is redeemed
+pub fn claim_address(emitter_address: &[u8; 32], emitter_chain: u16, sequence: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[emitter_address, &emitter_chain.to_be_bytes(), &sequence.to_be_bytes()],
+        &TOKEN_BRIDGE_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Read the message fee from the core bridge config account
+pub fn message_fee(bridge_data: &[u8]) -> Result<u64> {
+    let bytes = bridge_data
+        .get(BRIDGE_FEE_OFFSET..BRIDGE_FEE_OFFSET + 8)
+        .ok_or(ContinuumError::InvalidWormholeAccount)?;
+    Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+/// Accounts of the core bridge `post_message` instruction
+pub struct PostMessageAccounts {
+    pub bridge: Pubkey,
+    pub message: Pubkey,
+    pub emitter: Pubkey,
+    pub sequence: Pubkey,
+    pub payer: Pubkey,
+    pub fee_collector: Pubkey,
+}
+
+/// Build the core bridge `post_message` instruction. Message and emitter sign.
+pub fn post_message_ix(
+    accounts: PostMessageAccounts,
+    nonce: u32,
+    payload: Vec<u8>,
+    consistency_level: u8,
+) -> Instruction {
+    let mut data = vec![POST_MESSAGE_INSTRUCTION];
+    data.extend_from_slice(&nonce.to_le_bytes());
+    data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    data.extend_from_slice(&payload);
+    data.push(consistency_level);
+
+    Instruction {
+        program_id: WORMHOLE_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(accounts.bridge, false),
+            AccountMeta::new(accounts.message, true),
+            AccountMeta::new_readonly(accounts.emitter, true),
+            AccountMeta::new(accounts.sequence, false),
+            AccountMeta::new(accounts.payer, true),
+            AccountMeta::new(accounts.fee_collector, false),
+            AccountMeta::new_readonly(sysvar::clock::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+/// Fill attestation published after an execution. Encoded big-endian:
+/// payload_id (u8 = 2) | pool_id (32) | user (32) | sequence (u64)
+/// | amount_in (u64) | amount_out (u64) | slot (u64)
+pub struct FillAttestation;
+
+impl FillAttestation {
+    pub const PAYLOAD_ID: u8 = 2;
+
+    pub fn encode(receipt: &ExecutionReceipt) -> Vec<u8> {
+        let mut payload = vec![Self::PAYLOAD_ID];
+        payload.extend_from_slice(receipt.pool_id.as_ref());
+        payload.extend_from_slice(receipt.user.as_ref());
+        payload.extend_from_slice(&receipt.sequence.to_be_bytes());
+        payload.extend_from_slice(&receipt.amount_in.to_be_bytes());
+        payload.extend_from_slice(&receipt.amount_out.to_be_bytes());
+        payload.extend_from_slice(&receipt.slot.to_be_bytes());
+        payload
+    }
+}
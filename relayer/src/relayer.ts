@@ -136,7 +136,9 @@ export class Relayer {
           poolId: orderState.poolId,
           userSource: orderState.userSource, // These would need to be retrieved
           userDestination: orderState.userDestination,
+          sourceMint: orderState.sourceMint,
           cpSwapRemainingAccounts: cpSwapAccounts,
+          withRelayerInfo: true,
         };
 
         const signature = await this.client.executeOrder(
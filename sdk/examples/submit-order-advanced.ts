@@ -45,6 +45,7 @@ async function submitBasicOrder() {
   // Swap parameters
   const swapParams: SwapParams = {
     poolId,
+    inputMint: token0,
     amountIn: new BN(1_000_000), // 1 token with 6 decimals
     minAmountOut: new BN(950_000), // 0.95 tokens (5% slippage tolerance)
     isBaseInput: true, // Swapping token0 for token1
@@ -54,14 +55,17 @@ async function submitBasicOrder() {
   
   try {
     console.log('Submitting order...');
-    const { signature, sequence } = await client.submitOrder(user, swapParams);
+    // Escrow the input in the order's vault at submission
+    const { signature, sequence } = await client.submitOrder(user, swapParams, {}, {
+      escrow: { source: userToken0 },
+    });
     
     console.log('\nOrder submitted successfully!');
     console.log('Transaction:', signature);
     console.log('Order sequence:', sequence.toString());
     
     // Monitor order status
-    await monitorOrderStatus(client, user.publicKey, sequence);
+    await monitorOrderStatus(client, poolId, sequence);
     
   } catch (error) {
     console.error('Error submitting order:', error);
@@ -114,6 +118,7 @@ async function submitOrderWithAccountCreation() {
   // Add swap instruction (simplified - use actual SDK)
   const swapParams: SwapParams = {
     poolId,
+    inputMint: token0,
     amountIn: new BN(1_000_000),
     minAmountOut: new BN(950_000),
     isBaseInput: true,
@@ -148,6 +153,7 @@ async function submitOrderThroughRelayer() {
   // Create partially signed transaction
   const swapParams: SwapParams = {
     poolId: new PublicKey('YourPoolIdHere'),
+    inputMint: new PublicKey('Token0MintAddress'),
     amountIn: new BN(1_000_000),
     minAmountOut: new BN(950_000),
     isBaseInput: true,
@@ -209,6 +215,7 @@ async function submitBatchOrders() {
     
     const swapParams: SwapParams = {
       poolId,
+      inputMint: new PublicKey('Token0MintAddress'),
       amountIn: new BN((index + 1) * 1_000_000), // Different amounts
       minAmountOut: new BN((index + 1) * 950_000),
       isBaseInput: true,
@@ -217,7 +224,8 @@ async function submitBatchOrders() {
     };
     
     try {
-      const result = await client.submitOrder(user, swapParams);
+      // Spend from the wallet, which approves the pool authority beforehand
+      const result = await client.submitOrder(user, swapParams, { fromWallet: true });
       console.log(`Order ${index + 1} submitted:`, result.sequence.toString());
       return result;
     } catch (error) {
@@ -263,6 +271,7 @@ async function submitOrderWithCustomParameters() {
   
   const swapParams: SwapParams = {
     poolId: new PublicKey('YourPoolIdHere'),
+    inputMint: new PublicKey('Token0MintAddress'),
     amountIn,
     minAmountOut,
     isBaseInput: true,
@@ -292,7 +301,7 @@ async function submitOrderWithCustomParameters() {
  */
 async function monitorOrderStatus(
   client: ContinuumClient,
-  user: PublicKey,
+  poolId: PublicKey,
   sequence: BN
 ) {
   console.log('\nMonitoring order status...');
@@ -301,7 +310,7 @@ async function monitorOrderStatus(
   const maxAttempts = 30;
   
   while (attempts < maxAttempts) {
-    const orderState = await client.getOrderState(user, sequence);
+    const orderState = await client.getOrderState(poolId, sequence);
     
     if (orderState) {
       console.log(`Status: ${getOrderStatusString(orderState.status)}`);
@@ -60,6 +60,7 @@ async function submitOrderExample() {
   // Swap parameters
   const swapParams: SwapParams = {
     poolId,
+    inputMint: token0,
     amountIn: new BN(1_000_000), // 1 token with 6 decimals
     minAmountOut: new BN(950_000), // 0.95 tokens (5% slippage)
     isBaseInput: true,
@@ -113,7 +114,7 @@ async function submitOrderExample() {
   // Test order status query
   console.log('\n=== Testing Order Query ===');
   try {
-    const orderState = await client.getOrderState(user.publicKey, new BN(0));
+    const orderState = await client.getOrderState(poolId, new BN(1));
     if (orderState) {
       console.log('Order found:', orderState);
     } else {
@@ -136,11 +137,11 @@ async function submitOrderExample() {
  */
 async function monitorOrder(
   client: ContinuumClient,
-  user: PublicKey,
+  poolId: PublicKey,
   sequence: BN
 ) {
   console.log('\n=== Monitoring Order ===');
-  console.log('User:', user.toBase58());
+  console.log('Pool:', poolId.toBase58());
   console.log('Sequence:', sequence.toString());
   
   let attempts = 0;
@@ -148,7 +149,7 @@ async function monitorOrder(
   
   while (attempts < maxAttempts) {
     try {
-      const orderState = await client.getOrderState(user, sequence);
+      const orderState = await client.getOrderState(poolId, sequence);
       
       if (orderState) {
         console.log(`\nOrder Status: ${getStatusString(orderState.status)}`);
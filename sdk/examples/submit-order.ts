@@ -15,6 +15,7 @@ async function submitSwapOrder() {
   // Swap parameters
   const swapParams: SwapParams = {
     poolId: new PublicKey('YOUR_POOL_ID_HERE'),
+    inputMint: new PublicKey('INPUT_MINT_HERE'),
     amountIn: new BN(1000000), // 1 token (assuming 6 decimals)
     minAmountOut: new BN(950000), // Minimum 0.95 tokens out (5% slippage)
     isBaseInput: true,
@@ -24,14 +25,17 @@ async function submitSwapOrder() {
   
   try {
     // Submit the order
-    const { signature, sequence } = await client.submitOrder(user, swapParams);
+    // Escrow the input in the order's vault at submission
+    const { signature, sequence } = await client.submitOrder(user, swapParams, {}, {
+      escrow: { source: swapParams.userSourceToken },
+    });
     
     console.log('Order submitted successfully!');
     console.log('Transaction signature:', signature);
     console.log('Order sequence:', sequence.toString());
     
     // Check order status
-    const orderState = await client.getOrderState(user.publicKey, sequence);
+    const orderState = await client.getOrderState(swapParams.poolId, sequence);
     console.log('Order state:', orderState);
     
   } catch (error) {
@@ -48,6 +52,7 @@ async function createPartiallySignedOrder() {
   
   const swapParams: SwapParams = {
     poolId: new PublicKey('YOUR_POOL_ID_HERE'),
+    inputMint: new PublicKey('INPUT_MINT_HERE'),
     amountIn: new BN(1000000),
     minAmountOut: new BN(950000),
     isBaseInput: true,
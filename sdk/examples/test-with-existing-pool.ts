@@ -169,6 +169,7 @@ async function testWithRealPool() {
   
   const swapParams: SwapParams = {
     poolId,
+    inputMint: token0,
     amountIn: new BN(1000 * 10 ** 6), // 1000 token0
     minAmountOut: new BN(900 * 10 ** 9), // Min 900 token1 (allowing ~10% slippage)
     isBaseInput: true,
@@ -201,7 +202,7 @@ async function testWithRealPool() {
   
   // Test getOrderState (with dummy values)
   try {
-    const orderState = await userClient.getOrderState(user.publicKey, new BN(0));
+    const orderState = await userClient.getOrderState(poolId, new BN(1));
     if (orderState) {
       console.log('Found order state');
     } else {
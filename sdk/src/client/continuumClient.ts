@@ -17,16 +17,18 @@ import {
   createExecuteOrderInstruction,
   createCancelOrderInstruction,
   createInitializeCpSwapPoolInstruction,
+  CancelOrderAccounts,
   ExecuteOrderParams,
   InitializeCpSwapPoolParams
 } from '../instructions';
 import { 
   getFifoStatePDA, 
   getPoolRegistryPDA, 
+  getPoolFifoPDA,
   getOrderPDA,
   getPoolAuthorityPDA 
 } from '../utils';
-import { FifoState, OrderState, CpSwapPoolRegistry, SwapParams } from '../types';
+import { FifoState, PoolFifoState, OrderState, CpSwapPoolRegistry, SwapParams, SubmitOrderParams, SubmitOrderAccounts } from '../types';
 import { CONTINUUM_PROGRAM_ID } from '../constants';
 
 export class ContinuumClient {
@@ -69,23 +71,46 @@ export class ContinuumClient {
     };
   }
 
+  /**
+   * Get a pool's FIFO queue, which sequences its orders
+   */
+  async getPoolFifoState(poolId: PublicKey): Promise<PoolFifoState | null> {
+    const [poolFifoPDA] = getPoolFifoPDA(poolId);
+    const accountInfo = await this.connection.getAccountInfo(poolFifoPDA);
+    
+    if (!accountInfo) return null;
+    
+    // Parse the account data (simplified - use Anchor's decoder in production)
+    const data = accountInfo.data;
+    return {
+      poolId: new PublicKey(data.slice(8, 40)),
+      currentSequence: new BN(data.slice(40, 48), 'le'),
+      head: new BN(data.slice(48, 56), 'le'),
+    };
+  }
+
   /**
    * Submit a swap order
    */
   async submitOrder(
     user: Keypair,
-    params: SwapParams
+    params: SwapParams,
+    orderParams: SubmitOrderParams = {},
+    accounts: SubmitOrderAccounts = {}
   ): Promise<{ signature: TransactionSignature; sequence: BN }> {
-    const fifoState = await this.getFifoState();
-    if (!fifoState) throw new Error('FIFO state not initialized');
+    const poolFifo = await this.getPoolFifoState(params.poolId);
+    if (!poolFifo) throw new Error('Pool FIFO state not initialized');
 
     const ix = await createSubmitOrderInstruction(
       user.publicKey,
       params.poolId,
+      params.inputMint,
       params.amountIn,
       params.minAmountOut,
       params.isBaseInput,
-      fifoState.currentSequence
+      poolFifo.currentSequence,
+      orderParams,
+      accounts
     );
 
     const tx = new Transaction().add(ix);
@@ -93,7 +118,7 @@ export class ContinuumClient {
     
     return {
       signature,
-      sequence: fifoState.currentSequence.add(new BN(1))
+      sequence: poolFifo.currentSequence.add(new BN(1))
     };
   }
 
@@ -114,9 +139,11 @@ export class ContinuumClient {
    */
   async cancelOrder(
     user: Keypair,
-    sequence: BN
+    poolId: PublicKey,
+    sequence: BN,
+    accounts: CancelOrderAccounts = {}
   ): Promise<TransactionSignature> {
-    const ix = createCancelOrderInstruction(user.publicKey, sequence);
+    const ix = createCancelOrderInstruction(user.publicKey, poolId, sequence, accounts);
     const tx = new Transaction().add(ix);
     return await this.sendTransaction(tx, [user]);
   }
@@ -124,8 +151,8 @@ export class ContinuumClient {
   /**
    * Get order state
    */
-  async getOrderState(user: PublicKey, sequence: BN): Promise<OrderState | null> {
-    const [orderPDA] = getOrderPDA(user, sequence);
+  async getOrderState(poolId: PublicKey, sequence: BN): Promise<OrderState | null> {
+    const [orderPDA] = getOrderPDA(poolId, sequence);
     const accountInfo = await this.connection.getAccountInfo(orderPDA);
     
     if (!accountInfo) return null;
@@ -161,18 +188,23 @@ export class ContinuumClient {
    */
   async createPartiallySignedSubmitOrder(
     user: PublicKey,
-    params: SwapParams
+    params: SwapParams,
+    orderParams: SubmitOrderParams = {},
+    accounts: SubmitOrderAccounts = {}
   ): Promise<{ transaction: Transaction; sequence: BN }> {
-    const fifoState = await this.getFifoState();
-    if (!fifoState) throw new Error('FIFO state not initialized');
+    const poolFifo = await this.getPoolFifoState(params.poolId);
+    if (!poolFifo) throw new Error('Pool FIFO state not initialized');
 
     const ix = await createSubmitOrderInstruction(
       user,
       params.poolId,
+      params.inputMint,
       params.amountIn,
       params.minAmountOut,
       params.isBaseInput,
-      fifoState.currentSequence
+      poolFifo.currentSequence,
+      orderParams,
+      accounts
     );
 
     const tx = new Transaction().add(ix);
@@ -182,7 +214,7 @@ export class ContinuumClient {
 
     return {
       transaction: tx,
-      sequence: fifoState.currentSequence.add(new BN(1))
+      sequence: poolFifo.currentSequence.add(new BN(1))
     };
   }
 
@@ -3,9 +3,28 @@ import { PublicKey } from '@solana/web3.js';
 // Program IDs
 export const CONTINUUM_PROGRAM_ID = new PublicKey('A548C9LR926hnAWvYDjsXJddidhfzLf3bRb8dmYPgRKn');
 export const CP_SWAP_PROGRAM_ID = new PublicKey('GkenxCtvEabZrwFf15D3E6LjoZTywH2afNwiqDwthyDp');
+export const MEMO_PROGRAM_ID = new PublicKey('MemoSq4gqABAXKb96qnH8TyNcLFNEuQYBe8WN4Hc2Q');
 
 // Seeds
 export const FIFO_STATE_SEED = Buffer.from('fifo_state');
 export const POOL_REGISTRY_SEED = Buffer.from('pool_registry');
 export const CP_POOL_AUTHORITY_SEED = Buffer.from('cp_pool_authority');
-export const ORDER_SEED = Buffer.from('order');
\ No newline at end of file
+export const ORDER_SEED = Buffer.from('order');
+export const POOL_FIFO_SEED = Buffer.from('pool_fifo');
+export const USER_ORDERING_SEED = Buffer.from('user_ordering');
+export const SHARED_ESCROW_VAULT_SEED = Buffer.from('shared_escrow_vault');
+export const ORDER_VAULT_SEED = Buffer.from('order_vault');
+export const COMPLIANCE_RECORD_SEED = Buffer.from('compliance');
+export const POOL_RELAYERS_SEED = Buffer.from('pool_relayers');
+export const RELAYER_SEED = Buffer.from('relayer');
+export const RELAYER_QUOTA_SEED = Buffer.from('relayer_quota');
+export const RECEIPT_SEED = Buffer.from('receipt');
+export const FEE_LEDGER_SEED = Buffer.from('fee_ledger');
+export const FEE_VAULT_SEED = Buffer.from('fee_vault');
+export const FEE_SCHEDULE_SEED = Buffer.from('fee_schedule');
+export const USER_VOLUME_SEED = Buffer.from('user_volume');
+export const RELAYER_FEES_SEED = Buffer.from('relayer_fees');
+export const POST_FILL_AUTHORITY_SEED = Buffer.from('post_fill_authority');
+export const STAKE_PROGRAM_SEED = Buffer.from('stake_program');
+export const STAKE_DEPOSIT_SEED = Buffer.from('stake_deposit');
+export const ESCROW_SEED = Buffer.from('escrow');
\ No newline at end of file
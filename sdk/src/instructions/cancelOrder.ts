@@ -1,18 +1,40 @@
 import { TransactionInstruction, PublicKey, SYSVAR_CLOCK_PUBKEY } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID } from '@solana/spl-token';
 import { CONTINUUM_PROGRAM_ID } from '../constants';
-import { getOrderPDA } from '../utils/pda';
+import { getOrderPDA, getPoolFifoPDA, getUserOrderingPDA, getOrderVaultPDA, getPoolAuthorityPDA } from '../utils/pda';
+import { optionalAccount } from '../utils/accounts';
 import BN from 'bn.js';
 
+// Optional accounts of a cancellation, each required when the order has it
+export interface CancelOrderAccounts {
+  sharedEscrow?: PublicKey; // The order's shared escrow, whose reservation is released
+  coSigner?: PublicKey; // The order's co-signer, when it must sign the cancellation
+  escrowRefund?: PublicKey; // The order's escrow refund account, refunded from its vault
+}
+
 export function createCancelOrderInstruction(
   user: PublicKey,
-  sequence: BN
+  poolId: PublicKey,
+  sequence: BN,
+  accounts: CancelOrderAccounts = {}
 ): TransactionInstruction {
-  const [orderState] = getOrderPDA(user, sequence);
-  
+  const [orderState] = getOrderPDA(poolId, sequence);
+  const [poolFifo] = getPoolFifoPDA(poolId);
+  const [userOrdering] = getUserOrderingPDA(user);
+  const { sharedEscrow, coSigner, escrowRefund } = accounts;
+
   const keys = [
     { pubkey: orderState, isSigner: false, isWritable: true },
+    { pubkey: poolFifo, isSigner: false, isWritable: true },
+    { pubkey: userOrdering, isSigner: false, isWritable: true },
     { pubkey: user, isSigner: true, isWritable: true },
     { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
+    optionalAccount(sharedEscrow, { isWritable: true }),
+    optionalAccount(coSigner, { isSigner: true }),
+    optionalAccount(escrowRefund && getOrderVaultPDA(orderState)[0], { isWritable: true }),
+    optionalAccount(escrowRefund, { isWritable: true }),
+    optionalAccount(escrowRefund && getPoolAuthorityPDA(poolId)[0]),
+    optionalAccount(escrowRefund && TOKEN_PROGRAM_ID),
   ];
 
   // Discriminator for cancel_order: [95, 129, 237, 240, 8, 49, 223, 132]
@@ -23,4 +45,4 @@ export function createCancelOrderInstruction(
     programId: CONTINUUM_PROGRAM_ID,
     data,
   });
-}
\ No newline at end of file
+}
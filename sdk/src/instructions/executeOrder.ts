@@ -1,7 +1,30 @@
-import { TransactionInstruction, PublicKey, SYSVAR_CLOCK_PUBKEY } from '@solana/web3.js';
+import { TransactionInstruction, PublicKey, SystemProgram, SYSVAR_CLOCK_PUBKEY } from '@solana/web3.js';
 import { TOKEN_PROGRAM_ID } from '@solana/spl-token';
-import { CONTINUUM_PROGRAM_ID, CP_SWAP_PROGRAM_ID } from '../constants';
-import { getFifoStatePDA, getPoolRegistryPDA, getOrderPDA, getPoolAuthorityPDA } from '../utils/pda';
+import { CONTINUUM_PROGRAM_ID, CP_SWAP_PROGRAM_ID, MEMO_PROGRAM_ID } from '../constants';
+import {
+  getFifoStatePDA,
+  getPoolRegistryPDA,
+  getPoolFifoPDA,
+  getOrderPDA,
+  getPoolAuthorityPDA,
+  getUserOrderingPDA,
+  getPoolRelayersPDA,
+  getRelayerInfoPDA,
+  getRelayerQuotaPDA,
+  getReceiptPDA,
+  getFeeLedgerPDA,
+  getFeeVaultPDA,
+  getFeeSchedulePDA,
+  getUserVolumePDA,
+  getRelayerFeeBalancePDA,
+  getPostFillAuthorityPDA,
+  getApprovedStakeProgramPDA,
+  getStakeDepositPDA,
+  getOrderEscrowPDA
+} from '../utils/pda';
+import { encodeOption, encodeU64 } from '../utils/encoding';
+import { optionalAccount } from '../utils/accounts';
+import { PostFillAction } from '../types';
 import BN from 'bn.js';
 
 export interface ExecuteOrderParams {
@@ -10,30 +33,89 @@ export interface ExecuteOrderParams {
   sequence: BN;
   poolId: PublicKey;
   userSource: PublicKey;
+  // The post-fill vault of the output mint when the order has a post-fill action
   userDestination: PublicKey;
+  sourceMint: PublicKey; // Mint of `userSource`, selecting the fee vault
+  cpSwapProgram?: PublicKey; // The FIFO state's CP-Swap program, by default the cluster's
   cpSwapRemainingAccounts: PublicKey[]; // Pool state, vaults, etc.
+  withReceipt?: boolean; // Create an execution receipt for this fill
+  // The executor is a registered relayer; required unless execution is permissionless
+  withRelayerInfo?: boolean;
+  withMemo?: boolean; // Tag the fill with an SPL Memo
+  // Each of the order's own accounts below must be passed when it has it
+  callbackProgram?: PublicKey;
+  postFill?: { action: PostFillAction; outputMint: PublicKey };
+  parentOrder?: PublicKey;
+  oracle?: PublicKey; // Oracle of the order's trailing stop
+  sharedEscrow?: PublicKey;
+  delegatedInput?: boolean;
+  permit?: PublicKey;
+  escrowRefund?: PublicKey; // `userSource` is then the order's vault
+  maxFillAmount?: BN; // Fill at most this much of the remaining input
 }
 
 export function createExecuteOrderInstruction(
   params: ExecuteOrderParams
 ): TransactionInstruction {
-  const { executor, orderUser, sequence, poolId, userSource, userDestination, cpSwapRemainingAccounts } = params;
-  
+  const {
+    executor,
+    orderUser,
+    sequence,
+    poolId,
+    userSource,
+    userDestination,
+    sourceMint,
+    cpSwapProgram = CP_SWAP_PROGRAM_ID,
+    cpSwapRemainingAccounts,
+    postFill,
+    escrowRefund,
+    maxFillAmount
+  } = params;
+
   const [fifoState] = getFifoStatePDA();
-  const [orderState] = getOrderPDA(orderUser, sequence);
+  const [orderState] = getOrderPDA(poolId, sequence);
   const [poolRegistry] = getPoolRegistryPDA(poolId);
+  const [poolFifo] = getPoolFifoPDA(poolId);
   const [poolAuthority] = getPoolAuthorityPDA(poolId);
-  
+  const stakeProgram = postFill && 'stake' in postFill.action ? postFill.action.stake.program : undefined;
+
   const keys = [
     { pubkey: fifoState, isSigner: false, isWritable: false },
     { pubkey: orderState, isSigner: false, isWritable: true },
-    { pubkey: poolRegistry, isSigner: false, isWritable: false },
+    { pubkey: poolRegistry, isSigner: false, isWritable: true },
+    { pubkey: poolFifo, isSigner: false, isWritable: true },
     { pubkey: poolAuthority, isSigner: false, isWritable: false },
+    { pubkey: getUserOrderingPDA(orderUser)[0], isSigner: false, isWritable: true },
     { pubkey: executor, isSigner: true, isWritable: true },
+    { pubkey: getPoolRelayersPDA(poolId)[0], isSigner: false, isWritable: false },
+    optionalAccount(params.withRelayerInfo ? getRelayerInfoPDA(executor)[0] : undefined),
+    { pubkey: getRelayerQuotaPDA(executor)[0], isSigner: false, isWritable: true },
     { pubkey: userSource, isSigner: false, isWritable: true },
     { pubkey: userDestination, isSigner: false, isWritable: true },
-    { pubkey: CP_SWAP_PROGRAM_ID, isSigner: false, isWritable: false },
+    optionalAccount(params.withReceipt ? getReceiptPDA(poolId, sequence)[0] : undefined, { isWritable: true }),
+    { pubkey: getFeeLedgerPDA(poolId)[0], isSigner: false, isWritable: true },
+    { pubkey: getFeeVaultPDA(poolId, sourceMint)[0], isSigner: false, isWritable: true },
+    { pubkey: getFeeSchedulePDA()[0], isSigner: false, isWritable: false },
+    { pubkey: getUserVolumePDA(poolId, orderUser)[0], isSigner: false, isWritable: true },
+    { pubkey: getRelayerFeeBalancePDA(poolId, executor)[0], isSigner: false, isWritable: true },
+    { pubkey: cpSwapProgram, isSigner: false, isWritable: false },
+    optionalAccount(params.withMemo ? MEMO_PROGRAM_ID : undefined),
+    optionalAccount(params.callbackProgram),
+    optionalAccount(postFill && getPostFillAuthorityPDA()[0]),
+    optionalAccount(postFill?.outputMint, { isWritable: true }),
+    optionalAccount(stakeProgram),
+    optionalAccount(stakeProgram && postFill && getStakeDepositPDA(stakeProgram, postFill.outputMint)[0], { isWritable: true }),
+    optionalAccount(stakeProgram && getApprovedStakeProgramPDA(stakeProgram)[0]),
+    optionalAccount(params.parentOrder),
+    optionalAccount(params.oracle),
+    optionalAccount(params.sharedEscrow, { isWritable: true }),
+    optionalAccount(params.delegatedInput ? sourceMint : undefined),
+    optionalAccount(params.delegatedInput ? getOrderEscrowPDA(orderState)[0] : undefined, { isWritable: true }),
+    optionalAccount(params.permit, { isWritable: true }),
+    optionalAccount(escrowRefund, { isWritable: true }),
+    optionalAccount(escrowRefund && orderUser, { isWritable: true }),
     { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
     { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
     // Add CP-Swap specific accounts
     ...cpSwapRemainingAccounts.map(pubkey => ({
@@ -45,11 +127,12 @@ export function createExecuteOrderInstruction(
 
   // Discriminator for execute_order: [115, 61, 180, 24, 168, 32, 215, 20]
   const discriminator = Buffer.from([115, 61, 180, 24, 168, 32, 215, 20]);
-  
-  // Encode expected_sequence
+
+  // Encode expected_sequence and max_fill_amount
   const data = Buffer.concat([
     discriminator,
-    sequence.toArrayLike(Buffer, 'le', 8),
+    encodeU64(sequence),
+    encodeOption(maxFillAmount, encodeU64),
   ]);
 
   return new TransactionInstruction({
@@ -57,4 +140,4 @@ export function createExecuteOrderInstruction(
     programId: CONTINUUM_PROGRAM_ID,
     data,
   });
-}
\ No newline at end of file
+}
@@ -1,40 +1,90 @@
 import { TransactionInstruction, PublicKey, SystemProgram, SYSVAR_CLOCK_PUBKEY } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID } from '@solana/spl-token';
 import { CONTINUUM_PROGRAM_ID } from '../constants';
-import { getFifoStatePDA, getPoolRegistryPDA, getOrderPDA } from '../utils/pda';
+import {
+  getFifoStatePDA,
+  getPoolRegistryPDA,
+  getPoolFifoPDA,
+  getOrderPDA,
+  getUserOrderingPDA,
+  getSharedEscrowVaultPDA,
+  getOrderVaultPDA,
+  getPoolAuthorityPDA,
+  getComplianceRecordPDA
+} from '../utils/pda';
+import {
+  encodeBool,
+  encodeFixedBytes,
+  encodeI64,
+  encodeOption,
+  encodePubkey,
+  encodeString,
+  encodeU16,
+  encodeU64
+} from '../utils/encoding';
+import { optionalAccount } from '../utils/accounts';
+import { CoSignScope, PostFillAction, SubmitOrderAccounts, SubmitOrderParams } from '../types';
 import BN from 'bn.js';
 
+// `inputMint` is the pool's mint the order sells. `currentSequence` is the
+// pool FIFO's current sequence read before submission; the order account is
+// derived from the one after it
 export async function createSubmitOrderInstruction(
   user: PublicKey,
   poolId: PublicKey,
+  inputMint: PublicKey,
   amountIn: BN,
   minAmountOut: BN,
   isBaseInput: boolean,
-  currentSequence: BN
+  currentSequence: BN,
+  params: SubmitOrderParams = {},
+  accounts: SubmitOrderAccounts = {}
 ): Promise<TransactionInstruction> {
   const [fifoState] = getFifoStatePDA();
   const [poolRegistry] = getPoolRegistryPDA(poolId);
+  const [poolFifo] = getPoolFifoPDA(poolId);
   const nextSequence = currentSequence.add(new BN(1));
-  const [orderState] = getOrderPDA(user, nextSequence);
-  
+  const [orderState] = getOrderPDA(poolId, nextSequence);
+  const [userOrdering] = getUserOrderingPDA(user);
+  const { complianceProgram, oracle, sharedEscrow, permit, escrow } = accounts;
+  const coSigner = params.coSigner && params.coSigner.scope !== CoSignScope.Cancellation
+    ? params.coSigner.key
+    : undefined;
+
   const keys = [
-    { pubkey: fifoState, isSigner: false, isWritable: true },
+    { pubkey: fifoState, isSigner: false, isWritable: false },
     { pubkey: poolRegistry, isSigner: false, isWritable: false },
+    { pubkey: poolFifo, isSigner: false, isWritable: true },
     { pubkey: orderState, isSigner: false, isWritable: true },
+    { pubkey: userOrdering, isSigner: false, isWritable: true },
     { pubkey: user, isSigner: true, isWritable: true },
     { pubkey: poolId, isSigner: false, isWritable: false },
     { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
     { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
+    optionalAccount(complianceProgram),
+    optionalAccount(complianceProgram && getComplianceRecordPDA(complianceProgram, user)[0]),
+    optionalAccount(oracle),
+    optionalAccount(sharedEscrow, { isWritable: true }),
+    optionalAccount(sharedEscrow && getSharedEscrowVaultPDA(sharedEscrow)[0]),
+    optionalAccount(permit),
+    optionalAccount(coSigner, { isSigner: true }),
+    optionalAccount(escrow && getOrderVaultPDA(orderState)[0], { isWritable: true }),
+    { pubkey: inputMint, isSigner: false, isWritable: false },
+    optionalAccount(escrow?.source, { isWritable: true }),
+    optionalAccount(escrow && getPoolAuthorityPDA(poolId)[0]),
+    optionalAccount(escrow && TOKEN_PROGRAM_ID),
   ];
 
   // Discriminator for submit_order: [230, 150, 200, 53, 92, 208, 109, 108]
   const discriminator = Buffer.from([230, 150, 200, 53, 92, 208, 109, 108]);
-  
+
   // Encode arguments
   const data = Buffer.concat([
     discriminator,
-    amountIn.toArrayLike(Buffer, 'le', 8),
-    minAmountOut.toArrayLike(Buffer, 'le', 8),
-    Buffer.from([isBaseInput ? 1 : 0]),
+    encodeU64(amountIn),
+    encodeU64(minAmountOut),
+    encodeBool(isBaseInput),
+    encodeSubmitOrderParams(params),
   ]);
 
   return new TransactionInstruction({
@@ -42,4 +92,31 @@ export async function createSubmitOrderInstruction(
     programId: CONTINUUM_PROGRAM_ID,
     data,
   });
-}
\ No newline at end of file
+}
+
+// Borsh encoding of the program's `SubmitOrderParams`, in its field order
+export function encodeSubmitOrderParams(params: SubmitOrderParams): Buffer {
+  return Buffer.concat([
+    encodeOption(params.recipient, encodePubkey),
+    encodeOption(params.callbackProgram, encodePubkey),
+    encodeOption(params.destinationAccount, encodePubkey),
+    encodeOption(params.postFill, encodePostFillAction),
+    encodeOption(params.validFrom, encodeI64),
+    encodeOption(params.parentOrder, encodePubkey),
+    encodeOption(params.trailingStop, (stop) => Buffer.concat([Buffer.from([stop.direction]), encodeU16(stop.trailBps)])),
+    encodeBool(params.delegatedInput ?? false),
+    encodeOption(params.coSigner, (coSigner) => Buffer.concat([encodePubkey(coSigner.key), Buffer.from([coSigner.scope])])),
+    encodeOption(params.traceId, (traceId) => encodeFixedBytes(traceId, 16)),
+    encodeOption(params.ttl, encodeI64),
+    encodeOption(params.executionPolicy, (policy) => Buffer.from([policy])),
+    encodeOption(params.clientOrderId, (clientOrderId) => encodeFixedBytes(clientOrderId, 32)),
+    encodeOption(params.memo, encodeString),
+    encodeBool(params.fromWallet ?? false),
+  ]);
+}
+
+function encodePostFillAction(action: PostFillAction): Buffer {
+  return 'burn' in action
+    ? Buffer.from([0])
+    : Buffer.concat([Buffer.from([1]), encodePubkey(action.stake.program)]);
+}
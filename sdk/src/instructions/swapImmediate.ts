@@ -1,7 +1,16 @@
 import { PublicKey, TransactionInstruction } from '@solana/web3.js';
 import BN from 'bn.js';
 import { CONTINUUM_PROGRAM_ID } from '../constants';
-import { getFifoStatePDA } from '../utils';
+import {
+  getFifoStatePDA,
+  getPoolRegistryPDA,
+  getPoolFifoPDA,
+  getRelayerInfoPDA,
+  getComplianceRecordPDA,
+  optionalAccount,
+  encodeI64,
+  encodeOption
+} from '../utils';
 
 export interface SwapImmediateParams {
   user: PublicKey;
@@ -11,6 +20,11 @@ export interface SwapImmediateParams {
   minAmountOut: BN;
   isBaseInput: boolean;
   poolAuthorityBump: number;
+  /** Unix timestamp after which the swap must not execute */
+  deadline?: BN;
+  /** The pool's compliance program, required when it has one */
+  complianceProgram?: PublicKey;
+  /** The CP-Swap swap accounts, user first; the user must be a registered relayer */
   remainingAccounts: Array<{
     pubkey: PublicKey;
     isSigner: boolean;
@@ -29,14 +43,24 @@ export function createSwapImmediateInstruction(
     minAmountOut,
     isBaseInput,
     poolAuthorityBump,
+    deadline,
+    complianceProgram,
     remainingAccounts
   } = params;
 
   const [fifoState] = getFifoStatePDA();
+  const [poolRegistry] = getPoolRegistryPDA(poolId);
+  const [poolFifo] = getPoolFifoPDA(poolId);
+  const [relayerInfo] = getRelayerInfoPDA(user);
 
   const keys = [
-    { pubkey: fifoState, isSigner: false, isWritable: true },
+    { pubkey: fifoState, isSigner: false, isWritable: false },
+    { pubkey: poolRegistry, isSigner: false, isWritable: true },
+    { pubkey: poolFifo, isSigner: false, isWritable: true },
     { pubkey: cpSwapProgram, isSigner: false, isWritable: false },
+    optionalAccount(complianceProgram),
+    optionalAccount(complianceProgram && getComplianceRecordPDA(complianceProgram, user)[0]),
+    { pubkey: relayerInfo, isSigner: false, isWritable: true },
     ...remainingAccounts
   ];
 
@@ -49,7 +73,8 @@ export function createSwapImmediateInstruction(
     minAmountOut.toArrayLike(Buffer, 'le', 8),
     Buffer.from([isBaseInput ? 1 : 0]),
     poolId.toBuffer(),
-    Buffer.from([poolAuthorityBump])
+    Buffer.from([poolAuthorityBump]),
+    encodeOption(deadline, encodeI64)
   ]);
 
   return new TransactionInstruction({
@@ -6,6 +6,7 @@ export enum OrderStatus {
   Executed,
   Cancelled,
   Failed,
+  Expired,
 }
 
 export interface OrderState {
@@ -26,6 +27,62 @@ export interface FifoState {
   emergencyPause: boolean;
 }
 
+export interface PoolFifoState {
+  poolId: PublicKey;
+  currentSequence: BN;
+  head: BN;
+}
+
+export enum TrailDirection {
+  Sell,
+  Buy,
+}
+
+export enum CoSignScope {
+  Submission,
+  Cancellation,
+  Both,
+}
+
+export enum ExecutionPolicy {
+  GoodTillCancelled,
+  ImmediateOrCancel,
+  FillOrKill,
+}
+
+export type PostFillAction = { burn: {} } | { stake: { program: PublicKey } };
+
+// Optional behaviour of a submitted order; every field left out leaves it a
+// plain good-till-cancelled order delivering to the user, escrowed in its vault
+export interface SubmitOrderParams {
+  recipient?: PublicKey;
+  callbackProgram?: PublicKey;
+  destinationAccount?: PublicKey;
+  postFill?: PostFillAction;
+  validFrom?: BN;
+  parentOrder?: PublicKey;
+  trailingStop?: { direction: TrailDirection; trailBps: number };
+  delegatedInput?: boolean;
+  coSigner?: { key: PublicKey; scope: CoSignScope };
+  traceId?: Uint8Array; // 16 bytes
+  ttl?: BN; // Seconds after submission the order expires
+  executionPolicy?: ExecutionPolicy;
+  clientOrderId?: Uint8Array; // 32 bytes
+  memo?: string;
+  // Leave the input in the wallet, for the pool authority to spend by
+  // approval, instead of escrowing it; required without another source
+  fromWallet?: boolean;
+}
+
+// Optional accounts of a submission
+export interface SubmitOrderAccounts {
+  complianceProgram?: PublicKey; // The pool's, required when it has one
+  oracle?: PublicKey; // Price update account of a trailing stop
+  sharedEscrow?: PublicKey; // The user's shared escrow to spend from
+  permit?: PublicKey; // The user's permit to spend through
+  escrow?: { source: PublicKey }; // Deposit the input into the order's vault
+}
+
 export interface CpSwapPoolRegistry {
   poolId: PublicKey;
   token0: PublicKey;
@@ -37,6 +94,7 @@ export interface CpSwapPoolRegistry {
 
 export interface SwapParams {
   poolId: PublicKey;
+  inputMint: PublicKey; // The pool's mint the order sells
   amountIn: BN;
   minAmountOut: BN;
   isBaseInput: boolean;
@@ -0,0 +1,13 @@
+import { AccountMeta, PublicKey } from '@solana/web3.js';
+import { CONTINUUM_PROGRAM_ID } from '../constants';
+
+// An optional account of the program, which is passed as the program ID
+// when left out
+export function optionalAccount(
+  pubkey: PublicKey | undefined,
+  { isSigner = false, isWritable = false } = {}
+): AccountMeta {
+  return pubkey
+    ? { pubkey, isSigner, isWritable }
+    : { pubkey: CONTINUUM_PROGRAM_ID, isSigner: false, isWritable: false };
+}
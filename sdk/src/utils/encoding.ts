@@ -0,0 +1,46 @@
+import { PublicKey } from '@solana/web3.js';
+import BN from 'bn.js';
+
+// Borsh encoding of the instruction arguments the SDK builds by hand
+
+export function encodeU16(value: number): Buffer {
+  const buffer = Buffer.alloc(2);
+  buffer.writeUInt16LE(value);
+  return buffer;
+}
+
+export function encodeU64(value: BN): Buffer {
+  return value.toArrayLike(Buffer, 'le', 8);
+}
+
+export function encodeI64(value: BN): Buffer {
+  return value.toTwos(64).toArrayLike(Buffer, 'le', 8);
+}
+
+export function encodeBool(value: boolean): Buffer {
+  return Buffer.from([value ? 1 : 0]);
+}
+
+export function encodePubkey(value: PublicKey): Buffer {
+  return value.toBuffer();
+}
+
+export function encodeString(value: string): Buffer {
+  const bytes = Buffer.from(value, 'utf8');
+  const length = Buffer.alloc(4);
+  length.writeUInt32LE(bytes.length);
+  return Buffer.concat([length, bytes]);
+}
+
+export function encodeFixedBytes(value: Uint8Array, length: number): Buffer {
+  if (value.length !== length) {
+    throw new Error(`Expected ${length} bytes, got ${value.length}`);
+  }
+  return Buffer.from(value);
+}
+
+export function encodeOption<T>(value: T | null | undefined, encode: (value: T) => Buffer): Buffer {
+  return value === null || value === undefined
+    ? Buffer.from([0])
+    : Buffer.concat([Buffer.from([1]), encode(value)]);
+}
@@ -1 +1,3 @@
-export * from './pda';
\ No newline at end of file
+export * from './pda';
+export * from './encoding';
+export * from './accounts';
\ No newline at end of file
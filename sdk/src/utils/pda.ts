@@ -4,7 +4,25 @@ import {
   FIFO_STATE_SEED,
   POOL_REGISTRY_SEED,
   CP_POOL_AUTHORITY_SEED,
-  ORDER_SEED 
+  ORDER_SEED,
+  POOL_FIFO_SEED,
+  USER_ORDERING_SEED,
+  SHARED_ESCROW_VAULT_SEED,
+  ORDER_VAULT_SEED,
+  COMPLIANCE_RECORD_SEED,
+  POOL_RELAYERS_SEED,
+  RELAYER_SEED,
+  RELAYER_QUOTA_SEED,
+  RECEIPT_SEED,
+  FEE_LEDGER_SEED,
+  FEE_VAULT_SEED,
+  FEE_SCHEDULE_SEED,
+  USER_VOLUME_SEED,
+  RELAYER_FEES_SEED,
+  POST_FILL_AUTHORITY_SEED,
+  STAKE_PROGRAM_SEED,
+  STAKE_DEPOSIT_SEED,
+  ESCROW_SEED
 } from '../constants';
 import BN from 'bn.js';
 
@@ -29,9 +47,140 @@ export function getPoolAuthorityPDA(poolId: PublicKey): [PublicKey, number] {
   );
 }
 
-export function getOrderPDA(user: PublicKey, sequence: BN): [PublicKey, number] {
+export function getPoolFifoPDA(poolId: PublicKey): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
-    [ORDER_SEED, user.toBuffer(), sequence.toArrayLike(Buffer, 'le', 8)],
+    [POOL_FIFO_SEED, poolId.toBuffer()],
     CONTINUUM_PROGRAM_ID
   );
-}
\ No newline at end of file
+}
+
+// Orders are seeded with their pool and their sequence in its queue
+export function getOrderPDA(poolId: PublicKey, sequence: BN): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [ORDER_SEED, poolId.toBuffer(), sequence.toArrayLike(Buffer, 'le', 8)],
+    CONTINUUM_PROGRAM_ID
+  );
+}
+
+export function getUserOrderingPDA(user: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [USER_ORDERING_SEED, user.toBuffer()],
+    CONTINUUM_PROGRAM_ID
+  );
+}
+
+export function getSharedEscrowVaultPDA(sharedEscrow: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [SHARED_ESCROW_VAULT_SEED, sharedEscrow.toBuffer()],
+    CONTINUUM_PROGRAM_ID
+  );
+}
+
+export function getOrderVaultPDA(order: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [ORDER_VAULT_SEED, order.toBuffer()],
+    CONTINUUM_PROGRAM_ID
+  );
+}
+
+// A user's record under a pool's compliance program, which owns it
+export function getComplianceRecordPDA(complianceProgram: PublicKey, user: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [COMPLIANCE_RECORD_SEED, user.toBuffer()],
+    complianceProgram
+  );
+}
+
+export function getPoolRelayersPDA(poolId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [POOL_RELAYERS_SEED, poolId.toBuffer()],
+    CONTINUUM_PROGRAM_ID
+  );
+}
+
+export function getRelayerInfoPDA(relayer: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [RELAYER_SEED, relayer.toBuffer()],
+    CONTINUUM_PROGRAM_ID
+  );
+}
+
+export function getRelayerQuotaPDA(relayer: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [RELAYER_QUOTA_SEED, relayer.toBuffer()],
+    CONTINUUM_PROGRAM_ID
+  );
+}
+
+export function getReceiptPDA(poolId: PublicKey, sequence: BN): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [RECEIPT_SEED, poolId.toBuffer(), sequence.toArrayLike(Buffer, 'le', 8)],
+    CONTINUUM_PROGRAM_ID
+  );
+}
+
+export function getFeeLedgerPDA(poolId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [FEE_LEDGER_SEED, poolId.toBuffer()],
+    CONTINUUM_PROGRAM_ID
+  );
+}
+
+export function getFeeVaultPDA(poolId: PublicKey, mint: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [FEE_VAULT_SEED, poolId.toBuffer(), mint.toBuffer()],
+    CONTINUUM_PROGRAM_ID
+  );
+}
+
+export function getFeeSchedulePDA(): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [FEE_SCHEDULE_SEED],
+    CONTINUUM_PROGRAM_ID
+  );
+}
+
+export function getUserVolumePDA(poolId: PublicKey, user: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [USER_VOLUME_SEED, poolId.toBuffer(), user.toBuffer()],
+    CONTINUUM_PROGRAM_ID
+  );
+}
+
+export function getRelayerFeeBalancePDA(poolId: PublicKey, relayer: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [RELAYER_FEES_SEED, poolId.toBuffer(), relayer.toBuffer()],
+    CONTINUUM_PROGRAM_ID
+  );
+}
+
+export function getPostFillAuthorityPDA(): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [POST_FILL_AUTHORITY_SEED],
+    CONTINUUM_PROGRAM_ID
+  );
+}
+
+export function getApprovedStakeProgramPDA(program: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [STAKE_PROGRAM_SEED, program.toBuffer()],
+    CONTINUUM_PROGRAM_ID
+  );
+}
+
+// A staking program's deposit account for `mint`, which that program owns
+export function getStakeDepositPDA(program: PublicKey, mint: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [STAKE_DEPOSIT_SEED, mint.toBuffer()],
+    program
+  );
+}
+
+// Escrow PDA of an order, the delegate a user approves for an order with
+// `delegatedInput`
+export function getOrderEscrowPDA(order: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [ESCROW_SEED, order.toBuffer()],
+    CONTINUUM_PROGRAM_ID
+  );
+}
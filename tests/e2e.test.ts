@@ -21,6 +21,7 @@ import { expect } from 'chai';
 
 // Import our programs
 import { ContinuumCpSwap } from '../target/types/continuum_cp_swap';
+import { PLAIN_ORDER_PARAMS, getOrderPDA, getPoolFifoPDA, getFeeVaultPDA, getCpSwapExecuteAccounts } from './helpers';
 import { RaydiumCpSwap } from '../../raydium-cp-swap/target/types/raydium_cp_swap';
 
 describe('Continuum CP-Swap End-to-End Tests', () => {
@@ -344,6 +345,9 @@ describe('Continuum CP-Swap End-to-End Tests', () => {
   });
 
   it('Submit swap orders', async () => {
+    // Orders sell token0, so execution swaps it for token1
+    const [token0] = tokenA.toBuffer().compare(tokenB.toBuffer()) < 0 ? [tokenA, tokenB] : [tokenB, tokenA];
+
     // User 1 submits order
     const user1TokenA = await getAssociatedTokenAddress(tokenA, user1.publicKey);
     const user1TokenB = await getAssociatedTokenAddress(tokenB, user1.publicKey);
@@ -352,13 +356,15 @@ describe('Continuum CP-Swap End-to-End Tests', () => {
     const minAmountOut1 = new BN(19 * 10 ** 6); // Expecting ~20 tokens (2:1 ratio)
 
     await continuumProgram.methods
-      .submitOrder(amountIn1, minAmountOut1, true) // base_input = true
-      .accounts({
+      .submitOrder(amountIn1, minAmountOut1, true, PLAIN_ORDER_PARAMS) // base_input = true
+      .accountsPartial({
         fifoState: fifoStatePDA,
         poolRegistry: poolRegistryPDA,
-        orderState: getOrderPDA(user1.publicKey, new BN(1)),
+        poolFifo: getPoolFifoPDA(continuumProgram.programId, poolState),
+        orderState: getOrderPDA(continuumProgram.programId, poolState, new BN(1)),
         user: user1.publicKey,
         poolId: poolState,
+        inputMint: token0,
         systemProgram: SystemProgram.programId,
         clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
       })
@@ -375,13 +381,15 @@ describe('Continuum CP-Swap End-to-End Tests', () => {
     const minAmountOut2 = new BN(9 * 10 ** 6); // Expecting ~10 tokens
 
     await continuumProgram.methods
-      .submitOrder(amountIn2, minAmountOut2, true)
-      .accounts({
+      .submitOrder(amountIn2, minAmountOut2, true, PLAIN_ORDER_PARAMS)
+      .accountsPartial({
         fifoState: fifoStatePDA,
         poolRegistry: poolRegistryPDA,
-        orderState: getOrderPDA(user2.publicKey, new BN(2)),
+        poolFifo: getPoolFifoPDA(continuumProgram.programId, poolState),
+        orderState: getOrderPDA(continuumProgram.programId, poolState, new BN(2)),
         user: user2.publicKey,
         poolId: poolState,
+        inputMint: token0,
         systemProgram: SystemProgram.programId,
         clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
       })
@@ -390,17 +398,16 @@ describe('Continuum CP-Swap End-to-End Tests', () => {
 
     console.log('✅ User 2 submitted order with sequence 2');
 
-    // Verify FIFO state
-    const fifoState = await continuumProgram.account.fifoState.fetch(fifoStatePDA);
-    expect(fifoState.currentSequence.toNumber()).to.equal(2);
+    // Verify the pool's FIFO state
+    const poolFifo = await continuumProgram.account.poolFifoState.fetch(getPoolFifoPDA(continuumProgram.programId, poolState));
+    expect(poolFifo.currentSequence.toNumber()).to.equal(2);
   });
 
   it('Execute orders in FIFO sequence', async () => {
-    // Get pool PDAs for swap execution
+    // Sort the pool's mints for swap execution
     const [token0, token1] = tokenA.toBuffer().compare(tokenB.toBuffer()) < 0 
       ? [tokenA, tokenB] 
       : [tokenB, tokenA];
-    const pdas = getPoolPDAs(token0, token1);
 
     // Execute order 1
     const user1TokenA = await getAssociatedTokenAddress(tokenA, user1.publicKey);
@@ -414,25 +421,20 @@ describe('Continuum CP-Swap End-to-End Tests', () => {
     const user1DestBefore = await getAccount(provider.connection, user1Dest);
 
     // CP-Swap remaining accounts for swap
-    const cpSwapAccounts = [
-      { pubkey: poolState, isSigner: false, isWritable: true },
-      { pubkey: pdas.vault0, isSigner: false, isWritable: true },
-      { pubkey: pdas.vault1, isSigner: false, isWritable: true },
-      { pubkey: pdas.observationState, isSigner: false, isWritable: true },
-      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
-      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
-    ];
+    const cpSwapAccounts = getCpSwapExecuteAccounts(cpSwapProgram.programId, ammConfigPDA, poolState, token0, token1);
 
     await continuumProgram.methods
-      .executeOrder(new BN(1))
-      .accounts({
+      .executeOrder(new BN(1), null)
+      .accountsPartial({
         fifoState: fifoStatePDA,
-        orderState: getOrderPDA(user1.publicKey, new BN(1)),
+        orderState: getOrderPDA(continuumProgram.programId, poolState, new BN(1)),
         poolRegistry: poolRegistryPDA,
+        poolFifo: getPoolFifoPDA(continuumProgram.programId, poolState),
         poolAuthority: poolAuthorityPDA,
         executor: relayer.publicKey,
         userSource: user1Source,
         userDestination: user1Dest,
+        feeVault: getFeeVaultPDA(continuumProgram.programId, poolState, token0),
         cpSwapProgram: cpSwapProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
         clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
@@ -453,15 +455,17 @@ describe('Continuum CP-Swap End-to-End Tests', () => {
     // Try to execute order 2 before order 1 (should fail)
     try {
       await continuumProgram.methods
-        .executeOrder(new BN(2))
-        .accounts({
+        .executeOrder(new BN(2), null)
+        .accountsPartial({
           fifoState: fifoStatePDA,
-          orderState: getOrderPDA(user2.publicKey, new BN(2)),
+          orderState: getOrderPDA(continuumProgram.programId, poolState, new BN(2)),
           poolRegistry: poolRegistryPDA,
+          poolFifo: getPoolFifoPDA(continuumProgram.programId, poolState),
           poolAuthority: poolAuthorityPDA,
           executor: relayer.publicKey,
           userSource: user1Source, // dummy
           userDestination: user1Dest, // dummy
+          feeVault: getFeeVaultPDA(continuumProgram.programId, poolState, token0),
           cpSwapProgram: cpSwapProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
@@ -481,8 +485,9 @@ describe('Continuum CP-Swap End-to-End Tests', () => {
     // User 2 cancels their order
     await continuumProgram.methods
       .cancelOrder()
-      .accounts({
-        orderState: getOrderPDA(user2.publicKey, new BN(2)),
+      .accountsPartial({
+        orderState: getOrderPDA(continuumProgram.programId, poolState, new BN(2)),
+        poolFifo: getPoolFifoPDA(continuumProgram.programId, poolState),
         user: user2.publicKey,
         clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
       })
@@ -493,7 +498,7 @@ describe('Continuum CP-Swap End-to-End Tests', () => {
 
     // Verify order status
     const orderState = await continuumProgram.account.orderState.fetch(
-      getOrderPDA(user2.publicKey, new BN(2))
+      getOrderPDA(continuumProgram.programId, poolState, new BN(2))
     );
     expect(orderState.status).to.deep.equal({ cancelled: {} });
   });
@@ -538,18 +543,6 @@ describe('Continuum CP-Swap End-to-End Tests', () => {
     }
   });
 
-  // Helper function to get order PDA
-  function getOrderPDA(user: PublicKey, sequence: BN): PublicKey {
-    const [pda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from('order'),
-        user.toBuffer(),
-        sequence.toArrayLike(Buffer, 'le', 8)
-      ],
-      continuumProgram.programId
-    );
-    return pda;
-  }
 });
 
 describe('Continuum CP-Swap Stress Tests', () => {
@@ -571,19 +564,6 @@ describe('Continuum CP-Swap Stress Tests', () => {
     );
     await provider.connection.confirmTransaction(sig);
   };
-  
-  // Helper function to get order PDA
-  function getOrderPDA(user: PublicKey, sequence: BN): PublicKey {
-    const [pda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from('order'),
-        user.toBuffer(),
-        sequence.toArrayLike(Buffer, 'le', 8)
-      ],
-      continuumProgram.programId
-    );
-    return pda;
-  }
 
   before(async () => {
     // Get necessary PDAs from the previous test suite
@@ -628,13 +608,15 @@ describe('Continuum CP-Swap Stress Tests', () => {
     // Submit all orders
     for (const order of orders) {
       await continuumProgram.methods
-        .submitOrder(new BN(1 * 10 ** 6), new BN(0), true)
-        .accounts({
+        .submitOrder(new BN(1 * 10 ** 6), new BN(0), true, PLAIN_ORDER_PARAMS)
+        .accountsPartial({
           fifoState: fifoStatePDA,
           poolRegistry: poolRegistryPDA,
-          orderState: getOrderPDA(order.user.publicKey, order.sequence),
+          poolFifo: getPoolFifoPDA(continuumProgram.programId, poolState),
+          orderState: getOrderPDA(continuumProgram.programId, poolState, order.sequence),
           user: order.user.publicKey,
           poolId: poolState,
+          inputMint: tokenA,
           systemProgram: SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
@@ -644,8 +626,8 @@ describe('Continuum CP-Swap Stress Tests', () => {
 
     console.log('✅ Submitted 10 concurrent orders');
 
-    // Verify FIFO state
-    const fifoState = await continuumProgram.account.fifoState.fetch(fifoStatePDA);
-    expect(fifoState.currentSequence.toNumber()).to.equal(12);
+    // Verify the pool's FIFO state
+    const poolFifo = await continuumProgram.account.poolFifoState.fetch(getPoolFifoPDA(continuumProgram.programId, poolState));
+    expect(poolFifo.currentSequence.toNumber()).to.equal(12);
   });
 });
\ No newline at end of file
@@ -0,0 +1,87 @@
+import { AccountMeta, PublicKey } from '@solana/web3.js';
+import { BN } from '@coral-xyz/anchor';
+import { TOKEN_PROGRAM_ID } from '@solana/spl-token';
+
+// `SubmitOrderParams` of a plain good-till-cancelled order delivering to the
+// user from their wallet, which approves the pool authority
+export const PLAIN_ORDER_PARAMS = {
+  recipient: null,
+  callbackProgram: null,
+  destinationAccount: null,
+  postFill: null,
+  validFrom: null,
+  parentOrder: null,
+  trailingStop: null,
+  delegatedInput: false,
+  coSigner: null,
+  traceId: null,
+  ttl: null,
+  executionPolicy: null,
+  clientOrderId: null,
+  memo: null,
+  fromWallet: true,
+};
+
+// Orders are seeded with their pool and their sequence in its queue
+export function getOrderPDA(programId: PublicKey, poolId: PublicKey, sequence: BN): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('order'), poolId.toBuffer(), sequence.toArrayLike(Buffer, 'le', 8)],
+    programId
+  );
+  return pda;
+}
+
+export function getPoolFifoPDA(programId: PublicKey, poolId: PublicKey): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('pool_fifo'), poolId.toBuffer()],
+    programId
+  );
+  return pda;
+}
+
+export function getFeeVaultPDA(programId: PublicKey, poolId: PublicKey, mint: PublicKey): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('fee_vault'), poolId.toBuffer(), mint.toBuffer()],
+    programId
+  );
+  return pda;
+}
+
+// The pool's CP-Swap accounts, which execute_order takes as remaining
+// accounts in this order
+export function getCpSwapExecuteAccounts(
+  cpSwapProgramId: PublicKey,
+  ammConfig: PublicKey,
+  poolState: PublicKey,
+  inputMint: PublicKey,
+  outputMint: PublicKey
+): AccountMeta[] {
+  const [authority] = PublicKey.findProgramAddressSync(
+    [Buffer.from('vault_and_lp_mint_auth_seed')],
+    cpSwapProgramId
+  );
+  const [inputVault] = PublicKey.findProgramAddressSync(
+    [Buffer.from('pool_vault'), poolState.toBuffer(), inputMint.toBuffer()],
+    cpSwapProgramId
+  );
+  const [outputVault] = PublicKey.findProgramAddressSync(
+    [Buffer.from('pool_vault'), poolState.toBuffer(), outputMint.toBuffer()],
+    cpSwapProgramId
+  );
+  const [observationState] = PublicKey.findProgramAddressSync(
+    [Buffer.from('observation'), poolState.toBuffer()],
+    cpSwapProgramId
+  );
+  return [
+    { pubkey: authority, isSigner: false, isWritable: false },
+    { pubkey: ammConfig, isSigner: false, isWritable: false },
+    { pubkey: poolState, isSigner: false, isWritable: true },
+    { pubkey: inputVault, isSigner: false, isWritable: true },
+    { pubkey: outputVault, isSigner: false, isWritable: true },
+    { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    { pubkey: inputMint, isSigner: false, isWritable: false },
+    { pubkey: outputMint, isSigner: false, isWritable: false },
+    { pubkey: observationState, isSigner: false, isWritable: true },
+  ];
+}
@@ -17,6 +17,7 @@ import { expect } from 'chai';
 
 // Import programs
 import { ContinuumCpSwap } from '../target/types/continuum_cp_swap';
+import { PLAIN_ORDER_PARAMS, getOrderPDA, getPoolFifoPDA } from './helpers';
 import { RaydiumCpSwap } from '../../raydium-cp-swap/target/types/raydium_cp_swap';
 
 describe('Minimal Working Test', () => {
@@ -69,16 +70,9 @@ describe('Minimal Working Test', () => {
       continuumProgram.programId
     );
 
-    // Get order PDA - use sequence 1 for test
+    // Get order PDA - the first in the pool's queue
     const sequence = new BN(1);
-    const [orderPDA] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from('order'),
-        user1.publicKey.toBuffer(),
-        sequence.toArrayLike(Buffer, 'le', 8)
-      ],
-      continuumProgram.programId
-    );
+    const orderPDA = getOrderPDA(continuumProgram.programId, dummyPoolId, sequence);
 
     console.log('Order PDA:', orderPDA.toString());
     console.log('User:', user1.publicKey.toString());
@@ -88,14 +82,17 @@ describe('Minimal Working Test', () => {
         .submitOrder(
           new BN(10 * 10 ** 6), // amount in
           new BN(19 * 10 ** 6), // min amount out
-          true // base_input
+          true, // base_input
+          PLAIN_ORDER_PARAMS
         )
-        .accounts({
+        .accountsPartial({
           fifoState: fifoStatePDA,
           poolRegistry: poolRegistryPDA,
+          poolFifo: getPoolFifoPDA(continuumProgram.programId, dummyPoolId),
           orderState: orderPDA,
           user: user1.publicKey,
           poolId: dummyPoolId,
+          inputMint: Keypair.generate().publicKey,
           systemProgram: SystemProgram.programId,
           clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
         })
@@ -18,6 +18,7 @@ import {
 } from '@solana/spl-token';
 import { expect } from 'chai';
 import { ContinuumCpSwap } from '../target/types/continuum_cp_swap';
+import { PLAIN_ORDER_PARAMS, getOrderPDA, getPoolFifoPDA, getFeeVaultPDA, getCpSwapExecuteAccounts } from './helpers';
 import { RaydiumCpSwap } from '../../raydium-cp-swap/target/types/raydium_cp_swap';
 
 describe('Working Real Swap Test', () => {
@@ -327,25 +328,21 @@ describe('Working Real Swap Test', () => {
     const amountIn = new BN(1000 * 10 ** 6); // 1000 token0
     const minAmountOut = new BN(400 * 10 ** 9); // Min 400 token1
     
-    const fifoState = await continuumProgram.account.fifoState.fetch(fifoStatePDA);
-    const sequence = fifoState.currentSequence;
-    
-    const [orderPDA] = PublicKey.findProgramAddressSync(
-      [Buffer.from('order'), user.publicKey.toBuffer(), sequence.toArrayLike(Buffer, 'le', 8)],
-      continuumProgram.programId
-    );
+    const poolFifoPDA = getPoolFifoPDA(continuumProgram.programId, poolState);
+    const poolFifo = await continuumProgram.account.poolFifoState.fetch(poolFifoPDA);
+    const sequence = poolFifo.currentSequence.add(new BN(1));
+    const orderPDA = getOrderPDA(continuumProgram.programId, poolState, sequence);
     
     await continuumProgram.methods
-      .submitOrder(amountIn, minAmountOut, true)
-      .accounts({
+      .submitOrder(amountIn, minAmountOut, true, PLAIN_ORDER_PARAMS)
+      .accountsPartial({
         fifoState: fifoStatePDA,
         poolRegistry: poolRegistryPDA,
+        poolFifo: poolFifoPDA,
         orderState: orderPDA,
         user: user.publicKey,
         poolId: poolState,
-        userSourceToken: userToken0,
-        userDestinationToken: userToken1,
-        tokenProgram: TOKEN_PROGRAM_ID,
+        inputMint: token0,
         systemProgram: SystemProgram.programId,
         clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
       })
@@ -355,40 +352,20 @@ describe('Working Real Swap Test', () => {
     console.log('✅ Order submitted, sequence:', sequence.toString());
     
     // Execute order
-    const [vault0] = PublicKey.findProgramAddressSync(
-      [Buffer.from('pool_vault'), poolState.toBuffer(), token0.toBuffer()],
-      cpSwapProgram.programId
-    );
-
-    const [vault1] = PublicKey.findProgramAddressSync(
-      [Buffer.from('pool_vault'), poolState.toBuffer(), token1.toBuffer()],
-      cpSwapProgram.programId
-    );
-
-    const [observationState] = PublicKey.findProgramAddressSync(
-      [Buffer.from('observation'), poolState.toBuffer()],
-      cpSwapProgram.programId
-    );
-    
-    const cpSwapAccounts = [
-      { pubkey: poolState, isSigner: false, isWritable: true },
-      { pubkey: vault0, isSigner: false, isWritable: true },
-      { pubkey: vault1, isSigner: false, isWritable: true },
-      { pubkey: observationState, isSigner: false, isWritable: true },
-      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
-      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
-    ];
+    const cpSwapAccounts = getCpSwapExecuteAccounts(cpSwapProgram.programId, ammConfigPDA, poolState, token0, token1);
     
     await continuumProgram.methods
-      .executeOrder(sequence)
-      .accounts({
+      .executeOrder(sequence, null)
+      .accountsPartial({
         fifoState: fifoStatePDA,
         orderState: orderPDA,
         poolRegistry: poolRegistryPDA,
+        poolFifo: poolFifoPDA,
         poolAuthority: poolAuthorityPDA,
         executor: relayer.publicKey,
         userSource: userToken0,
         userDestination: userToken1,
+        feeVault: getFeeVaultPDA(continuumProgram.programId, poolState, token0),
         cpSwapProgram: cpSwapProgram.programId,
         tokenProgram: TOKEN_PROGRAM_ID,
         clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,